@@ -0,0 +1,160 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{Assertion, AssertionError};
+use crate::TraceInfo;
+use math::FieldElement;
+use utils::collections::Vec;
+
+// TRACE STEP
+// ================================================================================================
+
+/// A step of an execution trace which may be expressed relative to the end of the trace.
+///
+/// Steps relative to the end of the trace are resolved against the original (unpadded) length of
+/// the trace. Thus, [TraceStep::FromEnd(0)](TraceStep::FromEnd) always refers to the last step of
+/// the actual computation, even if the execution trace was padded to a power of two.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum TraceStep {
+    /// A step at the specified index counting from the first step of the trace.
+    At(usize),
+    /// A step at the specified index counting backwards from the last step of the computation;
+    /// index 0 refers to the last step.
+    FromEnd(usize),
+}
+
+impl TraceStep {
+    /// Returns the first step of an execution trace.
+    pub fn first() -> Self {
+        TraceStep::At(0)
+    }
+
+    /// Returns the last step of the computation described by an execution trace.
+    pub fn last() -> Self {
+        TraceStep::FromEnd(0)
+    }
+
+    /// Returns the step `k` steps before the last step of the computation described by an
+    /// execution trace.
+    pub fn from_end(k: usize) -> Self {
+        TraceStep::FromEnd(k)
+    }
+
+    /// Returns the index of this step in the execution trace described by the `trace_info`.
+    ///
+    /// # Errors
+    /// Returns an error if this step is relative to the end of the trace and the original length
+    /// of the trace is too short to contain it.
+    pub fn resolve(&self, trace_info: &TraceInfo) -> Result<usize, AssertionError> {
+        match *self {
+            TraceStep::At(step) => Ok(step),
+            TraceStep::FromEnd(offset) => {
+                let original_length = trace_info.original_length();
+                if offset >= original_length {
+                    return Err(AssertionError::StepFromEndOutOfBounds(
+                        offset,
+                        original_length,
+                    ));
+                }
+                Ok(original_length - 1 - offset)
+            }
+        }
+    }
+}
+
+impl From<usize> for TraceStep {
+    fn from(step: usize) -> Self {
+        TraceStep::At(step)
+    }
+}
+
+// ASSERTIONS BUILDER
+// ================================================================================================
+
+/// A builder of a list of assertions against an execution trace.
+///
+/// Single-value assertions can be placed against [TraceStep]s which are resolved against the
+/// trace info only when the assertions are built. This removes the need to compute the index of
+/// the last step manually, for example:
+///
+/// ```ignore
+/// Assertions::new()
+///     .single(0, TraceStep::first(), seed)
+///     .single(0, TraceStep::last(), result)
+///     .build(self.trace_info())
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Assertions<E: FieldElement> {
+    single: Vec<(usize, TraceStep, E)>,
+    resolved: Vec<Assertion<E>>,
+}
+
+impl<E: FieldElement> Assertions<E> {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    /// Returns a new empty assertions builder.
+    pub fn new() -> Self {
+        Self {
+            single: Vec::new(),
+            resolved: Vec::new(),
+        }
+    }
+
+    // BUILDER METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Adds an assertion that the value in the specified `column` at the specified `step` is
+    /// equal to the provided `value`.
+    pub fn single<S: Into<TraceStep>>(mut self, column: usize, step: S, value: E) -> Self {
+        self.single.push((column, step.into(), value));
+        self
+    }
+
+    /// Adds the provided assertion to the list of assertions as is.
+    pub fn with_assertion(mut self, assertion: Assertion<E>) -> Self {
+        self.resolved.push(assertion);
+        self
+    }
+
+    /// Resolves all assertions against the execution trace described by the `trace_info` and
+    /// returns the resulting list of assertions.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * A step relative to the end of the trace could not be resolved against the original
+    ///   length of the trace.
+    /// * Any of the assertions is placed against a column which does not exist in the trace, or
+    ///   is not valid for the length of the trace.
+    /// * Any two assertions are placed against the same cell of the trace.
+    pub fn build(self, trace_info: &TraceInfo) -> Result<Vec<Assertion<E>>, AssertionError> {
+        let mut result = Vec::with_capacity(self.single.len() + self.resolved.len());
+        for (column, step, value) in self.single {
+            result.push(Assertion::single(column, step.resolve(trace_info)?, value));
+        }
+        result.extend(self.resolved);
+
+        for (i, assertion) in result.iter().enumerate() {
+            assertion.validate_trace_width(trace_info.width())?;
+            assertion.validate_trace_length(trace_info.length())?;
+            if result[..i]
+                .iter()
+                .any(|other| other.overlaps_with(assertion))
+            {
+                return Err(AssertionError::Overlapping(
+                    assertion.column(),
+                    assertion.first_step(),
+                ));
+            }
+        }
+
+        Ok(result)
+    }
+}
+
+impl<E: FieldElement> Default for Assertions<E> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
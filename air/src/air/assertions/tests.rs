@@ -3,7 +3,8 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use super::{Assertion, AssertionError};
+use super::{Assertion, AssertionError, Assertions, TraceStep};
+use crate::TraceInfo;
 use math::{fields::f128::BaseElement, FieldElement};
 use rand_utils::{rand_value, rand_vector};
 use utils::collections::Vec;
@@ -363,3 +364,73 @@ fn assertion_overlap() {
     assert!(!a.overlaps_with(&b));
     assert!(!b.overlaps_with(&a));
 }
+
+// ASSERTIONS BUILDER
+// ================================================================================================
+
+#[test]
+fn assertions_builder() {
+    let trace_info = TraceInfo::new(4, 16);
+    let assertions = Assertions::new()
+        .single(0, TraceStep::first(), BaseElement::ONE)
+        .single(0, TraceStep::last(), BaseElement::ZERO)
+        .single(1, TraceStep::from_end(2), BaseElement::ONE)
+        .single(2, 5, BaseElement::ONE)
+        .with_assertion(Assertion::periodic(3, 0, 4, BaseElement::ZERO))
+        .build(&trace_info)
+        .unwrap();
+
+    let expected = vec![
+        Assertion::single(0, 0, BaseElement::ONE),
+        Assertion::single(0, 15, BaseElement::ZERO),
+        Assertion::single(1, 13, BaseElement::ONE),
+        Assertion::single(2, 5, BaseElement::ONE),
+        Assertion::periodic(3, 0, 4, BaseElement::ZERO),
+    ];
+    assert_eq!(expected, assertions);
+}
+
+#[test]
+fn assertions_builder_padded_trace() {
+    // steps from the end are resolved against the original length of the trace
+    let trace_info = TraceInfo::new(2, 16).set_original_length(10);
+    let assertions = Assertions::new()
+        .single(0, TraceStep::last(), BaseElement::ONE)
+        .single(1, TraceStep::from_end(9), BaseElement::ONE)
+        .build(&trace_info)
+        .unwrap();
+    assert_eq!(9, assertions[0].first_step());
+    assert_eq!(0, assertions[1].first_step());
+
+    assert_eq!(Ok(9), TraceStep::FromEnd(0).resolve(&trace_info));
+    assert_eq!(
+        Err(AssertionError::StepFromEndOutOfBounds(10, 10)),
+        TraceStep::FromEnd(10).resolve(&trace_info)
+    );
+}
+
+#[test]
+fn assertions_builder_errors() {
+    let trace_info = TraceInfo::new(2, 16);
+
+    let result = Assertions::new()
+        .single(0, TraceStep::from_end(16), BaseElement::ONE)
+        .build(&trace_info);
+    assert_eq!(Err(AssertionError::StepFromEndOutOfBounds(16, 16)), result);
+
+    let result = Assertions::new()
+        .single(2, TraceStep::first(), BaseElement::ONE)
+        .build(&trace_info);
+    assert_eq!(Err(AssertionError::TraceWidthTooShort(2, 2)), result);
+
+    let result = Assertions::new()
+        .single(0, 16, BaseElement::ONE)
+        .build(&trace_info);
+    assert_eq!(Err(AssertionError::TraceLengthTooShort(32, 16)), result);
+
+    let result = Assertions::new()
+        .single(1, TraceStep::last(), BaseElement::ONE)
+        .with_assertion(Assertion::periodic(1, 3, 4, BaseElement::ZERO))
+        .build(&trace_info);
+    assert_eq!(Err(AssertionError::Overlapping(1, 3)), result);
+}
@@ -129,6 +129,19 @@ where
     /// Thus, the merged evaluations represent a polynomial of degree $D$, as the degree of the
     /// numerator is $D + deg(z(x))$, and the division by $z(x)$ reduces the degree by $deg(z(x))$.
     pub fn evaluate_at(&self, state: &[E], x: E, xp: E) -> E {
+        let numerator = self.merge_evaluations_at(state, x, xp);
+        let denominator = self.divisor.evaluate_at(x);
+
+        numerator / denominator
+    }
+
+    /// Evaluates all constraints in this group at the specified point `x` and merges them into
+    /// a single value in the same way as [BoundaryConstraintGroup::evaluate_at()], but does not
+    /// divide the result by the divisor of this group.
+    ///
+    /// This is useful when divisors of several groups are evaluated together (see
+    /// [ConstraintDivisor::evaluate_inverses_at()]).
+    pub fn merge_evaluations_at(&self, state: &[E], x: E, xp: E) -> E {
         debug_assert_eq!(
             x.exp(self.degree_adjustment.into()),
             xp,
@@ -140,9 +153,6 @@ where
             let evaluation = constraint.evaluate_at(x, trace_value);
             numerator += evaluation * (constraint.cc().0 + constraint.cc().1 * xp);
         }
-
-        let denominator = self.divisor.evaluate_at(x);
-
-        numerator / denominator
+        numerator
     }
 }
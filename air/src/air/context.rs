@@ -3,7 +3,7 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use crate::{air::TransitionConstraintDegree, ProofOptions, TraceInfo};
+use crate::{air::TransitionConstraintDegree, ProofOptions, QuerySampling, TraceInfo};
 use math::StarkField;
 use utils::collections::Vec;
 
@@ -41,6 +41,8 @@ impl<B: StarkField> AirContext<B> {
     /// * `num_assertions` is zero.
     /// * Blowup factor specified by the provided `options` is too small to accommodate degrees
     ///   of the specified transition constraints.
+    /// * Domain offset specified by the provided `options` is zero or is an element of the
+    ///   low-degree extension domain subgroup.
     /// * `trace_info` describes a multi-segment execution trace.
     pub fn new(
         trace_info: TraceInfo,
@@ -84,6 +86,8 @@ impl<B: StarkField> AirContext<B> {
     ///   - `num_aux_assertions` is greater than zero.
     /// * Blowup factor specified by the provided `options` is too small to accommodate degrees
     ///   of the specified transition constraints.
+    /// * Domain offset specified by the provided `options` is zero or is an element of the
+    ///   low-degree extension domain subgroup.
     pub fn new_multi_segment(
         trace_info: TraceInfo,
         main_transition_constraint_degrees: Vec<TransitionConstraintDegree>,
@@ -143,8 +147,43 @@ impl<B: StarkField> AirContext<B> {
             options.blowup_factor()
         );
 
+        // compute the size of the LDE domain using checked arithmetic so that requests for very
+        // large domains are rejected (rather than silently overflowing) on 32-bit platforms
         let trace_length = trace_info.length();
-        let lde_domain_size = trace_length * options.blowup_factor();
+        let lde_domain_size = get_lde_domain_size(trace_length, options.blowup_factor())
+            .unwrap_or_else(|| {
+                panic!(
+                    "LDE domain for trace length {trace_length} and blowup factor {} does not fit into {} bits",
+                    options.blowup_factor(),
+                    usize::BITS
+                )
+            });
+        assert!(
+            lde_domain_size.ilog2() <= B::TWO_ADICITY,
+            "LDE domain size cannot exceed 2^{}, but was 2^{}",
+            B::TWO_ADICITY,
+            lde_domain_size.ilog2()
+        );
+
+        // the LDE domain is a coset of the subgroup of size lde_domain_size; for it to be
+        // disjoint from the subgroup (and thus from the trace domain), the offset must not be
+        // in the subgroup
+        let domain_offset = options.domain_offset::<B>();
+        assert!(
+            domain_offset != B::ZERO && domain_offset.exp((lde_domain_size as u64).into()) != B::ONE,
+            "domain offset {domain_offset} must not be zero or in the LDE domain subgroup of size {lde_domain_size}"
+        );
+
+        // for stratified query sampling, every segment of the LDE domain must be large enough to
+        // accommodate all queries assigned to it
+        if let QuerySampling::Stratified(num_segments) = options.query_sampling() {
+            let segment_size = lde_domain_size / num_segments;
+            let max_segment_queries = (options.num_queries() + num_segments - 1) / num_segments;
+            assert!(
+                max_segment_queries < segment_size,
+                "LDE domain segments of size {segment_size} are too small for {max_segment_queries} queries per segment"
+            );
+        }
 
         AirContext {
             options,
@@ -227,6 +266,16 @@ impl<B: StarkField> AirContext<B> {
         self.num_main_assertions + self.num_aux_assertions
     }
 
+    /// Returns the number of assertions placed against the main trace segment.
+    pub fn num_main_assertions(&self) -> usize {
+        self.num_main_assertions
+    }
+
+    /// Returns the number of assertions placed against all auxiliary trace segments.
+    pub fn num_aux_assertions(&self) -> usize {
+        self.num_aux_assertions
+    }
+
     /// Returns the number of rows at the end of an execution trace to which transition constraints
     /// do not apply.
     ///
@@ -237,6 +286,15 @@ impl<B: StarkField> AirContext<B> {
         self.num_transition_exemptions
     }
 
+    /// Returns the number of rows at the start of an execution trace which are "real" (i.e., to
+    /// which transition constraints apply, either as the current or as the next row).
+    ///
+    /// The remaining rows are virtual: they pad the trace to a power of two and can contain
+    /// arbitrary values. By default, this is equal to the length of the execution trace.
+    pub fn real_trace_length(&self) -> usize {
+        self.trace_len() - self.num_transition_exemptions + 1
+    }
+
     // DATA MUTATORS
     // --------------------------------------------------------------------------------------------
 
@@ -279,4 +337,72 @@ impl<B: StarkField> AirContext<B> {
         self.num_transition_exemptions = n;
         self
     }
+
+    /// Declares that only the first `n` rows of the execution trace are real, and the remaining
+    /// rows are virtual.
+    ///
+    /// Transition constraints are automatically disabled for the virtual tail of the trace by
+    /// excluding it from the transition constraint divisor; that is, transition constraints are
+    /// enforced only between pairs of consecutive real rows, and virtual rows can contain
+    /// arbitrary values (e.g., zeros). This allows describing computations whose natural length
+    /// is not a power of two without modifying their constraints. Usually, `n` is set to
+    /// [TraceInfo::original_length()]; since this value is provided by the prover, assertions
+    /// against the last real row (or public inputs) should be used to bind it to the computation.
+    ///
+    /// Excluding rows from the divisor lowers its degree; to keep the degrees of the resulting
+    /// constraint quotients within the constraint evaluation domain, the constraint evaluation
+    /// blowup factor is increased as needed.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * `n` is greater than the length of the execution trace or smaller than half of it.
+    /// * The blowup factor specified by the proof options of this context is too small to
+    ///   accommodate the constraint evaluation blowup factor required for the virtual rows.
+    pub fn set_real_trace_length(mut self, n: usize) -> Self {
+        let trace_length = self.trace_len();
+        assert!(
+            n >= trace_length / 2 && n <= trace_length,
+            "real trace length must be between {} and {}, but was {}",
+            trace_length / 2,
+            trace_length,
+            n
+        );
+        let num_exemptions = trace_length - n + 1;
+
+        // the degree of the transition divisor is trace_length - num_exemptions; thus, for the
+        // quotient of a constraint to fit into the constraint evaluation domain, the domain size
+        // must exceed evaluation_degree - trace_length + num_exemptions
+        for degree in self
+            .main_transition_constraint_degrees
+            .iter()
+            .chain(self.aux_transition_constraint_degrees.iter())
+        {
+            let quotient_degree = (degree.get_evaluation_degree(trace_length) + num_exemptions)
+                .saturating_sub(trace_length);
+            let ce_blowup_factor = (quotient_degree / trace_length + 1).next_power_of_two();
+            self.ce_blowup_factor = self.ce_blowup_factor.max(ce_blowup_factor);
+        }
+        assert!(
+            self.options.blowup_factor() >= self.ce_blowup_factor,
+            "blowup factor too small for {} virtual rows; expected at least {}, but was {}",
+            trace_length - n,
+            self.ce_blowup_factor,
+            self.options.blowup_factor()
+        );
+
+        self.set_num_transition_exemptions(num_exemptions)
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns the size of the LDE domain for an execution trace of the specified length extended by
+/// the specified blowup factor.
+///
+/// The size is computed using checked 64-bit arithmetic; `None` is returned if the size does not
+/// fit into `usize` on the target platform.
+pub(crate) fn get_lde_domain_size(trace_length: usize, blowup_factor: usize) -> Option<usize> {
+    let domain_size = (trace_length as u64).checked_mul(blowup_factor as u64)?;
+    usize::try_from(domain_size).ok()
 }
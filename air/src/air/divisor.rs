@@ -5,7 +5,7 @@
 
 use crate::air::Assertion;
 use core::fmt::{Display, Formatter};
-use math::{FieldElement, StarkField};
+use math::{batch_inversion, polynom::VanishingPoly, FieldElement, StarkField};
 use utils::collections::Vec;
 
 // CONSTRAINT DIVISOR
@@ -40,6 +40,11 @@ impl<B: StarkField> ConstraintDivisor<B> {
         }
     }
 
+    /// Returns a new divisor for the provided vanishing polynomial.
+    fn from_vanishing_poly(poly: VanishingPoly<B>) -> Self {
+        Self::new(vec![poly.numerator()], poly.exclusions().to_vec())
+    }
+
     /// Builds a divisor for transition constraints.
     ///
     /// For transition constraints, the divisor polynomial $z(x)$ is always the same:
@@ -60,8 +65,8 @@ impl<B: StarkField> ConstraintDivisor<B> {
         );
         let exemptions = (trace_length - num_exemptions..trace_length)
             .map(|step| get_trace_domain_value_at::<B>(trace_length, step))
-            .collect();
-        Self::new(vec![(trace_length, B::ONE)], exemptions)
+            .collect::<Vec<_>>();
+        Self::from_vanishing_poly(VanishingPoly::subgroup(trace_length).puncture(&exemptions))
     }
 
     /// Builds a divisor for a boundary constraint described by the assertion.
@@ -91,14 +96,11 @@ impl<B: StarkField> ConstraintDivisor<B> {
     where
         E: FieldElement<BaseField = B>,
     {
+        // asserted steps form a coset of the subgroup of size num_steps in the trace domain,
+        // defined by the first asserted step
         let num_steps = assertion.get_num_steps(trace_length);
-        if assertion.first_step == 0 {
-            Self::new(vec![(num_steps, B::ONE)], vec![])
-        } else {
-            let trace_offset = num_steps * assertion.first_step;
-            let offset = get_trace_domain_value_at::<B>(trace_length, trace_offset);
-            Self::new(vec![(num_steps, offset)], vec![])
-        }
+        let offset = get_trace_domain_value_at::<B>(trace_length, assertion.first_step);
+        Self::from_vanishing_poly(VanishingPoly::coset(num_steps, offset))
     }
 
     // PUBLIC ACCESSORS
@@ -129,17 +131,44 @@ impl<B: StarkField> ConstraintDivisor<B> {
     /// Evaluates the divisor polynomial at the provided `x` coordinate.
     pub fn evaluate_at<E: FieldElement<BaseField = B>>(&self, x: E) -> E {
         // compute the numerator value
+        let numerator = self.evaluate_numerator_at(x);
+
+        // compute the denominator value
+        let denominator = self.evaluate_exemptions_at(x);
+
+        numerator / denominator
+    }
+
+    /// Evaluates inverses of all specified divisors at the provided `x` coordinate.
+    ///
+    /// This is equivalent to computing `divisor.evaluate_at(x).inv()` for every divisor, but
+    /// numerators of all divisors are inverted together using batch inversion. Thus, the
+    /// evaluation requires a single field inversion regardless of the number of divisors. If a
+    /// divisor evaluates to zero at `x`, the corresponding inverse is set to zero.
+    pub fn evaluate_inverses_at<E: FieldElement<BaseField = B>>(
+        divisors: &[&Self],
+        x: E,
+    ) -> Vec<E> {
+        let numerators = divisors
+            .iter()
+            .map(|divisor| divisor.evaluate_numerator_at(x))
+            .collect::<Vec<_>>();
+        batch_inversion(&numerators)
+            .into_iter()
+            .zip(divisors)
+            .map(|(numerator_inv, divisor)| numerator_inv * divisor.evaluate_exemptions_at(x))
+            .collect()
+    }
+
+    /// Evaluates the numerator of this divisor at the provided `x` coordinate.
+    pub fn evaluate_numerator_at<E: FieldElement<BaseField = B>>(&self, x: E) -> E {
         let mut numerator = E::ONE;
         for (degree, constant) in self.numerator.iter() {
             let v = x.exp((*degree as u32).into());
             let v = v - E::from(*constant);
             numerator *= v;
         }
-
-        // compute the denominator value
-        let denominator = self.evaluate_exemptions_at(x);
-
-        numerator / denominator
+        numerator
     }
 
     /// Evaluates the denominator of this divisor (the exemption points) at the provided `x`
@@ -217,6 +246,34 @@ mod tests {
         assert_eq!(7, div.degree());
     }
 
+    #[test]
+    fn constraint_divisor_batch_evaluation() {
+        let transition =
+            ConstraintDivisor::new(vec![(8, BaseElement::ONE)], vec![BaseElement::new(3)]);
+        let boundary = ConstraintDivisor::new(vec![(1, BaseElement::new(5))], vec![]);
+        let periodic = ConstraintDivisor::new(
+            vec![(4, BaseElement::new(2)), (2, BaseElement::new(7))],
+            vec![BaseElement::new(11), BaseElement::new(13)],
+        );
+        let divisors = [&transition, &boundary, &periodic];
+
+        let x = BaseElement::new(23);
+        let inverses = ConstraintDivisor::evaluate_inverses_at(&divisors, x);
+        assert_eq!(divisors.len(), inverses.len());
+        for (divisor, inverse) in divisors.iter().zip(inverses) {
+            assert_eq!(divisor.evaluate_at(x).inv(), inverse);
+        }
+
+        // a divisor which evaluates to zero at x has zero inverse; other inverses are unaffected
+        let inverses = ConstraintDivisor::evaluate_inverses_at(&divisors, BaseElement::new(5));
+        assert_eq!(BaseElement::ZERO, inverses[1]);
+        assert_eq!(
+            transition.evaluate_at(BaseElement::new(5)).inv(),
+            inverses[0]
+        );
+        assert_eq!(periodic.evaluate_at(BaseElement::new(5)).inv(), inverses[2]);
+    }
+
     #[test]
     fn constraint_divisor_evaluation() {
         // single term numerator: (x^4 - 1)
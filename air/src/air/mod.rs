@@ -3,7 +3,7 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use crate::ProofOptions;
+use crate::{ProofOptions, QuerySampling};
 use crypto::{RandomCoin, RandomCoinError};
 use math::{fft, ExtensibleField, ExtensionOf, FieldElement, StarkField, ToElements};
 use utils::collections::{BTreeMap, Vec};
@@ -12,17 +12,19 @@ mod trace_info;
 pub use trace_info::{TraceInfo, TraceLayout};
 
 mod context;
+pub(crate) use context::get_lde_domain_size;
 pub use context::AirContext;
 
 mod assertions;
-pub use assertions::Assertion;
+pub use assertions::{Assertion, Assertions, TraceStep};
 
 mod boundary;
 pub use boundary::{BoundaryConstraint, BoundaryConstraintGroup, BoundaryConstraints};
 
 mod transition;
 pub use transition::{
-    EvaluationFrame, TransitionConstraintDegree, TransitionConstraintGroup, TransitionConstraints,
+    EvaluationFrame, Expr, Register, TransitionConstraintDegree, TransitionConstraintGroup,
+    TransitionConstraints, TransitionExpressions,
 };
 
 mod coefficients;
@@ -33,6 +35,9 @@ pub use coefficients::{
 mod divisor;
 pub use divisor::ConstraintDivisor;
 
+mod multi;
+pub use multi::{MultiInstanceAir, MultiInstanceInputs};
+
 #[cfg(test)]
 mod tests;
 
@@ -41,6 +46,10 @@ mod tests;
 
 const MIN_CYCLE_LENGTH: usize = 2;
 
+/// Maximum number of times a linear combination coefficient is re-drawn from the public coin
+/// because the drawn value was zero.
+const MAX_COEFFICIENT_DRAWS: usize = 100;
+
 // AIR TRAIT
 // ================================================================================================
 /// Describes algebraic intermediate representation of a computation.
@@ -296,6 +305,14 @@ pub trait Air: Send + Sync {
     /// The default implementation of this method returns an empty vector. For computations which
     /// rely on periodic columns, this method should be overridden in the specialized
     /// implementation. Number of values for each periodic column must be a power of two.
+    ///
+    /// Values of periodic columns may depend on public inputs (e.g., a message schedule derived
+    /// from a public hash input): both the prover and the verifier instantiate the AIR from the
+    /// same public inputs, and thus, derive the same values. Unlike values of trace columns, such
+    /// values do not need to be committed to or included in the execution trace. A column whose
+    /// values do not repeat can be described by a periodic column with a cycle equal to the trace
+    /// length; however, the verifier then interpolates a polynomial of degree equal to the trace
+    /// length, and thus, such columns should be used only for short traces.
     fn get_periodic_column_values(&self) -> Vec<Vec<Self::BaseField>> {
         Vec::new()
     }
@@ -502,6 +519,10 @@ pub trait Air: Send + Sync {
 
     /// Returns coefficients needed for random linear combination during construction of constraint
     /// composition polynomial.
+    ///
+    /// All coefficients are guaranteed to be non-zero: whenever a zero is drawn from the public
+    /// coin, the coefficient is re-drawn (which advances the coin state), as otherwise, the
+    /// corresponding constraint would silently drop out of the linear combination.
     fn get_constraint_composition_coefficients<E, R>(
         &self,
         public_coin: &mut R,
@@ -512,12 +533,12 @@ pub trait Air: Send + Sync {
     {
         let mut t_coefficients = Vec::new();
         for _ in 0..self.context().num_transition_constraints() {
-            t_coefficients.push(public_coin.draw_pair()?);
+            t_coefficients.push(draw_nonzero_pair(public_coin)?);
         }
 
         let mut b_coefficients = Vec::new();
         for _ in 0..self.context().num_assertions() {
-            b_coefficients.push(public_coin.draw_pair()?);
+            b_coefficients.push(draw_nonzero_pair(public_coin)?);
         }
 
         Ok(ConstraintCompositionCoefficients {
@@ -528,6 +549,9 @@ pub trait Air: Send + Sync {
 
     /// Returns coefficients needed for random linear combinations during construction of DEEP
     /// composition polynomial.
+    ///
+    /// Same as for constraint composition coefficients, all coefficients are guaranteed to be
+    /// non-zero.
     fn get_deep_composition_coefficients<E, R>(
         &self,
         public_coin: &mut R,
@@ -538,19 +562,83 @@ pub trait Air: Send + Sync {
     {
         let mut t_coefficients = Vec::new();
         for _ in 0..self.trace_info().width() {
-            t_coefficients.push(public_coin.draw_pair()?);
+            t_coefficients.push(draw_nonzero_pair(public_coin)?);
         }
 
         // self.ce_blowup_factor() is the same as number of composition columns
         let mut c_coefficients = Vec::new();
         for _ in 0..self.ce_blowup_factor() {
-            c_coefficients.push(public_coin.draw()?);
+            c_coefficients.push(draw_nonzero(public_coin)?);
         }
 
         Ok(DeepCompositionCoefficients {
             trace: t_coefficients,
             constraints: c_coefficients,
-            degree: public_coin.draw_pair()?,
+            degree: draw_nonzero_pair(public_coin)?,
         })
     }
+
+    // QUERY POSITIONS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a set of distinct positions in the LDE domain at which trace and constraint
+    /// composition polynomial evaluations are to be queried.
+    ///
+    /// The positions are drawn from the provided public coin according to the query sampling
+    /// strategy specified in the proof options. For stratified sampling, queries are assigned to
+    /// segments of the LDE domain in round-robin order, and positions for each segment are drawn
+    /// uniformly from within that segment; the returned positions are ordered by segment.
+    fn get_query_positions<R>(&self, public_coin: &mut R) -> Result<Vec<usize>, RandomCoinError>
+    where
+        R: RandomCoin<BaseField = Self::BaseField>,
+    {
+        let num_queries = self.options().num_queries();
+        let lde_domain_size = self.lde_domain_size();
+        match self.options().query_sampling() {
+            QuerySampling::Uniform => public_coin.draw_integers(num_queries, lde_domain_size),
+            QuerySampling::Stratified(num_segments) => {
+                let segment_size = lde_domain_size / num_segments;
+                let mut result = Vec::with_capacity(num_queries);
+                for segment_idx in 0..num_segments {
+                    // queries are assigned to segments in round-robin order
+                    let num_values = (num_queries - segment_idx + num_segments - 1) / num_segments;
+                    let offset = segment_idx * segment_size;
+                    let positions = public_coin.draw_integers(num_values, segment_size)?;
+                    result.extend(positions.into_iter().map(|p| p + offset));
+                }
+                Ok(result)
+            }
+        }
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Draws a non-zero field element from the specified public coin.
+///
+/// Zero values are discarded, and the element is re-drawn up to [MAX_COEFFICIENT_DRAWS] times.
+fn draw_nonzero<E, R>(public_coin: &mut R) -> Result<E, RandomCoinError>
+where
+    E: FieldElement,
+    R: RandomCoin<BaseField = E::BaseField>,
+{
+    for _ in 0..MAX_COEFFICIENT_DRAWS {
+        let value: E = public_coin.draw()?;
+        if value != E::ZERO {
+            return Ok(value);
+        }
+    }
+    Err(RandomCoinError::FailedToDrawFieldElement(
+        MAX_COEFFICIENT_DRAWS,
+    ))
+}
+
+/// Draws a pair of non-zero field elements from the specified public coin.
+fn draw_nonzero_pair<E, R>(public_coin: &mut R) -> Result<(E, E), RandomCoinError>
+where
+    E: FieldElement,
+    R: RandomCoin<BaseField = E::BaseField>,
+{
+    Ok((draw_nonzero(public_coin)?, draw_nonzero(public_coin)?))
 }
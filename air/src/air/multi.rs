@@ -0,0 +1,185 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{Air, AirContext, Assertion, EvaluationFrame, TraceInfo};
+use crate::ProofOptions;
+use math::{FieldElement, StarkField, ToElements};
+use utils::collections::Vec;
+
+// MULTI-INSTANCE PUBLIC INPUTS
+// ================================================================================================
+
+/// Public inputs for multiple independent instances of the same computation; one entry per
+/// instance.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MultiInstanceInputs<P>(pub Vec<P>);
+
+impl<B: StarkField, P: ToElements<B>> ToElements<B> for MultiInstanceInputs<P> {
+    fn to_elements(&self) -> Vec<B> {
+        // the number of instances is bound first so that inputs of different numbers of
+        // instances cannot be confused
+        let mut result = vec![B::from(self.0.len() as u64)];
+        for inputs in self.0.iter() {
+            result.append(&mut inputs.to_elements());
+        }
+        result
+    }
+}
+
+// MULTI-INSTANCE AIR
+// ================================================================================================
+
+/// AIR for multiple independent instances of the same computation proven in a single proof.
+///
+/// Execution traces of all instances are placed side by side: columns of instance `i` occupy
+/// columns `i * w..(i + 1) * w` of the combined trace, where `w` is the width of a single
+/// instance trace. Transition constraints, periodic columns, and assertions of each instance are
+/// those of the underlying AIR instantiated with the public inputs of that instance; all
+/// constraints are combined using the same random coefficients and are checked with a single
+/// FRI instance.
+///
+/// All instances must have traces of the same length, and only computations which require a
+/// single execution trace segment are supported.
+pub struct MultiInstanceAir<A: Air> {
+    context: AirContext<A::BaseField>,
+    instances: Vec<A>,
+    instance_width: usize,
+    num_instance_constraints: usize,
+    num_instance_periodic_columns: usize,
+}
+
+impl<A: Air> MultiInstanceAir<A> {
+    /// Returns AIRs of the individual instances.
+    pub fn instances(&self) -> &[A] {
+        &self.instances
+    }
+}
+
+impl<A: Air> Air for MultiInstanceAir<A> {
+    type BaseField = A::BaseField;
+    type PublicInputs = MultiInstanceInputs<A::PublicInputs>;
+
+    /// Returns a new AIR for the combined trace described by `trace_info`.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * `pub_inputs` is empty.
+    /// * `trace_info` describes a multi-segment execution trace.
+    /// * Trace width is not divisible by the number of instances.
+    fn new(trace_info: TraceInfo, pub_inputs: Self::PublicInputs, options: ProofOptions) -> Self {
+        let num_instances = pub_inputs.0.len();
+        assert!(num_instances > 0, "at least one instance must be provided");
+        assert!(
+            !trace_info.is_multi_segment(),
+            "multi-segment execution traces are not supported for multiple instances"
+        );
+        assert_eq!(
+            trace_info.width() % num_instances,
+            0,
+            "trace width {} is not divisible by the number of instances {}",
+            trace_info.width(),
+            num_instances
+        );
+
+        let instance_width = trace_info.width() / num_instances;
+        let instance_info = TraceInfo::with_meta(
+            instance_width,
+            trace_info.length(),
+            trace_info.meta().to_vec(),
+        )
+        .set_original_length(trace_info.original_length());
+        let instances = pub_inputs
+            .0
+            .into_iter()
+            .map(|inputs| A::new(instance_info.clone(), inputs, options.clone()))
+            .collect::<Vec<_>>();
+
+        let instance_context = instances[0].context();
+        let num_instance_constraints = instance_context.num_main_transition_constraints();
+        let real_trace_length = instance_context.real_trace_length();
+        let num_instance_periodic_columns = instances[0].get_periodic_column_values().len();
+
+        let transition_constraint_degrees = instances
+            .iter()
+            .flat_map(|instance| {
+                instance
+                    .context()
+                    .main_transition_constraint_degrees
+                    .iter()
+                    .cloned()
+            })
+            .collect();
+        let num_assertions = instances
+            .iter()
+            .map(|instance| instance.context().num_main_assertions)
+            .sum();
+
+        let context = AirContext::new(
+            trace_info,
+            transition_constraint_degrees,
+            num_assertions,
+            options,
+        )
+        .set_real_trace_length(real_trace_length);
+
+        MultiInstanceAir {
+            context,
+            instances,
+            instance_width,
+            num_instance_constraints,
+            num_instance_periodic_columns,
+        }
+    }
+
+    fn context(&self) -> &AirContext<Self::BaseField> {
+        &self.context
+    }
+
+    fn evaluate_transition<E: FieldElement<BaseField = Self::BaseField>>(
+        &self,
+        frame: &EvaluationFrame<E>,
+        periodic_values: &[E],
+        result: &mut [E],
+    ) {
+        let width = self.instance_width;
+        let num_periodic_columns = self.num_instance_periodic_columns;
+        let mut instance_frame = EvaluationFrame::new(width);
+        for (i, (instance, result)) in self
+            .instances
+            .iter()
+            .zip(result.chunks_mut(self.num_instance_constraints))
+            .enumerate()
+        {
+            let columns = i * width..(i + 1) * width;
+            instance_frame
+                .current_mut()
+                .copy_from_slice(&frame.current()[columns.clone()]);
+            instance_frame
+                .next_mut()
+                .copy_from_slice(&frame.next()[columns]);
+            let periodic_values =
+                &periodic_values[i * num_periodic_columns..(i + 1) * num_periodic_columns];
+            instance.evaluate_transition(&instance_frame, periodic_values, result);
+        }
+    }
+
+    fn get_assertions(&self) -> Vec<Assertion<Self::BaseField>> {
+        let mut result = Vec::new();
+        for (i, instance) in self.instances.iter().enumerate() {
+            for mut assertion in instance.get_assertions() {
+                assertion.column += i * self.instance_width;
+                result.push(assertion);
+            }
+        }
+        result
+    }
+
+    fn get_periodic_column_values(&self) -> Vec<Vec<Self::BaseField>> {
+        self.instances
+            .iter()
+            .flat_map(|instance| instance.get_periodic_column_values())
+            .collect()
+    }
+}
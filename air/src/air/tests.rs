@@ -4,11 +4,11 @@
 // LICENSE file in the root directory of this source tree.
 
 use super::{
-    Air, AirContext, Assertion, EvaluationFrame, ProofOptions, TraceInfo,
+    Air, AirContext, Assertion, EvaluationFrame, ProofOptions, QuerySampling, TraceInfo,
     TransitionConstraintDegree,
 };
 use crate::{AuxTraceRandElements, FieldExtension};
-use crypto::{hashers::Blake3_256, DefaultRandomCoin, RandomCoin};
+use crypto::{hashers::Blake3_256, DefaultRandomCoin, Hasher, RandomCoin, RandomCoinError};
 use math::{fields::f128::BaseElement, get_power_series, polynom, FieldElement, StarkField};
 use utils::collections::{BTreeMap, Vec};
 
@@ -59,7 +59,106 @@ fn get_periodic_column_polys_num_values_not_power_of_two() {
 // TRANSITION CONSTRAINTS
 // ================================================================================================
 
-// TODO
+#[test]
+fn transition_constraint_degree_min_blowup_factor() {
+    // trace columns only
+    assert_eq!(2, TransitionConstraintDegree::new(1).min_blowup_factor());
+    assert_eq!(2, TransitionConstraintDegree::new(3).min_blowup_factor());
+    assert_eq!(4, TransitionConstraintDegree::new(4).min_blowup_factor());
+    assert_eq!(8, TransitionConstraintDegree::new(6).min_blowup_factor());
+
+    // periodic columns contribute less than a full trace column
+    let degree = TransitionConstraintDegree::with_cycles(3, vec![32]);
+    assert_eq!(4, degree.min_blowup_factor());
+    let degree = TransitionConstraintDegree::with_cycles(2, vec![2, 2]);
+    assert_eq!(2, degree.min_blowup_factor());
+    let degree = TransitionConstraintDegree::with_cycles(2, vec![4, 8, 16]);
+    assert_eq!(4, degree.min_blowup_factor());
+    let degree = TransitionConstraintDegree::with_cycles(1, vec![2, 2, 2, 2]);
+    assert_eq!(4, degree.min_blowup_factor());
+}
+
+#[test]
+fn transition_constraint_degree_min_blowup_factor_fits_evaluation_degree() {
+    let degrees = [
+        TransitionConstraintDegree::new(2),
+        TransitionConstraintDegree::with_cycles(1, vec![8]),
+        TransitionConstraintDegree::with_cycles(1, vec![2, 2]),
+        TransitionConstraintDegree::with_cycles(1, vec![2, 2, 2, 2]),
+        TransitionConstraintDegree::with_cycles(2, vec![2, 2]),
+        TransitionConstraintDegree::with_cycles(3, vec![4, 16, 32]),
+    ];
+    for trace_length in [32, 64, 1024] {
+        for degree in degrees.iter() {
+            // once divided by the transition divisor, the constraint polynomial must fit into
+            // the constraint evaluation domain
+            let divisor_degree = trace_length - 1;
+            let ce_domain_size = trace_length * degree.min_blowup_factor();
+            assert!(ce_domain_size + divisor_degree > degree.get_evaluation_degree(trace_length));
+        }
+    }
+}
+
+#[test]
+fn air_context_real_trace_length() {
+    let trace_info = TraceInfo::new(1, 16);
+    let options = ProofOptions::new(32, 8, 0, FieldExtension::None, 4, 31);
+    let degrees = vec![TransitionConstraintDegree::new(3)];
+
+    // by default, all rows are real and only the last row is exempt from transition constraints
+    let context = AirContext::<BaseElement>::new(trace_info.clone(), degrees.clone(), 1, options);
+    assert_eq!(16, context.real_trace_length());
+    assert_eq!(1, context.num_transition_exemptions());
+    assert_eq!(2, context.ce_blowup_factor);
+
+    // with 6 virtual rows, the last 7 rows are exempt, and the degree of the constraint quotient
+    // becomes 45 - 9 = 36; thus, constraint evaluation blowup factor must grow to 4
+    let context = context.set_real_trace_length(10);
+    assert_eq!(10, context.real_trace_length());
+    assert_eq!(7, context.num_transition_exemptions());
+    assert_eq!(4, context.ce_blowup_factor);
+}
+
+#[test]
+#[should_panic(expected = "real trace length must be between 8 and 16, but was 7")]
+fn air_context_real_trace_length_too_small() {
+    let trace_info = TraceInfo::new(1, 16);
+    let options = ProofOptions::new(32, 8, 0, FieldExtension::None, 4, 31);
+    let degrees = vec![TransitionConstraintDegree::new(2)];
+    let _ =
+        AirContext::<BaseElement>::new(trace_info, degrees, 1, options).set_real_trace_length(7);
+}
+
+#[test]
+#[should_panic(
+    expected = "blowup factor too small for 6 virtual rows; expected at least 4, but was 2"
+)]
+fn air_context_real_trace_length_blowup_too_small() {
+    let trace_info = TraceInfo::new(1, 16);
+    let options = ProofOptions::new(32, 2, 0, FieldExtension::None, 4, 31);
+    let degrees = vec![TransitionConstraintDegree::new(3)];
+    let _ =
+        AirContext::<BaseElement>::new(trace_info, degrees, 1, options).set_real_trace_length(10);
+}
+
+#[test]
+#[should_panic(expected = "LDE domain size cannot exceed 2^40, but was 2^41")]
+fn air_context_lde_domain_too_large() {
+    // the two-adicity of the 128-bit field is 40
+    let trace_info = TraceInfo::new(1, 1 << 38);
+    let options = ProofOptions::new(32, 8, 0, FieldExtension::None, 4, 31);
+    let degrees = vec![TransitionConstraintDegree::new(2)];
+    let _ = AirContext::<BaseElement>::new(trace_info, degrees, 1, options);
+}
+
+#[test]
+#[should_panic(expected = "does not fit into")]
+fn air_context_lde_domain_size_overflow() {
+    let trace_info = TraceInfo::new(1, 1 << (usize::BITS - 2));
+    let options = ProofOptions::new(32, 8, 0, FieldExtension::None, 4, 31);
+    let degrees = vec![TransitionConstraintDegree::new(2)];
+    let _ = AirContext::<BaseElement>::new(trace_info, degrees, 1, options);
+}
 
 // BOUNDARY CONSTRAINTS
 // ================================================================================================
@@ -217,6 +316,174 @@ fn get_boundary_constraints() {
     assert_eq!(expected_cc[&7], constraint.cc().clone());
 }
 
+// QUERY POSITIONS
+// ================================================================================================
+
+#[test]
+fn get_query_positions_stratified() {
+    let options = ProofOptions::new(30, 8, 0, FieldExtension::None, 4, 31)
+        .set_query_sampling(QuerySampling::Stratified(8));
+    let air = MockAir::new(TraceInfo::with_meta(4, 16, vec![1]), (), options);
+    let lde_domain_size = air.lde_domain_size();
+    let segment_size = lde_domain_size / 8;
+
+    let mut prng = build_prng();
+    let positions = air.get_query_positions(&mut prng).unwrap();
+    assert_eq!(30, positions.len());
+
+    // all positions are distinct and within the LDE domain
+    let mut sorted = positions.clone();
+    sorted.sort_unstable();
+    sorted.dedup();
+    assert_eq!(30, sorted.len());
+    assert!(sorted.iter().all(|&p| p < lde_domain_size));
+
+    // queries are assigned to segments in round-robin order
+    let mut segment_counts = [0; 8];
+    for p in positions {
+        segment_counts[p / segment_size] += 1;
+    }
+    assert_eq!([4, 4, 4, 4, 4, 4, 3, 3], segment_counts);
+
+    // drawing positions is deterministic
+    let mut prng = build_prng();
+    let mut expected = air.get_query_positions(&mut prng).unwrap();
+    expected.sort_unstable();
+    assert_eq!(sorted, expected);
+}
+
+// LINEAR COMBINATION COEFFICIENTS
+// ================================================================================================
+
+#[test]
+fn composition_coefficients_redraw_zeros() {
+    let air = MockAir::with_assertions(
+        vec![
+            Assertion::single(0, 0, BaseElement::ONE),
+            Assertion::single(1, 0, BaseElement::ONE),
+        ],
+        16,
+    );
+
+    // the coin returns zeros for several draws, including two consecutive ones; coefficients
+    // must be the same as the ones drawn from a coin which never returns zeros
+    let mut coin = ZeroingCoin::new(vec![0, 1, 4, 9]);
+    let cc = air
+        .get_constraint_composition_coefficients::<BaseElement, _>(&mut coin)
+        .unwrap();
+    let dc = air
+        .get_deep_composition_coefficients::<BaseElement, _>(&mut coin)
+        .unwrap();
+    assert_eq!(4, coin.num_zeros);
+
+    let mut prng = build_prng();
+    let expected_cc = air
+        .get_constraint_composition_coefficients::<BaseElement, _>(&mut prng)
+        .unwrap();
+    let expected_dc = air
+        .get_deep_composition_coefficients::<BaseElement, _>(&mut prng)
+        .unwrap();
+
+    assert_eq!(expected_cc.transition, cc.transition);
+    assert_eq!(expected_cc.boundary, cc.boundary);
+    assert_eq!(expected_dc.trace, dc.trace);
+    assert_eq!(expected_dc.constraints, dc.constraints);
+    assert_eq!(expected_dc.degree, dc.degree);
+
+    let pairs = cc.transition.iter().chain(&cc.boundary).chain(&dc.trace);
+    for (a, b) in pairs {
+        assert_ne!(BaseElement::ZERO, *a);
+        assert_ne!(BaseElement::ZERO, *b);
+    }
+}
+
+#[test]
+fn composition_coefficients_too_many_zeros() {
+    let air = MockAir::with_assertions(vec![Assertion::single(0, 0, BaseElement::ONE)], 16);
+    let mut coin = ZeroingCoin::new((0..100).collect());
+    assert_eq!(
+        Err(RandomCoinError::FailedToDrawFieldElement(100)),
+        air.get_constraint_composition_coefficients::<BaseElement, _>(&mut coin)
+            .map(|_| ())
+    );
+}
+
+// MOCK RANDOM COIN
+// ================================================================================================
+
+/// A random coin which returns zeros for the specified draws of field elements, and otherwise
+/// returns elements drawn from the coin returned by [build_prng()].
+struct ZeroingCoin {
+    inner: DefaultRandomCoin<Blake3_256<BaseElement>>,
+    zero_draws: Vec<usize>,
+    num_draws: usize,
+    num_zeros: usize,
+}
+
+impl ZeroingCoin {
+    fn new(zero_draws: Vec<usize>) -> Self {
+        Self {
+            inner: build_prng(),
+            zero_draws,
+            num_draws: 0,
+            num_zeros: 0,
+        }
+    }
+}
+
+impl RandomCoin for ZeroingCoin {
+    type BaseField = BaseElement;
+    type Hasher = Blake3_256<BaseElement>;
+
+    fn new(seed: &[Self::BaseField]) -> Self {
+        Self {
+            inner: DefaultRandomCoin::new(seed),
+            zero_draws: Vec::new(),
+            num_draws: 0,
+            num_zeros: 0,
+        }
+    }
+
+    fn reseed(&mut self, data: <Self::Hasher as Hasher>::Digest) {
+        self.inner.reseed(data)
+    }
+
+    fn reseed_with_int(&mut self, value: u64) {
+        self.inner.reseed_with_int(value)
+    }
+
+    fn leading_zeros(&self) -> u32 {
+        self.inner.leading_zeros()
+    }
+
+    fn check_leading_zeros(&self, value: u64) -> u32 {
+        self.inner.check_leading_zeros(value)
+    }
+
+    fn seed(&self) -> Option<<Self::Hasher as Hasher>::Digest> {
+        self.inner.seed()
+    }
+
+    fn draw<E: FieldElement<BaseField = Self::BaseField>>(&mut self) -> Result<E, RandomCoinError> {
+        let draw_idx = self.num_draws;
+        self.num_draws += 1;
+        if self.zero_draws.contains(&draw_idx) {
+            self.num_zeros += 1;
+            Ok(E::ZERO)
+        } else {
+            self.inner.draw()
+        }
+    }
+
+    fn draw_integers(
+        &mut self,
+        num_values: usize,
+        domain_size: usize,
+    ) -> Result<Vec<usize>, RandomCoinError> {
+        self.inner.draw_integers(num_values, domain_size)
+    }
+}
+
 // MOCK AIR
 // ================================================================================================
 
@@ -234,7 +501,7 @@ impl MockAir {
         let mut result = Self::new(
             TraceInfo::with_meta(4, trace_length, vec![1]),
             (),
-            ProofOptions::new(32, 8, 0, FieldExtension::None, 4, 31),
+            ProofOptions::for_testing(),
         );
         result.periodic_columns = column_values;
         result
@@ -244,7 +511,7 @@ impl MockAir {
         let mut result = Self::new(
             TraceInfo::with_meta(4, trace_length, vec![assertions.len() as u8]),
             (),
-            ProofOptions::new(32, 8, 0, FieldExtension::None, 4, 31),
+            ProofOptions::for_testing(),
         );
         result.assertions = assertions;
         result
@@ -255,9 +522,10 @@ impl Air for MockAir {
     type BaseField = BaseElement;
     type PublicInputs = ();
 
-    fn new(trace_info: TraceInfo, _pub_inputs: (), _options: ProofOptions) -> Self {
+    fn new(trace_info: TraceInfo, _pub_inputs: (), options: ProofOptions) -> Self {
         let num_assertions = trace_info.meta()[0] as usize;
-        let context = build_context(trace_info.length(), trace_info.width(), num_assertions);
+        let t_degrees = vec![TransitionConstraintDegree::new(2)];
+        let context = AirContext::new(trace_info, t_degrees, num_assertions, options);
         MockAir {
             context,
             assertions: Vec::new(),
@@ -289,17 +557,6 @@ impl Air for MockAir {
 // UTILITY FUNCTIONS
 // ================================================================================================
 
-pub fn build_context<B: StarkField>(
-    trace_length: usize,
-    trace_width: usize,
-    num_assertions: usize,
-) -> AirContext<B> {
-    let options = ProofOptions::new(32, 8, 0, FieldExtension::None, 4, 31);
-    let t_degrees = vec![TransitionConstraintDegree::new(2)];
-    let trace_info = TraceInfo::new(trace_width, trace_length);
-    AirContext::new(trace_info, t_degrees, num_assertions, options)
-}
-
 pub fn build_prng() -> DefaultRandomCoin<Blake3_256<BaseElement>> {
     RandomCoin::new(&[BaseElement::ZERO; 32])
 }
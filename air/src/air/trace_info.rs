@@ -23,10 +23,15 @@ const NUM_AUX_SEGMENTS: usize = 1;
 /// specifies the number of columns for all trace segments. Currently, a trace can consist of at
 /// most two segments. Metadata is just a vector of bytes and can store any values up to 64KB in
 /// size.
+///
+/// If the execution trace was padded to a power of two, trace info also records the original
+/// (unpadded) length of the trace. This allows assertions to be placed against the last step of
+/// the actual computation.
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub struct TraceInfo {
     layout: TraceLayout,
     length: usize,
+    original_length: usize,
     meta: Vec<u8>,
 }
 
@@ -97,10 +102,30 @@ impl TraceInfo {
         TraceInfo {
             layout,
             length,
+            original_length: length,
             meta,
         }
     }
 
+    /// Sets the length of the execution trace before it was padded to a power of two.
+    ///
+    /// # Panics
+    /// Panics if `original_length` is zero or is greater than the length of the trace.
+    pub fn set_original_length(mut self, original_length: usize) -> Self {
+        assert!(
+            original_length > 0,
+            "original trace length must be greater than zero"
+        );
+        assert!(
+            original_length <= self.length,
+            "original trace length cannot be greater than trace length {}, but was {}",
+            self.length,
+            original_length
+        );
+        self.original_length = original_length;
+        self
+    }
+
     // PUBLIC ACCESSORS
     // --------------------------------------------------------------------------------------------
 
@@ -125,6 +150,20 @@ impl TraceInfo {
         self.length
     }
 
+    /// Returns the length of the execution trace before it was padded to a power of two.
+    ///
+    /// If the trace was not padded, this is the same as [TraceInfo::length()]. Thus, the last
+    /// step of the actual computation is always at `original_length() - 1`.
+    pub fn original_length(&self) -> usize {
+        self.original_length
+    }
+
+    /// Returns true if the execution trace was padded to a power of two (i.e., the original
+    /// length of the trace is smaller than its length).
+    pub fn is_padded(&self) -> bool {
+        self.original_length < self.length
+    }
+
     /// Returns execution trace metadata.
     pub fn meta(&self) -> &[u8] {
         &self.meta
@@ -154,12 +193,19 @@ impl TraceInfo {
 /// The number of random elements may be different from the number of columns in a given auxiliary
 /// segment. For example, an auxiliary segment may contain just one column, but may require many
 /// random elements.
+///
+/// The first few columns of the main segment may be designated as preprocessed columns. Values
+/// in these columns do not depend on the inputs of a computation (e.g., a fixed lookup table or
+/// round constants), and thus, they can be committed to once at setup time and reused across many
+/// proofs. Preprocessed columns are committed to separately from the rest of the main segment,
+/// and the verifier must obtain the commitment to them from the setup rather than from a proof.
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub struct TraceLayout {
     main_segment_width: usize,
     aux_segment_widths: [usize; NUM_AUX_SEGMENTS],
     aux_segment_rands: [usize; NUM_AUX_SEGMENTS],
     num_aux_segments: usize,
+    num_preprocessed_columns: usize,
 }
 
 impl TraceLayout {
@@ -227,9 +273,27 @@ impl TraceLayout {
             aux_segment_widths: aux_widths,
             aux_segment_rands: aux_rands,
             num_aux_segments,
+            num_preprocessed_columns: 0,
         }
     }
 
+    /// Designates the first `num_columns` columns of the main trace segment as preprocessed
+    /// columns.
+    ///
+    /// # Panics
+    /// Panics if `num_columns` is not smaller than the width of the main trace segment; that is,
+    /// the main segment must contain at least one column which is not preprocessed.
+    pub fn set_num_preprocessed_columns(mut self, num_columns: usize) -> Self {
+        assert!(
+            num_columns < self.main_segment_width,
+            "number of preprocessed columns must be smaller than main trace segment width {}, but was {}",
+            self.main_segment_width,
+            num_columns
+        );
+        self.num_preprocessed_columns = num_columns;
+        self
+    }
+
     // PUBLIC ACCESSORS
     // --------------------------------------------------------------------------------------------
 
@@ -240,6 +304,17 @@ impl TraceLayout {
         self.main_segment_width
     }
 
+    /// Returns the number of preprocessed columns at the start of the main segment of an execution
+    /// trace.
+    pub fn num_preprocessed_columns(&self) -> usize {
+        self.num_preprocessed_columns
+    }
+
+    /// Returns true if the main segment of an execution trace contains preprocessed columns.
+    pub fn has_preprocessed_columns(&self) -> bool {
+        self.num_preprocessed_columns > 0
+    }
+
     /// Returns the number of columns in all auxiliary segments of an execution trace.
     pub fn aux_trace_width(&self) -> usize {
         self.aux_segment_widths.iter().sum()
@@ -255,6 +330,14 @@ impl TraceLayout {
         self.num_aux_segments
     }
 
+    /// Returns the number of separate commitments to an execution trace.
+    ///
+    /// This is the number of trace segments plus one if the main segment contains preprocessed
+    /// columns (which are committed to separately from the rest of the main segment).
+    pub fn num_trace_commitments(&self) -> usize {
+        self.num_segments() + self.has_preprocessed_columns() as usize
+    }
+
     /// Returns the number of columns in the auxiliary trace segment at the specified index.
     pub fn get_aux_segment_width(&self, segment_idx: usize) -> usize {
         // TODO: panic if segment_idx is not within num_aux_segments
@@ -291,6 +374,11 @@ impl<E: StarkField> ToElements<E> for TraceLayout {
             result.push(E::from(buf));
         }
 
+        // the number of preprocessed columns (if any) goes into the last element
+        if self.has_preprocessed_columns() {
+            result.push(E::from(self.num_preprocessed_columns as u64));
+        }
+
         result
     }
 }
@@ -302,17 +390,18 @@ impl Serializable for TraceLayout {
         for &w in self.aux_segment_widths.iter() {
             debug_assert!(
                 w <= u16::MAX as usize,
-                "aux segment width does not fit into u8 value"
+                "aux segment width does not fit into u16 value"
             );
             target.write_u16(w as u16);
         }
         for &rc in self.aux_segment_rands.iter() {
             debug_assert!(
-                rc <= u8::MAX as usize,
-                "aux segment random element count does not fit into u8 value"
+                rc <= u16::MAX as usize,
+                "aux segment random element count does not fit into u16 value"
             );
             target.write_u16(rc as u16);
         }
+        target.write_u16(self.num_preprocessed_columns as u16);
     }
 }
 
@@ -347,7 +436,7 @@ impl Deserializable for TraceLayout {
         }
 
         let full_trace_width = main_width + aux_widths.iter().sum::<usize>();
-        if full_trace_width >= TraceInfo::MAX_TRACE_WIDTH {
+        if full_trace_width > TraceInfo::MAX_TRACE_WIDTH {
             return Err(DeserializationError::InvalidValue(format!(
                 "full trace width cannot be greater than {}, but was {}",
                 TraceInfo::MAX_TRACE_WIDTH,
@@ -377,7 +466,16 @@ impl Deserializable for TraceLayout {
             }
         }
 
-        Ok(TraceLayout::new(main_width, aux_widths, aux_rands))
+        // read and validate the number of preprocessed columns
+        let num_preprocessed_columns = source.read_u16()? as usize;
+        if num_preprocessed_columns >= main_width {
+            return Err(DeserializationError::InvalidValue(format!(
+                "number of preprocessed columns must be smaller than main trace segment width {main_width}, but was {num_preprocessed_columns}"
+            )));
+        }
+
+        Ok(TraceLayout::new(main_width, aux_widths, aux_rands)
+            .set_num_preprocessed_columns(num_preprocessed_columns))
     }
 }
 
@@ -386,8 +484,9 @@ impl Deserializable for TraceLayout {
 
 #[cfg(test)]
 mod tests {
-    use super::{ToElements, TraceLayout};
+    use super::{ToElements, TraceInfo, TraceLayout};
     use math::fields::f64::BaseElement;
+    use utils::{Deserializable, Serializable};
 
     #[test]
     fn trace_layout_to_elements() {
@@ -486,4 +585,44 @@ mod tests {
         let layout = TraceLayout::new(main_width, [aux_width], [aux_rands]);
         assert_eq!(expected, layout.to_elements());
     }
+
+    #[test]
+    fn trace_layout_with_preprocessed_columns() {
+        let layout = TraceLayout::new(20, [0], [0]);
+        let preprocessed = layout.clone().set_num_preprocessed_columns(3);
+        assert_eq!(3, preprocessed.num_preprocessed_columns());
+        assert_eq!(20, preprocessed.main_trace_width());
+        assert_eq!(2, preprocessed.num_trace_commitments());
+
+        // the number of preprocessed columns is appended to the elements of the layout
+        let mut expected = layout.to_elements();
+        expected.push(BaseElement::from(3u64));
+        assert_eq!(expected, preprocessed.to_elements());
+
+        let bytes = preprocessed.to_bytes();
+        assert_eq!(layout.to_bytes().len(), bytes.len());
+        assert_eq!(preprocessed, TraceLayout::read_from_bytes(&bytes).unwrap());
+    }
+
+    #[test]
+    #[should_panic]
+    fn trace_layout_all_columns_preprocessed() {
+        TraceLayout::new(20, [0], [0]).set_num_preprocessed_columns(20);
+    }
+
+    #[test]
+    fn wide_trace_layout_serialization() {
+        // layouts of the maximum width must survive a serialization round trip
+        let layout = TraceLayout::new(TraceInfo::MAX_TRACE_WIDTH - 300, [300], [1]);
+        assert_eq!(
+            layout,
+            TraceLayout::read_from_bytes(&layout.to_bytes()).unwrap()
+        );
+
+        let layout = TraceLayout::new(300, [0], [0]);
+        assert_eq!(
+            layout,
+            TraceLayout::read_from_bytes(&layout.to_bytes()).unwrap()
+        );
+    }
 }
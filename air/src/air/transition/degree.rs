@@ -104,20 +104,38 @@ impl TransitionConstraintDegree {
 
     /// Returns a minimum blowup factor needed to evaluate constraint of this degree.
     ///
+    /// Multiplication by a periodic column with cycle length $c$ contributes only
+    /// $\frac{n \cdot (c - 1)}{c}$ to the degree of the constraint polynomial (rather than a full
+    /// $n - 1$ contributed by a trace column), and this is taken into account when computing the
+    /// blowup factor.
+    ///
     /// This is guaranteed to be a power of two, greater than one.
     pub fn min_blowup_factor(&self) -> usize {
         // The blowup factor needs to be a power of two large enough to accommodate degree of
         // transition constraints defined by rational functions `C(x) / z(x)` where `C(x)` is the
         // constraint polynomial and `z(x)` is the transition constraint divisor.
         //
-        // Degree of `C(x)` is always smaller than or equal to `[self.base + self.cycles.len()] * [trace_length - 1]`.
-        // Degree of `z(x)` is `[trace_length - 1]`. Thus, the degree of `C(x) / z(x)` is
-        // `[self.base + self.cycles.len() - 1] * [trace_length - 1]` and the blowup factor needed
-        // to accommodate this degree can be estimated as `self.base + self.cycles.len() - 1`.
+        // Degree of `C(x)` is `self.base * [n - 1] + sum(n * [c_i - 1] / c_i)`, where `n` is the
+        // trace length and `c_i` are cycle lengths. Degree of `z(x)` is `[n - 1]`. Thus, the
+        // degree of `C(x) / z(x)` is at most `n * r + 1 - self.base`, where
+        // `r = [self.base - 1] + sum([c_i - 1] / c_i)`, and the blowup factor `k` needs to be
+        // the smallest integer such that `k * n >= n * r + 2 - self.base`. For `self.base > 1`
+        // this is `ceil(r)`, and for `self.base = 1` this is `floor(r) + 1`.
         //
         // For example, if degree of our constraints is 6, the blowup factor would need to be 8.
         // However, if the degree is 5, the blowup factor could be as small as 4.
-        let degree_bound = self.base + self.cycles.len() - 1;
+        let max_cycle = self.cycles.iter().copied().max().unwrap_or(1);
+        let scaled_bound = (self.base - 1) * max_cycle
+            + self
+                .cycles
+                .iter()
+                .map(|&cycle| (cycle - 1) * (max_cycle / cycle))
+                .sum::<usize>();
+        let degree_bound = if self.base > 1 {
+            (scaled_bound + max_cycle - 1) / max_cycle
+        } else {
+            scaled_bound / max_cycle + 1
+        };
         cmp::max(
             degree_bound.next_power_of_two(),
             ProofOptions::MIN_BLOWUP_FACTOR,
@@ -0,0 +1,522 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{EvaluationFrame, FieldElement, TransitionConstraintDegree, Vec};
+use core::ops;
+use math::StarkField;
+use utils::{Box, ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable};
+
+// CONSTANTS
+// ================================================================================================
+
+/// Maximum nesting depth of a deserialized expression; this prevents malformed inputs from
+/// exhausting the stack.
+const MAX_EXPR_DEPTH: usize = 256;
+
+// REGISTER
+// ================================================================================================
+
+/// A column of an evaluation frame referenced by an [Expr].
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Register {
+    /// The column with the specified index in the current row of the frame.
+    Current(usize),
+    /// The column with the specified index in the next row of the frame.
+    Next(usize),
+}
+
+impl Register {
+    /// Returns the index of the column referenced by this register.
+    pub fn column(&self) -> usize {
+        match self {
+            Self::Current(column) | Self::Next(column) => *column,
+        }
+    }
+}
+
+// EXPRESSION
+// ================================================================================================
+
+/// An arithmetic expression over the columns of an evaluation frame.
+///
+/// Expressions can be used to describe transition constraints at runtime (e.g., constraints
+/// loaded from a file produced by an external compiler) rather than implementing
+/// [Air::evaluate_transition()](crate::Air::evaluate_transition) by hand. A set of expressions is
+/// interpreted by [TransitionExpressions].
+///
+/// Expressions can be built directly from the variants of this enum, or combined via `+`, `-`,
+/// `*`, and unary `-` operators.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Expr<B: StarkField> {
+    /// Value of a column in the evaluation frame.
+    Var(Register),
+    /// A constant in the base field.
+    Const(B),
+    /// Sum of two expressions.
+    Add(Box<Expr<B>>, Box<Expr<B>>),
+    /// Product of two expressions.
+    Mul(Box<Expr<B>>, Box<Expr<B>>),
+    /// An expression raised to the specified power.
+    Pow(Box<Expr<B>>, u32),
+}
+
+impl<B: StarkField> Expr<B> {
+    // CONSTRUCTORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns an expression for the value of the specified column in the current row.
+    pub fn current(column: usize) -> Self {
+        Self::Var(Register::Current(column))
+    }
+
+    /// Returns an expression for the value of the specified column in the next row.
+    pub fn next(column: usize) -> Self {
+        Self::Var(Register::Next(column))
+    }
+
+    /// Returns this expression raised to the specified power.
+    pub fn pow(self, exponent: u32) -> Self {
+        Self::Pow(Box::new(self), exponent)
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the degree of this expression in the values of the frame columns.
+    ///
+    /// The degree is computed syntactically: the degree of a sum is the maximum of degrees of
+    /// its terms, and thus, the actual degree may be smaller if leading terms cancel out.
+    pub fn degree(&self) -> usize {
+        match self {
+            Self::Var(_) => 1,
+            Self::Const(_) => 0,
+            Self::Add(lhs, rhs) => lhs.degree().max(rhs.degree()),
+            Self::Mul(lhs, rhs) => lhs.degree() + rhs.degree(),
+            Self::Pow(base, exponent) => base.degree() * *exponent as usize,
+        }
+    }
+
+    /// Returns the number of columns an evaluation frame must have for this expression to be
+    /// evaluated over it; this is one more than the largest referenced column index, or zero if
+    /// no columns are referenced.
+    pub fn num_columns(&self) -> usize {
+        match self {
+            Self::Var(register) => register.column() + 1,
+            Self::Const(_) => 0,
+            Self::Add(lhs, rhs) | Self::Mul(lhs, rhs) => lhs.num_columns().max(rhs.num_columns()),
+            Self::Pow(base, _) => base.num_columns(),
+        }
+    }
+
+    // EVALUATION
+    // --------------------------------------------------------------------------------------------
+
+    /// Evaluates this expression over the specified frame.
+    ///
+    /// # Panics
+    /// Panics if the expression references a column which is not in the frame.
+    pub fn evaluate<E>(&self, frame: &EvaluationFrame<E>) -> E
+    where
+        E: FieldElement<BaseField = B>,
+    {
+        match self {
+            Self::Var(Register::Current(column)) => frame.current()[*column],
+            Self::Var(Register::Next(column)) => frame.next()[*column],
+            Self::Const(value) => E::from(*value),
+            Self::Add(lhs, rhs) => lhs.evaluate(frame) + rhs.evaluate(frame),
+            Self::Mul(lhs, rhs) => lhs.evaluate(frame) * rhs.evaluate(frame),
+            Self::Pow(base, exponent) => base.evaluate(frame).exp((*exponent).into()),
+        }
+    }
+
+    // COMPACTION
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns an equivalent expression with constant sub-expressions folded and trivial
+    /// operations removed.
+    ///
+    /// Specifically, operations over constants are replaced with their results, additions of
+    /// zero and multiplications by one are removed, multiplications by zero are replaced with
+    /// zero, and nested powers are merged when the product of exponents fits into 32 bits.
+    pub fn compact(self) -> Self {
+        match self {
+            Self::Var(_) | Self::Const(_) => self,
+            Self::Add(lhs, rhs) => match (lhs.compact(), rhs.compact()) {
+                (Self::Const(a), Self::Const(b)) => Self::Const(a + b),
+                (Self::Const(a), expr) | (expr, Self::Const(a)) if a == B::ZERO => expr,
+                (lhs, rhs) => Self::Add(Box::new(lhs), Box::new(rhs)),
+            },
+            Self::Mul(lhs, rhs) => match (lhs.compact(), rhs.compact()) {
+                (Self::Const(a), Self::Const(b)) => Self::Const(a * b),
+                (Self::Const(a), _) | (_, Self::Const(a)) if a == B::ZERO => Self::Const(B::ZERO),
+                (Self::Const(a), expr) | (expr, Self::Const(a)) if a == B::ONE => expr,
+                (lhs, rhs) => Self::Mul(Box::new(lhs), Box::new(rhs)),
+            },
+            Self::Pow(base, exponent) => match (base.compact(), exponent) {
+                (_, 0) => Self::Const(B::ONE),
+                (base, 1) => base,
+                (Self::Const(a), exponent) => Self::Const(a.exp(exponent.into())),
+                (Self::Pow(base, inner), exponent) => match inner.checked_mul(exponent) {
+                    Some(exponent) => Self::Pow(base, exponent),
+                    None => Self::Pow(Box::new(Self::Pow(base, inner)), exponent),
+                },
+                (base, exponent) => Self::Pow(Box::new(base), exponent),
+            },
+        }
+    }
+
+    // HELPER METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Reads an expression nested at the specified depth from the `source`.
+    fn read_nested<R: ByteReader>(
+        source: &mut R,
+        depth: usize,
+    ) -> Result<Self, DeserializationError> {
+        if depth > MAX_EXPR_DEPTH {
+            return Err(DeserializationError::InvalidValue(format!(
+                "expression nesting depth cannot exceed {MAX_EXPR_DEPTH}"
+            )));
+        }
+        match source.read_u8()? {
+            0 => Ok(Self::current(source.read_usize()?)),
+            1 => Ok(Self::next(source.read_usize()?)),
+            2 => Ok(Self::Const(B::read_from(source)?)),
+            3 => Ok(Self::Add(
+                Box::new(Self::read_nested(source, depth + 1)?),
+                Box::new(Self::read_nested(source, depth + 1)?),
+            )),
+            4 => Ok(Self::Mul(
+                Box::new(Self::read_nested(source, depth + 1)?),
+                Box::new(Self::read_nested(source, depth + 1)?),
+            )),
+            5 => Ok(Self::Pow(
+                Box::new(Self::read_nested(source, depth + 1)?),
+                source.read_u32()?,
+            )),
+            value => Err(DeserializationError::InvalidValue(format!(
+                "value {value} cannot be deserialized as an expression tag"
+            ))),
+        }
+    }
+}
+
+impl<B: StarkField> ops::Add for Expr<B> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Self::Add(Box::new(self), Box::new(rhs))
+    }
+}
+
+impl<B: StarkField> ops::Sub for Expr<B> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        self + -rhs
+    }
+}
+
+impl<B: StarkField> ops::Mul for Expr<B> {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        Self::Mul(Box::new(self), Box::new(rhs))
+    }
+}
+
+impl<B: StarkField> ops::Neg for Expr<B> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self::Mul(Box::new(Self::Const(-B::ONE)), Box::new(self))
+    }
+}
+
+impl<B: StarkField> Serializable for Expr<B> {
+    /// Serializes `self` and writes the resulting bytes into the `target`.
+    ///
+    /// Every node is encoded as a single-byte tag followed by its operands in prefix order.
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        match self {
+            Self::Var(Register::Current(column)) => {
+                target.write_u8(0);
+                target.write_usize(*column);
+            }
+            Self::Var(Register::Next(column)) => {
+                target.write_u8(1);
+                target.write_usize(*column);
+            }
+            Self::Const(value) => {
+                target.write_u8(2);
+                value.write_into(target);
+            }
+            Self::Add(lhs, rhs) => {
+                target.write_u8(3);
+                lhs.write_into(target);
+                rhs.write_into(target);
+            }
+            Self::Mul(lhs, rhs) => {
+                target.write_u8(4);
+                lhs.write_into(target);
+                rhs.write_into(target);
+            }
+            Self::Pow(base, exponent) => {
+                target.write_u8(5);
+                base.write_into(target);
+                target.write_u32(*exponent);
+            }
+        }
+    }
+}
+
+impl<B: StarkField> Deserializable for Expr<B> {
+    /// Reads an expression from the specified `source`.
+    ///
+    /// # Errors
+    /// Returns an error if a valid expression could not be read from the `source`, or if the
+    /// expression is nested more than 256 levels deep.
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        Self::read_nested(source, 0)
+    }
+}
+
+// TRANSITION EXPRESSIONS
+// ================================================================================================
+
+/// An interpreter of transition constraints described by a list of [Expr]s.
+///
+/// Every expression describes a single transition constraint which must evaluate to zero over
+/// all valid evaluation frames. Expressions are compacted when the interpreter is instantiated.
+///
+/// This can be used to define an AIR whose transition constraints are loaded at runtime: the
+/// AIR passes [TransitionExpressions::degrees()] to its [AirContext](crate::AirContext), and
+/// delegates [Air::evaluate_transition()](crate::Air::evaluate_transition) to
+/// [TransitionExpressions::evaluate()]. Periodic columns are not supported.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct TransitionExpressions<B: StarkField> {
+    constraints: Vec<Expr<B>>,
+}
+
+impl<B: StarkField> TransitionExpressions<B> {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a new interpreter for the specified transition constraints.
+    ///
+    /// # Panics
+    /// Panics if no constraints are provided, or if any of the constraints has degree zero after
+    /// compaction (i.e., does not depend on the values of the frame).
+    pub fn new(constraints: Vec<Expr<B>>) -> Self {
+        assert!(
+            !constraints.is_empty(),
+            "at least one transition constraint must be provided"
+        );
+        let constraints = constraints
+            .into_iter()
+            .map(Expr::compact)
+            .collect::<Vec<_>>();
+        for (i, constraint) in constraints.iter().enumerate() {
+            assert!(
+                constraint.degree() > 0,
+                "transition constraint {i} does not depend on the evaluation frame"
+            );
+        }
+        Self { constraints }
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the number of transition constraints described by this interpreter.
+    pub fn num_constraints(&self) -> usize {
+        self.constraints.len()
+    }
+
+    /// Returns the (compacted) expressions of transition constraints.
+    pub fn constraints(&self) -> &[Expr<B>] {
+        &self.constraints
+    }
+
+    /// Returns the number of columns an evaluation frame must have for the constraints to be
+    /// evaluated over it.
+    pub fn num_columns(&self) -> usize {
+        self.constraints
+            .iter()
+            .map(Expr::num_columns)
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Returns degree descriptors of the transition constraints.
+    pub fn degrees(&self) -> Vec<TransitionConstraintDegree> {
+        self.constraints
+            .iter()
+            .map(|constraint| TransitionConstraintDegree::new(constraint.degree()))
+            .collect()
+    }
+
+    // EVALUATION
+    // --------------------------------------------------------------------------------------------
+
+    /// Evaluates the transition constraints over the specified frame and writes the results into
+    /// `result`.
+    ///
+    /// # Panics
+    /// Panics if the length of `result` is not equal to the number of constraints, or if the
+    /// frame contains fewer columns than referenced by the constraints.
+    pub fn evaluate<E>(&self, frame: &EvaluationFrame<E>, result: &mut [E])
+    where
+        E: FieldElement<BaseField = B>,
+    {
+        assert_eq!(
+            self.constraints.len(),
+            result.len(),
+            "expected {} constraint evaluations, but result has length {}",
+            self.constraints.len(),
+            result.len()
+        );
+        for (constraint, result) in self.constraints.iter().zip(result.iter_mut()) {
+            *result = constraint.evaluate(frame);
+        }
+    }
+}
+
+impl<B: StarkField> Serializable for TransitionExpressions<B> {
+    /// Serializes `self` and writes the resulting bytes into the `target`.
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write_usize(self.constraints.len());
+        for constraint in self.constraints.iter() {
+            constraint.write_into(target);
+        }
+    }
+}
+
+impl<B: StarkField> Deserializable for TransitionExpressions<B> {
+    /// Reads transition constraint expressions from the specified `source`.
+    ///
+    /// # Errors
+    /// Returns an error if valid expressions could not be read from the `source`, if no
+    /// expressions were read, or if any of the expressions does not depend on the frame.
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let num_constraints = source.read_usize()?;
+        if num_constraints == 0 {
+            return Err(DeserializationError::InvalidValue(
+                "at least one transition constraint must be provided".into(),
+            ));
+        }
+        let mut constraints = Vec::new();
+        for i in 0..num_constraints {
+            let constraint = Expr::read_from(source)?.compact();
+            if constraint.degree() == 0 {
+                return Err(DeserializationError::InvalidValue(format!(
+                    "transition constraint {i} does not depend on the evaluation frame"
+                )));
+            }
+            constraints.push(constraint);
+        }
+        Ok(Self { constraints })
+    }
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::{Expr, Register, TransitionExpressions};
+    use crate::{EvaluationFrame, TransitionConstraintDegree};
+    use math::{fields::f128::BaseElement, FieldElement};
+    use utils::{Deserializable, Serializable};
+
+    type Ex = Expr<BaseElement>;
+
+    #[test]
+    fn expr_evaluate() {
+        // next[0] - (current[0]^2 + 3 * current[1])
+        let expr =
+            Ex::next(0) - (Ex::current(0).pow(2) + Ex::Const(BaseElement::new(3)) * Ex::current(1));
+        let frame = EvaluationFrame::from_rows(
+            vec![BaseElement::new(2), BaseElement::new(5)],
+            vec![BaseElement::new(19), BaseElement::new(0)],
+        );
+        assert_eq!(BaseElement::ZERO, expr.evaluate(&frame));
+        assert_eq!(2, expr.degree());
+        assert_eq!(2, expr.num_columns());
+        assert_eq!(0, Register::Next(0).column());
+    }
+
+    #[test]
+    fn expr_compact() {
+        let zero = Ex::Const(BaseElement::ZERO);
+        let one = Ex::Const(BaseElement::ONE);
+
+        assert_eq!(Ex::current(0), (Ex::current(0) + zero.clone()).compact());
+        assert_eq!(Ex::current(0), (one.clone() * Ex::current(0)).compact());
+        assert_eq!(zero, (Ex::current(0) * zero.clone()).compact());
+        assert_eq!(one, Ex::current(0).pow(0).compact());
+        assert_eq!(Ex::current(0), Ex::current(0).pow(1).compact());
+        assert_eq!(
+            Ex::current(0).pow(6),
+            Ex::current(0).pow(2).pow(3).compact()
+        );
+
+        // constant sub-expressions are folded
+        let expr = Ex::Const(BaseElement::new(2)).pow(3) + Ex::Const(BaseElement::new(1));
+        assert_eq!(Ex::Const(BaseElement::new(9)), expr.compact());
+
+        // compaction does not change the value of an expression, but may reduce its degree
+        let expr = Ex::next(1) * (Ex::current(0).pow(3) * zero + one) - Ex::current(1).pow(2);
+        let compacted = expr.clone().compact();
+        let frame = EvaluationFrame::from_rows(
+            vec![BaseElement::new(4), BaseElement::new(7)],
+            vec![BaseElement::new(11), BaseElement::new(13)],
+        );
+        assert_eq!(expr.evaluate(&frame), compacted.evaluate(&frame));
+        assert_eq!(4, expr.degree());
+        assert_eq!(2, compacted.degree());
+    }
+
+    #[test]
+    fn transition_expressions() {
+        // Fibonacci-style constraints over two columns
+        let constraints = TransitionExpressions::new(vec![
+            Ex::next(0) - (Ex::current(0) + Ex::current(1)),
+            Ex::next(1) - (Ex::current(1) + Ex::next(0)),
+        ]);
+        assert_eq!(2, constraints.num_constraints());
+        assert_eq!(2, constraints.num_columns());
+        assert_eq!(
+            vec![TransitionConstraintDegree::new(1); 2],
+            constraints.degrees()
+        );
+
+        let frame = EvaluationFrame::from_rows(
+            vec![BaseElement::new(1), BaseElement::new(1)],
+            vec![BaseElement::new(2), BaseElement::new(3)],
+        );
+        let mut result = [BaseElement::ONE; 2];
+        constraints.evaluate(&frame, &mut result);
+        assert_eq!([BaseElement::ZERO; 2], result);
+
+        // expressions survive serialization round-trip
+        let bytes = constraints.to_bytes();
+        assert_eq!(
+            constraints,
+            TransitionExpressions::read_from_bytes(&bytes).unwrap()
+        );
+
+        // unknown expression tag
+        let mut invalid = bytes;
+        invalid[8] = 6;
+        assert!(TransitionExpressions::<BaseElement>::read_from_bytes(&invalid).is_err());
+    }
+
+    #[test]
+    #[should_panic(expected = "transition constraint 0 does not depend on the evaluation frame")]
+    fn transition_expressions_constant_constraint() {
+        TransitionExpressions::new(vec![Ex::current(0) * Ex::Const(BaseElement::ZERO)]);
+    }
+}
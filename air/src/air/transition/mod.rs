@@ -11,6 +11,9 @@ pub use frame::EvaluationFrame;
 mod degree;
 pub use degree::TransitionConstraintDegree;
 
+mod expr;
+pub use expr::{Expr, Register, TransitionExpressions};
+
 // CONSTANTS
 // ================================================================================================
 
@@ -165,6 +168,24 @@ impl<E: FieldElement> TransitionConstraints<E> {
     /// divisor rather than dividing each individual $C(x)$ evaluation. This requires executing only
     /// one division at the end.
     pub fn combine_evaluations<F>(&self, main_evaluations: &[F], aux_evaluations: &[E], x: F) -> E
+    where
+        F: FieldElement<BaseField = E::BaseField>,
+        E: ExtensionOf<F>,
+    {
+        let result = self.merge_evaluations(main_evaluations, aux_evaluations, x);
+
+        // divide out the evaluation of divisor at x and return the result
+        let z = E::from(self.divisor.evaluate_at(x));
+        result / z
+    }
+
+    /// Computes a linear combination of all transition constraint evaluations in the same way
+    /// as [TransitionConstraints::combine_evaluations()], but does not divide the result by the
+    /// transition constraint divisor.
+    ///
+    /// This is useful when the transition divisor is evaluated together with other divisors (see
+    /// [ConstraintDivisor::evaluate_inverses_at()]).
+    pub fn merge_evaluations<F>(&self, main_evaluations: &[F], aux_evaluations: &[E], x: F) -> E
     where
         F: FieldElement<BaseField = E::BaseField>,
         E: ExtensionOf<F>,
@@ -183,9 +204,7 @@ impl<E: FieldElement> TransitionConstraints<E> {
             });
         }
 
-        // divide out the evaluation of divisor at x and return the result
-        let z = E::from(self.divisor.evaluate_at(x));
-        result / z
+        result
     }
 }
 
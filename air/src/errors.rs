@@ -4,11 +4,12 @@
 // LICENSE file in the root directory of this source tree.
 
 use core::fmt;
+use utils::{ErrorCategory, ErrorCode, ToErrorCode};
 
 // ASSERTION ERROR
 // ================================================================================================
 /// Represents an error returned during assertion evaluation.
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum AssertionError {
     /// This error occurs when an assertion is evaluated against an execution trace which does not
     /// contain a column specified by the assertion.
@@ -22,6 +23,13 @@ pub enum AssertionError {
     /// This error occurs when a `Sequence` assertion is placed against an execution trace with
     /// length which conflicts with the trace length implied by the assertion.
     TraceLengthNotExact(usize, usize),
+    /// This error occurs when a step counted from the end of an execution trace does not exist
+    /// in a trace with the specified original length.
+    StepFromEndOutOfBounds(usize, usize),
+    /// This error occurs when an assertion is placed against a cell of an execution trace which
+    /// is already covered by another assertion; the error contains the column and the first
+    /// step of the overlapping assertion.
+    Overlapping(usize, usize),
 }
 
 impl fmt::Display for AssertionError {
@@ -40,6 +48,26 @@ impl fmt::Display for AssertionError {
             Self::TraceLengthNotExact(expected, actual) => {
                 write!(f, "expected trace length to be exactly {expected}, but was {actual}")
             }
+            Self::StepFromEndOutOfBounds(offset, length) => {
+                write!(f, "step {offset} from the end does not exist in a trace of length {length}")
+            }
+            Self::Overlapping(column, step) => {
+                write!(f, "assertion against column {column} at step {step} overlaps with another assertion")
+            }
         }
     }
 }
+
+impl ToErrorCode for AssertionError {
+    fn error_code(&self) -> ErrorCode {
+        let index = match self {
+            Self::TraceWidthTooShort(..) => 1,
+            Self::TraceLengthNotPowerOfTwo(_) => 2,
+            Self::TraceLengthTooShort(..) => 3,
+            Self::TraceLengthNotExact(..) => 4,
+            Self::StepFromEndOutOfBounds(..) => 5,
+            Self::Overlapping(..) => 6,
+        };
+        ErrorCode::new(ErrorCategory::Air, index)
+    }
+}
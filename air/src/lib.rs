@@ -33,18 +33,20 @@
 #[macro_use]
 extern crate alloc;
 
+pub mod params;
 pub mod proof;
 
 mod errors;
 pub use errors::AssertionError;
 
 mod options;
-pub use options::{FieldExtension, ProofOptions};
+pub use options::{FieldExtension, GrindingFunction, ProofOptions, QuerySampling};
 
 mod air;
 pub use air::{
-    Air, AirContext, Assertion, AuxTraceRandElements, BoundaryConstraint, BoundaryConstraintGroup,
-    BoundaryConstraints, ConstraintCompositionCoefficients, ConstraintDivisor,
-    DeepCompositionCoefficients, EvaluationFrame, TraceInfo, TraceLayout,
-    TransitionConstraintDegree, TransitionConstraintGroup, TransitionConstraints,
+    Air, AirContext, Assertion, Assertions, AuxTraceRandElements, BoundaryConstraint,
+    BoundaryConstraintGroup, BoundaryConstraints, ConstraintCompositionCoefficients,
+    ConstraintDivisor, DeepCompositionCoefficients, EvaluationFrame, Expr, MultiInstanceAir,
+    MultiInstanceInputs, Register, TraceInfo, TraceLayout, TraceStep, TransitionConstraintDegree,
+    TransitionConstraintGroup, TransitionConstraints, TransitionExpressions,
 };
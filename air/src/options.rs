@@ -3,10 +3,12 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
+use crypto::{HashProofOfWork, MemoryHardProofOfWork, RandomCoin};
 use fri::FriOptions;
 use math::{StarkField, ToElements};
 use utils::{
-    collections::Vec, ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable,
+    collections::Vec, string::ToString, ByteReader, ByteWriter, Deserializable,
+    DeserializationError, Serializable,
 };
 
 // CONSTANTS
@@ -14,7 +16,7 @@ use utils::{
 
 // most of these constants are set so that values fit into a u8 integer.
 
-const MAX_NUM_QUERIES: usize = 255;
+const MAX_NUM_QUERIES: usize = 1024;
 
 const MIN_BLOWUP_FACTOR: usize = 2;
 const MAX_BLOWUP_FACTOR: usize = 128;
@@ -52,6 +54,42 @@ pub enum FieldExtension {
     Cubic = 3,
 }
 
+/// Defines how query positions are sampled from the low-degree extension domain.
+///
+/// With uniform sampling, all query positions are drawn uniformly at random from the entire LDE
+/// domain. With stratified sampling, the LDE domain is split into the specified number of equally
+/// sized contiguous segments, and queries are distributed among the segments in round-robin
+/// fashion; within each segment, positions are drawn uniformly at random. This guarantees that
+/// every segment of the domain is queried at least once, which is useful when commitments to the
+/// LDE domain are partitioned (e.g., among several machines).
+///
+/// In both cases, all drawn positions are distinct.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum QuerySampling {
+    /// Query positions are drawn uniformly at random from the entire LDE domain.
+    Uniform,
+    /// Query positions are drawn from the specified number of equally sized segments of the LDE
+    /// domain such that every segment is queried at least once.
+    Stratified(usize),
+}
+
+/// Defines the proof-of-work function used for query seed grinding.
+///
+/// With the hash-based function, checking a nonce requires a single hash function invocation,
+/// and thus, grinding can be efficiently parallelized on GPUs. The memory-hard function requires
+/// a table of hash digests to be built and randomly accessed for every nonce (see
+/// [MemoryHardProofOfWork](crypto::MemoryHardProofOfWork)); this makes GPU-accelerated grinding
+/// much less effective, but also makes grinding more expensive for an honest prover, and
+/// requires the verifier to build the same table once.
+#[repr(u8)]
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum GrindingFunction {
+    /// Nonces are checked by computing hash(`seed` || `nonce`).
+    Hash = 0,
+    /// Nonces are checked using a memory-hard function built from the hash function.
+    MemoryHard = 1,
+}
+
 /// STARK protocol parameters.
 ///
 /// These parameters have a direct impact on proof soundness, proof generation time, and proof
@@ -68,7 +106,8 @@ pub enum FieldExtension {
 ///    time decrease the number of queries in such a way that the proofs become smaller.
 /// 4. Grinding factor - higher values increase proof soundness, but also may increase proof
 ///    generation time. More precisely, conjectured proof soundness is bounded by
-///    `num_queries * log2(blowup_factor) + grinding_factor`.
+///    `num_queries * log2(blowup_factor) + grinding_factor`. The cost of grinding for a
+///    dishonest prover also depends on the [GrindingFunction].
 ///
 /// Another important parameter in defining STARK security level, which is not a part of [ProofOptions]
 /// is the hash function used in the protocol. The soundness of a STARK proof is limited by the
@@ -76,12 +115,17 @@ pub enum FieldExtension {
 /// with 128-bit collision resistance is used, soundness of a STARK proof cannot exceed 128 bits.
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub struct ProofOptions {
-    num_queries: u8,
+    num_queries: u16,
     blowup_factor: u16,
     grinding_factor: u8,
     field_extension: FieldExtension,
     fri_folding_factor: u8,
     fri_remainder_max_degree: u8,
+    domain_offset: Option<u64>,
+    query_sampling: QuerySampling,
+    fri_aggregate_layer_proofs: bool,
+    base_field_composition: bool,
+    grinding_function: GrindingFunction,
 }
 
 // PROOF OPTIONS IMPLEMENTATION
@@ -103,7 +147,7 @@ impl ProofOptions {
     ///
     /// # Panics
     /// Panics if:
-    /// - `num_queries` is zero or greater than 255.
+    /// - `num_queries` is zero or greater than 1024.
     /// - `blowup_factor` is smaller than 2, greater than 128, or is not a power of two.
     /// - `grinding_factor` is greater than 32.
     /// - `fri_folding_factor` is not 2, 4, 8, or 16.
@@ -141,15 +185,118 @@ impl ProofOptions {
         );
 
         ProofOptions {
-            num_queries: num_queries as u8,
+            num_queries: num_queries as u16,
             blowup_factor: blowup_factor as u16,
             grinding_factor: grinding_factor as u8,
             field_extension,
             fri_folding_factor: fri_folding_factor as u8,
             fri_remainder_max_degree: fri_remainder_max_degree as u8,
+            domain_offset: None,
+            query_sampling: QuerySampling::Uniform,
+            fri_aggregate_layer_proofs: false,
+            base_field_composition: false,
+            grinding_function: GrindingFunction::Hash,
         }
     }
 
+    /// Returns proof options intended for unit tests and CI runs.
+    ///
+    /// These options use 32 queries, blowup factor 8, no grinding, no field extension, FRI folding
+    /// factor 4, and FRI remainder of degree at most 31. Since no proof-of-work needs to be
+    /// computed, proofs for small traces are generated in milliseconds. Public coins of the prover
+    /// and the verifier are seeded only from the proof context and public inputs, and thus,
+    /// proofs generated with these options are fully deterministic.
+    ///
+    /// The resulting proofs provide low security, and these options must not be used in
+    /// production.
+    pub fn for_testing() -> ProofOptions {
+        ProofOptions::new(32, 8, 0, FieldExtension::None, 4, 31)
+    }
+
+    /// Sets the offset by which the low-degree extension domain is shifted in relation to the
+    /// trace domain.
+    ///
+    /// The offset is interpreted as an element of the base field of the computation. If this is
+    /// not set, the offset defaults to the primitive element of the base field. The offset must
+    /// not be in the low-degree extension domain; this is checked when an [AirContext] is
+    /// instantiated with these options.
+    ///
+    /// # Panics
+    /// Panics if `offset` is zero.
+    ///
+    /// [AirContext]: crate::AirContext
+    pub fn set_domain_offset(mut self, offset: u64) -> Self {
+        assert!(offset != 0, "domain offset cannot be zero");
+        self.domain_offset = Some(offset);
+        self
+    }
+
+    /// Sets the strategy for sampling query positions from the low-degree extension domain.
+    ///
+    /// If this is not set, query positions are sampled uniformly from the entire domain.
+    ///
+    /// # Panics
+    /// Panics if the sampling is stratified and the number of segments is zero, is not a power
+    /// of two, or is greater than the number of queries.
+    pub fn set_query_sampling(mut self, query_sampling: QuerySampling) -> Self {
+        if let QuerySampling::Stratified(num_segments) = query_sampling {
+            assert!(
+                num_segments.is_power_of_two(),
+                "number of query sampling segments must be a power of 2"
+            );
+            assert!(
+                num_segments <= self.num_queries(),
+                "number of query sampling segments cannot be greater than the number of queries {}, but was {}",
+                self.num_queries(),
+                num_segments
+            );
+        }
+        self.query_sampling = query_sampling;
+        self
+    }
+
+    /// Sets whether Merkle authentication paths for FRI layer queries are aggregated across all
+    /// FRI layers into a single multiproof.
+    ///
+    /// Aggregation makes proofs slightly smaller, but proofs with aggregated FRI layer proofs
+    /// cannot be converted into the serialization format of upstream Winterfell. If this is not
+    /// set, a separate batch Merkle proof is included in the proof for every FRI layer.
+    pub fn set_fri_layer_proof_aggregation(mut self, aggregate: bool) -> Self {
+        self.fri_aggregate_layer_proofs = aggregate;
+        self
+    }
+
+    /// Sets whether constraints are evaluated in the base field while only the constraint
+    /// composition polynomial, the DEEP composition polynomial, and FRI are computed in the
+    /// extension field.
+    ///
+    /// With base field composition, the main trace segment and evaluations of individual
+    /// constraints over it remain in the base field. Constraint composition coefficients and
+    /// random elements for auxiliary trace segments are drawn from the extension field specified
+    /// by [ProofOptions::field_extension()], and the base field constraint evaluations are combined
+    /// into extension field elements before the constraint composition polynomial is committed
+    /// to. Thus, security of the proof is the same as if all of these steps were performed in the
+    /// extension field.
+    ///
+    /// # Panics
+    /// Panics if `enabled` is true and the field extension of these options is `None`.
+    pub fn set_base_field_composition(mut self, enabled: bool) -> Self {
+        assert!(
+            !enabled || !self.field_extension.is_none(),
+            "base field composition requires a field extension"
+        );
+        self.base_field_composition = enabled;
+        self
+    }
+
+    /// Sets the proof-of-work function used for query seed grinding.
+    ///
+    /// If this is not set, nonces are checked with the hash-based function.
+    pub fn set_grinding_function(mut self, grinding_function: GrindingFunction) -> Self {
+        self.grinding_function = grinding_function;
+        self
+    }
+
     // PUBLIC ACCESSORS
     // --------------------------------------------------------------------------------------------
 
@@ -183,6 +330,11 @@ impl ProofOptions {
         self.grinding_factor as u32
     }
 
+    /// Returns the proof-of-work function used for query seed grinding.
+    pub fn grinding_function(&self) -> GrindingFunction {
+        self.grinding_function
+    }
+
     /// Specifies whether composition polynomial should be constructed in an extension field
     /// of STARK protocol.
     ///
@@ -195,16 +347,43 @@ impl ProofOptions {
     /// Returns the offset by which the low-degree extension domain is shifted in relation to the
     /// trace domain.
     ///
-    /// Currently, this is hard-coded to the primitive element of the underlying base field.
+    /// Unless a custom offset was set via [ProofOptions::set_domain_offset()], this is the
+    /// primitive element of the underlying base field.
     pub fn domain_offset<B: StarkField>(&self) -> B {
-        B::GENERATOR
+        match self.domain_offset {
+            Some(offset) => B::from(offset),
+            None => B::GENERATOR,
+        }
+    }
+
+    /// Returns the strategy for sampling query positions from the low-degree extension domain.
+    pub fn query_sampling(&self) -> QuerySampling {
+        self.query_sampling
+    }
+
+    /// Returns `true` if Merkle authentication paths for FRI layer queries are aggregated across
+    /// all FRI layers into a single multiproof.
+    pub fn aggregates_fri_layer_proofs(&self) -> bool {
+        self.fri_aggregate_layer_proofs
+    }
+
+    /// Returns `true` if constraints are evaluated in the base field while only the constraint
+    /// composition polynomial, the DEEP composition polynomial, and FRI are computed in the
+    /// extension field.
+    pub fn uses_base_field_composition(&self) -> bool {
+        self.base_field_composition
     }
 
     /// Returns options for FRI protocol instantiated with parameters from this proof options.
     pub fn to_fri_options(&self) -> FriOptions {
         let folding_factor = self.fri_folding_factor as usize;
         let remainder_max_degree = self.fri_remainder_max_degree as usize;
-        FriOptions::new(self.blowup_factor(), folding_factor, remainder_max_degree)
+        let options = FriOptions::new(self.blowup_factor(), folding_factor, remainder_max_degree)
+            .set_layer_proof_aggregation(self.fri_aggregate_layer_proofs);
+        match self.domain_offset {
+            Some(offset) => options.set_domain_offset(offset),
+            None => options,
+        }
     }
 }
 
@@ -214,25 +393,56 @@ impl<E: StarkField> ToElements<E> for ProofOptions {
         let mut buf = self.field_extension as u32;
         buf = (buf << 8) | self.fri_folding_factor as u32;
         buf = (buf << 8) | self.fri_remainder_max_degree as u32;
+        // log2 of the number of segments for stratified query sampling (offset by one) goes into
+        // the top byte, which is left as zero for uniform sampling
+        if let QuerySampling::Stratified(num_segments) = self.query_sampling {
+            buf |= (num_segments.ilog2() + 1) << 24;
+        }
+        // aggregation of FRI layer proofs is encoded in the most significant bit
+        if self.fri_aggregate_layer_proofs {
+            buf |= 1 << 31;
+        }
+        // base field composition is encoded in the second most significant bit
+        if self.base_field_composition {
+            buf |= 1 << 30;
+        }
+        // memory-hard grinding function is encoded in the third most significant bit
+        if self.grinding_function == GrindingFunction::MemoryHard {
+            buf |= 1 << 29;
+        }
 
-        vec![
+        let mut result = vec![
             E::from(buf),
             E::from(self.grinding_factor),
             E::from(self.blowup_factor),
             E::from(self.num_queries),
-        ]
+        ];
+
+        // a custom domain offset is bound to the proof context only when it is set so that
+        // encoding of proof options with the default offset remains unchanged
+        if let Some(offset) = self.domain_offset {
+            result.push(E::from(offset));
+        }
+
+        result
     }
 }
 
 impl Serializable for ProofOptions {
     /// Serializes `self` and writes the resulting bytes into the `target`.
     fn write_into<W: ByteWriter>(&self, target: &mut W) {
-        target.write_u8(self.num_queries);
+        target.write_u16(self.num_queries);
         target.write_u16(self.blowup_factor);
         target.write_u8(self.grinding_factor);
         target.write(self.field_extension);
         target.write_u8(self.fri_folding_factor);
         target.write_u8(self.fri_remainder_max_degree);
+        // the default domain offset is encoded as 0
+        target.write_u64(self.domain_offset.unwrap_or(0));
+        target.write(self.query_sampling);
+        target.write_bool(self.fri_aggregate_layer_proofs);
+        target.write_bool(self.base_field_composition);
+        target.write(self.grinding_function);
     }
 }
 
@@ -242,14 +452,151 @@ impl Deserializable for ProofOptions {
     /// # Errors
     /// Returns an error of a valid proof options could not be read from the specified `source`.
     fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
-        Ok(ProofOptions::new(
-            source.read_u8()? as usize,
-            source.read_u16()? as usize,
-            source.read_u8()? as u32,
-            FieldExtension::read_from(source)?,
-            source.read_u8()? as usize,
-            source.read_u8()? as usize,
-        ))
+        // read and validate all values before instantiating the options so that malformed
+        // inputs result in errors rather than panics
+        let num_queries = source.read_u16()? as usize;
+        if num_queries == 0 || num_queries > MAX_NUM_QUERIES {
+            return Err(DeserializationError::InvalidValue(format!(
+                "number of queries must be between 1 and {MAX_NUM_QUERIES}, but was {num_queries}"
+            )));
+        }
+
+        let blowup_factor = source.read_u16()? as usize;
+        if !blowup_factor.is_power_of_two()
+            || !(MIN_BLOWUP_FACTOR..=MAX_BLOWUP_FACTOR).contains(&blowup_factor)
+        {
+            return Err(DeserializationError::InvalidValue(format!(
+                "blowup factor must be a power of 2 between {MIN_BLOWUP_FACTOR} and {MAX_BLOWUP_FACTOR}, but was {blowup_factor}"
+            )));
+        }
+
+        let grinding_factor = source.read_u8()? as u32;
+        if grinding_factor > MAX_GRINDING_FACTOR {
+            return Err(DeserializationError::InvalidValue(format!(
+                "grinding factor cannot be greater than {MAX_GRINDING_FACTOR}, but was {grinding_factor}"
+            )));
+        }
+
+        let field_extension = FieldExtension::read_from(source)?;
+
+        let fri_folding_factor = source.read_u8()? as usize;
+        if !fri_folding_factor.is_power_of_two()
+            || !(FRI_MIN_FOLDING_FACTOR..=FRI_MAX_FOLDING_FACTOR).contains(&fri_folding_factor)
+        {
+            return Err(DeserializationError::InvalidValue(format!(
+                "FRI folding factor must be a power of 2 between {FRI_MIN_FOLDING_FACTOR} and {FRI_MAX_FOLDING_FACTOR}, but was {fri_folding_factor}"
+            )));
+        }
+
+        let fri_remainder_max_degree = source.read_u8()? as usize;
+        if !(fri_remainder_max_degree + 1).is_power_of_two() {
+            return Err(DeserializationError::InvalidValue(format!(
+                "FRI polynomial remainder degree must be one less than a power of two, but was {fri_remainder_max_degree}"
+            )));
+        }
+
+        let options = ProofOptions::new(
+            num_queries,
+            blowup_factor,
+            grinding_factor,
+            field_extension,
+            fri_folding_factor,
+            fri_remainder_max_degree,
+        );
+
+        let options = match source.read_u64()? {
+            0 => options,
+            offset => options.set_domain_offset(offset),
+        };
+
+        let query_sampling = QuerySampling::read_from(source)?;
+        if let QuerySampling::Stratified(num_segments) = query_sampling {
+            if !num_segments.is_power_of_two() || num_segments > num_queries {
+                return Err(DeserializationError::InvalidValue(format!(
+                    "number of query sampling segments must be a power of 2 not greater than {num_queries}, but was {num_segments}"
+                )));
+            }
+        }
+
+        let fri_aggregate_layer_proofs = source.read_bool()?;
+
+        let base_field_composition = source.read_bool()?;
+        if base_field_composition && field_extension.is_none() {
+            return Err(DeserializationError::InvalidValue(
+                "base field composition requires a field extension".to_string(),
+            ));
+        }
+
+        let grinding_function = GrindingFunction::read_from(source)?;
+
+        Ok(options
+            .set_query_sampling(query_sampling)
+            .set_fri_layer_proof_aggregation(fri_aggregate_layer_proofs)
+            .set_base_field_composition(base_field_composition)
+            .set_grinding_function(grinding_function))
+    }
+}
+
+// QUERY SAMPLING IMPLEMENTATION
+// ================================================================================================
+
+impl Serializable for QuerySampling {
+    /// Serializes `self` and writes the resulting bytes into the `target`.
+    ///
+    /// Uniform sampling is encoded as 0, and stratified sampling is encoded as the number of
+    /// segments.
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        match self {
+            Self::Uniform => target.write_u16(0),
+            Self::Stratified(num_segments) => target.write_u16(*num_segments as u16),
+        }
+    }
+}
+
+impl Deserializable for QuerySampling {
+    /// Reads a query sampling strategy from the specified `source`.
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        match source.read_u16()? {
+            0 => Ok(QuerySampling::Uniform),
+            num_segments => Ok(QuerySampling::Stratified(num_segments as usize)),
+        }
+    }
+}
+
+// GRINDING FUNCTION IMPLEMENTATION
+// ================================================================================================
+
+impl GrindingFunction {
+    /// Evaluates this proof-of-work function for the current seed of the `public_coin` and the
+    /// specified `nonce`, and returns the number of leading zeros in the result.
+    ///
+    /// Returns None if the function cannot be evaluated using the `public_coin` (see
+    /// [RandomCoin::check_proof_of_work()]).
+    pub fn check_nonce<R: RandomCoin>(&self, public_coin: &R, nonce: u64) -> Option<u32> {
+        match self {
+            Self::Hash => public_coin.check_proof_of_work::<HashProofOfWork>(nonce),
+            Self::MemoryHard => public_coin.check_proof_of_work::<MemoryHardProofOfWork>(nonce),
+        }
+    }
+}
+
+impl Serializable for GrindingFunction {
+    /// Serializes `self` and writes the resulting bytes into the `target`.
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write_u8(*self as u8);
+    }
+}
+
+impl Deserializable for GrindingFunction {
+    /// Reads a grinding function enum from the specified `source`.
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        match source.read_u8()? {
+            0 => Ok(GrindingFunction::Hash),
+            1 => Ok(GrindingFunction::MemoryHard),
+            value => Err(DeserializationError::InvalidValue(format!(
+                "value {value} cannot be deserialized as GrindingFunction enum"
+            ))),
+        }
     }
 }
 
@@ -298,8 +645,9 @@ impl Deserializable for FieldExtension {
 
 #[cfg(test)]
 mod tests {
-    use super::{FieldExtension, ProofOptions, ToElements};
-    use math::fields::f64::BaseElement;
+    use super::{FieldExtension, GrindingFunction, ProofOptions, QuerySampling, ToElements};
+    use math::{fields::f64::BaseElement, StarkField};
+    use utils::{Deserializable, Serializable, SliceReader};
 
     #[test]
     fn proof_options_to_elements() {
@@ -318,7 +666,7 @@ mod tests {
         ]);
         let expected = vec![
             BaseElement::from(ext_fri),
-            BaseElement::from(grinding_factor as u32),
+            BaseElement::from(grinding_factor),
             BaseElement::from(blowup_factor as u32),
             BaseElement::from(num_queries as u32),
         ];
@@ -333,4 +681,212 @@ mod tests {
         );
         assert_eq!(expected, options.to_elements());
     }
+
+    #[test]
+    fn proof_options_domain_offset() {
+        let options = ProofOptions::new(30, 8, 20, FieldExtension::None, 8, 127);
+        assert_eq!(
+            BaseElement::GENERATOR,
+            options.domain_offset::<BaseElement>()
+        );
+        let default_elements: Vec<BaseElement> = options.to_elements();
+
+        let options = options.set_domain_offset(11);
+        assert_eq!(
+            BaseElement::from(11u8),
+            options.domain_offset::<BaseElement>()
+        );
+        assert_eq!(
+            BaseElement::from(11u8),
+            options.to_fri_options().domain_offset::<BaseElement>()
+        );
+
+        // custom offset is appended to the default encoding
+        let mut expected = default_elements;
+        expected.push(BaseElement::from(11u8));
+        assert_eq!(expected, options.to_elements());
+
+        // custom offset survives serialization round-trip
+        let bytes = options.to_bytes();
+        let mut reader = SliceReader::new(&bytes);
+        assert_eq!(options, ProofOptions::read_from(&mut reader).unwrap());
+    }
+
+    #[test]
+    fn proof_options_query_sampling() {
+        let options = ProofOptions::new(30, 8, 20, FieldExtension::None, 8, 127);
+        assert_eq!(QuerySampling::Uniform, options.query_sampling());
+        let default_elements: Vec<BaseElement> = options.to_elements();
+
+        let options = options.set_query_sampling(QuerySampling::Stratified(4));
+        assert_eq!(QuerySampling::Stratified(4), options.query_sampling());
+
+        // log2 of the number of segments plus one is encoded in the top byte of the first element
+        let mut expected = default_elements;
+        expected[0] += BaseElement::from(3u32 << 24);
+        assert_eq!(expected, options.to_elements());
+
+        // query sampling survives serialization round-trip
+        let bytes = options.to_bytes();
+        let mut reader = SliceReader::new(&bytes);
+        assert_eq!(options, ProofOptions::read_from(&mut reader).unwrap());
+
+        // number of segments which is not a power of two; the number of segments is followed by
+        // the FRI layer proof aggregation and base field composition flags, and the grinding
+        // function
+        let mut invalid = bytes;
+        let last = invalid.len() - 5;
+        invalid[last] = 3;
+        assert!(ProofOptions::read_from(&mut SliceReader::new(&invalid)).is_err());
+    }
+
+    #[test]
+    fn proof_options_fri_layer_proof_aggregation() {
+        let options = ProofOptions::new(30, 8, 20, FieldExtension::None, 8, 127);
+        assert!(!options.aggregates_fri_layer_proofs());
+        assert!(!options.to_fri_options().aggregates_layer_proofs());
+        let default_elements: Vec<BaseElement> = options.to_elements();
+
+        let options = options.set_fri_layer_proof_aggregation(true);
+        assert!(options.aggregates_fri_layer_proofs());
+        assert!(options.to_fri_options().aggregates_layer_proofs());
+
+        // aggregation is encoded in the most significant bit of the first element
+        let mut expected = default_elements;
+        expected[0] += BaseElement::from(1u32 << 31);
+        assert_eq!(expected, options.to_elements());
+
+        // aggregation survives serialization round-trip
+        let bytes = options.to_bytes();
+        let mut reader = SliceReader::new(&bytes);
+        assert_eq!(options, ProofOptions::read_from(&mut reader).unwrap());
+    }
+
+    #[test]
+    fn proof_options_base_field_composition() {
+        let options = ProofOptions::new(30, 8, 20, FieldExtension::Quadratic, 8, 127);
+        assert!(!options.uses_base_field_composition());
+        let default_elements: Vec<BaseElement> = options.to_elements();
+
+        let options = options.set_base_field_composition(true);
+        assert!(options.uses_base_field_composition());
+
+        // base field composition is encoded in the second most significant bit of the first
+        // element
+        let mut expected = default_elements;
+        expected[0] += BaseElement::from(1u32 << 30);
+        assert_eq!(expected, options.to_elements());
+
+        // base field composition survives serialization round-trip
+        let bytes = options.to_bytes();
+        let mut reader = SliceReader::new(&bytes);
+        assert_eq!(options, ProofOptions::read_from(&mut reader).unwrap());
+
+        // base field composition without a field extension
+        let mut invalid = ProofOptions::new(30, 8, 20, FieldExtension::None, 8, 127).to_bytes();
+        let last = invalid.len() - 2;
+        invalid[last] = 1;
+        assert!(ProofOptions::read_from(&mut SliceReader::new(&invalid)).is_err());
+    }
+
+    #[test]
+    fn proof_options_grinding_function() {
+        let options = ProofOptions::new(30, 8, 20, FieldExtension::None, 8, 127);
+        assert_eq!(GrindingFunction::Hash, options.grinding_function());
+        let default_elements: Vec<BaseElement> = options.to_elements();
+
+        let options = options.set_grinding_function(GrindingFunction::MemoryHard);
+        assert_eq!(GrindingFunction::MemoryHard, options.grinding_function());
+
+        // memory-hard grinding function is encoded in the third most significant bit of the
+        // first element
+        let mut expected = default_elements;
+        expected[0] += BaseElement::from(1u32 << 29);
+        assert_eq!(expected, options.to_elements());
+
+        // grinding function survives serialization round-trip
+        let bytes = options.to_bytes();
+        let mut reader = SliceReader::new(&bytes);
+        assert_eq!(options, ProofOptions::read_from(&mut reader).unwrap());
+
+        // unknown grinding function
+        let mut invalid = bytes;
+        *invalid.last_mut().unwrap() = 2;
+        assert!(ProofOptions::read_from(&mut SliceReader::new(&invalid)).is_err());
+    }
+
+    #[test]
+    #[should_panic(expected = "base field composition requires a field extension")]
+    fn proof_options_base_field_composition_without_extension() {
+        ProofOptions::new(30, 8, 20, FieldExtension::None, 8, 127).set_base_field_composition(true);
+    }
+
+    #[test]
+    fn proof_options_for_testing() {
+        let options = ProofOptions::for_testing();
+        assert_eq!(0, options.grinding_factor());
+        assert_eq!(8, options.blowup_factor());
+        assert_eq!(FieldExtension::None, options.field_extension());
+        assert_eq!(options, ProofOptions::for_testing());
+    }
+
+    #[test]
+    fn proof_options_many_queries() {
+        let options = ProofOptions::new(300, 8, 20, FieldExtension::None, 8, 127)
+            .set_query_sampling(QuerySampling::Stratified(256));
+        assert_eq!(300, options.num_queries());
+
+        let elements: Vec<BaseElement> = options.to_elements();
+        assert_eq!(BaseElement::from(300u32), elements[3]);
+
+        // options survive serialization round-trip without truncation
+        let bytes = options.to_bytes();
+        let mut reader = SliceReader::new(&bytes);
+        assert_eq!(options, ProofOptions::read_from(&mut reader).unwrap());
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "number of query sampling segments cannot be greater than the number of queries 30, but was 32"
+    )]
+    fn proof_options_query_sampling_too_many_segments() {
+        ProofOptions::new(30, 8, 20, FieldExtension::None, 8, 127)
+            .set_query_sampling(QuerySampling::Stratified(32));
+    }
+
+    #[test]
+    fn proof_options_read_invalid() {
+        let options = ProofOptions::new(30, 8, 20, FieldExtension::None, 8, 127);
+        let bytes = options.to_bytes();
+
+        // zero queries
+        let mut invalid = bytes.clone();
+        invalid[0] = 0;
+        assert!(ProofOptions::read_from(&mut SliceReader::new(&invalid)).is_err());
+
+        // too many queries
+        let mut invalid = bytes.clone();
+        invalid[..2].copy_from_slice(&1025u16.to_le_bytes());
+        assert!(ProofOptions::read_from(&mut SliceReader::new(&invalid)).is_err());
+
+        // blowup factor which is not a power of two
+        let mut invalid = bytes.clone();
+        invalid[2] = 3;
+        assert!(ProofOptions::read_from(&mut SliceReader::new(&invalid)).is_err());
+
+        // grinding factor which is too large
+        let mut invalid = bytes.clone();
+        invalid[4] = 33;
+        assert!(ProofOptions::read_from(&mut SliceReader::new(&invalid)).is_err());
+
+        // unsupported FRI folding factor
+        let mut invalid = bytes.clone();
+        invalid[6] = 32;
+        assert!(ProofOptions::read_from(&mut SliceReader::new(&invalid)).is_err());
+
+        // FRI remainder degree which is not one less than a power of two
+        let mut invalid = bytes;
+        invalid[7] = 100;
+        assert!(ProofOptions::read_from(&mut SliceReader::new(&invalid)).is_err());
+    }
 }
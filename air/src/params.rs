@@ -0,0 +1,232 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+//! Vetted presets of STARK protocol parameters.
+//!
+//! Picking [ProofOptions] by hand is error-prone: it is easy to end up with a combination of
+//! parameters which provides less security than intended, or with one which wastes prover time
+//! and proof size on security that is never realized. The presets in this module derive all
+//! parameters from the base field of the computation and a target security level.
+//!
+//! # Soundness accounting
+//! All presets target *conjectured* security as estimated by
+//! [StarkProof::security_level()](crate::proof::StarkProof::security_level), which is the minimum
+//! of:
+//! * Field security - `field_bits - log2(trace_length) - 1`, where `field_bits` is the size of
+//!   the field (or its extension) from which random values are drawn. The smallest field
+//!   extension is chosen such that this bound meets the target for traces of up to
+//!   2<sup>30</sup> steps.
+//! * Query security - `num_queries * log2(blowup_factor) + grinding_factor - 1`. The grinding
+//!   factor contributes only when queries alone provide at least 80 bits, and the number of
+//!   queries is chosen accordingly.
+//!
+//! Security of a proof is also bounded by the collision resistance of the hash function used by
+//! the protocol, which is not a part of [ProofOptions]. For example, a 192-bit preset used with a
+//! hash function with 128-bit collision resistance results in proofs with 128-bit security.
+
+use crate::{proof::GRINDING_CONTRIBUTION_FLOOR, FieldExtension, ProofOptions};
+use math::StarkField;
+
+// CONSTANTS
+// ================================================================================================
+
+/// Log2 of the longest execution trace for which presets guarantee field security.
+const MAX_TRACE_LENGTH_LOG2: u32 = 30;
+
+/// Smallest target security level supported by the presets.
+const MIN_SECURITY_LEVEL: u32 = 64;
+
+/// Largest target security level supported by the presets.
+const MAX_SECURITY_LEVEL: u32 = 256;
+
+// PRESET
+// ================================================================================================
+
+/// A named trade-off between proof generation time, proof size, and verification cost.
+///
+/// All presets provide the same target security level; they differ only in how this security
+/// level is achieved.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Preset {
+    /// Minimizes proof generation time: blowup factor 8, grinding factor 16, FRI folding factor
+    /// 8, and FRI remainder of degree at most 31.
+    Fast,
+    /// A middle ground between proof generation time and proof size: blowup factor 16, grinding
+    /// factor 20, FRI folding factor 8, and FRI remainder of degree at most 31.
+    Balanced,
+    /// Minimizes the cost of verifying proofs inside another proof: blowup factor 8, no
+    /// grinding, FRI folding factor 4, and FRI remainder of degree at most 7. Using an algebraic
+    /// hash function (e.g., Rescue Prime) is recommended with this preset.
+    RecursionFriendly,
+    /// Minimizes proof size: blowup factor 32, grinding factor 24, FRI folding factor 16, and FRI
+    /// remainder of degree at most 63.
+    SmallProof,
+}
+
+impl Preset {
+    // CONSTANTS
+    // --------------------------------------------------------------------------------------------
+
+    /// All available presets.
+    pub const ALL: [Preset; 4] = [
+        Preset::Fast,
+        Preset::Balanced,
+        Preset::RecursionFriendly,
+        Preset::SmallProof,
+    ];
+
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a preset with the specified name, or None if no such preset exists.
+    ///
+    /// Valid names are `fast`, `balanced`, `recursion_friendly`, and `small_proof`.
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .iter()
+            .find(|preset| preset.name() == name)
+            .copied()
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the name of this preset.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Fast => "fast",
+            Self::Balanced => "balanced",
+            Self::RecursionFriendly => "recursion_friendly",
+            Self::SmallProof => "small_proof",
+        }
+    }
+
+    /// Returns proof options which provide the specified conjectured `security_level` (in bits)
+    /// for computations over the base field `B`.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * `security_level` is smaller than 64 or greater than 256.
+    /// * The specified security level cannot be achieved with any of the supported extensions
+    ///   of field `B`.
+    pub fn options<B: StarkField>(&self, security_level: u32) -> ProofOptions {
+        assert!(
+            (MIN_SECURITY_LEVEL..=MAX_SECURITY_LEVEL).contains(&security_level),
+            "security level must be between {MIN_SECURITY_LEVEL} and {MAX_SECURITY_LEVEL} bits, but was {security_level}"
+        );
+
+        let (blowup_factor, grinding_factor, fri_folding_factor, fri_remainder_max_degree) =
+            match self {
+                Self::Fast => (8, 16, 8, 31),
+                Self::Balanced => (16, 20, 8, 31),
+                Self::RecursionFriendly => (8, 0, 4, 7),
+                Self::SmallProof => (32, 24, 16, 63),
+            };
+
+        ProofOptions::new(
+            get_num_queries(security_level, blowup_factor, grinding_factor),
+            blowup_factor,
+            grinding_factor,
+            get_field_extension(security_level, B::MODULUS_BITS),
+            fri_folding_factor,
+            fri_remainder_max_degree,
+        )
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns the smallest number of queries for which query security (including grinding) is
+/// greater than the target security level.
+fn get_num_queries(security_level: u32, blowup_factor: usize, grinding_factor: u32) -> usize {
+    // queries alone must provide enough security for grinding to count
+    let target = security_level + 1;
+    let query_bits =
+        (target - grinding_factor.min(target)).max(GRINDING_CONTRIBUTION_FLOOR.min(target));
+    let security_per_query = blowup_factor.ilog2();
+    ((query_bits + security_per_query - 1) / security_per_query) as usize
+}
+
+/// Returns the smallest field extension for which field security is greater than the target
+/// security level for all traces of up to 2^MAX_TRACE_LENGTH_LOG2 steps.
+fn get_field_extension(security_level: u32, base_field_bits: u32) -> FieldExtension {
+    let required_bits = security_level + MAX_TRACE_LENGTH_LOG2 + 1;
+    [
+        FieldExtension::None,
+        FieldExtension::Quadratic,
+        FieldExtension::Cubic,
+    ]
+    .into_iter()
+    .find(|extension| base_field_bits * extension.degree() >= required_bits)
+    .unwrap_or_else(|| {
+        panic!(
+            "security level of {security_level} bits cannot be achieved for a {base_field_bits}-bit field"
+        )
+    })
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::{Preset, MAX_TRACE_LENGTH_LOG2};
+    use crate::{proof::get_conjectured_security, FieldExtension};
+    use math::{
+        fields::{f128, f62, f64},
+        StarkField,
+    };
+
+    #[test]
+    fn preset_names() {
+        for preset in Preset::ALL {
+            assert_eq!(Some(preset), Preset::from_name(preset.name()));
+        }
+        assert_eq!(None, Preset::from_name("insecure"));
+    }
+
+    #[test]
+    fn preset_security() {
+        for preset in Preset::ALL {
+            for security_level in [80, 96, 100, 128] {
+                check_security::<f62::BaseElement>(preset, security_level);
+                check_security::<f64::BaseElement>(preset, security_level);
+                check_security::<f128::BaseElement>(preset, security_level);
+            }
+        }
+    }
+
+    #[test]
+    fn preset_field_extension() {
+        let options = Preset::Fast.options::<f128::BaseElement>(96);
+        assert_eq!(FieldExtension::None, options.field_extension());
+        let options = Preset::Fast.options::<f128::BaseElement>(128);
+        assert_eq!(FieldExtension::Quadratic, options.field_extension());
+        let options = Preset::Fast.options::<f64::BaseElement>(96);
+        assert_eq!(FieldExtension::Quadratic, options.field_extension());
+        let options = Preset::Fast.options::<f64::BaseElement>(128);
+        assert_eq!(FieldExtension::Cubic, options.field_extension());
+    }
+
+    #[test]
+    #[should_panic(expected = "security level of 256 bits cannot be achieved for a 62-bit field")]
+    fn preset_unachievable_security() {
+        Preset::Balanced.options::<f62::BaseElement>(256);
+    }
+
+    fn check_security<B: StarkField>(preset: Preset, security_level: u32) {
+        let options = preset.options::<B>(security_level);
+        let trace_length = 1u64 << MAX_TRACE_LENGTH_LOG2;
+        let security = get_conjectured_security(&options, B::MODULUS_BITS, trace_length, 256);
+        assert!(
+            security >= security_level,
+            "{} preset provides {} instead of {} bits",
+            preset.name(),
+            security,
+            security_level
+        );
+    }
+}
@@ -89,8 +89,7 @@ impl Commitments {
 impl Serializable for Commitments {
     /// Serializes `self` and writes the resulting bytes into the `target`.
     fn write_into<W: ByteWriter>(&self, target: &mut W) {
-        assert!(self.0.len() < u16::MAX as usize);
-        target.write_u16(self.0.len() as u16);
+        target.write_usize(self.0.len());
         target.write_bytes(&self.0);
     }
 }
@@ -102,7 +101,7 @@ impl Deserializable for Commitments {
     /// Returns an error of a valid Commitments struct could not be read from the specified
     /// `source`.
     fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
-        let num_bytes = source.read_u16()? as usize;
+        let num_bytes = source.read_usize()?;
         let result = source.read_vec(num_bytes)?;
         Ok(Commitments(result))
     }
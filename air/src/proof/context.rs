@@ -3,7 +3,7 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use crate::{ProofOptions, TraceInfo, TraceLayout};
+use crate::{air::get_lde_domain_size, ProofOptions, TraceInfo, TraceLayout};
 use math::{StarkField, ToElements};
 use utils::{
     collections::Vec, string::ToString, ByteReader, ByteWriter, Deserializable,
@@ -17,6 +17,7 @@ use utils::{
 pub struct Context {
     trace_layout: TraceLayout,
     trace_length: usize,
+    original_trace_length: usize,
     trace_meta: Vec<u8>,
     field_modulus_bytes: Vec<u8>,
     options: ProofOptions,
@@ -31,6 +32,7 @@ impl Context {
         Context {
             trace_layout: trace_info.layout().clone(),
             trace_length: trace_info.length(),
+            original_trace_length: trace_info.original_length(),
             trace_meta: trace_info.meta().to_vec(),
             field_modulus_bytes: B::get_modulus_le_bytes(),
             options,
@@ -51,6 +53,13 @@ impl Context {
         self.trace_length
     }
 
+    /// Returns length of the execution trace before it was padded to a power of two.
+    ///
+    /// If the trace was not padded, this is the same as [Context::trace_length()].
+    pub fn original_trace_length(&self) -> usize {
+        self.original_trace_length
+    }
+
     /// Returns execution trace info for the computation described by this context.
     pub fn get_trace_info(&self) -> TraceInfo {
         TraceInfo::new_multi_segment(
@@ -58,6 +67,7 @@ impl Context {
             self.trace_length(),
             self.trace_meta.clone(),
         )
+        .set_original_length(self.original_trace_length)
     }
 
     /// Returns the size of the LDE domain for the computation described by this context.
@@ -104,6 +114,7 @@ impl<E: StarkField> ToElements<E> for Context {
     /// - blowup factor [1 element].
     /// - number of queries [1 element].
     /// - trace length [1 element].
+    /// - original trace length [1 element], only if the trace was padded.
     /// - trace metadata [0 or more elements].
     fn to_elements(&self) -> Vec<E> {
         // convert trace layout
@@ -118,6 +129,9 @@ impl<E: StarkField> ToElements<E> for Context {
         // convert proof options and trace length to elements
         result.append(&mut self.options.to_elements());
         result.push(E::from(self.trace_length as u64));
+        if self.original_trace_length != self.trace_length {
+            result.push(E::from(self.original_trace_length as u64));
+        }
 
         // convert trace metadata to elements; this is done by breaking trace metadata into chunks
         // of bytes which are slightly smaller than the number of bytes needed to encode a field
@@ -137,6 +151,7 @@ impl Serializable for Context {
     fn write_into<W: ByteWriter>(&self, target: &mut W) {
         self.trace_layout.write_into(target);
         target.write_u8(self.trace_length.ilog2() as u8); // store as power of two
+        target.write_u64(self.original_trace_length as u64);
         target.write_u16(self.trace_meta.len() as u16);
         target.write_bytes(&self.trace_meta);
         assert!(self.field_modulus_bytes.len() < u8::MAX as usize);
@@ -164,8 +179,24 @@ impl Deserializable for Context {
                 trace_length
             )));
         }
+        if trace_length as u32 >= usize::BITS {
+            return Err(DeserializationError::InvalidValue(format!(
+                "trace length cannot be greater than or equal to 2^{}, but was 2^{}",
+                usize::BITS,
+                trace_length
+            )));
+        }
         let trace_length = 2_usize.pow(trace_length as u32);
 
+        // read and validate original trace length
+        let original_trace_length = source.read_u64()?;
+        if original_trace_length == 0 || original_trace_length > trace_length as u64 {
+            return Err(DeserializationError::InvalidValue(format!(
+                "original trace length must be between 1 and {trace_length}, but was {original_trace_length}"
+            )));
+        }
+        let original_trace_length = original_trace_length as usize;
+
         // read trace metadata
         let num_meta_bytes = source.read_u16()? as usize;
         let trace_meta = if num_meta_bytes != 0 {
@@ -183,12 +214,21 @@ impl Deserializable for Context {
         }
         let field_modulus_bytes = source.read_vec(num_modulus_bytes)?;
 
-        // read options
+        // read options and make sure the LDE domain implied by them can be represented on this
+        // platform
         let options = ProofOptions::read_from(source)?;
+        if get_lde_domain_size(trace_length, options.blowup_factor()).is_none() {
+            return Err(DeserializationError::InvalidValue(format!(
+                "LDE domain for trace length {trace_length} and blowup factor {} does not fit into {} bits",
+                options.blowup_factor(),
+                usize::BITS
+            )));
+        }
 
         Ok(Context {
             trace_layout,
             trace_length,
+            original_trace_length,
             trace_meta,
             field_modulus_bytes,
             options,
@@ -221,9 +261,10 @@ fn bytes_to_element<B: StarkField>(bytes: &[u8]) -> B {
 
 #[cfg(test)]
 mod tests {
-    use super::{Context, ProofOptions, ToElements, TraceInfo};
+    use super::{get_lde_domain_size, Context, ProofOptions, ToElements, TraceInfo};
     use crate::{FieldExtension, TraceLayout};
     use math::fields::f64::BaseElement;
+    use utils::{Deserializable, Serializable};
 
     #[test]
     fn context_to_elements() {
@@ -268,7 +309,7 @@ mod tests {
             BaseElement::from(1_u32),    // lower bits of field modulus
             BaseElement::from(u32::MAX), // upper bits of field modulus
             BaseElement::from(ext_fri),
-            BaseElement::from(grinding_factor as u32),
+            BaseElement::from(grinding_factor),
             BaseElement::from(blowup_factor as u32),
             BaseElement::from(num_queries as u32),
             BaseElement::from(trace_length as u32),
@@ -291,4 +332,45 @@ mod tests {
         let context = Context::new::<BaseElement>(&trace_info, options);
         assert_eq!(expected, context.to_elements());
     }
+
+    #[test]
+    fn context_padded_trace() {
+        let options = ProofOptions::for_testing();
+        let trace_info = TraceInfo::new(4, 1024).set_original_length(1000);
+        let context = Context::new::<BaseElement>(&trace_info, options.clone());
+        assert_eq!(1000, context.original_trace_length());
+        assert_eq!(trace_info, context.get_trace_info());
+
+        // padding is bound into the context elements
+        let unpadded = Context::new::<BaseElement>(&TraceInfo::new(4, 1024), options);
+        let padded_elements: Vec<BaseElement> = context.to_elements();
+        let unpadded_elements: Vec<BaseElement> = unpadded.to_elements();
+        assert_ne!(padded_elements, unpadded_elements);
+
+        // original trace length survives serialization
+        let parsed = Context::read_from_bytes(&context.to_bytes()).unwrap();
+        assert_eq!(context, parsed);
+
+        // original trace length cannot be greater than trace length
+        let mut bytes = unpadded.to_bytes();
+        let offset = unpadded.trace_layout().to_bytes().len() + 1;
+        bytes[offset..offset + 8].copy_from_slice(&1025u64.to_le_bytes());
+        assert!(Context::read_from_bytes(&bytes).is_err());
+    }
+
+    #[test]
+    fn context_lde_domain_size_overflow() {
+        assert_eq!(Some(1 << 13), get_lde_domain_size(1 << 10, 8));
+        assert_eq!(None, get_lde_domain_size(1 << (usize::BITS - 2), 8));
+
+        // a context with an LDE domain which does not fit into usize is rejected when parsed; the
+        // trace length is stored as a power of two right after the trace layout
+        let options = ProofOptions::new(32, 8, 0, FieldExtension::None, 4, 31);
+        let context = Context::new::<BaseElement>(&TraceInfo::new(4, 1024), options);
+        let mut bytes = context.to_bytes();
+        let offset = context.trace_layout().to_bytes().len();
+        bytes[offset] = (usize::BITS - 2) as u8;
+        let err = Context::read_from_bytes(&bytes).unwrap_err();
+        assert!(err.to_string().contains("does not fit into"));
+    }
 }
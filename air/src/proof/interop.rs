@@ -0,0 +1,458 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+//! Conversion of proofs to and from the serialization format of upstream Winterfell v0.6.
+//!
+//! Proofs generated by this crate are serialized slightly differently from proofs generated by
+//! upstream Winterfell. Specifically:
+//! * STARK proofs start with the version of the serialization format.
+//! * Lengths of commitments, OOD frame, queries, FRI layers, FRI remainder, and the number of FRI
+//!   layers are encoded as variable-length integers rather than fixed-width integers.
+//! * Trace layout contains the number of preprocessed columns.
+//! * Proof context contains the length of the execution trace before it was padded.
+//! * Proof options encode the number of queries using 2 bytes rather than 1, and also contain
+//!   the LDE domain offset, the query sampling strategy, the FRI layer proof aggregation flag,
+//!   the base field composition flag, and the grinding function.
+//! * Batch Merkle proofs (in trace queries, constraint queries, and FRI layers) encode the number
+//!   of node vectors as a variable-length integer rather than a single byte.
+//! * FRI proofs end with a flag which specifies whether Merkle paths for FRI layer queries are
+//!   aggregated into a single multiproof.
+//!
+//! Functions in this module convert between the two formats so that proofs and test vectors can
+//! be exchanged with upstream Winterfell. A proof can be converted into the upstream format only
+//! if it does not use any of the features which cannot be represented in that format: padded
+//! execution traces, preprocessed columns, custom LDE domain offsets, stratified query sampling,
+//! aggregated FRI layer proofs, base field composition, memory-hard grinding, or more than 255
+//! queries.
+//!
+//! Conversion changes only the encoding of a proof. Proof generation in this crate binds some
+//! values into the Fiat-Shamir transcript which upstream Winterfell does not (e.g., FRI layer
+//! salts); thus, a converted proof is not expected to pass verification with a verifier from the
+//! other code base.
+
+use super::{StarkProof, PROOF_VERSION};
+use crate::{GrindingFunction, TraceLayout};
+use fri::FriProof;
+use utils::{
+    collections::Vec, ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable,
+    SliceReader,
+};
+
+// PUBLIC FUNCTIONS
+// ================================================================================================
+
+/// Returns the provided STARK `proof` serialized in the format of upstream Winterfell.
+///
+/// # Errors
+/// Returns an error if the `proof` uses features which cannot be represented in the upstream
+/// format.
+pub fn stark_proof_to_upstream_bytes(proof: &StarkProof) -> Result<Vec<u8>, DeserializationError> {
+    convert_stark_proof(&proof.to_bytes(), Direction::ToUpstream)
+}
+
+/// Returns a STARK proof parsed from `bytes` serialized in the format of upstream Winterfell.
+///
+/// # Errors
+/// Returns an error if a valid STARK proof could not be read from `bytes`.
+pub fn stark_proof_from_upstream_bytes(bytes: &[u8]) -> Result<StarkProof, DeserializationError> {
+    let bytes = convert_stark_proof(bytes, Direction::FromUpstream)?;
+    StarkProof::from_bytes(&bytes)
+}
+
+/// Returns the provided FRI `proof` serialized in the format of upstream Winterfell.
+///
+/// # Errors
+/// Returns an error if any of the FRI layers contains more than 255 Merkle node vectors, or if
+/// Merkle paths for FRI layer queries are aggregated into a single multiproof.
+pub fn fri_proof_to_upstream_bytes(proof: &FriProof) -> Result<Vec<u8>, DeserializationError> {
+    if proof.is_aggregated() {
+        return Err(aggregated_fri_proof_error());
+    }
+    let bytes = proof.to_bytes();
+    let mut source = SliceReader::new(&bytes);
+    let mut result = Vec::new();
+    convert_fri_proof(&mut source, &mut result, Direction::ToUpstream)?;
+    check_consumed(&source)?;
+    Ok(result)
+}
+
+/// Returns a FRI proof parsed from `bytes` serialized in the format of upstream Winterfell.
+///
+/// # Errors
+/// Returns an error if a valid FRI proof could not be read from `bytes`.
+pub fn fri_proof_from_upstream_bytes(bytes: &[u8]) -> Result<FriProof, DeserializationError> {
+    let mut source = SliceReader::new(bytes);
+    let mut result = Vec::new();
+    convert_fri_proof(&mut source, &mut result, Direction::FromUpstream)?;
+    check_consumed(&source)?;
+    FriProof::read_from_bytes(&result)
+}
+
+// CONVERSION
+// ================================================================================================
+
+/// Direction of the format conversion.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+enum Direction {
+    ToUpstream,
+    FromUpstream,
+}
+
+/// Converts a serialized STARK proof; proof parts which are encoded identically in both formats
+/// (except for their length prefixes) are copied as is.
+fn convert_stark_proof(
+    bytes: &[u8],
+    direction: Direction,
+) -> Result<Vec<u8>, DeserializationError> {
+    let mut source = SliceReader::new(bytes);
+    let mut result = Vec::new();
+
+    convert_version(&mut source, &mut result, direction)?;
+    let trace_layout = convert_context(&mut source, &mut result, direction)?;
+    convert_bytes(&mut source, &mut result, direction, LengthWidth::U16)?;
+    for _ in 0..trace_layout.num_trace_commitments() {
+        convert_queries(&mut source, &mut result, direction)?;
+    }
+    convert_queries(&mut source, &mut result, direction)?;
+    // OOD frame consists of trace states and constraint evaluations
+    convert_bytes(&mut source, &mut result, direction, LengthWidth::U16)?;
+    convert_bytes(&mut source, &mut result, direction, LengthWidth::U16)?;
+    convert_fri_proof(&mut source, &mut result, direction)?;
+    result.write_u64(source.read_u64()?);
+
+    check_consumed(&source)?;
+    Ok(result)
+}
+
+/// Converts the version of the serialization format; upstream proofs do not contain a version.
+fn convert_version(
+    source: &mut SliceReader,
+    target: &mut Vec<u8>,
+    direction: Direction,
+) -> Result<(), DeserializationError> {
+    match direction {
+        Direction::ToUpstream => {
+            let version = source.read_u8()?;
+            if version != PROOF_VERSION {
+                return Err(DeserializationError::UnsupportedVersion(version));
+            }
+        }
+        Direction::FromUpstream => target.write_u8(PROOF_VERSION),
+    }
+    Ok(())
+}
+
+/// Converts a serialized proof context and returns the trace layout described by it.
+fn convert_context(
+    source: &mut SliceReader,
+    target: &mut Vec<u8>,
+    direction: Direction,
+) -> Result<TraceLayout, DeserializationError> {
+    let trace_layout = convert_trace_layout(source, target, direction)?;
+    let trace_length_log2 = source.read_u8()?;
+    target.write_u8(trace_length_log2);
+
+    // upstream contexts do not contain original trace length; thus, the trace must not have
+    // been padded
+    match direction {
+        Direction::ToUpstream => {
+            let original_trace_length = source.read_u64()?;
+            if trace_length_log2 as u32 >= u64::BITS
+                || original_trace_length != 1 << trace_length_log2
+            {
+                return Err(DeserializationError::InvalidValue(
+                    "proofs of padded execution traces cannot be converted into upstream format"
+                        .into(),
+                ));
+            }
+        }
+        Direction::FromUpstream => {
+            if trace_length_log2 as u32 >= u64::BITS {
+                return Err(DeserializationError::InvalidValue(format!(
+                    "trace length cannot be 2^{trace_length_log2}"
+                )));
+            }
+            target.write_u64(1 << trace_length_log2);
+        }
+    }
+
+    // trace metadata and field modulus are encoded identically in both formats
+    let num_meta_bytes = source.read_u16()?;
+    target.write_u16(num_meta_bytes);
+    target.write_bytes(&source.read_vec(num_meta_bytes as usize)?);
+    let num_modulus_bytes = source.read_u8()?;
+    target.write_u8(num_modulus_bytes);
+    target.write_bytes(&source.read_vec(num_modulus_bytes as usize)?);
+
+    convert_options(source, target, direction)?;
+    Ok(trace_layout)
+}
+
+/// Converts a serialized trace layout and returns the result; upstream trace layouts do not
+/// contain the number of preprocessed columns.
+fn convert_trace_layout(
+    source: &mut SliceReader,
+    target: &mut Vec<u8>,
+    direction: Direction,
+) -> Result<TraceLayout, DeserializationError> {
+    match direction {
+        Direction::ToUpstream => {
+            let trace_layout = TraceLayout::read_from(source)?;
+            if trace_layout.has_preprocessed_columns() {
+                return Err(DeserializationError::InvalidValue(
+                    "proofs of traces with preprocessed columns cannot be converted into upstream format"
+                        .into(),
+                ));
+            }
+            // the number of preprocessed columns is written into the last 2 bytes
+            let bytes = trace_layout.to_bytes();
+            target.write_bytes(&bytes[..bytes.len() - 2]);
+            Ok(trace_layout)
+        }
+        Direction::FromUpstream => {
+            // upstream layout consists of main segment width, auxiliary segment width, and the
+            // number of random elements for the auxiliary segment, all encoded as u16 values
+            let mut bytes = source.read_vec(6)?;
+            bytes.write_u16(0);
+            let trace_layout = TraceLayout::read_from_bytes(&bytes)?;
+            target.write_bytes(&bytes);
+            Ok(trace_layout)
+        }
+    }
+}
+
+/// Converts serialized proof options; upstream options encode the number of queries in a single
+/// byte, and do not contain domain offset, query sampling strategy, FRI layer proof aggregation
+/// flag, base field composition flag, and grinding function.
+fn convert_options(
+    source: &mut SliceReader,
+    target: &mut Vec<u8>,
+    direction: Direction,
+) -> Result<(), DeserializationError> {
+    match direction {
+        Direction::ToUpstream => {
+            let num_queries = source.read_u16()?;
+            let num_queries = u8::try_from(num_queries).map_err(|_| {
+                DeserializationError::InvalidValue(format!(
+                    "proofs with {num_queries} queries cannot be converted into upstream format"
+                ))
+            })?;
+            target.write_u8(num_queries);
+        }
+        Direction::FromUpstream => target.write_u16(source.read_u8()? as u16),
+    }
+
+    // blowup factor, grinding factor, field extension, FRI folding factor, and FRI remainder
+    // max degree are encoded identically in both formats
+    target.write_bytes(&source.read_vec(6)?);
+
+    match direction {
+        Direction::ToUpstream => {
+            if source.read_u64()? != 0 {
+                return Err(DeserializationError::InvalidValue(
+                    "proofs with custom domain offset cannot be converted into upstream format"
+                        .into(),
+                ));
+            }
+            if source.read_u16()? != 0 {
+                return Err(DeserializationError::InvalidValue(
+                    "proofs with stratified query sampling cannot be converted into upstream format"
+                        .into(),
+                ));
+            }
+            if source.read_bool()? {
+                return Err(aggregated_fri_proof_error());
+            }
+            if source.read_bool()? {
+                return Err(DeserializationError::InvalidValue(
+                    "proofs with base field composition cannot be converted into upstream format"
+                        .into(),
+                ));
+            }
+            if GrindingFunction::read_from(source)? != GrindingFunction::Hash {
+                return Err(DeserializationError::InvalidValue(
+                    "proofs with memory-hard grinding cannot be converted into upstream format"
+                        .into(),
+                ));
+            }
+        }
+        Direction::FromUpstream => {
+            // default domain offset, uniform query sampling, separate FRI layer proofs,
+            // composition in the extension field, and hash-based grinding
+            target.write_u64(0);
+            target.write_u16(0);
+            target.write_bool(false);
+            target.write_bool(false);
+            target.write(GrindingFunction::Hash);
+        }
+    }
+    Ok(())
+}
+
+/// Converts serialized queries; query values are encoded identically in both formats.
+fn convert_queries(
+    source: &mut SliceReader,
+    target: &mut Vec<u8>,
+    direction: Direction,
+) -> Result<(), DeserializationError> {
+    convert_bytes(source, target, direction, LengthWidth::U32)?;
+    convert_batch_paths(source, target, direction)
+}
+
+/// Converts a serialized FRI proof; FRI layer values and the remainder are encoded identically
+/// in both formats, but upstream FRI proofs do not end with the layer proof aggregation flag.
+fn convert_fri_proof(
+    source: &mut SliceReader,
+    target: &mut Vec<u8>,
+    direction: Direction,
+) -> Result<(), DeserializationError> {
+    let num_layers = convert_length(source, target, direction, LengthWidth::U8)?;
+    for _ in 0..num_layers {
+        convert_bytes(source, target, direction, LengthWidth::U32)?;
+        convert_batch_paths(source, target, direction)?;
+    }
+
+    convert_bytes(source, target, direction, LengthWidth::U16)?;
+    target.write_u8(source.read_u8()?);
+
+    match direction {
+        Direction::ToUpstream => {
+            if source.read_bool()? {
+                return Err(aggregated_fri_proof_error());
+            }
+        }
+        Direction::FromUpstream => target.write_bool(false),
+    }
+    Ok(())
+}
+
+/// Converts serialized internal nodes of a batch Merkle proof prefixed with the number of bytes;
+/// upstream batch proofs encode the number of node vectors in a single byte.
+fn convert_batch_paths(
+    source: &mut SliceReader,
+    target: &mut Vec<u8>,
+    direction: Direction,
+) -> Result<(), DeserializationError> {
+    let num_path_bytes = match direction {
+        Direction::ToUpstream => source.read_usize()?,
+        Direction::FromUpstream => source.read_u32()? as usize,
+    };
+    let path_bytes = source.read_vec(num_path_bytes)?;
+
+    let mut path_reader = SliceReader::new(&path_bytes);
+    let mut converted = Vec::with_capacity(path_bytes.len() + 2);
+    let num_node_vectors =
+        convert_length(&mut path_reader, &mut converted, direction, LengthWidth::U8)?;
+    let num_prefix_bytes = match direction {
+        Direction::ToUpstream => encoded_length_size(num_node_vectors),
+        Direction::FromUpstream => 1,
+    };
+    converted.write_bytes(&path_bytes[num_prefix_bytes..]);
+
+    match direction {
+        Direction::ToUpstream => {
+            let num_converted_bytes = u32::try_from(converted.len()).map_err(|_| {
+                DeserializationError::InvalidValue(
+                    "batch Merkle proof is too large to be converted into upstream format".into(),
+                )
+            })?;
+            target.write_u32(num_converted_bytes);
+        }
+        Direction::FromUpstream => target.write_usize(converted.len()),
+    }
+    target.write_bytes(&converted);
+    Ok(())
+}
+
+/// Width of a length prefix in the upstream format.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+enum LengthWidth {
+    U8,
+    U16,
+    U32,
+}
+
+impl LengthWidth {
+    /// Returns the largest length which can be encoded with this width.
+    fn max_length(&self) -> usize {
+        match self {
+            Self::U8 => u8::MAX as usize,
+            Self::U16 => u16::MAX as usize,
+            Self::U32 => u32::MAX as usize,
+        }
+    }
+}
+
+/// Converts a length prefix and returns the length; lengths are encoded as variable-length
+/// integers in this crate, and as fixed-width integers of the specified `width` upstream.
+fn convert_length(
+    source: &mut SliceReader,
+    target: &mut Vec<u8>,
+    direction: Direction,
+    width: LengthWidth,
+) -> Result<usize, DeserializationError> {
+    match direction {
+        Direction::ToUpstream => {
+            let length = source.read_usize()?;
+            if length > width.max_length() {
+                return Err(DeserializationError::InvalidValue(format!(
+                    "length {length} cannot be converted into upstream format"
+                )));
+            }
+            match width {
+                LengthWidth::U8 => target.write_u8(length as u8),
+                LengthWidth::U16 => target.write_u16(length as u16),
+                LengthWidth::U32 => target.write_u32(length as u32),
+            }
+            Ok(length)
+        }
+        Direction::FromUpstream => {
+            let length = match width {
+                LengthWidth::U8 => source.read_u8()? as usize,
+                LengthWidth::U16 => source.read_u16()? as usize,
+                LengthWidth::U32 => source.read_u32()? as usize,
+            };
+            target.write_usize(length);
+            Ok(length)
+        }
+    }
+}
+
+/// Converts a length-prefixed byte vector whose content is encoded identically in both formats.
+fn convert_bytes(
+    source: &mut SliceReader,
+    target: &mut Vec<u8>,
+    direction: Direction,
+    width: LengthWidth,
+) -> Result<(), DeserializationError> {
+    let length = convert_length(source, target, direction, width)?;
+    target.write_bytes(&source.read_vec(length)?);
+    Ok(())
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns the error for proofs with aggregated FRI layer proofs which cannot be converted.
+fn aggregated_fri_proof_error() -> DeserializationError {
+    DeserializationError::InvalidValue(
+        "proofs with aggregated FRI layer proofs cannot be converted into upstream format".into(),
+    )
+}
+
+/// Returns the number of bytes in the variable-length encoding of `length`.
+fn encoded_length_size(length: usize) -> usize {
+    let mut bytes = Vec::new();
+    bytes.write_usize(length);
+    bytes.len()
+}
+
+/// Returns an error if `source` contains unread bytes.
+fn check_consumed(source: &SliceReader) -> Result<(), DeserializationError> {
+    if source.has_more_bytes() {
+        return Err(DeserializationError::UnconsumedBytes);
+    }
+    Ok(())
+}
@@ -22,16 +22,28 @@ pub use commitments::Commitments;
 mod queries;
 pub use queries::Queries;
 
+mod openings;
+pub use openings::RowOpenings;
+
 mod ood_frame;
 pub use ood_frame::OodFrame;
 
 mod table;
 pub use table::Table;
 
+#[cfg(feature = "interop")]
+pub mod interop;
+
 // CONSTANTS
 // ================================================================================================
 
-const GRINDING_CONTRIBUTION_FLOOR: u32 = 80;
+pub(crate) const GRINDING_CONTRIBUTION_FLOOR: u32 = 80;
+
+/// Version of the serialization format of STARK proofs.
+///
+/// The version is written as the first byte of a serialized proof. Deserializing a proof
+/// serialized with a different version fails with [DeserializationError::UnsupportedVersion].
+pub const PROOF_VERSION: u8 = 1;
 
 // STARK PROOF
 // ================================================================================================
@@ -54,7 +66,8 @@ pub struct StarkProof {
     /// Commitments made by the prover during the commit phase of the protocol.
     pub commitments: Commitments,
     /// Decommitments of extended execution trace values (for all trace segments) at position
-    ///  queried by the verifier.
+    ///  queried by the verifier. If the main trace segment contains preprocessed columns,
+    /// decommitments of these columns are the last element of this vector.
     pub trace_queries: Vec<Queries>,
     /// Decommitments of constraint composition polynomial evaluations at positions queried by
     /// the verifier.
@@ -94,6 +107,50 @@ impl StarkProof {
         self.context.lde_domain_size()
     }
 
+    // COMMITMENTS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns commitments to the extended execution trace; there is one commitment per trace
+    /// segment.
+    ///
+    /// # Errors
+    /// Returns an error if commitments in this proof could not be parsed.
+    pub fn trace_commitments<H: Hasher>(&self) -> Result<Vec<H::Digest>, DeserializationError> {
+        let (trace_commitments, _, _) = self.parse_commitments::<H>()?;
+        Ok(trace_commitments)
+    }
+
+    /// Returns a commitment to the evaluations of the constraint composition polynomial over the
+    /// LDE domain.
+    ///
+    /// # Errors
+    /// Returns an error if commitments in this proof could not be parsed.
+    pub fn constraint_commitment<H: Hasher>(&self) -> Result<H::Digest, DeserializationError> {
+        let (_, constraint_commitment, _) = self.parse_commitments::<H>()?;
+        Ok(constraint_commitment)
+    }
+
+    /// Returns commitments to the FRI layers; the last commitment is a commitment to the FRI
+    /// remainder.
+    ///
+    /// # Errors
+    /// Returns an error if commitments in this proof could not be parsed.
+    pub fn fri_roots<H: Hasher>(&self) -> Result<Vec<H::Digest>, DeserializationError> {
+        let (_, _, fri_roots) = self.parse_commitments::<H>()?;
+        Ok(fri_roots)
+    }
+
+    /// Returns a digest of the statement proven by this proof.
+    ///
+    /// The digest is computed by hashing serialized proof context (which includes trace info and
+    /// proof options) together with all commitments made by the prover. Public inputs are not a
+    /// part of a proof, and thus, are not included in the digest.
+    pub fn statement_digest<H: Hasher>(&self) -> H::Digest {
+        let mut bytes = self.context.to_bytes();
+        self.commitments.write_into(&mut bytes);
+        H::hash(&bytes)
+    }
+
     // SECURITY LEVEL
     // --------------------------------------------------------------------------------------------
     /// Returns security level of this proof (in bits).
@@ -128,8 +185,11 @@ impl StarkProof {
     // --------------------------------------------------------------------------------------------
 
     /// Serializes this proof into a vector of bytes.
+    ///
+    /// The first byte of the result is the version of the serialization format, which is always
+    /// [PROOF_VERSION].
     pub fn to_bytes(&self) -> Vec<u8> {
-        let mut result = Vec::new();
+        let mut result = vec![PROOF_VERSION];
         self.context.write_into(&mut result);
         self.commitments.write_into(&mut result);
         self.trace_queries.write_into(&mut result);
@@ -143,10 +203,18 @@ impl StarkProof {
     /// Returns a STARK proof read from the specified `source`.
     ///
     /// # Errors
-    /// Returns an error of a valid STARK proof could not be read from the specified `source`.
+    /// Returns an error of a valid STARK proof could not be read from the specified `source`, or
+    /// if the proof was serialized using a version of the serialization format other than
+    /// [PROOF_VERSION].
     pub fn from_bytes(source: &[u8]) -> Result<Self, DeserializationError> {
         let mut source = SliceReader::new(source);
 
+        // check the serialization format version
+        let version = source.read_u8()?;
+        if version != PROOF_VERSION {
+            return Err(DeserializationError::UnsupportedVersion(version));
+        }
+
         // parse the context
         let context = Context::read_from(&mut source)?;
 
@@ -154,9 +222,9 @@ impl StarkProof {
         let commitments = Commitments::read_from(&mut source)?;
 
         // parse trace queries
-        let num_trace_segments = context.trace_layout().num_segments();
-        let mut trace_queries = Vec::with_capacity(num_trace_segments);
-        for _ in 0..num_trace_segments {
+        let num_trace_commitments = context.trace_layout().num_trace_commitments();
+        let mut trace_queries = Vec::with_capacity(num_trace_commitments);
+        for _ in 0..num_trace_commitments {
             trace_queries.push(Queries::read_from(&mut source)?);
         }
 
@@ -175,13 +243,31 @@ impl StarkProof {
         }
         Ok(proof)
     }
+
+    // HELPER METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Parses commitments in this proof into trace commitments, constraint commitment, and FRI
+    /// layer commitments.
+    #[allow(clippy::type_complexity)]
+    fn parse_commitments<H: Hasher>(
+        &self,
+    ) -> Result<(Vec<H::Digest>, H::Digest, Vec<H::Digest>), DeserializationError> {
+        let num_fri_layers = self
+            .options()
+            .to_fri_options()
+            .num_fri_layers(self.lde_domain_size());
+        self.commitments
+            .clone()
+            .parse::<H>(self.trace_layout().num_segments(), num_fri_layers)
+    }
 }
 
 // HELPER FUNCTIONS
 // ================================================================================================
 
 /// Computes conjectured security level for the specified proof parameters.
-fn get_conjectured_security(
+pub(crate) fn get_conjectured_security(
     options: &ProofOptions,
     base_field_bits: u32,
     trace_domain_size: u64,
@@ -127,11 +127,11 @@ impl Serializable for OodFrame {
     /// Serializes `self` and writes the resulting bytes into the `target`.
     fn write_into<W: ByteWriter>(&self, target: &mut W) {
         // write trace rows
-        target.write_u16(self.trace_states.len() as u16);
+        target.write_usize(self.trace_states.len());
         target.write_bytes(&self.trace_states);
 
         // write constraint evaluations row
-        target.write_u16(self.evaluations.len() as u16);
+        target.write_usize(self.evaluations.len());
         target.write_bytes(&self.evaluations)
     }
 }
@@ -143,11 +143,11 @@ impl Deserializable for OodFrame {
     /// Returns an error of a valid OOD frame could not be read from the specified `source`.
     fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
         // read trace rows
-        let num_trace_state_bytes = source.read_u16()? as usize;
+        let num_trace_state_bytes = source.read_usize()?;
         let trace_states = source.read_vec(num_trace_state_bytes)?;
 
         // read constraint evaluations row
-        let num_constraint_evaluation_bytes = source.read_u16()? as usize;
+        let num_constraint_evaluation_bytes = source.read_usize()?;
         let evaluations = source.read_vec(num_constraint_evaluation_bytes)?;
 
         Ok(OodFrame {
@@ -0,0 +1,132 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{Queries, Table};
+use crypto::{BatchMerkleProof, ElementHasher, MerkleTree, MerkleTreeError};
+use math::FieldElement;
+use utils::DeserializationError;
+
+// ROW OPENINGS
+// ================================================================================================
+
+/// Rows of a committed matrix opened at a set of positions.
+///
+/// A matrix is committed to by hashing each of its rows into a leaf of a Merkle tree. Opening the
+/// matrix at a set of positions reveals the rows at these positions together with a batch Merkle
+/// proof which authenticates the rows against the root of the tree. Openings are sent from the
+/// prover to the verifier as [Queries].
+#[derive(Debug, Clone)]
+pub struct RowOpenings<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> {
+    rows: Table<E>,
+    proof: BatchMerkleProof<H>,
+}
+
+impl<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> RowOpenings<E, H> {
+    // CONSTRUCTORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns openings consisting of the specified `rows` and a batch Merkle `proof` for these
+    /// rows.
+    ///
+    /// Rows must be listed in the same order as the leaves of the `proof`; this is checked when
+    /// the openings are verified.
+    pub fn new(rows: Table<E>, proof: BatchMerkleProof<H>) -> Self {
+        Self { rows, proof }
+    }
+
+    /// Parses the provided `queries` into openings of `num_openings` rows of `row_width` elements
+    /// each in a matrix with `num_rows` rows.
+    ///
+    /// # Errors
+    /// Returns an error if `queries` do not contain exactly the specified number of rows, or if
+    /// the batch Merkle proof in `queries` could not be parsed.
+    ///
+    /// # Panics
+    /// Panics if `num_rows` is not a power of two, or if `num_openings` or `row_width` is zero.
+    pub fn parse(
+        queries: Queries,
+        num_rows: usize,
+        num_openings: usize,
+        row_width: usize,
+    ) -> Result<Self, DeserializationError> {
+        let (proof, rows) = queries.parse::<H, E>(num_rows, num_openings, row_width)?;
+        Ok(Self { rows, proof })
+    }
+
+    /// Checks that `queries` are valid openings of a matrix with the specified commitment `root`
+    /// at the specified `positions`, and returns the opened rows.
+    ///
+    /// `num_rows` and `row_width` must be the number of rows and the number of columns of the
+    /// committed matrix. Rows in the returned table are in the same order as `positions`.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * `queries` could not be parsed into `positions.len()` rows of `row_width` elements each
+    ///   and a batch Merkle proof for a tree with `num_rows` leaves.
+    /// * Any of the opened rows does not resolve to the specified `root`.
+    ///
+    /// # Panics
+    /// Panics if `num_rows` is not a power of two, or if `positions` is empty or `row_width` is
+    /// zero.
+    pub fn verify_openings(
+        root: &H::Digest,
+        num_rows: usize,
+        row_width: usize,
+        positions: &[usize],
+        queries: Queries,
+    ) -> Result<Table<E>, MerkleTreeError> {
+        let openings = Self::parse(queries, num_rows, positions.len(), row_width)
+            .map_err(|_| MerkleTreeError::InvalidProof)?;
+        openings.verify(root, positions)?;
+        Ok(openings.into_rows())
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the opened rows.
+    pub fn rows(&self) -> &Table<E> {
+        &self.rows
+    }
+
+    /// Returns the batch Merkle proof for the opened rows.
+    pub fn proof(&self) -> &BatchMerkleProof<H> {
+        &self.proof
+    }
+
+    /// Decomposes these openings into the opened rows and the batch Merkle proof for these rows.
+    pub fn into_parts(self) -> (Table<E>, BatchMerkleProof<H>) {
+        (self.rows, self.proof)
+    }
+
+    /// Returns the opened rows, consuming these openings.
+    pub fn into_rows(self) -> Table<E> {
+        self.rows
+    }
+
+    // VERIFICATION
+    // --------------------------------------------------------------------------------------------
+
+    /// Checks that the opened rows are rows of a matrix with the specified commitment `root` at
+    /// the specified `positions`.
+    ///
+    /// # Errors
+    /// Returns an error if the number of opened rows is different from the number of
+    /// `positions`, if hashes of the opened rows are different from the leaves of the batch
+    /// Merkle proof, or if the batch Merkle proof does not resolve to `root` for `positions`.
+    pub fn verify(&self, root: &H::Digest, positions: &[usize]) -> Result<(), MerkleTreeError> {
+        if self.rows.num_rows() != positions.len()
+            || self.proof.leaves.len() != positions.len()
+            || self
+                .rows
+                .rows()
+                .zip(self.proof.leaves.iter())
+                .any(|(row, leaf)| H::hash_elements(row) != *leaf)
+        {
+            return Err(MerkleTreeError::InvalidProof);
+        }
+        MerkleTree::verify_batch(root, positions, &self.proof)
+    }
+}
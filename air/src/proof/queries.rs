@@ -144,11 +144,11 @@ impl Serializable for Queries {
     /// Serializes `self` and writes the resulting bytes into the `target`.
     fn write_into<W: ByteWriter>(&self, target: &mut W) {
         // write value bytes
-        target.write_u32(self.values.len() as u32);
+        target.write_usize(self.values.len());
         target.write_bytes(&self.values);
 
         // write path bytes
-        target.write_u32(self.paths.len() as u32);
+        target.write_usize(self.paths.len());
         target.write_bytes(&self.paths);
     }
 }
@@ -160,12 +160,12 @@ impl Deserializable for Queries {
     /// Returns an error of a valid query struct could not be read from the specified source.
     fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
         // read values
-        let num_value_bytes = source.read_u32()?;
-        let values = source.read_vec(num_value_bytes as usize)?;
+        let num_value_bytes = source.read_usize()?;
+        let values = source.read_vec(num_value_bytes)?;
 
         // read paths
-        let num_paths_bytes = source.read_u32()?;
-        let paths = source.read_vec(num_paths_bytes as usize)?;
+        let num_paths_bytes = source.read_usize()?;
+        let paths = source.read_vec(num_paths_bytes)?;
 
         Ok(Queries { paths, values })
     }
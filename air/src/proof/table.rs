@@ -49,7 +49,7 @@ impl<E: FieldElement> Table<E> {
         );
         assert!(num_cols > 0, "number of columns must be greater than 0");
         assert!(
-            num_cols < MAX_ROWS,
+            num_cols <= MAX_COLS,
             "number of columns cannot exceed {MAX_COLS}, but was {num_cols}"
         );
 
@@ -81,7 +81,7 @@ impl<E: FieldElement> Table<E> {
     }
 
     /// Returns an iterator over rows of this table.
-    pub fn rows(&self) -> RowIterator<E> {
+    pub fn rows(&self) -> RowIterator<'_, E> {
         RowIterator::new(self)
     }
 
@@ -91,16 +91,44 @@ impl<E: FieldElement> Table<E> {
     /// Combines multiple tables together into a single table by stacking tables column-wise (e.g.
     /// the number of rows remains the same but the number of columns changes).
     ///
-    /// Currently, this method does not support inputs containing more than one table.
-    ///
     /// # Panics
-    /// Panics if the list of tables is empty.
+    /// Panics if the list of tables is empty, or if the tables have different numbers of rows.
     pub fn merge(mut tables: Vec<Table<E>>) -> Table<E> {
         assert!(!tables.is_empty(), "cannot merge an empty set of tables");
         if tables.len() == 1 {
-            tables.remove(0)
-        } else {
-            unimplemented!("merging of multiple tables is not yet implemented")
+            return tables.remove(0);
+        }
+
+        let num_rows = tables[0].num_rows();
+        assert!(
+            tables.iter().all(|table| table.num_rows() == num_rows),
+            "cannot merge tables with different numbers of rows"
+        );
+
+        let row_width = tables.iter().map(|table| table.row_width).sum();
+        let mut data = Vec::with_capacity(num_rows * row_width);
+        for row_idx in 0..num_rows {
+            for table in tables.iter() {
+                data.extend_from_slice(table.get_row(row_idx));
+            }
+        }
+
+        Table { data, row_width }
+    }
+
+    /// Returns a new table consisting of the rows of this table at the specified indexes, in the
+    /// order in which the indexes are listed.
+    ///
+    /// # Panics
+    /// Panics if any of the indexes is out of bounds.
+    pub fn select_rows(&self, row_indexes: &[usize]) -> Table<E> {
+        let mut data = Vec::with_capacity(row_indexes.len() * self.row_width);
+        for &row_idx in row_indexes {
+            data.extend_from_slice(self.get_row(row_idx));
+        }
+        Table {
+            data,
+            row_width: self.row_width,
         }
     }
 }
@@ -4,6 +4,7 @@
 // LICENSE file in the root directory of this source tree.
 
 use core::fmt;
+use utils::{ErrorCategory, ErrorCode, ToErrorCode};
 
 // MERKLE TREE ERROR
 // ================================================================================================
@@ -72,9 +73,6 @@ impl fmt::Display for MerkleTreeError {
 pub enum RandomCoinError {
     /// A valid element could not be drawn from the field after the specified number of tries.
     FailedToDrawFieldElement(usize),
-    /// The required number of integer values could not be drawn from the specified domain after
-    /// the specified number of tries.
-    FailedToDrawIntegers(usize, usize, usize),
 }
 
 impl fmt::Display for RandomCoinError {
@@ -86,12 +84,32 @@ impl fmt::Display for RandomCoinError {
                     "failed to generate a valid field element after {num_tries} tries"
                 )
             }
-            Self::FailedToDrawIntegers(num_expected, num_actual, num_tries) => {
-                write!(
-                    f,
-                    "needed to draw {num_expected} integers from a domain, but drew only {num_actual} after {num_tries} tries"
-                )
-            }
         }
     }
 }
+
+impl ToErrorCode for MerkleTreeError {
+    fn error_code(&self) -> ErrorCode {
+        let index = match self {
+            Self::TooFewLeaves(..) => 1,
+            Self::NumberOfLeavesNotPowerOfTwo(_) => 2,
+            Self::LeafIndexOutOfBounds(..) => 3,
+            Self::DuplicateLeafIndex => 4,
+            Self::TooFewLeafIndexes => 5,
+            Self::TooManyLeafIndexes(..) => 6,
+            Self::InvalidProof => 7,
+        };
+        ErrorCode::new(ErrorCategory::Crypto, index)
+    }
+}
+
+impl ToErrorCode for RandomCoinError {
+    fn error_code(&self) -> ErrorCode {
+        // random coin errors are placed after Merkle tree errors, leaving room for new Merkle
+        // tree errors
+        let index = match self {
+            Self::FailedToDrawFieldElement(_) => 21,
+        };
+        ErrorCode::new(ErrorCategory::Crypto, index)
+    }
+}
@@ -29,7 +29,7 @@ impl<B: StarkField> Hasher for Blake3_256<B> {
     }
 
     fn merge(values: &[Self::Digest; 2]) -> Self::Digest {
-        ByteDigest(blake3::hash(ByteDigest::digests_as_bytes(values)).into())
+        ByteDigest(blake3::hash(&ByteDigest::merge_bytes::<64>(values)).into())
     }
 
     fn merge_with_int(seed: Self::Digest, value: u64) -> Self::Digest {
@@ -78,7 +78,7 @@ impl<B: StarkField> Hasher for Blake3_192<B> {
     }
 
     fn merge(values: &[Self::Digest; 2]) -> Self::Digest {
-        let result = blake3::hash(ByteDigest::digests_as_bytes(values));
+        let result = blake3::hash(&ByteDigest::merge_bytes::<48>(values));
         ByteDigest(result.as_bytes()[..24].try_into().unwrap())
     }
 
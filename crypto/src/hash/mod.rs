@@ -11,7 +11,7 @@ mod blake;
 pub use blake::{Blake3_192, Blake3_256};
 
 mod sha;
-pub use sha::Sha3_256;
+pub use sha::{Keccak256, Sha3_256};
 
 mod mds;
 
@@ -74,7 +74,12 @@ pub trait Digest:
     /// Ideally, the length of the returned array should be defined by an associated constant, but
     /// using associated constants in const generics is not supported by Rust yet. Thus, we put an
     /// upper limit on the possible digest size. For digests which are smaller than 32 bytes, the
-    /// unused bytes should be set to 0.
+    /// unused bytes should be set to 0; for digests which are larger than 32 bytes, only the
+    /// first 32 bytes should be returned.
+    ///
+    /// The returned bytes are used only to derive pseudo-random values (e.g., in a random coin),
+    /// and thus, truncating large digests does not affect serialization or Merkle tree
+    /// construction which operate on full digests.
     fn as_bytes(&self) -> [u8; 32];
 }
 
@@ -102,12 +107,26 @@ impl<const N: usize> ByteDigest<N> {
         let len = digests.len() * N;
         unsafe { slice::from_raw_parts(p as *const u8, len) }
     }
+
+    /// Returns bytes of the two digests to be merged by a hash function; `M` must be equal to
+    /// `2 * N`.
+    ///
+    /// Unlike [ByteDigest::digests_as_bytes()], this copies the bytes, and thus, does not rely on
+    /// re-interpreting the memory of the digests.
+    #[inline(always)]
+    pub(crate) fn merge_bytes<const M: usize>(values: &[ByteDigest<N>; 2]) -> [u8; M] {
+        let mut result = [0; M];
+        result[..N].copy_from_slice(&values[0].0);
+        result[N..].copy_from_slice(&values[1].0);
+        result
+    }
 }
 
 impl<const N: usize> Digest for ByteDigest<N> {
     fn as_bytes(&self) -> [u8; 32] {
         let mut result = [0; 32];
-        result[..N].copy_from_slice(&self.0);
+        let num_bytes = N.min(32);
+        result[..num_bytes].copy_from_slice(&self.0[..num_bytes]);
         result
     }
 }
@@ -143,5 +162,15 @@ mod tests {
         let mut expected = [255_u8; 32];
         expected[31] = 0;
         assert_eq!(expected, d.as_bytes());
+
+        let d = ByteDigest::new([255_u8; 20]);
+        let mut expected = [0_u8; 32];
+        expected[..20].copy_from_slice(&[255_u8; 20]);
+        assert_eq!(expected, d.as_bytes());
+
+        let mut bytes = [255_u8; 48];
+        bytes[32..].copy_from_slice(&[1_u8; 16]);
+        let d = ByteDigest::new(bytes);
+        assert_eq!([255_u8; 32], d.as_bytes());
     }
 }
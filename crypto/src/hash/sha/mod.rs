@@ -26,7 +26,7 @@ impl<B: StarkField> Hasher for Sha3_256<B> {
     }
 
     fn merge(values: &[Self::Digest; 2]) -> Self::Digest {
-        ByteDigest(sha3::Sha3_256::digest(ByteDigest::digests_as_bytes(values)).into())
+        ByteDigest(sha3::Sha3_256::digest(ByteDigest::merge_bytes::<64>(values)).into())
     }
 
     fn merge_with_int(seed: Self::Digest, value: u64) -> Self::Digest {
@@ -49,7 +49,58 @@ impl<B: StarkField> ElementHasher for Sha3_256<B> {
         } else {
             // when elements' internal and canonical representations differ, we need to serialize
             // them before hashing
-            let mut hasher = ShaHasher::new();
+            let mut hasher = ShaHasher::new(sha3::Sha3_256::new());
+            hasher.write(elements);
+            ByteDigest(hasher.finalize())
+        }
+    }
+}
+
+// KECCAK WITH 256-BIT OUTPUT
+// ================================================================================================
+
+/// Implementation of the [Hasher](super::Hasher) trait for Keccak hash function with 256-bit
+/// output.
+///
+/// This is the original Keccak submission (as used by Ethereum) rather than the standardized SHA3
+/// function; the two differ only in padding. Using this hash function makes it possible to verify
+/// proofs in environments which provide Keccak natively, such as the EVM.
+pub struct Keccak256<B: StarkField>(PhantomData<B>);
+
+impl<B: StarkField> Hasher for Keccak256<B> {
+    type Digest = ByteDigest<32>;
+
+    const COLLISION_RESISTANCE: u32 = 128;
+
+    fn hash(bytes: &[u8]) -> Self::Digest {
+        ByteDigest(sha3::Keccak256::digest(bytes).into())
+    }
+
+    fn merge(values: &[Self::Digest; 2]) -> Self::Digest {
+        ByteDigest(sha3::Keccak256::digest(ByteDigest::merge_bytes::<64>(values)).into())
+    }
+
+    fn merge_with_int(seed: Self::Digest, value: u64) -> Self::Digest {
+        let mut data = [0; 40];
+        data[..32].copy_from_slice(&seed.0);
+        data[32..].copy_from_slice(&value.to_le_bytes());
+        ByteDigest(sha3::Keccak256::digest(data).into())
+    }
+}
+
+impl<B: StarkField> ElementHasher for Keccak256<B> {
+    type BaseField = B;
+
+    fn hash_elements<E: FieldElement<BaseField = Self::BaseField>>(elements: &[E]) -> Self::Digest {
+        if B::IS_CANONICAL {
+            // when element's internal and canonical representations are the same, we can hash
+            // element bytes directly
+            let bytes = E::elements_as_bytes(elements);
+            ByteDigest(sha3::Keccak256::digest(bytes).into())
+        } else {
+            // when elements' internal and canonical representations differ, we need to serialize
+            // them before hashing
+            let mut hasher = ShaHasher::new(sha3::Keccak256::new());
             hasher.write(elements);
             ByteDigest(hasher.finalize())
         }
@@ -59,20 +110,22 @@ impl<B: StarkField> ElementHasher for Sha3_256<B> {
 // SHA HASHER
 // ================================================================================================
 
-/// Wrapper around SHA3 hasher to implement [ByteWriter] trait for it.
-struct ShaHasher(sha3::Sha3_256);
+/// Wrapper around SHA3 and Keccak hashers to implement [ByteWriter] trait for them.
+struct ShaHasher<D: Digest>(D);
 
-impl ShaHasher {
-    pub fn new() -> Self {
-        Self(sha3::Sha3_256::new())
+impl<D: Digest> ShaHasher<D> {
+    pub fn new(hasher: D) -> Self {
+        Self(hasher)
     }
 
     pub fn finalize(self) -> [u8; 32] {
-        self.0.finalize().into()
+        let mut result = [0; 32];
+        result.copy_from_slice(&self.0.finalize());
+        result
     }
 }
 
-impl ByteWriter for ShaHasher {
+impl<D: Digest> ByteWriter for ShaHasher<D> {
     fn write_u8(&mut self, value: u8) {
         self.0.update([value]);
     }
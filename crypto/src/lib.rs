@@ -15,6 +15,8 @@
 //! * **PRNG** - which is used to generate pseudo-random elements in a finite field. The
 //!   [RandomCoin] implementation uses a cryptographic hash function to generate pseudo-random
 //!   elements form a seed.
+//! * **Proof-of-work** - which is used for query seed grinding. The [ProofOfWork] trait has a
+//!   hash-based implementation and a memory-hard implementation.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
@@ -30,6 +32,7 @@ pub mod hashers {
     pub use super::hash::Blake3_192;
     pub use super::hash::Blake3_256;
     pub use super::hash::GriffinJive64_256;
+    pub use super::hash::Keccak256;
     pub use super::hash::Rp62_248;
     pub use super::hash::Rp64_256;
     pub use super::hash::RpJive64_256;
@@ -45,5 +48,8 @@ pub use merkle::concurrent;
 mod random;
 pub use random::{DefaultRandomCoin, RandomCoin};
 
+mod pow;
+pub use pow::{HashProofOfWork, MemoryHardProofOfWork, ProofOfWork};
+
 mod errors;
 pub use errors::{MerkleTreeError, RandomCoinError};
@@ -4,8 +4,10 @@
 // LICENSE file in the root directory of this source tree.
 
 use crate::Hasher;
-use core::slice;
-use utils::{collections::Vec, iterators::*, rayon};
+use utils::{collections::Vec, iterators::*};
+
+#[cfg(not(feature = "forbid-unsafe-bytes"))]
+use utils::{group_slice_elements, rayon};
 
 // CONSTANTS
 // ================================================================================================
@@ -18,6 +20,7 @@ pub const MIN_CONCURRENT_LEAVES: usize = 1024;
 /// Builds all internal nodes of the Merkle using all available threads and stores the
 /// results in a single vector such that root of the tree is at position 1, nodes immediately
 /// under the root is at positions 2 and 3 etc.
+#[cfg(not(feature = "forbid-unsafe-bytes"))]
 pub fn build_merkle_nodes<H: Hasher>(leaves: &[H::Digest]) -> Vec<H::Digest> {
     let n = leaves.len() / 2;
 
@@ -27,7 +30,7 @@ pub fn build_merkle_nodes<H: Hasher>(leaves: &[H::Digest]) -> Vec<H::Digest> {
 
     // re-interpret leaves as an array of two leaves fused together and use it to
     // build first row of internal nodes (parents of leaves)
-    let two_leaves = unsafe { slice::from_raw_parts(leaves.as_ptr() as *const [H::Digest; 2], n) };
+    let two_leaves = group_slice_elements::<H::Digest, 2>(leaves);
     nodes[n..]
         .par_iter_mut()
         .zip(two_leaves.par_iter())
@@ -40,10 +43,8 @@ pub fn build_merkle_nodes<H: Hasher>(leaves: &[H::Digest]) -> Vec<H::Digest> {
     let num_subtrees = rayon::current_num_threads().next_power_of_two();
     let batch_size = n / num_subtrees;
 
-    // re-interpret nodes as an array of two nodes fused together
-    let two_nodes = unsafe { slice::from_raw_parts(nodes.as_ptr() as *const [H::Digest; 2], n) };
-
-    // process each subtree in a separate thread
+    // process each subtree in a separate thread; children of each node are re-interpreted as an
+    // array of two nodes fused together
     rayon::scope(|s| {
         for i in 0..num_subtrees {
             let nodes = unsafe { &mut *(&mut nodes[..] as *mut [H::Digest]) };
@@ -52,7 +53,9 @@ pub fn build_merkle_nodes<H: Hasher>(leaves: &[H::Digest]) -> Vec<H::Digest> {
                 let mut start_idx = n / 2 + batch_size * i;
                 while start_idx >= num_subtrees {
                     for k in (start_idx..(start_idx + batch_size)).rev() {
-                        nodes[k] = H::merge(&two_nodes[k]);
+                        let two_nodes =
+                            group_slice_elements::<H::Digest, 2>(&nodes[2 * k..2 * k + 2]);
+                        nodes[k] = H::merge(&two_nodes[0]);
                     }
                     start_idx /= 2;
                     batch_size /= 2;
@@ -63,7 +66,42 @@ pub fn build_merkle_nodes<H: Hasher>(leaves: &[H::Digest]) -> Vec<H::Digest> {
 
     // finish the tip of the tree
     for i in (1..num_subtrees).rev() {
-        nodes[i] = H::merge(&two_nodes[i]);
+        let two_nodes = group_slice_elements::<H::Digest, 2>(&nodes[2 * i..2 * i + 2]);
+        let parent = H::merge(&two_nodes[0]);
+        nodes[i] = parent;
+    }
+
+    nodes
+}
+
+/// Builds all internal nodes of the Merkle using all available threads and stores the
+/// results in a single vector such that root of the tree is at position 1, nodes immediately
+/// under the root is at positions 2 and 3 etc.
+///
+/// Unlike the default implementation, subtrees are not processed by threads writing into the
+/// same vector; instead, the tree is built one row at a time, and nodes within each row are
+/// computed in parallel. This does not require any `unsafe` code.
+#[cfg(feature = "forbid-unsafe-bytes")]
+pub fn build_merkle_nodes<H: Hasher>(leaves: &[H::Digest]) -> Vec<H::Digest> {
+    let n = leaves.len() / 2;
+    let mut nodes = vec![H::Digest::default(); 2 * n];
+
+    // build first row of internal nodes (parents of leaves)
+    nodes[n..]
+        .par_iter_mut()
+        .zip(leaves.par_chunks(2))
+        .for_each(|(target, source)| *target = H::merge(&[source[0], source[1]]));
+
+    // build all other rows; parents of nodes at positions [2 * k, 4 * k) are located at
+    // positions [k, 2 * k)
+    let mut k = n / 2;
+    while k > 0 {
+        let (parents, children) = nodes.split_at_mut(2 * k);
+        parents[k..]
+            .par_iter_mut()
+            .zip(children[..2 * k].par_chunks(2))
+            .for_each(|(target, source)| *target = H::merge(&[source[0], source[1]]));
+        k /= 2;
     }
 
     nodes
@@ -82,7 +120,7 @@ mod tests {
     proptest! {
         #[test]
         fn build_merkle_nodes_concurrent(ref data in vec(any::<[u8; 32]>(), 256..257).no_shrink()) {
-            let leaves = ByteDigest::bytes_as_digests(&data).to_vec();
+            let leaves = ByteDigest::bytes_as_digests(data).to_vec();
             let sequential = super::super::build_merkle_nodes::<Sha3_256<BaseElement>>(&leaves);
             let concurrent = super::build_merkle_nodes::<Sha3_256<BaseElement>>(&leaves);
             assert_eq!(concurrent, sequential);
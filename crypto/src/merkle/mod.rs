@@ -4,9 +4,11 @@
 // LICENSE file in the root directory of this source tree.
 
 use crate::{errors::MerkleTreeError, hash::Hasher};
-use core::slice;
 use utils::collections::{BTreeMap, BTreeSet, Vec};
 
+#[cfg(not(feature = "forbid-unsafe-bytes"))]
+use utils::group_slice_elements;
+
 mod proofs;
 pub use proofs::BatchMerkleProof;
 
@@ -172,6 +174,15 @@ impl<H: Hasher> MerkleTree<H> {
         &self.leaves
     }
 
+    /// Returns internal nodes of the tree.
+    ///
+    /// The nodes are stored in the same way as by [build_merkle_nodes()]: the root is stored at
+    /// position 1, and the children of a node at position `i` are stored at positions `2i` and
+    /// `2i + 1`. The node at position 0 is unused.
+    pub fn nodes(&self) -> &[H::Digest] {
+        &self.nodes
+    }
+
     // PROVING METHODS
     // --------------------------------------------------------------------------------------------
 
@@ -206,7 +217,7 @@ impl<H: Hasher> MerkleTree<H> {
     /// # Errors
     /// Returns an error if:
     /// * No indexes were provided (i.e., `indexes` is an empty slice).
-    /// * Number of provided indexes is greater than 255.
+    /// * Number of provided indexes is greater than 65535.
     /// * Any of the provided indexes are greater than or equal to the number of leaves in the
     ///   tree.
     /// * List of indexes contains duplicates.
@@ -226,45 +237,43 @@ impl<H: Hasher> MerkleTree<H> {
         let mut leaves = vec![H::Digest::default(); index_map.len()];
         let mut nodes: Vec<Vec<H::Digest>> = Vec::with_capacity(indexes.len());
 
-        // populate the proof with leaf node values
+        // populate the proof with leaf node values; since the indexes are sorted, indexes of
+        // the parent nodes are sorted as well
         let n = self.leaves.len();
-        let mut next_indexes: Vec<usize> = Vec::new();
+        let mut node_indexes: Vec<usize> = Vec::with_capacity(indexes.len());
         for index in indexes {
-            let missing: Vec<H::Digest> = (index..index + 2)
-                .flat_map(|i| {
-                    let v = self.leaves[i];
-                    if let Some(idx) = index_map.get(&i) {
-                        leaves[*idx] = v;
-                        None
-                    } else {
-                        Some(v)
-                    }
-                })
-                .collect();
+            let mut missing = Vec::with_capacity(self.depth());
+            for i in index..index + 2 {
+                match index_map.get(&i) {
+                    Some(&idx) => leaves[idx] = self.leaves[i],
+                    None => missing.push(self.leaves[i]),
+                }
+            }
             nodes.push(missing);
-
-            next_indexes.push((index + n) >> 1);
+            node_indexes.push((index + n) >> 1);
         }
 
-        // add required internal nodes to the proof, skipping redundancies
+        // add required internal nodes to the proof, skipping redundancies; parent indexes are
+        // written in place over the current level because there are never more parents than
+        // nodes processed so far
         for _ in 1..self.depth() {
-            let indexes = next_indexes.clone();
-            next_indexes.truncate(0);
-
+            let mut num_parents = 0;
             let mut i = 0;
-            while i < indexes.len() {
-                let sibling_index = indexes[i] ^ 1;
-                if i + 1 < indexes.len() && indexes[i + 1] == sibling_index {
+            while i < node_indexes.len() {
+                let sibling_index = node_indexes[i] ^ 1;
+                if i + 1 < node_indexes.len() && node_indexes[i + 1] == sibling_index {
                     i += 1;
                 } else {
                     nodes[i].push(self.nodes[sibling_index]);
                 }
 
                 // add parent index to the set of next indexes
-                next_indexes.push(sibling_index >> 1);
+                node_indexes[num_parents] = sibling_index >> 1;
+                num_parents += 1;
 
                 i += 1;
             }
+            node_indexes.truncate(num_parents);
         }
 
         Ok(BatchMerkleProof {
@@ -311,7 +320,7 @@ impl<H: Hasher> MerkleTree<H> {
     /// # Errors
     /// Returns an error if:
     /// * No indexes were provided (i.e., `indexes` is an empty slice).
-    /// * Number of provided indexes is greater than 255.
+    /// * Number of provided indexes is greater than 65535.
     /// * Any of the specified `indexes` is greater than or equal to the number of leaves in the
     ///   tree from which the batch proof was generated.
     /// * List of indexes contains duplicates.
@@ -321,7 +330,7 @@ impl<H: Hasher> MerkleTree<H> {
         indexes: &[usize],
         proof: &BatchMerkleProof<H>,
     ) -> Result<(), MerkleTreeError> {
-        if *root != proof.get_root(indexes)? {
+        if *root != proof.verify_into_root(indexes)? {
             return Err(MerkleTreeError::InvalidProof);
         }
         Ok(())
@@ -342,23 +351,40 @@ pub fn build_merkle_nodes<H: Hasher>(leaves: &[H::Digest]) -> Vec<H::Digest> {
     let n = leaves.len() / 2;
 
     // create un-initialized array to hold all intermediate nodes
+    #[cfg(not(feature = "forbid-unsafe-bytes"))]
     let mut nodes = unsafe { utils::uninit_vector::<H::Digest>(2 * n) };
+    #[cfg(feature = "forbid-unsafe-bytes")]
+    let mut nodes = vec![H::Digest::default(); 2 * n];
     nodes[0] = H::Digest::default();
 
     // re-interpret leaves as an array of two leaves fused together
-    let two_leaves = unsafe { slice::from_raw_parts(leaves.as_ptr() as *const [H::Digest; 2], n) };
+    #[cfg(not(feature = "forbid-unsafe-bytes"))]
+    {
+        let two_leaves = group_slice_elements::<H::Digest, 2>(leaves);
 
-    // build first row of internal nodes (parents of leaves)
-    for (i, j) in (0..n).zip(n..nodes.len()) {
-        nodes[j] = H::merge(&two_leaves[i]);
-    }
+        // build first row of internal nodes (parents of leaves)
+        for (i, j) in (0..n).zip(n..nodes.len()) {
+            nodes[j] = H::merge(&two_leaves[i]);
+        }
 
-    // re-interpret nodes as an array of two nodes fused together
-    let two_nodes = unsafe { slice::from_raw_parts(nodes.as_ptr() as *const [H::Digest; 2], n) };
+        // calculate all other tree nodes; children of each node are re-interpreted as an array
+        // of two nodes fused together
+        for i in (1..n).rev() {
+            let two_nodes = group_slice_elements::<H::Digest, 2>(&nodes[2 * i..2 * i + 2]);
+            let parent = H::merge(&two_nodes[0]);
+            nodes[i] = parent;
+        }
+    }
 
-    // calculate all other tree nodes
-    for i in (1..n).rev() {
-        nodes[i] = H::merge(&two_nodes[i]);
+    // without re-interpreting memory, children of each node are copied into an array
+    #[cfg(feature = "forbid-unsafe-bytes")]
+    {
+        for (i, j) in (0..n).zip(n..nodes.len()) {
+            nodes[j] = H::merge(&[leaves[2 * i], leaves[2 * i + 1]]);
+        }
+        for i in (1..n).rev() {
+            nodes[i] = H::merge(&[nodes[2 * i], nodes[2 * i + 1]]);
+        }
     }
 
     nodes
@@ -7,13 +7,13 @@ use crate::{errors::MerkleTreeError, Hasher};
 use utils::{
     collections::{BTreeMap, Vec},
     string::ToString,
-    ByteReader, Deserializable, DeserializationError, Serializable,
+    ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable,
 };
 
 // CONSTANTS
 // ================================================================================================
 
-pub(super) const MAX_PATHS: usize = 255;
+pub(super) const MAX_PATHS: usize = u16::MAX as usize;
 
 // BATCH MERKLE PROOF
 // ================================================================================================
@@ -25,7 +25,7 @@ pub(super) const MAX_PATHS: usize = 255;
 /// individual Merkle paths. The algorithm is for aggregation is a variation of
 /// [Octopus](https://eprint.iacr.org/2017/933).
 ///
-/// Currently, at most 255 paths can be aggregated into a single proof. This limitation is
+/// Currently, at most 65535 paths can be aggregated into a single proof. This limitation is
 /// imposed primarily for serialization purposes.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct BatchMerkleProof<H: Hasher> {
@@ -43,7 +43,7 @@ impl<H: Hasher> BatchMerkleProof<H> {
     /// # Panics
     /// Panics if:
     /// * No paths have been provided (i.e., `paths` is an empty slice).
-    /// * More than 255 paths have been provided.
+    /// * More than 65535 paths have been provided.
     /// * Number of paths is not equal to the number of indexes.
     /// * Not all paths have the same length.
     pub fn from_paths(paths: &[Vec<H::Digest>], indexes: &[usize]) -> BatchMerkleProof<H> {
@@ -119,15 +119,37 @@ impl<H: Hasher> BatchMerkleProof<H> {
 
     /// Computes a node to which all Merkle paths aggregated in this proof resolve.
     ///
+    /// This is equivalent to [BatchMerkleProof::verify_into_root()].
+    ///
     /// # Errors
     /// Returns an error if:
     /// * No indexes were provided (i.e., `indexes` is an empty slice).
-    /// * Number of provided indexes is greater than 255.
+    /// * Number of provided indexes is greater than 65535.
     /// * Any of the specified `indexes` is greater than or equal to the number of leaves in the
     ///   tree for which this batch proof was generated.
     /// * List of indexes contains duplicates.
     /// * The proof does not resolve to a single root.
     pub fn get_root(&self, indexes: &[usize]) -> Result<H::Digest, MerkleTreeError> {
+        self.verify_into_root(indexes)
+    }
+
+    /// Checks that all Merkle paths aggregated in this proof resolve to a single node, and
+    /// returns this node.
+    ///
+    /// The root is computed in a single pass over the sorted set of the specified `indexes`:
+    /// nodes at each level of the tree are hashed into their parents in place, and thus, the
+    /// memory required is proportional to the number of indexes rather than to the size of the
+    /// tree. The caller is expected to compare the returned node against the commitment root.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * No indexes were provided (i.e., `indexes` is an empty slice).
+    /// * Number of provided indexes is greater than 65535.
+    /// * Any of the specified `indexes` is greater than or equal to the number of leaves in the
+    ///   tree for which this batch proof was generated.
+    /// * List of indexes contains duplicates.
+    /// * The proof does not resolve to a single root.
+    pub fn verify_into_root(&self, indexes: &[usize]) -> Result<H::Digest, MerkleTreeError> {
         if indexes.is_empty() {
             return Err(MerkleTreeError::TooFewLeafIndexes);
         }
@@ -138,9 +160,6 @@ impl<H: Hasher> BatchMerkleProof<H> {
             ));
         }
 
-        let mut buf = [H::Digest::default(); 2];
-        let mut v = BTreeMap::new();
-
         // replace odd indexes, offset, and sort in ascending order
         let index_map = super::map_indexes(indexes, self.depth as usize)?;
         let indexes = super::normalize_indexes(indexes);
@@ -148,113 +167,79 @@ impl<H: Hasher> BatchMerkleProof<H> {
             return Err(MerkleTreeError::InvalidProof);
         }
 
-        // for each index use values to compute parent nodes
+        // for each index use values to compute parent nodes; since the indexes are sorted, the
+        // resulting list of (index, node) pairs is sorted as well
         let offset = 2usize.pow(self.depth as u32);
-        let mut next_indexes: Vec<usize> = Vec::new();
+        let mut level: Vec<(usize, H::Digest)> = Vec::with_capacity(indexes.len());
         let mut proof_pointers: Vec<usize> = Vec::with_capacity(indexes.len());
         for (i, index) in indexes.into_iter().enumerate() {
-            // copy values of leaf sibling leaf nodes into the buffer
-            match index_map.get(&index) {
-                Some(&index1) => {
-                    if self.leaves.len() <= index1 {
-                        return Err(MerkleTreeError::InvalidProof);
-                    }
-                    buf[0] = self.leaves[index1];
-                    match index_map.get(&(index + 1)) {
-                        Some(&index2) => {
-                            if self.leaves.len() <= index2 {
-                                return Err(MerkleTreeError::InvalidProof);
-                            }
-                            buf[1] = self.leaves[index2];
-                            proof_pointers.push(0);
-                        }
-                        None => {
-                            if self.nodes[i].is_empty() {
-                                return Err(MerkleTreeError::InvalidProof);
-                            }
-                            buf[1] = self.nodes[i][0];
-                            proof_pointers.push(1);
-                        }
-                    }
-                }
-                None => {
-                    if self.nodes[i].is_empty() {
-                        return Err(MerkleTreeError::InvalidProof);
-                    }
-                    buf[0] = self.nodes[i][0];
-                    match index_map.get(&(index + 1)) {
-                        Some(&index2) => {
-                            if self.leaves.len() <= index2 {
-                                return Err(MerkleTreeError::InvalidProof);
-                            }
-                            buf[1] = self.leaves[index2];
-                        }
-                        None => return Err(MerkleTreeError::InvalidProof),
-                    }
-                    proof_pointers.push(1);
-                }
-            }
+            // get values of sibling leaf nodes either from the leaves or from the proof nodes
+            let left = match index_map.get(&index) {
+                Some(&idx) => self.leaves.get(idx),
+                None => self.nodes[i].first(),
+            };
+            let right = match index_map.get(&(index + 1)) {
+                Some(&idx) => self.leaves.get(idx),
+                None if index_map.contains_key(&index) => self.nodes[i].first(),
+                None => return Err(MerkleTreeError::InvalidProof),
+            };
+            let (left, right) = match (left, right) {
+                (Some(&left), Some(&right)) => (left, right),
+                _ => return Err(MerkleTreeError::InvalidProof),
+            };
+
+            // record how many proof nodes were consumed for this index
+            let both_leaves =
+                index_map.contains_key(&index) && index_map.contains_key(&(index + 1));
+            proof_pointers.push(if both_leaves { 0 } else { 1 });
 
             // hash sibling nodes into their parent
-            let parent = H::merge(&buf);
-
-            let parent_index = (offset + index) >> 1;
-            v.insert(parent_index, parent);
-            next_indexes.push(parent_index);
+            level.push(((offset + index) >> 1, H::merge(&[left, right])));
         }
 
-        // iteratively move up, until we get to the root
+        // iteratively move up, until we get to the root; parents are written in place over the
+        // current level because there are never more parents than nodes processed so far
         for _ in 1..self.depth {
-            let indexes = next_indexes.clone();
-            next_indexes.truncate(0);
-
+            let mut num_parents = 0;
             let mut i = 0;
-            while i < indexes.len() {
-                let node_index = indexes[i];
+            while i < level.len() {
+                let (node_index, node) = level[i];
                 let sibling_index = node_index ^ 1;
 
                 // determine the sibling
-                let sibling: H::Digest;
-                if i + 1 < indexes.len() && indexes[i + 1] == sibling_index {
-                    sibling = match v.get(&sibling_index) {
-                        Some(sibling) => *sibling,
-                        None => return Err(MerkleTreeError::InvalidProof),
-                    };
+                let sibling = if i + 1 < level.len() && level[i + 1].0 == sibling_index {
                     i += 1;
+                    level[i].1
                 } else {
                     let pointer = proof_pointers[i];
-                    if self.nodes[i].len() <= pointer {
-                        return Err(MerkleTreeError::InvalidProof);
-                    }
-                    sibling = self.nodes[i][pointer];
+                    let sibling = match self.nodes[i].get(pointer) {
+                        Some(sibling) => *sibling,
+                        None => return Err(MerkleTreeError::InvalidProof),
+                    };
                     proof_pointers[i] += 1;
-                }
-
-                // get the node from the map of hashed nodes
-                let node = match v.get(&node_index) {
-                    Some(node) => node,
-                    None => return Err(MerkleTreeError::InvalidProof),
+                    sibling
                 };
 
                 // compute parent node from node and sibling
-                if node_index & 1 != 0 {
-                    buf[0] = sibling;
-                    buf[1] = *node;
+                let parent = if node_index & 1 != 0 {
+                    H::merge(&[sibling, node])
                 } else {
-                    buf[0] = *node;
-                    buf[1] = sibling;
-                }
-                let parent = H::merge(&buf);
+                    H::merge(&[node, sibling])
+                };
 
-                // add the parent node to the next set of nodes
-                let parent_index = node_index >> 1;
-                v.insert(parent_index, parent);
-                next_indexes.push(parent_index);
+                // add the parent node to the next level
+                level[num_parents] = (node_index >> 1, parent);
+                num_parents += 1;
 
                 i += 1;
             }
+            level.truncate(num_parents);
+        }
+
+        match level.as_slice() {
+            [(1, root)] => Ok(*root),
+            _ => Err(MerkleTreeError::InvalidProof),
         }
-        v.remove(&1).ok_or(MerkleTreeError::InvalidProof)
     }
 
     /// Computes the uncompressed Merkle paths which aggregate to this proof.
@@ -262,7 +247,7 @@ impl<H: Hasher> BatchMerkleProof<H> {
     /// # Errors
     /// Returns an error if:
     /// * No indexes were provided (i.e., `indexes` is an empty slice).
-    /// * Number of provided indexes is greater than 255.
+    /// * Number of provided indexes is greater than 65535.
     /// * Number of provided indexes does not match the number of leaf nodes in the proof.
     pub fn into_paths(self, indexes: &[usize]) -> Result<Vec<Vec<H::Digest>>, MerkleTreeError> {
         if indexes.is_empty() {
@@ -415,14 +400,14 @@ impl<H: Hasher> BatchMerkleProof<H> {
     ///
     /// # Panics
     /// Panics if:
-    /// * The proof contains more than 255 Merkle paths.
+    /// * The proof contains more than 65535 Merkle paths.
     /// * The Merkle paths consist of more than 255 nodes.
     pub fn serialize_nodes(&self) -> Vec<u8> {
         let mut result = Vec::new();
 
         // record total number of node vectors
-        assert!(self.nodes.len() <= u8::MAX as usize, "too many paths");
-        result.push(self.nodes.len() as u8);
+        assert!(self.nodes.len() <= MAX_PATHS, "too many paths");
+        result.write_usize(self.nodes.len());
 
         // record each node vector as individual bytes
         for nodes in self.nodes.iter() {
@@ -443,7 +428,7 @@ impl<H: Hasher> BatchMerkleProof<H> {
     /// # Errors
     /// Returns an error if:
     /// * No leaves were provided (i.e., `leaves` is an empty slice).
-    /// * Number of provided leaves is greater than 255.
+    /// * Number of provided leaves is greater than 65535.
     /// * Tree `depth` was set to zero.
     /// * `node_bytes` could not be deserialized into a valid set of internal nodes.
     pub fn deserialize<R: ByteReader>(
@@ -469,7 +454,7 @@ impl<H: Hasher> BatchMerkleProof<H> {
             )));
         }
 
-        let num_node_vectors = node_bytes.read_u8()? as usize;
+        let num_node_vectors = node_bytes.read_usize_bounded(MAX_PATHS)?;
         let mut nodes = Vec::with_capacity(num_node_vectors);
         for _ in 0..num_node_vectors {
             // read the number of digests in the vector
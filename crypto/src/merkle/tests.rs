@@ -6,6 +6,7 @@
 use super::*;
 use math::fields::f128::BaseElement;
 use proptest::prelude::*;
+use utils::{Serializable, SliceReader};
 
 type Digest256 = crate::hash::ByteDigest<32>;
 type Blake3_256 = crate::hash::Blake3_256<BaseElement>;
@@ -89,6 +90,13 @@ fn new_tree() {
         ),
     );
     assert_eq!(&root, tree.root());
+
+    // internal nodes are stored level by level starting with the root at position 1
+    let nodes = tree.nodes();
+    assert_eq!(8, nodes.len());
+    assert_eq!(root, nodes[1]);
+    assert_eq!(hash_2x1(leaves[2], leaves[3]), nodes[5]);
+    assert_eq!(hash_2x1(nodes[6], nodes[7]), nodes[3]);
 }
 
 #[test]
@@ -232,6 +240,29 @@ fn verify_batch() {
     assert!(MerkleTree::verify_batch(tree.root(), &[0, 1, 2, 3, 4, 5, 6, 7], &proof).is_ok());
 }
 
+#[test]
+fn verify_into_root() {
+    let leaves = Digest256::bytes_as_digests(&LEAVES8).to_vec();
+    let tree = MerkleTree::<Blake3_256>::new(leaves).unwrap();
+
+    let proof = tree.prove_batch(&[1]).unwrap();
+    assert_eq!(*tree.root(), proof.verify_into_root(&[1]).unwrap());
+    assert_ne!(*tree.root(), proof.verify_into_root(&[2]).unwrap());
+
+    let proof = tree.prove_batch(&[1, 2]).unwrap();
+    assert_eq!(*tree.root(), proof.verify_into_root(&[1, 2]).unwrap());
+    assert!(proof.verify_into_root(&[1]).is_err());
+    assert!(proof.verify_into_root(&[1, 1]).is_err());
+    assert!(proof.verify_into_root(&[]).is_err());
+
+    let proof = tree.prove_batch(&[6, 3, 1]).unwrap();
+    assert_eq!(*tree.root(), proof.verify_into_root(&[6, 3, 1]).unwrap());
+
+    let proof = tree.prove_batch(&[0, 1, 2, 3, 4, 5, 6, 7]).unwrap();
+    let indexes = [0, 1, 2, 3, 4, 5, 6, 7];
+    assert_eq!(*tree.root(), proof.verify_into_root(&indexes).unwrap());
+}
+
 #[test]
 fn verify_into_paths() {
     let leaves = Digest256::bytes_as_digests(&LEAVES8).to_vec();
@@ -256,6 +287,52 @@ fn verify_into_paths() {
     assert_eq!(proof6, result[2]);
 }
 
+#[test]
+fn large_digest_tree() {
+    // build a tree with 48-byte digests to make sure the tree does not depend on digest size
+    let leaves = (0..8u8).map(|i| Blake3_384::hash(&[i])).collect::<Vec<_>>();
+    let tree = MerkleTree::<Blake3_384>::new(leaves.clone()).unwrap();
+    assert_eq!(3, tree.depth());
+
+    let proof = tree.prove(6).unwrap();
+    assert!(MerkleTree::<Blake3_384>::verify(*tree.root(), 6, &proof).is_ok());
+
+    let proof = tree.prove_batch(&[1, 3, 6]).unwrap();
+    assert!(MerkleTree::verify_batch(tree.root(), &[1, 3, 6], &proof).is_ok());
+
+    // internal nodes survive serialization round-trip
+    let node_bytes = proof.serialize_nodes();
+    let parsed = BatchMerkleProof::<Blake3_384>::deserialize(
+        &mut SliceReader::new(&node_bytes),
+        proof.leaves.clone(),
+        proof.depth,
+    )
+    .unwrap();
+    assert_eq!(proof, parsed);
+}
+
+#[test]
+fn many_paths_batch_proof() {
+    // aggregate more than 255 paths to make sure node counts are not truncated
+    let leaves = (0..1024u32)
+        .map(|i| Blake3_256::hash(&i.to_le_bytes()))
+        .collect::<Vec<_>>();
+    let tree = MerkleTree::<Blake3_256>::new(leaves).unwrap();
+    let indexes = (0..300).map(|i| i * 3).collect::<Vec<_>>();
+
+    let proof = tree.prove_batch(&indexes).unwrap();
+    assert!(MerkleTree::verify_batch(tree.root(), &indexes, &proof).is_ok());
+
+    let node_bytes = proof.serialize_nodes();
+    let parsed = BatchMerkleProof::<Blake3_256>::deserialize(
+        &mut SliceReader::new(&node_bytes),
+        proof.leaves.clone(),
+        proof.depth,
+    )
+    .unwrap();
+    assert_eq!(proof, parsed);
+}
+
 proptest! {
     #[test]
     fn prove_n_verify(tree in random_blake3_merkle_tree(128),
@@ -319,6 +396,35 @@ fn hash_2x1(v1: Digest256, v2: Digest256) -> Digest256 {
     Blake3_256::merge(&[v1, v2])
 }
 
+/// BLAKE3 hasher with 384-bit output used to test trees with digests larger than 32 bytes.
+#[derive(Debug, PartialEq, Eq)]
+struct Blake3_384;
+
+impl Hasher for Blake3_384 {
+    type Digest = crate::hash::ByteDigest<48>;
+
+    const COLLISION_RESISTANCE: u32 = 192;
+
+    fn hash(bytes: &[u8]) -> Self::Digest {
+        let mut result = [0; 48];
+        blake3::Hasher::new()
+            .update(bytes)
+            .finalize_xof()
+            .fill(&mut result);
+        crate::hash::ByteDigest::new(result)
+    }
+
+    fn merge(values: &[Self::Digest; 2]) -> Self::Digest {
+        Self::hash(crate::hash::ByteDigest::digests_as_bytes(values))
+    }
+
+    fn merge_with_int(seed: Self::Digest, value: u64) -> Self::Digest {
+        let mut data = seed.to_bytes();
+        data.extend_from_slice(&value.to_le_bytes());
+        Self::hash(&data)
+    }
+}
+
 pub fn random_blake3_merkle_tree(
     leave_count: usize,
 ) -> impl Strategy<Value = MerkleTree<Blake3_256>> {
@@ -0,0 +1,118 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use crate::{Digest, Hasher, RandomCoin};
+use utils::collections::Vec;
+
+#[cfg(test)]
+mod tests;
+
+// PROOF OF WORK TRAIT
+// ================================================================================================
+
+/// Defines a proof-of-work function used for query seed grinding.
+///
+/// A proof-of-work function maps a seed and a nonce to a digest; the amount of work performed
+/// for a nonce is measured by the number of leading zeros in this digest. A prover searches for
+/// a nonce for which the number of leading zeros is at least the grinding factor, while a
+/// verifier needs to evaluate the function only once.
+pub trait ProofOfWork {
+    /// Evaluates the proof-of-work function for the specified `seed` and `nonce`, and returns
+    /// the number of leading zeros in the result.
+    ///
+    /// Leading zeros are counted in the same way as by [RandomCoin::leading_zeros()]: the first
+    /// 8 bytes of the resulting digest are interpreted as a little-endian integer, and trailing
+    /// zeros of this integer are counted.
+    fn leading_zeros<H: Hasher>(seed: H::Digest, nonce: u64) -> u32;
+
+    /// Evaluates the proof-of-work function for the current seed of the specified `coin` and
+    /// `nonce`, and returns the number of leading zeros in the result.
+    ///
+    /// Returns None if the function cannot be evaluated because the coin does not expose its
+    /// seed via [RandomCoin::seed()].
+    fn check_coin<R: RandomCoin + ?Sized>(coin: &R, nonce: u64) -> Option<u32> {
+        coin.seed()
+            .map(|seed| Self::leading_zeros::<R::Hasher>(seed, nonce))
+    }
+}
+
+// HASH-BASED PROOF OF WORK
+// ================================================================================================
+
+/// Proof-of-work function which computes hash(`seed` || `nonce`).
+///
+/// Every nonce requires a single hash function invocation, and thus, nonces can be checked
+/// cheaply in parallel (e.g., on GPUs). This is the proof-of-work function used by default.
+pub struct HashProofOfWork;
+
+impl ProofOfWork for HashProofOfWork {
+    fn leading_zeros<H: Hasher>(seed: H::Digest, nonce: u64) -> u32 {
+        digest_leading_zeros(&H::merge_with_int(seed, nonce))
+    }
+
+    /// Evaluates the function via [RandomCoin::check_leading_zeros()], and thus, works for any
+    /// random coin.
+    fn check_coin<R: RandomCoin + ?Sized>(coin: &R, nonce: u64) -> Option<u32> {
+        Some(coin.check_leading_zeros(nonce))
+    }
+}
+
+// MEMORY-HARD PROOF OF WORK
+// ================================================================================================
+
+/// Memory-hard proof-of-work function built from the specified hash function.
+///
+/// The function follows the sequential memory-hard construction of scrypt's ROMix: first,
+/// hash(`seed` || `nonce`) is expanded into a table of [NUM_BLOCKS](Self::NUM_BLOCKS) digests by
+/// iterated hashing; then, the same number of data-dependent lookups into the table are mixed
+/// into the running digest. Evaluating the function for a single nonce thus requires about
+/// `2 * NUM_BLOCKS` hash function invocations and `NUM_BLOCKS` digests of memory, which makes
+/// massively parallel grinding (e.g., on GPUs) considerably less effective than for
+/// [HashProofOfWork].
+///
+/// The verifier also needs to evaluate the function once, and thus, verification of proofs using
+/// this function is more expensive as well.
+pub struct MemoryHardProofOfWork;
+
+impl MemoryHardProofOfWork {
+    /// Number of digests in the table built for every evaluation of the function.
+    pub const NUM_BLOCKS: usize = 1 << 14;
+}
+
+impl ProofOfWork for MemoryHardProofOfWork {
+    fn leading_zeros<H: Hasher>(seed: H::Digest, nonce: u64) -> u32 {
+        let mut state = H::merge_with_int(seed, nonce);
+
+        // fill the table with a sequence of iterated hashes
+        let mut blocks = Vec::with_capacity(Self::NUM_BLOCKS);
+        for i in 0..Self::NUM_BLOCKS {
+            blocks.push(state);
+            state = H::merge_with_int(state, i as u64);
+        }
+
+        // mix blocks selected by the current state into the state
+        for _ in 0..Self::NUM_BLOCKS {
+            let index = digest_head(&state) as usize % Self::NUM_BLOCKS;
+            state = H::merge(&[state, blocks[index]]);
+        }
+
+        digest_leading_zeros(&state)
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns the first 8 bytes of the specified digest interpreted as a little-endian integer.
+fn digest_head<D: Digest>(digest: &D) -> u64 {
+    let bytes = digest.as_bytes();
+    u64::from_le_bytes(bytes[..8].try_into().unwrap())
+}
+
+/// Returns the number of leading zeros in the specified digest as defined by
+/// [ProofOfWork::leading_zeros()].
+pub(crate) fn digest_leading_zeros<D: Digest>(digest: &D) -> u32 {
+    digest_head(digest).trailing_zeros()
+}
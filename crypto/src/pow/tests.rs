@@ -0,0 +1,112 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::*;
+use crate::{DefaultRandomCoin, RandomCoin, RandomCoinError};
+use math::{fields::f128::BaseElement, FieldElement};
+
+type Blake3_256 = crate::hash::Blake3_256<BaseElement>;
+
+#[test]
+fn hash_pow_matches_coin_leading_zeros() {
+    let seed = [BaseElement::new(1), BaseElement::new(2)];
+    let coin = DefaultRandomCoin::<Blake3_256>::new(&seed);
+
+    for nonce in 0..64 {
+        let expected = coin.check_leading_zeros(nonce);
+        assert_eq!(
+            Some(expected),
+            coin.check_proof_of_work::<HashProofOfWork>(nonce)
+        );
+
+        let mut reseeded = DefaultRandomCoin::<Blake3_256>::new(&seed);
+        reseeded.reseed_with_int(nonce);
+        assert_eq!(expected, reseeded.leading_zeros());
+    }
+}
+
+#[test]
+fn memory_hard_pow() {
+    let seed = Blake3_256::hash(&[1, 2, 3, 4]);
+
+    // the function is deterministic and depends on both the seed and the nonce
+    let nonce = (0..u64::MAX)
+        .find(|&nonce| MemoryHardProofOfWork::leading_zeros::<Blake3_256>(seed, nonce) >= 4)
+        .unwrap();
+    assert!(MemoryHardProofOfWork::leading_zeros::<Blake3_256>(seed, nonce) >= 4);
+
+    let seeds = (0..16).map(|i| Blake3_256::hash(&[i]));
+    let results = seeds
+        .map(|seed| MemoryHardProofOfWork::leading_zeros::<Blake3_256>(seed, nonce))
+        .collect::<Vec<_>>();
+    assert!(results.iter().any(|&zeros| zeros < 4));
+}
+
+#[test]
+fn pow_with_seedless_coin() {
+    let seed = [BaseElement::new(1), BaseElement::new(2)];
+    let coin = DefaultRandomCoin::<Blake3_256>::new(&seed);
+    let seedless = SeedlessCoin::new(&seed);
+
+    // hash-based proof of work is evaluated via check_leading_zeros() for any coin
+    for nonce in 0..64 {
+        assert_eq!(
+            coin.check_proof_of_work::<HashProofOfWork>(nonce),
+            seedless.check_proof_of_work::<HashProofOfWork>(nonce)
+        );
+    }
+
+    // other proof-of-work functions require access to the seed of the coin
+    assert!(coin
+        .check_proof_of_work::<MemoryHardProofOfWork>(1)
+        .is_some());
+    assert_eq!(
+        None,
+        seedless.check_proof_of_work::<MemoryHardProofOfWork>(1)
+    );
+}
+
+// SEEDLESS RANDOM COIN
+// ================================================================================================
+
+/// A random coin which implements only the required methods of [RandomCoin].
+struct SeedlessCoin(DefaultRandomCoin<Blake3_256>);
+
+impl RandomCoin for SeedlessCoin {
+    type BaseField = BaseElement;
+    type Hasher = Blake3_256;
+
+    fn new(seed: &[Self::BaseField]) -> Self {
+        Self(DefaultRandomCoin::new(seed))
+    }
+
+    fn reseed(&mut self, data: <Self::Hasher as Hasher>::Digest) {
+        self.0.reseed(data)
+    }
+
+    fn reseed_with_int(&mut self, value: u64) {
+        self.0.reseed_with_int(value)
+    }
+
+    fn leading_zeros(&self) -> u32 {
+        self.0.leading_zeros()
+    }
+
+    fn check_leading_zeros(&self, value: u64) -> u32 {
+        self.0.check_leading_zeros(value)
+    }
+
+    fn draw<E: FieldElement<BaseField = Self::BaseField>>(&mut self) -> Result<E, RandomCoinError> {
+        self.0.draw()
+    }
+
+    fn draw_integers(
+        &mut self,
+        num_values: usize,
+        domain_size: usize,
+    ) -> Result<Vec<usize>, RandomCoinError> {
+        self.0.draw_integers(num_values, domain_size)
+    }
+}
@@ -3,10 +3,14 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use crate::{errors::RandomCoinError, Digest, ElementHasher, RandomCoin};
+use crate::{
+    errors::RandomCoinError,
+    pow::{digest_leading_zeros, HashProofOfWork},
+    Digest, ElementHasher, ProofOfWork, RandomCoin,
+};
 use core::convert::TryInto;
 use math::{FieldElement, StarkField};
-use utils::collections::Vec;
+use utils::collections::{BTreeMap, Vec};
 
 // DEFAULT RANDOM COIN IMPLEMENTATION
 // ================================================================================================
@@ -72,6 +76,23 @@ impl<H: ElementHasher> DefaultRandomCoin<H> {
         self.counter += 1;
         H::merge_with_int(self.seed, self.counter)
     }
+
+    /// Returns an integer drawn uniformly at random from the range [0, bound).
+    ///
+    /// Pseudo-random values are masked to the smallest power of two which is not smaller than
+    /// `bound`, and masked values which are not smaller than `bound` are rejected. Thus, each
+    /// call to the PRNG succeeds with probability greater than 1/2.
+    fn draw_below(&mut self, bound: usize) -> usize {
+        let mask = (bound.next_power_of_two() - 1) as u64;
+        loop {
+            // get the next pseudo-random value and read the first 8 bytes from it
+            let bytes: [u8; 8] = self.next().as_bytes()[..8].try_into().unwrap();
+            let value = u64::from_le_bytes(bytes) & mask;
+            if value < bound as u64 {
+                return value as usize;
+            }
+        }
+    }
 }
 
 impl<B: StarkField, H: ElementHasher<BaseField = B>> RandomCoin for DefaultRandomCoin<H> {
@@ -170,18 +191,18 @@ impl<B: StarkField, H: ElementHasher<BaseField = B>> RandomCoin for DefaultRando
     /// assert!(coin.leading_zeros() >= 2);
     /// ```
     fn leading_zeros(&self) -> u32 {
-        let bytes = self.seed.as_bytes();
-        let seed_head = u64::from_le_bytes(bytes[..8].try_into().unwrap());
-        seed_head.trailing_zeros()
+        digest_leading_zeros(&self.seed)
     }
 
     /// Computes hash(`seed` || `value`) and returns the number of leading zeros in the resulting
     /// value if it is interpreted as an integer in big-endian byte order.
     fn check_leading_zeros(&self, value: u64) -> u32 {
-        let new_seed = H::merge_with_int(self.seed, value);
-        let bytes = new_seed.as_bytes();
-        let seed_head = u64::from_le_bytes(bytes[..8].try_into().unwrap());
-        seed_head.trailing_zeros()
+        HashProofOfWork::leading_zeros::<H>(self.seed, value)
+    }
+
+    /// Returns the current seed of this coin.
+    fn seed(&self) -> Option<H::Digest> {
+        Some(self.seed)
     }
 
     // DRAW METHODS
@@ -210,9 +231,14 @@ impl<B: StarkField, H: ElementHasher<BaseField = B>> RandomCoin for DefaultRando
 
     /// Returns a vector of unique integers selected from the range [0, domain_size).
     ///
+    /// The integers are selected via a partial Fisher-Yates shuffle of the domain. Each step of
+    /// the shuffle draws a position uniformly at random by masking a pseudo-random value to the
+    /// next power of two and rejecting values outside of the remaining range. Thus, the number
+    /// of draws does not depend on collisions between drawn values, and the expected number of
+    /// calls to the PRNG is smaller than `2 * num_values`.
+    ///
     /// # Errors
-    /// Returns an error if the specified number of unique integers could not be generated
-    /// after 1000 calls to the PRNG.
+    /// This implementation never returns an error.
     ///
     /// # Panics
     /// Panics if:
@@ -255,34 +281,19 @@ impl<B: StarkField, H: ElementHasher<BaseField = B>> RandomCoin for DefaultRando
             "number of values must be smaller than domain size"
         );
 
-        // determine how many bits are needed to represent valid values in the domain
-        let v_mask = (domain_size - 1) as u64;
+        // draw values using a partial Fisher-Yates shuffle of the domain: at step i, a position
+        // j is drawn from [i, domain_size), and the values at positions i and j are swapped. Only
+        // the swapped positions are tracked, and thus, the shuffle requires memory proportional
+        // to num_values.
+        let mut swaps = BTreeMap::new();
+        let mut values = Vec::with_capacity(num_values);
+        for i in 0..num_values {
+            let j = i + self.draw_below(domain_size - i);
 
-        // draw values from PRNG until we get as many unique values as specified by num_queries
-        let mut values = Vec::new();
-        for _ in 0..1000 {
-            // get the next pseudo-random value and read the first 8 bytes from it
-            let bytes: [u8; 8] = self.next().as_bytes()[..8].try_into().unwrap();
-
-            // convert to integer and limit the integer to the number of bits which can fit
-            // into the specified domain
-            let value = (u64::from_le_bytes(bytes) & v_mask) as usize;
-
-            if values.contains(&value) {
-                continue;
-            }
+            let value = *swaps.get(&j).unwrap_or(&j);
+            let replacement = *swaps.get(&i).unwrap_or(&i);
+            swaps.insert(j, replacement);
             values.push(value);
-            if values.len() == num_values {
-                break;
-            }
-        }
-
-        if values.len() < num_values {
-            return Err(RandomCoinError::FailedToDrawIntegers(
-                num_values,
-                values.len(),
-                1000,
-            ));
         }
 
         Ok(values)
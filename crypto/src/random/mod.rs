@@ -3,13 +3,16 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use crate::{errors::RandomCoinError, ElementHasher, Hasher};
+use crate::{errors::RandomCoinError, ElementHasher, Hasher, ProofOfWork};
 use math::{FieldElement, StarkField};
 use utils::collections::Vec;
 
 mod default;
 pub use default::DefaultRandomCoin;
 
+#[cfg(test)]
+mod tests;
+
 // RANDOM COIN TRAIT
 // ================================================================================================
 
@@ -58,8 +61,7 @@ pub trait RandomCoin: Sync {
     /// Returns a vector of unique integers selected from the range [0, domain_size).
     ///
     /// # Errors
-    /// Returns an error if the specified number of unique integers could not be generated
-    /// after 1000 calls to the PRNG.
+    /// Returns an error if the specified number of unique integers could not be generated.
     ///
     /// # Panics
     /// Panics if:
@@ -74,6 +76,26 @@ pub trait RandomCoin: Sync {
     // PROVIDED METHODS
     // --------------------------------------------------------------------------------------------
 
+    /// Returns the current seed of this coin, or None if the coin does not expose its seed.
+    ///
+    /// The seed is required to evaluate proof-of-work functions other than
+    /// [HashProofOfWork](crate::HashProofOfWork) (see [RandomCoin::check_proof_of_work()]). The
+    /// default implementation returns None.
+    fn seed(&self) -> Option<<Self::Hasher as Hasher>::Digest> {
+        None
+    }
+
+    /// Evaluates the proof-of-work function `P` for the current seed and the specified `nonce`,
+    /// and returns the number of leading zeros in the result.
+    ///
+    /// For [HashProofOfWork](crate::HashProofOfWork), this is the same as
+    /// [RandomCoin::check_leading_zeros()]. Other proof-of-work functions are evaluated over the
+    /// seed returned by [RandomCoin::seed()]; None is returned if the coin does not expose its
+    /// seed.
+    fn check_proof_of_work<P: ProofOfWork>(&self, nonce: u64) -> Option<u32> {
+        P::check_coin(self, nonce)
+    }
+
     /// Returns the next pair of pseudo-random field elements.
     ///
     /// # Errors
@@ -0,0 +1,53 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::*;
+use math::fields::f128::BaseElement;
+use utils::collections::BTreeSet;
+
+type Blake3_256 = crate::hash::Blake3_256<BaseElement>;
+
+// DRAW INTEGERS
+// ================================================================================================
+
+#[test]
+fn draw_integers_near_full_domain() {
+    let seed = [BaseElement::new(1), BaseElement::new(2)];
+    let domain_size = 256;
+    let num_values = domain_size - 1;
+
+    let mut coin = DefaultRandomCoin::<Blake3_256>::new(&seed);
+    let values = coin.draw_integers(num_values, domain_size).unwrap();
+
+    let value_set = values.iter().copied().collect::<BTreeSet<_>>();
+    assert_eq!(num_values, value_set.len());
+    assert!(values.iter().all(|&v| v < domain_size));
+
+    // the same seed must result in the same values
+    let mut coin = DefaultRandomCoin::<Blake3_256>::new(&seed);
+    assert_eq!(values, coin.draw_integers(num_values, domain_size).unwrap());
+}
+
+#[test]
+fn draw_integers_uniform() {
+    let domain_size = 8;
+    let num_seeds = 8000;
+
+    // every value must be drawn at every position of the output with the same probability; in
+    // particular, this holds for positions drawn from ranges which are not powers of two
+    let mut counts = [[0usize; 8]; 7];
+    for i in 0..num_seeds {
+        let mut coin = DefaultRandomCoin::<Blake3_256>::new(&[BaseElement::new(i)]);
+        let values = coin.draw_integers(domain_size - 1, domain_size).unwrap();
+        for (position, &value) in values.iter().enumerate() {
+            counts[position][value] += 1;
+        }
+    }
+
+    let expected = num_seeds as usize / domain_size;
+    for count in counts.iter().flatten() {
+        assert!(count.abs_diff(expected) < expected / 5);
+    }
+}
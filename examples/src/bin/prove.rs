@@ -0,0 +1,132 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use examples::{
+    cli::{self, AirId, CliInputs},
+    HashFunction,
+};
+use std::{fs, path::PathBuf, process};
+use structopt::StructOpt;
+use winterfell::{
+    math::{fields::f128::BaseElement, StarkField},
+    params::Preset,
+    FieldExtension, ProofOptions,
+};
+
+// PROVE OPTIONS
+// ================================================================================================
+
+#[derive(StructOpt, Debug)]
+#[structopt(
+    name = "winterfell-prove",
+    about = "Generate a STARK proof for an execution trace read from a file"
+)]
+struct ProveOptions {
+    /// Path to the serialized execution trace
+    #[structopt(long = "trace", parse(from_os_str))]
+    trace: PathBuf,
+
+    /// Name of the built-in AIR defining transition constraints (fib2, mulfib2)
+    #[structopt(long = "air")]
+    air: String,
+
+    /// Path to a JSON file with assertions against the execution trace
+    #[structopt(long = "assertions", parse(from_os_str))]
+    assertions: PathBuf,
+
+    /// Path to which the generated proof is written
+    #[structopt(short = "o", long = "output", parse(from_os_str))]
+    output: PathBuf,
+
+    /// Hash function used in the protocol
+    #[structopt(short = "h", long = "hash_fn", default_value = "blake3_256")]
+    hash_fn: String,
+
+    /// Number of queries to include in a proof
+    #[structopt(short = "q", long = "queries", default_value = "28")]
+    num_queries: usize,
+
+    /// Blowup factor for low degree extension
+    #[structopt(short = "b", long = "blowup", default_value = "8")]
+    blowup_factor: usize,
+
+    /// Grinding factor for query seed
+    #[structopt(short = "g", long = "grinding", default_value = "16")]
+    grinding_factor: u32,
+
+    /// Field extension degree for composition polynomial
+    #[structopt(short = "e", long = "field_extension", default_value = "1")]
+    field_extension: u32,
+
+    /// Folding factor for FRI protocol
+    #[structopt(short = "f", long = "folding", default_value = "8")]
+    folding_factor: usize,
+
+    /// Name of a parameter preset (fast, balanced, recursion_friendly, small_proof); when set,
+    /// queries, blowup, grinding, field extension, and folding options are ignored
+    #[structopt(short = "p", long = "preset")]
+    preset: Option<String>,
+
+    /// Target security level in bits for the parameter preset
+    #[structopt(short = "s", long = "security", default_value = "100")]
+    security_level: u32,
+}
+
+impl ProveOptions {
+    fn to_proof_options<B: StarkField>(&self) -> Result<ProofOptions, String> {
+        let field_extension = match self.field_extension {
+            1 => FieldExtension::None,
+            2 => FieldExtension::Quadratic,
+            3 => FieldExtension::Cubic,
+            val => return Err(format!("'{val}' is not a valid field extension option")),
+        };
+
+        let options = match self.preset.as_deref() {
+            Some(name) => Preset::from_name(name)
+                .ok_or_else(|| format!("'{name}' is not a valid preset option"))?
+                .options::<B>(self.security_level),
+            None => ProofOptions::new(
+                self.num_queries,
+                self.blowup_factor,
+                self.grinding_factor,
+                field_extension,
+                self.folding_factor,
+                31,
+            ),
+        };
+        Ok(options)
+    }
+}
+
+// PROVER
+// ================================================================================================
+
+fn main() {
+    let options = ProveOptions::from_args();
+    if let Err(err) = run(&options) {
+        eprintln!("error: {err}");
+        process::exit(1);
+    }
+}
+
+fn run(options: &ProveOptions) -> Result<(), String> {
+    let air = AirId::from_name(&options.air)
+        .ok_or_else(|| format!("'{}' is not a valid AIR option", options.air))?;
+    let hash_fn = HashFunction::from_name(&options.hash_fn)
+        .ok_or_else(|| format!("'{}' is not a valid hash function option", options.hash_fn))?;
+
+    let trace = fs::read(&options.trace)
+        .map_err(|err| format!("failed to read {}: {err}", options.trace.display()))?;
+    let trace = cli::read_trace(&trace).map_err(|err| format!("invalid trace: {err}"))?;
+    let assertions = fs::read_to_string(&options.assertions)
+        .map_err(|err| format!("failed to read {}: {err}", options.assertions.display()))?;
+    let assertions = cli::parse_assertions(&assertions)?;
+
+    let proof_options = options.to_proof_options::<BaseElement>()?;
+    let proof = cli::prove(trace, CliInputs { air, assertions }, proof_options, hash_fn)?;
+
+    fs::write(&options.output, proof.to_bytes())
+        .map_err(|err| format!("failed to write {}: {err}", options.output.display()))
+}
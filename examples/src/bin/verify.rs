@@ -0,0 +1,68 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use examples::{
+    cli::{self, AirId, CliInputs},
+    HashFunction,
+};
+use std::{fs, path::PathBuf, process};
+use structopt::StructOpt;
+use winterfell::StarkProof;
+
+// VERIFY OPTIONS
+// ================================================================================================
+
+#[derive(StructOpt, Debug)]
+#[structopt(
+    name = "winterfell-verify",
+    about = "Verify a STARK proof generated by winterfell-prove"
+)]
+struct VerifyOptions {
+    /// Path to the proof
+    #[structopt(long = "proof", parse(from_os_str))]
+    proof: PathBuf,
+
+    /// Name of the built-in AIR defining transition constraints (fib2, mulfib2)
+    #[structopt(long = "air")]
+    air: String,
+
+    /// Path to a JSON file with assertions against the execution trace
+    #[structopt(long = "assertions", parse(from_os_str))]
+    assertions: PathBuf,
+
+    /// Hash function used in the protocol
+    #[structopt(short = "h", long = "hash_fn", default_value = "blake3_256")]
+    hash_fn: String,
+}
+
+// VERIFIER
+// ================================================================================================
+
+fn main() {
+    let options = VerifyOptions::from_args();
+    match run(&options) {
+        Ok(_) => println!("proof is valid"),
+        Err(err) => {
+            eprintln!("error: {err}");
+            process::exit(1);
+        }
+    }
+}
+
+fn run(options: &VerifyOptions) -> Result<(), String> {
+    let air = AirId::from_name(&options.air)
+        .ok_or_else(|| format!("'{}' is not a valid AIR option", options.air))?;
+    let hash_fn = HashFunction::from_name(&options.hash_fn)
+        .ok_or_else(|| format!("'{}' is not a valid hash function option", options.hash_fn))?;
+
+    let proof = fs::read(&options.proof)
+        .map_err(|err| format!("failed to read {}: {err}", options.proof.display()))?;
+    let proof = StarkProof::from_bytes(&proof).map_err(|err| format!("invalid proof: {err}"))?;
+    let assertions = fs::read_to_string(&options.assertions)
+        .map_err(|err| format!("failed to read {}: {err}", options.assertions.display()))?;
+    let assertions = cli::parse_assertions(&assertions)?;
+
+    cli::verify(proof, CliInputs { air, assertions }, hash_fn)
+}
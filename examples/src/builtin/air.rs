@@ -0,0 +1,76 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{BaseElement, FieldElement, ProofOptions, ALPHA};
+use winterfell::{
+    math::ToElements, Air, AirContext, Assertion, EvaluationFrame, TraceInfo,
+    TransitionConstraintDegree,
+};
+
+// PUBLIC INPUTS
+// ================================================================================================
+
+#[derive(Clone)]
+pub struct BuiltinInputs {
+    pub seed: BaseElement,
+    pub result: BaseElement,
+}
+
+impl ToElements<BaseElement> for BuiltinInputs {
+    fn to_elements(&self) -> Vec<BaseElement> {
+        vec![self.seed, self.result]
+    }
+}
+
+// BUILTIN AIR
+// ================================================================================================
+
+/// AIR for a trace with a preprocessed column of round constants in column 0 and the state of
+/// the builtin in column 1.
+pub struct BuiltinAir {
+    context: AirContext<BaseElement>,
+    seed: BaseElement,
+    result: BaseElement,
+}
+
+impl Air for BuiltinAir {
+    type BaseField = BaseElement;
+    type PublicInputs = BuiltinInputs;
+
+    fn new(trace_info: TraceInfo, pub_inputs: BuiltinInputs, options: ProofOptions) -> Self {
+        assert_eq!(1, trace_info.layout().num_preprocessed_columns());
+        let degrees = vec![TransitionConstraintDegree::new(3)];
+        Self {
+            context: AirContext::new(trace_info, degrees, 2, options),
+            seed: pub_inputs.seed,
+            result: pub_inputs.result,
+        }
+    }
+
+    fn evaluate_transition<E: FieldElement<BaseField = Self::BaseField>>(
+        &self,
+        frame: &EvaluationFrame<E>,
+        _periodic_values: &[E],
+        result: &mut [E],
+    ) {
+        let constant = frame.current()[0];
+        let current_state = frame.current()[1];
+        let next_state = frame.next()[1];
+
+        result[0] = next_state - (current_state.exp(ALPHA.into()) + constant);
+    }
+
+    fn get_assertions(&self) -> Vec<Assertion<Self::BaseField>> {
+        let last_step = self.trace_length() - 1;
+        vec![
+            Assertion::single(1, 0, self.seed),
+            Assertion::single(1, last_step, self.result),
+        ]
+    }
+
+    fn context(&self) -> &AirContext<Self::BaseField> {
+        &self.context
+    }
+}
@@ -0,0 +1,185 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use crate::{Blake3_192, Blake3_256, Example, ExampleOptions, HashFunction, Sha3_256};
+use log::debug;
+use std::time::Instant;
+use winterfell::{
+    crypto::{DefaultRandomCoin, ElementHasher},
+    math::{fields::f128::BaseElement, FieldElement},
+    ColMatrix, PreprocessedCommitment, ProofOptions, Prover, StarkProof, Trace, TraceTable,
+    VerifierError,
+};
+
+mod air;
+use air::{BuiltinAir, BuiltinInputs};
+
+mod prover;
+use prover::BuiltinProver;
+
+#[cfg(test)]
+mod tests;
+
+// CONSTANTS
+// ================================================================================================
+
+const ALPHA: u64 = 3;
+
+// BUILTIN EXAMPLE
+// ================================================================================================
+
+/// Applies a builtin function x' = x^3 + c_i to a seed value for a number of steps, where c_i are
+/// round constants fixed for all executions of the builtin.
+///
+/// Similarly to builtins of Cairo-like VMs, the round constants are placed into a preprocessed
+/// column of the execution trace. This column is committed to once at setup time, and the same
+/// commitment is reused for all proofs generated for this builtin; the verifier receives the
+/// commitment root at setup time as well.
+pub fn get_example(options: &ExampleOptions, num_steps: usize) -> Result<Box<dyn Example>, String> {
+    let (options, hash_fn) = options.to_proof_options(42, 8);
+
+    match hash_fn {
+        HashFunction::Blake3_192 => Ok(Box::new(BuiltinExample::<Blake3_192>::new(
+            num_steps, options,
+        ))),
+        HashFunction::Blake3_256 => Ok(Box::new(BuiltinExample::<Blake3_256>::new(
+            num_steps, options,
+        ))),
+        HashFunction::Sha3_256 => Ok(Box::new(BuiltinExample::<Sha3_256>::new(
+            num_steps, options,
+        ))),
+        _ => Err("The specified hash function cannot be used with this example.".to_string()),
+    }
+}
+
+pub struct BuiltinExample<H: ElementHasher<BaseField = BaseElement>> {
+    prover: BuiltinProver<H>,
+    preprocessed_root: H::Digest,
+    num_steps: usize,
+    seed: BaseElement,
+    result: BaseElement,
+}
+
+impl<H: ElementHasher<BaseField = BaseElement>> BuiltinExample<H> {
+    pub fn new(num_steps: usize, options: ProofOptions) -> Self {
+        assert!(
+            num_steps.is_power_of_two(),
+            "number of steps must be a power of 2"
+        );
+
+        // commit to the round constants; this needs to be done only once for all executions of
+        // the builtin with the same number of steps
+        let now = Instant::now();
+        let commitment = build_preprocessed_commitment::<H>(num_steps, &options);
+        let preprocessed_root = commitment.root();
+        debug!(
+            "Committed to round constants for {} steps in {} ms",
+            num_steps,
+            now.elapsed().as_millis()
+        );
+
+        // run the builtin
+        let now = Instant::now();
+        let seed = BaseElement::new(123);
+        let result = execute_builtin(seed, num_steps);
+        debug!(
+            "Executed the builtin for {} steps in {} ms",
+            num_steps,
+            now.elapsed().as_millis()
+        );
+
+        Self {
+            prover: BuiltinProver::new(options, commitment),
+            preprocessed_root,
+            num_steps,
+            seed,
+            result,
+        }
+    }
+}
+
+// EXAMPLE IMPLEMENTATION
+// ================================================================================================
+
+impl<H: ElementHasher> Example for BuiltinExample<H>
+where
+    H: ElementHasher<BaseField = BaseElement>,
+{
+    fn prove(&self) -> StarkProof {
+        debug!(
+            "Generating proof for executing a builtin for {} steps\n\
+            ---------------------",
+            self.num_steps
+        );
+
+        // generate execution trace
+        let now = Instant::now();
+        let trace = BuiltinProver::<H>::build_trace(self.seed, self.num_steps);
+
+        let trace_width = trace.width();
+        let trace_length = trace.length();
+        debug!(
+            "Generated execution trace of {} registers and 2^{} steps in {} ms",
+            trace_width,
+            trace_length.ilog2(),
+            now.elapsed().as_millis()
+        );
+
+        // generate the proof
+        self.prover.prove(trace).unwrap()
+    }
+
+    fn verify(&self, proof: StarkProof) -> Result<(), VerifierError> {
+        let pub_inputs = BuiltinInputs {
+            seed: self.seed,
+            result: self.result,
+        };
+        winterfell::verify_with_preprocessed::<BuiltinAir, H, DefaultRandomCoin<H>>(
+            proof,
+            pub_inputs,
+            self.preprocessed_root,
+        )
+    }
+
+    fn verify_with_wrong_inputs(&self, proof: StarkProof) -> Result<(), VerifierError> {
+        let pub_inputs = BuiltinInputs {
+            seed: self.seed,
+            result: self.result + BaseElement::ONE,
+        };
+        winterfell::verify_with_preprocessed::<BuiltinAir, H, DefaultRandomCoin<H>>(
+            proof,
+            pub_inputs,
+            self.preprocessed_root,
+        )
+    }
+}
+
+// BUILTIN FUNCTION
+// ================================================================================================
+
+/// Returns round constants for a builtin executed for `n` steps.
+fn build_round_constants(n: usize) -> Vec<BaseElement> {
+    (0..n as u128)
+        .map(|i| BaseElement::new(i * i + 42))
+        .collect()
+}
+
+/// Builds a commitment to the round constants of a builtin executed for `n` steps.
+fn build_preprocessed_commitment<H: ElementHasher<BaseField = BaseElement>>(
+    n: usize,
+    options: &ProofOptions,
+) -> PreprocessedCommitment<BaseElement, H> {
+    let columns = ColMatrix::new(vec![build_round_constants(n)]);
+    PreprocessedCommitment::new(columns, options)
+}
+
+fn execute_builtin(seed: BaseElement, n: usize) -> BaseElement {
+    let constants = build_round_constants(n);
+    let mut state = seed;
+    for &constant in constants.iter().take(n - 1) {
+        state = state.exp(ALPHA.into()) + constant;
+    }
+    state
+}
@@ -0,0 +1,75 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{
+    build_round_constants, BaseElement, BuiltinAir, BuiltinInputs, DefaultRandomCoin,
+    ElementHasher, FieldElement, PreprocessedCommitment, ProofOptions, Prover, Trace, TraceTable,
+    ALPHA,
+};
+
+// BUILTIN PROVER
+// ================================================================================================
+
+pub struct BuiltinProver<H: ElementHasher<BaseField = BaseElement>> {
+    options: ProofOptions,
+    preprocessed: PreprocessedCommitment<BaseElement, H>,
+}
+
+impl<H: ElementHasher<BaseField = BaseElement>> BuiltinProver<H> {
+    pub fn new(
+        options: ProofOptions,
+        preprocessed: PreprocessedCommitment<BaseElement, H>,
+    ) -> Self {
+        Self {
+            options,
+            preprocessed,
+        }
+    }
+
+    /// Builds an execution trace with round constants in the first (preprocessed) column and
+    /// states of the builtin in the second column.
+    pub fn build_trace(seed: BaseElement, n: usize) -> TraceTable<BaseElement> {
+        let constants = build_round_constants(n);
+        let mut states = Vec::with_capacity(n);
+        let mut state = seed;
+
+        states.push(state);
+        for &constant in constants.iter().take(n - 1) {
+            state = state.exp(ALPHA.into()) + constant;
+            states.push(state);
+        }
+
+        let mut trace = TraceTable::init(vec![constants, states]);
+        trace.set_num_preprocessed_columns(1);
+        trace
+    }
+}
+
+impl<H: ElementHasher> Prover for BuiltinProver<H>
+where
+    H: ElementHasher<BaseField = BaseElement>,
+{
+    type BaseField = BaseElement;
+    type Air = BuiltinAir;
+    type Trace = TraceTable<BaseElement>;
+    type HashFn = H;
+    type RandomCoin = DefaultRandomCoin<Self::HashFn>;
+
+    fn get_pub_inputs(&self, trace: &Self::Trace) -> BuiltinInputs {
+        let last_step = trace.length() - 1;
+        BuiltinInputs {
+            seed: trace.get(1, 0),
+            result: trace.get(1, last_step),
+        }
+    }
+
+    fn options(&self) -> &ProofOptions {
+        &self.options
+    }
+
+    fn preprocessed_commitment(&self) -> Option<&PreprocessedCommitment<BaseElement, H>> {
+        Some(&self.preprocessed)
+    }
+}
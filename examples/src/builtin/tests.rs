@@ -0,0 +1,206 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{
+    build_preprocessed_commitment, execute_builtin, BaseElement, BuiltinAir, BuiltinInputs,
+    BuiltinProver, Prover,
+};
+use crate::Blake3_256;
+use winterfell::{
+    crypto::DefaultRandomCoin, Air, ColMatrix, FieldExtension, PreparedVerifier,
+    PreprocessedCommitment, ProofOptions, ProverError, VerifierError,
+};
+
+#[test]
+fn builtin_test_basic_proof_verification() {
+    let builtin = Box::new(super::BuiltinExample::<Blake3_256>::new(
+        128,
+        build_options(false),
+    ));
+    crate::tests::test_basic_proof_verification(builtin);
+}
+
+#[test]
+fn builtin_test_basic_proof_verification_extension() {
+    let builtin = Box::new(super::BuiltinExample::<Blake3_256>::new(
+        128,
+        build_options(true),
+    ));
+    crate::tests::test_basic_proof_verification(builtin);
+}
+
+#[test]
+fn builtin_test_basic_proof_verification_fail() {
+    let builtin = Box::new(super::BuiltinExample::<Blake3_256>::new(
+        128,
+        build_options(false),
+    ));
+    crate::tests::test_basic_proof_verification_fail(builtin);
+}
+
+#[test]
+fn builtin_test_commitment_reused_across_proofs() {
+    let options = build_options(false);
+    let commitment = build_preprocessed_commitment::<Blake3_256>(64, &options);
+    let root = commitment.root();
+    let prover = BuiltinProver::new(options, commitment);
+
+    for seed in [BaseElement::new(1), BaseElement::new(2)] {
+        let trace = BuiltinProver::<Blake3_256>::build_trace(seed, 64);
+        let proof = prover.prove(trace).unwrap();
+        let pub_inputs = BuiltinInputs {
+            seed,
+            result: execute_builtin(seed, 64),
+        };
+        assert!(verify(proof, pub_inputs, root).is_ok());
+    }
+}
+
+#[test]
+fn builtin_test_prepared_verifier() {
+    let options = build_options(false);
+    let commitment = build_preprocessed_commitment::<Blake3_256>(64, &options);
+    let root = commitment.root();
+    let prover = BuiltinProver::new(options.clone(), commitment);
+
+    let proofs = [BaseElement::new(1), BaseElement::new(2)].map(|seed| {
+        let trace = BuiltinProver::<Blake3_256>::build_trace(seed, 64);
+        let pub_inputs = BuiltinInputs {
+            seed,
+            result: execute_builtin(seed, 64),
+        };
+        (prover.prove(trace).unwrap(), pub_inputs)
+    });
+
+    // a single prepared verifier verifies proofs for different public inputs
+    let air = BuiltinAir::new(proofs[0].0.get_trace_info(), proofs[0].1.clone(), options);
+    let verifier = PreparedVerifier::<BuiltinAir, Blake3_256, DefaultRandomCoin<Blake3_256>>::new(
+        &air,
+        Some(root),
+    )
+    .unwrap();
+    for (proof, pub_inputs) in proofs.iter() {
+        assert!(verifier.verify(proof.clone(), pub_inputs.clone()).is_ok());
+    }
+
+    // the preprocessed commitment is required for computations with preprocessed columns
+    let verifier =
+        PreparedVerifier::<BuiltinAir, Blake3_256, DefaultRandomCoin<Blake3_256>>::new(&air, None)
+            .unwrap();
+    let (proof, pub_inputs) = proofs[0].clone();
+    assert_eq!(
+        Err(VerifierError::PreprocessedCommitmentNotProvided),
+        verifier.verify(proof, pub_inputs)
+    );
+}
+
+#[test]
+fn builtin_test_wrong_preprocessed_commitment() {
+    let options = build_options(false);
+    let commitment = build_preprocessed_commitment::<Blake3_256>(64, &options);
+    let prover = BuiltinProver::new(options.clone(), commitment);
+
+    let seed = BaseElement::new(1);
+    let trace = BuiltinProver::<Blake3_256>::build_trace(seed, 64);
+    let proof = prover.prove(trace).unwrap();
+    let pub_inputs = BuiltinInputs {
+        seed,
+        result: execute_builtin(seed, 64),
+    };
+
+    // proofs verified against a commitment to different constants are rejected
+    let constants = (0..64).map(BaseElement::new).collect();
+    let other =
+        PreprocessedCommitment::<_, Blake3_256>::new(ColMatrix::new(vec![constants]), &options);
+    assert!(verify(proof.clone(), pub_inputs.clone(), other.root()).is_err());
+
+    // proofs cannot be verified without a commitment to preprocessed columns
+    assert_eq!(
+        Err(VerifierError::PreprocessedCommitmentNotProvided),
+        winterfell::verify::<BuiltinAir, Blake3_256, DefaultRandomCoin<Blake3_256>>(
+            proof, pub_inputs
+        )
+    );
+}
+
+#[test]
+fn builtin_test_query_subset_verification() {
+    let options = build_options(false);
+    let commitment = build_preprocessed_commitment::<Blake3_256>(64, &options);
+    let root = commitment.root();
+    let prover = BuiltinProver::new(options, commitment);
+
+    let seed = BaseElement::new(1);
+    let proof = prover
+        .prove(BuiltinProver::<Blake3_256>::build_trace(seed, 64))
+        .unwrap();
+    let pub_inputs = BuiltinInputs {
+        seed,
+        result: execute_builtin(seed, 64),
+    };
+    let verify_queries = |indexes: &[usize], root| {
+        winterfell::verify_queries::<BuiltinAir, Blake3_256, DefaultRandomCoin<Blake3_256>>(
+            proof.clone(),
+            pub_inputs.clone(),
+            indexes,
+            root,
+        )
+    };
+
+    // queries of proofs with preprocessed columns are verified against the preprocessed
+    // commitment
+    assert!(verify_queries(&[0, 5, 17], Some(root)).is_ok());
+    assert_eq!(
+        Err(VerifierError::PreprocessedCommitmentNotProvided),
+        verify_queries(&[0], None)
+    );
+    let constants = (0..64).map(BaseElement::new).collect();
+    let other = PreprocessedCommitment::<_, Blake3_256>::new(
+        ColMatrix::new(vec![constants]),
+        &build_options(false),
+    );
+    assert!(verify_queries(&[0], Some(other.root())).is_err());
+}
+
+#[test]
+fn builtin_test_mismatched_preprocessed_columns() {
+    let options = build_options(false);
+    let commitment = build_preprocessed_commitment::<Blake3_256>(64, &options);
+    let prover = BuiltinProver::new(options.clone(), commitment);
+
+    // trace of a different length than the preprocessed columns
+    let trace = BuiltinProver::<Blake3_256>::build_trace(BaseElement::new(1), 128);
+    assert!(matches!(
+        prover.prove(trace),
+        Err(ProverError::InvalidPreprocessedCommitment(_))
+    ));
+
+    // trace with preprocessed values different from the committed ones
+    let mut trace = BuiltinProver::<Blake3_256>::build_trace(BaseElement::new(1), 64);
+    trace.set(0, 3, BaseElement::new(7));
+    assert!(matches!(
+        prover.prove(trace),
+        Err(ProverError::InvalidTrace(_))
+    ));
+}
+
+fn verify(
+    proof: winterfell::StarkProof,
+    pub_inputs: BuiltinInputs,
+    root: <Blake3_256 as winterfell::crypto::Hasher>::Digest,
+) -> Result<(), VerifierError> {
+    winterfell::verify_with_preprocessed::<BuiltinAir, Blake3_256, DefaultRandomCoin<Blake3_256>>(
+        proof, pub_inputs, root,
+    )
+}
+
+fn build_options(use_extension_field: bool) -> ProofOptions {
+    let extension = if use_extension_field {
+        FieldExtension::Quadratic
+    } else {
+        FieldExtension::None
+    };
+    ProofOptions::new(42, 8, 0, extension, 4, 31)
+}
@@ -0,0 +1,146 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{BaseElement, FieldElement, ProofOptions};
+use crate::utils::are_equal;
+use winterfell::{
+    math::ToElements, Air, AirContext, Assertion, EvaluationFrame, TraceInfo,
+    TransitionConstraintDegree,
+};
+
+// AIR REGISTRY
+// ================================================================================================
+
+/// Identifies one of the built-in transition constraint evaluators which can be used to prove
+/// execution traces supplied as files.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum AirId {
+    /// Fibonacci sequence computed in 2 columns (2 terms per step).
+    Fib2,
+    /// Multiplicative Fibonacci sequence computed in 2 columns (2 terms per step).
+    Mulfib2,
+}
+
+impl AirId {
+    /// All available evaluators.
+    pub const ALL: [Self; 2] = [Self::Fib2, Self::Mulfib2];
+
+    /// Returns an evaluator with the specified name, or None if no such evaluator exists.
+    ///
+    /// Valid names are `fib2` and `mulfib2`.
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.iter().find(|air| air.name() == name).copied()
+    }
+
+    /// Returns the name of this evaluator.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Fib2 => "fib2",
+            Self::Mulfib2 => "mulfib2",
+        }
+    }
+
+    /// Returns the number of columns an execution trace must have for this evaluator.
+    pub fn trace_width(&self) -> usize {
+        match self {
+            Self::Fib2 | Self::Mulfib2 => 2,
+        }
+    }
+
+    /// Returns degrees of transition constraints defined by this evaluator.
+    fn constraint_degrees(&self) -> Vec<TransitionConstraintDegree> {
+        match self {
+            Self::Fib2 => vec![
+                TransitionConstraintDegree::new(1),
+                TransitionConstraintDegree::new(1),
+            ],
+            Self::Mulfib2 => vec![
+                TransitionConstraintDegree::new(2),
+                TransitionConstraintDegree::new(2),
+            ],
+        }
+    }
+}
+
+// PUBLIC INPUTS
+// ================================================================================================
+
+/// Public inputs of a computation proven via the command-line interface: the evaluator for its
+/// transition constraints and the assertions placed against its execution trace.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CliInputs {
+    pub air: AirId,
+    pub assertions: Vec<Assertion<BaseElement>>,
+}
+
+impl ToElements<BaseElement> for CliInputs {
+    fn to_elements(&self) -> Vec<BaseElement> {
+        let mut result = vec![BaseElement::from(self.air as u8)];
+        for assertion in self.assertions.iter() {
+            result.push(BaseElement::from(assertion.column() as u64));
+            result.push(BaseElement::from(assertion.first_step() as u64));
+            result.push(BaseElement::from(assertion.stride() as u64));
+            result.extend_from_slice(assertion.values());
+        }
+        result
+    }
+}
+
+// COMMAND-LINE AIR
+// ================================================================================================
+
+pub struct CliAir {
+    context: AirContext<BaseElement>,
+    air: AirId,
+    assertions: Vec<Assertion<BaseElement>>,
+}
+
+impl Air for CliAir {
+    type BaseField = BaseElement;
+    type PublicInputs = CliInputs;
+
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    fn new(trace_info: TraceInfo, pub_inputs: CliInputs, options: ProofOptions) -> Self {
+        assert_eq!(pub_inputs.air.trace_width(), trace_info.width());
+        let degrees = pub_inputs.air.constraint_degrees();
+        CliAir {
+            context: AirContext::new(trace_info, degrees, pub_inputs.assertions.len(), options),
+            air: pub_inputs.air,
+            assertions: pub_inputs.assertions,
+        }
+    }
+
+    fn context(&self) -> &AirContext<Self::BaseField> {
+        &self.context
+    }
+
+    fn evaluate_transition<E: FieldElement + From<Self::BaseField>>(
+        &self,
+        frame: &EvaluationFrame<E>,
+        _periodic_values: &[E],
+        result: &mut [E],
+    ) {
+        let current = frame.current();
+        let next = frame.next();
+        debug_assert_eq!(self.air.trace_width(), current.len());
+        debug_assert_eq!(self.air.trace_width(), next.len());
+
+        match self.air {
+            AirId::Fib2 => {
+                result[0] = are_equal(next[0], current[0] + current[1]);
+                result[1] = are_equal(next[1], current[1] + next[0]);
+            }
+            AirId::Mulfib2 => {
+                result[0] = are_equal(next[0], current[0] * current[1]);
+                result[1] = are_equal(next[1], current[1] * next[0]);
+            }
+        }
+    }
+
+    fn get_assertions(&self) -> Vec<Assertion<Self::BaseField>> {
+        self.assertions.clone()
+    }
+}
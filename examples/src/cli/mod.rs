@@ -0,0 +1,235 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+//! Support for proving and verifying computations supplied as files.
+//!
+//! This module backs the `winterfell-prove` and `winterfell-verify` binaries, which allow
+//! pipelines written in other languages to generate and verify proofs without writing any Rust.
+//! A computation is described by:
+//! * an execution trace file (see [write_trace()] for the format);
+//! * an identifier of one of the built-in transition constraint evaluators (see [AirId]);
+//! * a JSON file with assertions against the execution trace (see [parse_assertions()]).
+
+use crate::{Blake3_192, Blake3_256, HashFunction, Keccak256, Sha3_256};
+use core::marker::PhantomData;
+use core_utils::{ByteReader, ByteWriter, DeserializationError, SliceReader};
+use serde::Deserialize;
+use winterfell::{
+    crypto::{DefaultRandomCoin, ElementHasher},
+    math::{fields::f128::BaseElement, FieldElement, StarkField},
+    Assertion, Deserializable, ProofOptions, Prover, Serializable, StarkProof, Trace, TraceInfo,
+    TraceTable,
+};
+
+mod air;
+pub use air::{AirId, CliAir, CliInputs};
+
+mod prover;
+use prover::CliProver;
+
+#[cfg(test)]
+mod tests;
+
+// TRACE FILES
+// ================================================================================================
+
+/// Serializes the specified execution trace into a vector of bytes.
+///
+/// The trace is encoded as its width and length (each as a little-endian `u32`), followed by
+/// trace columns one after another. Every element is encoded as 16 little-endian bytes.
+pub fn write_trace(trace: &TraceTable<BaseElement>) -> Vec<u8> {
+    let mut target = Vec::new();
+    target.write_u32(trace.width() as u32);
+    target.write_u32(trace.length() as u32);
+    for i in 0..trace.width() {
+        BaseElement::write_batch_into(trace.get_column(i), &mut target);
+    }
+    target
+}
+
+/// Parses an execution trace serialized via [write_trace()].
+///
+/// # Errors
+/// Returns an error if the trace width is zero or greater than [TraceInfo::MAX_TRACE_WIDTH], if
+/// the trace length is not a power of two or is smaller than [TraceInfo::MIN_TRACE_LENGTH], or
+/// if the bytes do not encode a valid trace of the specified dimensions.
+pub fn read_trace(bytes: &[u8]) -> Result<TraceTable<BaseElement>, DeserializationError> {
+    let mut source = SliceReader::new(bytes);
+    let width = source.read_u32()? as usize;
+    if width == 0 || width > TraceInfo::MAX_TRACE_WIDTH {
+        return Err(DeserializationError::InvalidValue(format!(
+            "trace width must be between 1 and {}, but was {width}",
+            TraceInfo::MAX_TRACE_WIDTH
+        )));
+    }
+    let length = source.read_u32()? as usize;
+    if !length.is_power_of_two() || length < TraceInfo::MIN_TRACE_LENGTH {
+        return Err(DeserializationError::InvalidValue(format!(
+            "trace length must be a power of two and at least {}, but was {length}",
+            TraceInfo::MIN_TRACE_LENGTH
+        )));
+    }
+    source.check_eor(width * length * BaseElement::ELEMENT_BYTES)?;
+
+    let mut columns = Vec::with_capacity(width);
+    for _ in 0..width {
+        columns.push(BaseElement::read_batch_from(&mut source, length)?);
+    }
+    if source.has_more_bytes() {
+        return Err(DeserializationError::UnconsumedBytes);
+    }
+
+    Ok(TraceTable::init(columns))
+}
+
+// ASSERTION FILES
+// ================================================================================================
+
+/// Description of a single assertion in an assertions file.
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct AssertionSpec {
+    column: usize,
+    step: usize,
+    stride: Option<usize>,
+    value: String,
+}
+
+/// Parses assertions from the specified JSON string.
+///
+/// The JSON must be an array of objects of the form
+/// `{"column": 0, "step": 0, "value": "1"}`, where `value` is a decimal string (field elements
+/// do not fit into JSON numbers). An object may also contain a `stride` field, in which case
+/// the assertion is periodic: the value is asserted at `step`, `step + stride`, etc.
+///
+/// # Errors
+/// Returns an error if the JSON is malformed, if no assertions are specified, if a value is not
+/// a valid field element, or if a stride is not a power of two greater than one.
+pub fn parse_assertions(json: &str) -> Result<Vec<Assertion<BaseElement>>, String> {
+    let specs: Vec<AssertionSpec> =
+        serde_json::from_str(json).map_err(|err| format!("invalid assertions JSON: {err}"))?;
+    if specs.is_empty() {
+        return Err("at least one assertion must be specified".to_string());
+    }
+
+    specs
+        .into_iter()
+        .map(|spec| {
+            let value = spec
+                .value
+                .parse::<u128>()
+                .ok()
+                .filter(|&value| value < BaseElement::MODULUS)
+                .ok_or_else(|| format!("'{}' is not a valid field element", spec.value))?;
+            let value = BaseElement::new(value);
+            match spec.stride {
+                None => Ok(Assertion::single(spec.column, spec.step, value)),
+                Some(stride) if stride > 1 && stride.is_power_of_two() => {
+                    Ok(Assertion::periodic(spec.column, spec.step, stride, value))
+                }
+                Some(stride) => Err(format!(
+                    "stride must be a power of two greater than one, but was {stride}"
+                )),
+            }
+        })
+        .collect()
+}
+
+// PROVING AND VERIFICATION
+// ================================================================================================
+
+/// Generates a proof of the specified execution trace satisfying transition constraints of
+/// `inputs.air` and all assertions in `inputs.assertions`.
+///
+/// # Errors
+/// Returns an error if the trace does not match the shape expected by the evaluator or the
+/// assertions, if the hash function cannot be used with base field of the trace, or if proof
+/// generation fails (e.g., because the trace does not satisfy the constraints).
+pub fn prove(
+    trace: TraceTable<BaseElement>,
+    inputs: CliInputs,
+    options: ProofOptions,
+    hash_fn: HashFunction,
+) -> Result<StarkProof, String> {
+    if trace.width() != inputs.air.trace_width() {
+        return Err(format!(
+            "'{}' expects a trace of width {}, but the trace has width {}",
+            inputs.air.name(),
+            inputs.air.trace_width(),
+            trace.width()
+        ));
+    }
+    validate_assertions(&inputs, trace.length())?;
+
+    match hash_fn {
+        HashFunction::Blake3_192 => prove_with::<Blake3_192>(trace, inputs, options),
+        HashFunction::Blake3_256 => prove_with::<Blake3_256>(trace, inputs, options),
+        HashFunction::Sha3_256 => prove_with::<Sha3_256>(trace, inputs, options),
+        HashFunction::Keccak256 => prove_with::<Keccak256>(trace, inputs, options),
+        _ => Err("The specified hash function cannot be used with this trace.".to_string()),
+    }
+}
+
+/// Verifies that the specified proof attests to a computation with the specified public inputs.
+///
+/// # Errors
+/// Returns an error if the assertions are not valid for the trace described by the proof, if
+/// the hash function cannot be used with base field of the trace, or if the proof is invalid.
+pub fn verify(proof: StarkProof, inputs: CliInputs, hash_fn: HashFunction) -> Result<(), String> {
+    if proof.get_trace_info().width() != inputs.air.trace_width() {
+        return Err(format!(
+            "'{}' expects a trace of width {}, but the proof is for a trace of width {}",
+            inputs.air.name(),
+            inputs.air.trace_width(),
+            proof.get_trace_info().width()
+        ));
+    }
+    validate_assertions(&inputs, proof.trace_length())?;
+
+    match hash_fn {
+        HashFunction::Blake3_192 => verify_with::<Blake3_192>(proof, inputs),
+        HashFunction::Blake3_256 => verify_with::<Blake3_256>(proof, inputs),
+        HashFunction::Sha3_256 => verify_with::<Sha3_256>(proof, inputs),
+        HashFunction::Keccak256 => verify_with::<Keccak256>(proof, inputs),
+        _ => Err("The specified hash function cannot be used with this trace.".to_string()),
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+fn prove_with<H>(
+    trace: TraceTable<BaseElement>,
+    inputs: CliInputs,
+    options: ProofOptions,
+) -> Result<StarkProof, String>
+where
+    H: ElementHasher<BaseField = BaseElement>,
+{
+    CliProver::<H>::new(options, inputs)
+        .prove(trace)
+        .map_err(|err| format!("failed to generate proof: {err}"))
+}
+
+fn verify_with<H>(proof: StarkProof, inputs: CliInputs) -> Result<(), String>
+where
+    H: ElementHasher<BaseField = BaseElement>,
+{
+    winterfell::verify::<CliAir, H, DefaultRandomCoin<H>>(proof, inputs)
+        .map_err(|err| format!("failed to verify proof: {err}"))
+}
+
+/// Makes sure all assertions can be placed against a trace of the specified length and the
+/// width expected by the evaluator.
+fn validate_assertions(inputs: &CliInputs, trace_length: usize) -> Result<(), String> {
+    let trace_width = inputs.air.trace_width();
+    for assertion in inputs.assertions.iter() {
+        assertion
+            .validate_trace_width(trace_width)
+            .and_then(|_| assertion.validate_trace_length(trace_length))
+            .map_err(|err| format!("invalid assertion {assertion}: {err}"))?;
+    }
+    Ok(())
+}
@@ -0,0 +1,47 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{
+    BaseElement, CliAir, CliInputs, DefaultRandomCoin, ElementHasher, PhantomData, ProofOptions,
+    Prover, TraceTable,
+};
+
+// COMMAND-LINE PROVER
+// ================================================================================================
+
+pub struct CliProver<H: ElementHasher> {
+    options: ProofOptions,
+    pub_inputs: CliInputs,
+    _hasher: PhantomData<H>,
+}
+
+impl<H: ElementHasher> CliProver<H> {
+    pub fn new(options: ProofOptions, pub_inputs: CliInputs) -> Self {
+        Self {
+            options,
+            pub_inputs,
+            _hasher: PhantomData,
+        }
+    }
+}
+
+impl<H: ElementHasher> Prover for CliProver<H>
+where
+    H: ElementHasher<BaseField = BaseElement>,
+{
+    type BaseField = BaseElement;
+    type Air = CliAir;
+    type Trace = TraceTable<BaseElement>;
+    type HashFn = H;
+    type RandomCoin = DefaultRandomCoin<Self::HashFn>;
+
+    fn get_pub_inputs(&self, _trace: &Self::Trace) -> CliInputs {
+        self.pub_inputs.clone()
+    }
+
+    fn options(&self) -> &ProofOptions {
+        &self.options
+    }
+}
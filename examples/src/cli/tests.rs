@@ -0,0 +1,150 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{
+    parse_assertions, prove, read_trace, verify, write_trace, AirId, BaseElement, CliInputs,
+    FieldElement, HashFunction, StarkField, Trace, TraceTable,
+};
+use winterfell::{Assertion, FieldExtension, ProofOptions, StarkProof};
+
+fn build_fib_trace(num_steps: usize) -> TraceTable<BaseElement> {
+    TraceTable::from_transition(vec![BaseElement::ONE; 2], num_steps, |row| {
+        let next0 = row[0] + row[1];
+        vec![next0, row[1] + next0]
+    })
+}
+
+fn build_proof_options() -> ProofOptions {
+    ProofOptions::new(28, 8, 0, FieldExtension::None, 4, 7)
+}
+
+fn build_fib_inputs(trace: &TraceTable<BaseElement>) -> CliInputs {
+    let last_step = trace.length() - 1;
+    let json = format!(
+        r#"[
+            {{"column": 0, "step": 0, "value": "1"}},
+            {{"column": 1, "step": 0, "value": "1"}},
+            {{"column": 1, "step": {last_step}, "value": "{}"}}
+        ]"#,
+        trace.get(1, last_step).as_int()
+    );
+    CliInputs {
+        air: AirId::Fib2,
+        assertions: parse_assertions(&json).unwrap(),
+    }
+}
+
+#[test]
+fn cli_trace_serialization() {
+    let trace = build_fib_trace(16);
+    let bytes = write_trace(&trace);
+    assert_eq!(8 + 2 * 16 * 16, bytes.len());
+
+    let parsed = read_trace(&bytes).unwrap();
+    assert_eq!(trace.width(), parsed.width());
+    assert_eq!(trace.length(), parsed.length());
+    for i in 0..trace.width() {
+        assert_eq!(trace.get_column(i), parsed.get_column(i));
+    }
+
+    // truncated and extended traces are rejected
+    assert!(read_trace(&bytes[..bytes.len() - 1]).is_err());
+    let mut extended = bytes.clone();
+    extended.push(0);
+    assert!(read_trace(&extended).is_err());
+
+    // trace length must be a power of two
+    let mut invalid = bytes;
+    invalid[4..8].copy_from_slice(&15u32.to_le_bytes());
+    assert!(read_trace(&invalid).is_err());
+}
+
+#[test]
+fn cli_assertion_parsing() {
+    let assertions = parse_assertions(
+        r#"[{"column": 1, "step": 3, "value": "42"}, {"column": 0, "step": 1, "stride": 4, "value": "7"}]"#,
+    )
+    .unwrap();
+    assert_eq!(
+        vec![
+            Assertion::single(1, 3, BaseElement::new(42)),
+            Assertion::periodic(0, 1, 4, BaseElement::new(7)),
+        ],
+        assertions
+    );
+
+    assert!(parse_assertions("[]").is_err());
+    assert!(parse_assertions(r#"[{"column": 0, "step": 0}]"#).is_err());
+    assert!(parse_assertions(r#"[{"column": 0, "step": 0, "value": "x"}]"#).is_err());
+    assert!(parse_assertions(r#"[{"column": 0, "step": 0, "stride": 3, "value": "1"}]"#).is_err());
+
+    // values must be smaller than the field modulus
+    let modulus = BaseElement::MODULUS.to_string();
+    let json = format!(r#"[{{"column": 0, "step": 0, "value": "{modulus}"}}]"#);
+    assert!(parse_assertions(&json).is_err());
+}
+
+#[test]
+fn cli_air_registry() {
+    for air in AirId::ALL {
+        assert_eq!(Some(air), AirId::from_name(air.name()));
+    }
+    assert_eq!(None, AirId::from_name("fib3"));
+}
+
+#[test]
+fn cli_prove_verify_round_trip() {
+    let trace = build_fib_trace(32);
+    let inputs = build_fib_inputs(&trace);
+
+    // proofs are generated from and verified against serialized data only
+    let trace = read_trace(&write_trace(&trace)).unwrap();
+    let proof = prove(
+        trace,
+        inputs.clone(),
+        build_proof_options(),
+        HashFunction::Blake3_256,
+    )
+    .unwrap();
+    let proof = StarkProof::from_bytes(&proof.to_bytes()).unwrap();
+    assert!(verify(proof.clone(), inputs.clone(), HashFunction::Blake3_256).is_ok());
+
+    // verification fails with a different hash function
+    assert!(verify(proof.clone(), inputs.clone(), HashFunction::Sha3_256).is_err());
+
+    // verification fails with a different evaluator
+    let wrong_air = CliInputs {
+        air: AirId::Mulfib2,
+        ..inputs.clone()
+    };
+    assert!(verify(proof.clone(), wrong_air, HashFunction::Blake3_256).is_err());
+
+    // verification fails with different assertions
+    let mut wrong_assertions = inputs;
+    wrong_assertions.assertions[2] = Assertion::single(1, 31, BaseElement::new(42));
+    assert!(verify(proof, wrong_assertions, HashFunction::Blake3_256).is_err());
+}
+
+#[test]
+fn cli_prove_invalid_inputs() {
+    let trace = build_fib_trace(32);
+    let inputs = build_fib_inputs(&trace);
+    let options = build_proof_options();
+
+    // assertions outside of the trace are rejected before proving
+    let mut out_of_bounds = inputs.clone();
+    out_of_bounds.assertions[2] = Assertion::single(1, 32, BaseElement::ONE);
+    let result = prove(
+        trace,
+        out_of_bounds,
+        options.clone(),
+        HashFunction::Blake3_256,
+    );
+    assert!(result.is_err());
+
+    // hash functions over other fields are not supported
+    let result = prove(build_fib_trace(32), inputs, options, HashFunction::Rp64_256);
+    assert!(result.is_err());
+}
@@ -0,0 +1,108 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{generator, AffinePoint, BaseElement, FieldElement, ProofOptions, TRACE_WIDTH};
+use winterfell::{
+    math::ToElements, Air, AirContext, Assertion, EvaluationFrame, TraceInfo,
+    TransitionConstraintDegree,
+};
+
+// PUBLIC INPUTS
+// ================================================================================================
+
+#[derive(Clone)]
+pub struct PublicInputs {
+    pub public_key: AffinePoint,
+}
+
+impl ToElements<BaseElement> for PublicInputs {
+    fn to_elements(&self) -> Vec<BaseElement> {
+        vec![self.public_key.x, self.public_key.y]
+    }
+}
+
+// ELLIPTIC CURVE SCALAR MULTIPLICATION AIR
+// ================================================================================================
+
+/// AIR for double-and-add scalar multiplication with the following trace columns:
+/// * 0: bit of the scalar added at the current step.
+/// * 1, 2: coordinates of the accumulated point R.
+/// * 3: slope of the tangent line at R.
+/// * 4, 5: coordinates of D = 2 * R.
+/// * 6: slope of the line through D and the generator G.
+/// * 7, 8: coordinates of S = D + G.
+///
+/// The next accumulated point is S if the bit is set, and D otherwise.
+pub struct EccAir {
+    context: AirContext<BaseElement>,
+    generator: AffinePoint,
+    public_key: AffinePoint,
+}
+
+impl Air for EccAir {
+    type BaseField = BaseElement;
+    type PublicInputs = PublicInputs;
+
+    fn new(trace_info: TraceInfo, pub_inputs: PublicInputs, options: ProofOptions) -> Self {
+        assert_eq!(TRACE_WIDTH, trace_info.width());
+        let degrees = vec![TransitionConstraintDegree::new(2); 9];
+        Self {
+            context: AirContext::new(trace_info, degrees, 4, options),
+            generator: generator(),
+            public_key: pub_inputs.public_key,
+        }
+    }
+
+    fn evaluate_transition<E: FieldElement<BaseField = Self::BaseField>>(
+        &self,
+        frame: &EvaluationFrame<E>,
+        _periodic_values: &[E],
+        result: &mut [E],
+    ) {
+        let current = frame.current();
+        let next = frame.next();
+        debug_assert_eq!(TRACE_WIDTH, current.len());
+
+        let bit = current[0];
+        let (x, y) = (current[1], current[2]);
+        let (slope_d, x_d, y_d) = (current[3], current[4], current[5]);
+        let (slope_s, x_s, y_s) = (current[6], current[7], current[8]);
+        let x_g = E::from(self.generator.x);
+        let y_g = E::from(self.generator.y);
+
+        // the bit must be binary
+        result[0] = bit * bit - bit;
+
+        // D = 2 * R
+        result[1] = slope_d * y.double() - x.square() * E::from(3u32);
+        result[2] = x_d - (slope_d.square() - x.double());
+        result[3] = y_d - (slope_d * (x - x_d) - y);
+
+        // S = D + G
+        result[4] = slope_s * (x_g - x_d) - (y_g - y_d);
+        result[5] = x_s - (slope_s.square() - x_d - x_g);
+        result[6] = y_s - (slope_s * (x_d - x_s) - y_d);
+
+        // the next accumulated point is S if the bit is set, and D otherwise
+        result[7] = next[1] - (bit * x_s + (E::ONE - bit) * x_d);
+        result[8] = next[2] - (bit * y_s + (E::ONE - bit) * y_d);
+    }
+
+    fn get_assertions(&self) -> Vec<Assertion<Self::BaseField>> {
+        // the accumulator starts at the generator (this accounts for the most significant bit of
+        // the scalar) and ends at the public key
+        let last_step = self.trace_length() - 1;
+        vec![
+            Assertion::single(1, 0, self.generator.x),
+            Assertion::single(2, 0, self.generator.y),
+            Assertion::single(1, last_step, self.public_key.x),
+            Assertion::single(2, last_step, self.public_key.y),
+        ]
+    }
+
+    fn context(&self) -> &AirContext<Self::BaseField> {
+        &self.context
+    }
+}
@@ -0,0 +1,200 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use crate::{Blake3_192, Blake3_256, Example, ExampleOptions, HashFunction, Sha3_256};
+use core::marker::PhantomData;
+use log::debug;
+use std::time::Instant;
+use winterfell::{
+    crypto::{DefaultRandomCoin, ElementHasher},
+    math::{fields::f128::BaseElement, FieldElement, StarkFieldExt},
+    ProofOptions, Prover, StarkProof, Trace, TraceTable, VerifierError,
+};
+
+mod air;
+use air::{EccAir, PublicInputs};
+
+mod prover;
+use prover::EccProver;
+
+#[cfg(test)]
+mod tests;
+
+// CONSTANTS
+// ================================================================================================
+
+/// Number of columns in the execution trace.
+const TRACE_WIDTH: usize = 9;
+
+/// Coefficient b of the curve y^2 = x^3 + b used in this example.
+const CURVE_B: BaseElement = BaseElement::new(7);
+
+// ELLIPTIC CURVE SCALAR MULTIPLICATION EXAMPLE
+// ================================================================================================
+
+/// Proves knowledge of a secret scalar k such that k * G is equal to a public key, where G is a
+/// fixed generator of a short Weierstrass curve y^2 = x^3 + 7 defined over the f128 field.
+///
+/// This is a skeleton for signature verification STARKs: the scalar multiplication is computed
+/// using the double-and-add method in affine coordinates, with one bit of the scalar processed
+/// per step. The example does not handle exceptional cases of affine addition (e.g., adding a
+/// point to itself or to its negation), which are overwhelmingly unlikely for random scalars.
+pub fn get_example(options: &ExampleOptions, num_bits: usize) -> Result<Box<dyn Example>, String> {
+    let (options, hash_fn) = options.to_proof_options(28, 8);
+
+    match hash_fn {
+        HashFunction::Blake3_192 => Ok(Box::new(EccExample::<Blake3_192>::new(num_bits, options))),
+        HashFunction::Blake3_256 => Ok(Box::new(EccExample::<Blake3_256>::new(num_bits, options))),
+        HashFunction::Sha3_256 => Ok(Box::new(EccExample::<Sha3_256>::new(num_bits, options))),
+        _ => Err("The specified hash function cannot be used with this example.".to_string()),
+    }
+}
+
+pub struct EccExample<H: ElementHasher> {
+    options: ProofOptions,
+    secret_key: Vec<bool>,
+    public_key: AffinePoint,
+    _hasher: PhantomData<H>,
+}
+
+impl<H: ElementHasher> EccExample<H> {
+    pub fn new(num_bits: usize, options: ProofOptions) -> Self {
+        assert!(
+            num_bits.is_power_of_two() && num_bits >= 8,
+            "number of scalar bits must be a power of 2 and at least 8"
+        );
+
+        // derive the secret key from a fixed seed; the most significant bit of the key is always
+        // set so that scalar multiplication never passes through the point at infinity
+        let seed = blake3::hash(b"ecc example secret key");
+        let mut secret_key = seed
+            .as_bytes()
+            .iter()
+            .cycle()
+            .flat_map(|byte| (0..8).map(move |i| (byte >> i) & 1 == 1))
+            .take(num_bits)
+            .collect::<Vec<_>>();
+        secret_key[0] = true;
+
+        // compute the public key
+        let now = Instant::now();
+        let public_key = scalar_mul(&secret_key, generator());
+        debug!(
+            "Computed public key for a {}-bit secret key in {} ms",
+            num_bits,
+            now.elapsed().as_millis()
+        );
+
+        Self {
+            options,
+            secret_key,
+            public_key,
+            _hasher: PhantomData,
+        }
+    }
+}
+
+// EXAMPLE IMPLEMENTATION
+// ================================================================================================
+
+impl<H: ElementHasher> Example for EccExample<H>
+where
+    H: ElementHasher<BaseField = BaseElement>,
+{
+    fn prove(&self) -> StarkProof {
+        debug!(
+            "Generating proof for multiplying curve generator by a {}-bit scalar\n\
+            ---------------------",
+            self.secret_key.len()
+        );
+
+        // create a prover
+        let prover = EccProver::<H>::new(self.options.clone());
+
+        // generate execution trace
+        let now = Instant::now();
+        let trace = prover.build_trace(&self.secret_key);
+
+        let trace_width = trace.width();
+        let trace_length = trace.length();
+        debug!(
+            "Generated execution trace of {} registers and 2^{} steps in {} ms",
+            trace_width,
+            trace_length.ilog2(),
+            now.elapsed().as_millis()
+        );
+
+        // generate the proof
+        prover.prove(trace).unwrap()
+    }
+
+    fn verify(&self, proof: StarkProof) -> Result<(), VerifierError> {
+        let pub_inputs = PublicInputs {
+            public_key: self.public_key,
+        };
+        winterfell::verify::<EccAir, H, DefaultRandomCoin<H>>(proof, pub_inputs)
+    }
+
+    fn verify_with_wrong_inputs(&self, proof: StarkProof) -> Result<(), VerifierError> {
+        let pub_inputs = PublicInputs {
+            public_key: add(self.public_key, generator()).1,
+        };
+        winterfell::verify::<EccAir, H, DefaultRandomCoin<H>>(proof, pub_inputs)
+    }
+}
+
+// CURVE ARITHMETIC
+// ================================================================================================
+
+/// A point on the curve y^2 = x^3 + 7 in affine coordinates.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AffinePoint {
+    pub x: BaseElement,
+    pub y: BaseElement,
+}
+
+/// Returns the generator of the curve used in this example; this is the point with the smallest
+/// x coordinate for which x^3 + 7 is a non-zero square.
+pub fn generator() -> AffinePoint {
+    let mut x = BaseElement::ONE;
+    loop {
+        if let Some(y) = (x.cube() + CURVE_B).sqrt() {
+            if y != BaseElement::ZERO {
+                return AffinePoint { x, y };
+            }
+        }
+        x += BaseElement::ONE;
+    }
+}
+
+/// Returns 2 * p along with the slope of the tangent line at p.
+pub fn double(p: AffinePoint) -> (BaseElement, AffinePoint) {
+    let slope = (p.x.square() * BaseElement::new(3)) / p.y.double();
+    let x = slope.square() - p.x.double();
+    let y = slope * (p.x - x) - p.y;
+    (slope, AffinePoint { x, y })
+}
+
+/// Returns p + q along with the slope of the line through p and q.
+pub fn add(p: AffinePoint, q: AffinePoint) -> (BaseElement, AffinePoint) {
+    let slope = (q.y - p.y) / (q.x - p.x);
+    let x = slope.square() - p.x - q.x;
+    let y = slope * (p.x - x) - p.y;
+    (slope, AffinePoint { x, y })
+}
+
+/// Returns k * p, where the bits of k are listed starting with the most significant bit; the
+/// most significant bit is expected to be set.
+pub fn scalar_mul(bits: &[bool], p: AffinePoint) -> AffinePoint {
+    assert!(bits[0], "most significant bit of the scalar must be set");
+    let mut result = p;
+    for &bit in bits.iter().skip(1) {
+        result = double(result).1;
+        if bit {
+            result = add(result, p).1;
+        }
+    }
+    result
+}
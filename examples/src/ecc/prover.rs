@@ -0,0 +1,108 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{
+    add, double, generator, AffinePoint, BaseElement, DefaultRandomCoin, EccAir, ElementHasher,
+    FieldElement, PhantomData, ProofOptions, Prover, PublicInputs, Trace, TraceTable, TRACE_WIDTH,
+};
+
+// ELLIPTIC CURVE SCALAR MULTIPLICATION PROVER
+// ================================================================================================
+
+pub struct EccProver<H: ElementHasher> {
+    options: ProofOptions,
+    _hasher: PhantomData<H>,
+}
+
+impl<H: ElementHasher> EccProver<H> {
+    pub fn new(options: ProofOptions) -> Self {
+        Self {
+            options,
+            _hasher: PhantomData,
+        }
+    }
+
+    /// Builds an execution trace for multiplying the curve generator by the scalar with the
+    /// specified bits; the bits are listed starting with the most significant one, which must
+    /// be set.
+    pub fn build_trace(&self, bits: &[bool]) -> TraceTable<BaseElement> {
+        let g = generator();
+        let mut trace = TraceTable::new(TRACE_WIDTH, bits.len());
+        trace.fill(
+            |state| {
+                // the most significant bit is accounted for by initializing the accumulator
+                // with the generator
+                let bit = bits.get(1).copied().unwrap_or(false);
+                apply_step(state, AffinePoint { x: g.x, y: g.y }, bit, g);
+            },
+            |step, state| {
+                let bit = bits.get(step + 2).copied().unwrap_or(false);
+                let acc = if state[0] == BaseElement::ONE {
+                    AffinePoint {
+                        x: state[7],
+                        y: state[8],
+                    }
+                } else {
+                    AffinePoint {
+                        x: state[4],
+                        y: state[5],
+                    }
+                };
+                apply_step(state, acc, bit, g);
+            },
+        );
+        trace
+    }
+}
+
+impl<H: ElementHasher> Prover for EccProver<H>
+where
+    H: ElementHasher<BaseField = BaseElement>,
+{
+    type BaseField = BaseElement;
+    type Air = EccAir;
+    type Trace = TraceTable<BaseElement>;
+    type HashFn = H;
+    type RandomCoin = DefaultRandomCoin<Self::HashFn>;
+
+    fn get_pub_inputs(&self, trace: &Self::Trace) -> PublicInputs {
+        let last_step = trace.length() - 1;
+        PublicInputs {
+            public_key: AffinePoint {
+                x: trace.get(1, last_step),
+                y: trace.get(2, last_step),
+            },
+        }
+    }
+
+    fn options(&self) -> &ProofOptions {
+        &self.options
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Writes the accumulated point `acc`, the `bit` to be added at this step, and the intermediate
+/// values of doubling `acc` and adding the generator `g` to the result into the `state`.
+fn apply_step(state: &mut [BaseElement], acc: AffinePoint, bit: bool, g: AffinePoint) {
+    let (slope_d, d) = double(acc);
+    let (slope_s, s) = add(d, g);
+    state.copy_from_slice(&[
+        if bit {
+            BaseElement::ONE
+        } else {
+            BaseElement::ZERO
+        },
+        acc.x,
+        acc.y,
+        slope_d,
+        d.x,
+        d.y,
+        slope_s,
+        s.x,
+        s.y,
+    ]);
+}
@@ -0,0 +1,56 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{add, double, generator, scalar_mul, Blake3_256, CURVE_B};
+use winterfell::{math::FieldElement, FieldExtension, ProofOptions};
+
+#[test]
+fn ecc_test_curve_arithmetic() {
+    let g = generator();
+    assert_eq!(g.y.square(), g.x.cube() + CURVE_B);
+
+    // 5 * G computed via double-and-add must match (2 * G) + (2 * G) + G
+    let g2 = double(g).1;
+    let g3 = add(g2, g).1;
+    let expected = add(g3, g2).1;
+    assert_eq!(expected, scalar_mul(&[true, false, true], g));
+    assert_eq!(expected.y.square(), expected.x.cube() + CURVE_B);
+}
+
+#[test]
+fn ecc_test_basic_proof_verification() {
+    let ecc = Box::new(super::EccExample::<Blake3_256>::new(
+        64,
+        build_options(false),
+    ));
+    crate::tests::test_basic_proof_verification(ecc);
+}
+
+#[test]
+fn ecc_test_basic_proof_verification_extension() {
+    let ecc = Box::new(super::EccExample::<Blake3_256>::new(
+        64,
+        build_options(true),
+    ));
+    crate::tests::test_basic_proof_verification(ecc);
+}
+
+#[test]
+fn ecc_test_basic_proof_verification_fail() {
+    let ecc = Box::new(super::EccExample::<Blake3_256>::new(
+        64,
+        build_options(false),
+    ));
+    crate::tests::test_basic_proof_verification_fail(ecc);
+}
+
+fn build_options(use_extension_field: bool) -> ProofOptions {
+    let extension = if use_extension_field {
+        FieldExtension::Quadratic
+    } else {
+        FieldExtension::None
+    };
+    ProofOptions::new(28, 8, 0, extension, 4, 31)
+}
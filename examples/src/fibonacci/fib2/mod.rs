@@ -4,7 +4,7 @@
 // LICENSE file in the root directory of this source tree.
 
 use super::utils::compute_fib_term;
-use crate::{Blake3_192, Blake3_256, Example, ExampleOptions, HashFunction, Sha3_256};
+use crate::{Blake3_192, Blake3_256, Example, ExampleOptions, HashFunction, Keccak256, Sha3_256};
 use core::marker::PhantomData;
 use log::debug;
 use std::time::Instant;
@@ -50,6 +50,10 @@ pub fn get_example(
             sequence_length,
             options,
         ))),
+        HashFunction::Keccak256 => Ok(Box::new(FibExample::<Keccak256>::new(
+            sequence_length,
+            options,
+        ))),
         _ => Err("The specified hash function cannot be used with this example.".to_string()),
     }
 }
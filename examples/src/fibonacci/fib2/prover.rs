@@ -32,19 +32,11 @@ impl<H: ElementHasher> FibProver<H> {
             "sequence length must be a power of 2"
         );
 
-        let mut trace = TraceTable::new(TRACE_WIDTH, sequence_length / 2);
-        trace.fill(
-            |state| {
-                state[0] = BaseElement::ONE;
-                state[1] = BaseElement::ONE;
-            },
-            |_, state| {
-                state[0] += state[1];
-                state[1] += state[0];
-            },
-        );
-
-        trace
+        let init_row = vec![BaseElement::ONE; TRACE_WIDTH];
+        TraceTable::from_transition(init_row, sequence_length / 2, |row| {
+            let next0 = row[0] + row[1];
+            vec![next0, row[1] + next0]
+        })
     }
 }
 
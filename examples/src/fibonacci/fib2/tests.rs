@@ -3,7 +3,7 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use super::{super::utils::build_proof_options, Blake3_256};
+use super::{super::utils::build_proof_options, BaseElement, Blake3_256, Example, FibAir};
 
 #[test]
 fn fib2_test_basic_proof_verification() {
@@ -14,6 +14,43 @@ fn fib2_test_basic_proof_verification() {
     crate::tests::test_basic_proof_verification(fib);
 }
 
+#[test]
+fn fib2_test_basic_proof_verification_min_blowup_factor() {
+    let options = winterfell::ProofOptions::new(64, 2, 0, winterfell::FieldExtension::None, 2, 7);
+    let fib = Box::new(super::FibExample::<Blake3_256>::new(256, options));
+    crate::tests::test_basic_proof_verification(fib);
+}
+
+#[test]
+fn fib2_test_basic_proof_verification_many_queries() {
+    // more than 255 queries exercise the wide encoding of query counts and Merkle proofs
+    let options = winterfell::ProofOptions::new(300, 8, 0, winterfell::FieldExtension::None, 4, 31);
+    let fib = Box::new(super::FibExample::<Blake3_256>::new(1024, options));
+    crate::tests::test_basic_proof_verification(fib);
+}
+
+#[test]
+fn fib2_test_tampered_proofs_rejected() {
+    let fib = Box::new(super::FibExample::<Blake3_256>::new(
+        256,
+        build_proof_options(false),
+    ));
+    crate::tests::test_tampered_proofs_rejected(fib);
+}
+
+#[test]
+fn fib2_test_extended_fri_remainder_rejected_early() {
+    use winterfell::{FriVerifierError, VerifierError};
+
+    let fib = super::FibExample::<Blake3_256>::new(256, build_proof_options(false));
+    let proof = crate::tamper::extend_fri_remainder(&fib.prove());
+    match fib.verify(proof) {
+        Err(VerifierError::FriVerificationFailed(FriVerifierError::RemainderDegreeMismatch(_))) => {
+        }
+        result => panic!("expected remainder degree mismatch, but was {result:?}"),
+    }
+}
+
 #[test]
 fn fib2_test_basic_proof_verification_extension() {
     let fib = Box::new(super::FibExample::<Blake3_256>::new(
@@ -23,6 +60,77 @@ fn fib2_test_basic_proof_verification_extension() {
     crate::tests::test_basic_proof_verification(fib);
 }
 
+#[test]
+fn fib2_test_basic_proof_verification_base_field_composition() {
+    let options = build_proof_options(true).set_base_field_composition(true);
+    let fib = Box::new(super::FibExample::<Blake3_256>::new(16, options.clone()));
+    crate::tests::test_basic_proof_verification(fib);
+
+    let fib = Box::new(super::FibExample::<Blake3_256>::new(256, options.clone()));
+    crate::tests::test_tampered_proofs_rejected(fib);
+
+    // composition coefficients are drawn from the extension field, and thus, security is not
+    // limited by the size of the base field
+    let proof = super::FibExample::<Blake3_256>::new(256, options).prove();
+    let ext_proof = super::FibExample::<Blake3_256>::new(256, build_proof_options(true)).prove();
+    assert_eq!(
+        ext_proof.security_level::<Blake3_256>(true),
+        proof.security_level::<Blake3_256>(true)
+    );
+}
+
+#[test]
+fn fib2_test_basic_proof_verification_custom_domain_offset() {
+    let fib = Box::new(super::FibExample::<Blake3_256>::new(
+        16,
+        build_proof_options(false).set_domain_offset(5),
+    ));
+    crate::tests::test_basic_proof_verification(fib);
+}
+
+#[test]
+fn fib2_test_basic_proof_verification_stratified_query_sampling() {
+    let fib = Box::new(super::FibExample::<Blake3_256>::new(
+        16,
+        build_proof_options(false).set_query_sampling(winterfell::QuerySampling::Stratified(4)),
+    ));
+    crate::tests::test_basic_proof_verification(fib);
+}
+
+#[test]
+fn fib2_test_basic_proof_verification_aggregated_fri_layer_proofs() {
+    let fib = Box::new(super::FibExample::<Blake3_256>::new(
+        16,
+        build_proof_options(false).set_fri_layer_proof_aggregation(true),
+    ));
+    crate::tests::test_basic_proof_verification(fib);
+
+    let options = build_proof_options(false).set_fri_layer_proof_aggregation(true);
+    let proof = super::FibExample::<Blake3_256>::new(16, options).prove();
+    assert!(proof.fri_proof.is_aggregated());
+}
+
+#[test]
+fn fib2_test_basic_proof_verification_memory_hard_grinding() {
+    use winterfell::{FieldExtension, GrindingFunction, ProofOptions};
+
+    let options = ProofOptions::new(28, 8, 4, FieldExtension::None, 4, 7)
+        .set_grinding_function(GrindingFunction::MemoryHard);
+    let fib = super::FibExample::<Blake3_256>::new(16, options);
+    let proof = fib.prove();
+    assert_eq!(
+        GrindingFunction::MemoryHard,
+        proof.options().grinding_function()
+    );
+    assert!(fib.verify(proof.clone()).is_ok());
+
+    // a different nonce either fails the proof-of-work check or results in different query
+    // positions
+    let mut tampered = proof;
+    tampered.pow_nonce += 1;
+    assert!(fib.verify(tampered).is_err());
+}
+
 #[test]
 fn fib2_test_basic_proof_verification_fail() {
     let fib = Box::new(super::FibExample::<Blake3_256>::new(
@@ -31,3 +139,25 @@ fn fib2_test_basic_proof_verification_fail() {
     ));
     crate::tests::test_basic_proof_verification_fail(fib);
 }
+
+#[test]
+fn fib2_test_constraint_evaluations_snapshot() {
+    use winterfell::{Air, TraceInfo};
+
+    let trace_info = TraceInfo::new(super::TRACE_WIDTH, 64);
+    let air = FibAir::new(trace_info, BaseElement::new(42), build_proof_options(false));
+    crate::tests::test_constraint_evaluations_snapshot(&air, "fib2");
+}
+
+#[test]
+fn fib2_test_solidity_verifier() {
+    use crate::{solidity, Keccak256};
+    use rand_utils::rand_value;
+
+    let fib = super::FibExample::<Keccak256>::new(256, build_proof_options(false));
+    solidity::tests::test_solidity_verifier::<FibAir>(
+        fib.prove(),
+        fib.result,
+        [rand_value(), rand_value()],
+    );
+}
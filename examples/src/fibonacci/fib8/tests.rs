@@ -31,3 +31,12 @@ fn fib8_test_basic_proof_verification_fail() {
     ));
     crate::tests::test_basic_proof_verification_fail(fib);
 }
+
+#[test]
+fn fib8_test_constraint_evaluations_snapshot() {
+    use winterfell::{math::fields::f128::BaseElement, Air, TraceInfo};
+
+    let trace_info = TraceInfo::new(super::TRACE_WIDTH, 64);
+    let air = super::Fib8Air::new(trace_info, BaseElement::new(42), build_proof_options(false));
+    crate::tests::test_constraint_evaluations_snapshot(&air, "fib8");
+}
@@ -42,7 +42,7 @@ pub fn get_example(
     options: &ExampleOptions,
     sequence_length: usize,
 ) -> Result<Box<dyn Example>, String> {
-    let (options, hash_fn) = options.to_proof_options(28, 8);
+    let (options, hash_fn) = options.to_proof_options_for::<BaseElement>(28, 8);
 
     match hash_fn {
         HashFunction::Blake3_192 => Ok(Box::new(FibExample::<Blake3_192>::new(
@@ -69,6 +69,7 @@ pub fn get_example(
             sequence_length,
             options,
         ))),
+        _ => Err("The specified hash function cannot be used with this example.".to_string()),
     }
 }
 
@@ -32,15 +32,11 @@ impl<H: ElementHasher> MulFib2Prover<H> {
             "sequence length must be a power of 2"
         );
 
-        let mut reg0 = vec![BaseElement::new(1)];
-        let mut reg1 = vec![BaseElement::new(2)];
-
-        for i in 0..(length / 2 - 1) {
-            reg0.push(reg0[i] * reg1[i]);
-            reg1.push(reg1[i] * reg0[i + 1]);
-        }
-
-        TraceTable::init(vec![reg0, reg1])
+        let init_row = vec![BaseElement::new(1), BaseElement::new(2)];
+        TraceTable::from_transition(init_row, length / 2, |row| {
+            let next0 = row[0] * row[1];
+            vec![next0, row[1] * next0]
+        })
     }
 }
 
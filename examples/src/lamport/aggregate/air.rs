@@ -7,7 +7,6 @@ use super::{
     rescue, CYCLE_LENGTH as HASH_CYCLE_LEN, SIG_CYCLE_LENGTH as SIG_CYCLE_LEN, TRACE_WIDTH,
 };
 use crate::utils::{are_equal, is_binary, is_zero, not, EvaluationResult};
-use core_utils::flatten_slice_elements;
 use winterfell::{
     math::{fields::f128::BaseElement, FieldElement, ToElements},
     Air, AirContext, Assertion, EvaluationFrame, ProofOptions, TraceInfo,
@@ -30,8 +29,8 @@ pub struct PublicInputs {
 impl ToElements<BaseElement> for PublicInputs {
     fn to_elements(&self) -> Vec<BaseElement> {
         let mut result = Vec::new();
-        result.extend_from_slice(flatten_slice_elements(&self.pub_keys));
-        result.extend_from_slice(flatten_slice_elements(&self.messages));
+        result.extend(self.pub_keys.iter().flatten());
+        result.extend(self.messages.iter().flatten());
         result
     }
 }
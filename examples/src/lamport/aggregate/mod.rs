@@ -4,7 +4,8 @@
 // LICENSE file in the root directory of this source tree.
 
 use super::{
-    message_to_elements, rescue, Example, PrivateKey, Signature, CYCLE_LENGTH, NUM_HASH_ROUNDS,
+    message_to_elements, rescue, verify_aggregate, Example, PrivateKey, Signature, CYCLE_LENGTH,
+    NUM_HASH_ROUNDS,
 };
 use crate::{Blake3_192, Blake3_256, ExampleOptions, HashFunction, Sha3_256};
 use core::marker::PhantomData;
@@ -22,6 +23,9 @@ use air::{LamportAggregateAir, PublicInputs};
 mod prover;
 use prover::LamportAggregateProver;
 
+#[cfg(test)]
+mod tests;
+
 // CONSTANTS
 // ================================================================================================
 
@@ -85,10 +89,12 @@ impl<H: ElementHasher> LamportAggregateExample<H> {
         let now = Instant::now();
         let mut signatures = Vec::new();
         let mut messages = Vec::new();
+        let mut raw_messages = Vec::new();
         for (i, private_key) in private_keys.iter().enumerate() {
             let msg = format!("test message {i}");
             signatures.push(private_key.sign(msg.as_bytes()));
             messages.push(message_to_elements(msg.as_bytes()));
+            raw_messages.push(msg);
         }
         debug!(
             "Signed {} messages in {} ms",
@@ -96,15 +102,15 @@ impl<H: ElementHasher> LamportAggregateExample<H> {
             now.elapsed().as_millis()
         );
 
-        // verify signature
+        // verify signatures natively; the AIR must accept exactly the signatures accepted here
         let now = Instant::now();
-        let mut pub_keys = Vec::new();
-        for (i, signature) in signatures.iter().enumerate() {
-            let pk = private_keys[i].pub_key();
-            pub_keys.push(pk.to_elements());
-            let msg = format!("test message {i}");
-            assert!(pk.verify(msg.as_bytes(), signature));
-        }
+        let pub_keys = private_keys.iter().map(|k| k.pub_key()).collect::<Vec<_>>();
+        let raw_messages = raw_messages
+            .iter()
+            .map(|m| m.as_bytes())
+            .collect::<Vec<_>>();
+        let results = verify_aggregate(&raw_messages, &signatures, &pub_keys);
+        assert!(results.into_iter().all(|is_valid| is_valid));
         debug!(
             "Verified {} signature in {} ms",
             num_signatures,
@@ -113,7 +119,7 @@ impl<H: ElementHasher> LamportAggregateExample<H> {
 
         LamportAggregateExample {
             options,
-            pub_keys,
+            pub_keys: public_keys,
             messages,
             signatures,
             _hasher: PhantomData,
@@ -0,0 +1,51 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{
+    message_to_elements, verify_aggregate, BaseElement, LamportAggregateAir, PrivateKey,
+    PublicInputs,
+};
+use winterfell::{Air, FieldExtension, ProofOptions, TraceInfo};
+
+#[test]
+fn lamport_aggregate_test_constraint_evaluations_snapshot() {
+    let pub_inputs = PublicInputs {
+        pub_keys: vec![
+            [BaseElement::new(42), BaseElement::new(43)],
+            [BaseElement::new(44), BaseElement::new(45)],
+        ],
+        messages: vec![
+            message_to_elements(b"test message 0"),
+            message_to_elements(b"test message 1"),
+        ],
+    };
+    let trace_info = TraceInfo::new(super::TRACE_WIDTH, 2 * super::SIG_CYCLE_LENGTH);
+    let options = ProofOptions::new(28, 8, 0, FieldExtension::None, 4, 31);
+    let air = LamportAggregateAir::new(trace_info, pub_inputs, options);
+    crate::tests::test_constraint_evaluations_snapshot(&air, "lamport_aggregate");
+}
+
+#[test]
+fn lamport_aggregate_test_native_verification() {
+    let private_keys = (0..4)
+        .map(|i| PrivateKey::from_seed([i as u8; 32]))
+        .collect::<Vec<_>>();
+    let messages: [&[u8]; 4] = [b"message 0", b"message 1", b"message 2", b"message 3"];
+    let signatures = private_keys
+        .iter()
+        .zip(messages)
+        .map(|(key, message)| key.sign(message))
+        .collect::<Vec<_>>();
+
+    // swap public keys of the last two signers so that their signatures become invalid
+    let mut pub_keys = private_keys.iter().map(|k| k.pub_key()).collect::<Vec<_>>();
+    pub_keys.swap(2, 3);
+
+    let results = verify_aggregate(&messages, &signatures, &pub_keys);
+    assert_eq!(vec![true, true, false, false], results);
+    for (i, is_valid) in results.into_iter().enumerate() {
+        assert_eq!(pub_keys[i].verify(messages[i], &signatures[i]), is_valid);
+    }
+}
@@ -4,6 +4,7 @@
 // LICENSE file in the root directory of this source tree.
 
 use super::rescue::Rescue128;
+use core_utils::iter_mut;
 use rand_utils::prng_vector;
 use std::{cmp::Ordering, convert::TryInto};
 use winterfell::{
@@ -11,6 +12,9 @@ use winterfell::{
     Serializable,
 };
 
+#[cfg(feature = "concurrent")]
+use core_utils::rayon::prelude::*;
+
 // CONSTANTS
 // ================================================================================================
 
@@ -97,33 +101,12 @@ impl PrivateKey {
 impl PublicKey {
     /// Returns true if the specified signature was generated by signing the specified message
     /// with a private key corresponding to this public key.
+    #[allow(dead_code)]
     pub fn verify(&self, message: &[u8], sig: &Signature) -> bool {
-        let mut n_zeros = 0;
-        let mut n_ones = 0;
-        let mut pub_keys = Vec::with_capacity(MESSAGE_BITS);
-        let elements = message_to_elements(message);
-        for element_bits in elements.iter().map(|e| e.as_int()) {
-            // make sure the least significant bit is 0
-            assert_eq!(element_bits & (1 << 127), 0);
-            for i in 0..127 {
-                if (element_bits >> i) & 1 == 1 {
-                    if n_ones == sig.ones.len() {
-                        return false;
-                    }
-                    pub_keys.push(Rescue128::digest(&sig.ones[n_ones]).to_elements());
-                    n_ones += 1;
-                } else {
-                    if n_zeros == sig.zeros.len() {
-                        return false;
-                    }
-                    pub_keys.push(sig.zeros[n_zeros]);
-                    n_zeros += 1;
-                }
-            }
+        match recover_pub_keys(message, sig) {
+            Some(pub_keys) => *self == hash_pub_keys(&pub_keys),
+            None => false,
         }
-
-        let pub_key_hash = hash_pub_keys(&pub_keys);
-        *self == pub_key_hash
     }
 
     #[allow(dead_code, clippy::wrong_self_convention)]
@@ -162,6 +145,48 @@ impl PartialOrd for PublicKey {
     }
 }
 
+// AGGREGATE VERIFICATION
+// ================================================================================================
+
+/// Verifies a batch of signatures and returns a verification result for every signature.
+///
+/// The i-th result is true if the i-th signature was generated by signing the i-th message with
+/// a private key corresponding to the i-th public key. This is equivalent to calling
+/// [PublicKey::verify()] for every signature, but the hasher state shared by all public key
+/// reductions is computed only once, and when `concurrent` feature is enabled, signatures are
+/// verified in multiple threads.
+///
+/// This is intended for checking the results of Lamport+ AIRs against a native computation.
+///
+/// # Panics
+/// Panics if the number of messages, signatures, and public keys is not the same.
+pub fn verify_aggregate(
+    messages: &[&[u8]],
+    signatures: &[Signature],
+    pub_keys: &[PublicKey],
+) -> Vec<bool> {
+    assert_eq!(
+        messages.len(),
+        signatures.len(),
+        "number of messages must be equal to the number of signatures"
+    );
+    assert_eq!(
+        pub_keys.len(),
+        signatures.len(),
+        "number of public keys must be equal to the number of signatures"
+    );
+
+    let initial_state = pub_keys_hasher();
+    let mut result = vec![false; signatures.len()];
+    iter_mut!(result).enumerate().for_each(|(i, is_valid)| {
+        if let Some(keys) = recover_pub_keys(messages[i], &signatures[i]) {
+            let pub_key_hash = reduce_pub_keys(initial_state.clone(), &keys);
+            *is_valid = pub_keys[i] == pub_key_hash;
+        }
+    });
+    result
+}
+
 // HELPER FUNCTIONS
 // ================================================================================================
 
@@ -196,12 +221,52 @@ pub fn message_to_elements(message: &[u8]) -> [BaseElement; 2] {
 /// hash(key[0] | key[127] | key[1] | key[128] | key[2] | key[129] ... )
 /// This hashing methodology is implemented to simplify AIR design.
 fn hash_pub_keys(keys: &[KeyData]) -> PublicKey {
-    let mut pub_key_hash = Rescue128::new();
-    pub_key_hash.update(&[BaseElement::ZERO; 4]);
+    reduce_pub_keys(pub_keys_hasher(), keys)
+}
+
+/// Returns the hasher state from which all public key reductions start.
+fn pub_keys_hasher() -> Rescue128 {
+    let mut hasher = Rescue128::new();
+    hasher.update(&[BaseElement::ZERO; 4]);
+    hasher
+}
+
+/// Absorbs the list of public key elements into the specified hasher as described in
+/// [hash_pub_keys()], and returns the resulting hash.
+fn reduce_pub_keys(mut hasher: Rescue128, keys: &[KeyData]) -> PublicKey {
     for i in 0..(MESSAGE_BITS / 2) {
-        pub_key_hash.update(&keys[i]);
-        pub_key_hash.update(&keys[i + MESSAGE_BITS / 2]);
+        hasher.update(&keys[i]);
+        hasher.update(&keys[i + MESSAGE_BITS / 2]);
     }
 
-    PublicKey(pub_key_hash.finalize().to_elements())
+    PublicKey(hasher.finalize().to_elements())
+}
+
+/// Returns the list of public key elements implied by the specified signature of the specified
+/// message, or None if the signature does not contain enough keys for the message.
+fn recover_pub_keys(message: &[u8], sig: &Signature) -> Option<Vec<KeyData>> {
+    let mut n_zeros = 0;
+    let mut n_ones = 0;
+    let mut pub_keys = Vec::with_capacity(MESSAGE_BITS);
+    let elements = message_to_elements(message);
+    for element_bits in elements.iter().map(|e| e.as_int()) {
+        // make sure the least significant bit is 0
+        assert_eq!(element_bits & (1 << 127), 0);
+        for i in 0..127 {
+            if (element_bits >> i) & 1 == 1 {
+                if n_ones == sig.ones.len() {
+                    return None;
+                }
+                pub_keys.push(Rescue128::digest(&sig.ones[n_ones]).to_elements());
+                n_ones += 1;
+            } else {
+                if n_zeros == sig.zeros.len() {
+                    return None;
+                }
+                pub_keys.push(sig.zeros[n_zeros]);
+                n_zeros += 1;
+            }
+        }
+    }
+    Some(pub_keys)
 }
@@ -4,8 +4,8 @@
 // LICENSE file in the root directory of this source tree.
 
 use super::{
-    message_to_elements, rescue, Example, PrivateKey, Signature, CYCLE_LENGTH as HASH_CYCLE_LENGTH,
-    NUM_HASH_ROUNDS,
+    message_to_elements, rescue, verify_aggregate, Example, PrivateKey, Signature,
+    CYCLE_LENGTH as HASH_CYCLE_LENGTH, NUM_HASH_ROUNDS,
 };
 use crate::{Blake3_192, Blake3_256, ExampleOptions, HashFunction, Sha3_256};
 use core::marker::PhantomData;
@@ -26,6 +26,9 @@ use air::{LamportThresholdAir, PublicInputs};
 mod prover;
 use prover::LamportThresholdProver;
 
+#[cfg(test)]
+mod tests;
+
 // CONSTANTS
 // ================================================================================================
 
@@ -85,11 +88,26 @@ impl<H: ElementHasher> LamportThresholdExample<H> {
         // sign the message with the subset of previously generated keys
         let message = "test message";
         let selected_indexes = pick_random_indexes(num_signers);
-        let mut signatures = Vec::new();
-        for &key_index in selected_indexes.iter() {
-            let signature = private_keys[key_index].sign(message.as_bytes());
-            signatures.push((key_index, signature));
-        }
+        let signatures = selected_indexes
+            .iter()
+            .map(|&key_index| private_keys[key_index].sign(message.as_bytes()))
+            .collect::<Vec<_>>();
+
+        // verify signatures natively; the AIR must accept exactly the signatures accepted here
+        let now = Instant::now();
+        let signer_keys = selected_indexes
+            .iter()
+            .map(|&key_index| private_keys[key_index].pub_key())
+            .collect::<Vec<_>>();
+        let messages = vec![message.as_bytes(); signatures.len()];
+        let results = verify_aggregate(&messages, &signatures, &signer_keys);
+        assert!(results.into_iter().all(|is_valid| is_valid));
+        debug!(
+            "Verified {} signatures in {} ms",
+            signatures.len(),
+            now.elapsed().as_millis()
+        );
+        let signatures = selected_indexes.into_iter().zip(signatures).collect();
 
         // build the aggregated public key
         let now = Instant::now();
@@ -0,0 +1,21 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{message_to_elements, BaseElement, LamportThresholdAir, PublicInputs};
+use winterfell::{Air, FieldExtension, ProofOptions, TraceInfo};
+
+#[test]
+fn lamport_threshold_test_constraint_evaluations_snapshot() {
+    let pub_inputs = PublicInputs {
+        pub_key_root: [BaseElement::new(42), BaseElement::new(43)],
+        num_pub_keys: 2,
+        num_signatures: 1,
+        message: message_to_elements(b"test message"),
+    };
+    let trace_info = TraceInfo::new(super::TRACE_WIDTH, 2 * super::SIG_CYCLE_LENGTH);
+    let options = ProofOptions::new(28, 8, 0, FieldExtension::None, 4, 31);
+    let air = LamportThresholdAir::new(trace_info, pub_inputs, options);
+    crate::tests::test_constraint_evaluations_snapshot(&air, "lamport_threshold");
+}
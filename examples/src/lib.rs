@@ -6,10 +6,15 @@
 use structopt::StructOpt;
 use winterfell::{
     crypto::hashers::{GriffinJive64_256, Rp64_256, RpJive64_256},
-    math::fields::f128::BaseElement,
+    math::{fields::f128::BaseElement, StarkField},
+    params::Preset,
     FieldExtension, ProofOptions, StarkProof, VerifierError,
 };
 
+pub mod builtin;
+#[cfg(feature = "std")]
+pub mod cli;
+pub mod ecc;
 pub mod fibonacci;
 #[cfg(feature = "std")]
 pub mod lamport;
@@ -18,6 +23,9 @@ pub mod merkle;
 pub mod rescue;
 #[cfg(feature = "std")]
 pub mod rescue_raps;
+#[cfg(feature = "std")]
+pub mod solidity;
+pub mod tamper;
 pub mod utils;
 pub mod vdf;
 
@@ -30,6 +38,7 @@ mod tests;
 pub type Blake3_192 = winterfell::crypto::hashers::Blake3_192<BaseElement>;
 pub type Blake3_256 = winterfell::crypto::hashers::Blake3_256<BaseElement>;
 pub type Sha3_256 = winterfell::crypto::hashers::Sha3_256<BaseElement>;
+pub type Keccak256 = winterfell::crypto::hashers::Keccak256<BaseElement>;
 
 pub trait Example {
     fn prove(&self) -> StarkProof;
@@ -66,13 +75,41 @@ pub struct ExampleOptions {
     #[structopt(short = "e", long = "field_extension", default_value = "1")]
     pub field_extension: u32,
 
+    /// Evaluate constraints in the base field and use the field extension only for constraint
+    /// composition, the DEEP composition polynomial, and FRI
+    #[structopt(long = "base-field-composition")]
+    pub base_field_composition: bool,
+
     /// Folding factor for FRI protocol
     #[structopt(short = "f", long = "folding", default_value = "8")]
     pub folding_factor: usize,
+
+    /// Name of a parameter preset (fast, balanced, recursion_friendly, small_proof); when set,
+    /// queries, blowup, grinding, field extension, and folding options are ignored
+    #[structopt(short = "p", long = "preset")]
+    pub preset: Option<String>,
+
+    /// Target security level in bits for the parameter preset
+    #[structopt(short = "s", long = "security", default_value = "100")]
+    pub security_level: u32,
+
+    /// Verify tampered copies of the generated proof and make sure all of them are rejected
+    #[structopt(long = "negative-tests")]
+    pub negative_tests: bool,
 }
 
 impl ExampleOptions {
     pub fn to_proof_options(&self, q: usize, b: usize) -> (ProofOptions, HashFunction) {
+        self.to_proof_options_for::<BaseElement>(q, b)
+    }
+
+    /// Same as [ExampleOptions::to_proof_options()], but parameter presets are resolved for
+    /// computations over the base field `B`.
+    pub fn to_proof_options_for<B: StarkField>(
+        &self,
+        q: usize,
+        b: usize,
+    ) -> (ProofOptions, HashFunction) {
         let num_queries = self.num_queries.unwrap_or(q);
         let blowup_factor = self.blowup_factor.unwrap_or(b);
         let field_extension = match self.field_extension {
@@ -82,18 +119,14 @@ impl ExampleOptions {
             val => panic!("'{val}' is not a valid field extension option"),
         };
 
-        let hash_fn = match self.hash_fn.as_str() {
-            "blake3_192" => HashFunction::Blake3_192,
-            "blake3_256" => HashFunction::Blake3_256,
-            "sha3_256" => HashFunction::Sha3_256,
-            "rp64_256" => HashFunction::Rp64_256,
-            "rp_jive64_256" => HashFunction::RpJive64_256,
-            "griffin_jive64_256" => HashFunction::GriffinJive64_256,
-            val => panic!("'{val}' is not a valid hash function option"),
-        };
+        let hash_fn = HashFunction::from_name(&self.hash_fn)
+            .unwrap_or_else(|| panic!("'{}' is not a valid hash function option", self.hash_fn));
 
-        (
-            ProofOptions::new(
+        let options = match self.preset.as_deref() {
+            Some(name) => Preset::from_name(name)
+                .unwrap_or_else(|| panic!("'{name}' is not a valid preset option"))
+                .options::<B>(self.security_level),
+            None => ProofOptions::new(
                 num_queries,
                 blowup_factor,
                 self.grinding_factor,
@@ -101,8 +134,10 @@ impl ExampleOptions {
                 self.folding_factor,
                 31,
             ),
-            hash_fn,
-        )
+        };
+        let options = options.set_base_field_composition(self.base_field_composition);
+
+        (options, hash_fn)
     }
 
     /// Returns security level of the input proof in bits.
@@ -111,6 +146,7 @@ impl ExampleOptions {
             "blake3_192" => proof.security_level::<Blake3_192>(conjectured),
             "blake3_256" => proof.security_level::<Blake3_256>(conjectured),
             "sha3_256" => proof.security_level::<Sha3_256>(conjectured),
+            "keccak_256" => proof.security_level::<Keccak256>(conjectured),
             "rp64_256" => proof.security_level::<Rp64_256>(conjectured),
             "rp_jive64_256" => proof.security_level::<RpJive64_256>(conjectured),
             "griffin_jive64_256" => proof.security_level::<GriffinJive64_256>(conjectured),
@@ -166,6 +202,25 @@ pub enum ExampleType {
         #[structopt(short = "n", default_value = "1048575")]
         num_steps: usize,
     },
+    /// Similar to the VDF example, but pads the trace with virtual rows to support any number of
+    /// steps.
+    VdfPadded {
+        /// Number of steps in the VDF function; must be at least 8
+        #[structopt(short = "n", default_value = "1000000")]
+        num_steps: usize,
+    },
+    /// Apply a builtin with round constants committed to once in a preprocessed trace column
+    Builtin {
+        /// Number of steps in the builtin function; must be a power of two
+        #[structopt(short = "n", default_value = "1048576")]
+        num_steps: usize,
+    },
+    /// Multiply an elliptic curve generator by a secret scalar matching a public key
+    Ecc {
+        /// Number of bits in the secret scalar; must be a power of two
+        #[structopt(short = "n", default_value = "256")]
+        num_bits: usize,
+    },
     /// Compute a hash chain using Rescue hash function
     Rescue {
         /// Length of the hash chain; must be a power of two
@@ -226,6 +281,12 @@ pub enum HashFunction {
     /// When this function is used in the STARK protocol, proof security cannot exceed 128 bits.
     Sha3_256,
 
+    /// Keccak hash function with 256 bit output (as used by Ethereum). Proofs generated with this
+    /// function can be verified by Solidity verifiers generated via the [solidity] module.
+    ///
+    /// When this function is used in the STARK protocol, proof security cannot exceed 128 bits.
+    Keccak256,
+
     /// Rescue Prime hash function with 256 bit output. It only works in `f64` field.
     ///
     /// When this function is used in the STARK protocol, proof security cannot exceed 128 bits.
@@ -243,3 +304,22 @@ pub enum HashFunction {
     /// When this function is used in the STARK protocol, proof security cannot exceed 128 bits.
     GriffinJive64_256,
 }
+
+impl HashFunction {
+    /// Returns a hash function with the specified name, or None if no such hash function exists.
+    ///
+    /// Valid names are `blake3_192`, `blake3_256`, `sha3_256`, `keccak_256`, `rp64_256`,
+    /// `rp_jive64_256`, and `griffin_jive64_256`.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "blake3_192" => Some(Self::Blake3_192),
+            "blake3_256" => Some(Self::Blake3_256),
+            "sha3_256" => Some(Self::Sha3_256),
+            "keccak_256" => Some(Self::Keccak256),
+            "rp64_256" => Some(Self::Rp64_256),
+            "rp_jive64_256" => Some(Self::RpJive64_256),
+            "griffin_jive64_256" => Some(Self::GriffinJive64_256),
+            _ => None,
+        }
+    }
+}
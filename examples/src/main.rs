@@ -9,7 +9,7 @@ use std::time::Instant;
 use structopt::StructOpt;
 use winterfell::StarkProof;
 
-use examples::{fibonacci, rescue, vdf, ExampleOptions, ExampleType};
+use examples::{builtin, ecc, fibonacci, rescue, tamper, vdf, ExampleOptions, ExampleType};
 #[cfg(feature = "std")]
 use examples::{lamport, merkle, rescue_raps};
 
@@ -47,6 +47,9 @@ fn main() {
         }
         ExampleType::Vdf { num_steps } => vdf::regular::get_example(&options, num_steps),
         ExampleType::VdfExempt { num_steps } => vdf::exempt::get_example(&options, num_steps),
+        ExampleType::VdfPadded { num_steps } => vdf::padded::get_example(&options, num_steps),
+        ExampleType::Builtin { num_steps } => builtin::get_example(&options, num_steps),
+        ExampleType::Ecc { num_bits } => ecc::get_example(&options, num_bits),
         ExampleType::Rescue { chain_length } => rescue::get_example(&options, chain_length),
         #[cfg(feature = "std")]
         ExampleType::RescueRaps { chain_length } => {
@@ -100,6 +103,11 @@ fn main() {
     debug!("---------------------");
     let parsed_proof = StarkProof::from_bytes(&proof_bytes).unwrap();
     assert_eq!(proof, parsed_proof);
+    let tampered_proofs = if options.negative_tests {
+        tamper::tampered_proofs(&proof)
+    } else {
+        Vec::new()
+    };
     let now = Instant::now();
     match example.verify(proof) {
         Ok(_) => debug!(
@@ -108,5 +116,16 @@ fn main() {
         ),
         Err(msg) => debug!("Failed to verify proof: {}", msg),
     }
+
+    // make sure tampered proofs are rejected
+    if options.negative_tests {
+        debug!("---------------------");
+        for (description, tampered_proof) in tampered_proofs {
+            match example.verify(tampered_proof) {
+                Ok(_) => panic!("Tampered proof ({description}) was accepted"),
+                Err(msg) => debug!("Tampered proof ({}) rejected: {}", description, msg),
+            }
+        }
+    }
     debug!("============================================================");
 }
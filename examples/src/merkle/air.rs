@@ -6,13 +6,14 @@
 use super::{rescue, BaseElement, FieldElement, HASH_CYCLE_LEN, HASH_STATE_WIDTH, TRACE_WIDTH};
 use crate::utils::{are_equal, is_binary, is_zero, not, EvaluationResult};
 use winterfell::{
-    math::ToElements, Air, AirContext, Assertion, EvaluationFrame, ProofOptions, TraceInfo,
-    TransitionConstraintDegree,
+    math::ToElements, Air, AirContext, Assertion, Assertions, EvaluationFrame, ProofOptions,
+    TraceInfo, TraceStep, TransitionConstraintDegree,
 };
 
 // MERKLE PATH VERIFICATION AIR
 // ================================================================================================
 
+#[derive(Clone)]
 pub struct PublicInputs {
     pub tree_root: [BaseElement; 2],
 }
@@ -102,13 +103,13 @@ impl Air for MerkleAir {
     fn get_assertions(&self) -> Vec<Assertion<Self::BaseField>> {
         // assert that Merkle path resolves to the tree root, and that hash capacity
         // registers (registers 4 and 5) are reset to ZERO every 8 steps
-        let last_step = self.trace_length() - 1;
-        vec![
-            Assertion::single(0, last_step, self.tree_root[0]),
-            Assertion::single(1, last_step, self.tree_root[1]),
-            Assertion::periodic(4, 0, HASH_CYCLE_LEN, BaseElement::ZERO),
-            Assertion::periodic(5, 0, HASH_CYCLE_LEN, BaseElement::ZERO),
-        ]
+        Assertions::new()
+            .single(0, TraceStep::last(), self.tree_root[0])
+            .single(1, TraceStep::last(), self.tree_root[1])
+            .with_assertion(Assertion::periodic(4, 0, HASH_CYCLE_LEN, BaseElement::ZERO))
+            .with_assertion(Assertion::periodic(5, 0, HASH_CYCLE_LEN, BaseElement::ZERO))
+            .build(self.trace_info())
+            .expect("invalid Merkle path assertions")
     }
 
     fn get_periodic_column_values(&self) -> Vec<Vec<Self::BaseField>> {
@@ -9,7 +9,7 @@ use crate::utils::rescue::{
 };
 use crate::{
     utils::rescue::{Hash, Rescue128},
-    Blake3_192, Blake3_256, Example, ExampleOptions, HashFunction, Sha3_256,
+    Blake3_192, Blake3_256, Example, ExampleOptions, HashFunction, Keccak256, Sha3_256,
 };
 use core::marker::PhantomData;
 use log::debug;
@@ -53,6 +53,9 @@ pub fn get_example(
         HashFunction::Sha3_256 => Ok(Box::new(MerkleExample::<Sha3_256>::new(
             tree_depth, options,
         ))),
+        HashFunction::Keccak256 => Ok(Box::new(MerkleExample::<Keccak256>::new(
+            tree_depth, options,
+        ))),
         _ => Err("The specified hash function cannot be used with this example.".to_string()),
     }
 }
@@ -33,6 +33,53 @@ fn merkle_test_basic_proof_verification_fail() {
     crate::tests::test_basic_proof_verification_fail(merkle);
 }
 
+#[test]
+fn merkle_test_solidity_verifier() {
+    use crate::{solidity, Example, Keccak256};
+    use rand_utils::rand_value;
+
+    let merkle = super::MerkleExample::<Keccak256>::new(7, build_options(false));
+    let pub_inputs = super::PublicInputs {
+        tree_root: merkle.tree_root.to_elements(),
+    };
+    let sample_inputs = || super::PublicInputs {
+        tree_root: [rand_value(), rand_value()],
+    };
+    solidity::tests::test_solidity_verifier::<super::MerkleAir>(
+        merkle.prove(),
+        pub_inputs,
+        [sample_inputs(), sample_inputs()],
+    );
+}
+
+#[test]
+fn merkle_test_seeded_entropy_reproducible() {
+    use crate::Example;
+    use rand_utils::{with_entropy, Entropy};
+
+    // with a fixed seed, the randomly chosen leaf (and thus the proof) is the same across runs
+    let prove = || {
+        with_entropy(Entropy::from_u64(42), || {
+            super::MerkleExample::<Blake3_256>::new(7, build_options(false))
+                .prove()
+                .to_bytes()
+        })
+    };
+    assert_eq!(prove(), prove());
+}
+
+#[test]
+fn merkle_test_constraint_evaluations_snapshot() {
+    use winterfell::{math::fields::f128::BaseElement, Air, TraceInfo};
+
+    let pub_inputs = super::PublicInputs {
+        tree_root: [BaseElement::new(42), BaseElement::new(43)],
+    };
+    let trace_info = TraceInfo::new(super::TRACE_WIDTH, 64);
+    let air = super::MerkleAir::new(trace_info, pub_inputs, build_options(false));
+    crate::tests::test_constraint_evaluations_snapshot(&air, "merkle");
+}
+
 fn build_options(use_extension_field: bool) -> ProofOptions {
     let extension = if use_extension_field {
         FieldExtension::Quadratic
@@ -6,8 +6,8 @@
 use super::{rescue, BaseElement, FieldElement, ProofOptions, CYCLE_LENGTH, TRACE_WIDTH};
 use crate::utils::{are_equal, is_zero, not, EvaluationResult};
 use winterfell::{
-    math::ToElements, Air, AirContext, Assertion, EvaluationFrame, TraceInfo,
-    TransitionConstraintDegree,
+    math::ToElements, Air, AirContext, Assertion, Assertions, EvaluationFrame, TraceInfo,
+    TraceStep, TransitionConstraintDegree,
 };
 
 // CONSTANTS
@@ -36,6 +36,7 @@ const CYCLE_MASK: [BaseElement; CYCLE_LENGTH] = [
 // RESCUE AIR
 // ================================================================================================
 
+#[derive(Clone)]
 pub struct PublicInputs {
     pub seed: [BaseElement; 2],
     pub result: [BaseElement; 2],
@@ -107,13 +108,13 @@ impl Air for RescueAir {
 
     fn get_assertions(&self) -> Vec<Assertion<Self::BaseField>> {
         // Assert starting and ending values of the hash chain
-        let last_step = self.trace_length() - 1;
-        vec![
-            Assertion::single(0, 0, self.seed[0]),
-            Assertion::single(1, 0, self.seed[1]),
-            Assertion::single(0, last_step, self.result[0]),
-            Assertion::single(1, last_step, self.result[1]),
-        ]
+        Assertions::new()
+            .single(0, TraceStep::first(), self.seed[0])
+            .single(1, TraceStep::first(), self.seed[1])
+            .single(0, TraceStep::last(), self.result[0])
+            .single(1, TraceStep::last(), self.result[1])
+            .build(self.trace_info())
+            .expect("invalid hash chain assertions")
     }
 
     fn get_periodic_column_values(&self) -> Vec<Vec<Self::BaseField>> {
@@ -33,6 +33,40 @@ fn rescue_test_basic_proof_verification_fail() {
     crate::tests::test_basic_proof_verification_fail(rescue_eg);
 }
 
+#[test]
+fn rescue_test_solidity_verifier() {
+    use crate::{solidity, Example, Keccak256};
+    use rand_utils::rand_value;
+
+    let rescue = super::RescueExample::<Keccak256>::new(128, build_options(false));
+    let pub_inputs = super::PublicInputs {
+        seed: rescue.seed,
+        result: rescue.result,
+    };
+    let sample_inputs = || super::PublicInputs {
+        seed: [rand_value(), rand_value()],
+        result: [rand_value(), rand_value()],
+    };
+    solidity::tests::test_solidity_verifier::<super::RescueAir>(
+        rescue.prove(),
+        pub_inputs,
+        [sample_inputs(), sample_inputs()],
+    );
+}
+
+#[test]
+fn rescue_test_constraint_evaluations_snapshot() {
+    use winterfell::{math::fields::f128::BaseElement, Air, TraceInfo};
+
+    let pub_inputs = super::PublicInputs {
+        seed: [BaseElement::new(42), BaseElement::new(43)],
+        result: [BaseElement::new(44), BaseElement::new(45)],
+    };
+    let trace_info = TraceInfo::new(super::TRACE_WIDTH, 128);
+    let air = super::RescueAir::new(trace_info, pub_inputs, build_options(false));
+    crate::tests::test_constraint_evaluations_snapshot(&air, "rescue");
+}
+
 fn build_options(use_extension_field: bool) -> ProofOptions {
     let extension = if use_extension_field {
         FieldExtension::Quadratic
@@ -8,7 +8,6 @@ use super::{
     BaseElement, ExtensionOf, FieldElement, ProofOptions, CYCLE_LENGTH, TRACE_WIDTH,
 };
 use crate::utils::{are_equal, not, EvaluationResult};
-use core_utils::flatten_slice_elements;
 use winterfell::{
     math::ToElements, Air, AirContext, Assertion, AuxTraceRandElements, EvaluationFrame, TraceInfo,
     TransitionConstraintDegree,
@@ -46,7 +45,7 @@ pub struct PublicInputs {
 
 impl ToElements<BaseElement> for PublicInputs {
     fn to_elements(&self) -> Vec<BaseElement> {
-        flatten_slice_elements(&self.result).to_vec()
+        self.result.iter().flatten().copied().collect()
     }
 }
 
@@ -97,7 +97,7 @@ impl<B: StarkField> RapTraceTable<B> {
             meta.len()
         );
 
-        let columns = unsafe { (0..width).map(|_| uninit_vector(length)).collect() };
+        let columns = (0..width).map(|_| B::zeroed_vector(length)).collect();
         Self {
             layout: TraceLayout::new(width, [3], [3]),
             trace: ColMatrix::new(columns),
@@ -33,6 +33,32 @@ fn rescue_test_basic_proof_verification_fail() {
     crate::tests::test_basic_proof_verification_fail(rescue_eg);
 }
 
+#[test]
+fn rescue_raps_test_constraint_evaluations_snapshot() {
+    use winterfell::{math::fields::f128::BaseElement, Air, TraceInfo, TraceLayout};
+
+    let result = [
+        [BaseElement::new(42), BaseElement::new(43)],
+        [BaseElement::new(44), BaseElement::new(45)],
+    ];
+    let layout = TraceLayout::new(super::TRACE_WIDTH, [3], [3]);
+    let trace_info = TraceInfo::new_multi_segment(layout, 128, vec![]);
+    let air = super::RescueRapsAir::new(
+        trace_info,
+        super::PublicInputs { result },
+        build_options(false),
+    );
+    crate::tests::test_constraint_evaluations_snapshot(&air, "rescue_raps");
+}
+
+#[test]
+fn rescue_test_basic_proof_verification_base_field_composition() {
+    // random elements for the auxiliary trace segment are drawn from the extension field
+    let options = build_options(true).set_base_field_composition(true);
+    let rescue_eg = Box::new(super::RescueRapsExample::<Blake3_256>::new(128, options));
+    crate::tests::test_basic_proof_verification(rescue_eg);
+}
+
 fn build_options(use_extension_field: bool) -> ProofOptions {
     let extension = if use_extension_field {
         FieldExtension::Quadratic
@@ -0,0 +1,271 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{Keccak256, SolidityError, VerifierParams};
+use winterfell::{
+    crypto::{DefaultRandomCoin, Digest, ElementHasher, Hasher, RandomCoin},
+    math::{fields::f128::BaseElement, StarkField, ToElements},
+    Air, ConstraintCompositionCoefficients, StarkProof,
+};
+
+// CALLDATA
+// ================================================================================================
+
+/// Arguments of the `verify()` function of a generated verifier contract.
+///
+/// Every argument is an array of 256-bit words; each word is stored as 32 bytes in big-endian
+/// order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Calldata {
+    pub public_inputs: Vec<[u8; 32]>,
+    pub proof: Vec<[u8; 32]>,
+}
+
+impl Calldata {
+    /// Signature of the verification function of generated contracts.
+    pub const VERIFY_SIGNATURE: &'static str = "verify(uint256[],uint256[])";
+
+    /// Returns ABI-encoded data of a transaction which calls the `verify()` function of a
+    /// generated verifier with these arguments.
+    pub fn encode(&self) -> Vec<u8> {
+        let selector = Keccak256::hash(Self::VERIFY_SIGNATURE.as_bytes());
+
+        let mut result = selector.as_bytes()[..4].to_vec();
+        // offsets of the arrays are relative to the start of the arguments section
+        result.extend_from_slice(&to_word(64));
+        result.extend_from_slice(&to_word(64 + 32 * (self.public_inputs.len() as u128 + 1)));
+        for values in [&self.public_inputs, &self.proof] {
+            result.extend_from_slice(&to_word(values.len() as u128));
+            for value in values.iter() {
+                result.extend_from_slice(value);
+            }
+        }
+        result
+    }
+}
+
+/// Converts the `proof` generated for the specified public inputs into arguments of a generated
+/// verifier contract.
+///
+/// # Errors
+/// Returns an error if the proof uses features which generated verifiers do not support, or if
+/// the proof is malformed.
+pub fn build_calldata<A>(
+    proof: &StarkProof,
+    pub_inputs: A::PublicInputs,
+) -> Result<Calldata, SolidityError>
+where
+    A: Air<BaseField = BaseElement>,
+{
+    let public_inputs = pub_inputs.to_elements();
+    let air = A::new(proof.get_trace_info(), pub_inputs, proof.options().clone());
+    let params = VerifierParams::new(&air)?;
+    if proof.fri_proof.num_partitions() != 1 {
+        return Err(SolidityError::UnsupportedFeature(
+            "partitioned FRI layers".to_string(),
+        ));
+    }
+
+    let parsed = ParsedProof::new(proof, &params)?;
+    let transcript = Transcript::new(&air, proof, &parsed, &public_inputs)?;
+    let positions = transcript.query_positions;
+    let invalid_proof = |err: String| SolidityError::InvalidProof(err);
+
+    let mut words = Vec::new();
+
+    // commitments
+    words.push(parsed.trace_root.as_bytes());
+    words.push(parsed.constraint_root.as_bytes());
+    words.extend(parsed.fri_roots.iter().map(|root| root.as_bytes()));
+
+    // out-of-domain frame and proof-of-work nonce
+    words.extend(parsed.ood_current.iter().map(to_element_word));
+    words.extend(parsed.ood_next.iter().map(to_element_word));
+    words.extend(parsed.ood_constraints.iter().map(to_element_word));
+    words.push(to_word(proof.pow_nonce as u128));
+
+    // trace and constraint queries; Merkle paths are listed without the leaf node which the
+    // verifier computes from the queried values
+    let queries = [
+        (&proof.trace_queries[0], params.trace_width),
+        (&proof.constraint_queries, params.num_composition_columns),
+    ];
+    for (queries, width) in queries {
+        let (merkle_proof, values) = queries
+            .clone()
+            .parse::<Keccak256, BaseElement>(params.lde_domain_size, params.num_queries, width)
+            .map_err(|err| invalid_proof(err.to_string()))?;
+        let paths = merkle_proof
+            .into_paths(&positions)
+            .map_err(|err| invalid_proof(err.to_string()))?;
+        for (row, path) in values.rows().zip(paths) {
+            words.extend(row.iter().map(to_element_word));
+            words.extend(path[1..].iter().map(|node| node.as_bytes()));
+        }
+    }
+
+    // FRI remainder
+    words.extend(parsed.fri_remainder.iter().map(to_element_word));
+
+    // FRI layer queries
+    let (layer_queries, layer_proofs) = proof
+        .fri_proof
+        .clone()
+        .parse_layers::<Keccak256, BaseElement>(params.lde_domain_size, params.folding_factor)
+        .map_err(|err| invalid_proof(err.to_string()))?;
+    let mut positions = positions;
+    let mut domain_size = params.lde_domain_size;
+    for (values, merkle_proof) in layer_queries.into_iter().zip(layer_proofs) {
+        positions = fri::folding::fold_positions(&positions, domain_size, params.folding_factor);
+        let paths = merkle_proof
+            .into_paths(&positions)
+            .map_err(|err| invalid_proof(err.to_string()))?;
+        for (row, path) in values.chunks(params.folding_factor).zip(paths) {
+            words.extend(row.iter().map(to_element_word));
+            words.extend(path[1..].iter().map(|node| node.as_bytes()));
+        }
+        domain_size /= params.folding_factor;
+    }
+
+    Ok(Calldata {
+        public_inputs: public_inputs.iter().map(to_element_word).collect(),
+        proof: words,
+    })
+}
+
+// PARSED PROOF
+// ================================================================================================
+
+/// Components of a proof which are read by the verifier before the queries.
+pub(super) struct ParsedProof {
+    pub trace_root: <Keccak256 as Hasher>::Digest,
+    pub constraint_root: <Keccak256 as Hasher>::Digest,
+    pub fri_roots: Vec<<Keccak256 as Hasher>::Digest>,
+    pub ood_current: Vec<BaseElement>,
+    pub ood_next: Vec<BaseElement>,
+    pub ood_constraints: Vec<BaseElement>,
+    pub fri_remainder: Vec<BaseElement>,
+}
+
+impl ParsedProof {
+    pub(super) fn new(proof: &StarkProof, params: &VerifierParams) -> Result<Self, SolidityError> {
+        let invalid_proof = |err: String| SolidityError::InvalidProof(err);
+
+        let (trace_roots, constraint_root, fri_roots) = proof
+            .commitments
+            .clone()
+            .parse::<Keccak256>(1, params.num_fri_layers)
+            .map_err(|err| invalid_proof(err.to_string()))?;
+        let (ood_frame, _, ood_constraints) = proof
+            .ood_frame
+            .clone()
+            .parse::<BaseElement>(params.trace_width, 0, params.num_composition_columns)
+            .map_err(|err| invalid_proof(err.to_string()))?;
+        let fri_remainder = proof
+            .fri_proof
+            .parse_remainder::<BaseElement>()
+            .map_err(|err| invalid_proof(err.to_string()))?;
+        if fri_remainder.len() != params.remainder_size {
+            return Err(invalid_proof(format!(
+                "expected FRI remainder with {} coefficients, but was {}",
+                params.remainder_size,
+                fri_remainder.len()
+            )));
+        }
+
+        Ok(Self {
+            trace_root: trace_roots[0],
+            constraint_root,
+            fri_roots,
+            ood_current: ood_frame.current().to_vec(),
+            ood_next: ood_frame.next().to_vec(),
+            ood_constraints,
+            fri_remainder,
+        })
+    }
+}
+
+// TRANSCRIPT
+// ================================================================================================
+
+/// Random values drawn by the verifier from the public coin.
+///
+/// Only query positions are needed to build calldata; the remaining values are used to test
+/// generated constraint evaluation code.
+pub(super) struct Transcript {
+    #[allow(dead_code)]
+    pub constraint_coefficients: ConstraintCompositionCoefficients<BaseElement>,
+    #[allow(dead_code)]
+    pub z: BaseElement,
+    pub query_positions: Vec<usize>,
+}
+
+impl Transcript {
+    /// Replays the interaction between the prover and the verifier in the same way as the
+    /// verifier does.
+    pub(super) fn new<A: Air<BaseField = BaseElement>>(
+        air: &A,
+        proof: &StarkProof,
+        parsed: &ParsedProof,
+        public_inputs: &[BaseElement],
+    ) -> Result<Self, SolidityError> {
+        let coin_error = |err: String| SolidityError::InvalidProof(err);
+
+        let mut seed = proof.context.to_elements();
+        seed.extend_from_slice(public_inputs);
+        let mut coin = DefaultRandomCoin::<Keccak256>::new(&seed);
+
+        coin.reseed(parsed.trace_root);
+        let constraint_coefficients = air
+            .get_constraint_composition_coefficients::<BaseElement, _>(&mut coin)
+            .map_err(|err| coin_error(err.to_string()))?;
+
+        coin.reseed(parsed.constraint_root);
+        let z = coin.draw().map_err(|err| coin_error(err.to_string()))?;
+        coin.reseed(Keccak256::hash_elements(&parsed.ood_current));
+        coin.reseed(Keccak256::hash_elements(&parsed.ood_next));
+        coin.reseed(Keccak256::hash_elements(&parsed.ood_constraints));
+
+        air.get_deep_composition_coefficients::<BaseElement, _>(&mut coin)
+            .map_err(|err| coin_error(err.to_string()))?;
+        let options = air.options().to_fri_options();
+        let mut domain_size = air.lde_domain_size();
+        for (depth, root) in parsed.fri_roots.iter().enumerate() {
+            coin.reseed(*root);
+            coin.reseed_with_int(fri::utils::get_layer_salt(depth, domain_size));
+            coin.draw::<BaseElement>()
+                .map_err(|err| coin_error(err.to_string()))?;
+            domain_size /= options.folding_factor();
+        }
+
+        coin.reseed_with_int(proof.pow_nonce);
+        if coin.leading_zeros() < air.options().grinding_factor() {
+            return Err(coin_error("proof-of-work verification failed".to_string()));
+        }
+        let query_positions = air
+            .get_query_positions(&mut coin)
+            .map_err(|err| coin_error(err.to_string()))?;
+
+        Ok(Self {
+            constraint_coefficients,
+            z,
+            query_positions,
+        })
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns the big-endian encoding of the value as a 256-bit word.
+fn to_word(value: u128) -> [u8; 32] {
+    let mut result = [0; 32];
+    result[16..].copy_from_slice(&value.to_be_bytes());
+    result
+}
+
+fn to_element_word(element: &BaseElement) -> [u8; 32] {
+    to_word(element.as_int())
+}
@@ -0,0 +1,465 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+//! Generation of Solidity verifiers for STARK proofs.
+//!
+//! A verifier contract is generated for a specific computation (defined by an [Air]) with a fixed
+//! execution trace shape and fixed [ProofOptions](winterfell::ProofOptions). Public inputs are
+//! passed to the contract together with the proof; [build_calldata()] converts a [StarkProof]
+//! into the format expected by the contract.
+//!
+//! Constraints of the computation are translated into Solidity automatically: the generator
+//! evaluates the constraints of the [Air] over symbolic field elements, and emits the recorded
+//! operations as straight-line code. For this to work, every value which the [Air] derives from
+//! public inputs must be equal to one of the public input elements; for example, an assertion
+//! value may be taken directly from public inputs, but not computed from them.
+//!
+//! Generated verifiers support only a subset of the features of the Winterfell verifier:
+//! - computations must be defined over the 128-bit field, and proofs must be generated with
+//!   Keccak256 as the hash function and without field extensions;
+//! - execution traces must consist of a single segment without preprocessed columns;
+//! - queries must be sampled uniformly from the LDE domain.
+//!
+//! Merkle authentication paths are passed to the contract separately for every query rather than
+//! as batch proofs; this increases the size of the calldata, but considerably simplifies the
+//! verification logic.
+
+use crate::Keccak256;
+use core::fmt;
+use winterfell::{
+    math::{fields::f128::BaseElement, FieldElement, StarkField, ToElements},
+    Air, AuxTraceRandElements, ConstraintCompositionCoefficients, EvaluationFrame, FieldExtension,
+    GrindingFunction, QuerySampling, StarkProof,
+};
+
+mod symbolic;
+use symbolic::{Node, SymbolicElement};
+
+mod calldata;
+pub use calldata::{build_calldata, Calldata};
+
+#[cfg(test)]
+pub(crate) mod tests;
+
+// CONSTANTS
+// ================================================================================================
+
+/// Template of the verifier contract.
+const VERIFIER_TEMPLATE: &str = include_str!("verifier.sol");
+
+// VERIFIER GENERATION
+// ================================================================================================
+
+/// Returns source code of a Solidity contract named `name` which verifies proofs of the
+/// computation specified by the `AIR` type parameter.
+///
+/// The trace shape and proof options of the contract are taken from `proof`, which can be any
+/// proof of the computation. The contract is built by evaluating constraints of the computation
+/// instantiated with each of the `sample_inputs`; the samples must differ in every public input
+/// element, and should be chosen at random to make sure that constants of the computation are
+/// not mistaken for public inputs.
+///
+/// # Errors
+/// Returns an error if:
+/// - The computation or the proof options use features which generated verifiers do not support.
+/// - Sample public inputs are inconsistent, or the way the computation depends on public inputs
+///   cannot be expressed in the generated contract.
+pub fn generate_verifier<A>(
+    name: &str,
+    proof: &StarkProof,
+    sample_inputs: [A::PublicInputs; 2],
+) -> Result<String, SolidityError>
+where
+    A: Air<BaseField = BaseElement>,
+{
+    let [inputs1, inputs2] = sample_inputs;
+    let public_inputs = [inputs1.to_elements(), inputs2.to_elements()];
+    if public_inputs[0].len() != public_inputs[1].len() {
+        return Err(SolidityError::InvalidSampleInputs(
+            "sample public inputs have different numbers of elements".to_string(),
+        ));
+    }
+    if public_inputs[0]
+        .iter()
+        .zip(public_inputs[1].iter())
+        .any(|(a, b)| a == b)
+    {
+        return Err(SolidityError::InvalidSampleInputs(
+            "sample public inputs must differ in every element".to_string(),
+        ));
+    }
+
+    let air1 = A::new(proof.get_trace_info(), inputs1, proof.options().clone());
+    let air2 = A::new(proof.get_trace_info(), inputs2, proof.options().clone());
+    let params = VerifierParams::new(&air1)?;
+
+    // record constraint evaluation for both samples; the resulting graphs must have the same
+    // structure, and constants which differ between the graphs must come from public inputs
+    let (root, graph1) = symbolic::record(|| evaluate_constraints(&air1).index());
+    let (_, graph2) = symbolic::record(|| evaluate_constraints(&air2).index());
+    let bindings = bind_public_inputs(&graph1, &graph2, &public_inputs)?;
+    let constraint_evaluation = build_constraint_evaluation(&graph1, &bindings, root);
+
+    let context_bytes = ToElements::<BaseElement>::to_elements(&proof.context)
+        .iter()
+        .flat_map(|element| element.as_int().to_le_bytes())
+        .collect::<Vec<u8>>();
+    let fri_layer_salts = params
+        .fri_layer_salts()
+        .iter()
+        .map(|salt| format!("uint64({salt})"))
+        .collect::<Vec<_>>();
+    let folding_root = BaseElement::get_root_of_unity(params.folding_factor.ilog2());
+
+    let replacements = [
+        ("NAME", name.to_string()),
+        ("MODULUS", BaseElement::MODULUS.to_string()),
+        ("TRACE_WIDTH", params.trace_width.to_string()),
+        (
+            "NUM_COMPOSITION_COLUMNS",
+            params.num_composition_columns.to_string(),
+        ),
+        ("NUM_PUBLIC_INPUTS", public_inputs[0].len().to_string()),
+        (
+            "NUM_CONSTRAINT_COEFFICIENTS",
+            params.num_constraint_coefficients.to_string(),
+        ),
+        ("NUM_QUERIES", params.num_queries.to_string()),
+        ("LDE_DOMAIN_SIZE", params.lde_domain_size.to_string()),
+        (
+            "LDE_DOMAIN_DEPTH",
+            params.lde_domain_size.ilog2().to_string(),
+        ),
+        (
+            "LDE_DOMAIN_GENERATOR",
+            air1.lde_domain_generator().to_string(),
+        ),
+        (
+            "TRACE_DOMAIN_GENERATOR",
+            air1.trace_domain_generator().to_string(),
+        ),
+        ("DOMAIN_OFFSET", air1.domain_offset().to_string()),
+        (
+            "GRINDING_FACTOR",
+            air1.options().grinding_factor().to_string(),
+        ),
+        ("FOLDING_FACTOR", params.folding_factor.to_string()),
+        (
+            "FOLDING_FACTOR_LOG2",
+            params.folding_factor.ilog2().to_string(),
+        ),
+        (
+            "INV_FOLDING_FACTOR",
+            BaseElement::from(params.folding_factor as u64)
+                .inv()
+                .to_string(),
+        ),
+        ("INV_FOLDING_ROOT", folding_root.inv().to_string()),
+        ("NUM_FRI_LAYERS", params.num_fri_layers.to_string()),
+        ("REMAINDER_SIZE", params.remainder_size.to_string()),
+        ("CONTEXT", hex::encode(context_bytes)),
+        ("FRI_LAYER_SALTS", fri_layer_salts.join(", ")),
+        ("CONSTRAINT_EVALUATION", constraint_evaluation),
+    ];
+
+    let mut source = VERIFIER_TEMPLATE.to_string();
+    for (key, value) in replacements {
+        source = source.replace(&format!("{{{{{key}}}}}"), &value);
+    }
+    debug_assert!(
+        !source.contains("{{"),
+        "not all template values were replaced"
+    );
+
+    Ok(source)
+}
+
+// ERRORS
+// ================================================================================================
+
+/// Represents an error returned during generation of Solidity verifiers and their calldata.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SolidityError {
+    /// The computation or proof options use features which generated verifiers do not support.
+    UnsupportedFeature(String),
+    /// Sample public inputs cannot be used to determine how the computation depends on public
+    /// inputs.
+    InvalidSampleInputs(String),
+    /// The proof could not be converted into calldata.
+    InvalidProof(String),
+}
+
+impl fmt::Display for SolidityError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnsupportedFeature(feature) => {
+                write!(f, "generated verifiers do not support {feature}")
+            }
+            Self::InvalidSampleInputs(err) => {
+                write!(f, "invalid sample public inputs: {err}")
+            }
+            Self::InvalidProof(err) => {
+                write!(f, "proof could not be converted into calldata: {err}")
+            }
+        }
+    }
+}
+
+// VERIFIER PARAMETERS
+// ================================================================================================
+
+/// Parameters of a computation and its proofs which are fixed in a generated verifier.
+struct VerifierParams {
+    trace_width: usize,
+    num_composition_columns: usize,
+    num_constraint_coefficients: usize,
+    num_queries: usize,
+    lde_domain_size: usize,
+    folding_factor: usize,
+    num_fri_layers: usize,
+    remainder_size: usize,
+}
+
+impl VerifierParams {
+    /// Returns parameters of the computation described by the `air`, or an error if the
+    /// computation cannot be verified by generated verifiers.
+    fn new<A: Air<BaseField = BaseElement>>(air: &A) -> Result<Self, SolidityError> {
+        let options = air.options();
+        if options.field_extension() != FieldExtension::None {
+            return Err(SolidityError::UnsupportedFeature(
+                "field extensions".to_string(),
+            ));
+        }
+        if options.query_sampling() != QuerySampling::Uniform {
+            return Err(SolidityError::UnsupportedFeature(
+                "stratified query sampling".to_string(),
+            ));
+        }
+        if options.aggregates_fri_layer_proofs() {
+            return Err(SolidityError::UnsupportedFeature(
+                "aggregated FRI layer proofs".to_string(),
+            ));
+        }
+        if options.grinding_function() != GrindingFunction::Hash {
+            return Err(SolidityError::UnsupportedFeature(
+                "memory-hard grinding".to_string(),
+            ));
+        }
+        let layout = air.trace_layout();
+        if layout.num_segments() > 1 {
+            return Err(SolidityError::UnsupportedFeature(
+                "auxiliary trace segments".to_string(),
+            ));
+        }
+        if layout.has_preprocessed_columns() {
+            return Err(SolidityError::UnsupportedFeature(
+                "preprocessed trace columns".to_string(),
+            ));
+        }
+
+        let fri_options = options.to_fri_options();
+        let folding_factor = fri_options.folding_factor();
+        let lde_domain_size = air.lde_domain_size();
+        let num_fri_layers = fri_options.num_fri_layers(lde_domain_size);
+
+        // the degree of the DEEP composition polynomial must be reducible by the folding factor
+        // at every FRI layer
+        let mut max_degree_plus_1 = air.trace_poly_degree() + 1;
+        for depth in 0..num_fri_layers {
+            if max_degree_plus_1 % folding_factor != 0 {
+                return Err(SolidityError::UnsupportedFeature(format!(
+                    "FRI degree truncation at layer {depth}"
+                )));
+            }
+            max_degree_plus_1 /= folding_factor;
+        }
+
+        Ok(Self {
+            trace_width: layout.main_trace_width(),
+            num_composition_columns: air.ce_blowup_factor(),
+            num_constraint_coefficients: 2
+                * (air.context().num_transition_constraints() + air.context().num_assertions()),
+            num_queries: options.num_queries(),
+            lde_domain_size,
+            folding_factor,
+            num_fri_layers,
+            remainder_size: lde_domain_size
+                / folding_factor.pow(num_fri_layers as u32)
+                / options.blowup_factor(),
+        })
+    }
+
+    /// Returns the values with which the public coin is reseeded after each FRI layer commitment
+    /// (including the commitment to the remainder).
+    fn fri_layer_salts(&self) -> Vec<u64> {
+        (0..=self.num_fri_layers)
+            .map(|depth| {
+                let domain_size = self.lde_domain_size / self.folding_factor.pow(depth as u32);
+                fri::utils::get_layer_salt(depth, domain_size)
+            })
+            .collect()
+    }
+}
+
+// CONSTRAINT EVALUATION
+// ================================================================================================
+
+/// Evaluates constraints of the `air` at the out-of-domain point in the same way as the
+/// verifier does, but over symbolic elements.
+///
+/// The inputs of the evaluation are laid out as follows: the out-of-domain point z, the current
+/// and the next rows of the out-of-domain trace frame, and pairs of constraint composition
+/// coefficients for transition constraints followed by pairs of coefficients for assertions.
+fn evaluate_constraints<A: Air<BaseField = BaseElement>>(air: &A) -> SymbolicElement {
+    let trace_width = air.trace_layout().main_trace_width();
+    let num_transition_constraints = air.context().num_transition_constraints();
+    let num_assertions = air.context().num_assertions();
+
+    let mut inputs = (0..).map(SymbolicElement::input);
+    let x = inputs.next().unwrap();
+    let current = inputs.by_ref().take(trace_width).collect::<Vec<_>>();
+    let next = inputs.by_ref().take(trace_width).collect::<Vec<_>>();
+    let mut coefficient_pairs = || {
+        let first = inputs.next().unwrap();
+        (first, inputs.next().unwrap())
+    };
+    let coefficients = ConstraintCompositionCoefficients {
+        transition: (0..num_transition_constraints)
+            .map(|_| coefficient_pairs())
+            .collect(),
+        boundary: (0..num_assertions).map(|_| coefficient_pairs()).collect(),
+    };
+    let frame = EvaluationFrame::from_rows(current, next);
+
+    // evaluate transition constraints and merge them into a single value
+    let t_constraints = air.get_transition_constraints(&coefficients.transition);
+    let periodic_values = air
+        .get_periodic_column_polys()
+        .iter()
+        .map(|poly| {
+            let num_cycles = air.trace_length() / poly.len();
+            let x = x.exp_vartime((num_cycles as u32).into());
+            winterfell::math::polynom::eval(poly, x)
+        })
+        .collect::<Vec<_>>();
+    let mut t_evaluations = SymbolicElement::zeroed_vector(t_constraints.num_main_constraints());
+    air.evaluate_transition(&frame, &periodic_values, &mut t_evaluations);
+    let mut result = t_constraints.combine_evaluations::<SymbolicElement>(&t_evaluations, &[], x);
+
+    // evaluate boundary constraints
+    let b_constraints =
+        air.get_boundary_constraints(&AuxTraceRandElements::new(), &coefficients.boundary);
+    for group in b_constraints.main_constraints() {
+        let xp = x.exp_vartime(group.degree_adjustment().into());
+        result += group.evaluate_at(frame.current(), x, xp);
+    }
+
+    result
+}
+
+/// Returns a binding to public input elements for every node of the graph `graph1`.
+///
+/// A node is bound to a public input element if it is a constant which differs from the
+/// corresponding constant of `graph2`, and both constants are equal to the same element of the
+/// respective `public_inputs`.
+fn bind_public_inputs(
+    graph1: &[Node],
+    graph2: &[Node],
+    public_inputs: &[Vec<BaseElement>; 2],
+) -> Result<Vec<Option<usize>>, SolidityError> {
+    let structure_error = || {
+        SolidityError::InvalidSampleInputs(
+            "structure of constraints depends on public inputs".to_string(),
+        )
+    };
+    if graph1.len() != graph2.len() {
+        return Err(structure_error());
+    }
+
+    let mut bindings = Vec::with_capacity(graph1.len());
+    for (node1, node2) in graph1.iter().zip(graph2) {
+        let binding =
+            match (node1, node2) {
+                (Node::Constant(value1), Node::Constant(value2)) if value1 != value2 => {
+                    let position = public_inputs[0].iter().zip(&public_inputs[1]).position(
+                        |(input1, input2)| input1.as_int() == *value1 && input2.as_int() == *value2,
+                    );
+                    match position {
+                        Some(position) => Some(position),
+                        None => {
+                            return Err(SolidityError::InvalidSampleInputs(format!(
+                                "constraints depend on value {value1} which is not a public input"
+                            )))
+                        }
+                    }
+                }
+                (Node::Constant(_), Node::Constant(_)) => None,
+                _ if node1 == node2 => None,
+                _ => return Err(structure_error()),
+            };
+        bindings.push(binding);
+    }
+
+    Ok(bindings)
+}
+
+/// Returns Solidity statements which evaluate the `root` node of the `graph`.
+///
+/// Values of nodes are stored in the `t` memory array; only nodes on which the root depends are
+/// evaluated. Constant nodes are inlined unless they are bound to public inputs.
+fn build_constraint_evaluation(graph: &[Node], bindings: &[Option<usize>], root: usize) -> String {
+    // mark the nodes needed to compute the root
+    let mut is_used = vec![false; graph.len()];
+    is_used[root] = true;
+    for i in (0..graph.len()).rev() {
+        if !is_used[i] {
+            continue;
+        }
+        match graph[i] {
+            Node::Add(a, b) | Node::Sub(a, b) | Node::Mul(a, b) => {
+                is_used[a] = true;
+                is_used[b] = true;
+            }
+            Node::Neg(a) | Node::Inv(a) => is_used[a] = true,
+            Node::Constant(_) | Node::Input(_) => (),
+        }
+    }
+
+    // assign a slot in the `t` array to each used operation
+    let mut slots = vec![0; graph.len()];
+    let mut num_slots = 0;
+    for (i, node) in graph.iter().enumerate() {
+        if is_used[i] && !matches!(node, Node::Constant(_) | Node::Input(_)) {
+            slots[i] = num_slots;
+            num_slots += 1;
+        }
+    }
+
+    let operand = |i: usize| match (graph[i], bindings[i]) {
+        (Node::Constant(_), Some(position)) => format!("publicInputs[{position}]"),
+        (Node::Constant(value), None) => value.to_string(),
+        (Node::Input(index), _) => format!("inputs[{index}]"),
+        _ => format!("t[{}]", slots[i]),
+    };
+
+    let mut code = format!("        uint256[] memory t = new uint256[]({num_slots});\n");
+    for (i, node) in graph.iter().enumerate() {
+        if !is_used[i] {
+            continue;
+        }
+        let value = match *node {
+            Node::Add(a, b) => format!("addmod({}, {}, P)", operand(a), operand(b)),
+            Node::Sub(a, b) => format!("addmod({}, P - {}, P)", operand(a), operand(b)),
+            Node::Mul(a, b) => format!("mulmod({}, {}, P)", operand(a), operand(b)),
+            Node::Neg(a) => format!("(P - {}) % P", operand(a)),
+            Node::Inv(a) => format!("_inv({})", operand(a)),
+            Node::Constant(_) | Node::Input(_) => continue,
+        };
+        code.push_str(&format!("        t[{}] = {value};\n", slots[i]));
+    }
+    code.push_str(&format!("        return {};", operand(root)));
+
+    code
+}
@@ -0,0 +1,371 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use core::{
+    cell::RefCell,
+    convert::TryFrom,
+    fmt::{Display, Formatter},
+    ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign},
+    slice,
+};
+use core_utils::{
+    AsBytes, ByteReader, ByteWriter, Deserializable, DeserializationError, Randomizable,
+    Serializable,
+};
+use std::collections::HashMap;
+use winterfell::math::{fields::f128::BaseElement, ExtensionOf, FieldElement, StarkField};
+
+// EXPRESSION GRAPH
+// ================================================================================================
+
+/// An operation in an expression graph; operands are referenced by their index in the graph.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Node {
+    Constant(u128),
+    Input(usize),
+    Add(usize, usize),
+    Sub(usize, usize),
+    Mul(usize, usize),
+    Neg(usize),
+    Inv(usize),
+}
+
+/// An expression graph recorded while evaluating a function over symbolic elements.
+///
+/// Identical nodes are recorded only once; thus, every node of the graph is computed exactly
+/// once, and nodes are always listed after their operands. The first two nodes of the graph are
+/// always the ZERO and ONE constants.
+#[derive(Debug, Default)]
+struct Graph {
+    nodes: Vec<Node>,
+    node_map: HashMap<Node, usize>,
+}
+
+impl Graph {
+    fn new() -> Self {
+        let mut graph = Self::default();
+        graph.insert(Node::Constant(0));
+        graph.insert(Node::Constant(1));
+        graph
+    }
+
+    fn insert(&mut self, node: Node) -> usize {
+        let nodes = &mut self.nodes;
+        *self.node_map.entry(node).or_insert_with(|| {
+            nodes.push(node);
+            nodes.len() - 1
+        })
+    }
+}
+
+thread_local! {
+    static GRAPH: RefCell<Option<Graph>> = const { RefCell::new(None) };
+}
+
+/// Executes `f` and returns its result together with the graph of all operations performed on
+/// symbolic elements during the execution.
+///
+/// Symbolic elements are valid only within the function in which they were created.
+pub fn record<R, F: FnOnce() -> R>(f: F) -> (R, Vec<Node>) {
+    GRAPH.with(|graph| {
+        let previous = graph.borrow_mut().replace(Graph::new());
+        assert!(previous.is_none(), "expression graphs cannot be nested");
+    });
+    let result = f();
+    let graph = GRAPH.with(|graph| graph.borrow_mut().take().expect("graph was removed"));
+    (result, graph.nodes)
+}
+
+#[cfg(test)]
+/// Evaluates all nodes of the `graph` for the specified input values.
+pub fn evaluate(graph: &[Node], inputs: &[BaseElement]) -> Vec<BaseElement> {
+    let mut values: Vec<BaseElement> = Vec::with_capacity(graph.len());
+    for node in graph {
+        let value = match *node {
+            Node::Constant(value) => BaseElement::new(value),
+            Node::Input(i) => inputs[i],
+            Node::Add(a, b) => values[a] + values[b],
+            Node::Sub(a, b) => values[a] - values[b],
+            Node::Mul(a, b) => values[a] * values[b],
+            Node::Neg(a) => -values[a],
+            Node::Inv(a) => values[a].inv(),
+        };
+        values.push(value);
+    }
+    values
+}
+
+// SYMBOLIC ELEMENT
+// ================================================================================================
+
+/// A field element which records operations performed on it into an expression graph instead of
+/// computing their results.
+///
+/// A symbolic element is represented by the index of its node in the expression graph encoded
+/// as an f128 field element. Conversions into base field elements and bytes, as well as
+/// serialization, operate on this representation rather than on the value of the expression.
+/// Symbolic elements cannot be drawn at random. Operations on constants are computed eagerly over
+/// the f128 field.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[repr(transparent)]
+pub struct SymbolicElement(BaseElement);
+
+impl SymbolicElement {
+    /// Returns a symbolic element referring to the input with the specified index.
+    pub fn input(index: usize) -> Self {
+        Self::insert(Node::Input(index))
+    }
+
+    /// Returns the index of the node of this element in the expression graph.
+    pub fn index(&self) -> usize {
+        self.0.as_int() as usize
+    }
+
+    fn constant(value: BaseElement) -> Self {
+        Self::insert(Node::Constant(value.as_int()))
+    }
+
+    fn insert(node: Node) -> Self {
+        GRAPH.with(|graph| {
+            let mut graph = graph.borrow_mut();
+            let graph = graph
+                .as_mut()
+                .expect("symbolic elements can be used only while recording an expression graph");
+            Self(BaseElement::new(graph.insert(node) as u128))
+        })
+    }
+
+    fn as_constant(&self) -> Option<BaseElement> {
+        GRAPH.with(|graph| match graph.borrow().as_ref()?.nodes[self.index()] {
+            Node::Constant(value) => Some(BaseElement::new(value)),
+            _ => None,
+        })
+    }
+}
+
+impl FieldElement for SymbolicElement {
+    type PositiveInteger = u128;
+    type BaseField = BaseElement;
+
+    const EXTENSION_DEGREE: usize = 1;
+
+    const ELEMENT_BYTES: usize = BaseElement::ELEMENT_BYTES;
+
+    const IS_CANONICAL: bool = true;
+
+    const ZERO: Self = Self(BaseElement::new(0));
+
+    const ONE: Self = Self(BaseElement::new(1));
+
+    fn inv(self) -> Self {
+        match self.as_constant() {
+            Some(value) => Self::constant(value.inv()),
+            None => Self::insert(Node::Inv(self.index())),
+        }
+    }
+
+    fn conjugate(&self) -> Self {
+        *self
+    }
+
+    fn base_element(&self, i: usize) -> Self::BaseField {
+        match i {
+            0 => self.0,
+            _ => panic!("element index must be 0, but was {i}"),
+        }
+    }
+
+    fn slice_as_base_elements(elements: &[Self]) -> &[Self::BaseField] {
+        // this is safe because symbolic elements are transparent wrappers of base field elements
+        unsafe { slice::from_raw_parts(elements.as_ptr() as *const BaseElement, elements.len()) }
+    }
+
+    fn slice_from_base_elements(elements: &[Self::BaseField]) -> &[Self] {
+        // this is safe because symbolic elements are transparent wrappers of base field elements
+        unsafe { slice::from_raw_parts(elements.as_ptr() as *const Self, elements.len()) }
+    }
+
+    fn elements_as_bytes(elements: &[Self]) -> &[u8] {
+        BaseElement::elements_as_bytes(Self::slice_as_base_elements(elements))
+    }
+
+    unsafe fn bytes_as_elements(bytes: &[u8]) -> Result<&[Self], DeserializationError> {
+        BaseElement::bytes_as_elements(bytes).map(Self::slice_from_base_elements)
+    }
+}
+
+impl ExtensionOf<BaseElement> for SymbolicElement {
+    fn mul_base(self, other: BaseElement) -> Self {
+        self * Self::from(other)
+    }
+}
+
+impl Display for SymbolicElement {
+    fn fmt(&self, f: &mut Formatter) -> core::fmt::Result {
+        write!(f, "node {}", self.index())
+    }
+}
+
+// OVERLOADED OPERATORS
+// ================================================================================================
+
+impl Add for SymbolicElement {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        match (self.as_constant(), rhs.as_constant()) {
+            (Some(a), Some(b)) => Self::constant(a + b),
+            _ if self == Self::ZERO => rhs,
+            _ if rhs == Self::ZERO => self,
+            _ => Self::insert(Node::Add(self.index(), rhs.index())),
+        }
+    }
+}
+
+impl AddAssign for SymbolicElement {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs
+    }
+}
+
+impl Sub for SymbolicElement {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        match (self.as_constant(), rhs.as_constant()) {
+            (Some(a), Some(b)) => Self::constant(a - b),
+            _ if rhs == Self::ZERO => self,
+            _ => Self::insert(Node::Sub(self.index(), rhs.index())),
+        }
+    }
+}
+
+impl SubAssign for SymbolicElement {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl Mul for SymbolicElement {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        match (self.as_constant(), rhs.as_constant()) {
+            (Some(a), Some(b)) => Self::constant(a * b),
+            _ if self == Self::ZERO || rhs == Self::ZERO => Self::ZERO,
+            _ if self == Self::ONE => rhs,
+            _ if rhs == Self::ONE => self,
+            _ => Self::insert(Node::Mul(self.index(), rhs.index())),
+        }
+    }
+}
+
+impl MulAssign for SymbolicElement {
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = *self * rhs
+    }
+}
+
+impl Div for SymbolicElement {
+    type Output = Self;
+
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn div(self, rhs: Self) -> Self {
+        self * rhs.inv()
+    }
+}
+
+impl DivAssign for SymbolicElement {
+    fn div_assign(&mut self, rhs: Self) {
+        *self = *self / rhs
+    }
+}
+
+impl Neg for SymbolicElement {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        match self.as_constant() {
+            Some(value) => Self::constant(-value),
+            None => Self::insert(Node::Neg(self.index())),
+        }
+    }
+}
+
+// TYPE CONVERSIONS
+// ================================================================================================
+
+impl From<BaseElement> for SymbolicElement {
+    fn from(value: BaseElement) -> Self {
+        Self::constant(value)
+    }
+}
+
+impl From<u128> for SymbolicElement {
+    fn from(value: u128) -> Self {
+        Self::constant(BaseElement::new(value))
+    }
+}
+
+impl From<u64> for SymbolicElement {
+    fn from(value: u64) -> Self {
+        Self::constant(BaseElement::from(value))
+    }
+}
+
+impl From<u32> for SymbolicElement {
+    fn from(value: u32) -> Self {
+        Self::constant(BaseElement::from(value))
+    }
+}
+
+impl From<u16> for SymbolicElement {
+    fn from(value: u16) -> Self {
+        Self::constant(BaseElement::from(value))
+    }
+}
+
+impl From<u8> for SymbolicElement {
+    fn from(value: u8) -> Self {
+        Self::constant(BaseElement::from(value))
+    }
+}
+
+impl<'a> TryFrom<&'a [u8]> for SymbolicElement {
+    type Error = String;
+
+    fn try_from(bytes: &'a [u8]) -> Result<Self, Self::Error> {
+        BaseElement::try_from(bytes).map(Self)
+    }
+}
+
+impl AsBytes for SymbolicElement {
+    fn as_bytes(&self) -> &[u8] {
+        self.0.as_bytes()
+    }
+}
+
+impl Randomizable for SymbolicElement {
+    const VALUE_SIZE: usize = BaseElement::ELEMENT_BYTES;
+
+    fn from_random_bytes(_bytes: &[u8]) -> Option<Self> {
+        None
+    }
+}
+
+// SERIALIZATION / DESERIALIZATION
+// ================================================================================================
+
+impl Serializable for SymbolicElement {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        self.0.write_into(target)
+    }
+}
+
+impl Deserializable for SymbolicElement {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        BaseElement::read_from(source).map(Self)
+    }
+}
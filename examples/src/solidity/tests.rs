@@ -0,0 +1,473 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{
+    bind_public_inputs, build_calldata,
+    calldata::{ParsedProof, Transcript},
+    evaluate_constraints, generate_verifier, symbolic, Keccak256, Node, SolidityError,
+    VerifierParams,
+};
+use rand_utils::rand_vector;
+use winterfell::{
+    crypto::{DefaultRandomCoin, Digest, ElementHasher, Hasher},
+    math::{fields::f128::BaseElement, FieldElement, StarkField, ToElements},
+    Air, AirContext, Assertion, EvaluationFrame, FieldExtension, ProofOptions, Prover, StarkProof,
+    Trace, TraceInfo, TraceTable, TransitionConstraintDegree,
+};
+
+// SHARED TESTS
+// ================================================================================================
+
+/// Generates a verifier and calldata for the `proof`, and checks them against the proof.
+///
+/// This checks that calldata is laid out as the contract expects it, and that the generated
+/// constraint evaluation code computes the same value as the Winterfell verifier. When the
+/// `solc-tests` feature is enabled, the contract is also compiled and executed in an EVM.
+pub fn test_solidity_verifier<A>(
+    proof: StarkProof,
+    pub_inputs: A::PublicInputs,
+    sample_inputs: [A::PublicInputs; 2],
+) where
+    A: Air<BaseField = BaseElement>,
+    A::PublicInputs: Clone,
+{
+    let source = generate_verifier::<A>("Verifier", &proof, sample_inputs.clone()).unwrap();
+    assert!(source.contains("contract Verifier {"));
+    assert!(!source.contains("{{"));
+
+    let calldata = build_calldata::<A>(&proof, pub_inputs.clone()).unwrap();
+    let encoded = calldata.encode();
+    assert_eq!(
+        4 + 32 * (4 + calldata.public_inputs.len() + calldata.proof.len()),
+        encoded.len()
+    );
+
+    let public_inputs = pub_inputs.to_elements();
+    let air = A::new(proof.get_trace_info(), pub_inputs, proof.options().clone());
+    let params = VerifierParams::new(&air).unwrap();
+    let parsed = ParsedProof::new(&proof, &params).unwrap();
+    let transcript = Transcript::new(&air, &proof, &parsed, &public_inputs).unwrap();
+
+    check_calldata_layout(&calldata.proof, &params, &transcript.query_positions);
+    check_constraint_evaluation(&air, sample_inputs, &public_inputs, &parsed, &transcript);
+
+    #[cfg(feature = "solc-tests")]
+    evm::check_verifier(&source, &calldata);
+}
+
+// UNIT TESTS
+// ================================================================================================
+
+#[test]
+fn fold_row_matches_fri_folding() {
+    fn check<const N: usize>() {
+        let rows = (0..4)
+            .map(|_| rand_vector::<BaseElement>(N).try_into().unwrap())
+            .collect::<Vec<[BaseElement; N]>>();
+        let xs = rand_vector::<BaseElement>(4);
+        let inv_xs = xs.iter().map(|x| x.inv()).collect::<Vec<_>>();
+        let alpha = rand_vector::<BaseElement>(1)[0];
+
+        let expected = fri::folding::fold_rows(&rows, &inv_xs, alpha);
+        for ((row, &x), expected) in rows.iter().zip(&xs).zip(expected) {
+            assert_eq!(expected, fold_row(row, x, alpha));
+        }
+    }
+
+    check::<2>();
+    check::<4>();
+    check::<8>();
+    check::<16>();
+}
+
+#[test]
+fn public_inputs_are_bound_by_value() {
+    let public_inputs = [
+        vec![BaseElement::new(3), BaseElement::new(5)],
+        vec![BaseElement::new(4), BaseElement::new(6)],
+    ];
+    let graph1 = [
+        Node::Constant(0),
+        Node::Constant(1),
+        Node::Constant(5),
+        Node::Input(0),
+    ];
+    let graph2 = [
+        Node::Constant(0),
+        Node::Constant(1),
+        Node::Constant(6),
+        Node::Input(0),
+    ];
+    let bindings = bind_public_inputs(&graph1, &graph2, &public_inputs).unwrap();
+    assert_eq!(vec![None, None, Some(1), None], bindings);
+
+    // values which cannot be matched to public inputs result in an error
+    let graph2 = [
+        Node::Constant(0),
+        Node::Constant(1),
+        Node::Constant(7),
+        Node::Input(0),
+    ];
+    assert!(bind_public_inputs(&graph1, &graph2, &public_inputs).is_err());
+
+    // so do graphs with different structure
+    let graph2 = [
+        Node::Constant(0),
+        Node::Constant(1),
+        Node::Constant(5),
+        Node::Input(1),
+    ];
+    assert!(bind_public_inputs(&graph1, &graph2, &public_inputs).is_err());
+}
+
+#[test]
+fn invalid_verifier_inputs_are_rejected() {
+    let options = ProofOptions::new(28, 8, 0, FieldExtension::None, 4, 7);
+    let (proof, result) = build_fib_proof(options);
+
+    // sample public inputs must differ from each other
+    assert!(matches!(
+        generate_verifier::<FibAir>("Verifier", &proof, [result, result]),
+        Err(SolidityError::InvalidSampleInputs(_))
+    ));
+
+    // proofs which use field extensions cannot be verified by generated contracts
+    let options = ProofOptions::new(28, 8, 0, FieldExtension::Quadratic, 4, 7);
+    let (proof, result) = build_fib_proof(options);
+    assert!(matches!(
+        build_calldata::<FibAir>(&proof, result),
+        Err(SolidityError::UnsupportedFeature(_))
+    ));
+}
+
+// FIBONACCI AIR
+// ================================================================================================
+
+/// AIR for a Fibonacci sequence computed in 2 columns; the result is the last term of the
+/// sequence.
+struct FibAir {
+    context: AirContext<BaseElement>,
+    result: BaseElement,
+}
+
+impl Air for FibAir {
+    type BaseField = BaseElement;
+    type PublicInputs = BaseElement;
+
+    fn new(trace_info: TraceInfo, result: BaseElement, options: ProofOptions) -> Self {
+        let degrees = vec![
+            TransitionConstraintDegree::new(1),
+            TransitionConstraintDegree::new(1),
+        ];
+        FibAir {
+            context: AirContext::new(trace_info, degrees, 3, options),
+            result,
+        }
+    }
+
+    fn context(&self) -> &AirContext<Self::BaseField> {
+        &self.context
+    }
+
+    fn evaluate_transition<E: FieldElement + From<Self::BaseField>>(
+        &self,
+        frame: &EvaluationFrame<E>,
+        _periodic_values: &[E],
+        result: &mut [E],
+    ) {
+        let current = frame.current();
+        let next = frame.next();
+        result[0] = next[0] - (current[0] + current[1]);
+        result[1] = next[1] - (current[1] + next[0]);
+    }
+
+    fn get_assertions(&self) -> Vec<Assertion<Self::BaseField>> {
+        let last_step = self.trace_length() - 1;
+        vec![
+            Assertion::single(0, 0, BaseElement::ONE),
+            Assertion::single(1, 0, BaseElement::ONE),
+            Assertion::single(1, last_step, self.result),
+        ]
+    }
+}
+
+// FIBONACCI PROVER
+// ================================================================================================
+
+struct FibProver {
+    options: ProofOptions,
+}
+
+impl Prover for FibProver {
+    type BaseField = BaseElement;
+    type Air = FibAir;
+    type Trace = TraceTable<BaseElement>;
+    type HashFn = Keccak256;
+    type RandomCoin = DefaultRandomCoin<Keccak256>;
+
+    fn get_pub_inputs(&self, trace: &Self::Trace) -> BaseElement {
+        trace.get(1, trace.length() - 1)
+    }
+
+    fn options(&self) -> &ProofOptions {
+        &self.options
+    }
+}
+
+/// Returns a proof of a Fibonacci computation with an execution trace of 64 rows together with
+/// the result of the computation.
+fn build_fib_proof(options: ProofOptions) -> (StarkProof, BaseElement) {
+    let trace = TraceTable::from_transition(vec![BaseElement::ONE; 2], 64, |row| {
+        let next0 = row[0] + row[1];
+        vec![next0, row[1] + next0]
+    });
+    let prover = FibProver { options };
+    let result = prover.get_pub_inputs(&trace);
+    (prover.prove(trace).unwrap(), result)
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Folds a row of FRI layer values in the same way as the `_foldRow()` function of the
+/// generated contract.
+fn fold_row<const N: usize>(
+    row: &[BaseElement; N],
+    x: BaseElement,
+    alpha: BaseElement,
+) -> BaseElement {
+    let inv_root = BaseElement::get_root_of_unity(N.ilog2()).inv();
+    let step = alpha * x.inv();
+    let mut result = BaseElement::ZERO;
+    for j in 0..N {
+        let root = inv_root.exp((j as u64).into());
+        let coefficient = row
+            .iter()
+            .enumerate()
+            .fold(BaseElement::ZERO, |acc, (k, &value)| {
+                acc + value * root.exp((k as u64).into())
+            });
+        result += coefficient * step.exp((j as u64).into());
+    }
+    result / BaseElement::from(N as u64)
+}
+
+/// Walks through the calldata in the same way as the generated contract does, and checks that
+/// all Merkle authentication paths are valid.
+fn check_calldata_layout(words: &[[u8; 32]], params: &VerifierParams, positions: &[usize]) {
+    let depth = params.lde_domain_size.ilog2() as usize;
+    let fri_roots = &words[2..params.num_fri_layers + 3];
+    let mut offset =
+        params.num_fri_layers + 3 + 2 * params.trace_width + params.num_composition_columns + 1;
+
+    // trace and constraint queries
+    for (root, width) in [
+        (words[0], params.trace_width),
+        (words[1], params.num_composition_columns),
+    ] {
+        for &position in positions {
+            offset = check_query(words, offset, width, depth, position, &root);
+        }
+    }
+
+    // FRI remainder
+    let remainder = to_elements(&words[offset..offset + params.remainder_size]);
+    let commitment = Keccak256::hash_elements(&remainder);
+    assert_eq!(fri_roots[params.num_fri_layers], commitment.as_bytes());
+    offset += params.remainder_size;
+
+    // FRI layer queries
+    let mut positions = positions.to_vec();
+    let mut domain_size = params.lde_domain_size;
+    for root in fri_roots.iter().take(params.num_fri_layers) {
+        positions = fri::folding::fold_positions(&positions, domain_size, params.folding_factor);
+        domain_size /= params.folding_factor;
+        let depth = domain_size.ilog2() as usize;
+        for &position in positions.iter() {
+            offset = check_query(words, offset, params.folding_factor, depth, position, root);
+        }
+    }
+    assert_eq!(words.len(), offset);
+}
+
+/// Checks the Merkle authentication path of a query with `width` values starting at the
+/// specified offset; returns the offset of the next query.
+fn check_query(
+    words: &[[u8; 32]],
+    offset: usize,
+    width: usize,
+    depth: usize,
+    position: usize,
+    root: &[u8; 32],
+) -> usize {
+    let values = to_elements(&words[offset..offset + width]);
+    let mut node = Keccak256::hash_elements(&values).as_bytes();
+    let mut index = position;
+    for sibling in &words[offset + width..offset + width + depth] {
+        let (left, right) = if index & 1 == 0 {
+            (&node, sibling)
+        } else {
+            (sibling, &node)
+        };
+        node = Keccak256::hash(&[*left, *right].concat()).as_bytes();
+        index >>= 1;
+    }
+    assert_eq!(root, &node, "invalid Merkle path for position {position}");
+    offset + width + depth
+}
+
+/// Evaluates the constraint graph recorded for the first sample public inputs with the same
+/// substitution of public inputs as performed by the generated contract, and checks the result
+/// against out-of-domain constraint evaluations from the proof.
+fn check_constraint_evaluation<A>(
+    air: &A,
+    sample_inputs: [A::PublicInputs; 2],
+    public_inputs: &[BaseElement],
+    parsed: &ParsedProof,
+    transcript: &Transcript,
+) where
+    A: Air<BaseField = BaseElement>,
+{
+    let [inputs1, inputs2] = sample_inputs;
+    let samples = [inputs1.to_elements(), inputs2.to_elements()];
+    let air1 = A::new(air.trace_info().clone(), inputs1, air.options().clone());
+    let air2 = A::new(air.trace_info().clone(), inputs2, air.options().clone());
+    let (root, graph1) = symbolic::record(|| evaluate_constraints(&air1).index());
+    let (_, graph2) = symbolic::record(|| evaluate_constraints(&air2).index());
+    let bindings = bind_public_inputs(&graph1, &graph2, &samples).unwrap();
+
+    let graph = graph1
+        .iter()
+        .zip(bindings)
+        .map(|(&node, binding)| match binding {
+            Some(position) => Node::Constant(public_inputs[position].as_int()),
+            None => node,
+        })
+        .collect::<Vec<_>>();
+
+    let coefficients = &transcript.constraint_coefficients;
+    let mut inputs = vec![transcript.z];
+    inputs.extend_from_slice(&parsed.ood_current);
+    inputs.extend_from_slice(&parsed.ood_next);
+    for &(a, b) in coefficients.transition.iter().chain(&coefficients.boundary) {
+        inputs.push(a);
+        inputs.push(b);
+    }
+    let evaluation = symbolic::evaluate(&graph, &inputs)[root];
+
+    let expected = parsed
+        .ood_constraints
+        .iter()
+        .enumerate()
+        .fold(BaseElement::ZERO, |acc, (i, &value)| {
+            acc + transcript.z.exp((i as u64).into()) * value
+        });
+    assert_eq!(expected, evaluation);
+}
+
+fn to_elements(words: &[[u8; 32]]) -> Vec<BaseElement> {
+    words
+        .iter()
+        .map(|word| {
+            assert_eq!([0; 16], word[..16], "value is not a field element");
+            BaseElement::new(u128::from_be_bytes(word[16..].try_into().unwrap()))
+        })
+        .collect()
+}
+
+// EVM EXECUTION
+// ================================================================================================
+
+#[cfg(feature = "solc-tests")]
+mod evm {
+    use super::super::Calldata;
+    use revm::{
+        primitives::{ExecutionResult, Output, TxKind, U256},
+        Evm, InMemoryDB,
+    };
+    use std::{env, io::Write, process::Command, process::Stdio};
+
+    /// Gas limit of transactions executed by the tests.
+    const GAS_LIMIT: u64 = 1_000_000_000;
+
+    /// Compiles the contract in `source`, deploys it, and checks that the contract accepts
+    /// `calldata` and rejects altered versions of it.
+    pub fn check_verifier(source: &str, calldata: &Calldata) {
+        let mut evm = Evm::builder()
+            .with_db(InMemoryDB::default())
+            .modify_cfg_env(|cfg| cfg.limit_contract_code_size = Some(usize::MAX))
+            .build();
+
+        // deploy the contract
+        evm.tx_mut().gas_limit = GAS_LIMIT;
+        evm.tx_mut().transact_to = TxKind::Create;
+        evm.tx_mut().data = compile(source).into();
+        let address = match evm.transact_commit().unwrap() {
+            ExecutionResult::Success {
+                output: Output::Create(_, Some(address)),
+                ..
+            } => address,
+            result => panic!("failed to deploy the verifier: {result:?}"),
+        };
+        evm.tx_mut().transact_to = TxKind::Call(address);
+
+        // the contract must accept valid calldata
+        evm.tx_mut().data = calldata.encode().into();
+        match evm.transact().unwrap().result {
+            ExecutionResult::Success {
+                output: Output::Call(output),
+                ..
+            } => assert_eq!(U256::from(1).to_be_bytes::<32>(), output.as_ref()),
+            result => panic!("valid proof was rejected: {result:?}"),
+        }
+
+        // and reject calldata with altered public inputs or proof
+        let mut altered = calldata.clone();
+        altered.public_inputs[0][31] ^= 1;
+        evm.tx_mut().data = altered.encode().into();
+        assert!(!evm.transact().unwrap().result.is_success());
+
+        let mut altered = calldata.clone();
+        let last = altered.proof.len() - 1;
+        altered.proof[last][31] ^= 1;
+        evm.tx_mut().data = altered.encode().into();
+        assert!(!evm.transact().unwrap().result.is_success());
+    }
+
+    /// Compiles the contract in `source` with `solc` and returns its deployment bytecode.
+    ///
+    /// `solc` is looked up in the path unless `SOLC` environment variable specifies otherwise.
+    fn compile(source: &str) -> Vec<u8> {
+        let solc = env::var("SOLC").unwrap_or_else(|_| "solc".to_string());
+        let mut child = Command::new(&solc)
+            .args(["--bin", "--optimize", "-"])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .unwrap_or_else(|err| panic!("failed to run {solc}: {err}"));
+        child
+            .stdin
+            .take()
+            .unwrap()
+            .write_all(source.as_bytes())
+            .unwrap();
+        let output = child.wait_with_output().unwrap();
+        assert!(
+            output.status.success(),
+            "failed to compile the verifier: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+
+        // the bytecode follows the "Binary:" line of the output
+        let stdout = String::from_utf8(output.stdout).unwrap();
+        let bytecode = stdout
+            .lines()
+            .skip_while(|line| !line.starts_with("Binary"))
+            .nth(1)
+            .expect("solc output does not contain bytecode");
+        hex::decode(bytecode.trim()).unwrap()
+    }
+}
@@ -0,0 +1,635 @@
+// SPDX-License-Identifier: MIT
+// This file was generated by the Winterfell Solidity verifier generator; do not edit it manually.
+pragma solidity ^0.8.17;
+
+/// Verifier of STARK proofs for a single computation.
+///
+/// Proofs and public inputs are passed to the verifier as arrays of 256-bit words; use the
+/// `build_calldata()` function of the generator to convert a Winterfell proof into this format.
+/// Field elements are passed as integers, and hash digests are passed as their raw bytes.
+contract {{NAME}} {
+    // FIELD AND PROTOCOL PARAMETERS
+    // --------------------------------------------------------------------------------------------
+
+    uint256 constant P = {{MODULUS}};
+
+    uint256 constant TRACE_WIDTH = {{TRACE_WIDTH}};
+    uint256 constant NUM_COMPOSITION_COLUMNS = {{NUM_COMPOSITION_COLUMNS}};
+    uint256 constant NUM_PUBLIC_INPUTS = {{NUM_PUBLIC_INPUTS}};
+    uint256 constant NUM_CONSTRAINT_COEFFICIENTS = {{NUM_CONSTRAINT_COEFFICIENTS}};
+    uint256 constant NUM_DEEP_COEFFICIENTS = 2 * TRACE_WIDTH + NUM_COMPOSITION_COLUMNS + 2;
+    uint256 constant NUM_QUERIES = {{NUM_QUERIES}};
+
+    uint256 constant LDE_DOMAIN_SIZE = {{LDE_DOMAIN_SIZE}};
+    uint256 constant LDE_DOMAIN_DEPTH = {{LDE_DOMAIN_DEPTH}};
+    uint256 constant LDE_DOMAIN_GENERATOR = {{LDE_DOMAIN_GENERATOR}};
+    uint256 constant TRACE_DOMAIN_GENERATOR = {{TRACE_DOMAIN_GENERATOR}};
+    uint256 constant DOMAIN_OFFSET = {{DOMAIN_OFFSET}};
+    uint256 constant GRINDING_FACTOR = {{GRINDING_FACTOR}};
+
+    uint256 constant FOLDING_FACTOR = {{FOLDING_FACTOR}};
+    uint256 constant FOLDING_FACTOR_LOG2 = {{FOLDING_FACTOR_LOG2}};
+    uint256 constant INV_FOLDING_FACTOR = {{INV_FOLDING_FACTOR}};
+    uint256 constant INV_FOLDING_ROOT = {{INV_FOLDING_ROOT}};
+    uint256 constant NUM_FRI_LAYERS = {{NUM_FRI_LAYERS}};
+    uint256 constant REMAINDER_SIZE = {{REMAINDER_SIZE}};
+
+    /// Proof context serialized into bytes of field elements; the public coin is seeded with the
+    /// context followed by public inputs.
+    bytes constant CONTEXT = hex"{{CONTEXT}}";
+
+    // PROOF LAYOUT
+    // --------------------------------------------------------------------------------------------
+
+    uint256 constant TRACE_ROOT_OFFSET = 0;
+    uint256 constant CONSTRAINT_ROOT_OFFSET = 1;
+    uint256 constant FRI_ROOTS_OFFSET = 2;
+    uint256 constant OOD_CURRENT_OFFSET = FRI_ROOTS_OFFSET + NUM_FRI_LAYERS + 1;
+    uint256 constant OOD_NEXT_OFFSET = OOD_CURRENT_OFFSET + TRACE_WIDTH;
+    uint256 constant OOD_CONSTRAINTS_OFFSET = OOD_NEXT_OFFSET + TRACE_WIDTH;
+    uint256 constant POW_NONCE_OFFSET = OOD_CONSTRAINTS_OFFSET + NUM_COMPOSITION_COLUMNS;
+    uint256 constant TRACE_QUERIES_OFFSET = POW_NONCE_OFFSET + 1;
+    uint256 constant CONSTRAINT_QUERIES_OFFSET =
+        TRACE_QUERIES_OFFSET + NUM_QUERIES * (TRACE_WIDTH + LDE_DOMAIN_DEPTH);
+    uint256 constant REMAINDER_OFFSET =
+        CONSTRAINT_QUERIES_OFFSET + NUM_QUERIES * (NUM_COMPOSITION_COLUMNS + LDE_DOMAIN_DEPTH);
+    uint256 constant FRI_QUERIES_OFFSET = REMAINDER_OFFSET + REMAINDER_SIZE;
+
+    // TYPES
+    // --------------------------------------------------------------------------------------------
+
+    /// Public coin which mirrors the default random coin of Winterfell instantiated with Keccak256.
+    struct Coin {
+        bytes32 seed;
+        uint64 counter;
+    }
+
+    /// Values derived from the proof during verification.
+    struct State {
+        Coin coin;
+        uint256 z;
+        uint256[] constraintCoefficients;
+        uint256[] deepCoefficients;
+        uint256[] friAlphas;
+        uint256[] positions;
+        uint256[] evaluations;
+    }
+
+    // VERIFICATION PROCEDURE
+    // --------------------------------------------------------------------------------------------
+
+    /// Verifies the proof against the specified public inputs; reverts if the proof is invalid.
+    function verify(uint256[] calldata publicInputs, uint256[] calldata proof)
+        external
+        view
+        returns (bool)
+    {
+        require(publicInputs.length == NUM_PUBLIC_INPUTS, "invalid number of public inputs");
+        require(proof.length >= FRI_QUERIES_OFFSET, "proof is too short");
+
+        State memory s;
+        s.coin = Coin(keccak256(abi.encodePacked(CONTEXT, _toBytes(publicInputs))), 0);
+
+        // 1 ----- trace commitment ---------------------------------------------------------------
+        _reseed(s.coin, bytes32(proof[TRACE_ROOT_OFFSET]));
+        s.constraintCoefficients = new uint256[](NUM_CONSTRAINT_COEFFICIENTS);
+        for (uint256 i = 0; i < NUM_CONSTRAINT_COEFFICIENTS; i++) {
+            s.constraintCoefficients[i] = _drawNonZero(s.coin);
+        }
+
+        // 2 ----- constraint commitment ----------------------------------------------------------
+        _reseed(s.coin, bytes32(proof[CONSTRAINT_ROOT_OFFSET]));
+        s.z = _draw(s.coin);
+
+        // 3 ----- OOD consistency check ----------------------------------------------------------
+        _checkOodConstraints(s, publicInputs, proof);
+
+        // 4 ----- FRI commitments ----------------------------------------------------------------
+        _drawDeepCoefficients(s);
+        _drawFriAlphas(s, proof);
+
+        // 5 ----- trace and constraint queries ---------------------------------------------------
+        uint256 nonce = proof[POW_NONCE_OFFSET];
+        require(nonce <= type(uint64).max, "invalid proof-of-work nonce");
+        _reseedWithInt(s.coin, uint64(nonce));
+        require(_leadingZeros(s.coin) >= GRINDING_FACTOR, "proof-of-work verification failed");
+        s.positions = _drawQueryPositions(s.coin);
+
+        // 6 ----- DEEP composition ---------------------------------------------------------------
+        _composeTraceQueries(s, proof);
+        _composeConstraintQueries(s, proof);
+
+        // 7 ----- FRI verification ---------------------------------------------------------------
+        _verifyFri(s, proof);
+
+        return true;
+    }
+
+    // CONSTRAINT EVALUATION
+    // --------------------------------------------------------------------------------------------
+
+    /// Evaluates constraints of the computation at the out-of-domain point and merges them into
+    /// a single value.
+    ///
+    /// The inputs are: the out-of-domain point z, the current and the next rows of the
+    /// out-of-domain trace frame, and constraint composition coefficients.
+    function _evaluateConstraints(uint256[] memory inputs, uint256[] calldata publicInputs)
+        internal
+        view
+        returns (uint256)
+    {
+{{CONSTRAINT_EVALUATION}}
+    }
+
+    function _checkOodConstraints(
+        State memory s,
+        uint256[] calldata publicInputs,
+        uint256[] calldata proof
+    ) internal view {
+        // reseeding the coin also makes sure all out-of-domain values are valid field elements
+        _reseed(s.coin, _hashElements(proof[OOD_CURRENT_OFFSET:OOD_NEXT_OFFSET]));
+        _reseed(s.coin, _hashElements(proof[OOD_NEXT_OFFSET:OOD_CONSTRAINTS_OFFSET]));
+        _reseed(s.coin, _hashElements(proof[OOD_CONSTRAINTS_OFFSET:POW_NONCE_OFFSET]));
+
+        uint256[] memory inputs = new uint256[](1 + 2 * TRACE_WIDTH + NUM_CONSTRAINT_COEFFICIENTS);
+        inputs[0] = s.z;
+        for (uint256 i = 0; i < 2 * TRACE_WIDTH; i++) {
+            inputs[1 + i] = proof[OOD_CURRENT_OFFSET + i];
+        }
+        for (uint256 i = 0; i < NUM_CONSTRAINT_COEFFICIENTS; i++) {
+            inputs[1 + 2 * TRACE_WIDTH + i] = s.constraintCoefficients[i];
+        }
+        uint256 evaluation1 = _evaluateConstraints(inputs, publicInputs);
+
+        uint256 evaluation2 = 0;
+        uint256 power = 1;
+        for (uint256 i = 0; i < NUM_COMPOSITION_COLUMNS; i++) {
+            evaluation2 = addmod(evaluation2, mulmod(power, proof[OOD_CONSTRAINTS_OFFSET + i], P), P);
+            power = mulmod(power, s.z, P);
+        }
+
+        require(evaluation1 == evaluation2, "inconsistent out-of-domain constraint evaluations");
+    }
+
+    // DEEP COMPOSITION
+    // --------------------------------------------------------------------------------------------
+
+    function _drawDeepCoefficients(State memory s) internal pure {
+        s.deepCoefficients = new uint256[](NUM_DEEP_COEFFICIENTS);
+        for (uint256 i = 0; i < NUM_DEEP_COEFFICIENTS; i++) {
+            s.deepCoefficients[i] = _drawNonZero(s.coin);
+        }
+    }
+
+    /// Verifies queried trace states against the trace commitment, and computes the trace part
+    /// of the DEEP composition polynomial at the queried positions.
+    function _composeTraceQueries(State memory s, uint256[] calldata proof) internal view {
+        s.evaluations = new uint256[](NUM_QUERIES);
+        uint256 zg = mulmod(s.z, TRACE_DOMAIN_GENERATOR, P);
+        for (uint256 q = 0; q < NUM_QUERIES; q++) {
+            uint256 offset = TRACE_QUERIES_OFFSET + q * (TRACE_WIDTH + LDE_DOMAIN_DEPTH);
+            bytes32 leaf = _hashElements(proof[offset:offset + TRACE_WIDTH]);
+            require(
+                _verifyPath(
+                    proof,
+                    offset + TRACE_WIDTH,
+                    LDE_DOMAIN_DEPTH,
+                    leaf,
+                    s.positions[q],
+                    bytes32(proof[TRACE_ROOT_OFFSET])
+                ),
+                "invalid trace query"
+            );
+
+            (uint256 t1, uint256 t2) = _traceNumerators(s, proof, offset);
+            uint256 x = _domainPoint(LDE_DOMAIN_GENERATOR, s.positions[q]);
+            s.evaluations[q] = _traceComposition(t1, t2, x, s.z, zg);
+        }
+    }
+
+    /// Returns t1 / (x - z) + t2 / (x - z * g).
+    function _traceComposition(uint256 t1, uint256 t2, uint256 x, uint256 z, uint256 zg)
+        internal
+        view
+        returns (uint256)
+    {
+        uint256 d1 = addmod(x, P - z, P);
+        uint256 d2 = addmod(x, P - zg, P);
+        uint256 numerator = addmod(mulmod(t1, d2, P), mulmod(t2, d1, P), P);
+        return mulmod(numerator, _inv(mulmod(d1, d2, P)), P);
+    }
+
+    function _traceNumerators(State memory s, uint256[] calldata proof, uint256 offset)
+        internal
+        pure
+        returns (uint256 t1, uint256 t2)
+    {
+        for (uint256 i = 0; i < TRACE_WIDTH; i++) {
+            uint256 value = proof[offset + i];
+            uint256 v1 = addmod(value, P - proof[OOD_CURRENT_OFFSET + i], P);
+            uint256 v2 = addmod(value, P - proof[OOD_NEXT_OFFSET + i], P);
+            t1 = addmod(t1, mulmod(v1, s.deepCoefficients[2 * i], P), P);
+            t2 = addmod(t2, mulmod(v2, s.deepCoefficients[2 * i + 1], P), P);
+        }
+    }
+
+    /// Verifies queried constraint evaluations against the constraint commitment, adds the
+    /// constraint part of the DEEP composition polynomial to the evaluations at the queried
+    /// positions, and adjusts the degree of the result.
+    function _composeConstraintQueries(State memory s, uint256[] calldata proof) internal view {
+        uint256 zm = _exp(s.z, NUM_COMPOSITION_COLUMNS);
+        for (uint256 q = 0; q < NUM_QUERIES; q++) {
+            uint256 offset =
+                CONSTRAINT_QUERIES_OFFSET + q * (NUM_COMPOSITION_COLUMNS + LDE_DOMAIN_DEPTH);
+            bytes32 leaf = _hashElements(proof[offset:offset + NUM_COMPOSITION_COLUMNS]);
+            require(
+                _verifyPath(
+                    proof,
+                    offset + NUM_COMPOSITION_COLUMNS,
+                    LDE_DOMAIN_DEPTH,
+                    leaf,
+                    s.positions[q],
+                    bytes32(proof[CONSTRAINT_ROOT_OFFSET])
+                ),
+                "invalid constraint query"
+            );
+
+            uint256 x = _domainPoint(LDE_DOMAIN_GENERATOR, s.positions[q]);
+            uint256 c = mulmod(_constraintNumerator(s, proof, offset), _inv(addmod(x, P - zm, P)), P);
+            uint256 adjustment = addmod(
+                s.deepCoefficients[NUM_DEEP_COEFFICIENTS - 2],
+                mulmod(x, s.deepCoefficients[NUM_DEEP_COEFFICIENTS - 1], P),
+                P
+            );
+            s.evaluations[q] = mulmod(addmod(s.evaluations[q], c, P), adjustment, P);
+        }
+    }
+
+    function _constraintNumerator(State memory s, uint256[] calldata proof, uint256 offset)
+        internal
+        pure
+        returns (uint256 result)
+    {
+        for (uint256 i = 0; i < NUM_COMPOSITION_COLUMNS; i++) {
+            uint256 value = addmod(proof[offset + i], P - proof[OOD_CONSTRAINTS_OFFSET + i], P);
+            result = addmod(result, mulmod(value, s.deepCoefficients[2 * TRACE_WIDTH + i], P), P);
+        }
+    }
+
+    // FRI VERIFICATION
+    // --------------------------------------------------------------------------------------------
+
+    function _drawFriAlphas(State memory s, uint256[] calldata proof) internal pure {
+        uint64[NUM_FRI_LAYERS + 1] memory salts = [{{FRI_LAYER_SALTS}}];
+        s.friAlphas = new uint256[](NUM_FRI_LAYERS + 1);
+        for (uint256 depth = 0; depth <= NUM_FRI_LAYERS; depth++) {
+            _reseed(s.coin, bytes32(proof[FRI_ROOTS_OFFSET + depth]));
+            _reseedWithInt(s.coin, salts[depth]);
+            s.friAlphas[depth] = _draw(s.coin);
+        }
+    }
+
+    function _verifyFri(State memory s, uint256[] calldata proof) internal view {
+        uint256 offset = FRI_QUERIES_OFFSET;
+        uint256 domainGenerator = LDE_DOMAIN_GENERATOR;
+        for (uint256 depth = 0; depth < NUM_FRI_LAYERS; depth++) {
+            offset = _verifyFriLayer(s, proof, offset, depth, domainGenerator);
+            domainGenerator = _exp(domainGenerator, FOLDING_FACTOR);
+        }
+        require(offset == proof.length, "invalid proof length");
+        _verifyRemainder(s, proof, domainGenerator);
+    }
+
+    /// Verifies queries against the FRI layer at the specified depth, and folds the queried rows
+    /// into evaluations at the next layer; returns the offset of the next layer queries.
+    function _verifyFriLayer(
+        State memory s,
+        uint256[] calldata proof,
+        uint256 offset,
+        uint256 depth,
+        uint256 domainGenerator
+    ) internal view returns (uint256) {
+        uint256 rowLength = (LDE_DOMAIN_SIZE >> (depth * FOLDING_FACTOR_LOG2)) / FOLDING_FACTOR;
+        uint256[] memory folded = _foldPositions(s.positions, rowLength);
+        uint256[] memory rowOffsets;
+        (rowOffsets, offset) = _readFriRows(proof, offset, folded, depth);
+        _checkFriRows(s, proof, folded, rowOffsets, rowLength);
+
+        uint256[] memory evaluations = new uint256[](folded.length);
+        for (uint256 i = 0; i < folded.length; i++) {
+            uint256 x = _domainPoint(domainGenerator, folded[i]);
+            evaluations[i] = _foldRow(proof, rowOffsets[i], x, s.friAlphas[depth]);
+        }
+        s.positions = folded;
+        s.evaluations = evaluations;
+
+        return offset;
+    }
+
+    /// Verifies queried rows of a FRI layer against the layer commitment; returns offsets of the
+    /// rows in the proof and the offset of the next layer queries.
+    function _readFriRows(
+        uint256[] calldata proof,
+        uint256 offset,
+        uint256[] memory positions,
+        uint256 depth
+    ) internal pure returns (uint256[] memory rowOffsets, uint256) {
+        bytes32 root = bytes32(proof[FRI_ROOTS_OFFSET + depth]);
+        uint256 pathLength = LDE_DOMAIN_DEPTH - (depth + 1) * FOLDING_FACTOR_LOG2;
+        rowOffsets = new uint256[](positions.length);
+        for (uint256 i = 0; i < positions.length; i++) {
+            rowOffsets[i] = offset;
+            bytes32 leaf = _hashElements(proof[offset:offset + FOLDING_FACTOR]);
+            require(
+                _verifyPath(proof, offset + FOLDING_FACTOR, pathLength, leaf, positions[i], root),
+                "invalid FRI layer query"
+            );
+            offset += FOLDING_FACTOR + pathLength;
+        }
+        return (rowOffsets, offset);
+    }
+
+    /// Makes sure evaluations from the previous layer are consistent with the queried rows.
+    function _checkFriRows(
+        State memory s,
+        uint256[] calldata proof,
+        uint256[] memory folded,
+        uint256[] memory rowOffsets,
+        uint256 rowLength
+    ) internal pure {
+        for (uint256 i = 0; i < s.positions.length; i++) {
+            uint256 position = s.positions[i];
+            uint256 row = _indexOf(folded, position % rowLength);
+            require(
+                proof[rowOffsets[row] + position / rowLength] == s.evaluations[i],
+                "invalid FRI layer folding"
+            );
+        }
+    }
+
+    /// Interpolates a row of FRI layer values at x * ω^k (for k in 0..FOLDING_FACTOR) into a
+    /// polynomial, and evaluates the polynomial at alpha.
+    function _foldRow(uint256[] calldata proof, uint256 offset, uint256 x, uint256 alpha)
+        internal
+        view
+        returns (uint256 result)
+    {
+        uint256 step = mulmod(alpha, _inv(x), P);
+        uint256 power = 1;
+        uint256 rootPower = 1;
+        for (uint256 j = 0; j < FOLDING_FACTOR; j++) {
+            // compute the jth coefficient of the polynomial (multiplied by FOLDING_FACTOR)
+            uint256 coefficient = 0;
+            uint256 root = 1;
+            for (uint256 k = 0; k < FOLDING_FACTOR; k++) {
+                coefficient = addmod(coefficient, mulmod(proof[offset + k], root, P), P);
+                root = mulmod(root, rootPower, P);
+            }
+            result = addmod(result, mulmod(coefficient, power, P), P);
+            power = mulmod(power, step, P);
+            rootPower = mulmod(rootPower, INV_FOLDING_ROOT, P);
+        }
+        result = mulmod(result, INV_FOLDING_FACTOR, P);
+    }
+
+    function _verifyRemainder(State memory s, uint256[] calldata proof, uint256 domainGenerator)
+        internal
+        view
+    {
+        bytes32 commitment = _hashElements(proof[REMAINDER_OFFSET:FRI_QUERIES_OFFSET]);
+        require(
+            commitment == bytes32(proof[FRI_ROOTS_OFFSET + NUM_FRI_LAYERS]),
+            "invalid FRI remainder commitment"
+        );
+
+        for (uint256 i = 0; i < s.positions.length; i++) {
+            uint256 x = _domainPoint(domainGenerator, s.positions[i]);
+            uint256 value = 0;
+            for (uint256 k = REMAINDER_SIZE; k > 0; k--) {
+                value = addmod(mulmod(value, x, P), proof[REMAINDER_OFFSET + k - 1], P);
+            }
+            require(value == s.evaluations[i], "invalid FRI remainder folding");
+        }
+    }
+
+    /// Maps positions to positions in a domain of the specified size, discarding duplicates.
+    function _foldPositions(uint256[] memory positions, uint256 domainSize)
+        internal
+        pure
+        returns (uint256[] memory result)
+    {
+        uint256[] memory buffer = new uint256[](positions.length);
+        uint256 count = 0;
+        for (uint256 i = 0; i < positions.length; i++) {
+            uint256 position = positions[i] % domainSize;
+            bool isDuplicate = false;
+            for (uint256 j = 0; j < count; j++) {
+                if (buffer[j] == position) {
+                    isDuplicate = true;
+                    break;
+                }
+            }
+            if (!isDuplicate) {
+                buffer[count] = position;
+                count++;
+            }
+        }
+
+        result = new uint256[](count);
+        for (uint256 i = 0; i < count; i++) {
+            result[i] = buffer[i];
+        }
+    }
+
+    function _indexOf(uint256[] memory values, uint256 value) internal pure returns (uint256) {
+        for (uint256 i = 0; i < values.length; i++) {
+            if (values[i] == value) {
+                return i;
+            }
+        }
+        revert("value not found");
+    }
+
+    // PUBLIC COIN
+    // --------------------------------------------------------------------------------------------
+
+    function _reseed(Coin memory coin, bytes32 data) internal pure {
+        coin.seed = keccak256(abi.encodePacked(coin.seed, data));
+        coin.counter = 0;
+    }
+
+    function _reseedWithInt(Coin memory coin, uint64 value) internal pure {
+        coin.seed = keccak256(abi.encodePacked(coin.seed, _reverse64(value)));
+        coin.counter = 0;
+    }
+
+    function _next(Coin memory coin) internal pure returns (bytes32) {
+        coin.counter += 1;
+        return keccak256(abi.encodePacked(coin.seed, _reverse64(coin.counter)));
+    }
+
+    /// Draws a field element from the coin by interpreting the first 16 bytes of the next
+    /// pseudo-random value as a little-endian integer; values which are not valid field elements
+    /// are discarded.
+    function _draw(Coin memory coin) internal pure returns (uint256) {
+        for (uint256 i = 0; i < 1000; i++) {
+            uint256 value = _reverse128(uint128(bytes16(_next(coin))));
+            if (value < P) {
+                return value;
+            }
+        }
+        revert("failed to draw a field element");
+    }
+
+    /// Draws a non-zero field element; zero values are re-drawn in the same way as for linear
+    /// combination coefficients drawn by the Rust verifier.
+    function _drawNonZero(Coin memory coin) internal pure returns (uint256) {
+        for (uint256 i = 0; i < 100; i++) {
+            uint256 value = _draw(coin);
+            if (value != 0) {
+                return value;
+            }
+        }
+        revert("failed to draw a non-zero field element");
+    }
+
+    /// Draws distinct query positions from the LDE domain using a partial Fisher-Yates shuffle;
+    /// swapped positions are tracked in a pair of arrays.
+    function _drawQueryPositions(Coin memory coin)
+        internal
+        pure
+        returns (uint256[] memory positions)
+    {
+        positions = new uint256[](NUM_QUERIES);
+        uint256[] memory swapKeys = new uint256[](NUM_QUERIES);
+        uint256[] memory swapValues = new uint256[](NUM_QUERIES);
+        uint256 numSwaps = 0;
+        for (uint256 i = 0; i < NUM_QUERIES; i++) {
+            uint256 value = _reverse64(uint64(bytes8(_next(coin))));
+            uint256 j = i + value % (LDE_DOMAIN_SIZE - i);
+
+            uint256 position = j;
+            uint256 replacement = i;
+            uint256 slot = numSwaps;
+            for (uint256 k = 0; k < numSwaps; k++) {
+                if (swapKeys[k] == j) {
+                    position = swapValues[k];
+                    slot = k;
+                }
+                if (swapKeys[k] == i) {
+                    replacement = swapValues[k];
+                }
+            }
+            swapKeys[slot] = j;
+            swapValues[slot] = replacement;
+            if (slot == numSwaps) {
+                numSwaps++;
+            }
+            positions[i] = position;
+        }
+    }
+
+    function _leadingZeros(Coin memory coin) internal pure returns (uint256 result) {
+        uint64 head = _reverse64(uint64(bytes8(coin.seed)));
+        if (head == 0) {
+            return 64;
+        }
+        while ((head & 1) == 0) {
+            head >>= 1;
+            result++;
+        }
+    }
+
+    // HASHING
+    // --------------------------------------------------------------------------------------------
+
+    /// Serializes field elements into bytes in the same way as Winterfell does (16 bytes per
+    /// element in little-endian order); reverts if any of the values is not a valid field element.
+    function _toBytes(uint256[] calldata elements) internal pure returns (bytes memory result) {
+        result = new bytes(elements.length * 16);
+        for (uint256 i = 0; i < elements.length; i++) {
+            uint256 value = elements[i];
+            require(value < P, "invalid field element");
+            bytes16 encoded = bytes16(_reverse128(uint128(value)));
+            assembly {
+                mstore(add(add(result, 32), mul(i, 16)), encoded)
+            }
+        }
+    }
+
+    function _hashElements(uint256[] calldata elements) internal pure returns (bytes32) {
+        return keccak256(_toBytes(elements));
+    }
+
+    /// Verifies a Merkle authentication path for the leaf at the specified index; the path
+    /// consists of `depth` sibling nodes located in the proof at the specified offset.
+    function _verifyPath(
+        uint256[] calldata proof,
+        uint256 offset,
+        uint256 depth,
+        bytes32 leaf,
+        uint256 index,
+        bytes32 root
+    ) internal pure returns (bool) {
+        bytes32 node = leaf;
+        for (uint256 i = 0; i < depth; i++) {
+            bytes32 sibling = bytes32(proof[offset + i]);
+            if ((index & 1) == 0) {
+                node = keccak256(abi.encodePacked(node, sibling));
+            } else {
+                node = keccak256(abi.encodePacked(sibling, node));
+            }
+            index >>= 1;
+        }
+        return node == root;
+    }
+
+    // FIELD ARITHMETIC
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns offset * generator^position.
+    function _domainPoint(uint256 generator, uint256 position) internal view returns (uint256) {
+        return mulmod(_exp(generator, position), DOMAIN_OFFSET, P);
+    }
+
+    function _exp(uint256 base, uint256 exponent) internal view returns (uint256 result) {
+        uint256 modulus = P;
+        assembly {
+            let ptr := mload(0x40)
+            mstore(ptr, 0x20)
+            mstore(add(ptr, 0x20), 0x20)
+            mstore(add(ptr, 0x40), 0x20)
+            mstore(add(ptr, 0x60), base)
+            mstore(add(ptr, 0x80), exponent)
+            mstore(add(ptr, 0xa0), modulus)
+            if iszero(staticcall(gas(), 0x05, ptr, 0xc0, ptr, 0x20)) {
+                revert(0, 0)
+            }
+            result := mload(ptr)
+        }
+    }
+
+    /// Returns the multiplicative inverse of the value; the inverse of zero is zero.
+    function _inv(uint256 value) internal view returns (uint256) {
+        return _exp(value, P - 2);
+    }
+
+    // BYTE ORDER
+    // --------------------------------------------------------------------------------------------
+
+    function _reverse128(uint128 value) internal pure returns (uint128) {
+        value = ((value >> 8) & 0x00FF00FF00FF00FF00FF00FF00FF00FF)
+            | ((value & 0x00FF00FF00FF00FF00FF00FF00FF00FF) << 8);
+        value = ((value >> 16) & 0x0000FFFF0000FFFF0000FFFF0000FFFF)
+            | ((value & 0x0000FFFF0000FFFF0000FFFF0000FFFF) << 16);
+        value = ((value >> 32) & 0x00000000FFFFFFFF00000000FFFFFFFF)
+            | ((value & 0x00000000FFFFFFFF00000000FFFFFFFF) << 32);
+        return (value >> 64) | (value << 64);
+    }
+
+    function _reverse64(uint64 value) internal pure returns (uint64) {
+        value = ((value >> 8) & 0x00FF00FF00FF00FF) | ((value & 0x00FF00FF00FF00FF) << 8);
+        value = ((value >> 16) & 0x0000FFFF0000FFFF) | ((value & 0x0000FFFF0000FFFF) << 16);
+        return (value >> 32) | (value << 32);
+    }
+}
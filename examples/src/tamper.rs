@@ -0,0 +1,165 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+//! Functions for tampering with valid proofs.
+//!
+//! Every function in this module takes a valid proof and returns a copy of the proof with a
+//! single part of it modified. A sound verifier must reject all such proofs; running them
+//! through the verifier guards against verifier checks being accidentally dropped.
+
+use winterfell::{ByteReader, ByteWriter, Deserializable, Serializable, SliceReader, StarkProof};
+
+// TAMPERING FUNCTIONS
+// ================================================================================================
+
+/// Returns a copy of the `proof` with a single bit flipped in the first queried value of the
+/// main trace segment.
+pub fn flip_trace_query_bit(proof: &StarkProof) -> StarkProof {
+    let mut result = proof.clone();
+    result.trace_queries[0] = flip_query_value_bit(&proof.trace_queries[0]);
+    result
+}
+
+/// Returns a copy of the `proof` with a single bit flipped in the first queried value of the
+/// constraint composition polynomial evaluations.
+pub fn flip_constraint_query_bit(proof: &StarkProof) -> StarkProof {
+    let mut result = proof.clone();
+    result.constraint_queries = flip_query_value_bit(&proof.constraint_queries);
+    result
+}
+
+/// Returns a copy of the `proof` with a single bit flipped in the first out-of-domain trace
+/// evaluation.
+pub fn flip_ood_frame_bit(proof: &StarkProof) -> StarkProof {
+    let mut result = proof.clone();
+    // the serialized OOD frame starts with the length of trace state bytes
+    let mut bytes = proof.ood_frame.to_bytes();
+    flip_first_value_bit(&mut bytes);
+    result.ood_frame = parse(&bytes);
+    result
+}
+
+/// Returns a copy of the `proof` with the last layer removed from the FRI proof.
+///
+/// # Panics
+/// Panics if the FRI proof contains no layers.
+pub fn truncate_fri_layer(proof: &StarkProof) -> StarkProof {
+    let bytes = proof.fri_proof.to_bytes();
+    let mut source = SliceReader::new(&bytes);
+    let num_layers = source.read_usize().unwrap();
+    assert!(num_layers > 0, "FRI proof contains no layers");
+
+    // copy all layers except for the last one; each layer consists of value and path bytes,
+    // both prefixed with their length
+    let mut truncated = Vec::new();
+    truncated.write_usize(num_layers - 1);
+    for i in 0..num_layers {
+        let mut layer = Vec::new();
+        for _ in 0..2 {
+            let num_bytes = source.read_usize().unwrap();
+            layer.write_usize(num_bytes);
+            layer.extend_from_slice(&source.read_vec(num_bytes).unwrap());
+        }
+        if i < num_layers - 1 {
+            truncated.extend_from_slice(&layer);
+        }
+    }
+
+    // copy the remainder, the number of partitions, and the layer multiproof as is
+    while source.has_more_bytes() {
+        truncated.push(source.read_u8().unwrap());
+    }
+
+    let mut result = proof.clone();
+    result.fri_proof = parse(&truncated);
+    result
+}
+
+/// Returns a copy of the `proof` with the FRI remainder polynomial extended to twice its length
+/// with zero coefficients.
+pub fn extend_fri_remainder(proof: &StarkProof) -> StarkProof {
+    let bytes = proof.fri_proof.to_bytes();
+    let mut source = SliceReader::new(&bytes);
+
+    // copy all layers as is
+    let num_layers = source.read_usize().unwrap();
+    let mut extended = Vec::new();
+    extended.write_usize(num_layers);
+    for _ in 0..num_layers {
+        for _ in 0..2 {
+            let num_bytes = source.read_usize().unwrap();
+            extended.write_usize(num_bytes);
+            extended.extend_from_slice(&source.read_vec(num_bytes).unwrap());
+        }
+    }
+
+    // double the number of remainder bytes by appending zeros; the number of remainder
+    // coefficients must remain a power of two
+    let num_remainder_bytes = source.read_usize().unwrap();
+    extended.write_usize(2 * num_remainder_bytes);
+    extended.extend_from_slice(&source.read_vec(num_remainder_bytes).unwrap());
+    extended.resize(extended.len() + num_remainder_bytes, 0);
+
+    // copy the number of partitions and the layer multiproof as is
+    while source.has_more_bytes() {
+        extended.push(source.read_u8().unwrap());
+    }
+
+    let mut result = proof.clone();
+    result.fri_proof = parse(&extended);
+    result
+}
+
+/// Returns a copy of the `proof` with a different proof-of-work nonce.
+pub fn change_pow_nonce(proof: &StarkProof) -> StarkProof {
+    let mut result = proof.clone();
+    result.pow_nonce = proof.pow_nonce.wrapping_add(1);
+    result
+}
+
+/// Returns a list of tampered copies of the `proof` produced by every function in this module
+/// together with a short description of the tampering.
+///
+/// FRI layer truncation is skipped if the FRI proof of the `proof` contains no layers.
+pub fn tampered_proofs(proof: &StarkProof) -> Vec<(&'static str, StarkProof)> {
+    let mut result = vec![
+        ("flipped trace query bit", flip_trace_query_bit(proof)),
+        (
+            "flipped constraint query bit",
+            flip_constraint_query_bit(proof),
+        ),
+        ("flipped OOD frame bit", flip_ood_frame_bit(proof)),
+        ("extended FRI remainder", extend_fri_remainder(proof)),
+        ("changed proof-of-work nonce", change_pow_nonce(proof)),
+    ];
+    if proof.fri_proof.num_layers() > 0 {
+        result.push(("truncated FRI layer", truncate_fri_layer(proof)));
+    }
+    result
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Flips the lowest bit of the first value byte of the specified queries; serialized queries
+/// start with the length of value bytes.
+fn flip_query_value_bit<Q: Serializable + Deserializable>(queries: &Q) -> Q {
+    let mut bytes = queries.to_bytes();
+    flip_first_value_bit(&mut bytes);
+    parse(&bytes)
+}
+
+/// Flips the lowest bit of the first byte following the length prefix at the start of `bytes`.
+fn flip_first_value_bit(bytes: &mut [u8]) {
+    let num_value_bytes = SliceReader::new(bytes).read_usize().unwrap();
+    let mut prefix = Vec::new();
+    prefix.write_usize(num_value_bytes);
+    bytes[prefix.len()] ^= 1;
+}
+
+/// Parses an instance of `T` from the provided bytes; the bytes are expected to be valid.
+fn parse<T: Deserializable>(bytes: &[u8]) -> T {
+    T::read_from_bytes(bytes).expect("failed to parse tampered proof part")
+}
@@ -3,7 +3,14 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use crate::Example;
+use crate::{tamper, Example};
+use core::fmt::Write;
+use std::{env, fs, path::Path};
+use winterfell::{
+    crypto::{hashers::Blake3_256, DefaultRandomCoin, RandomCoin},
+    math::FieldElement,
+    Air, AuxTraceRandElements, EvaluationFrame,
+};
 
 pub fn test_basic_proof_verification(e: Box<dyn Example>) {
     let proof = e.prove();
@@ -15,3 +22,143 @@ pub fn test_basic_proof_verification_fail(e: Box<dyn Example>) {
     let verified = e.verify_with_wrong_inputs(proof);
     assert!(verified.is_err());
 }
+
+pub fn test_tampered_proofs_rejected(e: Box<dyn Example>) {
+    let proof = e.prove();
+    for (description, tampered_proof) in tamper::tampered_proofs(&proof) {
+        assert!(
+            e.verify(tampered_proof).is_err(),
+            "tampered proof ({description}) was accepted"
+        );
+    }
+}
+
+// CONSTRAINT EVALUATION SNAPSHOTS
+// ================================================================================================
+
+/// Number of steps at which transition constraints are evaluated for a snapshot.
+const NUM_SNAPSHOT_STEPS: usize = 8;
+
+/// Distance between steps at which transition constraints are evaluated for a snapshot; this is
+/// an odd number so that the steps cover all positions of short periodic cycles.
+const SNAPSHOT_STEP_STRIDE: usize = 37;
+
+/// Compares evaluations of constraints defined by the specified `air` against the snapshot with
+/// the specified `name` stored in the `snapshots` directory of this crate.
+///
+/// Transition constraints are evaluated over pseudo-random evaluation frames at a few steps of the
+/// computation (and thus, against different values of periodic columns), and assertions are
+/// recorded as is. If `UPDATE_SNAPSHOTS` environment variable is set, the snapshot is overwritten
+/// with the current evaluations instead.
+pub fn test_constraint_evaluations_snapshot<A: Air>(air: &A, name: &str) {
+    let snapshot = build_constraint_snapshot(air);
+    let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("snapshots")
+        .join(format!("{name}.txt"));
+
+    if env::var_os("UPDATE_SNAPSHOTS").is_some() {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, snapshot).unwrap();
+        return;
+    }
+
+    let expected = fs::read_to_string(&path).unwrap_or_else(|err| {
+        panic!(
+            "failed to read snapshot {}: {err}; run tests with UPDATE_SNAPSHOTS=1 to create it",
+            path.display()
+        )
+    });
+    assert_eq!(
+        expected,
+        snapshot,
+        "constraint evaluations do not match the snapshot in {}; if the change is intended, run \
+        tests with UPDATE_SNAPSHOTS=1 to update it",
+        path.display()
+    );
+}
+
+/// Returns a textual representation of transition constraint evaluations and assertions of the
+/// specified `air`. Random values are drawn from a public coin seeded with an empty seed; thus,
+/// the result is deterministic.
+fn build_constraint_snapshot<A: Air>(air: &A) -> String {
+    let mut coin = DefaultRandomCoin::<Blake3_256<A::BaseField>>::new(&[]);
+    let mut draw_elements = |num_elements: usize| {
+        (0..num_elements)
+            .map(|_| coin.draw().expect("failed to draw a random value"))
+            .collect::<Vec<A::BaseField>>()
+    };
+
+    let layout = air.trace_layout();
+    let main_width = layout.main_trace_width();
+    let aux_width = layout.aux_trace_width();
+    let mut aux_rand_elements = AuxTraceRandElements::new();
+    for i in 0..layout.num_aux_segments() {
+        aux_rand_elements
+            .add_segment_elements(draw_elements(layout.get_aux_segment_rand_elements(i)));
+    }
+
+    let mut result = String::new();
+    let periodic_columns = air.get_periodic_column_values();
+    for i in 0..NUM_SNAPSHOT_STEPS {
+        let step = (i * SNAPSHOT_STEP_STRIDE) % air.trace_length();
+        let periodic_values = periodic_columns
+            .iter()
+            .map(|column| column[step % column.len()])
+            .collect::<Vec<_>>();
+
+        let main_frame =
+            EvaluationFrame::from_rows(draw_elements(main_width), draw_elements(main_width));
+        let mut evaluations =
+            vec![A::BaseField::ZERO; air.context().num_main_transition_constraints()];
+        air.evaluate_transition(&main_frame, &periodic_values, &mut evaluations);
+        writeln!(
+            result,
+            "step {step} main: {}",
+            format_elements(&evaluations)
+        )
+        .unwrap();
+
+        if aux_width > 0 {
+            let aux_frame =
+                EvaluationFrame::from_rows(draw_elements(aux_width), draw_elements(aux_width));
+            let mut evaluations =
+                vec![A::BaseField::ZERO; air.context().num_aux_transition_constraints()];
+            air.evaluate_aux_transition(
+                &main_frame,
+                &aux_frame,
+                &periodic_values,
+                &aux_rand_elements,
+                &mut evaluations,
+            );
+            writeln!(result, "step {step} aux: {}", format_elements(&evaluations)).unwrap();
+        }
+    }
+
+    let assertions = air.get_assertions();
+    let aux_assertions = air.get_aux_assertions(&aux_rand_elements);
+    for (segment, assertion) in assertions
+        .iter()
+        .map(|a| ("main", a))
+        .chain(aux_assertions.iter().map(|a| ("aux", a)))
+    {
+        writeln!(
+            result,
+            "assertion {segment}: column={}, first_step={}, stride={}, values={}",
+            assertion.column(),
+            assertion.first_step(),
+            assertion.stride(),
+            format_elements(assertion.values())
+        )
+        .unwrap();
+    }
+
+    result
+}
+
+fn format_elements<E: FieldElement>(elements: &[E]) -> String {
+    elements
+        .iter()
+        .map(|e| e.to_string())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
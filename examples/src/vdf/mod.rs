@@ -4,4 +4,5 @@
 // LICENSE file in the root directory of this source tree.
 
 pub mod exempt;
+pub mod padded;
 pub mod regular;
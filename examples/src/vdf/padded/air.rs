@@ -0,0 +1,83 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{BaseElement, FieldElement, ProofOptions, ALPHA, FORTY_TWO};
+use winterfell::{
+    math::ToElements, Air, AirContext, Assertion, EvaluationFrame, TraceInfo,
+    TransitionConstraintDegree,
+};
+
+// PUBLIC INPUTS
+// ================================================================================================
+
+#[derive(Clone)]
+pub struct VdfInputs {
+    pub seed: BaseElement,
+    pub result: BaseElement,
+    pub num_steps: usize,
+}
+
+impl ToElements<BaseElement> for VdfInputs {
+    fn to_elements(&self) -> Vec<BaseElement> {
+        vec![
+            self.seed,
+            self.result,
+            BaseElement::new(self.num_steps as u128),
+        ]
+    }
+}
+
+// VDF AIR
+// ================================================================================================
+
+pub struct VdfAir {
+    context: AirContext<BaseElement>,
+    seed: BaseElement,
+    result: BaseElement,
+    num_steps: usize,
+}
+
+impl Air for VdfAir {
+    type BaseField = BaseElement;
+    type PublicInputs = VdfInputs;
+
+    fn new(trace_info: TraceInfo, pub_inputs: VdfInputs, options: ProofOptions) -> Self {
+        let degrees = vec![TransitionConstraintDegree::new(3)];
+        // only the first num_steps rows of the trace are real; the rest of the trace is padded
+        // with zeros, and thus, transition constraints must be disabled for these rows
+        let context = AirContext::new(trace_info, degrees, 2, options)
+            .set_real_trace_length(pub_inputs.num_steps);
+        Self {
+            context,
+            seed: pub_inputs.seed,
+            result: pub_inputs.result,
+            num_steps: pub_inputs.num_steps,
+        }
+    }
+
+    fn evaluate_transition<E: FieldElement<BaseField = Self::BaseField>>(
+        &self,
+        frame: &EvaluationFrame<E>,
+        _periodic_values: &[E],
+        result: &mut [E],
+    ) {
+        let current_state = frame.current()[0];
+        let next_state = frame.next()[0];
+
+        result[0] = current_state - (next_state.exp(ALPHA.into()) + FORTY_TWO.into());
+    }
+
+    fn get_assertions(&self) -> Vec<Assertion<Self::BaseField>> {
+        // the result is asserted against the last real row of the trace
+        vec![
+            Assertion::single(0, 0, self.seed),
+            Assertion::single(0, self.num_steps - 1, self.result),
+        ]
+    }
+
+    fn context(&self) -> &AirContext<Self::BaseField> {
+        &self.context
+    }
+}
@@ -0,0 +1,65 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{
+    BaseElement, DefaultRandomCoin, ElementHasher, FieldElement, PhantomData, ProofOptions, Prover,
+    Trace, TraceTable, VdfAir, VdfInputs, FORTY_TWO, INV_ALPHA,
+};
+
+// VDF PROVER
+// ================================================================================================
+
+pub struct VdfProver<H: ElementHasher> {
+    options: ProofOptions,
+    _hasher: PhantomData<H>,
+}
+
+impl<H: ElementHasher> VdfProver<H> {
+    pub fn new(options: ProofOptions) -> Self {
+        Self {
+            options,
+            _hasher: PhantomData,
+        }
+    }
+
+    pub fn build_trace(seed: BaseElement, n: usize) -> TraceTable<BaseElement> {
+        let mut trace = Vec::with_capacity(n);
+        let mut state = seed;
+
+        trace.push(state);
+        for _ in 0..(n - 1) {
+            state = (state - FORTY_TWO).exp(INV_ALPHA);
+            trace.push(state);
+        }
+
+        // pad the trace to the next power of two with virtual rows of zeros
+        TraceTable::init_padded_with(vec![trace], |_, state| state[0] = BaseElement::ZERO)
+    }
+}
+
+impl<H: ElementHasher> Prover for VdfProver<H>
+where
+    H: ElementHasher<BaseField = BaseElement>,
+{
+    type BaseField = BaseElement;
+    type Air = VdfAir;
+    type Trace = TraceTable<BaseElement>;
+    type HashFn = H;
+    type RandomCoin = DefaultRandomCoin<Self::HashFn>;
+
+    fn get_pub_inputs(&self, trace: &Self::Trace) -> VdfInputs {
+        // the result is read from the last real row of the trace
+        let num_steps = trace.original_length();
+        VdfInputs {
+            seed: trace.get(0, 0),
+            result: trace.get(0, num_steps - 1),
+            num_steps,
+        }
+    }
+
+    fn options(&self) -> &ProofOptions {
+        &self.options
+    }
+}
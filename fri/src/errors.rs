@@ -6,6 +6,7 @@
 use core::fmt;
 
 use crypto::RandomCoinError;
+use utils::{ErrorCategory, ErrorCode, ToErrorCode};
 
 // VERIFIER ERROR
 // ================================================================================================
@@ -34,6 +35,12 @@ pub enum VerifierError {
     RemainderDegreeMismatch(usize),
     /// Polynomial degree at one of the FRI layers could not be divided evenly by the folding factor.
     DegreeTruncation(usize, usize, usize),
+    /// Merkle authentication paths for FRI layer queries were not aggregated across layers as
+    /// specified by the protocol options.
+    LayerProofAggregationMismatch,
+    /// Number of FRI layer commitments read from the channel is inconsistent with the number of
+    /// layers implied by the protocol options.
+    LayerCountMismatch(usize, usize),
 }
 
 impl fmt::Display for VerifierError {
@@ -70,6 +77,32 @@ impl fmt::Display for VerifierError {
             Self::DegreeTruncation(degree, folding, layer) => {
                 write!(f, "degree reduction from {degree} by {folding} at layer {layer} results in degree truncation")
             }
+            Self::LayerProofAggregationMismatch => {
+                write!(f, "aggregation of FRI layer proofs is inconsistent with protocol options")
+            }
+            Self::LayerCountMismatch(expected, actual) => {
+                write!(f, "expected {expected} FRI layer commitments, but was {actual}")
+            }
         }
     }
 }
+
+impl ToErrorCode for VerifierError {
+    fn error_code(&self) -> ErrorCode {
+        let index = match self {
+            Self::RandomCoinError(_) => 1,
+            Self::UnsupportedFoldingFactor(_) => 2,
+            Self::NumPositionEvaluationMismatch(..) => 3,
+            Self::LayerCommitmentMismatch => 4,
+            Self::InvalidLayerFolding(_) => 5,
+            Self::RemainderCommitmentMismatch => 6,
+            Self::InvalidRemainderFolding => 7,
+            Self::RemainderDegreeNotValid => 8,
+            Self::RemainderDegreeMismatch(_) => 9,
+            Self::DegreeTruncation(..) => 10,
+            Self::LayerProofAggregationMismatch => 11,
+            Self::LayerCountMismatch(..) => 12,
+        };
+        ErrorCode::new(ErrorCategory::Fri, index)
+    }
+}
@@ -17,6 +17,9 @@ use math::{
 };
 use utils::{collections::Vec, iter_mut, uninit_vector};
 
+#[cfg(test)]
+mod tests;
+
 // DEGREE-RESPECTING PROJECTION
 // ================================================================================================
 /// Applies degree-respecting projection to evaluations of a polynomial.
@@ -87,31 +90,86 @@ where
     B: StarkField,
     E: FieldElement<BaseField = B>,
 {
-    // build offset inverses and twiddles used during polynomial interpolation
     let inv_offsets = get_inv_offsets(values.len(), domain_offset, N);
-    let inv_twiddles = get_inv_twiddles::<B>(N);
-    let len_offset = E::inv((N as u64).into());
-
-    let mut result = unsafe { uninit_vector(values.len()) };
-    iter_mut!(result)
-        .zip(values)
-        .zip(inv_offsets)
-        .for_each(|((result, values), domain_offset)| {
-            // interpolate the values into a polynomial; this is similar to interpolation with
-            // offset implemented in math::fft module
-            let mut poly = *values;
-            serial_fft(&mut poly, &inv_twiddles);
-
-            let mut offset = len_offset;
-            let domain_offset = E::from(domain_offset);
-            for coeff in poly.iter_mut() {
-                *coeff *= offset;
-                offset *= domain_offset;
-            }
-
-            // evaluate the polynomial at alpha, and save the result
-            *result = polynom::eval(&poly, alpha)
-        });
+    fold_rows(values, &inv_offsets, alpha)
+}
+
+/// Folds each row of `N` evaluations into a single evaluation of the folded polynomial.
+///
+/// Row *i* must contain evaluations of a polynomial *f* at x<sub>i</sub> * ω<sup>j</sup> for
+/// *j* in 0..`N`, where ω is the `N`th root of unity, and `inv_xs[i]` must contain the inverse
+/// of x<sub>i</sub>. For every row, the values are interpolated into a polynomial of degree
+/// smaller than `N` which is then evaluated at `alpha`; the result is equal to the evaluation of
+/// the folded polynomial *f'* at x<sub>i</sub><sup>N</sup>.
+///
+/// Folding by 2 and 4 is performed via unrolled butterflies; for other values of `N` the rows
+/// are interpolated via FFT.
+///
+/// # Panics
+/// Panics if `rows` and `inv_xs` have different lengths.
+pub fn fold_rows<B, E, const N: usize>(rows: &[[E; N]], inv_xs: &[B], alpha: E) -> Vec<E>
+where
+    B: StarkField,
+    E: FieldElement<BaseField = B>,
+{
+    assert_eq!(
+        rows.len(),
+        inv_xs.len(),
+        "number of rows must be equal to the number of x coordinates"
+    );
+
+    // all folding procedures compute N times the folded value, and thus, the results need to be
+    // divided by N
+    let inv_n = E::inv((N as u64).into());
+
+    let mut result = unsafe { uninit_vector(rows.len()) };
+    match N {
+        2 => {
+            iter_mut!(result)
+                .zip(rows)
+                .zip(inv_xs)
+                .for_each(|((result, row), &x_inv)| {
+                    *result = fold2(row[0], row[1], E::from(x_inv), alpha) * inv_n;
+                });
+        }
+        4 => {
+            // inverse of the 4th root of unity; the second and the fourth values in a row are
+            // at x * ω and x * ω^3 = -(x * ω)
+            let inv_root = E::from(B::get_root_of_unity(2).inv());
+            let alpha2 = alpha.square();
+            iter_mut!(result)
+                .zip(rows)
+                .zip(inv_xs)
+                .for_each(|((result, row), &x_inv)| {
+                    let x_inv = E::from(x_inv);
+                    let p = fold2(row[0], row[2], x_inv, alpha);
+                    let q = fold2(row[1], row[3], x_inv * inv_root, alpha);
+                    *result = fold2(p, q, x_inv.square(), alpha2) * inv_n;
+                });
+        }
+        _ => {
+            let inv_twiddles = get_inv_twiddles::<B>(N);
+            iter_mut!(result)
+                .zip(rows)
+                .zip(inv_xs)
+                .for_each(|((result, row), &x_inv)| {
+                    // interpolate the values into a polynomial; this is similar to interpolation
+                    // with offset implemented in math::fft module
+                    let mut poly = *row;
+                    serial_fft(&mut poly, &inv_twiddles);
+
+                    let mut offset = inv_n;
+                    let x_inv = E::from(x_inv);
+                    for coeff in poly.iter_mut() {
+                        *coeff *= offset;
+                        offset *= x_inv;
+                    }
+
+                    // evaluate the polynomial at alpha, and save the result
+                    *result = polynom::eval(&poly, alpha)
+                });
+        }
+    }
 
     result
 }
@@ -177,6 +235,13 @@ pub fn fold_positions(
 // HELPER FUNCTIONS
 // ================================================================================================
 
+/// Returns 2 * (e(x^2) + alpha * o(x^2)) for a polynomial f(x) = e(x^2) + x * o(x^2) given
+/// evaluations of f at x and -x.
+#[inline(always)]
+fn fold2<E: FieldElement>(f_x: E, f_neg_x: E, x_inv: E, alpha: E) -> E {
+    (f_x + f_neg_x) + alpha * (f_x - f_neg_x) * x_inv
+}
+
 fn get_inv_offsets<B>(domain_size: usize, domain_offset: B, folding_factor: usize) -> Vec<B>
 where
     B: StarkField,
@@ -0,0 +1,87 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{apply_drp, fold_rows};
+use math::{
+    batch_inversion,
+    fields::{f128::BaseElement, QuadExtension},
+    get_power_series_with_offset, polynom, FieldElement, StarkField,
+};
+use rand_utils::{rand_value, rand_vector};
+use utils::{collections::Vec, transpose_slice};
+
+// FOLDING TESTS
+// ================================================================================================
+
+#[test]
+fn fold_rows_2() {
+    check_fold_rows::<BaseElement, 2>();
+    check_fold_rows::<QuadExtension<BaseElement>, 2>();
+}
+
+#[test]
+fn fold_rows_4() {
+    check_fold_rows::<BaseElement, 4>();
+    check_fold_rows::<QuadExtension<BaseElement>, 4>();
+}
+
+#[test]
+fn fold_rows_8() {
+    check_fold_rows::<BaseElement, 8>();
+    check_fold_rows::<QuadExtension<BaseElement>, 8>();
+}
+
+#[test]
+fn fold_rows_16() {
+    check_fold_rows::<BaseElement, 16>();
+    check_fold_rows::<QuadExtension<BaseElement>, 16>();
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Checks that folding rows is equivalent to interpolating each row into a polynomial and
+/// evaluating the polynomial at alpha, and that DRP applied to evaluations of a polynomial is
+/// equivalent to folding the polynomial in coefficient form.
+fn check_fold_rows<E: FieldElement<BaseField = BaseElement>, const N: usize>() {
+    let n = 64;
+    let alpha: E = rand_value();
+    let offset = BaseElement::GENERATOR;
+
+    // evaluate a random polynomial over a shifted domain
+    let poly: Vec<E> = rand_vector(n / 4);
+    let g = BaseElement::get_root_of_unity(n.ilog2());
+    let domain = get_power_series_with_offset(g, offset, n);
+    let evaluations = domain
+        .iter()
+        .map(|&x| polynom::eval(&poly, E::from(x)))
+        .collect::<Vec<_>>();
+    let rows = transpose_slice::<E, N>(&evaluations);
+
+    // fold the rows and compare the result to row polynomials evaluated at alpha
+    let inv_xs = batch_inversion(&domain[..n / N]);
+    let folded = fold_rows(&rows, &inv_xs, alpha);
+
+    let root = BaseElement::get_root_of_unity(N.ilog2());
+    for (i, row) in rows.iter().enumerate() {
+        let xs = (0..N)
+            .map(|j| E::from(domain[i] * root.exp((j as u64).into())))
+            .collect::<Vec<_>>();
+        let row_poly = polynom::interpolate(&xs, row, false);
+        assert_eq!(polynom::eval(&row_poly, alpha), folded[i]);
+    }
+
+    // folding must be equivalent to folding the polynomial in coefficient form
+    let folded_poly = poly
+        .chunks(N)
+        .map(|chunk| polynom::eval(chunk, alpha))
+        .collect::<Vec<_>>();
+    let expected = domain[..n / N]
+        .iter()
+        .map(|&x| polynom::eval(&folded_poly, E::from(x.exp((N as u64).into()))))
+        .collect::<Vec<_>>();
+    assert_eq!(expected, folded);
+    assert_eq!(expected, apply_drp(&rows, offset, alpha));
+}
@@ -69,6 +69,8 @@ extern crate alloc;
 
 pub mod folding;
 
+pub mod partition_strategy;
+
 mod prover;
 pub use prover::{DefaultProverChannel, FriProver, ProverChannel};
 
@@ -79,9 +81,12 @@ mod options;
 pub use options::FriOptions;
 
 mod proof;
-pub use proof::FriProof;
+pub use proof::{FriProof, LayerMultiproof};
 
 mod errors;
 pub use errors::VerifierError;
 
 pub mod utils;
+
+#[cfg(any(test, feature = "testing"))]
+pub mod testing;
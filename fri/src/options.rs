@@ -5,6 +5,12 @@
 
 use math::StarkField;
 
+// CONSTANTS
+// ================================================================================================
+
+/// Smallest blowup factor for which the remainder check of the FRI protocol is meaningful.
+const MIN_BLOWUP_FACTOR: usize = 2;
+
 // FRI OPTIONS
 // ================================================================================================
 
@@ -14,6 +20,8 @@ pub struct FriOptions {
     folding_factor: usize,
     remainder_max_degree: usize,
     blowup_factor: usize,
+    domain_offset: Option<u64>,
+    aggregate_layer_proofs: bool,
 }
 
 impl FriOptions {
@@ -21,14 +29,22 @@ impl FriOptions {
     ///
     /// # Panics
     /// Panics if:
-    /// - `blowup_factor` is not a power of two.
+    /// - `blowup_factor` is not a power of two or is smaller than 2.
     /// - `folding_factor` is not 2, 4, 8, or 16.
+    /// - `remainder_max_degree` is not one less than a power of two.
     pub fn new(blowup_factor: usize, folding_factor: usize, remainder_max_degree: usize) -> Self {
         // TODO: change panics to errors
         assert!(
             blowup_factor.is_power_of_two(),
             "blowup factor must be a power of two, but was {blowup_factor}"
         );
+        // with blowup factor 1, the remainder layer is evaluated over a domain of the same size as
+        // the number of remainder coefficients; any evaluations over such a domain are consistent
+        // with some remainder polynomial, and thus, the remainder check would be vacuous
+        assert!(
+            blowup_factor >= MIN_BLOWUP_FACTOR,
+            "blowup factor must be at least {MIN_BLOWUP_FACTOR}, but was {blowup_factor}"
+        );
         assert!(
             folding_factor == 2
                 || folding_factor == 4
@@ -36,21 +52,61 @@ impl FriOptions {
                 || folding_factor == 16,
             "folding factor {folding_factor} is not supported"
         );
+        // the remainder layer is reached by repeatedly dividing the domain size by the folding
+        // factor; a remainder degree bound which is not one less than a power of two would not
+        // match the size of any remainder layer
+        assert!(
+            (remainder_max_degree + 1).is_power_of_two(),
+            "remainder max degree must be one less than a power of two, but was {remainder_max_degree}"
+        );
         FriOptions {
             folding_factor,
             remainder_max_degree,
             blowup_factor,
+            domain_offset: None,
+            aggregate_layer_proofs: false,
         }
     }
 
+    /// Sets the offset by which the evaluation domain is shifted.
+    ///
+    /// The offset is interpreted as an element of the base field. If this is not set, the offset
+    /// defaults to the primitive element of the base field.
+    ///
+    /// # Panics
+    /// Panics if `offset` is zero.
+    pub fn set_domain_offset(mut self, offset: u64) -> Self {
+        assert!(offset != 0, "domain offset cannot be zero");
+        self.domain_offset = Some(offset);
+        self
+    }
+
+    /// Sets whether Merkle authentication paths for queried values are aggregated across all FRI
+    /// layers into a single [LayerMultiproof](crate::LayerMultiproof).
+    ///
+    /// If this is not set, a separate batch Merkle proof is included in the proof for every layer.
+    pub fn set_layer_proof_aggregation(mut self, aggregate: bool) -> Self {
+        self.aggregate_layer_proofs = aggregate;
+        self
+    }
+
     /// Returns the offset by which the evaluation domain is shifted.
     ///
     /// The domain is shifted by multiplying every element in the domain by this offset.
     ///
-    /// Currently, the offset is hard-coded to be the primitive element in the field specified by
-    /// type parameter `B`.
+    /// Unless a custom offset was set via [FriOptions::set_domain_offset()], the offset is the
+    /// primitive element in the field specified by type parameter `B`.
     pub fn domain_offset<B: StarkField>(&self) -> B {
-        B::GENERATOR
+        match self.domain_offset {
+            Some(offset) => B::from(offset),
+            None => B::GENERATOR,
+        }
+    }
+
+    /// Returns `true` if Merkle authentication paths for queried values are aggregated across all
+    /// FRI layers into a single [LayerMultiproof](crate::LayerMultiproof).
+    pub fn aggregates_layer_proofs(&self) -> bool {
+        self.aggregate_layer_proofs
     }
 
     /// Returns the factor by which the degree of a polynomial is reduced with each FRI layer.
@@ -78,6 +134,19 @@ impl FriOptions {
         self.blowup_factor
     }
 
+    /// Returns the maximum number of coefficients in a remainder polynomial for a FRI proof
+    /// computed over a domain of the specified size.
+    ///
+    /// The remainder layer is at most `(remainder_max_degree + 1) * blowup_factor` in size, and
+    /// the remainder polynomial must have at most `1 / blowup_factor` as many coefficients as
+    /// there are evaluations in the remainder layer; otherwise, the low-degree test of the
+    /// remainder layer would not hold with the soundness implied by the blowup factor.
+    pub fn max_remainder_size(&self, domain_size: usize) -> usize {
+        let num_layers = self.num_fri_layers(domain_size);
+        let remainder_domain_size = domain_size / self.folding_factor.pow(num_layers as u32);
+        remainder_domain_size / self.blowup_factor
+    }
+
     /// Computes and return the number of FRI layers required for a domain of the specified size.
     ///
     /// The number of layers for a given domain size is defined by the `folding_factor` and
@@ -92,3 +161,38 @@ impl FriOptions {
         result
     }
 }
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::FriOptions;
+
+    #[test]
+    fn max_remainder_size() {
+        let options = FriOptions::new(8, 4, 7);
+        assert_eq!(2, options.num_fri_layers(1024));
+        assert_eq!(8, options.max_remainder_size(1024));
+        assert_eq!(3, options.num_fri_layers(2048));
+        assert_eq!(4, options.max_remainder_size(2048));
+
+        // a domain which is already small enough is not folded
+        assert_eq!(0, options.num_fri_layers(64));
+        assert_eq!(8, options.max_remainder_size(64));
+    }
+
+    #[test]
+    #[should_panic(expected = "blowup factor must be at least 2, but was 1")]
+    fn blowup_factor_too_small() {
+        FriOptions::new(1, 4, 7);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "remainder max degree must be one less than a power of two, but was 6"
+    )]
+    fn remainder_max_degree_not_power_of_two() {
+        FriOptions::new(8, 4, 6);
+    }
+}
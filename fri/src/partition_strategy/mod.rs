@@ -0,0 +1,183 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+//! Contains strategies for partitioning FRI layer evaluations among multiple provers.
+//!
+//! When a FRI layer is committed to by several provers (e.g., threads or machines), evaluations
+//! of the layer are split into equally sized partitions, and the commitment Merkle tree is built
+//! over the concatenation of these partitions. The position of an evaluation in the evaluation
+//! domain may thus differ from the index of its leaf in the commitment tree. Both the prover
+//! (when opening queries) and the verifier (when checking them) must translate positions into
+//! indexes in the same way; the functions in this module are the single definition of this
+//! translation.
+//!
+//! The verifier learns only the number of partitions from a proof (see
+//! [FriProof::num_partitions()](crate::FriProof::num_partitions)), and translates positions using
+//! the [Interleaved](PartitionStrategy::Interleaved) strategy. Since the
+//! [Contiguous](PartitionStrategy::Contiguous) strategy preserves the order of evaluations,
+//! proofs committed with it are verified as proofs with a single partition.
+
+use utils::collections::Vec;
+
+#[cfg(test)]
+mod tests;
+
+// PARTITION STRATEGY
+// ================================================================================================
+
+/// Defines how evaluations of a FRI layer are assigned to partitions.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum PartitionStrategy {
+    /// Evaluations are assigned to partitions in round-robin fashion: the evaluation at position
+    /// `p` is assigned to partition `p % num_partitions`.
+    Interleaved,
+    /// The evaluation domain is split into contiguous segments: the evaluation at position `p`
+    /// is assigned to partition `p / partition_size`.
+    Contiguous,
+}
+
+impl PartitionStrategy {
+    // PUBLIC METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the number of partitions a proof committed with this strategy reports to the
+    /// verifier.
+    pub fn num_reported_partitions(&self, num_partitions: usize) -> usize {
+        match self {
+            Self::Interleaved => num_partitions,
+            Self::Contiguous => 1,
+        }
+    }
+
+    /// Returns the index of the partition to which the evaluation at the specified `position`
+    /// is assigned.
+    ///
+    /// # Panics
+    /// Panics if `num_partitions` does not divide `domain_size`, or if `position` is not in the
+    /// domain.
+    pub fn partition_of(
+        &self,
+        position: usize,
+        domain_size: usize,
+        num_partitions: usize,
+    ) -> usize {
+        let partition_size = get_partition_size(domain_size, num_partitions);
+        assert!(
+            position < domain_size,
+            "position {position} is not in the domain"
+        );
+        match self {
+            Self::Interleaved => position % num_partitions,
+            Self::Contiguous => position / partition_size,
+        }
+    }
+
+    /// Returns the index of the commitment tree leaf at which the evaluation at the specified
+    /// `position` is committed.
+    ///
+    /// # Panics
+    /// Panics if `num_partitions` does not divide `domain_size`, or if `position` is not in the
+    /// domain.
+    pub fn position_to_index(
+        &self,
+        position: usize,
+        domain_size: usize,
+        num_partitions: usize,
+    ) -> usize {
+        let partition_size = get_partition_size(domain_size, num_partitions);
+        assert!(
+            position < domain_size,
+            "position {position} is not in the domain"
+        );
+        match self {
+            Self::Interleaved => {
+                let partition_idx = position % num_partitions;
+                let local_idx = position / num_partitions;
+                partition_idx * partition_size + local_idx
+            }
+            Self::Contiguous => position,
+        }
+    }
+
+    /// Returns the position of the evaluation committed at the commitment tree leaf with the
+    /// specified `index`; this is the inverse of [PartitionStrategy::position_to_index()].
+    ///
+    /// # Panics
+    /// Panics if `num_partitions` does not divide `domain_size`, or if `index` is not smaller
+    /// than `domain_size`.
+    pub fn index_to_position(
+        &self,
+        index: usize,
+        domain_size: usize,
+        num_partitions: usize,
+    ) -> usize {
+        let partition_size = get_partition_size(domain_size, num_partitions);
+        assert!(index < domain_size, "index {index} is not in the domain");
+        match self {
+            Self::Interleaved => {
+                let partition_idx = index / partition_size;
+                let local_idx = index % partition_size;
+                local_idx * num_partitions + partition_idx
+            }
+            Self::Contiguous => index,
+        }
+    }
+
+    /// Maps positions in the evaluation domain to indexes of commitment tree leaves.
+    ///
+    /// # Panics
+    /// Panics if `num_partitions` does not divide `domain_size`, or if any of the positions is
+    /// not in the domain.
+    pub fn map_positions_to_indexes(
+        &self,
+        positions: &[usize],
+        domain_size: usize,
+        num_partitions: usize,
+    ) -> Vec<usize> {
+        positions
+            .iter()
+            .map(|&position| self.position_to_index(position, domain_size, num_partitions))
+            .collect()
+    }
+
+    /// Splits the specified evaluations into `num_partitions` partitions.
+    ///
+    /// Concatenation of the returned partitions is the order in which the evaluations are
+    /// committed to.
+    ///
+    /// # Panics
+    /// Panics if `num_partitions` does not divide the number of evaluations.
+    pub fn partition<T: Copy>(&self, evaluations: &[T], num_partitions: usize) -> Vec<Vec<T>> {
+        let partition_size = get_partition_size(evaluations.len(), num_partitions);
+        match self {
+            Self::Interleaved => (0..num_partitions)
+                .map(|i| {
+                    evaluations
+                        .iter()
+                        .skip(i)
+                        .step_by(num_partitions)
+                        .copied()
+                        .collect()
+                })
+                .collect(),
+            Self::Contiguous => evaluations
+                .chunks(partition_size)
+                .map(|chunk| chunk.to_vec())
+                .collect(),
+        }
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns the number of evaluations in each partition of a domain of the specified size.
+fn get_partition_size(domain_size: usize, num_partitions: usize) -> usize {
+    assert!(
+        num_partitions > 0 && domain_size % num_partitions == 0,
+        "number of partitions must divide domain size {domain_size}, but was {num_partitions}"
+    );
+    domain_size / num_partitions
+}
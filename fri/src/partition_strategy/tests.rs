@@ -0,0 +1,85 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::PartitionStrategy;
+use crate::utils::map_positions_to_indexes;
+use utils::collections::Vec;
+
+const STRATEGIES: [PartitionStrategy; 2] = [
+    PartitionStrategy::Interleaved,
+    PartitionStrategy::Contiguous,
+];
+
+// PARTITIONING TESTS
+// ================================================================================================
+
+#[test]
+fn partition_strategy_commitment_order() {
+    let domain_size = 64;
+    let evaluations = (0..domain_size).collect::<Vec<usize>>();
+
+    for strategy in STRATEGIES {
+        for num_partitions in [1, 2, 4, 8, 64] {
+            let partitions = strategy.partition(&evaluations, num_partitions);
+            assert_eq!(num_partitions, partitions.len());
+
+            // leaves of the commitment tree are the concatenation of all partitions
+            let leaves = partitions.iter().flatten().copied().collect::<Vec<_>>();
+            for (partition_idx, partition) in partitions.iter().enumerate() {
+                for &position in partition {
+                    assert_eq!(
+                        partition_idx,
+                        strategy.partition_of(position, domain_size, num_partitions)
+                    );
+                }
+            }
+            for position in 0..domain_size {
+                let index = strategy.position_to_index(position, domain_size, num_partitions);
+                assert_eq!(position, leaves[index]);
+                assert_eq!(
+                    position,
+                    strategy.index_to_position(index, domain_size, num_partitions)
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn partition_strategy_verifier_compatibility() {
+    let source_domain_size = 256;
+    let folding_factor = 4;
+    let domain_size = source_domain_size / folding_factor;
+    let positions = [0, 3, 17, 31, 32, 45, 63];
+
+    for strategy in STRATEGIES {
+        for num_partitions in [1, 2, 4, 8, 64] {
+            // the verifier translates positions using only the number of partitions reported in
+            // the proof; this must match the translation done by the prover
+            let reported = strategy.num_reported_partitions(num_partitions);
+            assert_eq!(
+                strategy.map_positions_to_indexes(&positions, domain_size, num_partitions),
+                map_positions_to_indexes(&positions, source_domain_size, folding_factor, reported)
+            );
+        }
+    }
+}
+
+#[test]
+fn partition_strategy_small_folded_domain() {
+    // at deep FRI layers, the folded domain may be smaller than the number of partitions; in
+    // such cases every evaluation is in its own partition, and the order of leaves is preserved
+    let positions = [0, 1, 2, 3];
+    assert_eq!(
+        positions.to_vec(),
+        map_positions_to_indexes(&positions, 16, 4, 8)
+    );
+}
+
+#[test]
+#[should_panic(expected = "number of partitions must divide domain size 64, but was 3")]
+fn partition_strategy_invalid_num_partitions() {
+    PartitionStrategy::Interleaved.position_to_index(5, 64, 3);
+}
@@ -3,11 +3,12 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use crypto::{BatchMerkleProof, ElementHasher, Hasher};
+use crypto::{BatchMerkleProof, ElementHasher, Hasher, MerkleTree, MerkleTreeError};
 use math::FieldElement;
 use utils::{
-    collections::Vec, string::ToString, ByteReader, ByteWriter, Deserializable,
-    DeserializationError, Serializable, SliceReader,
+    collections::{BTreeMap, Vec},
+    string::ToString,
+    ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable, SliceReader,
 };
 
 // FRI PROOF
@@ -25,29 +26,39 @@ use utils::{
 /// paths for these evaluations (the Merkle paths are compressed into a batch Merkle proof). The
 /// remainder polynomial is given by its list of coefficients i.e. field elements.
 ///
+/// Alternatively, Merkle authentication paths for all layers can be aggregated into a single
+/// [LayerMultiproof] (see [FriOptions::set_layer_proof_aggregation()]). In this case, the layers
+/// contain only the queried evaluations.
+///
 /// All values in a proof are stored as vectors of bytes. Thus, the values must be parsed before
-/// they can be returned to the user. To do this, [parse_layers()](FriProof::parse_layers())
-/// and [parse_remainder()](FriProof::parse_remainder()) methods can be used.
+/// they can be returned to the user. To do this, [parse_layers()](FriProof::parse_layers()) (or
+/// [parse_aggregated_layers()](FriProof::parse_aggregated_layers()) for proofs with aggregated
+/// layer proofs) and [parse_remainder()](FriProof::parse_remainder()) methods can be used.
+///
+/// [FriOptions::set_layer_proof_aggregation()]: crate::FriOptions::set_layer_proof_aggregation()
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub struct FriProof {
     layers: Vec<FriProofLayer>,
     remainder: Vec<u8>,
     num_partitions: u8, // stored as power of 2
+    layer_multiproof: Option<Vec<u8>>,
 }
 
 impl FriProof {
     // CONSTRUCTOR
     // --------------------------------------------------------------------------------------------
-    /// Creates a new FRI proof from the provided layers and remainder polynomial.
+    /// Creates a new FRI proof from the provided layers, remainder polynomial, and (optionally)
+    /// Merkle authentication paths aggregated across all layers.
     ///
     /// # Panics
     /// Panics if:
     /// * Number of remainder elements zero or is not a power of two.
     /// * `num_partitions` is zero or is not a power of two.
-    pub(crate) fn new<E: FieldElement>(
+    pub(crate) fn new<E: FieldElement, H: Hasher>(
         layers: Vec<FriProofLayer>,
         remainder: Vec<E>,
         num_partitions: usize,
+        layer_multiproof: Option<LayerMultiproof<H>>,
     ) -> Self {
         assert!(
             !remainder.is_empty(),
@@ -70,6 +81,7 @@ impl FriProof {
             layers,
             remainder: remainder.to_bytes(),
             num_partitions: num_partitions.trailing_zeros() as u8,
+            layer_multiproof: layer_multiproof.map(|multiproof| multiproof.serialize_nodes()),
         }
     }
 
@@ -94,12 +106,25 @@ impl FriProof {
         2usize.pow(self.num_partitions as u32)
     }
 
+    /// Returns `true` if Merkle authentication paths for all layers of this proof are aggregated
+    /// into a single [LayerMultiproof].
+    pub fn is_aggregated(&self) -> bool {
+        self.layer_multiproof.is_some()
+    }
+
     /// Returns the size of this proof in bytes.
     pub fn size(&self) -> usize {
-        // +1 for number of layers, +1 for remainder length, +1 for number of partitions
+        // +1 for number of layers, +1 for remainder length, +1 for number of partitions, +1 for
+        // the layer multiproof flag, and +4 for the length of the layer multiproof
+        let multiproof_size = self
+            .layer_multiproof
+            .as_ref()
+            .map_or(0, |multiproof| multiproof.len() + 4);
         self.layers
             .iter()
-            .fold(self.remainder.len() + 3, |acc, layer| acc + layer.size())
+            .fold(self.remainder.len() + 4 + multiproof_size, |acc, layer| {
+                acc + layer.size()
+            })
     }
 
     // PARSING
@@ -117,6 +142,7 @@ impl FriProof {
     /// Returns an error if:
     /// * This proof is not consistent with the specified `domain_size` and `folding_factor`.
     /// * Any of the layers could not be parsed successfully.
+    /// * Merkle authentication paths of this proof are aggregated across all layers.
     #[allow(clippy::type_complexity)]
     pub fn parse_layers<H, E>(
         self,
@@ -137,6 +163,12 @@ impl FriProof {
         );
         assert!(folding_factor > 1, "folding factor must be greater than 1");
 
+        if self.is_aggregated() {
+            return Err(DeserializationError::InvalidValue(
+                "FRI layer proofs are aggregated into a single multiproof".to_string(),
+            ));
+        }
+
         let mut layer_proofs = Vec::new();
         let mut layer_queries = Vec::new();
 
@@ -153,6 +185,69 @@ impl FriProof {
         Ok((layer_queries, layer_proofs))
     }
 
+    /// Decomposes this proof into vectors of query values for each layer and a multiproof which
+    /// contains Merkle authentication paths for these values across all layers.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * `domain_size` is not a power of two.
+    /// * `folding_factor` is smaller than two or is not a power of two.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * This proof is not consistent with the specified `domain_size` and `folding_factor`.
+    /// * Any of the layers or the multiproof could not be parsed successfully.
+    /// * Merkle authentication paths of this proof are not aggregated across layers.
+    #[allow(clippy::type_complexity)]
+    pub fn parse_aggregated_layers<H, E>(
+        self,
+        mut domain_size: usize,
+        folding_factor: usize,
+    ) -> Result<(Vec<Vec<E>>, LayerMultiproof<H>), DeserializationError>
+    where
+        E: FieldElement,
+        H: ElementHasher<BaseField = E::BaseField>,
+    {
+        assert!(
+            domain_size.is_power_of_two(),
+            "domain size must be a power of two"
+        );
+        assert!(
+            folding_factor.is_power_of_two(),
+            "folding factor must be a power of two"
+        );
+        assert!(folding_factor > 1, "folding factor must be greater than 1");
+
+        let multiproof_bytes = self.layer_multiproof.ok_or_else(|| {
+            DeserializationError::InvalidValue(
+                "FRI layer proofs are not aggregated into a single multiproof".to_string(),
+            )
+        })?;
+
+        let mut layer_depths = Vec::with_capacity(self.layers.len());
+        let mut layer_queries = Vec::with_capacity(self.layers.len());
+
+        // parse query values of all layers
+        for (i, layer) in self.layers.into_iter().enumerate() {
+            domain_size /= folding_factor;
+            if !layer.paths.is_empty() {
+                return Err(DeserializationError::InvalidValue(format!(
+                    "FRI layer {i} cannot contain Merkle paths when layer proofs are aggregated"
+                )));
+            }
+            let qv = layer.parse_values::<E>(folding_factor).map_err(|err| {
+                DeserializationError::InvalidValue(format!("failed to parse FRI layer {i}: {err}"))
+            })?;
+            layer_depths.push(domain_size.ilog2() as usize);
+            layer_queries.push(qv);
+        }
+
+        let mut reader = SliceReader::new(&multiproof_bytes);
+        let multiproof = LayerMultiproof::deserialize(&mut reader, layer_depths)?;
+
+        Ok((layer_queries, multiproof))
+    }
+
     /// Returns a vector of remainder values (last FRI layer) parsed from this proof.
     ///
     /// # Errors
@@ -186,17 +281,24 @@ impl Serializable for FriProof {
     /// Serializes `self` and writes the resulting bytes into the `target` writer.
     fn write_into<W: ByteWriter>(&self, target: &mut W) {
         // write layers
-        target.write_u8(self.layers.len() as u8);
+        target.write_usize(self.layers.len());
         for layer in self.layers.iter() {
             layer.write_into(target);
         }
 
         // write remainder
-        target.write_u16(self.remainder.len() as u16);
+        target.write_usize(self.remainder.len());
         target.write_bytes(&self.remainder);
 
         // write number of partitions
         target.write_u8(self.num_partitions);
+
+        // write layer multiproof
+        target.write_bool(self.layer_multiproof.is_some());
+        if let Some(multiproof) = &self.layer_multiproof {
+            target.write_usize(multiproof.len());
+            target.write_bytes(multiproof);
+        }
     }
 }
 
@@ -207,20 +309,36 @@ impl Deserializable for FriProof {
     /// Returns an error if a valid proof could not be read from the source.
     fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
         // read layers
-        let num_layers = source.read_u8()? as usize;
+        let num_layers = source.read_usize_bounded(u8::MAX as usize)?;
         let layers = FriProofLayer::read_batch_from(source, num_layers)?;
 
         // read remainder
-        let num_remainder_bytes = source.read_u16()? as usize;
+        let num_remainder_bytes = source.read_usize()?;
         let remainder = source.read_vec(num_remainder_bytes)?;
 
         // read number of partitions
         let num_partitions = source.read_u8()?;
+        if num_partitions as u32 >= usize::BITS {
+            return Err(DeserializationError::InvalidValue(format!(
+                "number of partitions cannot be greater than or equal to 2^{}, but was 2^{}",
+                usize::BITS,
+                num_partitions
+            )));
+        }
+
+        // read layer multiproof
+        let layer_multiproof = if source.read_bool()? {
+            let num_multiproof_bytes = source.read_usize()?;
+            Some(source.read_vec(num_multiproof_bytes)?)
+        } else {
+            None
+        };
 
         Ok(FriProof {
             layers,
             remainder,
             num_partitions,
+            layer_multiproof,
         })
     }
 }
@@ -259,6 +377,19 @@ impl FriProofLayer {
         }
     }
 
+    /// Creates a new proof layer from the specified query values; Merkle authentication paths for
+    /// these values are expected to be included in a [LayerMultiproof].
+    ///
+    /// # Panics
+    /// Panics if `query_values` is an empty slice.
+    pub(crate) fn from_values<E: FieldElement, const N: usize>(query_values: Vec<[E; N]>) -> Self {
+        assert!(!query_values.is_empty(), "query values cannot be empty");
+        FriProofLayer {
+            values: query_values.to_bytes(),
+            paths: Vec::new(),
+        }
+    }
+
     // PUBLIC ACCESSORS
     // --------------------------------------------------------------------------------------------
 
@@ -287,6 +418,31 @@ impl FriProofLayer {
         E: FieldElement,
         H: ElementHasher<BaseField = E::BaseField>,
     {
+        let query_values = self.parse_values::<E>(folding_factor)?;
+
+        // hash values of each query to build leaf nodes of the batch Merkle proof
+        let hashed_queries = query_values
+            .chunks(folding_factor)
+            .map(H::hash_elements)
+            .collect::<Vec<_>>();
+
+        // build batch Merkle proof
+        let mut reader = SliceReader::new(&self.paths);
+        let tree_depth = domain_size.ilog2() as u8;
+        let merkle_proof = BatchMerkleProof::deserialize(&mut reader, hashed_queries, tree_depth)?;
+        if reader.has_more_bytes() {
+            return Err(DeserializationError::UnconsumedBytes);
+        }
+
+        Ok((query_values, merkle_proof))
+    }
+
+    /// Parses query values of this layer; the values of each query are returned as
+    /// `folding_factor` consecutive elements.
+    fn parse_values<E: FieldElement>(
+        &self,
+        folding_factor: usize,
+    ) -> Result<Vec<E>, DeserializationError> {
         // make sure the number of value bytes can be parsed into a whole number of queries
         let num_query_bytes = E::ELEMENT_BYTES * folding_factor;
         if self.values.len() % num_query_bytes != 0 {
@@ -302,30 +458,15 @@ impl FriProofLayer {
                 "a FRI layer must contain at least one query".to_string(),
             ));
         }
-        let mut hashed_queries = vec![H::Digest::default(); num_queries];
-        let mut query_values = Vec::with_capacity(num_queries * folding_factor);
 
-        // read bytes corresponding to each query, convert them into field elements,
-        // and also hash them to build leaf nodes of the batch Merkle proof
+        // read bytes corresponding to all queries and convert them into field elements
         let mut reader = SliceReader::new(&self.values);
-        for query_hash in hashed_queries.iter_mut() {
-            let mut qe = E::read_batch_from(&mut reader, folding_factor)?;
-            *query_hash = H::hash_elements(&qe);
-            query_values.append(&mut qe);
-        }
+        let query_values = E::read_batch_from(&mut reader, num_queries * folding_factor)?;
         if reader.has_more_bytes() {
             return Err(DeserializationError::UnconsumedBytes);
         }
 
-        // build batch Merkle proof
-        let mut reader = SliceReader::new(&self.paths);
-        let tree_depth = domain_size.ilog2() as u8;
-        let merkle_proof = BatchMerkleProof::deserialize(&mut reader, hashed_queries, tree_depth)?;
-        if reader.has_more_bytes() {
-            return Err(DeserializationError::UnconsumedBytes);
-        }
-
-        Ok((query_values, merkle_proof))
+        Ok(query_values)
     }
 }
 
@@ -336,11 +477,11 @@ impl Serializable for FriProofLayer {
     /// Serializes this proof layer and writes the resulting bytes to the specified `target`.
     fn write_into<W: ByteWriter>(&self, target: &mut W) {
         // write value bytes
-        target.write_u32(self.values.len() as u32);
+        target.write_usize(self.values.len());
         target.write_bytes(&self.values);
 
         // write path bytes
-        target.write_u32(self.paths.len() as u32);
+        target.write_usize(self.paths.len());
         target.write_bytes(&self.paths);
     }
 }
@@ -352,18 +493,333 @@ impl Deserializable for FriProofLayer {
     /// Returns an error if a valid layer could not be read from the specified source.
     fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
         // read values
-        let num_value_bytes = source.read_u32()?;
+        let num_value_bytes = source.read_usize()?;
         if num_value_bytes == 0 {
             return Err(DeserializationError::InvalidValue(
                 "a FRI proof layer must contain at least one queried evaluation".to_string(),
             ));
         }
-        let values = source.read_vec(num_value_bytes as usize)?;
+        let values = source.read_vec(num_value_bytes)?;
 
         // read paths
-        let num_paths_bytes = source.read_u32()?;
-        let paths = source.read_vec(num_paths_bytes as usize)?;
+        let num_paths_bytes = source.read_usize()?;
+        let paths = source.read_vec(num_paths_bytes)?;
 
         Ok(FriProofLayer { values, paths })
     }
 }
+
+// LAYER MULTIPROOF
+// ================================================================================================
+
+/// Merkle authentication paths for queried values across all FRI layers aggregated into a single
+/// proof.
+///
+/// A multiproof contains only the nodes which the verifier cannot compute from the queried values
+/// or from other nodes of the multiproof. Nodes are identified by the index of their FRI layer in
+/// addition to their position in the layer tree; thus, nodes of different layer trees are never
+/// used in place of each other, even if their positions coincide. Nodes are listed layer by
+/// layer; within a layer, nodes are listed level by level starting from the leaves, and in the
+/// order of increasing index within each level.
+///
+/// Since node positions are implied by query positions, a multiproof does not record how the
+/// nodes are distributed among queries or layers. Compared to separate batch Merkle proofs for
+/// every layer, this removes per-layer and per-query encoding overhead from FRI proofs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LayerMultiproof<H: Hasher> {
+    nodes: Vec<H::Digest>,
+    layer_depths: Vec<usize>,
+    num_read_nodes: usize,
+    num_read_layers: usize,
+}
+
+impl<H: Hasher> LayerMultiproof<H> {
+    // CONSTRUCTORS
+    // --------------------------------------------------------------------------------------------
+    /// Returns a new multiproof which does not contain any layers.
+    pub(crate) fn new() -> Self {
+        LayerMultiproof {
+            nodes: Vec::new(),
+            layer_depths: Vec::new(),
+            num_read_nodes: 0,
+            num_read_layers: 0,
+        }
+    }
+
+    /// Appends to this multiproof the nodes needed to authenticate leaves at the specified
+    /// `indexes` of the next layer tree.
+    ///
+    /// # Panics
+    /// Panics if any of the `indexes` is greater than or equal to the number of leaves in the
+    /// `tree`.
+    pub(crate) fn add_layer(&mut self, tree: &MerkleTree<H>, indexes: &[usize]) {
+        // at level d (counting from the leaves), node i of the level is stored at position
+        // (n >> d) + i of the internal nodes of the tree.
+        let leaves = tree.leaves();
+        let nodes = tree.nodes();
+        self.push_layer(tree.depth(), indexes, |depth, index| {
+            Some(if depth == 0 {
+                leaves[index]
+            } else {
+                nodes[(leaves.len() >> depth) + index]
+            })
+        })
+        .expect("all tree nodes are known");
+    }
+
+    /// Returns a multiproof which contains only the nodes needed to authenticate leaves at
+    /// `selected_indexes[i]` of the i-th layer tree.
+    ///
+    /// `layer_indexes[i]` and `layer_leaves[i]` must be the indexes and the leaves of the i-th
+    /// layer tree which this multiproof authenticates; every selected index must be one of these
+    /// indexes. Roots of the layer trees are not checked; thus, the returned multiproof is valid
+    /// against the layer commitments only if this multiproof is.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * The number of layers in `layer_indexes`, `layer_leaves`, or `selected_indexes` is not
+    ///   equal to the number of layers in this multiproof.
+    /// * Layer roots could not be computed from the specified leaves and the nodes of this
+    ///   multiproof (see [LayerMultiproof::next_layer_root()]), or some of the nodes of this
+    ///   multiproof were not needed to compute the roots.
+    /// * Any of the selected indexes is not one of the layer indexes.
+    pub fn restrict(
+        &self,
+        layer_indexes: &[Vec<usize>],
+        layer_leaves: &[Vec<H::Digest>],
+        selected_indexes: &[Vec<usize>],
+    ) -> Result<Self, MerkleTreeError> {
+        let num_layers = self.num_layers();
+        if layer_indexes.len() != num_layers
+            || layer_leaves.len() != num_layers
+            || selected_indexes.len() != num_layers
+        {
+            return Err(MerkleTreeError::InvalidProof);
+        }
+
+        // expand every layer into all nodes which the verifier can compute or read from this
+        // multiproof, and then collect the nodes needed for the selected leaves only
+        let mut source = Self {
+            nodes: self.nodes.clone(),
+            layer_depths: self.layer_depths.clone(),
+            num_read_nodes: 0,
+            num_read_layers: 0,
+        };
+        let mut result = Self::new();
+        for ((indexes, leaves), selected) in
+            layer_indexes.iter().zip(layer_leaves).zip(selected_indexes)
+        {
+            let depth = source.layer_depths[source.num_read_layers];
+            let mut known_nodes = BTreeMap::new();
+            source.compute_layer_root(indexes, leaves, Some(&mut known_nodes))?;
+            if selected
+                .iter()
+                .any(|&index| !known_nodes.contains_key(&(0, index)))
+            {
+                return Err(MerkleTreeError::InvalidProof);
+            }
+            result.push_layer(depth, selected, |depth, index| {
+                known_nodes.get(&(depth, index)).copied()
+            })?;
+        }
+        if source.has_more_nodes() {
+            return Err(MerkleTreeError::InvalidProof);
+        }
+
+        Ok(result)
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the number of layers in this multiproof.
+    pub fn num_layers(&self) -> usize {
+        self.layer_depths.len()
+    }
+
+    /// Returns the number of nodes in this multiproof.
+    pub fn num_nodes(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Returns `true` if some of the nodes of this multiproof have not been used to compute layer
+    /// roots yet.
+    pub fn has_more_nodes(&self) -> bool {
+        self.num_read_nodes < self.nodes.len()
+    }
+
+    // VERIFICATION
+    // --------------------------------------------------------------------------------------------
+
+    /// Computes the root of the next layer tree from the `leaves` at the specified `indexes` and
+    /// the nodes of this multiproof, and advances the layer pointer by one.
+    ///
+    /// The nodes used to compute the root are consumed; thus, layer roots must be computed in the
+    /// same order in which the layers were added to the multiproof.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * Roots for all layers of this multiproof have already been computed.
+    /// * No indexes were provided, or the number of indexes is not equal to the number of leaves.
+    /// * Any of the `indexes` is greater than or equal to the number of leaves in the layer tree.
+    /// * List of indexes contains duplicates.
+    /// * This multiproof does not contain enough nodes to compute the root.
+    pub fn next_layer_root(
+        &mut self,
+        indexes: &[usize],
+        leaves: &[H::Digest],
+    ) -> Result<H::Digest, MerkleTreeError> {
+        self.compute_layer_root(indexes, leaves, None)
+    }
+
+    // SERIALIZATION / DESERIALIZATION
+    // --------------------------------------------------------------------------------------------
+
+    /// Converts all nodes of this multiproof into a vector of bytes.
+    pub fn serialize_nodes(&self) -> Vec<u8> {
+        self.nodes.to_bytes()
+    }
+
+    /// Parses nodes of a multiproof from all remaining bytes of the specified `source`, and
+    /// constructs a multiproof for layer trees of the specified depths from these nodes.
+    ///
+    /// # Errors
+    /// Returns an error if the remaining bytes could not be parsed into a whole number of nodes.
+    pub fn deserialize<R: ByteReader>(
+        source: &mut R,
+        layer_depths: Vec<usize>,
+    ) -> Result<Self, DeserializationError> {
+        let mut nodes = Vec::new();
+        while source.has_more_bytes() {
+            nodes.push(H::Digest::read_from(source)?);
+        }
+        Ok(LayerMultiproof {
+            nodes,
+            layer_depths,
+            num_read_nodes: 0,
+            num_read_layers: 0,
+        })
+    }
+
+    // HELPER METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Appends to this multiproof the nodes needed to authenticate leaves at the specified
+    /// `indexes` of the next layer tree of the specified `depth`; `get_node(d, i)` must return
+    /// node i at level d (counting from the leaves) of the layer tree.
+    ///
+    /// # Errors
+    /// Returns an error if `get_node` did not return any of the needed nodes.
+    fn push_layer<F>(
+        &mut self,
+        depth: usize,
+        indexes: &[usize],
+        get_node: F,
+    ) -> Result<(), MerkleTreeError>
+    where
+        F: Fn(usize, usize) -> Option<H::Digest>,
+    {
+        let mut indexes = indexes.to_vec();
+        indexes.sort_unstable();
+        indexes.dedup();
+
+        // walk the tree level by level keeping track of the nodes which the verifier can compute;
+        // siblings of these nodes are added to the multiproof unless the verifier can compute
+        // them as well.
+        for level in 0..depth {
+            let mut parents = Vec::with_capacity(indexes.len());
+            let mut i = 0;
+            while i < indexes.len() {
+                let index = indexes[i];
+                if index & 1 == 0 && indexes.get(i + 1) == Some(&(index + 1)) {
+                    i += 1;
+                } else {
+                    let sibling =
+                        get_node(level, index ^ 1).ok_or(MerkleTreeError::InvalidProof)?;
+                    self.nodes.push(sibling);
+                }
+                parents.push(index >> 1);
+                i += 1;
+            }
+            indexes = parents;
+        }
+
+        self.layer_depths.push(depth);
+        Ok(())
+    }
+
+    /// Computes the root of the next layer tree from the `leaves` at the specified `indexes` and
+    /// the nodes of this multiproof, and advances the layer pointer by one.
+    ///
+    /// If `known_nodes` is provided, all nodes used to compute the root (including the leaves)
+    /// are recorded in it keyed by their level (counting from the leaves) and index.
+    fn compute_layer_root(
+        &mut self,
+        indexes: &[usize],
+        leaves: &[H::Digest],
+        mut known_nodes: Option<&mut BTreeMap<(usize, usize), H::Digest>>,
+    ) -> Result<H::Digest, MerkleTreeError> {
+        let depth = *self
+            .layer_depths
+            .get(self.num_read_layers)
+            .ok_or(MerkleTreeError::InvalidProof)?;
+        if indexes.is_empty() {
+            return Err(MerkleTreeError::TooFewLeafIndexes);
+        }
+        if indexes.len() != leaves.len() {
+            return Err(MerkleTreeError::InvalidProof);
+        }
+
+        let mut known = indexes
+            .iter()
+            .copied()
+            .zip(leaves.iter().copied())
+            .collect::<Vec<_>>();
+        known.sort_unstable_by_key(|&(index, _)| index);
+        if known.windows(2).any(|pair| pair[0].0 == pair[1].0) {
+            return Err(MerkleTreeError::DuplicateLeafIndex);
+        }
+        let num_leaves = 1 << depth;
+        if let Some(&(index, _)) = known.last().filter(|&&(index, _)| index >= num_leaves) {
+            return Err(MerkleTreeError::LeafIndexOutOfBounds(num_leaves, index));
+        }
+
+        for level in 0..depth {
+            if let Some(known_nodes) = known_nodes.as_deref_mut() {
+                known_nodes.extend(known.iter().map(|&(index, node)| ((level, index), node)));
+            }
+            let mut parents = Vec::with_capacity(known.len());
+            let mut i = 0;
+            while i < known.len() {
+                let (index, node) = known[i];
+                let parent = match known.get(i + 1) {
+                    Some(&(next, sibling)) if index & 1 == 0 && next == index + 1 => {
+                        i += 1;
+                        H::merge(&[node, sibling])
+                    }
+                    _ => {
+                        let sibling = *self
+                            .nodes
+                            .get(self.num_read_nodes)
+                            .ok_or(MerkleTreeError::InvalidProof)?;
+                        self.num_read_nodes += 1;
+                        if let Some(known_nodes) = known_nodes.as_deref_mut() {
+                            known_nodes.insert((level, index ^ 1), sibling);
+                        }
+                        if index & 1 == 0 {
+                            H::merge(&[node, sibling])
+                        } else {
+                            H::merge(&[sibling, node])
+                        }
+                    }
+                };
+                parents.push((index >> 1, parent));
+                i += 1;
+            }
+            known = parents;
+        }
+
+        self.num_read_layers += 1;
+        Ok(known[0].1)
+    }
+}
@@ -3,6 +3,7 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
+use crate::utils::get_layer_salt;
 use core::marker::PhantomData;
 use crypto::{ElementHasher, Hasher, RandomCoin};
 use math::FieldElement;
@@ -32,9 +33,15 @@ pub trait ProverChannel<E: FieldElement> {
     /// value of the next FRI layer, and then putting each row of the matrix into a single
     /// leaf of the Merkle tree. Thus, the number of elements grouped into a single leaf is
     /// equal to the `folding_factor` used for FRI layer construction.
+    ///
+    /// `layer_depth` and `domain_size` specify the depth of the layer and the size of the domain
+    /// over which the layer is evaluated; they should be absorbed into the public coin together
+    /// with the `layer_root` via [get_layer_salt()](crate::utils::get_layer_salt).
     fn commit_fri_layer(
         &mut self,
         layer_root: <<Self as ProverChannel<E>>::Hasher as Hasher>::Digest,
+        layer_depth: usize,
+        domain_size: usize,
     );
 
     /// Returns a random α drawn uniformly at random from the entire field.
@@ -131,9 +138,11 @@ where
 {
     type Hasher = H;
 
-    fn commit_fri_layer(&mut self, layer_root: H::Digest) {
+    fn commit_fri_layer(&mut self, layer_root: H::Digest, layer_depth: usize, domain_size: usize) {
         self.commitments.push(layer_root);
         self.public_coin.reseed(layer_root);
+        self.public_coin
+            .reseed_with_int(get_layer_salt(layer_depth, domain_size));
     }
 
     fn draw_fri_alpha(&mut self) -> E {
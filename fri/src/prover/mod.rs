@@ -5,14 +5,17 @@
 
 use crate::{
     folding::{apply_drp, fold_positions},
-    proof::{FriProof, FriProofLayer},
+    proof::{FriProof, FriProofLayer, LayerMultiproof},
     utils::hash_values,
     FriOptions,
 };
-use core::marker::PhantomData;
+use core::{convert::Infallible, marker::PhantomData};
 use crypto::{ElementHasher, Hasher, MerkleTree};
 use math::{fft, FieldElement, StarkField};
-use utils::{collections::Vec, flatten_vector_elements, group_slice_elements, transpose_slice};
+use utils::{collections::Vec, flatten_vector_elements, transpose_slice};
+
+#[cfg(not(feature = "forbid-unsafe-bytes"))]
+use utils::group_slice_elements;
 
 mod channel;
 pub use channel::{DefaultProverChannel, ProverChannel};
@@ -82,6 +85,10 @@ mod tests;
 /// layer. To map these positions to the positions in all subsequent layers, the prover uses
 /// [fold_positions] procedure.
 ///
+/// If layer proof aggregation is enabled in the prover's [FriOptions], Merkle authentication
+/// paths for all layers are included in the proof as a single [LayerMultiproof](crate::LayerMultiproof)
+/// rather than as a separate batch Merkle proof for each layer.
+///
 /// After the proof is generated, the prover deletes all internally stored FRI layers.
 ///
 /// Calling [build_layers()](FriProver::build_layers()) when the internal state is dirty, or
@@ -148,6 +155,24 @@ where
         self.layers.len()
     }
 
+    /// Returns evaluations committed to in the FRI layer at the specified index during the last
+    /// execution of the [build_layers()](FriProver::build_layers()) method.
+    ///
+    /// The evaluations are returned in the transposed form in which they are committed: every
+    /// `folding_factor` consecutive values correspond to a single leaf of the layer commitment.
+    ///
+    /// # Panics
+    /// Panics if `layer_idx` is greater than or equal to the number of layers.
+    pub fn layer_evaluations(&self, layer_idx: usize) -> &[E] {
+        &self.layers[layer_idx].evaluations
+    }
+
+    /// Returns coefficients of the remainder polynomial computed during the last execution of
+    /// the [build_layers()](FriProver::build_layers()) method.
+    pub fn remainder_poly(&self) -> &[E] {
+        &self.remainder_poly.0
+    }
+
     /// Clears a vector of internally stored layers.
     pub fn reset(&mut self) {
         self.layers.clear();
@@ -169,7 +194,30 @@ where
     ///
     /// # Panics
     /// Panics if the prover state is dirty (the vector of layers is not empty).
-    pub fn build_layers(&mut self, channel: &mut C, mut evaluations: Vec<E>) {
+    pub fn build_layers(&mut self, channel: &mut C, evaluations: Vec<E>) {
+        self.try_build_layers(channel, evaluations, |_| Ok::<(), Infallible>(()))
+            .unwrap_or_else(|err| match err {})
+    }
+
+    /// Executes the commit phase of the FRI protocol in the same way as
+    /// [build_layers()](FriProver::build_layers()), but invokes `on_layer_built` with the index
+    /// of every layer after the layer has been committed to.
+    ///
+    /// If `on_layer_built` returns an error, the commit phase is aborted and the error is
+    /// returned; in such a case, the prover must be [reset](FriProver::reset()) before it can be
+    /// used again.
+    ///
+    /// # Panics
+    /// Panics if the prover state is dirty (the vector of layers is not empty).
+    pub fn try_build_layers<Err, F>(
+        &mut self,
+        channel: &mut C,
+        mut evaluations: Vec<E>,
+        mut on_layer_built: F,
+    ) -> Result<(), Err>
+    where
+        F: FnMut(usize) -> Result<(), Err>,
+    {
         assert!(
             self.layers.is_empty(),
             "a prior proof generation request has not been completed yet"
@@ -177,7 +225,7 @@ where
 
         // reduce the degree by folding_factor at each iteration until the remaining polynomial
         // has small enough degree
-        for _ in 0..self.options.num_fri_layers(evaluations.len()) {
+        for i in 0..self.options.num_fri_layers(evaluations.len()) {
             match self.folding_factor() {
                 2 => self.build_layer::<2>(channel, &mut evaluations),
                 4 => self.build_layer::<4>(channel, &mut evaluations),
@@ -185,9 +233,11 @@ where
                 16 => self.build_layer::<16>(channel, &mut evaluations),
                 _ => unimplemented!("folding factor {} is not supported", self.folding_factor()),
             }
+            on_layer_built(i)?;
         }
 
         self.set_remainder(channel, &mut evaluations);
+        Ok(())
     }
 
     /// Builds a single FRI layer by first committing to the `evaluations`, then drawing a random
@@ -201,7 +251,11 @@ where
         let hashed_evaluations = hash_values::<H, E, N>(&transposed_evaluations);
         let evaluation_tree =
             MerkleTree::<H>::new(hashed_evaluations).expect("failed to construct FRI layer tree");
-        channel.commit_fri_layer(*evaluation_tree.root());
+        channel.commit_fri_layer(
+            *evaluation_tree.root(),
+            self.layers.len(),
+            evaluations.len(),
+        );
 
         // draw a pseudo-random coefficient from the channel, and use it in degree-respecting
         // projection to reduce the degree of evaluations by N
@@ -221,7 +275,7 @@ where
         let remainder_poly_size = evaluations.len() / self.options.blowup_factor();
         let remainder_poly = evaluations[..remainder_poly_size].to_vec();
         let commitment = <H as ElementHasher>::hash_elements(&remainder_poly);
-        channel.commit_fri_layer(commitment);
+        channel.commit_fri_layer(commitment, self.layers.len(), evaluations.len());
         self.remainder_poly = FriRemainder(remainder_poly);
     }
 
@@ -234,6 +288,9 @@ where
     /// authentication paths from the root of layer commitment trees. For the remainder, we send
     /// the whole remainder polynomial resulting from interpolating the remainder layer.
     ///
+    /// If layer proof aggregation is enabled in the options of this prover, the Merkle paths for
+    /// all layers are aggregated into a single multiproof.
+    ///
     /// # Panics
     /// Panics is the prover state is clean (no FRI layers have been build yet).
     pub fn build_proof(&mut self, positions: &[usize]) -> FriProof {
@@ -243,6 +300,10 @@ where
         );
 
         let mut layers = Vec::with_capacity(self.layers.len());
+        let mut multiproof = self
+            .options
+            .aggregates_layer_proofs()
+            .then(LayerMultiproof::new);
 
         if !self.layers.is_empty() {
             let mut positions = positions.to_vec();
@@ -255,11 +316,13 @@ where
                 positions = fold_positions(&positions, domain_size, folding_factor);
 
                 // sort of a static dispatch for folding_factor parameter
+                let layer = &self.layers[i];
+                let layer_multiproof = multiproof.as_mut();
                 let proof_layer = match folding_factor {
-                    2 => query_layer::<B, E, H, 2>(&self.layers[i], &positions),
-                    4 => query_layer::<B, E, H, 4>(&self.layers[i], &positions),
-                    8 => query_layer::<B, E, H, 8>(&self.layers[i], &positions),
-                    16 => query_layer::<B, E, H, 16>(&self.layers[i], &positions),
+                    2 => query_layer::<B, E, H, 2>(layer, &positions, layer_multiproof),
+                    4 => query_layer::<B, E, H, 4>(layer, &positions, layer_multiproof),
+                    8 => query_layer::<B, E, H, 8>(layer, &positions, layer_multiproof),
+                    16 => query_layer::<B, E, H, 16>(layer, &positions, layer_multiproof),
                     _ => unimplemented!("folding factor {} is not supported", folding_factor),
                 };
 
@@ -274,7 +337,7 @@ where
         // clear layers so that another proof can be generated
         self.reset();
 
-        FriProof::new(layers, remainder, 1)
+        FriProof::new(layers, remainder, 1, multiproof)
     }
 }
 
@@ -283,24 +346,46 @@ where
 
 /// Builds a single proof layer by querying the evaluations of the passed in FRI layer at the
 /// specified positions.
+///
+/// If a `multiproof` is provided, Merkle authentication paths for the queried evaluations are
+/// added to it rather than to the proof layer.
 fn query_layer<B: StarkField, E: FieldElement<BaseField = B>, H: Hasher, const N: usize>(
     layer: &FriLayer<B, E, H>,
     positions: &[usize],
+    multiproof: Option<&mut LayerMultiproof<H>>,
 ) -> FriProofLayer {
-    // build Merkle authentication paths for all query positions
-    let proof = layer
-        .tree
-        .prove_batch(positions)
-        .expect("failed to generate a Merkle proof for FRI layer queries");
-
     // build a list of polynomial evaluations at each position; since evaluations in FRI layers
     // are stored in transposed form, a position refers to N evaluations which are committed
     // in a single leaf
-    let evaluations: &[[E; N]] = group_slice_elements(&layer.evaluations);
-    let mut queried_values: Vec<[E; N]> = Vec::with_capacity(positions.len());
-    for &position in positions.iter() {
-        queried_values.push(evaluations[position]);
-    }
+    #[cfg(not(feature = "forbid-unsafe-bytes"))]
+    let queried_values: Vec<[E; N]> = {
+        let evaluations: &[[E; N]] = group_slice_elements(&layer.evaluations);
+        positions
+            .iter()
+            .map(|&position| evaluations[position])
+            .collect()
+    };
+    #[cfg(feature = "forbid-unsafe-bytes")]
+    let queried_values: Vec<[E; N]> = positions
+        .iter()
+        .map(|&position| {
+            let leaf = &layer.evaluations[position * N..(position + 1) * N];
+            leaf.try_into().expect("leaf must contain N evaluations")
+        })
+        .collect();
 
-    FriProofLayer::new(queried_values, proof)
+    // build Merkle authentication paths for all query positions
+    match multiproof {
+        Some(multiproof) => {
+            multiproof.add_layer(&layer.tree, positions);
+            FriProofLayer::from_values(queried_values)
+        }
+        None => {
+            let proof = layer
+                .tree
+                .prove_batch(positions)
+                .expect("failed to generate a Merkle proof for FRI layer queries");
+            FriProofLayer::new(queried_values, proof)
+        }
+    }
 }
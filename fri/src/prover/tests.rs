@@ -3,14 +3,17 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use super::{DefaultProverChannel, FriProver};
+use super::{DefaultProverChannel, FriProver, ProverChannel};
 use crate::{
+    folding::fold_positions,
+    testing::{ChannelInteraction, MockProverChannel, MockVerifierChannel},
+    utils::map_positions_to_indexes,
     verifier::{DefaultVerifierChannel, FriVerifier},
     FriOptions, FriProof, VerifierError,
 };
-use crypto::{hashers::Blake3_256, DefaultRandomCoin, Hasher, RandomCoin};
+use crypto::{hashers::Blake3_256, DefaultRandomCoin, ElementHasher, Hasher, RandomCoin};
 use math::{fft, fields::f128::BaseElement, FieldElement};
-use utils::{collections::Vec, Deserializable, Serializable, SliceReader};
+use utils::{collections::Vec, ByteWriter, Deserializable, Serializable, SliceReader};
 
 type Blake3 = Blake3_256<BaseElement>;
 
@@ -45,6 +48,284 @@ fn fri_folding_4() {
     )
 }
 
+#[test]
+fn fri_layer_proof_aggregation() {
+    let trace_length = 1 << 10;
+    let lde_blowup = 8;
+    let domain_size = trace_length * lde_blowup;
+    let options = FriOptions::new(lde_blowup, 4, 7);
+    let evaluations = build_evaluations(trace_length, lde_blowup);
+
+    let build_proof = |options: &FriOptions| {
+        let mut channel = build_prover_channel(trace_length, options);
+        let mut prover = FriProver::new(options.clone());
+        prover.build_layers(&mut channel, evaluations.clone());
+        let positions = channel.draw_query_positions();
+        let proof = prover.build_proof(&positions);
+        (proof, channel.layer_commitments().to_vec(), positions)
+    };
+    let (separate, _, _) = build_proof(&options);
+    let aggregated_options = options.clone().set_layer_proof_aggregation(true);
+    let (proof, commitments, positions) = build_proof(&aggregated_options);
+    assert!(!separate.is_aggregated());
+    assert!(proof.is_aggregated());
+
+    // the multiproof contains the same nodes as separate batch proofs, but is encoded more
+    // compactly
+    let (_, layer_proofs) = separate
+        .clone()
+        .parse_layers::<Blake3, BaseElement>(domain_size, 4)
+        .unwrap();
+    let (_, multiproof) = proof
+        .clone()
+        .parse_aggregated_layers::<Blake3, BaseElement>(domain_size, 4)
+        .unwrap();
+    let num_nodes = layer_proofs
+        .iter()
+        .flat_map(|proof| proof.nodes.iter())
+        .map(|nodes| nodes.len())
+        .sum::<usize>();
+    assert_eq!(proof.num_layers(), multiproof.num_layers());
+    assert_eq!(num_nodes, multiproof.num_nodes());
+    assert!(proof.size() < separate.size());
+    assert!(proof
+        .clone()
+        .parse_layers::<Blake3, BaseElement>(domain_size, 4)
+        .is_err());
+
+    let verify = |proof: FriProof, options: &FriOptions| {
+        let max_degree = trace_length - 1;
+        verify_proof(
+            proof,
+            commitments.clone(),
+            &evaluations,
+            max_degree,
+            domain_size,
+            &positions,
+            options,
+        )
+    };
+    assert_eq!(Ok(()), verify(proof.clone(), &aggregated_options));
+
+    // aggregation must be consistent with the options of the verifier
+    assert_eq!(
+        Err(VerifierError::LayerProofAggregationMismatch),
+        verify(proof.clone(), &options)
+    );
+
+    // a modified multiproof node is detected
+    let mut bytes = proof.to_bytes();
+    *bytes.last_mut().unwrap() ^= 1;
+    let tampered = FriProof::read_from_bytes(&bytes).unwrap();
+    assert_eq!(
+        Err(VerifierError::LayerCommitmentMismatch),
+        verify(tampered, &aggregated_options)
+    );
+
+    // so are extra nodes appended to the multiproof; the multiproof is prefixed with its length
+    let mut bytes = proof.to_bytes();
+    let num_node_bytes = 32 * multiproof.num_nodes();
+    let mut prefix = Vec::new();
+    prefix.write_usize(num_node_bytes);
+    let node_bytes = bytes.split_off(bytes.len() - num_node_bytes);
+    bytes.truncate(bytes.len() - prefix.len());
+    bytes.write_usize(num_node_bytes + 32);
+    bytes.extend_from_slice(&node_bytes);
+    bytes.extend_from_slice(&[0; 32]);
+    let tampered = FriProof::read_from_bytes(&bytes).unwrap();
+    assert_eq!(
+        Err(VerifierError::LayerCommitmentMismatch),
+        verify(tampered, &aggregated_options)
+    );
+}
+
+#[test]
+fn fri_layer_multiproof_restriction() {
+    let trace_length = 1 << 10;
+    let lde_blowup = 8;
+    let domain_size = trace_length * lde_blowup;
+    let folding_factor = 4;
+    let options = FriOptions::new(lde_blowup, folding_factor, 7).set_layer_proof_aggregation(true);
+    let evaluations = build_evaluations(trace_length, lde_blowup);
+
+    let build_prover = || {
+        let mut channel = build_prover_channel(trace_length, &options);
+        let mut prover = FriProver::new(options.clone());
+        prover.build_layers(&mut channel, evaluations.clone());
+        (prover, channel)
+    };
+    let (mut prover, mut channel) = build_prover();
+    let positions = channel.draw_query_positions();
+    let commitments = channel.layer_commitments().to_vec();
+    let proof = prover.build_proof(&positions);
+    let num_partitions = proof.num_partitions();
+    let (layer_queries, multiproof) = proof
+        .parse_aggregated_layers::<Blake3, BaseElement>(domain_size, folding_factor)
+        .unwrap();
+
+    // determine leaves of every layer tree for all queries and for the selected queries
+    let selected_positions = [positions[1], positions[3]];
+    let (mut all_positions, mut some_positions) = (positions.clone(), selected_positions.to_vec());
+    let (mut layer_indexes, mut layer_leaves, mut selected_indexes) =
+        (Vec::new(), Vec::new(), Vec::new());
+    let mut layer_domain_size = domain_size;
+    for queries in layer_queries.iter() {
+        all_positions = fold_positions(&all_positions, layer_domain_size, folding_factor);
+        some_positions = fold_positions(&some_positions, layer_domain_size, folding_factor);
+        let map_positions = |positions: &[usize]| {
+            map_positions_to_indexes(positions, layer_domain_size, folding_factor, num_partitions)
+        };
+        layer_indexes.push(map_positions(&all_positions));
+        selected_indexes.push(map_positions(&some_positions));
+        layer_leaves.push(
+            queries
+                .chunks(folding_factor)
+                .map(Blake3::hash_elements)
+                .collect::<Vec<_>>(),
+        );
+        layer_domain_size /= folding_factor;
+    }
+
+    // the restricted multiproof is the same as the multiproof built for the selected queries only
+    let restricted = multiproof
+        .restrict(&layer_indexes, &layer_leaves, &selected_indexes)
+        .unwrap();
+    let expected = build_prover()
+        .0
+        .build_proof(&selected_positions)
+        .parse_aggregated_layers::<Blake3, BaseElement>(domain_size, folding_factor)
+        .unwrap()
+        .1;
+    assert_eq!(expected, restricted);
+
+    // and it authenticates the leaves of the selected queries against the layer commitments
+    let mut restricted = restricted;
+    for (i, commitment) in commitments.iter().take(layer_indexes.len()).enumerate() {
+        let leaves = selected_indexes[i]
+            .iter()
+            .map(|index| {
+                let row = layer_indexes[i].iter().position(|i| i == index).unwrap();
+                layer_leaves[i][row]
+            })
+            .collect::<Vec<_>>();
+        let root = restricted
+            .next_layer_root(&selected_indexes[i], &leaves)
+            .unwrap();
+        assert_eq!(*commitment, root);
+    }
+    assert!(!restricted.has_more_nodes());
+
+    // queries which were not opened by the multiproof cannot be selected
+    let mut invalid_indexes = selected_indexes.clone();
+    let missing = (0..)
+        .find(|index| !layer_indexes[0].contains(index))
+        .unwrap();
+    invalid_indexes[0].push(missing);
+    assert!(multiproof
+        .restrict(&layer_indexes, &layer_leaves, &invalid_indexes)
+        .is_err());
+
+    // leaves which do not match the multiproof are rejected
+    layer_leaves[0].pop();
+    assert!(multiproof
+        .restrict(&layer_indexes, &layer_leaves, &selected_indexes)
+        .is_err());
+}
+
+#[test]
+fn fri_layer_salts() {
+    // identical commitments at different depths or domain sizes must result in different alphas
+    let commitment = Blake3::hash(&[1, 2, 3]);
+    let draw_alpha = |layer_depth: usize, domain_size: usize| -> BaseElement {
+        let mut channel =
+            DefaultProverChannel::<BaseElement, Blake3, DefaultRandomCoin<Blake3>>::new(1024, 32);
+        channel.commit_fri_layer(commitment, layer_depth, domain_size);
+        channel.draw_fri_alpha()
+    };
+
+    assert_eq!(draw_alpha(1, 256), draw_alpha(1, 256));
+    assert_ne!(draw_alpha(0, 256), draw_alpha(1, 256));
+    assert_ne!(draw_alpha(1, 256), draw_alpha(1, 64));
+}
+
+#[test]
+fn fri_mock_channels() {
+    let trace_length = 1 << 7;
+    let lde_blowup = 8;
+    let domain_size = trace_length * lde_blowup;
+    let options = FriOptions::new(lde_blowup, 4, 7);
+    let evaluations = build_evaluations(trace_length, lde_blowup);
+
+    // the prover draws an alpha after committing to each layer but the remainder; once scripted
+    // alphas are exhausted, the channel falls back to a sequence of small integers
+    let alphas = vec![BaseElement::new(7)];
+    let mut channel = MockProverChannel::<BaseElement, Blake3>::with_alphas(alphas);
+    let mut prover = FriProver::new(options.clone());
+    prover.build_layers(&mut channel, evaluations.clone());
+    assert_eq!(2, prover.num_layers());
+
+    let commitments = channel.layer_commitments();
+    let expected = vec![
+        ChannelInteraction::Commitment {
+            layer_root: commitments[0],
+            layer_depth: 0,
+            domain_size,
+        },
+        ChannelInteraction::Alpha(BaseElement::new(7)),
+        ChannelInteraction::Commitment {
+            layer_root: commitments[1],
+            layer_depth: 1,
+            domain_size: domain_size / 4,
+        },
+        ChannelInteraction::Alpha(BaseElement::new(2)),
+        ChannelInteraction::Commitment {
+            layer_root: commitments[2],
+            layer_depth: 2,
+            domain_size: domain_size / 16,
+        },
+    ];
+    assert_eq!(expected, channel.interactions());
+
+    // the verifier reads every message sent by the prover exactly once
+    let mut channel = build_prover_channel(trace_length, &options);
+    let mut prover = FriProver::new(options.clone());
+    prover.build_layers(&mut channel, evaluations.clone());
+    let positions = channel.draw_query_positions();
+    let proof = prover.build_proof(&positions);
+    let remainder = proof.parse_remainder().unwrap();
+    let (layer_queries, layer_proofs) = proof
+        .parse_layers::<Blake3, BaseElement>(domain_size, options.folding_factor())
+        .unwrap();
+
+    let mut channel = MockVerifierChannel::<BaseElement, Blake3>::new(
+        channel.layer_commitments().to_vec(),
+        layer_queries,
+        layer_proofs,
+        remainder,
+    );
+    let mut coin = DefaultRandomCoin::<Blake3>::new(&[]);
+    let verifier =
+        FriVerifier::new(&mut channel, &mut coin, options.clone(), trace_length - 1).unwrap();
+    let queried_evaluations = positions
+        .iter()
+        .map(|&p| evaluations[p])
+        .collect::<Vec<_>>();
+    assert_eq!(
+        Ok(()),
+        verifier.verify(&mut channel, &queried_evaluations, &positions)
+    );
+    assert!(channel.is_empty());
+
+    // the number of layer commitments must match the number of layers implied by the options
+    let mut commitments = commitments;
+    commitments.pop();
+    let mut channel =
+        MockVerifierChannel::<BaseElement, Blake3>::new(commitments, vec![], vec![], vec![]);
+    let mut coin = DefaultRandomCoin::<Blake3>::new(&[]);
+    let result = FriVerifier::new(&mut channel, &mut coin, options, trace_length - 1);
+    assert_eq!(Some(VerifierError::LayerCountMismatch(3, 2)), result.err());
+}
+
 // TEST UTILS
 // ================================================================================================
 
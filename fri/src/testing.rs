@@ -0,0 +1,212 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+//! Mock implementations of FRI channels intended for unit-testing protocols built on top of FRI.
+//!
+//! Unlike the default channels, mock channels do not use a public coin: [MockProverChannel]
+//! returns a scripted (or fixed deterministic) sequence of α values and records every interaction
+//! with the prover, while [MockVerifierChannel] returns scripted prover messages to the verifier.
+//! Thus, tests can assert on the exact sequence of messages exchanged over a channel without
+//! running the full protocol.
+//!
+//! This module is available only when the crate is compiled with `testing` feature enabled.
+
+use crate::{LayerMultiproof, ProverChannel, VerifierChannel};
+use core::marker::PhantomData;
+use crypto::{BatchMerkleProof, ElementHasher, Hasher};
+use math::FieldElement;
+use utils::collections::Vec;
+
+// CHANNEL INTERACTION
+// ================================================================================================
+
+/// An interaction of a prover with a [MockProverChannel].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ChannelInteraction<E: FieldElement, D> {
+    /// A FRI layer commitment was sent to the verifier.
+    Commitment {
+        layer_root: D,
+        layer_depth: usize,
+        domain_size: usize,
+    },
+    /// An α value was drawn from the channel.
+    Alpha(E),
+}
+
+// MOCK PROVER CHANNEL
+// ================================================================================================
+
+/// A [ProverChannel] which returns a predefined sequence of α values and records all interactions
+/// of the prover with the channel.
+pub struct MockProverChannel<E: FieldElement, H: Hasher> {
+    alphas: Vec<E>,
+    interactions: Vec<ChannelInteraction<E, H::Digest>>,
+    num_drawn_alphas: usize,
+    _hasher: PhantomData<H>,
+}
+
+impl<E: FieldElement, H: Hasher> MockProverChannel<E, H> {
+    /// Returns a new channel which returns α values 1, 2, 3, ... in this order.
+    pub fn new() -> Self {
+        Self::with_alphas(Vec::new())
+    }
+
+    /// Returns a new channel which returns the specified `alphas` in order; once the scripted
+    /// values are exhausted, the channel falls back to the sequence returned by a channel created
+    /// via [MockProverChannel::new()].
+    pub fn with_alphas(alphas: Vec<E>) -> Self {
+        MockProverChannel {
+            alphas,
+            interactions: Vec::new(),
+            num_drawn_alphas: 0,
+            _hasher: PhantomData,
+        }
+    }
+
+    /// Returns all interactions of the prover with this channel in the order in which they took
+    /// place.
+    pub fn interactions(&self) -> &[ChannelInteraction<E, H::Digest>] {
+        &self.interactions
+    }
+
+    /// Returns a list of FRI layer roots committed to this channel.
+    pub fn layer_commitments(&self) -> Vec<H::Digest> {
+        self.interactions
+            .iter()
+            .filter_map(|interaction| match interaction {
+                ChannelInteraction::Commitment { layer_root, .. } => Some(*layer_root),
+                ChannelInteraction::Alpha(_) => None,
+            })
+            .collect()
+    }
+}
+
+impl<E: FieldElement, H: Hasher> Default for MockProverChannel<E, H> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<E: FieldElement, H: Hasher> ProverChannel<E> for MockProverChannel<E, H> {
+    type Hasher = H;
+
+    fn commit_fri_layer(&mut self, layer_root: H::Digest, layer_depth: usize, domain_size: usize) {
+        self.interactions.push(ChannelInteraction::Commitment {
+            layer_root,
+            layer_depth,
+            domain_size,
+        });
+    }
+
+    fn draw_fri_alpha(&mut self) -> E {
+        let alpha = match self.alphas.get(self.num_drawn_alphas) {
+            Some(&alpha) => alpha,
+            None => E::from((self.num_drawn_alphas + 1) as u64),
+        };
+        self.num_drawn_alphas += 1;
+        self.interactions.push(ChannelInteraction::Alpha(alpha));
+        alpha
+    }
+}
+
+// MOCK VERIFIER CHANNEL
+// ================================================================================================
+
+/// A [VerifierChannel] which returns scripted prover messages to the verifier.
+///
+/// Every message can be read from the channel only once; reading a message which has not been
+/// scripted (or has already been read) causes a panic.
+pub struct MockVerifierChannel<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> {
+    layer_commitments: Option<Vec<H::Digest>>,
+    layer_queries: Vec<Vec<E>>,
+    layer_proofs: Vec<BatchMerkleProof<H>>,
+    layer_multiproof: Option<LayerMultiproof<H>>,
+    remainder: Option<Vec<E>>,
+    num_partitions: usize,
+}
+
+impl<E, H> MockVerifierChannel<E, H>
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+{
+    /// Returns a new channel with the specified layer commitments, layer queries, layer proofs,
+    /// and remainder. The channel reports the number of partitions to be 1, and does not contain
+    /// a layer multiproof.
+    pub fn new(
+        layer_commitments: Vec<H::Digest>,
+        layer_queries: Vec<Vec<E>>,
+        layer_proofs: Vec<BatchMerkleProof<H>>,
+        remainder: Vec<E>,
+    ) -> Self {
+        MockVerifierChannel {
+            layer_commitments: Some(layer_commitments),
+            layer_queries,
+            layer_proofs,
+            layer_multiproof: None,
+            remainder: Some(remainder),
+            num_partitions: 1,
+        }
+    }
+
+    /// Sets the number of partitions reported by this channel.
+    pub fn set_num_partitions(mut self, num_partitions: usize) -> Self {
+        self.num_partitions = num_partitions;
+        self
+    }
+
+    /// Sets the layer multiproof returned by this channel.
+    pub fn set_layer_multiproof(mut self, multiproof: LayerMultiproof<H>) -> Self {
+        self.layer_multiproof = Some(multiproof);
+        self
+    }
+
+    /// Returns true if all messages scripted for this channel have been read from it.
+    pub fn is_empty(&self) -> bool {
+        self.layer_commitments.is_none()
+            && self.layer_queries.is_empty()
+            && self.layer_proofs.is_empty()
+            && self.layer_multiproof.is_none()
+            && self.remainder.is_none()
+    }
+}
+
+impl<E, H> VerifierChannel<E> for MockVerifierChannel<E, H>
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+{
+    type Hasher = H;
+
+    fn read_fri_num_partitions(&self) -> usize {
+        self.num_partitions
+    }
+
+    fn read_fri_layer_commitments(&mut self) -> Vec<H::Digest> {
+        self.layer_commitments
+            .take()
+            .expect("layer commitments have already been read")
+    }
+
+    fn take_next_fri_layer_queries(&mut self) -> Vec<E> {
+        assert!(!self.layer_queries.is_empty(), "no more layer queries");
+        self.layer_queries.remove(0)
+    }
+
+    fn take_next_fri_layer_proof(&mut self) -> BatchMerkleProof<H> {
+        assert!(!self.layer_proofs.is_empty(), "no more layer proofs");
+        self.layer_proofs.remove(0)
+    }
+
+    fn take_fri_remainder(&mut self) -> Vec<E> {
+        self.remainder
+            .take()
+            .expect("remainder has already been read")
+    }
+
+    fn take_fri_layer_multiproof(&mut self) -> Option<LayerMultiproof<H>> {
+        self.layer_multiproof.take()
+    }
+}
@@ -3,6 +3,7 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
+use crate::partition_strategy::PartitionStrategy;
 use crypto::ElementHasher;
 use math::FieldElement;
 use utils::{collections::Vec, iter_mut, uninit_vector};
@@ -10,7 +11,38 @@ use utils::{collections::Vec, iter_mut, uninit_vector};
 #[cfg(feature = "concurrent")]
 use utils::iterators::*;
 
+// CONSTANTS
+// ================================================================================================
+
+/// Version of the data absorbed into the public coin together with FRI layer commitments; this
+/// is incremented whenever the transcription of FRI layers changes.
+pub const FRI_TRANSCRIPT_VERSION: u8 = 1;
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns a salt which is absorbed into the public coin together with the commitment to a FRI
+/// layer at the specified depth.
+///
+/// The salt encodes the transcript version, the depth of the layer, and log2 of the size of the
+/// domain over which the layer is evaluated. This rules out reuse of the same α across layers
+/// even if two layers have identical commitments (e.g., for degenerate constant polynomials).
+pub fn get_layer_salt(layer_depth: usize, domain_size: usize) -> u64 {
+    debug_assert!(
+        domain_size.is_power_of_two(),
+        "domain size must be a power of two"
+    );
+    ((FRI_TRANSCRIPT_VERSION as u64) << 56)
+        | ((layer_depth as u64) << 32)
+        | domain_size.ilog2() as u64
+}
+
 /// Maps positions in the evaluation domain to indexes of commitment Merkle tree.
+///
+/// `positions` are positions in the domain obtained by folding a domain of `source_domain_size`
+/// by `folding_factor`. Evaluations are assumed to be partitioned using the
+/// [Interleaved](PartitionStrategy::Interleaved) strategy; if the folded domain is smaller than
+/// `num_partitions`, every evaluation is assumed to be in its own partition.
 pub fn map_positions_to_indexes(
     positions: &[usize],
     source_domain_size: usize,
@@ -24,17 +56,12 @@ pub fn map_positions_to_indexes(
     }
 
     let target_domain_size = source_domain_size / folding_factor;
-    let partition_size = target_domain_size / num_partitions;
-
-    let mut result = Vec::new();
-    for position in positions {
-        let partition_idx = position % num_partitions;
-        let local_idx = (position - partition_idx) / num_partitions;
-        let position = partition_idx * partition_size + local_idx;
-        result.push(position);
-    }
-
-    result
+    let num_partitions = num_partitions.min(target_domain_size);
+    PartitionStrategy::Interleaved.map_positions_to_indexes(
+        positions,
+        target_domain_size,
+        num_partitions,
+    )
 }
 
 /// Hashes each of the arrays in the provided slice and returns a vector of resulting hashes.
@@ -3,8 +3,8 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use crate::{FriProof, VerifierError};
-use crypto::{BatchMerkleProof, ElementHasher, Hasher, MerkleTree};
+use crate::{utils::hash_values, FriProof, LayerMultiproof, VerifierError};
+use crypto::{BatchMerkleProof, ElementHasher, Hasher};
 use math::FieldElement;
 use utils::{collections::Vec, group_vector_elements, DeserializationError};
 
@@ -68,6 +68,15 @@ pub trait VerifierChannel<E: FieldElement> {
     // PROVIDED METHODS
     // --------------------------------------------------------------------------------------------
 
+    /// Reads and removes from the channel Merkle authentication paths for queried evaluations
+    /// across all FRI layers aggregated into a single multiproof.
+    ///
+    /// Returns `None` if the prover sent a separate batch Merkle proof for every FRI layer; this
+    /// is also the default implementation of this method.
+    fn take_fri_layer_multiproof(&mut self) -> Option<LayerMultiproof<Self::Hasher>> {
+        None
+    }
+
     /// Returns FRI query values at the specified positions from the current FRI layer and advances
     /// layer pointer by one.
     ///
@@ -81,8 +90,12 @@ pub trait VerifierChannel<E: FieldElement> {
         commitment: &<<Self as VerifierChannel<E>>::Hasher as Hasher>::Digest,
     ) -> Result<Vec<[E; N]>, VerifierError> {
         let layer_proof = self.take_next_fri_layer_proof();
-        MerkleTree::<Self::Hasher>::verify_batch(commitment, positions, &layer_proof)
+        let layer_root = layer_proof
+            .verify_into_root(positions)
             .map_err(|_| VerifierError::LayerCommitmentMismatch)?;
+        if layer_root != *commitment {
+            return Err(VerifierError::LayerCommitmentMismatch);
+        }
 
         // TODO: make sure layer queries hash into leaves of layer proof
 
@@ -90,6 +103,37 @@ pub trait VerifierChannel<E: FieldElement> {
         Ok(group_vector_elements(layer_queries))
     }
 
+    /// Returns FRI query values at the specified positions from the current FRI layer and advances
+    /// layer pointer by one; Merkle authentication paths for the values are read from the
+    /// specified `multiproof`.
+    ///
+    /// This also checks if the values are valid against the provided FRI layer commitment.
+    ///
+    /// # Errors
+    /// Returns an error if query values did not match layer commitment.
+    fn read_aggregated_layer_queries<const N: usize>(
+        &mut self,
+        positions: &[usize],
+        commitment: &<<Self as VerifierChannel<E>>::Hasher as Hasher>::Digest,
+        multiproof: &mut LayerMultiproof<Self::Hasher>,
+    ) -> Result<Vec<[E; N]>, VerifierError> {
+        let layer_queries = self.take_next_fri_layer_queries();
+        if layer_queries.len() != positions.len() * N {
+            return Err(VerifierError::LayerCommitmentMismatch);
+        }
+        let layer_queries = group_vector_elements(layer_queries);
+
+        let leaves = hash_values::<Self::Hasher, E, N>(&layer_queries);
+        let layer_root = multiproof
+            .next_layer_root(positions, &leaves)
+            .map_err(|_| VerifierError::LayerCommitmentMismatch)?;
+        if layer_root != *commitment {
+            return Err(VerifierError::LayerCommitmentMismatch);
+        }
+
+        Ok(layer_queries)
+    }
+
     /// Returns FRI remainder polynomial read from this channel.
     fn read_remainder(&mut self) -> Result<Vec<E>, VerifierError> {
         let remainder = self.take_fri_remainder();
@@ -110,6 +154,7 @@ pub trait VerifierChannel<E: FieldElement> {
 pub struct DefaultVerifierChannel<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> {
     layer_commitments: Vec<H::Digest>,
     layer_proofs: Vec<BatchMerkleProof<H>>,
+    layer_multiproof: Option<LayerMultiproof<H>>,
     layer_queries: Vec<Vec<E>>,
     remainder: Vec<E>,
     num_partitions: usize,
@@ -133,12 +178,20 @@ where
         let num_partitions = proof.num_partitions();
 
         let remainder = proof.parse_remainder()?;
-        let (layer_queries, layer_proofs) =
-            proof.parse_layers::<H, E>(domain_size, folding_factor)?;
+        let (layer_queries, layer_proofs, layer_multiproof) = if proof.is_aggregated() {
+            let (layer_queries, multiproof) =
+                proof.parse_aggregated_layers::<H, E>(domain_size, folding_factor)?;
+            (layer_queries, Vec::new(), Some(multiproof))
+        } else {
+            let (layer_queries, layer_proofs) =
+                proof.parse_layers::<H, E>(domain_size, folding_factor)?;
+            (layer_queries, layer_proofs, None)
+        };
 
         Ok(DefaultVerifierChannel {
             layer_commitments,
             layer_proofs,
+            layer_multiproof,
             layer_queries,
             remainder,
             num_partitions,
@@ -172,4 +225,8 @@ where
     fn take_fri_remainder(&mut self) -> Vec<E> {
         self.remainder.clone()
     }
+
+    fn take_fri_layer_multiproof(&mut self) -> Option<LayerMultiproof<H>> {
+        self.layer_multiproof.take()
+    }
 }
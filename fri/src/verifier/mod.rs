@@ -5,10 +5,14 @@
 
 //! Contains an implementation of FRI verifier and associated components.
 
-use crate::{folding::fold_positions, utils::map_positions_to_indexes, FriOptions, VerifierError};
-use core::{convert::TryInto, marker::PhantomData, mem};
+use crate::{
+    folding::{fold_positions, fold_rows},
+    utils::{get_layer_salt, map_positions_to_indexes},
+    FriOptions, VerifierError,
+};
+use core::{marker::PhantomData, mem};
 use crypto::{ElementHasher, RandomCoin};
-use math::{polynom, FieldElement, StarkField};
+use math::{batch_inversion, domain::Domain, FieldElement};
 use utils::collections::Vec;
 
 mod channel;
@@ -63,8 +67,7 @@ where
     R: RandomCoin<BaseField = E::BaseField, Hasher = H>,
 {
     max_poly_degree: usize,
-    domain_size: usize,
-    domain_generator: E::BaseField,
+    domain: Domain<E::BaseField>,
     layer_commitments: Vec<H::Digest>,
     layer_alphas: Vec<E>,
     options: FriOptions,
@@ -98,6 +101,8 @@ where
     /// Returns an error if:
     /// * `max_poly_degree` is inconsistent with the number of FRI layers read from the channel
     ///   and `folding_factor` specified in the `options` parameter.
+    /// * The number of FRI layer commitments read from the channel is not equal to the number of
+    ///   FRI layers (including the remainder layer) implied by the `options` parameter.
     /// * An error was encountered while drawing a random α value from the coin.
     pub fn new(
         channel: &mut C,
@@ -107,16 +112,25 @@ where
     ) -> Result<Self, VerifierError> {
         // infer evaluation domain info
         let domain_size = max_poly_degree.next_power_of_two() * options.blowup_factor();
-        let domain_generator = E::BaseField::get_root_of_unity(domain_size.ilog2());
+        let domain = Domain::with_offset(domain_size, options.domain_offset());
 
         let num_partitions = channel.read_fri_num_partitions();
 
         // read layer commitments from the channel and use them to build a list of alphas
         let layer_commitments = channel.read_fri_layer_commitments();
+        let num_layers = options.num_fri_layers(domain_size) + 1;
+        if layer_commitments.len() != num_layers {
+            return Err(VerifierError::LayerCountMismatch(
+                num_layers,
+                layer_commitments.len(),
+            ));
+        }
         let mut layer_alphas = Vec::with_capacity(layer_commitments.len());
         let mut max_degree_plus_1 = max_poly_degree + 1;
+        let mut layer_domain_size = domain_size;
         for (depth, commitment) in layer_commitments.iter().enumerate() {
             public_coin.reseed(*commitment);
+            public_coin.reseed_with_int(get_layer_salt(depth, layer_domain_size));
             let alpha = public_coin.draw().map_err(VerifierError::RandomCoinError)?;
             layer_alphas.push(alpha);
 
@@ -132,12 +146,12 @@ where
                 ));
             }
             max_degree_plus_1 /= options.folding_factor();
+            layer_domain_size /= options.folding_factor();
         }
 
         Ok(FriVerifier {
             max_poly_degree,
-            domain_size,
-            domain_generator,
+            domain,
             layer_commitments,
             layer_alphas,
             options,
@@ -161,7 +175,7 @@ where
     /// The domain size can be computed by rounding `max_poly_degree` to the next power of two
     /// and multiplying the result by the `blowup_factor` from the protocol options.
     pub fn domain_size(&self) -> usize {
-        self.domain_size
+        self.domain.size()
     }
 
     /// Returns number of partitions used during FRI proof generation.
@@ -195,12 +209,15 @@ where
     /// Returns an error if:
     /// * The length of `evaluations` is not equal to the length of `positions`.
     /// * An unsupported folding factor was specified by the `options` for this verifier.
+    /// * Merkle authentication paths read from the `channel` are not aggregated across FRI layers
+    ///   as specified by the `options` for this verifier.
     /// * Decommitments to polynomial evaluations don't match the commitment value at any of the
     ///   FRI layers.
     /// * The verifier detects an error in how the degree-respecting projection was applied
     ///   at any of the FRI layers.
     /// * The degree of the remainder at the last FRI layer is greater than the degree implied by
-    ///   `max_poly_degree` reduced by the folding factor at each FRI layer.
+    ///   `max_poly_degree` reduced by the folding factor at each FRI layer, or is greater than
+    ///   the remainder size implied by the protocol options.
     pub fn verify(
         &self,
         channel: &mut C,
@@ -233,61 +250,62 @@ where
         evaluations: &[E],
         positions: &[usize],
     ) -> Result<(), VerifierError> {
-        // pre-compute roots of unity used in computing x coordinates in the folded domain
-        let folding_roots = (0..N)
-            .map(|i| {
-                self.domain_generator
-                    .exp_vartime(((self.domain_size / N * i) as u64).into())
-            })
-            .collect::<Vec<_>>();
-
         // 1 ----- verify the recursive components of the FRI proof -----------------------------------
-        let mut domain_generator = self.domain_generator;
-        let mut domain_size = self.domain_size;
+        let mut domain = self.domain;
         let mut max_degree_plus_1 = self.max_poly_degree + 1;
         let mut positions = positions.to_vec();
         let mut evaluations = evaluations.to_vec();
 
-        for depth in 0..self.options.num_fri_layers(self.domain_size) {
+        let mut layer_multiproof = channel.take_fri_layer_multiproof();
+        if layer_multiproof.is_some() != self.options.aggregates_layer_proofs() {
+            return Err(VerifierError::LayerProofAggregationMismatch);
+        }
+
+        for depth in 0..self.options.num_fri_layers(self.domain.size()) {
             // determine which evaluations were queried in the folded layer
             let mut folded_positions =
-                fold_positions(&positions, domain_size, self.options.folding_factor());
+                fold_positions(&positions, domain.size(), self.options.folding_factor());
             // determine where these evaluations are in the commitment Merkle tree
             let position_indexes = map_positions_to_indexes(
                 &folded_positions,
-                domain_size,
+                domain.size(),
                 self.options.folding_factor(),
                 self.num_partitions,
             );
             // read query values from the specified indexes in the Merkle tree
             let layer_commitment = self.layer_commitments[depth];
             // TODO: add layer depth to the potential error message
-            let layer_values = channel.read_layer_queries(&position_indexes, &layer_commitment)?;
-            let query_values =
-                get_query_values::<E, N>(&layer_values, &positions, &folded_positions, domain_size);
+            let layer_values = match layer_multiproof.as_mut() {
+                Some(multiproof) => channel.read_aggregated_layer_queries(
+                    &position_indexes,
+                    &layer_commitment,
+                    multiproof,
+                )?,
+                None => channel.read_layer_queries(&position_indexes, &layer_commitment)?,
+            };
+            let query_values = get_query_values::<E, N>(
+                &layer_values,
+                &positions,
+                &folded_positions,
+                domain.size(),
+            );
             if evaluations != query_values {
                 return Err(VerifierError::InvalidLayerFolding(depth));
             }
 
-            // build a set of x coordinates for each row polynomial
-            #[rustfmt::skip]
-            let xs = folded_positions.iter().map(|&i| {
-                let xe = domain_generator.exp_vartime((i as u64).into()) * self.options.domain_offset();
-                folding_roots.iter()
-                    .map(|&r| E::from(xe * r))
-                    .collect::<Vec<_>>().try_into().unwrap()
-            })
-            .collect::<Vec<_>>();
-
-            // interpolate x and y values into row polynomials
-            let row_polys = polynom::interpolate_batch(&xs, &layer_values);
+            // compute inverses of x coordinates of the first values in each row
+            let xs = folded_positions
+                .iter()
+                .map(|&i| domain.element(i))
+                .collect::<Vec<_>>();
+            let inv_xs = batch_inversion(&xs);
 
             // calculate the pseudo-random value used for linear combination in layer folding
             let alpha = self.layer_alphas[depth];
 
-            // check that when the polynomials are evaluated at alpha, the result is equal to
-            // the corresponding column value
-            evaluations = row_polys.iter().map(|p| polynom::eval(p, alpha)).collect();
+            // interpolate each row into a polynomial and evaluate it at alpha; the results must
+            // be equal to the corresponding values queried from the next layer
+            evaluations = fold_rows(&layer_values, &inv_xs, alpha);
 
             // make sure next degree reduction does not result in degree truncation
             if max_degree_plus_1 % N != 0 {
@@ -299,29 +317,32 @@ where
             }
 
             // update variables for the next iteration of the loop
-            domain_generator = domain_generator.exp_vartime((N as u32).into());
+            domain = Domain::with_offset(domain.size() / N, domain.offset());
             max_degree_plus_1 /= N;
-            domain_size /= N;
             mem::swap(&mut positions, &mut folded_positions);
         }
 
+        // make sure the multiproof does not contain any nodes which were not needed to verify
+        // layer queries
+        if layer_multiproof.map_or(false, |multiproof| multiproof.has_more_nodes()) {
+            return Err(VerifierError::LayerCommitmentMismatch);
+        }
+
         // 2 ----- verify the remainder polynomial of the FRI proof -------------------------------
 
         // read the remainder polynomial from the channel and make sure it agrees with the evaluations
         // from the previous layer.
+        // the remainder must also respect the remainder size implied by the protocol options
         let remainder_poly = channel.read_remainder()?;
-        if remainder_poly.len() > max_degree_plus_1 {
+        let max_remainder_size =
+            max_degree_plus_1.min(self.options.max_remainder_size(self.domain.size()));
+        if remainder_poly.len() > max_remainder_size {
             return Err(VerifierError::RemainderDegreeMismatch(
-                max_degree_plus_1 - 1,
+                max_remainder_size.saturating_sub(1),
             ));
         }
-        let offset: E::BaseField = self.options().domain_offset();
-
         for (&position, evaluation) in positions.iter().zip(evaluations) {
-            let comp_eval = eval_horner::<E>(
-                &remainder_poly,
-                offset * domain_generator.exp_vartime((position as u64).into()),
-            );
+            let comp_eval = eval_horner::<E>(&remainder_poly, domain.element(position));
             if comp_eval != evaluation {
                 return Err(VerifierError::InvalidRemainderFolding);
             }
@@ -0,0 +1,31 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+//! Writes a valid proof for the fuzzed computation into the corpus directory of each target.
+//!
+//! Run from the `fuzz` directory with `cargo run --example generate_corpus`.
+
+use std::{fs, path::Path};
+use winterfell::Serializable;
+
+fn main() {
+    let (proof, result) = winter_fuzz::build_proof();
+    assert_eq!(winter_fuzz::expected_result(), result);
+    assert!(winter_fuzz::verify_proof(proof.clone(), result).is_ok());
+
+    let proof_bytes = proof.to_bytes();
+    let fri_proof_bytes = proof.fri_proof.to_bytes();
+
+    write_seed("stark_proof_from_bytes", &proof_bytes);
+    write_seed("verify_proof", &proof_bytes);
+    write_seed("fri_proof_from_bytes", &fri_proof_bytes);
+}
+
+fn write_seed(target: &str, bytes: &[u8]) {
+    let dir = Path::new("corpus").join(target);
+    fs::create_dir_all(&dir).expect("failed to create corpus directory");
+    fs::write(dir.join("valid_proof"), bytes).expect("failed to write corpus seed");
+    println!("wrote {} bytes into {}", bytes.len(), dir.display());
+}
@@ -0,0 +1,31 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+#![no_main]
+
+use fri::FriProof;
+use libfuzzer_sys::fuzz_target;
+use winterfell::{
+    crypto::hashers::Blake3_256,
+    math::fields::f128::BaseElement,
+    Deserializable, SliceReader,
+};
+
+fuzz_target!(|data: &[u8]| {
+    let proof = match FriProof::read_from(&mut SliceReader::new(data)) {
+        Ok(proof) => proof,
+        Err(_) => return,
+    };
+
+    // parse the proof against the parameters used by the fuzzed computation (trace length 16,
+    // blowup factor 8, folding factor 4); parsing must never panic
+    let lde_domain_size = winter_fuzz::TRACE_LENGTH * winter_fuzz::proof_options().blowup_factor();
+    let _ = proof.num_partitions();
+    let _ = proof.parse_remainder::<BaseElement>();
+    let _ = proof
+        .clone()
+        .parse_layers::<Blake3_256<BaseElement>, BaseElement>(lde_domain_size, 4);
+    let _ = proof.parse_aggregated_layers::<Blake3_256<BaseElement>, BaseElement>(lde_domain_size, 4);
+});
@@ -0,0 +1,19 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use winterfell::StarkProof;
+
+fuzz_target!(|data: &[u8]| {
+    // deserialization must never panic; if it succeeds, serializing the proof again must
+    // produce a proof which deserializes into the same value
+    if let Ok(proof) = StarkProof::from_bytes(data) {
+        let bytes = proof.to_bytes();
+        let proof2 = StarkProof::from_bytes(&bytes).expect("failed to re-read a valid proof");
+        assert_eq!(bytes, proof2.to_bytes());
+    }
+});
@@ -0,0 +1,17 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use winterfell::StarkProof;
+
+fuzz_target!(|data: &[u8]| {
+    // verification of an arbitrary proof must either succeed or return an error, but must never
+    // panic
+    if let Ok(proof) = StarkProof::from_bytes(data) {
+        let _ = winter_fuzz::verify_proof(proof, winter_fuzz::expected_result());
+    }
+});
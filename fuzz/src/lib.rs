@@ -0,0 +1,158 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+//! Shared components of the fuzzing targets.
+//!
+//! All targets which need a concrete computation use a simple Fibonacci AIR defined in this
+//! module. Proofs for this AIR are generated with fixed parameters so that valid proofs can be
+//! used as corpus seeds (see `examples/generate_corpus.rs`).
+
+use winterfell::{
+    crypto::{hashers::Blake3_256, DefaultRandomCoin},
+    math::{fields::f128::BaseElement, FieldElement},
+    Air, AirContext, Assertion, EvaluationFrame, FieldExtension, ProofOptions, Prover, StarkProof,
+    Trace, TraceInfo, TraceTable, TransitionConstraintDegree, VerifierError,
+};
+
+// CONSTANTS
+// ================================================================================================
+
+/// Number of steps in the execution trace of the fuzzed computation.
+pub const TRACE_LENGTH: usize = 16;
+
+const TRACE_WIDTH: usize = 2;
+
+type HashFn = Blake3_256<BaseElement>;
+
+// PROOF GENERATION AND VERIFICATION
+// ================================================================================================
+
+/// Returns proof options used by all fuzzing targets.
+pub fn proof_options() -> ProofOptions {
+    ProofOptions::new(28, 8, 0, FieldExtension::None, 4, 7)
+}
+
+/// Generates a valid proof for the Fibonacci computation, returning the proof together with
+/// the public inputs against which it should be verified.
+pub fn build_proof() -> (StarkProof, BaseElement) {
+    let prover = FibProver::new(proof_options());
+    let trace = prover.build_trace();
+    let result = prover.get_pub_inputs(&trace);
+    let proof = prover.prove(trace).expect("failed to generate a proof");
+    (proof, result)
+}
+
+/// Returns the result of the Fibonacci computation for which proofs are generated.
+pub fn expected_result() -> BaseElement {
+    let mut state = [BaseElement::ONE, BaseElement::ONE];
+    for _ in 0..TRACE_LENGTH - 1 {
+        state[0] += state[1];
+        state[1] += state[0];
+    }
+    state[1]
+}
+
+/// Verifies the provided proof against the specified result of the Fibonacci computation.
+pub fn verify_proof(proof: StarkProof, result: BaseElement) -> Result<(), VerifierError> {
+    winterfell::verify::<FibAir, HashFn, DefaultRandomCoin<HashFn>>(proof, result)
+}
+
+// FIBONACCI AIR
+// ================================================================================================
+
+pub struct FibAir {
+    context: AirContext<BaseElement>,
+    result: BaseElement,
+}
+
+impl Air for FibAir {
+    type BaseField = BaseElement;
+    type PublicInputs = BaseElement;
+
+    fn new(trace_info: TraceInfo, pub_inputs: BaseElement, options: ProofOptions) -> Self {
+        let degrees = vec![
+            TransitionConstraintDegree::new(1),
+            TransitionConstraintDegree::new(1),
+        ];
+        FibAir {
+            context: AirContext::new(trace_info, degrees, 3, options),
+            result: pub_inputs,
+        }
+    }
+
+    fn context(&self) -> &AirContext<Self::BaseField> {
+        &self.context
+    }
+
+    fn evaluate_transition<E: FieldElement + From<Self::BaseField>>(
+        &self,
+        frame: &EvaluationFrame<E>,
+        _periodic_values: &[E],
+        result: &mut [E],
+    ) {
+        // a malformed proof may declare a trace of a different width
+        let current = frame.current();
+        let next = frame.next();
+        if current.len() != TRACE_WIDTH || result.len() != TRACE_WIDTH {
+            return;
+        }
+
+        result[0] = next[0] - (current[0] + current[1]);
+        result[1] = next[1] - (current[1] + next[0]);
+    }
+
+    fn get_assertions(&self) -> Vec<Assertion<Self::BaseField>> {
+        let last_step = self.trace_length() - 1;
+        vec![
+            Assertion::single(0, 0, Self::BaseField::ONE),
+            Assertion::single(1, 0, Self::BaseField::ONE),
+            Assertion::single(1, last_step, self.result),
+        ]
+    }
+}
+
+// FIBONACCI PROVER
+// ================================================================================================
+
+struct FibProver {
+    options: ProofOptions,
+}
+
+impl FibProver {
+    fn new(options: ProofOptions) -> Self {
+        Self { options }
+    }
+
+    fn build_trace(&self) -> TraceTable<BaseElement> {
+        let mut trace = TraceTable::new(TRACE_WIDTH, TRACE_LENGTH);
+        trace.fill(
+            |state| {
+                state[0] = BaseElement::ONE;
+                state[1] = BaseElement::ONE;
+            },
+            |_, state| {
+                state[0] += state[1];
+                state[1] += state[0];
+            },
+        );
+        trace
+    }
+}
+
+impl Prover for FibProver {
+    type BaseField = BaseElement;
+    type Air = FibAir;
+    type Trace = TraceTable<BaseElement>;
+    type HashFn = HashFn;
+    type RandomCoin = DefaultRandomCoin<HashFn>;
+
+    fn get_pub_inputs(&self, trace: &Self::Trace) -> BaseElement {
+        trace.get(1, trace.length() - 1)
+    }
+
+    fn options(&self) -> &ProofOptions {
+        &self.options
+    }
+}
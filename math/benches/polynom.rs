@@ -38,5 +38,22 @@ fn syn_div(c: &mut Criterion) {
     group.finish();
 }
 
-criterion_group!(polynom_group, syn_div);
+fn mul(c: &mut Criterion) {
+    let mut group = c.benchmark_group("mul");
+    group.sample_size(10);
+    group.measurement_time(Duration::from_secs(10));
+
+    for &size in [1_024, 4_096].iter() {
+        let p1: Vec<BaseElement> = rand_vector(size);
+        let p2: Vec<BaseElement> = rand_vector(size);
+
+        group.bench_function(BenchmarkId::new("fft", size), |bench| {
+            bench.iter(|| polynom::mul_fft(&p1, &p2));
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(polynom_group, syn_div, mul);
 criterion_main!(polynom_group);
@@ -0,0 +1,229 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+//! Evaluation domains defined by multiplicative subgroups of STARK fields and their cosets.
+
+use crate::{fft, field::StarkField, utils::get_power_series_with_offset, FieldElement};
+use utils::collections::Vec;
+
+// DOMAIN
+// ================================================================================================
+
+/// A multiplicative subgroup of a STARK field, optionally shifted by an offset.
+///
+/// A domain of size `n` with generator `g` and offset `s` consists of elements
+/// `s * g^0, s * g^1, ..., s * g^(n - 1)`, where `g` is the root of unity of order `n` returned
+/// by [StarkField::get_root_of_unity()]. When the offset is ONE, the domain is the subgroup
+/// itself; otherwise, the domain is a coset of the subgroup.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Domain<B: StarkField> {
+    size: usize,
+    generator: B,
+    offset: B,
+}
+
+impl<B: StarkField> Domain<B> {
+    // CONSTRUCTORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the multiplicative subgroup of the specified size.
+    ///
+    /// # Panics
+    /// Panics if `size` is not a power of two, or if the field does not contain a multiplicative
+    /// subgroup of this size.
+    pub fn new(size: usize) -> Self {
+        Self::with_offset(size, B::ONE)
+    }
+
+    /// Returns the coset of the multiplicative subgroup of the specified size shifted by the
+    /// specified `offset`.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * `size` is not a power of two.
+    /// * The field does not contain a multiplicative subgroup of size `size`.
+    /// * `offset` is ZERO.
+    pub fn with_offset(size: usize, offset: B) -> Self {
+        assert!(
+            size.is_power_of_two(),
+            "domain size must be a power of two, but was {size}"
+        );
+        assert!(
+            size.ilog2() <= B::TWO_ADICITY,
+            "multiplicative subgroup of size {size} does not exist in the specified base field"
+        );
+        assert_ne!(offset, B::ZERO, "domain offset cannot be zero");
+        // the subgroup of size 1 consists of ONE only
+        let generator = match size {
+            1 => B::ONE,
+            _ => B::get_root_of_unity(size.ilog2()),
+        };
+        Domain {
+            size,
+            generator,
+            offset,
+        }
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the number of elements in this domain.
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Returns the generator of the multiplicative subgroup underlying this domain.
+    pub fn generator(&self) -> B {
+        self.generator
+    }
+
+    /// Returns the offset by which the multiplicative subgroup underlying this domain is shifted.
+    pub fn offset(&self) -> B {
+        self.offset
+    }
+
+    /// Returns the element of this domain at the specified index; indexes wrap around the size
+    /// of the domain.
+    ///
+    /// Elements of a domain are public, and thus, the element is computed via variable-time
+    /// exponentiation.
+    pub fn element(&self, index: usize) -> B {
+        let index = (index % self.size) as u64;
+        self.offset * self.generator.exp_vartime(index.into())
+    }
+
+    /// Returns an iterator over the elements of this domain in index order.
+    pub fn iter(&self) -> impl Iterator<Item = B> {
+        let generator = self.generator;
+        let mut element = self.offset;
+        (0..self.size).map(move |_| {
+            let result = element;
+            element *= generator;
+            result
+        })
+    }
+
+    /// Returns a vector containing all elements of this domain in index order.
+    ///
+    /// When `concurrent` feature is enabled, the elements are computed in multiple threads.
+    pub fn elements(&self) -> Vec<B> {
+        get_power_series_with_offset(self.generator, self.offset, self.size)
+    }
+
+    // POLYNOMIALS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns coefficients of the vanishing polynomial of this domain.
+    ///
+    /// The vanishing polynomial of a domain of size `n` with offset `s` is `x^n - s^n`; it
+    /// evaluates to ZERO at all elements of the domain.
+    pub fn vanishing_poly(&self) -> Vec<B> {
+        let mut result = vec![B::ZERO; self.size + 1];
+        result[0] = -self.offset.exp((self.size as u64).into());
+        result[self.size] = B::ONE;
+        result
+    }
+
+    /// Returns the evaluation of the vanishing polynomial of this domain at `x`.
+    pub fn evaluate_vanishing_poly<E>(&self, x: E) -> E
+    where
+        E: FieldElement<BaseField = B>,
+    {
+        let n = self.size as u64;
+        x.exp(n.into()) - E::from(self.offset.exp(n.into()))
+    }
+
+    /// Returns coefficients of the polynomial of degree smaller than the size of this domain
+    /// which evaluates to the specified `values` at the elements of this domain.
+    ///
+    /// # Panics
+    /// Panics if the number of `values` is not equal to the size of this domain.
+    pub fn interpolate<E>(&self, values: &[E]) -> Vec<E>
+    where
+        E: FieldElement<BaseField = B>,
+    {
+        assert_eq!(
+            self.size,
+            values.len(),
+            "number of values must be equal to domain size {}, but was {}",
+            self.size,
+            values.len()
+        );
+        let mut result = values.to_vec();
+        if self.size > 1 {
+            let inv_twiddles = fft::get_inv_twiddles::<B>(self.size);
+            fft::interpolate_poly_with_offset(&mut result, &inv_twiddles, self.offset);
+        }
+        result
+    }
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::Domain;
+    use crate::{fields::f128::BaseElement, get_power_series, polynom, FieldElement, StarkField};
+    use rand_utils::rand_vector;
+
+    #[test]
+    fn domain_elements() {
+        let domain = Domain::<BaseElement>::new(16);
+        let g = BaseElement::get_root_of_unity(4);
+        assert_eq!(g, domain.generator());
+        assert_eq!(BaseElement::ONE, domain.offset());
+        assert_eq!(get_power_series(g, 16), domain.elements());
+        assert_eq!(domain.elements(), domain.iter().collect::<Vec<_>>());
+
+        let offset = BaseElement::GENERATOR;
+        let coset = Domain::with_offset(16, offset);
+        let expected = get_power_series(g, 16)
+            .into_iter()
+            .map(|x| x * offset)
+            .collect::<Vec<_>>();
+        assert_eq!(expected, coset.elements());
+        assert_eq!(expected, coset.iter().collect::<Vec<_>>());
+        for (i, &x) in expected.iter().enumerate() {
+            assert_eq!(x, coset.element(i));
+            assert_eq!(x, coset.element(i + 16));
+        }
+    }
+
+    #[test]
+    fn domain_vanishing_poly() {
+        let domain = Domain::with_offset(8, BaseElement::GENERATOR);
+        let poly = domain.vanishing_poly();
+        for x in domain.iter() {
+            assert_eq!(BaseElement::ZERO, polynom::eval(&poly, x));
+            assert_eq!(BaseElement::ZERO, domain.evaluate_vanishing_poly(x));
+        }
+
+        let x = BaseElement::new(42);
+        assert_ne!(BaseElement::ZERO, domain.evaluate_vanishing_poly(x));
+        assert_eq!(polynom::eval(&poly, x), domain.evaluate_vanishing_poly(x));
+    }
+
+    #[test]
+    fn domain_interpolate() {
+        let domain = Domain::with_offset(32, BaseElement::GENERATOR);
+        let poly: Vec<BaseElement> = rand_vector(32);
+        let values = polynom::eval_many(&poly, &domain.elements());
+        assert_eq!(poly, domain.interpolate(&values));
+
+        let domain = Domain::<BaseElement>::new(1);
+        assert_eq!(
+            vec![BaseElement::new(7)],
+            domain.interpolate(&[BaseElement::new(7)])
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "domain size must be a power of two, but was 12")]
+    fn domain_size_not_power_of_two() {
+        Domain::<BaseElement>::new(12);
+    }
+}
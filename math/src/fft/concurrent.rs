@@ -81,8 +81,8 @@ where
         .for_each(|(i, batch)| {
             let mut offset = domain_offset.exp(((i * batch_size) as u64).into()) * inv_len;
             for coeff in batch.iter_mut() {
-                *coeff = *coeff * offset;
-                offset = offset * domain_offset;
+                *coeff *= offset;
+                offset *= domain_offset;
             }
         });
 }
@@ -139,7 +139,7 @@ pub(super) fn split_radix_fft<B: StarkField, E: FieldElement<BaseField = B>>(
     // apply inner FFTs
     values
         .par_chunks_mut(outer_len)
-        .for_each(|row| row.fft_in_place_raw(&twiddles, stretch, stretch, 0));
+        .for_each(|row| row.fft_in_place_raw(twiddles, stretch, stretch, 0));
 
     // transpose inner x inner x stretch square matrix
     transpose_square_stretch(values, inner_len, stretch);
@@ -155,10 +155,10 @@ pub(super) fn split_radix_fft<B: StarkField, E: FieldElement<BaseField = B>>(
                 let mut outer_twiddle = inner_twiddle;
                 for element in row.iter_mut().skip(1) {
                     *element = (*element).mul_base(outer_twiddle);
-                    outer_twiddle = outer_twiddle * inner_twiddle;
+                    outer_twiddle *= inner_twiddle;
                 }
             }
-            row.fft_in_place(&twiddles);
+            row.fft_in_place(twiddles);
         });
 }
 
@@ -222,7 +222,7 @@ fn clone_and_shift<E: FieldElement>(source: &[E], destination: &mut [E], offset:
             let mut factor = offset.exp(((i * batch_size) as u64).into());
             for (s, d) in source.iter().zip(destination.iter_mut()) {
                 *d = (*s).mul_base(factor);
-                factor = factor * offset;
+                factor *= offset;
             }
         });
 }
@@ -47,11 +47,19 @@ impl<B: ExtensibleField<3>> CubeExtension<B> {
             "source vector length must be divisible by three, but was {}",
             source.len()
         );
-        let mut v = core::mem::ManuallyDrop::new(source);
-        let p = v.as_mut_ptr();
-        let len = v.len() / Self::EXTENSION_DEGREE;
-        let cap = v.capacity() / Self::EXTENSION_DEGREE;
-        unsafe { Vec::from_raw_parts(p as *mut Self, len, cap) }
+        #[cfg(not(feature = "forbid-unsafe-bytes"))]
+        {
+            let mut v = core::mem::ManuallyDrop::new(source);
+            let p = v.as_mut_ptr();
+            let len = v.len() / Self::EXTENSION_DEGREE;
+            let cap = v.capacity() / Self::EXTENSION_DEGREE;
+            unsafe { Vec::from_raw_parts(p as *mut Self, len, cap) }
+        }
+        #[cfg(feature = "forbid-unsafe-bytes")]
+        source
+            .chunks(Self::EXTENSION_DEGREE)
+            .map(|c| Self::new(c[0], c[1], c[2]))
+            .collect()
     }
 
     /// Returns an array of base field elements comprising this extension field element.
@@ -331,7 +339,7 @@ impl<B: ExtensibleField<3>> From<u8> for CubeExtension<B> {
     }
 }
 
-impl<'a, B: ExtensibleField<3>> TryFrom<&'a [u8]> for CubeExtension<B> {
+impl<B: ExtensibleField<3>> TryFrom<&[u8]> for CubeExtension<B> {
     type Error = DeserializationError;
 
     /// Converts a slice of bytes into a field element; returns error if the value encoded in bytes
@@ -47,11 +47,19 @@ impl<B: ExtensibleField<2>> QuadExtension<B> {
             "source vector length must be divisible by two, but was {}",
             source.len()
         );
-        let mut v = core::mem::ManuallyDrop::new(source);
-        let p = v.as_mut_ptr();
-        let len = v.len() / Self::EXTENSION_DEGREE;
-        let cap = v.capacity() / Self::EXTENSION_DEGREE;
-        unsafe { Vec::from_raw_parts(p as *mut Self, len, cap) }
+        #[cfg(not(feature = "forbid-unsafe-bytes"))]
+        {
+            let mut v = core::mem::ManuallyDrop::new(source);
+            let p = v.as_mut_ptr();
+            let len = v.len() / Self::EXTENSION_DEGREE;
+            let cap = v.capacity() / Self::EXTENSION_DEGREE;
+            unsafe { Vec::from_raw_parts(p as *mut Self, len, cap) }
+        }
+        #[cfg(feature = "forbid-unsafe-bytes")]
+        source
+            .chunks(Self::EXTENSION_DEGREE)
+            .map(|c| Self::new(c[0], c[1]))
+            .collect()
     }
 
     /// Returns an array of base field elements comprising this extension field element.
@@ -321,7 +329,7 @@ impl<B: ExtensibleField<2>> From<u8> for QuadExtension<B> {
     }
 }
 
-impl<'a, B: ExtensibleField<2>> TryFrom<&'a [u8]> for QuadExtension<B> {
+impl<B: ExtensibleField<2>> TryFrom<&[u8]> for QuadExtension<B> {
     type Error = DeserializationError;
 
     /// Converts a slice of bytes into a field element; returns error if the value encoded in bytes
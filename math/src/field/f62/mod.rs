@@ -170,18 +170,23 @@ impl FieldElement for BaseElement {
     // --------------------------------------------------------------------------------------------
 
     fn zeroed_vector(n: usize) -> Vec<Self> {
-        // this uses a specialized vector initialization code which requests zero-filled memory
-        // from the OS; unfortunately, this works only for built-in types and we can't use
-        // Self::ZERO here as much less efficient initialization procedure will be invoked.
-        // We also use u64 to make sure the memory is aligned correctly for our element size.
-        let result = vec![0u64; n];
-
-        // translate a zero-filled vector of u64s into a vector of base field elements
-        let mut v = core::mem::ManuallyDrop::new(result);
-        let p = v.as_mut_ptr();
-        let len = v.len();
-        let cap = v.capacity();
-        unsafe { Vec::from_raw_parts(p as *mut Self, len, cap) }
+        #[cfg(not(feature = "forbid-unsafe-bytes"))]
+        {
+            // this uses a specialized vector initialization code which requests zero-filled memory
+            // from the OS; unfortunately, this works only for built-in types and we can't use
+            // Self::ZERO here as much less efficient initialization procedure will be invoked.
+            // We also use u64 to make sure the memory is aligned correctly for our element size.
+            let result = vec![0u64; n];
+
+            // translate a zero-filled vector of u64s into a vector of base field elements
+            let mut v = core::mem::ManuallyDrop::new(result);
+            let p = v.as_mut_ptr();
+            let len = v.len();
+            let cap = v.capacity();
+            unsafe { Vec::from_raw_parts(p as *mut Self, len, cap) }
+        }
+        #[cfg(feature = "forbid-unsafe-bytes")]
+        vec![Self::ZERO; n]
     }
 }
 
@@ -457,7 +462,7 @@ impl From<[u8; 8]> for BaseElement {
     }
 }
 
-impl<'a> TryFrom<&'a [u8]> for BaseElement {
+impl TryFrom<&[u8]> for BaseElement {
     type Error = DeserializationError;
 
     /// Converts a slice of bytes into a field element; returns error if the value encoded in bytes
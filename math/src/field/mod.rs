@@ -6,9 +6,15 @@
 mod traits;
 pub use traits::{ExtensibleField, ExtensionOf, FieldElement, StarkField, ToElements};
 
+mod roots;
+pub use roots::StarkFieldExt;
+
 pub mod f128;
 pub mod f62;
 pub mod f64;
 
 mod extensions;
 pub use extensions::{CubeExtension, QuadExtension};
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,116 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::StarkField;
+
+// STARK FIELD EXTENSION TRAIT
+// ================================================================================================
+
+/// Defines square roots, quadratic residuosity, and roots of unity of arbitrary small order for
+/// elements of a [StarkField].
+///
+/// This trait is implemented for all STARK fields. The implementation assumes that
+/// [StarkField::TWO_ADICITY] is the full 2-adicity of `MODULUS - 1` (i.e., `MODULUS - 1` divided by
+/// 2^`TWO_ADICITY` is odd), which holds for all fields defined in this crate.
+pub trait StarkFieldExt: StarkField {
+    /// Returns the Legendre symbol of this element; that is:
+    /// * 0 if this element is zero.
+    /// * 1 if this element is a non-zero quadratic residue.
+    /// * -1 if this element is a quadratic non-residue.
+    fn legendre(&self) -> i8 {
+        if *self == Self::ZERO {
+            return 0;
+        }
+        // since MODULUS is odd, (MODULUS - 1) / 2 is the same as MODULUS >> 1
+        if self.exp(Self::MODULUS >> 1) == Self::ONE {
+            1
+        } else {
+            -1
+        }
+    }
+
+    /// Returns true if this element is a square in the field; zero is considered a square.
+    fn is_square(&self) -> bool {
+        self.legendre() >= 0
+    }
+
+    /// Returns a square root of this element, or None if this element is not a square.
+    ///
+    /// The root is computed using the Tonelli-Shanks algorithm; the other square root of this
+    /// element is the negation of the returned value.
+    fn sqrt(&self) -> Option<Self> {
+        match self.legendre() {
+            0 => return Some(Self::ZERO),
+            -1 => return None,
+            _ => (),
+        }
+
+        // MODULUS - 1 = q * 2^s with q odd; since s > 0, q is the same as MODULUS >> s
+        let q = Self::MODULUS >> Self::TWO_ADICITY;
+        let mut m = Self::TWO_ADICITY;
+        let mut c = Self::TWO_ADIC_ROOT_OF_UNITY;
+        let mut t = self.exp(q);
+        // r = self^((q + 1) / 2); since q is odd, (q + 1) / 2 = (q >> 1) + 1
+        let mut r = self.exp(q >> 1) * *self;
+
+        while t != Self::ONE {
+            // find the least i such that t^(2^i) = 1; since t is in a subgroup of order 2^m,
+            // i is guaranteed to be smaller than m
+            let mut i = 0;
+            let mut t_pow = t;
+            while t_pow != Self::ONE {
+                t_pow = t_pow.square();
+                i += 1;
+            }
+
+            let mut b = c;
+            for _ in 0..(m - i - 1) {
+                b = b.square();
+            }
+
+            m = i;
+            c = b.square();
+            t *= c;
+            r *= b;
+        }
+
+        Some(r)
+    }
+
+    /// Returns a primitive root of unity of the specified `order`, or None if the multiplicative
+    /// group of the field does not contain a subgroup of this order.
+    ///
+    /// Unlike [StarkField::get_root_of_unity()], `order` does not need to be a power of two.
+    ///
+    /// # Panics
+    /// Panics if the field modulus does not fit into 128 bits.
+    fn get_root_of_order(order: u64) -> Option<Self> {
+        let modulus_bytes = Self::get_modulus_le_bytes();
+        assert!(
+            modulus_bytes.len() <= 16,
+            "field modulus cannot exceed 128 bits"
+        );
+        let mut buf = [0u8; 16];
+        buf[..modulus_bytes.len()].copy_from_slice(&modulus_bytes);
+        let group_order = u128::from_le_bytes(buf) - 1;
+
+        if order == 0 || group_order % order as u128 != 0 {
+            return None;
+        }
+
+        // GENERATOR generates the entire multiplicative group, and thus, raising it to the power
+        // (MODULUS - 1) / order yields an element of order exactly `order`
+        let power = group_order / order as u128;
+        let high = Self::GENERATOR.exp(((power >> 64) as u64).into());
+        let low = Self::GENERATOR.exp((power as u64).into());
+        let mut result = high;
+        for _ in 0..64 {
+            result = result.square();
+        }
+        Some(result * low)
+    }
+}
+
+impl<B: StarkField> StarkFieldExt for B {}
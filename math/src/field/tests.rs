@@ -0,0 +1,213 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+//! Property-based tests of field axioms which are run against every field implementation (and
+//! every supported field extension) in this crate.
+
+use super::{f128, f62, f64, CubeExtension, QuadExtension};
+use proptest::prelude::*;
+
+// FIELD AXIOMS
+// ================================================================================================
+
+/// Generates property tests checking field axioms for the element type `$elem`. Random elements
+/// are drawn using the `$strategy` function.
+macro_rules! field_axioms {
+    ($name:ident, $elem:ty, $strategy:ident) => {
+        mod $name {
+            use super::*;
+            use crate::FieldElement;
+            use utils::{Deserializable, Serializable, SliceReader};
+
+            proptest! {
+                #[test]
+                fn add_associative(a in $strategy(), b in $strategy(), c in $strategy()) {
+                    prop_assert_eq!((a + b) + c, a + (b + c));
+                }
+
+                #[test]
+                fn add_commutative(a in $strategy(), b in $strategy()) {
+                    prop_assert_eq!(a + b, b + a);
+                }
+
+                #[test]
+                fn add_identity_and_inverse(a in $strategy(), b in $strategy()) {
+                    prop_assert_eq!(a, a + <$elem>::ZERO);
+                    prop_assert_eq!(<$elem>::ZERO, a + (-a));
+                    prop_assert_eq!(a - b, a + (-b));
+                    prop_assert_eq!(a + a, a.double());
+                }
+
+                #[test]
+                fn mul_associative(a in $strategy(), b in $strategy(), c in $strategy()) {
+                    prop_assert_eq!((a * b) * c, a * (b * c));
+                }
+
+                #[test]
+                fn mul_commutative(a in $strategy(), b in $strategy()) {
+                    prop_assert_eq!(a * b, b * a);
+                }
+
+                #[test]
+                fn mul_identity_and_inverse(a in $strategy(), b in $strategy()) {
+                    prop_assert_eq!(a, a * <$elem>::ONE);
+                    prop_assert_eq!(<$elem>::ZERO, a * <$elem>::ZERO);
+                    if a != <$elem>::ZERO {
+                        prop_assert_eq!(<$elem>::ONE, a * a.inv());
+                        prop_assert_eq!(b, (b / a) * a);
+                    }
+                    prop_assert_eq!(a * a, a.square());
+                    prop_assert_eq!(a * a * a, a.cube());
+                }
+
+                #[test]
+                fn distributive(a in $strategy(), b in $strategy(), c in $strategy()) {
+                    prop_assert_eq!(a * (b + c), a * b + a * c);
+                    prop_assert_eq!((a - b) * c, a * c - b * c);
+                }
+
+                #[test]
+                fn exp_additive(a in $strategy(), m in any::<u16>(), n in any::<u16>()) {
+                    let (m, n) = (m as u32, n as u32);
+                    prop_assert_eq!(a.exp((m + n).into()), a.exp(m.into()) * a.exp(n.into()));
+                    prop_assert_eq!(a.exp(m.into()), a.exp_vartime(m.into()));
+                }
+
+                #[test]
+                fn serialization_round_trip(a in $strategy()) {
+                    let bytes = a.to_bytes();
+                    let mut reader = SliceReader::new(&bytes);
+                    prop_assert_eq!(a, <$elem>::read_from(&mut reader).unwrap());
+                }
+            }
+        }
+    };
+}
+
+field_axioms!(f62_base, f62::BaseElement, f62_element);
+field_axioms!(f62_quad, QuadExtension<f62::BaseElement>, f62_quad_element);
+field_axioms!(f62_cube, CubeExtension<f62::BaseElement>, f62_cube_element);
+
+field_axioms!(f64_base, f64::BaseElement, f64_element);
+field_axioms!(f64_quad, QuadExtension<f64::BaseElement>, f64_quad_element);
+field_axioms!(f64_cube, CubeExtension<f64::BaseElement>, f64_cube_element);
+
+field_axioms!(f128_base, f128::BaseElement, f128_element);
+field_axioms!(
+    f128_quad,
+    QuadExtension<f128::BaseElement>,
+    f128_quad_element
+);
+
+// SQUARE ROOTS AND ROOTS OF UNITY
+// ================================================================================================
+
+/// Generates property tests for [StarkFieldExt](crate::StarkFieldExt) methods of the prime field
+/// element type `$elem`. Random elements are drawn using the `$strategy` function.
+macro_rules! field_roots {
+    ($name:ident, $elem:ty, $strategy:ident) => {
+        mod $name {
+            use super::*;
+            use crate::{FieldElement, StarkField, StarkFieldExt};
+
+            proptest! {
+                #[test]
+                fn sqrt_of_square(a in $strategy()) {
+                    let square = a.square();
+                    prop_assert!(square.is_square());
+                    let root = square.sqrt().unwrap();
+                    prop_assert!(root == a || root == -a);
+                }
+
+                #[test]
+                fn legendre_multiplicative(a in $strategy(), b in $strategy()) {
+                    prop_assert_eq!((a * b).legendre(), a.legendre() * b.legendre());
+                }
+
+                #[test]
+                fn sqrt_matches_legendre(a in $strategy()) {
+                    match a.sqrt() {
+                        Some(root) => prop_assert_eq!(a, root.square()),
+                        None => prop_assert_eq!(-1, a.legendre()),
+                    }
+                }
+            }
+
+            #[test]
+            fn non_residue() {
+                // a generator of the multiplicative group is never a square
+                assert_eq!(-1, <$elem>::GENERATOR.legendre());
+                assert_eq!(None, <$elem>::GENERATOR.sqrt());
+                assert_eq!(Some(<$elem>::ZERO), <$elem>::ZERO.sqrt());
+            }
+
+            #[test]
+            fn roots_of_two_power_order() {
+                for n in 1..=<$elem>::TWO_ADICITY.min(16) {
+                    let root = <$elem>::get_root_of_order(1 << n).unwrap();
+                    assert_eq!(<$elem>::ONE, root.exp((1u64 << n).into()));
+                    assert_ne!(<$elem>::ONE, root.exp((1u64 << (n - 1)).into()));
+                }
+                assert_eq!(None, <$elem>::get_root_of_order(0));
+            }
+        }
+    };
+}
+
+field_roots!(f62_roots, f62::BaseElement, f62_element);
+field_roots!(f64_roots, f64::BaseElement, f64_element);
+field_roots!(f128_roots, f128::BaseElement, f128_element);
+
+#[test]
+fn f64_roots_of_odd_order() {
+    use crate::{FieldElement, StarkFieldExt};
+
+    // MODULUS - 1 = 2^32 * 3 * 5 * 17 * 257 * 65537
+    for order in [3u64, 5, 15, 17, 257, 65537, 3 << 32] {
+        let root = f64::BaseElement::get_root_of_order(order).unwrap();
+        assert_eq!(f64::BaseElement::ONE, root.exp(order));
+        for prime in [2u64, 3, 5, 17, 257, 65537] {
+            if order % prime == 0 {
+                assert_ne!(f64::BaseElement::ONE, root.exp(order / prime));
+            }
+        }
+    }
+    assert_eq!(None, f64::BaseElement::get_root_of_order(7));
+}
+
+// STRATEGIES
+// ================================================================================================
+
+fn f62_element() -> impl Strategy<Value = f62::BaseElement> {
+    any::<u64>().prop_map(f62::BaseElement::from)
+}
+
+fn f62_quad_element() -> impl Strategy<Value = QuadExtension<f62::BaseElement>> {
+    (f62_element(), f62_element()).prop_map(|(a, b)| QuadExtension::new(a, b))
+}
+
+fn f62_cube_element() -> impl Strategy<Value = CubeExtension<f62::BaseElement>> {
+    (f62_element(), f62_element(), f62_element()).prop_map(|(a, b, c)| CubeExtension::new(a, b, c))
+}
+
+fn f64_element() -> impl Strategy<Value = f64::BaseElement> {
+    any::<u64>().prop_map(f64::BaseElement::from)
+}
+
+fn f64_quad_element() -> impl Strategy<Value = QuadExtension<f64::BaseElement>> {
+    (f64_element(), f64_element()).prop_map(|(a, b)| QuadExtension::new(a, b))
+}
+
+fn f64_cube_element() -> impl Strategy<Value = CubeExtension<f64::BaseElement>> {
+    (f64_element(), f64_element(), f64_element()).prop_map(|(a, b, c)| CubeExtension::new(a, b, c))
+}
+
+fn f128_element() -> impl Strategy<Value = f128::BaseElement> {
+    any::<u128>().prop_map(f128::BaseElement::from)
+}
+
+fn f128_quad_element() -> impl Strategy<Value = QuadExtension<f128::BaseElement>> {
+    (f128_element(), f128_element()).prop_map(|(a, b)| QuadExtension::new(a, b))
+}
@@ -15,6 +15,7 @@
 //! * Basic arithmetic operations: addition, multiplication, subtraction, division, inversion.
 //! * Drawing random and pseudo-random elements from the field.
 //! * Computing roots of unity of a given order.
+//! * Computing square roots and Legendre symbols of field elements (see [StarkFieldExt]).
 //!
 //! Currently, there are two implementations of finite fields:
 //!
@@ -65,6 +66,11 @@
 //! This can be used to interpolate and evaluate polynomials in *O(n log n)* time as long as
 //! the domain of the polynomial is a multiplicative subgroup with size which is a power of 2.
 //!
+//! # Domains
+//! [Domain](domain::Domain) type describes a multiplicative subgroup of a STARK field (or a coset
+//! of such a subgroup), and provides methods for enumerating its elements, computing its
+//! vanishing polynomial, and interpolating polynomials over it.
+//!
 //! # Concurrent execution
 //!
 //! When the crate is compiled with `concurrent` feature enabled, some operations will be
@@ -77,6 +83,9 @@
 //!   - [add_in_place()]
 //!   - [mul_acc()]
 //!   - [batch_inversion()]
+//! * `domain` module:
+//!   - [Domain::elements()](domain::Domain::elements())
+//!   - [Domain::interpolate()](domain::Domain::interpolate())
 //! * `fft` module:
 //!   - [evaluate_poly()](fft::evaluate_poly())
 //!   - [evaluate_poly_with_offset()](fft::evaluate_poly_with_offset())
@@ -93,11 +102,14 @@
 #[macro_use]
 extern crate alloc;
 
+pub mod domain;
 pub mod fft;
 pub mod polynom;
 
 mod field;
-pub use field::{ExtensibleField, ExtensionOf, FieldElement, StarkField, ToElements};
+pub use field::{
+    ExtensibleField, ExtensionOf, FieldElement, StarkField, StarkFieldExt, ToElements,
+};
 pub mod fields {
     //! Finite field implementations.
     //!
@@ -8,9 +8,12 @@
 //! This module provides a set of function for basic polynomial operations, including:
 //! - Polynomial evaluation using Horner method.
 //! - Polynomial interpolation using Lagrange method.
-//! - Polynomial addition, subtraction, multiplication, and division.
+//! - Polynomial addition, subtraction, multiplication, and division; for large polynomials
+//!   multiplication and division are performed using FFT.
 //! - Synthetic polynomial division for efficient division by polynomials of the form
 //!   `x`^`a` - `b`.
+//! - Vanishing polynomials for multiplicative subgroups and their cosets, optionally excluding
+//!   a set of points (see [VanishingPoly]).
 //!
 //! In the context of this module any slice of field elements is considered to be a polynomial
 //! in reverse coefficient form. A few examples:
@@ -24,13 +27,27 @@
 //! let p = [BaseElement::new(3), BaseElement::ZERO, BaseElement::new(4)];
 //! ```
 
-use crate::{field::FieldElement, utils::batch_inversion};
+use crate::{
+    fft,
+    field::{FieldElement, StarkField},
+    utils::batch_inversion,
+};
 use core::mem;
 use utils::{collections::Vec, group_vector_elements};
 
+mod vanishing;
+pub use vanishing::VanishingPoly;
+
 #[cfg(test)]
 mod tests;
 
+// CONSTANTS
+// ================================================================================================
+
+/// Minimum length of both operands for which polynomial multiplication and division are
+/// performed using FFT; for smaller polynomials the quadratic algorithms are faster.
+const MIN_FFT_LENGTH: usize = 64;
+
 // POLYNOMIAL EVALUATION
 // ================================================================================================
 
@@ -305,6 +322,10 @@ where
 /// polynomial will be in the coefficient form as well. The length of the returned vector
 /// will be a.len() + b.len() - 1.
 ///
+/// When both polynomials are large and the base field contains a multiplicative subgroup large
+/// enough to hold the product, the multiplication is performed using FFT (see [mul_fft()]);
+/// otherwise, the quadratic schoolbook algorithm is used.
+///
 /// # Examples
 /// ```
 /// # use winter_math::polynom::*;
@@ -328,6 +349,10 @@ where
     E: FieldElement,
 {
     let result_len = a.len() + b.len() - 1;
+    if a.len().min(b.len()) >= MIN_FFT_LENGTH && is_fft_supported::<E>(result_len) {
+        return mul_fft(a, b);
+    }
+
     let mut result = E::zeroed_vector(result_len);
     for i in 0..a.len() {
         for j in 0..b.len() {
@@ -338,6 +363,66 @@ where
     result
 }
 
+/// Returns a polynomial resulting from multiplying two polynomials together using FFT.
+///
+/// Both polynomials are evaluated over a domain large enough to hold their product, the
+/// evaluations are multiplied point-wise, and the result is interpolated back into coefficient
+/// form. The runtime complexity is O(`n` log `n`), where `n` is the length of the result.
+/// Polynomials `a` and `b` are expected to be in the coefficient form, and the returned
+/// polynomial will be in the coefficient form as well. The length of the returned vector will be
+/// a.len() + b.len() - 1.
+///
+/// # Panics
+/// Panics if:
+/// * Either of the polynomials is empty.
+/// * The base field of `E` does not contain a multiplicative subgroup of size
+///   (a.len() + b.len() - 1).next_power_of_two().
+///
+/// # Examples
+/// ```
+/// # use winter_math::polynom::*;
+/// # use winter_math::{fields::{f128::BaseElement}, FieldElement};
+/// # use rand_utils::rand_vector;
+/// let p1: Vec<BaseElement> = rand_vector(100);
+/// let p2: Vec<BaseElement> = rand_vector(50);
+///
+/// let mut expected = vec![BaseElement::ZERO; 149];
+/// for (i, &c1) in p1.iter().enumerate() {
+///     for (j, &c2) in p2.iter().enumerate() {
+///         expected[i + j] += c1 * c2;
+///     }
+/// }
+/// assert_eq!(expected, mul_fft(&p1, &p2));
+/// ```
+pub fn mul_fft<E>(a: &[E], b: &[E]) -> Vec<E>
+where
+    E: FieldElement,
+{
+    assert!(
+        !a.is_empty() && !b.is_empty(),
+        "cannot multiply empty polynomials"
+    );
+    let result_len = a.len() + b.len() - 1;
+    let domain_size = result_len.next_power_of_two().max(2);
+
+    // evaluate both polynomials over the domain
+    let twiddles = fft::get_twiddles::<E::BaseField>(domain_size);
+    let mut a_evaluations = pad_to(a, domain_size);
+    fft::evaluate_poly(&mut a_evaluations, &twiddles);
+    let mut b_evaluations = pad_to(b, domain_size);
+    fft::evaluate_poly(&mut b_evaluations, &twiddles);
+
+    // multiply the evaluations and interpolate the product
+    for (a, &b) in a_evaluations.iter_mut().zip(b_evaluations.iter()) {
+        *a *= b;
+    }
+    let inv_twiddles = fft::get_inv_twiddles::<E::BaseField>(domain_size);
+    fft::interpolate_poly(&mut a_evaluations, &inv_twiddles);
+
+    a_evaluations.truncate(result_len);
+    a_evaluations
+}
+
 /// Returns a polynomial resulting from multiplying a given polynomial by a scalar value.
 ///
 /// Specifically, multiplies every coefficient of polynomial `p` by constant `k` and returns
@@ -379,6 +464,9 @@ where
 /// be in the coefficient form, and the returned polynomial will be in the coefficient form as
 /// well. The length of the returned vector will be a.len() - b.len() + 1.
 ///
+/// When both the divisor and the quotient are large, the division is performed using FFT (see
+/// [div_fft()]); otherwise, the quadratic long division algorithm is used.
+///
 /// # Panics
 /// Panics if:
 /// * Polynomial `b` is empty.
@@ -408,8 +496,6 @@ where
     E: FieldElement,
 {
     let mut apos = degree_of(a);
-    let mut a = a.to_vec();
-
     let bpos = degree_of(b);
     assert!(apos >= bpos, "cannot divide by polynomial of higher degree");
     if bpos == 0 {
@@ -417,7 +503,15 @@ where
         assert!(b[0] != E::ZERO, "cannot divide polynomial by zero");
     }
 
-    let mut result = E::zeroed_vector(apos - bpos + 1);
+    // the inverse computed during FFT-based division requires products of up to twice the
+    // length of the quotient
+    let quotient_len = apos - bpos + 1;
+    if quotient_len.min(bpos + 1) >= MIN_FFT_LENGTH && is_fft_supported::<E>(2 * quotient_len) {
+        return div_fft(a, b);
+    }
+
+    let mut a = a.to_vec();
+    let mut result = E::zeroed_vector(quotient_len);
     for i in (0..result.len()).rev() {
         let quot = a[apos] / b[bpos];
         result[i] = quot;
@@ -430,6 +524,65 @@ where
     result
 }
 
+/// Returns a polynomial resulting from dividing one polynomial by another using FFT.
+///
+/// The quotient is computed by multiplying the reversal of `a` by the inverse of the reversal
+/// of `b` modulo x^k, where k is the length of the quotient; the inverse is computed using
+/// Newton iteration. The runtime complexity is O(`n` log `n`), where `n` is the length of `a`.
+/// If the polynomials don't divide evenly, the remainder is ignored. Both polynomials are
+/// expected to be in the coefficient form, and the returned polynomial will be in the
+/// coefficient form as well. The length of the returned vector will be a.len() - b.len() + 1
+/// (with leading zeros of both polynomials ignored).
+///
+/// # Panics
+/// Panics if:
+/// * Polynomial `b` is empty.
+/// * Degree of polynomial `b` is zero and the constant coefficient is ZERO.
+/// * The degree of polynomial `b` is greater than the degree of polynomial `a`.
+/// * The base field of `E` does not contain a multiplicative subgroup large enough to hold the
+///   intermediate products.
+///
+/// # Examples
+/// ```
+/// # use winter_math::polynom::*;
+/// # use winter_math::{fields::{f128::BaseElement}, FieldElement};
+/// # use rand_utils::rand_vector;
+/// let p1: Vec<BaseElement> = rand_vector(100);
+/// let p2: Vec<BaseElement> = rand_vector(50);
+/// let p3 = mul(&p1, &p2);
+///
+/// assert_eq!(p1, div_fft(&p3, &p2));
+/// ```
+pub fn div_fft<E>(a: &[E], b: &[E]) -> Vec<E>
+where
+    E: FieldElement,
+{
+    let apos = degree_of(a);
+    let bpos = degree_of(b);
+    assert!(apos >= bpos, "cannot divide by polynomial of higher degree");
+    if bpos == 0 {
+        assert!(!b.is_empty(), "cannot divide by empty polynomial");
+        assert!(b[0] != E::ZERO, "cannot divide polynomial by zero");
+    }
+
+    // rev(q) = rev(a) * rev(b)^-1 mod x^k, where rev(p) is the polynomial with coefficients of
+    // p in reverse order
+    let quotient_len = apos - bpos + 1;
+    let rev_a = a[..=apos]
+        .iter()
+        .rev()
+        .take(quotient_len)
+        .copied()
+        .collect::<Vec<_>>();
+    let rev_b = b[..=bpos].iter().rev().copied().collect::<Vec<_>>();
+    let rev_b_inv = inv_mod_xn(&rev_b, quotient_len);
+
+    let mut result = mul(&rev_a, &rev_b_inv);
+    result.truncate(quotient_len);
+    result.reverse();
+    result
+}
+
 /// Returns a polynomial resulting from dividing a polynomial by a polynomial of special form.
 ///
 /// Specifically, divides polynomial `p` by polynomial (x^`a` - `b`) using
@@ -655,3 +808,41 @@ fn fill_zero_roots<E: FieldElement>(xs: &[E], result: &mut [E]) {
         }
     }
 }
+
+/// Returns true if the base field of `E` contains a multiplicative subgroup large enough to
+/// multiply polynomials with product of the specified length using FFT.
+fn is_fft_supported<E: FieldElement>(result_len: usize) -> bool {
+    result_len.next_power_of_two().ilog2() <= E::BaseField::TWO_ADICITY
+}
+
+/// Returns a copy of polynomial `p` padded with zeros to the specified length.
+fn pad_to<E: FieldElement>(p: &[E], len: usize) -> Vec<E> {
+    let mut result = E::zeroed_vector(len);
+    result[..p.len()].copy_from_slice(p);
+    result
+}
+
+/// Returns the inverse of polynomial `p` modulo x^`n`; the constant coefficient of `p` must not
+/// be ZERO.
+///
+/// The inverse is computed using Newton iteration g' = g * (2 - p * g), which doubles the number
+/// of correct coefficients of g at each step.
+fn inv_mod_xn<E: FieldElement>(p: &[E], n: usize) -> Vec<E> {
+    let mut result = vec![p[0].inv()];
+    let mut len = 1;
+    while len < n {
+        len = (len * 2).min(n);
+
+        // t = 2 - p * g mod x^len
+        let mut t = mul(&p[..len.min(p.len())], &result);
+        t.truncate(len);
+        for c in t.iter_mut() {
+            *c = -*c;
+        }
+        t[0] += E::ONE.double();
+
+        result = mul(&result, &t);
+        result.truncate(len);
+    }
+    result
+}
@@ -3,11 +3,12 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use super::remove_leading_zeros;
+use super::{remove_leading_zeros, VanishingPoly};
 use crate::{
     field::{f128::BaseElement, FieldElement, StarkField},
-    utils::get_power_series,
+    utils::{get_power_series, get_power_series_with_offset},
 };
+use rand_utils::{rand_value, rand_vector};
 use utils::collections::Vec;
 
 #[test]
@@ -174,6 +175,57 @@ fn div() {
     );
 }
 
+#[test]
+fn mul_fft() {
+    let poly1: Vec<BaseElement> = rand_vector(100);
+    let poly2: Vec<BaseElement> = rand_vector(70);
+
+    let mut expected = vec![BaseElement::ZERO; 169];
+    for (i, &c1) in poly1.iter().enumerate() {
+        for (j, &c2) in poly2.iter().enumerate() {
+            expected[i + j] += c1 * c2;
+        }
+    }
+    assert_eq!(expected, super::mul_fft(&poly1, &poly2));
+    assert_eq!(expected, super::mul(&poly1, &poly2));
+
+    // small polynomials
+    assert_eq!(
+        super::mul(&poly1[..3], &poly2[..2]),
+        super::mul_fft(&poly1[..3], &poly2[..2])
+    );
+    assert_eq!(
+        vec![poly1[0] * poly2[0]],
+        super::mul_fft(&poly1[..1], &poly2[..1])
+    );
+}
+
+#[test]
+fn div_fft() {
+    let poly1: Vec<BaseElement> = rand_vector(100);
+    let poly2: Vec<BaseElement> = rand_vector(70);
+
+    // divide evenly
+    let poly3 = super::mul(&poly1, &poly2);
+    assert_eq!(poly1, super::div_fft(&poly3, &poly2));
+    assert_eq!(poly1, super::div(&poly3, &poly2));
+
+    // with remainder; remainder is ignored
+    let poly4 = super::add(&poly3, &poly2[..50]);
+    assert_eq!(poly1, super::div_fft(&poly4, &poly2));
+
+    // quotient of a single coefficient
+    let k = BaseElement::from(11269864713250585702u128);
+    let poly5 = super::mul_by_scalar(&poly2, k);
+    assert_eq!(vec![k], super::div_fft(&poly5, &poly2));
+
+    // results are the same as for long division for small polynomials
+    assert_eq!(
+        super::div(&poly1[..10], &poly2[..4]),
+        super::div_fft(&poly1[..10], &poly2[..4])
+    );
+}
+
 #[test]
 fn syn_div() {
     // ----- division by degree 1 polynomial ------------------------------------------------------
@@ -256,3 +308,70 @@ fn syn_div() {
     let result = super::syn_div(&poly, 4, root.exp(4));
     assert_eq!(poly, remove_leading_zeros(&super::mul(&result, &z_poly)));
 }
+
+#[test]
+fn vanishing_poly() {
+    let n: usize = 16;
+    let g = BaseElement::get_root_of_unity(n.ilog2());
+    let offset = BaseElement::GENERATOR;
+
+    // ----- subgroup -----------------------------------------------------------------------------
+    let z = VanishingPoly::<BaseElement>::subgroup(n);
+    assert_eq!(n, z.degree());
+    assert_eq!((n, BaseElement::ONE), z.numerator());
+    for x in get_power_series(g, n) {
+        assert_eq!(BaseElement::ZERO, z.evaluate_at(x));
+    }
+    assert_ne!(BaseElement::ZERO, z.evaluate_at(offset));
+
+    // ----- coset --------------------------------------------------------------------------------
+    let z = VanishingPoly::coset(n, offset);
+    assert_eq!((n, offset.exp(n as u128)), z.numerator());
+    for x in get_power_series_with_offset(g, offset, n) {
+        assert_eq!(BaseElement::ZERO, z.evaluate_at(x));
+    }
+    assert_ne!(BaseElement::ZERO, z.evaluate_at(g));
+
+    // ----- punctured coset ----------------------------------------------------------------------
+    let domain = get_power_series_with_offset(g, offset, n);
+    let z = VanishingPoly::coset(n, offset).puncture(&domain[n - 2..]);
+    assert_eq!(n - 2, z.degree());
+    for &x in domain[..n - 2].iter() {
+        assert_eq!(BaseElement::ZERO, z.evaluate_at(x));
+    }
+
+    // evaluation must match the product of (x - h) over the non-excluded points
+    let x = rand_value::<BaseElement>();
+    let expected = domain[..n - 2]
+        .iter()
+        .fold(BaseElement::ONE, |r, &h| r * (x - h));
+    assert_eq!(expected, z.evaluate_at(x));
+}
+
+#[test]
+fn vanishing_poly_divide() {
+    let n: usize = 8;
+    let g = BaseElement::get_root_of_unity(n.ilog2());
+    let offset = BaseElement::GENERATOR;
+    let domain = get_power_series_with_offset(g, offset, n);
+    let z = VanishingPoly::coset(n, offset).puncture(&domain[n - 1..]);
+
+    // build z(x) explicitly from the non-excluded points and multiply it by a random polynomial
+    let z_poly = domain[..n - 1]
+        .iter()
+        .fold(vec![BaseElement::ONE], |r, &h| {
+            super::mul(&r, &[-h, BaseElement::ONE])
+        });
+    let q = rand_vector::<BaseElement>(5);
+    let p = super::mul(&q, &z_poly);
+
+    let result = z.divide(&p);
+    assert_eq!(q, remove_leading_zeros(&result));
+}
+
+#[test]
+#[should_panic(expected = "is not in the coset of the vanishing polynomial")]
+fn vanishing_poly_puncture_invalid_point() {
+    let g = BaseElement::get_root_of_unity(3);
+    VanishingPoly::coset(8, BaseElement::GENERATOR).puncture(&[g]);
+}
@@ -0,0 +1,163 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{mul, syn_div_in_place};
+use crate::field::{FieldElement, StarkField};
+use utils::collections::Vec;
+
+// VANISHING POLYNOMIAL
+// ================================================================================================
+
+/// A polynomial which evaluates to zero on a coset of a multiplicative subgroup, optionally
+/// excluding a set of points from the coset.
+///
+/// For a subgroup $H$ of size $n$, a coset offset $s$, and a set of excluded points
+/// $e_1, ..., e_k$ in $s \cdot H$, the polynomial is defined as:
+///
+/// $$
+/// z(x) = \frac{x^n - s^n}{\prod_{i=1}^k (x - e_i)}
+/// $$
+///
+/// Thus, $z(x)$ evaluates to zero on all points of $s \cdot H$ except for the excluded points.
+/// When the offset is $1$, the polynomial vanishes on the subgroup $H$ itself.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct VanishingPoly<B: StarkField> {
+    size: usize,
+    offset: B,
+    exclusions: Vec<B>,
+}
+
+impl<B: StarkField> VanishingPoly<B> {
+    // CONSTRUCTORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a polynomial which vanishes on the multiplicative subgroup of the specified
+    /// `size`; that is, $x^n - 1$.
+    ///
+    /// # Panics
+    /// Panics if `size` is not a power of two, or if the field does not contain a subgroup of
+    /// the specified size.
+    pub fn subgroup(size: usize) -> Self {
+        Self::coset(size, B::ONE)
+    }
+
+    /// Returns a polynomial which vanishes on the coset of the multiplicative subgroup of the
+    /// specified `size` defined by the specified `offset`; that is, $x^n - s^n$.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * `size` is not a power of two, or the field does not contain a subgroup of this size.
+    /// * `offset` is zero.
+    pub fn coset(size: usize, offset: B) -> Self {
+        assert!(
+            size.is_power_of_two(),
+            "subgroup size must be a power of two, but was {size}"
+        );
+        assert!(
+            size.ilog2() <= B::TWO_ADICITY,
+            "subgroup size cannot exceed 2^{}, but was {size}",
+            B::TWO_ADICITY
+        );
+        assert!(offset != B::ZERO, "coset offset cannot be zero");
+        VanishingPoly {
+            size,
+            offset,
+            exclusions: Vec::new(),
+        }
+    }
+
+    /// Returns this polynomial with the specified `points` excluded from the set of points on
+    /// which it vanishes.
+    ///
+    /// # Panics
+    /// Panics if any of the `points` is not in the coset on which this polynomial vanishes, or
+    /// if a point has already been excluded.
+    pub fn puncture(mut self, points: &[B]) -> Self {
+        let coset_value = self.coset_value();
+        for &point in points {
+            assert!(
+                point.exp((self.size as u64).into()) == coset_value,
+                "point {point} is not in the coset of the vanishing polynomial"
+            );
+            assert!(
+                !self.exclusions.contains(&point),
+                "point {point} has already been excluded"
+            );
+            self.exclusions.push(point);
+        }
+        self
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the size of the subgroup on a coset of which this polynomial vanishes.
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Returns the offset of the coset on which this polynomial vanishes.
+    pub fn offset(&self) -> B {
+        self.offset
+    }
+
+    /// Returns the points excluded from the set of points on which this polynomial vanishes.
+    pub fn exclusions(&self) -> &[B] {
+        &self.exclusions
+    }
+
+    /// Returns the degree of this polynomial.
+    pub fn degree(&self) -> usize {
+        self.size - self.exclusions.len()
+    }
+
+    /// Returns the numerator of this polynomial as a tuple $(n, s^n)$ describing $x^n - s^n$.
+    pub fn numerator(&self) -> (usize, B) {
+        (self.size, self.coset_value())
+    }
+
+    // OPERATIONS
+    // --------------------------------------------------------------------------------------------
+
+    /// Evaluates this polynomial at the provided `x` coordinate.
+    ///
+    /// The result is undefined if `x` is one of the excluded points.
+    pub fn evaluate_at<E: FieldElement<BaseField = B>>(&self, x: E) -> E {
+        let numerator = x.exp((self.size as u64).into()) - E::from(self.coset_value());
+        let denominator = self
+            .exclusions
+            .iter()
+            .fold(E::ONE, |r, &e| r * (x - E::from(e)));
+        numerator / denominator
+    }
+
+    /// Divides polynomial `p` by this polynomial and returns the result.
+    ///
+    /// Polynomial `p` is expected to be in coefficient form. If `p` is not divisible by this
+    /// polynomial, the remainder is ignored.
+    ///
+    /// # Panics
+    /// Panics if the degree of `p` with excluded points multiplied in is smaller than the size
+    /// of the subgroup.
+    pub fn divide<E: FieldElement<BaseField = B>>(&self, p: &[E]) -> Vec<E> {
+        // multiply the polynomial by the terms corresponding to excluded points, and then
+        // divide the result by the numerator using synthetic division
+        let mut result = p.to_vec();
+        for &e in self.exclusions.iter() {
+            result = mul(&result, &[-E::from(e), E::ONE]);
+        }
+        syn_div_in_place(&mut result, self.size, E::from(self.coset_value()));
+        result.truncate(p.len());
+        result
+    }
+
+    // HELPER METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns $s^n$, where $s$ is the coset offset and $n$ is the subgroup size.
+    fn coset_value(&self) -> B {
+        self.offset.exp((self.size as u64).into())
+    }
+}
@@ -0,0 +1,49 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use crypto::hashers::Blake3_256;
+use math::fields::f64::BaseElement;
+use rand_utils::rand_vector;
+use std::time::Duration;
+use winter_prover::ColMatrix;
+
+type Blake3 = Blake3_256<BaseElement>;
+
+// CONSTANTS
+// ================================================================================================
+
+const SIZE: usize = 1_048_576;
+const NUM_COLS: [usize; 3] = [16, 64, 128];
+const SCRATCH_SIZE: [usize; 3] = [1024, 16_384, 262_144];
+
+fn commit_to_rows(c: &mut Criterion) {
+    let mut group = c.benchmark_group("matrix_commit_to_rows");
+    group.sample_size(10);
+    group.measurement_time(Duration::from_secs(10));
+
+    for &num_cols in NUM_COLS.iter() {
+        let columns: Vec<Vec<BaseElement>> = (0..num_cols).map(|_| rand_vector(SIZE)).collect();
+        let matrix = ColMatrix::new(columns);
+
+        let params = format!("num_cols: {num_cols}");
+        group.bench_function(BenchmarkId::new("gather", params), |bench| {
+            bench.iter_with_large_drop(|| matrix.commit_to_rows::<Blake3>());
+        });
+
+        for &scratch_size in SCRATCH_SIZE.iter() {
+            let params = format!("num_cols: {num_cols}, scratch_size: {scratch_size}");
+            group.bench_function(BenchmarkId::new("streaming", params), |bench| {
+                bench.iter_with_large_drop(|| {
+                    matrix.commit_to_rows_streaming::<Blake3>(scratch_size)
+                });
+            });
+        }
+    }
+    group.finish();
+}
+
+criterion_group!(commit_group, commit_to_rows);
+criterion_main!(commit_group);
@@ -3,6 +3,7 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
+use super::ProverError;
 use air::{
     proof::{Commitments, Context, OodFrame, Queries, StarkProof},
     Air, ConstraintCompositionCoefficients, DeepCompositionCoefficients,
@@ -71,6 +72,14 @@ where
     // COMMITMENT METHODS
     // --------------------------------------------------------------------------------------------
 
+    /// Commits the prover to the extended preprocessed trace columns.
+    ///
+    /// The root of the preprocessed commitment is known to the verifier in advance, and thus, it
+    /// is not included in the proof; only the public coin is reseeded with it.
+    pub fn commit_preprocessed(&mut self, preprocessed_root: H::Digest) {
+        self.public_coin.reseed(preprocessed_root);
+    }
+
     /// Commits the prover the extended execution trace.
     pub fn commit_trace(&mut self, trace_root: H::Digest) {
         self.commitments.add::<H>(&trace_root);
@@ -106,62 +115,81 @@ where
     /// the specified index.
     ///
     /// The elements are drawn from the public coin uniformly at random.
-    pub fn get_aux_trace_segment_rand_elements(&mut self, aux_segment_idx: usize) -> Vec<E> {
+    pub fn get_aux_trace_segment_rand_elements(
+        &mut self,
+        aux_segment_idx: usize,
+    ) -> Result<Vec<E>, ProverError> {
         self.air
             .get_aux_trace_segment_random_elements(aux_segment_idx, &mut self.public_coin)
-            .expect("failed to draw random elements for an auxiliary trace segment")
+            .map_err(|_| ProverError::RandomCoinError)
     }
 
     /// Returns a set of coefficients for constructing a constraint composition polynomial.
     ///
     /// The coefficients are drawn from the public coin uniformly at random.
-    pub fn get_constraint_composition_coeffs(&mut self) -> ConstraintCompositionCoefficients<E> {
+    pub fn get_constraint_composition_coeffs(
+        &mut self,
+    ) -> Result<ConstraintCompositionCoefficients<E>, ProverError> {
         self.air
             .get_constraint_composition_coefficients(&mut self.public_coin)
-            .expect("failed to draw composition coefficients")
+            .map_err(|_| ProverError::RandomCoinError)
     }
 
     /// Returns an out-of-domain point drawn uniformly at random from the public coin.
-    pub fn get_ood_point(&mut self) -> E {
-        self.public_coin.draw().expect("failed to draw OOD point")
+    pub fn get_ood_point(&mut self) -> Result<E, ProverError> {
+        self.public_coin
+            .draw()
+            .map_err(|_| ProverError::RandomCoinError)
     }
 
     /// Returns a set of coefficients for constructing a DEEP composition polynomial.
     ///
     /// The coefficients are drawn from the public coin uniformly at random.
-    pub fn get_deep_composition_coeffs(&mut self) -> DeepCompositionCoefficients<E> {
+    pub fn get_deep_composition_coeffs(
+        &mut self,
+    ) -> Result<DeepCompositionCoefficients<E>, ProverError> {
         self.air
             .get_deep_composition_coefficients(&mut self.public_coin)
-            .expect("failed to draw DEEP composition coefficients")
+            .map_err(|_| ProverError::RandomCoinError)
     }
 
     /// Returns a set of positions in the LDE domain against which the evaluations of trace and
     /// constraint composition polynomials should be queried.
     ///
-    /// The positions are drawn from the public coin uniformly at random.
-    pub fn get_query_positions(&mut self) -> Vec<usize> {
-        let num_queries = self.context.options().num_queries();
-        let lde_domain_size = self.context.lde_domain_size();
-        self.public_coin
-            .draw_integers(num_queries, lde_domain_size)
-            .expect("failed to draw query position")
+    /// The positions are drawn from the public coin according to the query sampling strategy
+    /// specified in the proof options.
+    pub fn get_query_positions(&mut self) -> Result<Vec<usize>, ProverError> {
+        self.air
+            .get_query_positions(&mut self.public_coin)
+            .map_err(|_| ProverError::RandomCoinError)
     }
 
-    /// Determines a nonce, which when hashed with the current seed of the public coin results
-    /// in a new seed with the number of leading zeros equal to the grinding_factor specified
-    /// in the proof options.
+    /// Determines a nonce, for which the grinding function specified in the proof options
+    /// evaluated over the current seed of the public coin results in a value with the number of
+    /// leading zeros equal to the grinding_factor specified in the proof options.
     pub fn grind_query_seed(&mut self) {
         let grinding_factor = self.context.options().grinding_factor();
+        let grinding_function = self.context.options().grinding_function();
 
         #[cfg(not(feature = "concurrent"))]
         let nonce = (1..u64::MAX)
-            .find(|&nonce| self.public_coin.check_leading_zeros(nonce) >= grinding_factor)
+            .find(|&nonce| {
+                grinding_function
+                    .check_nonce(&self.public_coin, nonce)
+                    .expect("public coin does not support the specified grinding function")
+                    >= grinding_factor
+            })
             .expect("nonce not found");
 
         #[cfg(feature = "concurrent")]
         let nonce = (1..u64::MAX)
             .into_par_iter()
-            .find_any(|&nonce| self.public_coin.check_leading_zeros(nonce) >= grinding_factor)
+            .find_any(|&nonce| {
+                grinding_function
+                    .check_nonce(&self.public_coin, nonce)
+                    .expect("public coin does not support the specified grinding function")
+                    >= grinding_factor
+            })
             .expect("nonce not found");
 
         self.pow_nonce = nonce;
@@ -203,9 +231,11 @@ where
     type Hasher = H;
 
     /// Commits the prover to a FRI layer.
-    fn commit_fri_layer(&mut self, layer_root: H::Digest) {
+    fn commit_fri_layer(&mut self, layer_root: H::Digest, layer_depth: usize, domain_size: usize) {
         self.commitments.add::<H>(&layer_root);
         self.public_coin.reseed(layer_root);
+        self.public_coin
+            .reseed_with_int(fri::utils::get_layer_salt(layer_depth, domain_size));
     }
 
     /// Returns a new alpha drawn from the public coin.
@@ -46,6 +46,11 @@ impl<E: FieldElement> DeepCompositionPoly<E> {
         polynom::degree_of(&self.coefficients)
     }
 
+    /// Returns coefficients of the DEEP composition polynomial.
+    pub fn coefficients(&self) -> &[E] {
+        &self.coefficients
+    }
+
     // TRACE POLYNOMIAL COMPOSITION
     // --------------------------------------------------------------------------------------------
     /// Combines all trace polynomials into a single polynomial and saves the result into
@@ -150,6 +155,10 @@ impl<E: FieldElement> DeepCompositionPoly<E> {
     ///   public coin.
     ///
     /// Note that evaluations of H_i(x) at z^m are passed in via the `ood_evaluations` parameter.
+    ///
+    /// Since division by (x - z^m) is linear, the columns are first combined as
+    /// sum((H_i(x) - H_i(z^m)) * cc_i), and the OOD point is then divided out of the combined
+    /// polynomial.
     pub fn add_composition_poly(
         &mut self,
         composition_poly: CompositionPoly<E>,
@@ -161,21 +170,26 @@ impl<E: FieldElement> DeepCompositionPoly<E> {
         let num_columns = composition_poly.num_columns() as u32;
         let z_m = self.z.exp(num_columns.into());
 
-        let mut column_polys = composition_poly.into_columns();
-
-        // Divide out the OOD point z from column polynomials
-        iter_mut!(column_polys)
+        // compute sum((H_i(x) - H_i(z^m)) * cc_i) for all i
+        let mut combined_poly = E::zeroed_vector(composition_poly.column_len());
+        for (i, (poly, value_at_z_m)) in composition_poly
+            .data()
+            .columns()
             .zip(ood_evaluations)
-            .for_each(|(poly, value_at_z_m)| {
-                // compute H'_i(x) = (H_i(x) - H_i(z^m)) / (x - z^m)
-                poly[0] -= value_at_z_m;
-                polynom::syn_div_in_place(poly, 1, z_m);
-            });
-
-        // add H'_i(x) * cc_i for all i into the DEEP composition polynomial
-        for (i, poly) in column_polys.into_iter().enumerate() {
-            mul_acc::<E, E>(&mut self.coefficients, &poly, self.cc.constraints[i]);
+            .enumerate()
+        {
+            acc_trace_poly::<E, E>(
+                &mut combined_poly,
+                poly,
+                value_at_z_m,
+                self.cc.constraints[i],
+            );
         }
+
+        // divide out the OOD point z^m from the combined polynomial, and add the result into the
+        // DEEP composition polynomial
+        polynom::syn_div_in_place(&mut combined_poly, 1, z_m);
+        add_in_place(&mut self.coefficients, &combined_poly);
         assert_eq!(self.poly_size() - 2, self.degree());
     }
 
@@ -3,10 +3,16 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use super::StarkDomain;
-use air::{Air, AuxTraceRandElements, ConstraintDivisor};
-use math::{fft, ExtensionOf, FieldElement};
-use utils::collections::{BTreeMap, Vec};
+use super::{ProverError, StarkDomain};
+use air::{Air, Assertion, AssertionError, AuxTraceRandElements, ConstraintDivisor};
+use math::{fft, ExtensionOf, FieldElement, StarkField};
+use utils::{
+    collections::{BTreeMap, Vec},
+    iter,
+};
+
+#[cfg(feature = "concurrent")]
+use utils::iterators::*;
 
 // CONSTANTS
 // ================================================================================================
@@ -31,47 +37,84 @@ impl<E: FieldElement> BoundaryConstraints<E> {
     // --------------------------------------------------------------------------------------------
     /// Returns a new instance of [BoundaryConstraints] constructed from the constraints defined
     /// by an instance of AIR for a specific computation.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * The number of assertions returned by the AIR does not match the number of assertions
+    ///   specified in the AIR context.
+    /// * Any of the assertions is not valid in the context of the execution trace, or overlaps
+    ///   with another assertion.
     pub fn new<A: Air<BaseField = E::BaseField>>(
         air: &A,
         aux_rand_elements: &AuxTraceRandElements<E>,
         composition_coefficients: &[(E, E)],
-    ) -> Self {
+    ) -> Result<Self, ProverError> {
+        // make sure the assertions are valid; the AIR panics on invalid assertions, so we check
+        // them here to be able to return an error instead
+        let context = air.context();
+        let main_assertions = air.get_assertions();
+        if main_assertions.len() != context.num_main_assertions() {
+            return Err(ProverError::MismatchedMainAssertionCount(
+                context.num_main_assertions(),
+                main_assertions.len(),
+            ));
+        }
+        validate_assertions(
+            &main_assertions,
+            air.trace_layout().main_trace_width(),
+            air.trace_length(),
+        )?;
+
+        let aux_assertions = air.get_aux_assertions(aux_rand_elements);
+        if aux_assertions.len() != context.num_aux_assertions() {
+            return Err(ProverError::MismatchedAuxAssertionCount(
+                context.num_aux_assertions(),
+                aux_assertions.len(),
+            ));
+        }
+        validate_assertions(
+            &aux_assertions,
+            air.trace_layout().aux_trace_width(),
+            air.trace_length(),
+        )?;
+
         // get constraints from the AIR instance
         let source = air.get_boundary_constraints(aux_rand_elements, composition_coefficients);
 
-        // initialize a map of twiddles here so that we can keep track of already computed
-        // twiddles; this helps us avoid building twiddles over and over again for constraints
-        // defined over the same domain. twiddles are relevant only for large polynomial
-        // constraints.
-        let mut twiddle_map = BTreeMap::new();
+        // build twiddles for all large polynomial constraints up front; this helps us avoid
+        // building twiddles over and over again for constraints defined over the same domain,
+        // and also lets us transform constraint groups independently of each other.
+        let twiddle_map = build_twiddle_map(&source);
 
         // transform constraints against the main segment of the execution trace into specialized
-        // constraints
-        let mut result = source
-            .main_constraints()
-            .iter()
-            .map(|group| {
-                BoundaryConstraintGroup::from_main_constraints(group, air, &mut twiddle_map)
-            })
+        // constraints; when `concurrent` feature is enabled, groups are transformed in multiple
+        // threads as this involves evaluating large polynomial constraints over the entire
+        // constraint evaluation domain
+        let mut result = iter!(source.main_constraints())
+            .map(|group| BoundaryConstraintGroup::from_main_constraints(group, air, &twiddle_map))
             .collect::<Vec<BoundaryConstraintGroup<E>>>();
 
         // transform constraints against auxiliary trace segments (if any) into specialized
-        // constraints. this also checks if a group with the same divisor has already been
-        // transformed (when processing constraints against the main trace above), and if so,
-        // appends constraints to that group rather than creating a new group. this ensures
-        // that we always end up with a single constraint group for the same divisor.
-        for group in source.aux_constraints() {
-            match result.iter_mut().find(|g| &g.divisor == group.divisor()) {
-                Some(x) => x.add_aux_constraints(group, air, &mut twiddle_map),
-                None => {
-                    let group =
-                        BoundaryConstraintGroup::from_aux_constraints(group, air, &mut twiddle_map);
-                    result.push(group);
-                }
+        // constraints. if a group with the same divisor has already been transformed (when
+        // processing constraints against the main trace above), the constraints are appended to
+        // that group rather than creating a new group. this ensures that we always end up with a
+        // single constraint group for the same divisor.
+        let aux_groups = iter!(source.aux_constraints())
+            .map(|group| BoundaryConstraintGroup::from_aux_constraints(group, air, &twiddle_map))
+            .collect::<Vec<BoundaryConstraintGroup<E>>>();
+        for group in aux_groups {
+            match result.iter_mut().find(|g| g.divisor == group.divisor) {
+                Some(x) => x.append_aux_constraints(group),
+                None => result.push(group),
             };
         }
 
-        Self(result)
+        // order groups by their degree adjustment factors so that groups with the same factor
+        // are next to each other; this way, the power of x for the degree adjustment can be
+        // computed once for all such groups during constraint evaluation
+        result.sort_by_key(|group| group.degree_adjustment);
+
+        Ok(Self(result))
     }
 
     // PUBLIC ACCESSORS
@@ -82,6 +125,11 @@ impl<E: FieldElement> BoundaryConstraints<E> {
         self.0.iter().map(|g| g.divisor.clone()).collect()
     }
 
+    /// Returns the number of distinct boundary constraint divisors.
+    pub fn num_divisors(&self) -> usize {
+        self.0.len()
+    }
+
     // EVALUATORS
     // --------------------------------------------------------------------------------------------
 
@@ -95,10 +143,10 @@ impl<E: FieldElement> BoundaryConstraints<E> {
         result: &mut [E],
     ) {
         let x = domain.get_ce_x_at(step);
+        let mut xp = XPowerCache::new();
         for (group, result) in self.0.iter().zip(result.iter_mut()) {
             // evaluate the group and save the result
-            let (power, offset_exp) = (group.degree_adjustment, group.domain_offset_exp);
-            let xp = domain.get_ce_x_power_at(step, power, offset_exp);
+            let xp = xp.get(group, domain, step);
             *result = group.evaluate_main(main_state, step, x, xp);
         }
     }
@@ -114,10 +162,10 @@ impl<E: FieldElement> BoundaryConstraints<E> {
         result: &mut [E],
     ) {
         let x = domain.get_ce_x_at(step);
+        let mut xp = XPowerCache::new();
         for (group, result) in self.0.iter().zip(result.iter_mut()) {
             // evaluate the group and save the result
-            let (power, offset_exp) = (group.degree_adjustment, group.domain_offset_exp);
-            let xp = domain.get_ce_x_power_at(step, power, offset_exp);
+            let xp = xp.get(group, domain, step);
             *result = group.evaluate_all(main_state, aux_state, step, x, xp);
         }
     }
@@ -181,7 +229,7 @@ impl<E: FieldElement> BoundaryConstraintGroup<E> {
     pub fn from_main_constraints<A: Air<BaseField = E::BaseField>>(
         source: &air::BoundaryConstraintGroup<E::BaseField, E>,
         air: &A,
-        twiddle_map: &mut BTreeMap<usize, Vec<E::BaseField>>,
+        twiddle_map: &BTreeMap<usize, Vec<E::BaseField>>,
     ) -> Self {
         let mut result = Self::new(
             source.divisor().clone(),
@@ -212,54 +260,48 @@ impl<E: FieldElement> BoundaryConstraintGroup<E> {
     /// Twiddles and [Air] instance are passed in for evaluating large polynomial constraints
     /// (if any).
     pub fn from_aux_constraints<A: Air<BaseField = E::BaseField>>(
-        group: &air::BoundaryConstraintGroup<E, E>,
+        source: &air::BoundaryConstraintGroup<E, E>,
         air: &A,
-        twiddle_map: &mut BTreeMap<usize, Vec<E::BaseField>>,
+        twiddle_map: &BTreeMap<usize, Vec<E::BaseField>>,
     ) -> Self {
         let mut result = Self::new(
-            group.divisor().clone(),
-            group.degree_adjustment(),
+            source.divisor().clone(),
+            source.degree_adjustment(),
             air.domain_offset(),
         );
-        result.add_aux_constraints(group, air, twiddle_map);
+
+        for constraint in source.constraints() {
+            if constraint.poly().len() == 1 {
+                let constraint = SingleValueConstraint::new(constraint);
+                result.aux_single_value.push(constraint);
+            } else if constraint.poly().len() < SMALL_POLY_DEGREE {
+                let constraint = SmallPolyConstraint::new(constraint);
+                result.aux_small_poly.push(constraint);
+            } else {
+                let constraint = LargePolyConstraint::new(constraint, air, twiddle_map);
+                result.aux_large_poly.push(constraint);
+            }
+        }
+
         result
     }
 
     // STATE MUTATORS
     // --------------------------------------------------------------------------------------------
 
-    /// Adds the provided constraints against auxiliary segments of an execution trace to this
-    /// group.
-    ///
-    /// Twiddles and [Air] instance are passed in for evaluating large polynomial constraints
-    /// (if any).
+    /// Moves constraints against auxiliary segments of an execution trace from the provided
+    /// group into this group.
     ///
     /// # Panics
-    /// Panics if the divisor of the provided constraints doesn't match the divisor of this group.
-    pub fn add_aux_constraints<A: Air<BaseField = E::BaseField>>(
-        &mut self,
-        group: &air::BoundaryConstraintGroup<E, E>,
-        air: &A,
-        twiddle_map: &mut BTreeMap<usize, Vec<E::BaseField>>,
-    ) {
+    /// Panics if the divisor of the provided group doesn't match the divisor of this group.
+    pub fn append_aux_constraints(&mut self, mut other: Self) {
         assert_eq!(
-            group.divisor(),
-            &self.divisor,
+            other.divisor, self.divisor,
             "inconsistent constraint divisor"
         );
-
-        for constraint in group.constraints() {
-            if constraint.poly().len() == 1 {
-                let constraint = SingleValueConstraint::new(constraint);
-                self.aux_single_value.push(constraint);
-            } else if constraint.poly().len() < SMALL_POLY_DEGREE {
-                let constraint = SmallPolyConstraint::new(constraint);
-                self.aux_small_poly.push(constraint);
-            } else {
-                let constraint = LargePolyConstraint::new(constraint, air, twiddle_map);
-                self.aux_large_poly.push(constraint);
-            }
-        }
+        self.aux_single_value.append(&mut other.aux_single_value);
+        self.aux_small_poly.append(&mut other.aux_small_poly);
+        self.aux_large_poly.append(&mut other.aux_large_poly);
     }
 
     // EVALUATORS
@@ -448,18 +490,18 @@ where
     pub fn new<A: Air<BaseField = F::BaseField>>(
         source: &air::BoundaryConstraint<F, E>,
         air: &A,
-        twiddle_map: &mut BTreeMap<usize, Vec<F::BaseField>>,
+        twiddle_map: &BTreeMap<usize, Vec<F::BaseField>>,
     ) -> Self {
         debug_assert!(
             source.poly().len() >= SMALL_POLY_DEGREE,
             "not a large poly constraint"
         );
-        // evaluate the polynomial over the entire constraint evaluation domain; first
-        // get twiddles for the evaluation; if twiddles haven't been built yet, build them
+        // evaluate the polynomial over the entire constraint evaluation domain using twiddles
+        // which were built for polynomials of this length
         let poly_length = source.poly().len();
         let twiddles = twiddle_map
-            .entry(poly_length)
-            .or_insert_with(|| fft::get_twiddles(poly_length));
+            .get(&poly_length)
+            .expect("twiddles for large polynomial constraint have not been built");
 
         let values = fft::evaluate_poly_with_offset(
             source.poly(),
@@ -497,3 +539,184 @@ where
         (self.coefficients.0 + self.coefficients.1.mul_base(xp)).mul_base(evaluation)
     }
 }
+
+// X POWER CACHE
+// ================================================================================================
+
+/// Keeps the most recently computed degree adjustment power of x at a given step of the constraint
+/// evaluation domain.
+///
+/// Since constraint groups are sorted by their degree adjustment factors, this ensures that the
+/// power is computed only once for all groups with the same degree adjustment factor.
+struct XPowerCache<B: StarkField> {
+    last: Option<(u64, B)>,
+}
+
+impl<B: StarkField> XPowerCache<B> {
+    /// Returns a new empty cache.
+    fn new() -> Self {
+        Self { last: None }
+    }
+
+    /// Returns x^degree_adjustment for the specified group at the specified step of the
+    /// constraint evaluation domain.
+    fn get<E: FieldElement<BaseField = B>>(
+        &mut self,
+        group: &BoundaryConstraintGroup<E>,
+        domain: &StarkDomain<B>,
+        step: usize,
+    ) -> B {
+        match self.last {
+            Some((power, xp)) if power == group.degree_adjustment => xp,
+            _ => {
+                let (power, offset_exp) = (group.degree_adjustment, group.domain_offset_exp);
+                let xp = domain.get_ce_x_power_at(step, power, offset_exp);
+                self.last = Some((power, xp));
+                xp
+            }
+        }
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Builds twiddles for all distinct lengths of large polynomial constraints in the provided
+/// boundary constraints.
+fn build_twiddle_map<E: FieldElement>(
+    source: &air::BoundaryConstraints<E>,
+) -> BTreeMap<usize, Vec<E::BaseField>> {
+    let main_lengths = source
+        .main_constraints()
+        .iter()
+        .flat_map(|group| group.constraints().iter().map(|c| c.poly().len()));
+    let aux_lengths = source
+        .aux_constraints()
+        .iter()
+        .flat_map(|group| group.constraints().iter().map(|c| c.poly().len()));
+
+    let mut twiddle_map = BTreeMap::new();
+    for poly_length in main_lengths.chain(aux_lengths) {
+        if poly_length >= SMALL_POLY_DEGREE {
+            twiddle_map
+                .entry(poly_length)
+                .or_insert_with(|| fft::get_twiddles(poly_length));
+        }
+    }
+    twiddle_map
+}
+
+/// Checks that all assertions are valid against an execution trace segment of the specified
+/// width and length, and that no two assertions overlap.
+fn validate_assertions<E: FieldElement>(
+    assertions: &[Assertion<E>],
+    trace_width: usize,
+    trace_length: usize,
+) -> Result<(), ProverError> {
+    for (i, assertion) in assertions.iter().enumerate() {
+        assertion
+            .validate_trace_width(trace_width)
+            .map_err(ProverError::InvalidAssertion)?;
+        assertion
+            .validate_trace_length(trace_length)
+            .map_err(ProverError::InvalidAssertion)?;
+        if let Some(other) = assertions[..i].iter().find(|a| a.overlaps_with(assertion)) {
+            return Err(ProverError::InvalidAssertion(AssertionError::Overlapping(
+                other.column(),
+                other.first_step(),
+            )));
+        }
+    }
+    Ok(())
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::BoundaryConstraints;
+    use crate::{tests::MockAir, ProverError, StarkDomain};
+    use air::{Air, Assertion, AssertionError, AuxTraceRandElements};
+    use math::{fields::f128::BaseElement, FieldElement};
+    use utils::collections::Vec;
+
+    #[test]
+    fn boundary_constraints_evaluation() {
+        // build assertions which result in constraint groups with different divisors and degree
+        // adjustment factors, and in all types of specialized constraints
+        let trace_length = 256;
+        let sequence = |n: usize| (0..n as u64).map(BaseElement::from).collect::<Vec<_>>();
+        let assertions = vec![
+            Assertion::single(0, 0, BaseElement::new(3)),
+            Assertion::single(1, 0, BaseElement::new(5)),
+            Assertion::single(2, 255, BaseElement::new(7)),
+            Assertion::periodic(3, 1, 4, BaseElement::new(11)),
+            Assertion::sequence(0, 2, 16, sequence(16)),
+            Assertion::sequence(1, 1, 2, sequence(128)),
+            Assertion::sequence(2, 0, 4, sequence(64)),
+        ];
+        let air = MockAir::with_declared_assertions(assertions, trace_length);
+        let domain = StarkDomain::new(&air);
+        let coefficients = (1..=7)
+            .map(|i| (BaseElement::new(i), BaseElement::new(i * 13)))
+            .collect::<Vec<_>>();
+
+        let constraints =
+            BoundaryConstraints::new(&air, &AuxTraceRandElements::new(), &coefficients).unwrap();
+        let expected = air.get_boundary_constraints(&AuxTraceRandElements::new(), &coefficients);
+        let divisors = constraints.get_divisors();
+        assert_eq!(expected.main_constraints().len(), divisors.len());
+
+        // compare evaluations of specialized constraints against direct evaluations of the
+        // constraints defined by the AIR; the latter also divide the result by the divisor
+        let mut result = vec![BaseElement::ZERO; divisors.len()];
+        for step in 0..domain.ce_domain_size() {
+            let state = (0..4)
+                .map(|i| BaseElement::new((step * 4 + i) as u128))
+                .collect::<Vec<_>>();
+            constraints.evaluate_main(&state, &domain, step, &mut result);
+
+            let x = domain.get_ce_x_at(step);
+            for (&evaluation, divisor) in result.iter().zip(divisors.iter()) {
+                let group = expected
+                    .main_constraints()
+                    .iter()
+                    .find(|group| group.divisor() == divisor)
+                    .unwrap();
+                let xp = x.exp(group.degree_adjustment().into());
+                let expected = group.evaluate_at(&state, x, xp);
+                assert_eq!(expected, evaluation / divisor.evaluate_at(x));
+            }
+        }
+    }
+
+    #[test]
+    fn boundary_constraints_invalid_assertion() {
+        let assertion = Assertion::single(5, 0, BaseElement::ONE);
+        let air = MockAir::with_assertions(vec![assertion], 16);
+        let coefficients = [(BaseElement::ONE, BaseElement::ONE)];
+        let result = BoundaryConstraints::new(&air, &AuxTraceRandElements::new(), &coefficients);
+        assert_eq!(
+            Some(ProverError::InvalidAssertion(
+                AssertionError::TraceWidthTooShort(5, 4)
+            )),
+            result.err()
+        );
+    }
+
+    #[test]
+    fn boundary_constraints_mismatched_assertion_count() {
+        let assertions = vec![
+            Assertion::single(0, 0, BaseElement::ONE),
+            Assertion::single(1, 0, BaseElement::ONE),
+        ];
+        let air = MockAir::with_assertions(assertions, 16);
+        let coefficients = [(BaseElement::ONE, BaseElement::ONE)];
+        let result = BoundaryConstraints::new(&air, &AuxTraceRandElements::new(), &coefficients);
+        assert_eq!(
+            Some(ProverError::MismatchedMainAssertionCount(1, 2)),
+            result.err()
+        );
+    }
+}
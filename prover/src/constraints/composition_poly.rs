@@ -3,7 +3,7 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use super::ColMatrix;
+use super::{ColMatrix, ProverError};
 use math::{polynom, FieldElement};
 use utils::{collections::Vec, uninit_vector};
 
@@ -18,7 +18,11 @@ pub struct CompositionPoly<E: FieldElement> {
 
 impl<E: FieldElement> CompositionPoly<E> {
     /// Returns a new composition polynomial.
-    pub fn new(coefficients: Vec<E>, trace_length: usize) -> Self {
+    ///
+    /// # Errors
+    /// Returns an error if the degree of the polynomial is smaller than the number of provided
+    /// coefficients minus one.
+    pub fn new(coefficients: Vec<E>, trace_length: usize) -> Result<Self, ProverError> {
         assert!(
             coefficients.len().is_power_of_two(),
             "size of composition polynomial must be a power of 2, but was {}",
@@ -32,19 +36,19 @@ impl<E: FieldElement> CompositionPoly<E> {
             trace_length < coefficients.len(),
             "trace length must be smaller than size of composition polynomial"
         );
-        assert!(
-            coefficients[coefficients.len() - 1] != E::ZERO,
-            "expected composition polynomial of degree {}, but was {}",
-            coefficients.len() - 1,
-            polynom::degree_of(&coefficients)
-        );
+        if coefficients[coefficients.len() - 1] == E::ZERO {
+            return Err(ProverError::MismatchedCompositionPolyDegree(
+                coefficients.len() - 1,
+                polynom::degree_of(&coefficients),
+            ));
+        }
 
         let num_columns = coefficients.len() / trace_length;
         let polys = transpose(coefficients, num_columns);
 
-        CompositionPoly {
+        Ok(CompositionPoly {
             data: ColMatrix::new(polys),
-        }
+        })
     }
 
     // PUBLIC ACCESSORS
@@ -0,0 +1,157 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::ProverError;
+use air::{Air, AuxTraceRandElements, EvaluationFrame};
+use crypto::RandomCoin;
+use math::FieldElement;
+use utils::collections::Vec;
+
+// UNCONSTRAINED COLUMNS
+// ================================================================================================
+
+/// Returns indexes of trace columns which do not affect any of the constraints defined by the
+/// specified AIR; columns of auxiliary segments are indexed after the columns of the main segment.
+///
+/// A column is considered to be constrained if an assertion is placed against it, if it is a
+/// preprocessed column, or if replacing its values in a pseudo-random evaluation frame changes
+/// the evaluation of any transition constraint.
+///
+/// This is a heuristic rather than a guarantee: constraints are evaluated at a single point, and
+/// pseudo-random values are drawn from a public coin of type `R` seeded with an empty seed. Thus,
+/// the result is deterministic, and a constraint which depends on a column may go undetected if
+/// the column does not affect the constraint at this specific point (e.g., when the constraint
+/// is designed to vanish there).
+///
+/// # Errors
+/// Returns an error if a random value could not be drawn from the public coin.
+pub fn find_unconstrained_columns<A, R>(air: &A) -> Result<Vec<usize>, ProverError>
+where
+    A: Air,
+    R: RandomCoin<BaseField = A::BaseField>,
+{
+    let layout = air.trace_layout();
+    let main_width = layout.main_trace_width();
+    let aux_width = layout.aux_trace_width();
+
+    let mut coin = R::new(&[]);
+    let mut draw_elements = |num_elements: usize| {
+        (0..num_elements)
+            .map(|_| coin.draw().map_err(|_| ProverError::RandomCoinError))
+            .collect::<Result<Vec<A::BaseField>, _>>()
+    };
+
+    let mut aux_rand_elements = AuxTraceRandElements::new();
+    for i in 0..layout.num_aux_segments() {
+        let rand_elements = draw_elements(layout.get_aux_segment_rand_elements(i))?;
+        aux_rand_elements.add_segment_elements(rand_elements);
+    }
+
+    // columns against which assertions are placed are constrained; so are preprocessed columns
+    // because their values are fixed by a commitment known to the verifier. assertions against
+    // columns which do not exist are reported when constraints are evaluated.
+    let mut constrained = vec![false; main_width + aux_width];
+    let main_columns = air.get_assertions().into_iter().map(|a| a.column());
+    let aux_columns = air
+        .get_aux_assertions(&aux_rand_elements)
+        .into_iter()
+        .map(|a| main_width + a.column());
+    for column in main_columns.chain(aux_columns) {
+        if let Some(constrained) = constrained.get_mut(column) {
+            *constrained = true;
+        }
+    }
+    constrained[..layout.num_preprocessed_columns()].fill(true);
+
+    // evaluate transition constraints over a random frame
+    let num_periodic_columns = air.get_periodic_column_values().len();
+    let periodic_values = draw_elements(num_periodic_columns)?;
+    let evaluate = |main_frame: &EvaluationFrame<A::BaseField>,
+                    aux_frame: &Option<EvaluationFrame<A::BaseField>>| {
+        let mut result = vec![A::BaseField::ZERO; air.context().num_main_transition_constraints()];
+        air.evaluate_transition(main_frame, &periodic_values, &mut result);
+        if let Some(aux_frame) = aux_frame {
+            let mut aux_result =
+                vec![A::BaseField::ZERO; air.context().num_aux_transition_constraints()];
+            air.evaluate_aux_transition(
+                main_frame,
+                aux_frame,
+                &periodic_values,
+                &aux_rand_elements,
+                &mut aux_result,
+            );
+            result.append(&mut aux_result);
+        }
+        result
+    };
+
+    let main_frame =
+        EvaluationFrame::from_rows(draw_elements(main_width)?, draw_elements(main_width)?);
+    let aux_frame = if aux_width > 0 {
+        let (current, next) = (draw_elements(aux_width)?, draw_elements(aux_width)?);
+        Some(EvaluationFrame::from_rows(current, next))
+    } else {
+        None
+    };
+    let evaluations = evaluate(&main_frame, &aux_frame);
+
+    // then, replace values of each column which is not yet known to be constrained with other
+    // random values, and check whether constraint evaluations change
+    for (column, constrained) in constrained.iter_mut().enumerate() {
+        if *constrained {
+            continue;
+        }
+
+        let values = draw_elements(2)?;
+        let mut main_frame = main_frame.clone();
+        let mut aux_frame = aux_frame.clone();
+        let (frame, idx) = match aux_frame.as_mut() {
+            Some(aux_frame) if column >= main_width => (aux_frame, column - main_width),
+            _ => (&mut main_frame, column),
+        };
+        frame.current_mut()[idx] = values[0];
+        frame.next_mut()[idx] = values[1];
+
+        *constrained = evaluate(&main_frame, &aux_frame) != evaluations;
+    }
+
+    Ok(constrained
+        .iter()
+        .enumerate()
+        .filter(|(_, &constrained)| !constrained)
+        .map(|(column, _)| column)
+        .collect())
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::find_unconstrained_columns;
+    use crate::tests::MockAir;
+    use air::Assertion;
+    use crypto::{hashers::Blake3_256, DefaultRandomCoin};
+    use math::{fields::f128::BaseElement, FieldElement};
+
+    type RandomCoin = DefaultRandomCoin<Blake3_256<BaseElement>>;
+
+    #[test]
+    fn unconstrained_columns() {
+        // mock AIR does not define any transition constraints
+        let air = MockAir::with_trace_length(16);
+        let columns = find_unconstrained_columns::<_, RandomCoin>(&air).unwrap();
+        assert_eq!(vec![0, 1, 2, 3], columns);
+
+        // columns against which assertions are placed are constrained
+        let assertions = vec![
+            Assertion::single(1, 0, BaseElement::ONE),
+            Assertion::periodic(3, 0, 4, BaseElement::ZERO),
+        ];
+        let air = MockAir::with_assertions(assertions, 16);
+        let columns = find_unconstrained_columns::<_, RandomCoin>(&air).unwrap();
+        assert_eq!(vec![0, 2], columns);
+    }
+}
@@ -4,7 +4,7 @@
 // LICENSE file in the root directory of this source tree.
 
 use super::{CompositionPoly, ConstraintDivisor, ProverError, StarkDomain};
-use math::{batch_inversion, fft, FieldElement, StarkField};
+use math::{batch_inversion, fft, polynom, FieldElement, StarkField};
 use utils::{batch_iter_mut, collections::Vec, iter_mut, uninit_vector};
 
 #[cfg(debug_assertions)]
@@ -16,7 +16,13 @@ use utils::iterators::*;
 // CONSTANTS
 // ================================================================================================
 
-const MIN_FRAGMENT_SIZE: usize = 16;
+pub(super) const MIN_FRAGMENT_SIZE: usize = 16;
+
+/// Maximum number of divisor exemption points for which the denominator of the divisor is
+/// evaluated directly at every point of the constraint evaluation domain; for divisors with more
+/// exemption points (e.g., when an execution trace contains virtual rows), the denominator is
+/// evaluated over the entire domain via an FFT.
+const MAX_INLINE_EXEMPTIONS: usize = 16;
 
 // CONSTRAINT EVALUATION TABLE
 // ================================================================================================
@@ -99,12 +105,17 @@ impl<'a, E: FieldElement> ConstraintEvaluationTable<'a, E> {
         self.evaluations.len()
     }
 
+    /// Returns all columns of this table.
+    pub fn columns(&self) -> &[Vec<E>] {
+        &self.evaluations
+    }
+
     // TABLE FRAGMENTS
     // --------------------------------------------------------------------------------------------
 
     /// Break the table into the number of specified fragments. All fragments can be updated
     /// independently - e.g. in different threads.
-    pub fn fragments(&mut self, num_fragments: usize) -> Vec<EvaluationTableFragment<E>> {
+    pub fn fragments(&mut self, num_fragments: usize) -> Vec<EvaluationTableFragment<'_, E>> {
         let fragment_size = self.num_rows() / num_fragments;
         assert!(
             fragment_size >= MIN_FRAGMENT_SIZE,
@@ -179,7 +190,7 @@ impl<'a, E: FieldElement> ConstraintEvaluationTable<'a, E> {
         fft::interpolate_poly_with_offset(&mut combined_poly, &inv_twiddles, self.domain.offset());
 
         let trace_length = self.domain.trace_length();
-        Ok(CompositionPoly::new(combined_poly, trace_length))
+        CompositionPoly::new(combined_poly, trace_length)
     }
 
     // DEBUG HELPERS
@@ -251,6 +262,22 @@ pub struct EvaluationTableFragment<'a, E: FieldElement> {
 }
 
 impl<'a, E: FieldElement> EvaluationTableFragment<'a, E> {
+    /// Returns a fragment which records evaluations into the specified columns; the first row of
+    /// the columns corresponds to the specified row of the constraint evaluation domain.
+    ///
+    /// Unlike fragments of a [ConstraintEvaluationTable], such fragment does not keep track of
+    /// individual transition constraint evaluations in debug mode.
+    pub fn from_columns(offset: usize, evaluations: Vec<&'a mut [E]>) -> Self {
+        EvaluationTableFragment {
+            offset,
+            evaluations,
+            #[cfg(debug_assertions)]
+            tm_evaluations: Vec::new(),
+            #[cfg(debug_assertions)]
+            ta_evaluations: Vec::new(),
+        }
+    }
+
     /// Returns the row at which the fragment starts.
     pub fn offset(&self) -> usize {
         self.offset
@@ -350,6 +377,18 @@ fn acc_column<E: FieldElement>(
                 // compute value * z and add it to the result
                 *acc_value += value.mul_base(z);
             });
+    } else if divisor.exemptions().len() > MAX_INLINE_EXEMPTIONS {
+        // the column represents merged evaluations of transition constraints, but the divisor
+        // has too many exemption points to evaluate e(x) at every point of the domain; so, we
+        // evaluate e(x) over the entire domain first, and then compute: value * e(x) * z.
+        let e = get_exemptions_evaluation(divisor, domain);
+        iter_mut!(result, 1024)
+            .zip(column)
+            .enumerate()
+            .for_each(|(i, (acc_value, value))| {
+                let z = z[i % z.len()];
+                *acc_value += value.mul_base(z * e[i]);
+            });
     } else {
         // the column represents merged evaluations of transition constraints, and divisor has the
         // form of (x^a - 1) / e(x), where e(x) describes the exemption points; thus, to divide
@@ -412,6 +451,40 @@ fn get_inv_evaluation<B: StarkField>(
     batch_inversion(&evaluations)
 }
 
+/// Computes evaluations of the divisor's denominator (the exemption points) over the constraint
+/// evaluation domain.
+fn get_exemptions_evaluation<B: StarkField>(
+    divisor: &ConstraintDivisor<B>,
+    domain: &StarkDomain<B>,
+) -> Vec<B> {
+    // the number of exemption points is always smaller than the trace length, and thus, the
+    // denominator polynomial can be evaluated in the same way as trace polynomials
+    let mut poly = get_poly_from_roots(divisor.exemptions());
+    debug_assert!(poly.len() <= domain.trace_length());
+    poly.resize(domain.trace_length(), B::ZERO);
+    fft::evaluate_poly_with_offset(
+        &poly,
+        domain.trace_twiddles(),
+        domain.offset(),
+        domain.trace_to_ce_blowup(),
+    )
+}
+
+/// Returns a polynomial in coefficient form which evaluates to zero at the specified `roots`.
+///
+/// The polynomial is computed by recursively multiplying polynomials built from the two halves
+/// of the roots, using FFT-based multiplication for large polynomials.
+fn get_poly_from_roots<B: StarkField>(roots: &[B]) -> Vec<B> {
+    if roots.len() <= MAX_INLINE_EXEMPTIONS {
+        roots.iter().fold(vec![B::ONE], |poly, &root| {
+            polynom::mul(&poly, &[-root, B::ONE])
+        })
+    } else {
+        let (left, right) = roots.split_at(roots.len() / 2);
+        polynom::mul_fft(&get_poly_from_roots(left), &get_poly_from_roots(right))
+    }
+}
+
 // DEBUG HELPERS
 // ================================================================================================
 
@@ -465,7 +538,7 @@ fn get_transition_poly_degree<E: FieldElement>(
         .map(|(&c, &d)| c / E::from(d))
         .collect::<Vec<_>>();
     fft::interpolate_poly(&mut evaluations, inv_twiddles);
-    math::polynom::degree_of(&evaluations)
+    polynom::degree_of(&evaluations)
 }
 
 /// Makes sure that the post-division degree of the polynomial matches the expected degree
@@ -491,10 +564,10 @@ fn validate_column_degree<B: StarkField, E: FieldElement<BaseField = B>>(
     fft::interpolate_poly_with_offset(&mut evaluations, &inv_twiddles, domain.offset());
     let poly = evaluations;
 
-    if expected_degree != math::polynom::degree_of(&poly) {
+    if expected_degree != polynom::degree_of(&poly) {
         return Err(ProverError::MismatchedConstraintPolynomialDegree(
             expected_degree,
-            math::polynom::degree_of(&poly),
+            polynom::degree_of(&poly),
         ));
     }
     Ok(())
@@ -509,10 +582,36 @@ fn evaluate_divisor<E: FieldElement>(
     domain_size: usize,
     domain_offset: E::BaseField,
 ) -> Vec<E> {
-    let g = E::BaseField::get_root_of_unity(domain_size.trailing_zeros());
-    let domain = math::get_power_series_with_offset(g, domain_offset, domain_size);
-    domain
-        .into_iter()
+    math::domain::Domain::with_offset(domain_size, domain_offset)
+        .iter()
         .map(|x| E::from(divisor.evaluate_at(x)))
         .collect()
 }
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::{get_exemptions_evaluation, ConstraintDivisor, StarkDomain};
+    use math::{fft, fields::f128::BaseElement, StarkField};
+
+    #[test]
+    fn exemptions_evaluation() {
+        let trace_length = 64;
+        let domain =
+            StarkDomain::from_twiddles(fft::get_twiddles(trace_length), 8, BaseElement::GENERATOR);
+
+        // use enough exemptions to exercise FFT-based multiplication of the exemption terms
+        for num_exemptions in [1, 17, 33] {
+            let divisor =
+                ConstraintDivisor::<BaseElement>::from_transition(trace_length, num_exemptions);
+            let evaluations = get_exemptions_evaluation(&divisor, &domain);
+            assert_eq!(domain.ce_domain_size(), evaluations.len());
+            for (i, &evaluation) in evaluations.iter().enumerate() {
+                let x = domain.get_ce_x_at(i);
+                assert_eq!(divisor.evaluate_exemptions_at(x), evaluation);
+            }
+        }
+    }
+}
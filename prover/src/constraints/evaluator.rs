@@ -4,15 +4,17 @@
 // LICENSE file in the root directory of this source tree.
 
 use super::{
-    super::TraceLde, evaluation_table::EvaluationTableFragment, BoundaryConstraints,
-    ConstraintEvaluationTable, PeriodicValueTable, StarkDomain,
+    super::{ProgressMonitor, ProvingPhase, TraceLde},
+    evaluation_table::{EvaluationTableFragment, MIN_FRAGMENT_SIZE},
+    BoundaryConstraints, ConstraintEvaluationTable, PeriodicValueTable, ProverError, StarkDomain,
 };
 use air::{
     Air, AuxTraceRandElements, ConstraintCompositionCoefficients, EvaluationFrame,
     TransitionConstraints,
 };
+use core::sync::atomic::{AtomicUsize, Ordering};
 use math::FieldElement;
-use utils::iter_mut;
+use utils::{collections::Vec, iter_mut};
 
 #[cfg(feature = "concurrent")]
 use utils::{iterators::*, rayon};
@@ -23,6 +25,11 @@ use utils::{iterators::*, rayon};
 #[cfg(feature = "concurrent")]
 const MIN_CONCURRENT_DOMAIN_SIZE: usize = 8192;
 
+/// Maximum number of fragments into which the constraint evaluation table is broken when
+/// constraints are evaluated in a single thread; this way, progress can be reported and
+/// cancellation can be checked while constraints are being evaluated.
+const MAX_SEQUENTIAL_FRAGMENTS: usize = 16;
+
 // CONSTRAINT EVALUATOR
 // ================================================================================================
 
@@ -39,11 +46,14 @@ impl<'a, A: Air, E: FieldElement<BaseField = A::BaseField>> ConstraintEvaluator<
     // --------------------------------------------------------------------------------------------
     /// Returns a new evaluator which can be used to evaluate transition and boundary constraints
     /// over extended execution trace.
+    ///
+    /// # Errors
+    /// Returns an error if assertions defined by the AIR are not valid.
     pub fn new(
         air: &'a A,
         aux_rand_elements: AuxTraceRandElements<E>,
         composition_coefficients: ConstraintCompositionCoefficients<E>,
-    ) -> Self {
+    ) -> Result<Self, ProverError> {
         // build transition constraint groups; these will be used to compose transition constraint
         // evaluations
         let transition_constraints =
@@ -55,15 +65,15 @@ impl<'a, A: Air, E: FieldElement<BaseField = A::BaseField>> ConstraintEvaluator<
         // build boundary constraint groups; these will be used to evaluate and compose boundary
         // constraint evaluations.
         let boundary_constraints =
-            BoundaryConstraints::new(air, &aux_rand_elements, &composition_coefficients.boundary);
+            BoundaryConstraints::new(air, &aux_rand_elements, &composition_coefficients.boundary)?;
 
-        ConstraintEvaluator {
+        Ok(ConstraintEvaluator {
             air,
             boundary_constraints,
             transition_constraints,
             aux_rand_elements,
             periodic_values,
-        }
+        })
     }
 
     // EVALUATOR
@@ -71,11 +81,18 @@ impl<'a, A: Air, E: FieldElement<BaseField = A::BaseField>> ConstraintEvaluator<
     /// Evaluates constraints against the provided extended execution trace. Constraints are
     /// evaluated over a constraint evaluation domain. This is an optimization because constraint
     /// evaluation domain can be many times smaller than the full LDE domain.
+    ///
+    /// Progress is reported to the `monitor` after each fragment of the evaluation table is
+    /// evaluated.
+    ///
+    /// # Errors
+    /// Returns an error if proof generation is cancelled.
     pub fn evaluate(
         self,
         trace: &TraceLde<E>,
         domain: &'a StarkDomain<E::BaseField>,
-    ) -> ConstraintEvaluationTable<'a, E> {
+        monitor: ProgressMonitor,
+    ) -> Result<ConstraintEvaluationTable<'a, E>, ProverError> {
         assert_eq!(
             trace.trace_len(),
             domain.lde_domain_size(),
@@ -99,41 +116,105 @@ impl<'a, A: Air, E: FieldElement<BaseField = A::BaseField>> ConstraintEvaluator<
 
         // when `concurrent` feature is enabled, break the evaluation table into multiple fragments
         // to evaluate them into multiple threads; unless the constraint evaluation domain is small,
-        // then don't bother with concurrent evaluation
+        // then don't bother with concurrent evaluation. otherwise, the table is still broken into
+        // a few fragments evaluated one after another.
 
         #[cfg(not(feature = "concurrent"))]
-        let num_fragments = 1;
+        let num_fragments =
+            (domain.ce_domain_size() / MIN_FRAGMENT_SIZE).clamp(1, MAX_SEQUENTIAL_FRAGMENTS);
 
         #[cfg(feature = "concurrent")]
         let num_fragments = if domain.ce_domain_size() >= MIN_CONCURRENT_DOMAIN_SIZE {
             rayon::current_num_threads().next_power_of_two()
         } else {
-            1
+            (domain.ce_domain_size() / MIN_FRAGMENT_SIZE).clamp(1, MAX_SEQUENTIAL_FRAGMENTS)
         };
 
         // evaluate constraints for each fragment; if the trace consist of multiple segments
         // we evaluate constraints for all segments. otherwise, we evaluate constraints only
         // for the main segment.
+        let phase = ProvingPhase::ConstraintEvaluation;
+        monitor.start(phase, num_fragments)?;
+        let num_completed = AtomicUsize::new(0);
         let mut fragments = evaluation_table.fragments(num_fragments);
-        iter_mut!(fragments).for_each(|fragment| {
-            if self.air.trace_info().is_multi_segment() {
-                self.evaluate_fragment_full(trace, domain, fragment);
-            } else {
-                self.evaluate_fragment_main(trace, domain, fragment);
-            }
-        });
+        iter_mut!(fragments).try_for_each(|fragment| {
+            monitor.check()?;
+            self.evaluate_fragment(trace, domain, fragment);
+            monitor.update(phase, num_completed.fetch_add(1, Ordering::Relaxed) + 1)
+        })?;
 
         // when in debug mode, make sure expected transition constraint degrees align with
         // actual degrees we got during constraint evaluation
         #[cfg(debug_assertions)]
         evaluation_table.validate_transition_degrees();
 
-        evaluation_table
+        Ok(evaluation_table)
+    }
+
+    /// Evaluates constraints against the provided extended execution trace at steps
+    /// `start_step..end_step` of the constraint evaluation domain.
+    ///
+    /// This allows disjoint ranges of the constraint evaluation domain to be evaluated
+    /// independently (e.g., by different workers) using the same constraint groups and
+    /// composition coefficients. The returned columns are the same as rows
+    /// `start_step..end_step` of the table returned by [ConstraintEvaluator::evaluate()]: the
+    /// first column contains merged transition constraint evaluations, and the remaining columns
+    /// contain boundary constraint evaluations merged by their common divisors.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * The length of the extended trace is not consistent with the evaluation domain.
+    /// * `start_step` is not smaller than `end_step`, or `end_step` is greater than the size of
+    ///   the constraint evaluation domain.
+    #[allow(dead_code)]
+    pub fn evaluate_range(
+        &self,
+        trace: &TraceLde<E>,
+        domain: &StarkDomain<E::BaseField>,
+        start_step: usize,
+        end_step: usize,
+    ) -> Vec<Vec<E>> {
+        assert_eq!(
+            trace.trace_len(),
+            domain.lde_domain_size(),
+            "extended trace length is not consistent with evaluation domain"
+        );
+        assert!(
+            start_step < end_step && end_step <= domain.ce_domain_size(),
+            "step range {start_step}..{end_step} is not a valid range of the constraint evaluation domain of size {}",
+            domain.ce_domain_size()
+        );
+
+        let num_columns = 1 + self.boundary_constraints.num_divisors();
+        let mut evaluations = vec![vec![E::ZERO; end_step - start_step]; num_columns];
+        let columns = evaluations
+            .iter_mut()
+            .map(|column| column.as_mut_slice())
+            .collect();
+        let mut fragment = EvaluationTableFragment::from_columns(start_step, columns);
+        self.evaluate_fragment(trace, domain, &mut fragment);
+
+        evaluations
     }
 
     // EVALUATION HELPERS
     // --------------------------------------------------------------------------------------------
 
+    /// Evaluates constraints for a single fragment of the evaluation table; if the trace consists
+    /// of multiple segments, constraints are evaluated for all segments.
+    fn evaluate_fragment(
+        &self,
+        trace: &TraceLde<E>,
+        domain: &StarkDomain<A::BaseField>,
+        fragment: &mut EvaluationTableFragment<E>,
+    ) {
+        if self.air.trace_info().is_multi_segment() {
+            self.evaluate_fragment_full(trace, domain, fragment);
+        } else {
+            self.evaluate_fragment_main(trace, domain, fragment);
+        }
+    }
+
     /// Evaluates constraints for a single fragment of the evaluation table.
     ///
     /// This evaluates constraints only over the main segment of the execution trace.
@@ -335,3 +416,92 @@ impl<'a, A: Air, E: FieldElement<BaseField = A::BaseField>> ConstraintEvaluator<
         self.transition_constraints.num_aux_constraints()
     }
 }
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::ConstraintEvaluator;
+    use crate::{
+        tests::{build_fib_trace, MockAir},
+        RowMatrix, StarkDomain, Trace, TraceCommitment,
+    };
+    use air::{Assertion, AuxTraceRandElements, ConstraintCompositionCoefficients};
+    use crypto::hashers::Blake3_256;
+    use math::{fields::f128::BaseElement, FieldElement};
+    use rand_utils::rand_value;
+    use utils::collections::Vec;
+
+    type Blake3 = Blake3_256<BaseElement>;
+
+    #[test]
+    fn evaluate_range() {
+        let trace_length = 8;
+        let assertions = vec![
+            Assertion::single(0, 0, BaseElement::ONE),
+            Assertion::single(1, trace_length - 1, BaseElement::new(987)),
+        ];
+        let air = MockAir::with_declared_assertions(assertions, trace_length);
+        let domain = StarkDomain::new(&air);
+        let trace_comm = build_trace_commitment(trace_length, &domain);
+        let trace_lde = trace_comm.trace_table();
+
+        let coefficients = ConstraintCompositionCoefficients {
+            transition: vec![(rand_value(), rand_value())],
+            boundary: vec![(rand_value(), rand_value()), (rand_value(), rand_value())],
+        };
+        let evaluator =
+            ConstraintEvaluator::new(&air, AuxTraceRandElements::new(), coefficients).unwrap();
+
+        // evaluate the constraint evaluation domain in disjoint ranges
+        let ce_domain_size = domain.ce_domain_size();
+        let ranges = [(0, 5), (5, 12), (12, ce_domain_size)];
+        let mut columns: Vec<Vec<BaseElement>> = Vec::new();
+        for (start_step, end_step) in ranges {
+            let range = evaluator.evaluate_range(trace_lde, &domain, start_step, end_step);
+            assert_eq!(3, range.len());
+            columns.resize(range.len(), Vec::new());
+            for (column, values) in columns.iter_mut().zip(range) {
+                assert_eq!(end_step - start_step, values.len());
+                column.extend(values);
+            }
+        }
+
+        // together, the ranges must match evaluations over the entire domain
+        let expected = evaluator.evaluate_range(trace_lde, &domain, 0, ce_domain_size);
+        assert_eq!(expected, columns);
+    }
+
+    #[test]
+    #[should_panic(expected = "step range 4..4 is not a valid range")]
+    fn evaluate_range_empty() {
+        let trace_length = 8;
+        let assertions = vec![Assertion::single(0, 0, BaseElement::ONE)];
+        let air = MockAir::with_declared_assertions(assertions, trace_length);
+        let domain = StarkDomain::new(&air);
+        let trace_comm = build_trace_commitment(trace_length, &domain);
+
+        let coefficients = ConstraintCompositionCoefficients {
+            transition: vec![(BaseElement::ONE, BaseElement::ONE)],
+            boundary: vec![(BaseElement::ONE, BaseElement::ONE)],
+        };
+        let evaluator =
+            ConstraintEvaluator::new(&air, AuxTraceRandElements::new(), coefficients).unwrap();
+        evaluator.evaluate_range(trace_comm.trace_table(), &domain, 4, 4);
+    }
+
+    // HELPER FUNCTIONS
+    // --------------------------------------------------------------------------------------------
+
+    fn build_trace_commitment(
+        trace_length: usize,
+        domain: &StarkDomain<BaseElement>,
+    ) -> TraceCommitment<BaseElement, Blake3> {
+        let trace = build_fib_trace(trace_length * 2);
+        let trace_polys = trace.main_segment().interpolate_columns();
+        let trace_lde = RowMatrix::evaluate_polys_over::<8>(&trace_polys, domain);
+        let trace_tree = trace_lde.commit_to_rows::<Blake3>();
+        TraceCommitment::new(trace_lde, trace_tree, domain.trace_to_lde_blowup())
+    }
+}
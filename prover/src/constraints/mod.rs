@@ -22,3 +22,8 @@ pub use evaluation_table::ConstraintEvaluationTable;
 
 mod commitment;
 pub use commitment::ConstraintCommitment;
+
+#[cfg(debug_assertions)]
+mod coverage;
+#[cfg(debug_assertions)]
+pub use coverage::find_unconstrained_columns;
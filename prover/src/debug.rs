@@ -0,0 +1,274 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{Prover, ProverError};
+use air::{proof::StarkProof, FieldExtension};
+use math::{
+    fields::{CubeExtension, QuadExtension},
+    FieldElement, StarkField,
+};
+use utils::collections::Vec;
+
+// DEBUG PROVER
+// ================================================================================================
+
+/// A wrapper around a [Prover] which records intermediate artifacts of proof generation.
+///
+/// The proofs generated by this prover are identical to the proofs generated by the underlying
+/// prover. In addition to a proof, [DebugProver::prove()] returns a [DebugTrace] which contains
+/// trace polynomials, constraint evaluations, composition polynomials, FRI layers and query
+/// positions computed while generating the proof. Debug traces of two runs can be compared via
+/// [DebugTrace::diff()] to find the first phase at which the runs diverged.
+///
+/// Recording artifacts requires copying large amounts of data, and thus, this prover should be
+/// used only for debugging.
+pub struct DebugProver<P: Prover> {
+    prover: P,
+}
+
+impl<P: Prover> DebugProver<P> {
+    /// Returns a new debug prover wrapping the specified `prover`.
+    pub fn new(prover: P) -> Self {
+        Self { prover }
+    }
+
+    /// Returns a reference to the underlying prover.
+    pub fn prover(&self) -> &P {
+        &self.prover
+    }
+
+    /// Returns a STARK proof attesting to a correct execution of a computation defined by the
+    /// provided trace together with a record of all intermediate artifacts computed while
+    /// generating the proof.
+    ///
+    /// # Errors
+    /// Returns an error if the underlying prover fails to generate a proof.
+    pub fn prove(
+        &self,
+        trace: P::Trace,
+    ) -> Result<(StarkProof, DebugTrace<P::BaseField>), ProverError> {
+        let mut debug_trace = DebugTrace::new();
+        let proof = match self.prover.options().field_extension() {
+            FieldExtension::None => self
+                .prover
+                .generate_proof_with_debug_trace::<P::BaseField>(trace, Some(&mut debug_trace))?,
+            FieldExtension::Quadratic => {
+                if !<QuadExtension<P::BaseField>>::is_supported() {
+                    return Err(ProverError::UnsupportedFieldExtension(2));
+                }
+                self.prover
+                    .generate_proof_with_debug_trace::<QuadExtension<P::BaseField>>(
+                        trace,
+                        Some(&mut debug_trace),
+                    )?
+            }
+            FieldExtension::Cubic => {
+                if !<CubeExtension<P::BaseField>>::is_supported() {
+                    return Err(ProverError::UnsupportedFieldExtension(3));
+                }
+                self.prover
+                    .generate_proof_with_debug_trace::<CubeExtension<P::BaseField>>(
+                        trace,
+                        Some(&mut debug_trace),
+                    )?
+            }
+        };
+        Ok((proof, debug_trace))
+    }
+}
+
+// PROOF ARTIFACT
+// ================================================================================================
+
+/// Identifies an intermediate artifact recorded during proof generation.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum ProofArtifact {
+    /// Polynomials (in coefficient form) interpolated from the columns of the main trace segment.
+    MainTracePolys,
+    /// Polynomials (in coefficient form) interpolated from the columns of the auxiliary trace
+    /// segment with the specified index.
+    AuxTracePolys(usize),
+    /// Merged constraint evaluations over the constraint evaluation domain; each column contains
+    /// evaluations of constraints with identical divisors.
+    ConstraintEvaluations,
+    /// Columns of the constraint composition polynomial in coefficient form.
+    CompositionPoly,
+    /// Coefficients of the DEEP composition polynomial.
+    DeepCompositionPoly,
+    /// Evaluations of the DEEP composition polynomial over the LDE domain.
+    DeepEvaluations,
+    /// Evaluations committed to in the FRI layer with the specified index. The evaluations are
+    /// stored in the same transposed form in which they are committed.
+    FriLayer(usize),
+    /// Coefficients of the FRI remainder polynomial.
+    FriRemainder,
+}
+
+// DEBUG TRACE
+// ================================================================================================
+
+/// A record of intermediate artifacts computed during generation of a single proof.
+///
+/// Artifacts are stored in the order in which they were computed. Values of each artifact are
+/// stored as columns of base field elements; for artifacts defined over an extension field,
+/// every extension field element is represented by its base field components.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DebugTrace<B: StarkField> {
+    artifacts: Vec<(ProofArtifact, Vec<Vec<B>>)>,
+    query_positions: Vec<usize>,
+}
+
+impl<B: StarkField> DebugTrace<B> {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    /// Returns a new empty debug trace.
+    pub fn new() -> Self {
+        Self {
+            artifacts: Vec::new(),
+            query_positions: Vec::new(),
+        }
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns an iterator over all recorded artifacts in the order in which they were computed.
+    pub fn artifacts(&self) -> impl Iterator<Item = (ProofArtifact, &[Vec<B>])> {
+        self.artifacts
+            .iter()
+            .map(|(artifact, columns)| (*artifact, columns.as_slice()))
+    }
+
+    /// Returns columns of the specified artifact, or None if the artifact was not recorded.
+    pub fn get(&self, artifact: ProofArtifact) -> Option<&[Vec<B>]> {
+        self.artifacts
+            .iter()
+            .find(|(a, _)| *a == artifact)
+            .map(|(_, columns)| columns.as_slice())
+    }
+
+    /// Returns query positions drawn during proof generation.
+    pub fn query_positions(&self) -> &[usize] {
+        &self.query_positions
+    }
+
+    /// Compares this debug trace to the `other` debug trace and returns the first difference
+    /// between them, or None if the traces are identical.
+    ///
+    /// Artifacts are compared in the order in which they were computed, and thus, the returned
+    /// difference identifies the earliest phase of proof generation at which two runs diverged.
+    pub fn diff(&self, other: &Self) -> Option<DebugTraceDiff> {
+        for (i, (artifact, columns)) in self.artifacts.iter().enumerate() {
+            let (other_artifact, other_columns) = match other.artifacts.get(i) {
+                Some((a, c)) if a == artifact => (a, c),
+                _ => return Some(DebugTraceDiff::MissingArtifact(*artifact)),
+            };
+
+            if columns.len() != other_columns.len()
+                || columns
+                    .iter()
+                    .zip(other_columns.iter())
+                    .any(|(a, b)| a.len() != b.len())
+            {
+                return Some(DebugTraceDiff::ShapeMismatch(*other_artifact));
+            }
+
+            for (col_idx, (a, b)) in columns.iter().zip(other_columns.iter()).enumerate() {
+                if let Some(row_idx) = a.iter().zip(b.iter()).position(|(x, y)| x != y) {
+                    return Some(DebugTraceDiff::ValueMismatch(*artifact, col_idx, row_idx));
+                }
+            }
+        }
+
+        if let Some((artifact, _)) = other.artifacts.get(self.artifacts.len()) {
+            return Some(DebugTraceDiff::MissingArtifact(*artifact));
+        }
+
+        if self.query_positions != other.query_positions {
+            return Some(DebugTraceDiff::QueryPositionsMismatch);
+        }
+
+        None
+    }
+
+    // RECORDING
+    // --------------------------------------------------------------------------------------------
+
+    /// Records the provided columns as values of the specified artifact.
+    pub(crate) fn record<'a, E, I>(&mut self, artifact: ProofArtifact, columns: I)
+    where
+        E: FieldElement<BaseField = B> + 'a,
+        I: IntoIterator<Item = &'a [E]>,
+    {
+        let columns = columns
+            .into_iter()
+            .map(|column| E::slice_as_base_elements(column).to_vec())
+            .collect();
+        self.artifacts.push((artifact, columns));
+    }
+
+    /// Records query positions drawn during proof generation.
+    pub(crate) fn record_query_positions(&mut self, positions: &[usize]) {
+        self.query_positions = positions.to_vec();
+    }
+}
+
+// DEBUG TRACE DIFF
+// ================================================================================================
+
+/// Describes the first difference between two debug traces.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum DebugTraceDiff {
+    /// The specified artifact was recorded in one of the traces but not at the same position in
+    /// the other trace.
+    MissingArtifact(ProofArtifact),
+    /// The specified artifact has different number of columns or different column lengths in
+    /// the two traces.
+    ShapeMismatch(ProofArtifact),
+    /// Values of the specified artifact differ at the specified column and row (in terms of base
+    /// field elements).
+    ValueMismatch(ProofArtifact, usize, usize),
+    /// All artifacts are identical, but the query positions differ.
+    QueryPositionsMismatch,
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::{DebugProver, DebugTraceDiff, ProofArtifact};
+    use crate::{
+        tests::{build_fib_trace, FibProver},
+        Prover,
+    };
+    use air::{FieldExtension, ProofOptions};
+
+    #[test]
+    fn debug_prover_records_artifacts() {
+        let options = ProofOptions::new(28, 8, 0, FieldExtension::Quadratic, 4, 7);
+        let debug_prover = DebugProver::new(FibProver::new(options));
+        let (proof, debug_trace) = debug_prover.prove(build_fib_trace(16)).unwrap();
+
+        // the proof is the same as the one generated by the underlying prover
+        let expected = debug_prover.prover().prove(build_fib_trace(16)).unwrap();
+        assert_eq!(expected.to_bytes(), proof.to_bytes());
+
+        // all phases are recorded, and proof generation is deterministic
+        assert!(debug_trace.get(ProofArtifact::MainTracePolys).is_some());
+        assert!(debug_trace.get(ProofArtifact::DeepEvaluations).is_some());
+        assert!(debug_trace.get(ProofArtifact::FriRemainder).is_some());
+        assert_eq!(28, debug_trace.query_positions().len());
+        let (_, other_trace) = debug_prover.prove(build_fib_trace(16)).unwrap();
+        assert_eq!(None, debug_trace.diff(&other_trace));
+
+        // a different trace results in a difference in the trace polynomials
+        let (_, other_trace) = debug_prover.prove(build_fib_trace(32)).unwrap();
+        assert_eq!(
+            Some(DebugTraceDiff::ShapeMismatch(ProofArtifact::MainTracePolys)),
+            debug_trace.diff(&other_trace)
+        );
+    }
+}
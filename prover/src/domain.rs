@@ -4,8 +4,11 @@
 // LICENSE file in the root directory of this source tree.
 
 use air::Air;
-use math::{fft, get_power_series, StarkField};
-use utils::collections::Vec;
+use math::{domain::Domain, fft, StarkField};
+use utils::{
+    collections::Vec, string::ToString, ByteReader, ByteWriter, Deserializable,
+    DeserializationError, Serializable,
+};
 
 // TYPES AND INTERFACES
 // ================================================================================================
@@ -15,6 +18,10 @@ pub struct StarkDomain<B: StarkField> {
     /// vector is half the length of the trace domain size.
     trace_twiddles: Vec<B>,
 
+    /// Twiddles which can be used to interpolate polynomials from evaluations over the trace
+    /// domain. Length of this vector is half the length of the trace domain size.
+    trace_inv_twiddles: Vec<B>,
+
     /// [g^i for i in (0..ce_domain_size)] where g is the constraint evaluation domain generator.
     ce_domain: Vec<B>,
 
@@ -37,13 +44,14 @@ impl<B: StarkField> StarkDomain<B> {
     /// Returns a new STARK domain initialized with the provided `context`.
     pub fn new<A: Air<BaseField = B>>(air: &A) -> Self {
         let trace_twiddles = fft::get_twiddles(air.trace_length());
+        let trace_inv_twiddles = fft::get_inv_twiddles(air.trace_length());
 
         // build constraint evaluation domain
-        let domain_gen = B::get_root_of_unity(air.ce_domain_size().ilog2());
-        let ce_domain = get_power_series(domain_gen, air.ce_domain_size());
+        let ce_domain = Domain::new(air.ce_domain_size()).elements();
 
         StarkDomain {
             trace_twiddles,
+            trace_inv_twiddles,
             ce_domain,
             ce_to_lde_blowup: air.lde_domain_size() / air.ce_domain_size(),
             ce_domain_mod_mask: air.ce_domain_size() - 1,
@@ -63,12 +71,13 @@ impl<B: StarkField> StarkDomain<B> {
             "blowup factor must be a power of 2"
         );
 
+        let trace_inv_twiddles = fft::get_inv_twiddles(trace_twiddles.len() * 2);
         let ce_domain_size = trace_twiddles.len() * blowup_factor * 2;
-        let domain_gen = B::get_root_of_unity(ce_domain_size.ilog2());
-        let ce_domain = get_power_series(domain_gen, ce_domain_size);
+        let ce_domain = Domain::new(ce_domain_size).elements();
 
         StarkDomain {
             trace_twiddles,
+            trace_inv_twiddles,
             ce_domain,
             ce_to_lde_blowup: 1,
             ce_domain_mod_mask: ce_domain_size - 1,
@@ -76,6 +85,21 @@ impl<B: StarkField> StarkDomain<B> {
         }
     }
 
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns true if this domain has the same trace, constraint evaluation, and LDE domains as
+    /// the domain which would be built for the specified `air`.
+    ///
+    /// A domain built for one instance of a computation can be reused for any other instance
+    /// for which this function returns true.
+    pub fn matches<A: Air<BaseField = B>>(&self, air: &A) -> bool {
+        self.trace_length() == air.trace_length()
+            && self.ce_domain_size() == air.ce_domain_size()
+            && self.lde_domain_size() == air.lde_domain_size()
+            && self.offset() == air.domain_offset()
+    }
+
     // EXECUTION TRACE
     // --------------------------------------------------------------------------------------------
 
@@ -89,6 +113,11 @@ impl<B: StarkField> StarkDomain<B> {
         &self.trace_twiddles
     }
 
+    /// Returns twiddles which can be used to interpolate trace polynomials.
+    pub fn trace_inv_twiddles(&self) -> &[B] {
+        &self.trace_inv_twiddles
+    }
+
     /// Returns blowup factor from trace to constraint evaluation domain.
     pub fn trace_to_ce_blowup(&self) -> usize {
         self.ce_domain_size() / self.trace_length()
@@ -154,3 +183,79 @@ impl<B: StarkField> StarkDomain<B> {
         self.domain_offset
     }
 }
+
+// SERIALIZATION / DESERIALIZATION
+// ================================================================================================
+
+impl<B: StarkField> Serializable for StarkDomain<B> {
+    /// Serializes `self` and writes the resulting bytes into the `target` writer.
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write_usize(self.trace_twiddles.len());
+        target.write(&self.trace_twiddles);
+        target.write(&self.trace_inv_twiddles);
+        target.write_usize(self.ce_domain.len());
+        target.write(&self.ce_domain);
+        target.write_usize(self.ce_to_lde_blowup);
+        target.write(self.domain_offset);
+    }
+}
+
+impl<B: StarkField> Deserializable for StarkDomain<B> {
+    /// Reads a STARK domain from the specified `source` and returns the result.
+    ///
+    /// Domain values are not recomputed, as this would defeat the purpose of serializing the
+    /// domain; only the sizes of the domains and the first elements of each vector are checked.
+    /// Thus, the source must be trusted; `DomainCache` ensures this by
+    /// verifying a checksum of the serialized domain before deserializing it.
+    ///
+    /// # Errors
+    /// Returns an error if a valid STARK domain could not be read from the specified `source`.
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let max_domain_size = 1_usize << B::TWO_ADICITY.min(usize::BITS - 1);
+
+        let num_twiddles = source.read_usize_bounded(max_domain_size / 2)?;
+        if !num_twiddles.is_power_of_two() {
+            return Err(DeserializationError::InvalidValue(format!(
+                "number of trace twiddles must be a power of two, but was {num_twiddles}"
+            )));
+        }
+        let trace_twiddles = B::read_batch_from(source, num_twiddles)?;
+        let trace_inv_twiddles = B::read_batch_from(source, num_twiddles)?;
+
+        let ce_domain_size = source.read_usize_bounded(max_domain_size)?;
+        if !ce_domain_size.is_power_of_two() || ce_domain_size < num_twiddles * 2 {
+            return Err(DeserializationError::InvalidValue(format!(
+                "constraint evaluation domain size {ce_domain_size} is invalid for trace length {}",
+                num_twiddles * 2
+            )));
+        }
+        let ce_domain = B::read_batch_from(source, ce_domain_size)?;
+
+        let ce_to_lde_blowup = source.read_usize_bounded(max_domain_size / ce_domain_size)?;
+        if !ce_to_lde_blowup.is_power_of_two() {
+            return Err(DeserializationError::InvalidValue(format!(
+                "constraint evaluation to LDE blowup must be a power of two, but was {ce_to_lde_blowup}"
+            )));
+        }
+        let domain_offset = B::read_from(source)?;
+
+        if trace_twiddles[0] != B::ONE
+            || trace_inv_twiddles[0] != B::ONE
+            || ce_domain[0] != B::ONE
+            || ce_domain[1] != B::get_root_of_unity(ce_domain_size.ilog2())
+        {
+            return Err(DeserializationError::InvalidValue(
+                "domain values are inconsistent with domain sizes".to_string(),
+            ));
+        }
+
+        Ok(StarkDomain {
+            trace_twiddles,
+            trace_inv_twiddles,
+            ce_domain,
+            ce_to_lde_blowup,
+            ce_domain_mod_mask: ce_domain_size - 1,
+            domain_offset,
+        })
+    }
+}
@@ -0,0 +1,233 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::StarkDomain;
+use air::Air;
+use crypto::{hashers::Blake3_256, Digest, Hasher};
+use log::debug;
+use math::StarkField;
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+    process,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+use utils::{Deserializable, Serializable};
+
+// CONSTANTS
+// ================================================================================================
+
+/// Number of bytes in the checksum which prefixes every domain file.
+const CHECKSUM_BYTES: usize = 32;
+
+/// Counter used to make names of temporary domain files unique within a process.
+static TMP_FILE_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+// DOMAIN CACHE
+// ================================================================================================
+
+/// A cache of STARK domains stored in files of a local directory.
+///
+/// Building a STARK domain for large traces (e.g., 2^24 steps or more) takes a significant amount
+/// of time which is spent on every proof generation request. A domain cache allows multiple
+/// provers (including provers running in different processes) to build a domain once, and then
+/// read it from a file for all subsequent proofs.
+///
+/// Domains are stored in files named after the field modulus, the sizes of trace, constraint
+/// evaluation, and LDE domains, and the domain offset; all of these uniquely define the content
+/// of a domain. Each file starts with a BLAKE3 checksum of these parameters and of the serialized
+/// domain; a file whose checksum does not match its content or the requested domain is treated
+/// as missing. The cache is best-effort: if a domain file is missing or could not be read, the
+/// domain is built from scratch, and if a domain file could not be written, proof generation
+/// proceeds without caching the domain.
+///
+/// Domain files are read into memory rather than memory-mapped: the checksum has to be computed
+/// over the entire file anyway, and the prover needs the domain in owned vectors.
+///
+/// A cache can be supplied to the prover via [Prover::domain_cache()](crate::Prover::domain_cache).
+#[derive(Debug, Clone)]
+pub struct DomainCache {
+    dir: PathBuf,
+}
+
+impl DomainCache {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    /// Returns a new cache which stores domains in the specified directory; the directory is
+    /// created when the first domain is stored in it.
+    pub fn new<P: Into<PathBuf>>(dir: P) -> Self {
+        DomainCache { dir: dir.into() }
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the directory in which this cache stores domains.
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Returns the path of the file in which the domain for the specified `air` is stored.
+    pub fn domain_path<A: Air>(&self, air: &A) -> PathBuf {
+        let modulus = A::BaseField::get_modulus_le_bytes()
+            .iter()
+            .rev()
+            .map(|byte| format!("{byte:02x}"))
+            .collect::<String>();
+        self.dir.join(format!(
+            "stark_domain_{modulus}_{}_{}_{}_{}.bin",
+            air.trace_length(),
+            air.ce_domain_size(),
+            air.lde_domain_size(),
+            air.domain_offset()
+        ))
+    }
+
+    // DOMAIN RETRIEVAL
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the STARK domain for the specified `air` read from this cache; if the domain is not
+    /// in the cache, the domain is built and stored in the cache.
+    pub fn get_or_build<A: Air>(&self, air: &A) -> StarkDomain<A::BaseField> {
+        let path = self.domain_path(air);
+        if let Some(domain) = read_domain(&path, air) {
+            return domain;
+        }
+
+        let domain = StarkDomain::new(air);
+        if let Err(err) = write_domain(&path, air, &domain) {
+            debug!("Failed to cache domain in {}: {}", path.display(), err);
+        }
+        domain
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Reads a domain for the specified `air` from the file at the specified `path`; returns `None`
+/// if the file does not exist or does not contain a valid domain for the `air`.
+fn read_domain<A: Air>(path: &Path, air: &A) -> Option<StarkDomain<A::BaseField>> {
+    let bytes = fs::read(path).ok()?;
+    if bytes.len() < CHECKSUM_BYTES {
+        debug!("Domain file {} is truncated", path.display());
+        return None;
+    }
+    let (checksum, domain_bytes) = bytes.split_at(CHECKSUM_BYTES);
+    if checksum != compute_checksum(air, domain_bytes) {
+        debug!("Checksum of domain in {} does not match", path.display());
+        return None;
+    }
+
+    match StarkDomain::read_from_bytes(domain_bytes) {
+        Ok(domain) if domain.matches(air) => Some(domain),
+        Ok(_) => {
+            debug!(
+                "Domain in {} does not match the computation",
+                path.display()
+            );
+            None
+        }
+        Err(err) => {
+            debug!("Failed to read domain from {}: {}", path.display(), err);
+            None
+        }
+    }
+}
+
+/// Writes the `domain` for the specified `air` into the file at the specified `path`.
+///
+/// The domain is first written into a temporary file which is then renamed; thus, other
+/// processes and threads never observe a partially written domain file.
+fn write_domain<A: Air>(
+    path: &Path,
+    air: &A,
+    domain: &StarkDomain<A::BaseField>,
+) -> io::Result<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let domain_bytes = domain.to_bytes();
+    let mut bytes = compute_checksum(air, &domain_bytes).to_vec();
+    bytes.extend_from_slice(&domain_bytes);
+
+    let counter = TMP_FILE_COUNTER.fetch_add(1, Ordering::Relaxed);
+    let tmp_path = path.with_extension(format!("{}.{counter}.tmp", process::id()));
+    fs::write(&tmp_path, bytes)?;
+    fs::rename(&tmp_path, path).map_err(|err| {
+        let _ = fs::remove_file(&tmp_path);
+        err
+    })
+}
+
+/// Returns a checksum binding the serialized domain to the parameters of the domain for the
+/// specified `air`.
+fn compute_checksum<A: Air>(air: &A, domain_bytes: &[u8]) -> [u8; CHECKSUM_BYTES] {
+    let mut data = A::BaseField::get_modulus_le_bytes();
+    data.extend_from_slice(&(air.trace_length() as u64).to_le_bytes());
+    data.extend_from_slice(&(air.ce_domain_size() as u64).to_le_bytes());
+    data.extend_from_slice(&(air.lde_domain_size() as u64).to_le_bytes());
+    air.domain_offset().write_into(&mut data);
+    data.extend_from_slice(domain_bytes);
+    Blake3_256::<A::BaseField>::hash(&data).as_bytes()
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::{DomainCache, CHECKSUM_BYTES};
+    use crate::tests::MockAir;
+    use math::{fields::f128::BaseElement, FieldElement};
+    use std::{env, fs, process};
+
+    #[test]
+    fn domain_cache() {
+        let dir = env::temp_dir().join(format!("winter_domain_cache_{}", process::id()));
+        let cache = DomainCache::new(&dir);
+        let air = MockAir::with_trace_length(32);
+        let path = cache.domain_path(&air);
+
+        // the domain is built and stored in the cache on first request
+        let domain = cache.get_or_build(&air);
+        assert!(path.exists());
+
+        // and is read from the cache on subsequent requests
+        let cached = cache.get_or_build(&air);
+        assert!(cached.matches(&air));
+        assert_eq!(domain.trace_twiddles(), cached.trace_twiddles());
+        assert_eq!(domain.trace_inv_twiddles(), cached.trace_inv_twiddles());
+        assert_eq!(domain.get_ce_x_at(5), cached.get_ce_x_at(5));
+
+        // a corrupted domain file is ignored, and the domain is rebuilt
+        fs::write(&path, [1, 2, 3]).unwrap();
+        let rebuilt = cache.get_or_build(&air);
+        assert_eq!(domain.trace_twiddles(), rebuilt.trace_twiddles());
+        assert_eq!(domain.get_ce_x_at(5), rebuilt.get_ce_x_at(5));
+
+        // a domain file with valid structure but modified values is rejected by the checksum
+        // (trace twiddles follow the checksum and a 1-byte number of twiddles; the first twiddle
+        // is always ONE, and thus, the second one is modified)
+        let mut bytes = fs::read(&path).unwrap();
+        bytes[CHECKSUM_BYTES + 1 + BaseElement::ELEMENT_BYTES] ^= 1;
+        fs::write(&path, &bytes).unwrap();
+        let rebuilt = cache.get_or_build(&air);
+        assert_eq!(domain.trace_twiddles(), rebuilt.trace_twiddles());
+
+        // a domain file built for different parameters is rejected even if its name matches
+        let other_air = MockAir::with_trace_length(64);
+        cache.get_or_build(&other_air);
+        fs::copy(cache.domain_path(&other_air), &path).unwrap();
+        let rebuilt = cache.get_or_build(&air);
+        assert!(rebuilt.matches(&air));
+        assert_eq!(domain.trace_twiddles(), rebuilt.trace_twiddles());
+
+        // domains of computations with different shapes are stored in different files
+        assert_ne!(path, cache.domain_path(&other_air));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -5,7 +5,9 @@
 
 //! Contains common error types for prover and verifier.
 
+use air::AssertionError;
 use core::fmt;
+use utils::{collections::Vec, string::String, ErrorCategory, ErrorCode, ToErrorCode};
 
 // PROVER ERROR
 // ================================================================================================
@@ -21,6 +23,54 @@ pub enum ProverError {
     /// This error occurs when the base field specified by the AIR does not support field extension
     /// of degree specified by proof options.
     UnsupportedFieldExtension(usize),
+    /// This error occurs when the state following the last row of a trace fragment does not
+    /// match the first row of the next fragment. The error contains the index of the fragment
+    /// and indexes of the columns for which the values did not match.
+    InconsistentTraceFragments(usize, Vec<usize>),
+    /// This error occurs when an execution trace is not valid against the AIR of the computation;
+    /// the error contains a description of the first violation found.
+    InvalidTrace(String),
+    /// This error occurs when the number of assertions against the main trace segment returned
+    /// by the AIR does not match the number of such assertions specified in the AIR context.
+    MismatchedMainAssertionCount(usize, usize),
+    /// This error occurs when the number of assertions against auxiliary trace segments returned
+    /// by the AIR does not match the number of such assertions specified in the AIR context.
+    MismatchedAuxAssertionCount(usize, usize),
+    /// This error occurs when an assertion returned by the AIR is not valid in the context of the
+    /// execution trace (e.g., it is placed against a column which does not exist).
+    InvalidAssertion(AssertionError),
+    /// This error occurs when the execution trace fails to build the auxiliary trace segment with
+    /// the specified index.
+    AuxTraceSegmentNotBuilt(usize),
+    /// This error occurs when the constraint composition polynomial does not have the degree
+    /// implied by the constraint evaluation domain.
+    MismatchedCompositionPolyDegree(usize, usize),
+    /// This error occurs when the DEEP composition polynomial does not have the degree of trace
+    /// polynomials.
+    MismatchedDeepCompositionPolyDegree(usize, usize),
+    /// This error occurs when the prover fails to draw a random value from a random coin
+    /// within a specified number of tries.
+    RandomCoinError,
+    /// This error occurs when the execution trace contains preprocessed columns, but the prover
+    /// does not provide a commitment to these columns.
+    PreprocessedCommitmentNotProvided,
+    /// This error occurs when the commitment to preprocessed columns provided by the prover was
+    /// built for a computation with a different shape.
+    InvalidPreprocessedCommitment(String),
+    /// This error occurs when proof generation is cancelled via the cancellation token of the
+    /// prover.
+    Cancelled,
+    /// This error occurs when some columns of the execution trace do not affect any of the
+    /// constraints or assertions defined by the AIR; the error contains indexes of such columns.
+    UnconstrainedTraceColumns(Vec<usize>),
+    /// This error occurs when the serialized proof is larger than the maximum proof size
+    /// specified by the prover; the error contains the size of the proof and the maximum
+    /// size (both in bytes).
+    ProofTooLarge(usize, usize),
+    /// This error occurs when the number of rows in an evaluation matrix is different from the
+    /// number of leaves in the Merkle tree committing to the matrix; the error contains the
+    /// number of leaves and the number of rows.
+    MismatchedOracleTree(usize, usize),
 }
 
 impl fmt::Display for ProverError {
@@ -36,6 +86,77 @@ impl fmt::Display for ProverError {
             Self::UnsupportedFieldExtension(degree) => {
                 write!(f, "field extension of degree {degree} is not supported for the specified base field")
             }
+            Self::InconsistentTraceFragments(fragment, columns) => {
+                write!(f, "boundary state of trace fragment {fragment} does not match the next fragment in columns {columns:?}")
+            }
+            Self::InvalidTrace(msg) => {
+                write!(f, "execution trace is not valid against the AIR: {msg}")
+            }
+            Self::MismatchedMainAssertionCount(expected, actual) => {
+                write!(f, "expected {expected} assertions against main trace segment, but received {actual}")
+            }
+            Self::MismatchedAuxAssertionCount(expected, actual) => {
+                write!(f, "expected {expected} assertions against auxiliary trace segments, but received {actual}")
+            }
+            Self::InvalidAssertion(err) => {
+                write!(f, "assertion is invalid: {err}")
+            }
+            Self::AuxTraceSegmentNotBuilt(segment) => {
+                write!(f, "failed to build auxiliary trace segment {segment}")
+            }
+            Self::MismatchedCompositionPolyDegree(expected, actual) => {
+                write!(f, "expected composition polynomial of degree {expected}, but was {actual}")
+            }
+            Self::MismatchedDeepCompositionPolyDegree(expected, actual) => {
+                write!(f, "expected DEEP composition polynomial of degree {expected}, but was {actual}")
+            }
+            Self::RandomCoinError => {
+                write!(f, "failed to draw a random value from a random coin")
+            }
+            Self::PreprocessedCommitmentNotProvided => {
+                write!(f, "execution trace contains preprocessed columns, but commitment to these columns was not provided")
+            }
+            Self::InvalidPreprocessedCommitment(msg) => {
+                write!(f, "commitment to preprocessed columns is not valid for the computation: {msg}")
+            }
+            Self::Cancelled => {
+                write!(f, "proof generation was cancelled")
+            }
+            Self::UnconstrainedTraceColumns(columns) => {
+                write!(f, "trace columns {columns:?} are not constrained by the AIR")
+            }
+            Self::ProofTooLarge(size, max_size) => {
+                write!(f, "proof size of {size} bytes exceeds the maximum of {max_size} bytes; consider using fewer queries with a higher grinding factor or blowup factor, or a larger FRI folding factor")
+            }
+            Self::MismatchedOracleTree(num_leaves, num_rows) => {
+                write!(f, "commitment tree with {num_leaves} leaves cannot commit to an evaluation matrix with {num_rows} rows")
+            }
         }
     }
 }
+
+impl ToErrorCode for ProverError {
+    fn error_code(&self) -> ErrorCode {
+        let index = match self {
+            Self::UnsatisfiedTransitionConstraintError(_) => 1,
+            Self::MismatchedConstraintPolynomialDegree(..) => 2,
+            Self::UnsupportedFieldExtension(_) => 3,
+            Self::InconsistentTraceFragments(..) => 4,
+            Self::InvalidTrace(_) => 5,
+            Self::MismatchedMainAssertionCount(..) => 6,
+            Self::MismatchedAuxAssertionCount(..) => 7,
+            Self::InvalidAssertion(_) => 8,
+            Self::AuxTraceSegmentNotBuilt(_) => 9,
+            Self::MismatchedCompositionPolyDegree(..) => 10,
+            Self::MismatchedDeepCompositionPolyDegree(..) => 11,
+            Self::RandomCoinError => 12,
+            Self::PreprocessedCommitmentNotProvided => 13,
+            Self::InvalidPreprocessedCommitment(_) => 14,
+            Self::Cancelled => 15,
+            Self::UnconstrainedTraceColumns(_) => 16,
+            Self::ProofTooLarge(..) => 18,
+            Self::MismatchedOracleTree(..) => 19,
+        };
+        ErrorCode::new(ErrorCategory::Prover, index)
+    }
+}
@@ -44,16 +44,27 @@
 extern crate alloc;
 
 pub use air::{
-    proof::StarkProof, Air, AirContext, Assertion, AuxTraceRandElements, BoundaryConstraint,
-    BoundaryConstraintGroup, ConstraintCompositionCoefficients, ConstraintDivisor,
-    DeepCompositionCoefficients, EvaluationFrame, FieldExtension, ProofOptions, TraceInfo,
-    TraceLayout, TransitionConstraintDegree, TransitionConstraintGroup,
+    params,
+    proof::{StarkProof, PROOF_VERSION},
+    Air, AirContext, Assertion, AssertionError, Assertions, AuxTraceRandElements,
+    BoundaryConstraint, BoundaryConstraintGroup, ConstraintCompositionCoefficients,
+    ConstraintDivisor, DeepCompositionCoefficients, EvaluationFrame, Expr, FieldExtension,
+    GrindingFunction, MultiInstanceAir, MultiInstanceInputs, ProofOptions, QuerySampling, Register,
+    TraceInfo, TraceLayout, TraceStep, TransitionConstraintDegree, TransitionConstraintGroup,
+    TransitionExpressions,
 };
+
+#[cfg(feature = "interop")]
+pub use air::proof::interop;
+
 pub use utils::{
-    iterators, ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable,
-    SliceReader,
+    iterators, ByteReader, ByteWriter, Deserializable, DeserializationError, ErrorCategory,
+    ErrorCode, Serializable, SliceReader, ToErrorCode,
 };
 
+#[cfg(feature = "concurrent")]
+pub use utils::ComputeContext;
+
 use fri::FriProver;
 use utils::collections::Vec;
 
@@ -75,10 +86,18 @@ use std::time::Instant;
 mod domain;
 pub use domain::StarkDomain;
 
+#[cfg(feature = "std")]
+mod domain_cache;
+#[cfg(feature = "std")]
+pub use domain_cache::DomainCache;
+
 pub mod matrix;
-pub use matrix::{ColMatrix, RowMatrix};
+use matrix::{get_evaluation_offsets, Segment};
+pub use matrix::{ColMatrix, ColumnIter, CommittedOracle, RowMatrix, RowView};
 
 mod constraints;
+#[cfg(debug_assertions)]
+use constraints::find_unconstrained_columns;
 use constraints::ConstraintEvaluator;
 pub use constraints::{CompositionPoly, ConstraintCommitment};
 
@@ -95,6 +114,22 @@ use channel::ProverChannel;
 mod errors;
 pub use errors::ProverError;
 
+mod debug;
+pub use debug::{DebugProver, DebugTrace, DebugTraceDiff, ProofArtifact};
+
+mod multi;
+use multi::MultiInstanceProver;
+
+mod session;
+pub use session::ProvingSession;
+
+mod preprocessed;
+pub use preprocessed::PreprocessedCommitment;
+
+mod progress;
+use progress::ProgressMonitor;
+pub use progress::{CancellationToken, ProgressHandler, ProvingPhase};
+
 #[cfg(test)]
 pub mod tests;
 
@@ -155,6 +190,69 @@ pub trait Prover {
     // PROVIDED METHODS
     // --------------------------------------------------------------------------------------------
 
+    /// Returns a commitment to preprocessed columns of execution traces generated for this
+    /// prover.
+    ///
+    /// A commitment must be provided when the traces contain preprocessed columns (as specified by
+    /// [TraceLayout::num_preprocessed_columns()]), and it is ignored otherwise. By default, this
+    /// returns `None`.
+    fn preprocessed_commitment(
+        &self,
+    ) -> Option<&PreprocessedCommitment<Self::BaseField, Self::HashFn>> {
+        None
+    }
+
+    /// Returns a handler which is notified about progress of proof generation.
+    ///
+    /// This can be used to display progress of long proving jobs. By default, this returns
+    /// `None`.
+    fn progress_handler(&self) -> Option<&dyn ProgressHandler> {
+        None
+    }
+
+    /// Returns a token which can be used to cancel proof generation.
+    ///
+    /// When the token is cancelled, proof generation is aborted at the next check and
+    /// [ProverError::Cancelled] is returned. By default, this returns `None`.
+    fn cancellation_token(&self) -> Option<&CancellationToken> {
+        None
+    }
+
+    /// Returns true if this prover generates proofs for execution traces with columns which do
+    /// not affect any of the constraints or assertions defined by the AIR.
+    ///
+    /// A proof says nothing about values in such columns, and usually, unconstrained columns
+    /// indicate a mistake in the AIR. Thus, in debug mode, the prover looks for such columns and,
+    /// unless this returns `true`, refuses to generate proofs for computations in which it finds
+    /// any; by default, this returns `false`. The check evaluates transition constraints at a
+    /// single pseudo-random point, and thus, it is a debugging aid rather than a guarantee: a
+    /// column which does not affect constraints at this point is reported as unconstrained, and
+    /// the check is not performed in release mode.
+    fn allows_unconstrained_columns(&self) -> bool {
+        false
+    }
+
+    /// Returns the maximum size (in bytes) of a serialized proof generated by this prover.
+    ///
+    /// If the serialized proof is larger than this, proof generation fails with
+    /// [ProverError::ProofTooLarge] rather than emitting a proof which cannot be handled
+    /// downstream (e.g., by a transport with a fixed message size limit, or by an on-chain
+    /// verifier). The limit is not a part of the proof context, and the verifier does not
+    /// enforce it. By default, this returns `None` and proof size is not limited.
+    fn max_proof_size_bytes(&self) -> Option<usize> {
+        None
+    }
+
+    /// Returns a cache from which the prover reads STARK domains, and into which the prover
+    /// stores newly built domains.
+    ///
+    /// This can be used to avoid rebuilding domains for large traces in every process which
+    /// generates proofs. By default, this returns `None`.
+    #[cfg(feature = "std")]
+    fn domain_cache(&self) -> Option<&DomainCache> {
+        None
+    }
+
     /// Returns a STARK proof attesting to a correct execution of a computation defined by the
     /// provided trace.
     ///
@@ -162,35 +260,156 @@ pub trait Prover {
     /// the computation described by [Self::Air](Prover::Air) and generated using some set of
     /// secret and public inputs. Public inputs must match the value returned from
     /// [Self::get_pub_inputs()](Prover::get_pub_inputs) for the provided trace.
-    #[rustfmt::skip]
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * The field extension specified by proof options is not supported for the base field.
+    /// * Assertions defined by the AIR are not valid in the context of the execution trace.
+    /// * An auxiliary trace segment could not be built.
+    /// * The `trace` contains preprocessed columns, but the commitment returned from
+    ///   [Self::preprocessed_commitment()](Prover::preprocessed_commitment) is missing or does
+    ///   not match the trace.
+    /// * In debug mode, the `trace` does not satisfy assertions or transition constraints of the
+    ///   AIR.
+    /// * A constraint polynomial does not have the expected degree.
+    /// * A random value could not be drawn from the public coin.
+    /// * Proof generation was cancelled via the token returned from
+    ///   [Self::cancellation_token()](Prover::cancellation_token).
+    /// * In debug mode, some trace columns are found not to be constrained by the AIR, unless
+    ///   [Self::allows_unconstrained_columns()](Prover::allows_unconstrained_columns) returns
+    ///   `true`.
     fn prove(&self, trace: Self::Trace) -> Result<StarkProof, ProverError> {
+        self.prove_with_cached_domain(trace, &mut None)
+    }
+
+    /// Returns a STARK proof attesting to a correct execution of a computation defined by the
+    /// provided trace; all concurrent work of proof generation is executed on the thread pool of
+    /// the specified `context`.
+    ///
+    /// By default, [Prover::prove()] runs concurrent work on the global rayon thread pool. This
+    /// method can be used to limit parallelism of individual proof generation requests, or to run
+    /// them on threads pinned to specific cores.
+    ///
+    /// # Errors
+    /// Returns an error for any of the reasons described in [Prover::prove()].
+    #[cfg(feature = "concurrent")]
+    fn prove_in(
+        &self,
+        context: &ComputeContext,
+        trace: Self::Trace,
+    ) -> Result<StarkProof, ProverError>
+    where
+        Self: Sync,
+        Self::Trace: Send,
+    {
+        context.install(|| self.prove(trace))
+    }
+
+    /// Returns a new [ProvingSession] which can be used to generate multiple proofs with this
+    /// prover.
+    ///
+    /// The session retains the STARK domain (including FFT twiddles) built for a proof, and
+    /// reuses it for subsequent proofs of computation instances with the same trace length,
+    /// constraint evaluation domain, and LDE domain. This is useful when the same computation is
+    /// proven repeatedly for different inputs (e.g., in benchmarks).
+    fn new_session(&self) -> ProvingSession<'_, Self>
+    where
+        Self: Sized,
+    {
+        ProvingSession::new(self)
+    }
+
+    /// Returns a single STARK proof attesting to correct executions of multiple independent
+    /// instances of the computation defined by the provided traces.
+    ///
+    /// The traces are placed side by side and proven against [MultiInstanceAir] built from
+    /// [Self::Air](Prover::Air); thus, all instances share constraint composition coefficients
+    /// and a single FRI instance. The proof can be verified by instantiating the verifier with
+    /// [MultiInstanceAir] and [MultiInstanceInputs] which contain public inputs of each instance
+    /// in the same order as the `traces`.
+    ///
+    /// # Errors
+    /// In addition to errors returned by [Prover::prove()], returns an error if:
+    /// * `traces` is empty.
+    /// * Any of the traces consists of more than one segment.
+    /// * Traces have different lengths, original lengths, or metadata.
+    /// * The combined trace is wider than [TraceInfo::MAX_TRACE_WIDTH] columns.
+    fn prove_many(&self, traces: Vec<Self::Trace>) -> Result<StarkProof, ProverError>
+    where
+        Self: Sized,
+    {
+        let (prover, trace) = MultiInstanceProver::new(self, traces)?;
+        prover.prove(trace)
+    }
+
+    // HELPER METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Generates a proof for the provided `trace` reusing the STARK domain in `domain` if it is
+    /// compatible with the computation; otherwise, a new domain is built and saved into `domain`.
+    #[doc(hidden)]
+    #[rustfmt::skip]
+    fn prove_with_cached_domain(
+        &self,
+        trace: Self::Trace,
+        domain: &mut Option<StarkDomain<Self::BaseField>>,
+    ) -> Result<StarkProof, ProverError> {
         // figure out which version of the generic proof generation procedure to run. this is a sort
         // of static dispatch for selecting two generic parameter: extension field and hash function.
         match self.options().field_extension() {
-            FieldExtension::None => self.generate_proof::<Self::BaseField>(trace),
+            FieldExtension::None => {
+                self.generate_proof_with_domain::<Self::BaseField>(trace, domain, None)
+            }
             FieldExtension::Quadratic => {
                 if !<QuadExtension<Self::BaseField>>::is_supported() {
                     return Err(ProverError::UnsupportedFieldExtension(2));
                 }
-                self.generate_proof::<QuadExtension<Self::BaseField>>(trace)
+                self.generate_proof_with_domain::<QuadExtension<Self::BaseField>>(trace, domain, None)
             }
             FieldExtension::Cubic => {
                 if !<CubeExtension<Self::BaseField>>::is_supported() {
                     return Err(ProverError::UnsupportedFieldExtension(3));
                 }
-                self.generate_proof::<CubeExtension<Self::BaseField>>(trace)
+                self.generate_proof_with_domain::<CubeExtension<Self::BaseField>>(trace, domain, None)
             }
         }
     }
 
-    // HELPER METHODS
-    // --------------------------------------------------------------------------------------------
-
     /// Performs the actual proof generation procedure, generating the proof that the provided
     /// execution `trace` is valid against this prover's AIR.
     /// TODO: make this function un-callable externally?
     #[doc(hidden)]
-    fn generate_proof<E>(&self, mut trace: Self::Trace) -> Result<StarkProof, ProverError>
+    fn generate_proof<E>(&self, trace: Self::Trace) -> Result<StarkProof, ProverError>
+    where
+        E: FieldElement<BaseField = Self::BaseField>,
+    {
+        self.generate_proof_with_debug_trace::<E>(trace, None)
+    }
+
+    /// Performs the actual proof generation procedure, and if `debug_trace` is provided, records
+    /// intermediate artifacts of proof generation into it.
+    #[doc(hidden)]
+    fn generate_proof_with_debug_trace<E>(
+        &self,
+        trace: Self::Trace,
+        debug_trace: Option<&mut DebugTrace<Self::BaseField>>,
+    ) -> Result<StarkProof, ProverError>
+    where
+        E: FieldElement<BaseField = Self::BaseField>,
+    {
+        self.generate_proof_with_domain::<E>(trace, &mut None, debug_trace)
+    }
+
+    /// Performs the actual proof generation procedure reusing the STARK domain in `domain_cache`
+    /// if it matches the computation, and if `debug_trace` is provided, records intermediate
+    /// artifacts of proof generation into it.
+    #[doc(hidden)]
+    fn generate_proof_with_domain<E>(
+        &self,
+        mut trace: Self::Trace,
+        domain_cache: &mut Option<StarkDomain<Self::BaseField>>,
+        mut debug_trace: Option<&mut DebugTrace<Self::BaseField>>,
+    ) -> Result<StarkProof, ProverError>
     where
         E: FieldElement<BaseField = Self::BaseField>,
     {
@@ -205,6 +424,17 @@ pub trait Prover {
         // execution of the computation for the provided public inputs.
         let air = Self::Air::new(trace.get_info(), pub_inputs, self.options().clone());
 
+        // look for trace columns which are not constrained by the AIR; the proof would not attest
+        // to anything about values in such columns. We do this in debug mode only because this
+        // requires evaluating constraints once for every column of the trace.
+        #[cfg(debug_assertions)]
+        if !self.allows_unconstrained_columns() {
+            let columns = find_unconstrained_columns::<_, Self::RandomCoin>(&air)?;
+            if !columns.is_empty() {
+                return Err(ProverError::UnconstrainedTraceColumns(columns));
+            }
+        }
+
         // create a channel which is used to simulate interaction between the prover and the
         // verifier; the channel will be used to commit to values and to draw randomness that
         // should come from the verifier.
@@ -213,12 +443,33 @@ pub trait Prover {
             pub_inputs_elements,
         );
 
+        // progress of proof generation is reported to the progress handler of this prover (if
+        // any); the monitor is also used to check whether proof generation has been cancelled
+        let monitor = ProgressMonitor::new(self.progress_handler(), self.cancellation_token());
+        monitor.check()?;
+
         // 1 ----- Commit to the execution trace --------------------------------------------------
 
-        // build computation domain; this is used later for polynomial evaluations
+        // build computation domain, or reuse the domain cached from a previous proof of a
+        // computation with the same shape; if the prover has a domain cache, the domain is read
+        // from the cache instead of being built. the domain is used later for polynomial
+        // evaluations
         #[cfg(feature = "std")]
         let now = Instant::now();
-        let domain = StarkDomain::new(&air);
+        if !domain_cache
+            .as_ref()
+            .map_or(false, |domain| domain.matches(&air))
+        {
+            #[cfg(feature = "std")]
+            let domain = match self.domain_cache() {
+                Some(cache) => cache.get_or_build(&air),
+                None => StarkDomain::new(&air),
+            };
+            #[cfg(not(feature = "std"))]
+            let domain = StarkDomain::new(&air);
+            *domain_cache = Some(domain);
+        }
+        let domain = &*domain_cache.get_or_insert_with(|| StarkDomain::new(&air));
         #[cfg(feature = "std")]
         debug!(
             "Built domain of 2^{} elements in {} ms",
@@ -226,9 +477,39 @@ pub trait Prover {
             now.elapsed().as_millis()
         );
 
+        // if the trace contains preprocessed columns, make sure the prover provides a commitment
+        // to these columns, and commit to them by reseeding the channel with the commitment root;
+        // the remaining columns of the main segment are committed to separately
+        let num_preprocessed_cols = air.trace_layout().num_preprocessed_columns();
+        let preprocessed = if num_preprocessed_cols > 0 {
+            let commitment = self
+                .preprocessed_commitment()
+                .ok_or(ProverError::PreprocessedCommitmentNotProvided)?;
+            commitment.validate(&air, trace.main_segment())?;
+            channel.commit_preprocessed(commitment.root());
+            Some(commitment)
+        } else {
+            None
+        };
+        let witness_segment;
+        let main_segment = match preprocessed {
+            Some(_) => {
+                witness_segment = ColMatrix::new(
+                    trace
+                        .main_segment()
+                        .columns()
+                        .skip(num_preprocessed_cols)
+                        .map(|column| column.to_vec())
+                        .collect(),
+                );
+                &witness_segment
+            }
+            None => trace.main_segment(),
+        };
+
         // extend the main execution trace and build a Merkle tree from the extended trace
-        let (main_trace_lde, main_trace_tree, main_trace_polys) =
-            self.build_trace_commitment::<Self::BaseField>(trace.main_segment(), &domain);
+        let (main_trace_lde, main_trace_tree, mut main_trace_polys) =
+            self.build_trace_commitment::<Self::BaseField>(main_segment, domain, 0)?;
 
         // commit to the LDE of the main trace by writing the root of its Merkle tree into
         // the channel
@@ -236,12 +517,27 @@ pub trait Prover {
 
         // initialize trace commitment and trace polynomial table structs with the main trace
         // data; for multi-segment traces these structs will be used as accumulators of all
-        // trace segments
+        // trace segments. preprocessed columns are placed in front of the other main trace
+        // columns.
         let mut trace_commitment = TraceCommitment::new(
             main_trace_lde,
             main_trace_tree,
             domain.trace_to_lde_blowup(),
         );
+        if let Some(commitment) = preprocessed {
+            trace_commitment.set_preprocessed_segment(commitment.lde().clone());
+            main_trace_polys = ColMatrix::new(
+                commitment
+                    .polys()
+                    .columns()
+                    .chain(main_trace_polys.columns())
+                    .map(|poly| poly.to_vec())
+                    .collect(),
+            );
+        }
+        if let Some(debug_trace) = debug_trace.as_deref_mut() {
+            debug_trace.record(ProofArtifact::MainTracePolys, main_trace_polys.columns());
+        }
         let mut trace_polys = TracePolyTable::new(main_trace_polys);
 
         // build auxiliary trace segments (if any), and append the resulting segments to trace
@@ -253,12 +549,12 @@ pub trait Prover {
             let now = Instant::now();
 
             // draw a set of random elements required to build an auxiliary trace segment
-            let rand_elements = channel.get_aux_trace_segment_rand_elements(i);
+            let rand_elements = channel.get_aux_trace_segment_rand_elements(i)?;
 
             // build the trace segment
             let aux_segment = trace
                 .build_aux_segment(&aux_trace_segments, &rand_elements)
-                .expect("failed build auxiliary trace segment");
+                .ok_or(ProverError::AuxTraceSegmentNotBuilt(i))?;
             #[cfg(feature = "std")]
             debug!(
                 "Built auxiliary trace segment of {} columns and 2^{} steps in {} ms",
@@ -269,7 +565,10 @@ pub trait Prover {
 
             // extend the auxiliary trace segment and build a Merkle tree from the extended trace
             let (aux_segment_lde, aux_segment_tree, aux_segment_polys) =
-                self.build_trace_commitment::<E>(&aux_segment, &domain);
+                self.build_trace_commitment::<E>(&aux_segment, domain, i + 1)?;
+            if let Some(debug_trace) = debug_trace.as_deref_mut() {
+                debug_trace.record(ProofArtifact::AuxTracePolys(i), aux_segment_polys.columns());
+            }
 
             // commit to the LDE of the extended auxiliary trace segment  by writing the root of
             // its Merkle tree into the channel
@@ -286,7 +585,7 @@ pub trait Prover {
         // This checks validity of both, assertions and state transitions. We do this in debug
         // mode only because this is a very expensive operation.
         #[cfg(debug_assertions)]
-        trace.validate(&air, &aux_trace_segments, &aux_trace_rand_elements);
+        trace.validate(&air, &aux_trace_segments, &aux_trace_rand_elements)?;
 
         // 2 ----- evaluate constraints -----------------------------------------------------------
         // evaluate constraints specified by the AIR over the constraint evaluation domain, and
@@ -297,9 +596,14 @@ pub trait Prover {
         // identical denominators.
         #[cfg(feature = "std")]
         let now = Instant::now();
-        let constraint_coeffs = channel.get_constraint_composition_coeffs();
-        let evaluator = ConstraintEvaluator::new(&air, aux_trace_rand_elements, constraint_coeffs);
-        let constraint_evaluations = evaluator.evaluate(trace_commitment.trace_table(), &domain);
+        let constraint_coeffs = channel.get_constraint_composition_coeffs()?;
+        let evaluator = ConstraintEvaluator::new(&air, aux_trace_rand_elements, constraint_coeffs)?;
+        let constraint_evaluations =
+            evaluator.evaluate(trace_commitment.trace_table(), domain, monitor)?;
+        if let Some(debug_trace) = debug_trace.as_deref_mut() {
+            let columns = constraint_evaluations.columns().iter().map(Vec::as_slice);
+            debug_trace.record(ProofArtifact::ConstraintEvaluations, columns);
+        }
         #[cfg(feature = "std")]
         debug!(
             "Evaluated constraints over domain of 2^{} elements in {} ms",
@@ -318,6 +622,12 @@ pub trait Prover {
         #[cfg(feature = "std")]
         let now = Instant::now();
         let composition_poly = constraint_evaluations.into_poly()?;
+        if let Some(debug_trace) = debug_trace.as_deref_mut() {
+            debug_trace.record(
+                ProofArtifact::CompositionPoly,
+                composition_poly.data().columns(),
+            );
+        }
         #[cfg(feature = "std")]
         debug!(
             "Converted constraint evaluations into {} composition polynomial columns of degree {} in {} ms",
@@ -328,7 +638,7 @@ pub trait Prover {
 
         // then, build a commitment to the evaluations of the composition polynomial columns
         let constraint_commitment =
-            self.build_constraint_commitment::<E>(&composition_poly, &domain);
+            self.build_constraint_commitment::<E>(&composition_poly, domain)?;
 
         // then, commit to the evaluations of constraints by writing the root of the constraint
         // Merkle tree into the channel
@@ -345,7 +655,7 @@ pub trait Prover {
         // increase security. Soundness is limited by the size of the field that the random point
         // is drawn from, and we can potentially save on performance by only drawing this point
         // from an extension field, rather than increasing the size of the field overall.
-        let z = channel.get_ood_point();
+        let z = channel.get_ood_point()?;
 
         // evaluate trace and constraint polynomials at the OOD point z, and send the results to
         // the verifier. the trace polynomials are actually evaluated over two points: z and z * g,
@@ -358,7 +668,7 @@ pub trait Prover {
 
         // draw random coefficients to use during DEEP polynomial composition, and use them to
         // initialize the DEEP composition polynomial
-        let deep_coefficients = channel.get_deep_composition_coeffs();
+        let deep_coefficients = channel.get_deep_composition_coeffs()?;
         let mut deep_composition_poly = DeepCompositionPoly::new(z, deep_coefficients);
 
         // combine all trace polynomials together and merge them into the DEEP composition
@@ -371,6 +681,10 @@ pub trait Prover {
         // raise the degree of the DEEP composition polynomial by one to make sure it is equal to
         // trace_length - 1
         deep_composition_poly.adjust_degree();
+        if let Some(debug_trace) = debug_trace.as_deref_mut() {
+            let coefficients = deep_composition_poly.coefficients();
+            debug_trace.record(ProofArtifact::DeepCompositionPoly, [coefficients]);
+        }
 
         #[cfg(feature = "std")]
         debug!(
@@ -381,12 +695,23 @@ pub trait Prover {
 
         // make sure the degree of the DEEP composition polynomial is equal to trace polynomial
         // degree
-        assert_eq!(domain.trace_length() - 1, deep_composition_poly.degree());
+        if deep_composition_poly.degree() != domain.trace_length() - 1 {
+            return Err(ProverError::MismatchedDeepCompositionPolyDegree(
+                domain.trace_length() - 1,
+                deep_composition_poly.degree(),
+            ));
+        }
 
         // 5 ----- evaluate DEEP composition polynomial over LDE domain ---------------------------
         #[cfg(feature = "std")]
         let now = Instant::now();
-        let deep_evaluations = deep_composition_poly.evaluate(&domain);
+        let deep_evaluations = deep_composition_poly.evaluate(domain);
+        if let Some(debug_trace) = debug_trace.as_deref_mut() {
+            debug_trace.record(
+                ProofArtifact::DeepEvaluations,
+                [deep_evaluations.as_slice()],
+            );
+        }
         // we check the following condition in debug mode only because infer_degree is an expensive
         // operation
         debug_assert_eq!(
@@ -403,8 +728,20 @@ pub trait Prover {
         // 6 ----- compute FRI layers for the composition polynomial ------------------------------
         #[cfg(feature = "std")]
         let now = Instant::now();
-        let mut fri_prover = FriProver::new(air.options().to_fri_options());
-        fri_prover.build_layers(&mut channel, deep_evaluations);
+        let fri_options = air.options().to_fri_options();
+        let num_fri_layers = fri_options.num_fri_layers(deep_evaluations.len());
+        let mut fri_prover = FriProver::new(fri_options);
+        monitor.start(ProvingPhase::FriLayers, num_fri_layers)?;
+        fri_prover.try_build_layers(&mut channel, deep_evaluations, |layer_idx| {
+            monitor.update(ProvingPhase::FriLayers, layer_idx + 1)
+        })?;
+        if let Some(debug_trace) = debug_trace.as_deref_mut() {
+            for i in 0..fri_prover.num_layers() {
+                let evaluations = fri_prover.layer_evaluations(i);
+                debug_trace.record(ProofArtifact::FriLayer(i), [evaluations]);
+            }
+            debug_trace.record(ProofArtifact::FriRemainder, [fri_prover.remainder_poly()]);
+        }
         #[cfg(feature = "std")]
         debug!(
             "Computed {} FRI layers from composition polynomial evaluations in {} ms",
@@ -420,7 +757,10 @@ pub trait Prover {
         channel.grind_query_seed();
 
         // generate pseudo-random query positions
-        let query_positions = channel.get_query_positions();
+        let query_positions = channel.get_query_positions()?;
+        if let Some(debug_trace) = debug_trace {
+            debug_trace.record_query_positions(&query_positions);
+        }
         #[cfg(feature = "std")]
         debug!(
             "Determined {} query positions in {} ms",
@@ -436,8 +776,12 @@ pub trait Prover {
         let fri_proof = fri_prover.build_proof(&query_positions);
 
         // query the execution trace at the selected position; for each query, we need the
-        // state of the trace at that position + Merkle authentication path
-        let trace_queries = trace_commitment.query(&query_positions);
+        // state of the trace at that position + Merkle authentication path. queries against
+        // preprocessed columns (if any) are placed after queries against all other segments.
+        let mut trace_queries = trace_commitment.query(&query_positions);
+        if let Some(commitment) = preprocessed {
+            trace_queries.push(commitment.query(&query_positions));
+        }
 
         // query the constraint commitment at the selected positions; for each query, we need just
         // a Merkle authentication path. this is because constraint evaluations for each step are
@@ -449,6 +793,14 @@ pub trait Prover {
         #[cfg(feature = "std")]
         debug!("Built proof object in {} ms", now.elapsed().as_millis());
 
+        // make sure the proof fits into the size limit of this prover (if any)
+        if let Some(max_proof_size) = self.max_proof_size_bytes() {
+            let proof_size = proof.to_bytes().len();
+            if proof_size > max_proof_size {
+                return Err(ProverError::ProofTooLarge(proof_size, max_proof_size));
+            }
+        }
+
         Ok(proof)
     }
 
@@ -461,20 +813,27 @@ pub trait Prover {
     ///
     /// Trace commitment is computed by hashing each row of the extended execution trace, and then
     /// building a Merkle tree from the resulting hashes.
+    ///
+    /// # Errors
+    /// Returns an error if proof generation is cancelled while the trace segment with the
+    /// specified index is being extended.
+    #[allow(clippy::type_complexity)]
     fn build_trace_commitment<E>(
         &self,
         trace: &ColMatrix<E>,
         domain: &StarkDomain<Self::BaseField>,
-    ) -> (RowMatrix<E>, MerkleTree<Self::HashFn>, ColMatrix<E>)
+        segment_idx: usize,
+    ) -> Result<(RowMatrix<E>, MerkleTree<Self::HashFn>, ColMatrix<E>), ProverError>
     where
         E: FieldElement<BaseField = Self::BaseField>,
     {
         // extend the execution trace
         #[cfg(feature = "std")]
         let now = Instant::now();
-        let trace_polys = trace.interpolate_columns();
-        let trace_lde =
-            RowMatrix::evaluate_polys_over::<DEFAULT_SEGMENT_WIDTH>(&trace_polys, domain);
+        let trace_polys = trace.interpolate_columns_over(domain);
+        let monitor = ProgressMonitor::new(self.progress_handler(), self.cancellation_token());
+        let phase = ProvingPhase::TraceExtension(segment_idx);
+        let trace_lde = evaluate_polys_over(&trace_polys, domain, monitor, phase)?;
         #[cfg(feature = "std")]
         debug!(
             "Extended execution trace of {} columns from 2^{} to 2^{} steps ({}x blowup) in {} ms",
@@ -496,7 +855,7 @@ pub trait Prover {
             now.elapsed().as_millis()
         );
 
-        (trace_lde, trace_tree, trace_polys)
+        Ok((trace_lde, trace_tree, trace_polys))
     }
 
     /// Evaluates constraint composition polynomial over the LDE domain and builds a commitment
@@ -507,21 +866,28 @@ pub trait Prover {
     ///
     /// The commitment is computed by hashing each row in the evaluation matrix, and then building
     /// a Merkle tree from the resulting hashes.
+    ///
+    /// # Errors
+    /// Returns an error if proof generation is cancelled while the composition polynomial
+    /// columns are being evaluated.
     fn build_constraint_commitment<E>(
         &self,
         composition_poly: &CompositionPoly<E>,
         domain: &StarkDomain<Self::BaseField>,
-    ) -> ConstraintCommitment<E, Self::HashFn>
+    ) -> Result<ConstraintCommitment<E, Self::HashFn>, ProverError>
     where
         E: FieldElement<BaseField = Self::BaseField>,
     {
         // evaluate composition polynomial columns over the LDE domain
         #[cfg(feature = "std")]
         let now = Instant::now();
-        let composed_evaluations = RowMatrix::evaluate_polys_over::<DEFAULT_SEGMENT_WIDTH>(
+        let monitor = ProgressMonitor::new(self.progress_handler(), self.cancellation_token());
+        let composed_evaluations = evaluate_polys_over(
             composition_poly.data(),
             domain,
-        );
+            monitor,
+            ProvingPhase::ConstraintExtension,
+        )?;
         #[cfg(feature = "std")]
         debug!(
             "Evaluated {} composition polynomial columns over LDE domain (2^{} elements) in {} ms",
@@ -533,14 +899,47 @@ pub trait Prover {
         // build constraint evaluation commitment
         #[cfg(feature = "std")]
         let now = Instant::now();
-        let commitment = composed_evaluations.commit_to_rows();
-        let constraint_commitment = ConstraintCommitment::new(composed_evaluations, commitment);
+        let constraint_commitment = ConstraintCommitment::new(composed_evaluations);
         #[cfg(feature = "std")]
         debug!(
             "Computed constraint evaluation commitment (Merkle tree of depth {}) in {} ms",
             constraint_commitment.tree_depth(),
             now.elapsed().as_millis()
         );
-        constraint_commitment
+        Ok(constraint_commitment)
     }
 }
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Evaluates polynomials in the provided matrix over the LDE domain in the same way as
+/// [RowMatrix::evaluate_polys_over()], and reports progress of the specified phase to the
+/// `monitor` after each segment of [DEFAULT_SEGMENT_WIDTH] columns is evaluated.
+fn evaluate_polys_over<E: FieldElement>(
+    polys: &ColMatrix<E>,
+    domain: &StarkDomain<E::BaseField>,
+    monitor: ProgressMonitor,
+    phase: ProvingPhase,
+) -> Result<RowMatrix<E>, ProverError> {
+    let offsets = get_evaluation_offsets::<E>(
+        polys.num_rows(),
+        domain.trace_to_lde_blowup(),
+        domain.offset(),
+    );
+
+    let num_segments = (polys.num_base_cols() + DEFAULT_SEGMENT_WIDTH - 1) / DEFAULT_SEGMENT_WIDTH;
+    monitor.start(phase, num_segments)?;
+    let mut segments = Vec::with_capacity(num_segments);
+    for i in 0..num_segments {
+        segments.push(Segment::<E::BaseField, DEFAULT_SEGMENT_WIDTH>::new(
+            polys,
+            i * DEFAULT_SEGMENT_WIDTH,
+            &offsets,
+            domain.trace_twiddles(),
+        ));
+        monitor.update(phase, i + 1)?;
+    }
+
+    Ok(RowMatrix::from_segments(segments, polys.num_base_cols()))
+}
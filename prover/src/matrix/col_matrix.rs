@@ -4,7 +4,7 @@
 // LICENSE file in the root directory of this source tree.
 
 use crate::StarkDomain;
-use core::{iter::FusedIterator, slice};
+use core::{cmp, iter::FusedIterator, slice};
 use crypto::{ElementHasher, MerkleTree};
 use math::{fft, polynom, FieldElement};
 use utils::{batch_iter_mut, collections::Vec, iter, iter_mut, uninit_vector};
@@ -136,6 +136,33 @@ impl<E: FieldElement> ColMatrix<E> {
         &mut self.columns[col_idx]
     }
 
+    /// Returns a view of the row at the specified index.
+    ///
+    /// Values of the row are read from the columns of this matrix on access; thus, no data is
+    /// copied when the view is created.
+    ///
+    /// # Panics
+    /// Panics if `row_idx` is out of bounds for this matrix.
+    pub fn get_row(&self, row_idx: usize) -> RowView<'_, E> {
+        assert!(
+            row_idx < self.num_rows(),
+            "row index {row_idx} is out of bounds for a matrix with {} rows",
+            self.num_rows()
+        );
+        RowView {
+            matrix: self,
+            row_idx,
+        }
+    }
+
+    /// Returns an iterator over views of all rows of this matrix.
+    pub fn rows(&self) -> impl ExactSizeIterator<Item = RowView<'_, E>> {
+        (0..self.num_rows()).map(move |row_idx| RowView {
+            matrix: self,
+            row_idx,
+        })
+    }
+
     /// Copies values of all columns at the specified row into the specified row slice.
     ///
     /// # Panics
@@ -171,12 +198,12 @@ impl<E: FieldElement> ColMatrix<E> {
     // --------------------------------------------------------------------------------------------
 
     /// Returns an iterator over the columns of this matrix.
-    pub fn columns(&self) -> ColumnIter<E> {
+    pub fn columns(&self) -> ColumnIter<'_, E> {
         ColumnIter::new(self)
     }
 
     /// Returns a mutable iterator over the columns of this matrix.
-    pub fn columns_mut(&mut self) -> ColumnIterMut<E> {
+    pub fn columns_mut(&mut self) -> ColumnIterMut<'_, E> {
         ColumnIterMut::new(self)
     }
 
@@ -205,6 +232,33 @@ impl<E: FieldElement> ColMatrix<E> {
         Self { columns }
     }
 
+    /// Interpolates columns of the matrix into polynomials in coefficient form using the trace
+    /// domain of the specified [StarkDomain], and returns the result.
+    ///
+    /// This is equivalent to [ColMatrix::interpolate_columns()] but uses twiddles cached in the
+    /// `domain` rather than computing them anew.
+    ///
+    /// # Panics
+    /// Panics if the number of rows in this matrix is not equal to the trace length of the
+    /// `domain`.
+    pub fn interpolate_columns_over(&self, domain: &StarkDomain<E::BaseField>) -> Self {
+        assert_eq!(
+            self.num_rows(),
+            domain.trace_length(),
+            "number of rows must be equal to trace length {}, but was {}",
+            domain.trace_length(),
+            self.num_rows()
+        );
+        let columns = iter!(self.columns)
+            .map(|evaluations| {
+                let mut column = evaluations.clone();
+                fft::interpolate_poly(&mut column, domain.trace_inv_twiddles());
+                column
+            })
+            .collect();
+        Self { columns }
+    }
+
     /// Interpolates columns of the matrix into polynomials in coefficient form and returns the
     /// result. The input matrix is consumed in the process.
     ///
@@ -290,6 +344,61 @@ impl<E: FieldElement> ColMatrix<E> {
         MerkleTree::new(row_hashes).expect("failed to construct trace Merkle tree")
     }
 
+    /// Returns a commitment to this matrix computed by streaming rows of the matrix through the
+    /// hash function using a bounded amount of scratch memory.
+    ///
+    /// The commitment is the same as the one returned by [ColMatrix::commit_to_rows()]. However,
+    /// instead of gathering each row from all columns one element at a time, rows are processed
+    /// in blocks: a block of consecutive rows is first transposed into a row-major scratch buffer
+    /// reading each column sequentially, and then every row of the buffer is hashed. For matrices
+    /// which do not fit into CPU cache, this avoids a cache miss for every element of the matrix.
+    ///
+    /// `scratch_size` specifies the maximum number of elements in a scratch buffer; a block
+    /// contains as many rows as fit into the buffer, but at least one row. When `concurrent`
+    /// feature is enabled, each thread uses its own scratch buffer.
+    ///
+    /// # Panics
+    /// Panics if `scratch_size` is zero.
+    pub fn commit_to_rows_streaming<H>(&self, scratch_size: usize) -> MerkleTree<H>
+    where
+        H: ElementHasher<BaseField = E::BaseField>,
+    {
+        assert!(scratch_size > 0, "scratch size must be greater than zero");
+        let num_cols = self.num_cols();
+        let block_size = cmp::max(1, scratch_size / num_cols);
+
+        // allocate vector to store row hashes
+        let mut row_hashes = unsafe { uninit_vector::<H::Digest>(self.num_rows()) };
+
+        batch_iter_mut!(
+            &mut row_hashes,
+            128, // min batch size
+            |batch: &mut [H::Digest], batch_offset: usize| {
+                let mut scratch = vec![E::ZERO; cmp::min(block_size, batch.len()) * num_cols];
+                for (block_idx, hashes) in batch.chunks_mut(block_size).enumerate() {
+                    let row_offset = batch_offset + block_idx * block_size;
+                    let block = &mut scratch[..hashes.len() * num_cols];
+
+                    // transpose the block of rows into the scratch buffer column by column
+                    for (col_idx, column) in self.columns.iter().enumerate() {
+                        let values = &column[row_offset..row_offset + hashes.len()];
+                        for (row, &value) in block.chunks_exact_mut(num_cols).zip(values) {
+                            row[col_idx] = value;
+                        }
+                    }
+
+                    // hash rows of the block
+                    for (row_hash, row) in hashes.iter_mut().zip(block.chunks_exact(num_cols)) {
+                        *row_hash = H::hash_elements(row);
+                    }
+                }
+            }
+        );
+
+        // build Merkle tree out of hashed rows
+        MerkleTree::new(row_hashes).expect("failed to construct trace Merkle tree")
+    }
+
     // CONVERSIONS
     // --------------------------------------------------------------------------------------------
 
@@ -338,6 +447,56 @@ impl<'a, E: FieldElement> ExactSizeIterator for ColumnIter<'a, E> {
 
 impl<'a, E: FieldElement> FusedIterator for ColumnIter<'a, E> {}
 
+// ROW VIEW
+// ================================================================================================
+
+/// A view of a single row of a [ColMatrix].
+#[derive(Debug, Clone, Copy)]
+pub struct RowView<'a, E: FieldElement> {
+    matrix: &'a ColMatrix<E>,
+    row_idx: usize,
+}
+
+impl<'a, E: FieldElement> RowView<'a, E> {
+    /// Returns the index of this row in the underlying matrix.
+    pub fn index(&self) -> usize {
+        self.row_idx
+    }
+
+    /// Returns the number of values in this row.
+    pub fn len(&self) -> usize {
+        self.matrix.num_cols()
+    }
+
+    /// Returns true if this row contains no values; this is never the case for rows of a valid
+    /// matrix.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the value of this row in the column at the specified index.
+    ///
+    /// # Panics
+    /// Panics if `col_idx` is out of bounds for the underlying matrix.
+    pub fn get(&self, col_idx: usize) -> E {
+        self.matrix.get(col_idx, self.row_idx)
+    }
+
+    /// Returns an iterator over values of this row.
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = E> + 'a {
+        let row_idx = self.row_idx;
+        self.matrix
+            .columns
+            .iter()
+            .map(move |column| column[row_idx])
+    }
+
+    /// Returns values of this row copied into a vector.
+    pub fn to_vec(&self) -> Vec<E> {
+        self.iter().collect()
+    }
+}
+
 // MUTABLE COLUMN ITERATOR
 // ================================================================================================
 
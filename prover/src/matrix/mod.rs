@@ -7,10 +7,14 @@ mod row_matrix;
 pub use row_matrix::{build_segments, get_evaluation_offsets, RowMatrix};
 
 mod col_matrix;
-pub use col_matrix::{ColMatrix, ColumnIter, MultiColumnIter};
+pub use col_matrix::{ColMatrix, ColumnIter, MultiColumnIter, RowView};
 
 mod segments;
 pub use segments::Segment;
 
+mod oracle;
+pub(crate) use oracle::open_rows;
+pub use oracle::CommittedOracle;
+
 #[cfg(test)]
 mod tests;
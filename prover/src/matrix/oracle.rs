@@ -0,0 +1,123 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::RowMatrix;
+use crate::ProverError;
+use air::proof::Queries;
+use crypto::{ElementHasher, MerkleTree};
+use math::FieldElement;
+use utils::collections::Vec;
+
+// COMMITTED ORACLE
+// ================================================================================================
+
+/// A matrix of evaluations together with a Merkle tree commitment to its rows.
+///
+/// The commitment is built by hashing each row of the matrix into a leaf of a Merkle tree (see
+/// [RowMatrix::commit_to_rows()]). Thus, opening the oracle at a given position reveals an entire
+/// row of the matrix together with a Merkle authentication path from the corresponding leaf to
+/// the root of the tree; such openings can be verified via [RowOpenings](air::proof::RowOpenings).
+pub struct CommittedOracle<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> {
+    evaluations: RowMatrix<E>,
+    tree: MerkleTree<H>,
+}
+
+impl<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> CommittedOracle<E, H> {
+    // CONSTRUCTORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a new oracle built by committing to the rows of the provided `evaluations` matrix.
+    pub fn commit(evaluations: RowMatrix<E>) -> Self {
+        let tree = evaluations.commit_to_rows();
+        Self { evaluations, tree }
+    }
+
+    /// Returns a new oracle instantiated from the provided `evaluations` matrix and a Merkle tree
+    /// previously built from the rows of this matrix.
+    ///
+    /// # Errors
+    /// Returns an error if the number of rows in `evaluations` is not equal to the number of
+    /// leaves in the `tree`.
+    pub fn from_parts(evaluations: RowMatrix<E>, tree: MerkleTree<H>) -> Result<Self, ProverError> {
+        if evaluations.num_rows() != tree.leaves().len() {
+            return Err(ProverError::MismatchedOracleTree(
+                tree.leaves().len(),
+                evaluations.num_rows(),
+            ));
+        }
+        Ok(Self { evaluations, tree })
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the root of the commitment Merkle tree.
+    pub fn root(&self) -> H::Digest {
+        *self.tree.root()
+    }
+
+    /// Returns the depth of the commitment Merkle tree.
+    pub fn tree_depth(&self) -> usize {
+        self.tree.depth()
+    }
+
+    /// Returns the matrix of evaluations committed to by this oracle.
+    pub fn evaluations(&self) -> &RowMatrix<E> {
+        &self.evaluations
+    }
+
+    /// Returns the Merkle tree built from the rows of the evaluation matrix.
+    pub fn tree(&self) -> &MerkleTree<H> {
+        &self.tree
+    }
+
+    /// Decomposes this oracle into the evaluation matrix and the commitment Merkle tree.
+    pub fn into_parts(self) -> (RowMatrix<E>, MerkleTree<H>) {
+        (self.evaluations, self.tree)
+    }
+
+    // OPENINGS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns matrix rows at the specified positions along with Merkle authentication paths
+    /// from the commitment root to these rows.
+    ///
+    /// # Panics
+    /// Panics if `positions` is empty or if any of the positions is out of bounds.
+    pub fn open_at(&self, positions: &[usize]) -> Queries {
+        open_rows(&self.evaluations, &self.tree, positions)
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns rows of the `evaluations` matrix at the specified positions along with Merkle
+/// authentication paths for these rows against the provided `tree`.
+///
+/// The tree is assumed to have been built from the rows of the `evaluations` matrix.
+pub(crate) fn open_rows<E, H>(
+    evaluations: &RowMatrix<E>,
+    tree: &MerkleTree<H>,
+    positions: &[usize],
+) -> Queries
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+{
+    // for each position, get the corresponding row from the matrix and put all these rows into
+    // a single vector
+    let rows = positions
+        .iter()
+        .map(|&pos| evaluations.row(pos).to_vec())
+        .collect::<Vec<_>>();
+
+    // build Merkle authentication paths to the leaves specified by positions
+    let merkle_proof = tree
+        .prove_batch(positions)
+        .expect("failed to generate a Merkle proof for oracle queries");
+
+    Queries::new(merkle_proof, rows)
+}
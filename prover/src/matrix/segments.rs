@@ -178,10 +178,10 @@ impl<B: StarkField, const N: usize> Segment<B, N> {
         poly_offset: usize,
         offsets: &[B],
     ) {
-        for row_idx in 0..dest.len() {
-            for i in 0..N {
+        for (row_idx, row) in dest.iter_mut().enumerate() {
+            for (i, value) in row.iter_mut().enumerate() {
                 let coeff = polys.get_base_element(poly_offset + i, row_idx);
-                dest[row_idx][i] = coeff * offsets[row_idx];
+                *value = coeff * offsets[row_idx];
             }
         }
     }
@@ -198,10 +198,10 @@ impl<B: StarkField, const N: usize> Segment<B, N> {
         offsets: &[B],
     ) {
         debug_assert!(num_polys < N);
-        for row_idx in 0..dest.len() {
-            for i in 0..num_polys {
+        for (row_idx, row) in dest.iter_mut().enumerate() {
+            for (i, value) in row.iter_mut().enumerate().take(num_polys) {
                 let coeff = polys.get_base_element(poly_offset + i, row_idx);
-                dest[row_idx][i] = coeff * offsets[row_idx];
+                *value = coeff * offsets[row_idx];
             }
         }
     }
@@ -246,7 +246,7 @@ mod concurrent {
 
         // apply inner FFTs
         data.par_chunks_mut(outer_len)
-            .for_each(|row| row.fft_in_place_raw(&twiddles, stretch, stretch, 0));
+            .for_each(|row| row.fft_in_place_raw(twiddles, stretch, stretch, 0));
 
         // transpose inner x inner x stretch square matrix
         transpose_square_stretch(data, inner_len, stretch);
@@ -260,13 +260,13 @@ mod concurrent {
                     let inner_twiddle = g.exp_vartime((i as u32).into());
                     let mut outer_twiddle = inner_twiddle;
                     for element in row.iter_mut().skip(1) {
-                        for col_idx in 0..N {
-                            element[col_idx] = element[col_idx] * outer_twiddle;
+                        for value in element.iter_mut() {
+                            *value *= outer_twiddle;
                         }
-                        outer_twiddle = outer_twiddle * inner_twiddle;
+                        outer_twiddle *= inner_twiddle;
                     }
                 }
-                row.fft_in_place(&twiddles)
+                row.fft_in_place(twiddles)
             });
     }
 
@@ -4,11 +4,16 @@
 // LICENSE file in the root directory of this source tree.
 
 use crate::{
+    crypto::{hashers::Blake3_256, MerkleTreeError},
     math::{fields::f64::BaseElement, get_power_series, polynom, StarkField},
-    ColMatrix, RowMatrix,
+    ColMatrix, CommittedOracle, ProverError, RowMatrix,
 };
+use air::proof::{Queries, RowOpenings};
 use rand_utils::rand_vector;
-use utils::collections::Vec;
+use utils::{collections::Vec, ByteReader, ByteWriter, Deserializable, Serializable, SliceReader};
+
+type Oracle = CommittedOracle<BaseElement, Blake3_256<BaseElement>>;
+type Openings = RowOpenings<BaseElement, Blake3_256<BaseElement>>;
 
 #[test]
 fn test_eval_poly_with_offset_matrix() {
@@ -39,6 +44,98 @@ fn test_eval_poly_with_offset_matrix() {
     }
 }
 
+#[test]
+fn test_commit_to_rows_streaming() {
+    let n = 1024;
+    let num_cols = 13;
+    let columns: Vec<Vec<BaseElement>> = (0..num_cols).map(|_| rand_vector(n)).collect();
+    let matrix = ColMatrix::new(columns);
+    let expected = matrix.commit_to_rows::<Blake3_256<BaseElement>>();
+
+    // scratch buffers smaller than a row, holding a number of rows which does not divide the
+    // number of rows in the matrix, and larger than the entire matrix
+    for scratch_size in [1, num_cols * 3 + 1, num_cols * n * 2] {
+        let tree = matrix.commit_to_rows_streaming::<Blake3_256<BaseElement>>(scratch_size);
+        assert_eq!(expected.root(), tree.root());
+        assert_eq!(expected.leaves(), tree.leaves());
+    }
+}
+
+#[test]
+fn test_oracle_openings_round_trip() {
+    let (num_rows, num_cols) = (64, 5);
+    let columns: Vec<Vec<BaseElement>> = (0..num_cols).map(|_| rand_vector(num_rows)).collect();
+    let oracle = Oracle::commit(RowMatrix::evaluate_polys::<8>(&ColMatrix::new(columns), 2));
+    let num_rows = oracle.evaluations().num_rows();
+
+    let positions = [3, 17, 42, 100];
+    let queries = oracle.open_at(&positions);
+    let rows =
+        Openings::verify_openings(&oracle.root(), num_rows, num_cols, &positions, queries).unwrap();
+    assert_eq!(positions.len(), rows.num_rows());
+    for (i, &position) in positions.iter().enumerate() {
+        assert_eq!(oracle.evaluations().row(position), rows.get_row(i));
+    }
+}
+
+#[test]
+fn test_oracle_openings_tampered() {
+    let (num_rows, num_cols) = (64, 5);
+    let columns: Vec<Vec<BaseElement>> = (0..num_cols).map(|_| rand_vector(num_rows)).collect();
+    let oracle = Oracle::commit(RowMatrix::evaluate_polys::<8>(&ColMatrix::new(columns), 2));
+    let num_rows = oracle.evaluations().num_rows();
+    let root = oracle.root();
+    let positions = [3, 17, 42, 100];
+
+    // opened values which do not match the commitment are rejected; value bytes follow their
+    // length prefix in serialized queries
+    let mut bytes = oracle.open_at(&positions).to_bytes();
+    let mut prefix = Vec::new();
+    prefix.write_usize(SliceReader::new(&bytes).read_usize().unwrap());
+    bytes[prefix.len()] ^= 1;
+    let queries = Queries::read_from_bytes(&bytes).unwrap();
+    assert!(matches!(
+        Openings::verify_openings(&root, num_rows, num_cols, &positions, queries),
+        Err(MerkleTreeError::InvalidProof)
+    ));
+
+    // openings are rejected against a different root
+    let other_root = oracle.tree().leaves()[0];
+    let queries = oracle.open_at(&positions);
+    assert!(
+        Openings::verify_openings(&other_root, num_rows, num_cols, &positions, queries).is_err()
+    );
+
+    // openings are rejected for positions other than the opened ones
+    let queries = oracle.open_at(&positions);
+    let other_positions = [3, 17, 42, 101];
+    assert!(
+        Openings::verify_openings(&root, num_rows, num_cols, &other_positions, queries).is_err()
+    );
+
+    // openings are rejected if the row width does not match
+    let queries = oracle.open_at(&positions);
+    assert!(Openings::verify_openings(&root, num_rows, num_cols - 1, &positions, queries).is_err());
+}
+
+#[test]
+fn test_oracle_from_parts() {
+    let columns: Vec<Vec<BaseElement>> = (0..3).map(|_| rand_vector(16)).collect();
+    let evaluations = RowMatrix::evaluate_polys::<8>(&ColMatrix::new(columns), 2);
+
+    // a tree built from a matrix with a different number of rows is rejected
+    let columns: Vec<Vec<BaseElement>> = (0..3).map(|_| rand_vector(32)).collect();
+    let other = RowMatrix::evaluate_polys::<8>(&ColMatrix::new(columns), 2);
+    assert!(matches!(
+        Oracle::from_parts(other, evaluations.commit_to_rows()),
+        Err(ProverError::MismatchedOracleTree(32, 64))
+    ));
+
+    let tree = evaluations.commit_to_rows();
+    let oracle = Oracle::from_parts(evaluations, tree).unwrap();
+    assert_eq!(32, oracle.evaluations().num_rows());
+}
+
 // HELPER FUNCTIONS
 // ================================================================================================
 
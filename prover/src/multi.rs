@@ -0,0 +1,255 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{CancellationToken, ColMatrix, ProgressHandler, Prover, ProverError, Trace};
+
+#[cfg(feature = "std")]
+use super::DomainCache;
+use air::{
+    Air, EvaluationFrame, MultiInstanceAir, MultiInstanceInputs, ProofOptions, TraceInfo,
+    TraceLayout,
+};
+use core::cell::Cell;
+use math::{FieldElement, StarkField};
+use utils::{collections::Vec, string::ToString};
+
+// MULTI-INSTANCE PROVER
+// ================================================================================================
+
+/// A prover for [MultiInstanceAir] which delegates all parameters to the prover of a single
+/// instance of the computation.
+///
+/// Public inputs of all instances are computed from the individual instance traces before the
+/// traces are stacked, and are consumed when the proof is generated.
+pub(super) struct MultiInstanceProver<'a, P: Prover> {
+    prover: &'a P,
+    pub_inputs: Cell<Option<MultiInstanceInputs<<P::Air as Air>::PublicInputs>>>,
+}
+
+impl<'a, P: Prover> MultiInstanceProver<'a, P> {
+    /// Returns a new prover for the specified `traces`, together with the trace in which
+    /// `traces` are placed side by side.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * `traces` is empty.
+    /// * Any of the traces consists of more than one segment.
+    /// * Traces have different lengths, original lengths, or metadata.
+    /// * The combined trace is wider than [TraceInfo::MAX_TRACE_WIDTH] columns.
+    pub fn new(
+        prover: &'a P,
+        traces: Vec<P::Trace>,
+    ) -> Result<(Self, StackedTrace<P::BaseField>), ProverError> {
+        let trace = StackedTrace::new(&traces)?;
+        let pub_inputs = traces
+            .iter()
+            .map(|trace| prover.get_pub_inputs(trace))
+            .collect();
+        let prover = MultiInstanceProver {
+            prover,
+            pub_inputs: Cell::new(Some(MultiInstanceInputs(pub_inputs))),
+        };
+        Ok((prover, trace))
+    }
+}
+
+impl<'a, P: Prover> Prover for MultiInstanceProver<'a, P> {
+    type BaseField = P::BaseField;
+    type Air = MultiInstanceAir<P::Air>;
+    type Trace = StackedTrace<P::BaseField>;
+    type HashFn = P::HashFn;
+    type RandomCoin = P::RandomCoin;
+
+    fn get_pub_inputs(
+        &self,
+        _trace: &Self::Trace,
+    ) -> MultiInstanceInputs<<P::Air as Air>::PublicInputs> {
+        self.pub_inputs
+            .take()
+            .expect("public inputs of all instances have already been consumed")
+    }
+
+    fn options(&self) -> &ProofOptions {
+        self.prover.options()
+    }
+
+    fn progress_handler(&self) -> Option<&dyn ProgressHandler> {
+        self.prover.progress_handler()
+    }
+
+    fn cancellation_token(&self) -> Option<&CancellationToken> {
+        self.prover.cancellation_token()
+    }
+
+    fn allows_unconstrained_columns(&self) -> bool {
+        self.prover.allows_unconstrained_columns()
+    }
+
+    fn max_proof_size_bytes(&self) -> Option<usize> {
+        self.prover.max_proof_size_bytes()
+    }
+
+    #[cfg(feature = "std")]
+    fn domain_cache(&self) -> Option<&DomainCache> {
+        self.prover.domain_cache()
+    }
+}
+
+// STACKED TRACE
+// ================================================================================================
+
+/// Main trace segments of multiple execution traces placed side by side.
+pub(super) struct StackedTrace<B: StarkField> {
+    layout: TraceLayout,
+    main_segment: ColMatrix<B>,
+    original_length: usize,
+    meta: Vec<u8>,
+}
+
+impl<B: StarkField> StackedTrace<B> {
+    fn new<T: Trace<BaseField = B>>(traces: &[T]) -> Result<Self, ProverError> {
+        let first = traces.first().ok_or_else(|| {
+            ProverError::InvalidTrace("at least one trace must be provided".to_string())
+        })?;
+        let mut columns = Vec::new();
+        for (i, trace) in traces.iter().enumerate() {
+            if trace.layout().num_aux_segments() != 0 {
+                return Err(ProverError::InvalidTrace(format!(
+                    "trace {i} consists of more than one segment"
+                )));
+            }
+            if trace.length() != first.length() {
+                return Err(ProverError::InvalidTrace(format!(
+                    "all traces must have the same length, but trace {} has length {} instead of {}",
+                    i,
+                    trace.length(),
+                    first.length()
+                )));
+            }
+            if trace.original_length() != first.original_length() {
+                return Err(ProverError::InvalidTrace(
+                    "all traces must have the same original length".to_string(),
+                ));
+            }
+            if trace.meta() != first.meta() {
+                return Err(ProverError::InvalidTrace(
+                    "all traces must have the same metadata".to_string(),
+                ));
+            }
+            columns.extend(trace.main_segment().columns().map(|column| column.to_vec()));
+        }
+
+        if columns.len() > TraceInfo::MAX_TRACE_WIDTH {
+            return Err(ProverError::InvalidTrace(format!(
+                "combined trace width cannot be greater than {}, but was {}",
+                TraceInfo::MAX_TRACE_WIDTH,
+                columns.len()
+            )));
+        }
+
+        let main_segment = ColMatrix::new(columns);
+        Ok(StackedTrace {
+            layout: TraceLayout::new(main_segment.num_cols(), [0], [0]),
+            main_segment,
+            original_length: first.original_length(),
+            meta: first.meta().to_vec(),
+        })
+    }
+}
+
+impl<B: StarkField> Trace for StackedTrace<B> {
+    type BaseField = B;
+
+    fn layout(&self) -> &TraceLayout {
+        &self.layout
+    }
+
+    fn length(&self) -> usize {
+        self.main_segment.num_rows()
+    }
+
+    fn original_length(&self) -> usize {
+        self.original_length
+    }
+
+    fn meta(&self) -> &[u8] {
+        &self.meta
+    }
+
+    fn read_main_frame(&self, row_idx: usize, frame: &mut EvaluationFrame<Self::BaseField>) {
+        let next_row_idx = (row_idx + 1) % self.length();
+        self.main_segment
+            .read_row_into(row_idx, frame.current_mut());
+        self.main_segment
+            .read_row_into(next_row_idx, frame.next_mut());
+    }
+
+    fn main_segment(&self) -> &ColMatrix<B> {
+        &self.main_segment
+    }
+
+    fn build_aux_segment<E>(
+        &mut self,
+        _aux_segments: &[ColMatrix<E>],
+        _rand_elements: &[E],
+    ) -> Option<ColMatrix<E>>
+    where
+        E: FieldElement<BaseField = Self::BaseField>,
+    {
+        None
+    }
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        tests::{build_fib_trace, Blake3, FibAir, FibProver},
+        Prover, ProverError, StarkProof,
+    };
+    use air::{MultiInstanceAir, MultiInstanceInputs, ProofOptions};
+    use crypto::DefaultRandomCoin;
+    use math::{fields::f128::BaseElement, FieldElement};
+    use utils::collections::Vec;
+    use verifier::VerifierError;
+
+    #[test]
+    fn prove_many() {
+        let prover = FibProver::new(ProofOptions::for_testing());
+        let traces = (0..4).map(|_| build_fib_trace(128)).collect();
+        let proof = prover.prove_many(traces).unwrap();
+
+        let result = prover.get_pub_inputs(&build_fib_trace(128));
+        assert!(verify_many(proof.clone(), vec![result; 4]).is_ok());
+
+        // a wrong result for a single instance must be rejected
+        let mut results = vec![result; 4];
+        results[2] += BaseElement::ONE;
+        assert!(verify_many(proof, results).is_err());
+    }
+
+    #[test]
+    fn prove_many_inconsistent_traces() {
+        let prover = FibProver::new(ProofOptions::for_testing());
+        let traces = vec![build_fib_trace(64), build_fib_trace(128)];
+        assert!(matches!(
+            prover.prove_many(traces),
+            Err(ProverError::InvalidTrace(_))
+        ));
+        assert!(matches!(
+            prover.prove_many(Vec::new()),
+            Err(ProverError::InvalidTrace(_))
+        ));
+    }
+
+    fn verify_many(proof: StarkProof, results: Vec<BaseElement>) -> Result<(), VerifierError> {
+        verifier::verify::<MultiInstanceAir<FibAir>, Blake3, DefaultRandomCoin<Blake3>>(
+            proof,
+            MultiInstanceInputs(results),
+        )
+    }
+}
@@ -0,0 +1,177 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{
+    matrix::open_rows, ColMatrix, ProofOptions, ProverError, RowMatrix, StarkDomain,
+    DEFAULT_SEGMENT_WIDTH,
+};
+use air::{proof::Queries, Air};
+use crypto::{ElementHasher, MerkleTree};
+use math::{fft, StarkField};
+
+#[cfg(feature = "std")]
+use log::debug;
+#[cfg(feature = "std")]
+use std::time::Instant;
+
+// PREPROCESSED COMMITMENT
+// ================================================================================================
+
+/// A commitment to preprocessed columns of an execution trace.
+///
+/// Preprocessed columns are the first columns of the main trace segment as specified by
+/// [TraceLayout::num_preprocessed_columns()](air::TraceLayout::num_preprocessed_columns). Values
+/// in these columns do not depend on the inputs of a computation, and thus, the commitment to them
+/// can be built once at setup time and reused for all proofs generated with the same proof
+/// options and trace length.
+///
+/// The commitment consists of the polynomials interpolated from the preprocessed columns, their
+/// evaluations over the LDE domain, and a Merkle tree built from the rows of these evaluations.
+/// The root of the Merkle tree must be provided to the verifier out of band (i.e., it is not
+/// included in the proofs); a prover uses this commitment for proof generation when it is
+/// returned from [Prover::preprocessed_commitment()](crate::Prover::preprocessed_commitment).
+pub struct PreprocessedCommitment<B: StarkField, H: ElementHasher<BaseField = B>> {
+    columns: ColMatrix<B>,
+    polys: ColMatrix<B>,
+    lde: RowMatrix<B>,
+    tree: MerkleTree<H>,
+    domain_offset: B,
+}
+
+impl<B: StarkField, H: ElementHasher<BaseField = B>> PreprocessedCommitment<B, H> {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a commitment to the provided preprocessed `columns` built for proofs generated
+    /// with the specified `options`.
+    ///
+    /// # Panics
+    /// Panics if the length of the columns is not a power of two or is smaller than 4.
+    pub fn new(columns: ColMatrix<B>, options: &ProofOptions) -> Self {
+        let trace_length = columns.num_rows();
+        assert!(
+            trace_length >= 4 && trace_length.is_power_of_two(),
+            "length of preprocessed columns must be a power of two and at least 4, but was {trace_length}"
+        );
+
+        #[cfg(feature = "std")]
+        let now = Instant::now();
+        let domain_offset = options.domain_offset::<B>();
+        let domain = StarkDomain::from_twiddles(
+            fft::get_twiddles(trace_length),
+            options.blowup_factor(),
+            domain_offset,
+        );
+        let polys = columns.interpolate_columns_over(&domain);
+        let lde = RowMatrix::evaluate_polys_over::<DEFAULT_SEGMENT_WIDTH>(&polys, &domain);
+        let tree = lde.commit_to_rows::<H>();
+        #[cfg(feature = "std")]
+        debug!(
+            "Committed to {} preprocessed columns extended from 2^{} to 2^{} steps in {} ms",
+            columns.num_cols(),
+            trace_length.ilog2(),
+            lde.num_rows().ilog2(),
+            now.elapsed().as_millis()
+        );
+
+        Self {
+            columns,
+            polys,
+            lde,
+            tree,
+            domain_offset,
+        }
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the root of the Merkle tree built from the extended preprocessed columns.
+    ///
+    /// This value must be provided to the verifier to verify proofs generated using this
+    /// commitment.
+    pub fn root(&self) -> H::Digest {
+        *self.tree.root()
+    }
+
+    /// Returns the number of preprocessed columns.
+    pub fn num_cols(&self) -> usize {
+        self.columns.num_cols()
+    }
+
+    /// Returns the length of preprocessed columns.
+    pub fn trace_length(&self) -> usize {
+        self.columns.num_rows()
+    }
+
+    /// Returns the size of the LDE domain over which the preprocessed columns were extended.
+    pub fn lde_domain_size(&self) -> usize {
+        self.lde.num_rows()
+    }
+
+    /// Returns the preprocessed columns.
+    pub fn columns(&self) -> &ColMatrix<B> {
+        &self.columns
+    }
+
+    /// Returns the polynomials interpolated from the preprocessed columns.
+    pub fn polys(&self) -> &ColMatrix<B> {
+        &self.polys
+    }
+
+    /// Returns evaluations of the preprocessed column polynomials over the LDE domain.
+    pub fn lde(&self) -> &RowMatrix<B> {
+        &self.lde
+    }
+
+    // HELPER METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Checks that this commitment can be used to generate a proof for the computation described
+    /// by the `air`, and that the preprocessed columns at the start of the provided main trace
+    /// segment are the same as the columns of this commitment.
+    pub(crate) fn validate<A: Air<BaseField = B>>(
+        &self,
+        air: &A,
+        main_segment: &ColMatrix<B>,
+    ) -> Result<(), ProverError> {
+        let num_cols = air.trace_layout().num_preprocessed_columns();
+        if self.num_cols() != num_cols {
+            return Err(ProverError::InvalidPreprocessedCommitment(format!(
+                "expected {} preprocessed columns, but commitment contains {}",
+                num_cols,
+                self.num_cols()
+            )));
+        }
+        if self.trace_length() != air.trace_length()
+            || self.lde_domain_size() != air.lde_domain_size()
+            || self.domain_offset != air.domain_offset()
+        {
+            return Err(ProverError::InvalidPreprocessedCommitment(format!(
+                "commitment was built for trace length {} and LDE domain of size {}, but the computation requires trace length {} and LDE domain of size {}",
+                self.trace_length(),
+                self.lde_domain_size(),
+                air.trace_length(),
+                air.lde_domain_size()
+            )));
+        }
+
+        for (i, column) in self.columns.columns().enumerate() {
+            if main_segment.get_column(i) != column {
+                return Err(ProverError::InvalidTrace(format!(
+                    "preprocessed column {i} does not match the preprocessed commitment"
+                )));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns rows of the extended preprocessed columns at the specified positions along with
+    /// Merkle authentication paths from the commitment root to these rows.
+    pub(crate) fn query(&self, positions: &[usize]) -> Queries {
+        open_rows(&self.lde, &self.tree, positions)
+    }
+}
@@ -0,0 +1,256 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::ProverError;
+use core::sync::atomic::{AtomicBool, Ordering};
+
+#[cfg(not(feature = "std"))]
+use alloc::sync::Arc;
+#[cfg(feature = "std")]
+use std::sync::Arc;
+
+// PROVING PHASE
+// ================================================================================================
+
+/// A phase of proof generation for which progress is reported to a [ProgressHandler].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProvingPhase {
+    /// Low-degree extension of the trace segment with the specified index; progress is reported
+    /// after each batch of columns is extended.
+    TraceExtension(usize),
+    /// Evaluation of constraints over the constraint evaluation domain; progress is reported
+    /// after each fragment of the constraint evaluation table is evaluated.
+    ConstraintEvaluation,
+    /// Evaluation of constraint composition polynomial columns over the LDE domain; progress is
+    /// reported after each batch of columns is evaluated.
+    ConstraintExtension,
+    /// Commit phase of the FRI protocol; progress is reported after each FRI layer is built.
+    FriLayers,
+}
+
+// PROGRESS HANDLER
+// ================================================================================================
+
+/// Receives notifications about progress of proof generation.
+///
+/// A handler can be supplied to the prover via [Prover::progress_handler()](crate::Prover::progress_handler).
+/// Notifications may be sent from multiple threads when the crate is compiled with `concurrent`
+/// feature enabled.
+pub trait ProgressHandler: Sync {
+    /// Invoked when the prover starts the specified `phase`; the phase consists of `num_steps`
+    /// steps.
+    fn on_phase_start(&self, _phase: ProvingPhase, _num_steps: usize) {}
+
+    /// Invoked after a step of the specified `phase` is completed; `num_completed` is the number
+    /// of steps completed so far.
+    ///
+    /// When steps are executed in multiple threads, notifications may arrive out of order.
+    fn on_phase_progress(&self, _phase: ProvingPhase, _num_completed: usize) {}
+}
+
+// CANCELLATION TOKEN
+// ================================================================================================
+
+/// A token which can be used to cancel proof generation.
+///
+/// A token can be supplied to the prover via
+/// [Prover::cancellation_token()](crate::Prover::cancellation_token). All clones of a token
+/// share the same state; thus, a clone of the token can be cancelled from another thread, and the
+/// prover will return [ProverError::Cancelled] at the next check. Cancellation is checked
+/// between batches of columns during low-degree extension, between fragments of the constraint
+/// evaluation table, and between FRI layers.
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+impl CancellationToken {
+    /// Returns a new token which has not been cancelled.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Cancels proof generation by provers which use this token.
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    /// Returns true if this token has been cancelled.
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+// PROGRESS MONITOR
+// ================================================================================================
+
+/// Forwards progress notifications to a progress handler (if any), and checks whether proof
+/// generation has been cancelled.
+#[derive(Clone, Copy, Default)]
+pub(crate) struct ProgressMonitor<'a> {
+    handler: Option<&'a dyn ProgressHandler>,
+    token: Option<&'a CancellationToken>,
+}
+
+impl<'a> ProgressMonitor<'a> {
+    pub fn new(
+        handler: Option<&'a dyn ProgressHandler>,
+        token: Option<&'a CancellationToken>,
+    ) -> Self {
+        Self { handler, token }
+    }
+
+    /// Notifies the handler that the specified phase has started.
+    ///
+    /// # Errors
+    /// Returns an error if proof generation has been cancelled.
+    pub fn start(&self, phase: ProvingPhase, num_steps: usize) -> Result<(), ProverError> {
+        self.check()?;
+        if let Some(handler) = self.handler {
+            handler.on_phase_start(phase, num_steps);
+        }
+        Ok(())
+    }
+
+    /// Notifies the handler that `num_completed` steps of the specified phase have been
+    /// completed.
+    ///
+    /// # Errors
+    /// Returns an error if proof generation has been cancelled.
+    pub fn update(&self, phase: ProvingPhase, num_completed: usize) -> Result<(), ProverError> {
+        if let Some(handler) = self.handler {
+            handler.on_phase_progress(phase, num_completed);
+        }
+        self.check()
+    }
+
+    /// Returns an error if proof generation has been cancelled.
+    pub fn check(&self) -> Result<(), ProverError> {
+        match self.token {
+            Some(token) if token.is_cancelled() => Err(ProverError::Cancelled),
+            _ => Ok(()),
+        }
+    }
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::{CancellationToken, ProgressHandler, ProvingPhase};
+    use crate::{
+        tests::{build_fib_trace, Blake3, FibAir, FibProver},
+        Prover, ProverError, TraceTable,
+    };
+    use air::{FieldExtension, ProofOptions};
+    use crypto::DefaultRandomCoin;
+    use math::fields::f128::BaseElement;
+    use std::sync::Mutex;
+
+    #[test]
+    fn progress_and_cancellation() {
+        // progress handlers do not affect generated proofs
+        let prover = MonitoredFibProver::new(None);
+        let proof = prover.prove(build_fib_trace(64)).unwrap();
+        let expected = prover.prover.prove(build_fib_trace(64)).unwrap();
+        assert_eq!(expected.to_bytes(), proof.to_bytes());
+
+        // all phases are reported in order, and run to completion
+        let phases = prover.phases.into_inner().unwrap();
+        let names = phases.iter().map(|&(phase, ..)| phase).collect::<Vec<_>>();
+        assert_eq!(
+            vec![
+                ProvingPhase::TraceExtension(0),
+                ProvingPhase::ConstraintEvaluation,
+                ProvingPhase::ConstraintExtension,
+                ProvingPhase::FriLayers,
+            ],
+            names
+        );
+        for (phase, num_steps, num_completed) in phases {
+            assert!(num_steps > 0, "phase {phase:?} has no steps");
+            assert_eq!(
+                num_steps, num_completed,
+                "phase {phase:?} was not completed"
+            );
+        }
+
+        // proof generation is aborted once the token is cancelled
+        let prover = MonitoredFibProver::new(Some(ProvingPhase::ConstraintEvaluation));
+        let result = prover.prove(build_fib_trace(64));
+        assert_eq!(Err(ProverError::Cancelled), result);
+        let phases = prover.phases.into_inner().unwrap();
+        assert_eq!(ProvingPhase::ConstraintEvaluation, phases.last().unwrap().0);
+
+        // including when the token is cancelled before proof generation starts
+        let prover = MonitoredFibProver::new(None);
+        prover.token.cancel();
+        let result = prover.prove(build_fib_trace(64));
+        assert_eq!(Err(ProverError::Cancelled), result);
+        assert!(prover.phases.into_inner().unwrap().is_empty());
+    }
+
+    // MONITORED PROVER
+    // --------------------------------------------------------------------------------------------
+
+    /// Fibonacci prover which records progress of proof generation, and cancels proof generation
+    /// once the specified phase starts.
+    struct MonitoredFibProver {
+        prover: FibProver,
+        phases: Mutex<Vec<(ProvingPhase, usize, usize)>>,
+        cancel_at: Option<ProvingPhase>,
+        token: CancellationToken,
+    }
+
+    impl MonitoredFibProver {
+        fn new(cancel_at: Option<ProvingPhase>) -> Self {
+            Self {
+                prover: FibProver::new(ProofOptions::new(28, 8, 0, FieldExtension::None, 4, 7)),
+                phases: Default::default(),
+                cancel_at,
+                token: Default::default(),
+            }
+        }
+    }
+
+    impl ProgressHandler for MonitoredFibProver {
+        fn on_phase_start(&self, phase: ProvingPhase, num_steps: usize) {
+            self.phases.lock().unwrap().push((phase, num_steps, 0));
+            if self.cancel_at == Some(phase) {
+                self.token.cancel();
+            }
+        }
+
+        fn on_phase_progress(&self, phase: ProvingPhase, num_completed: usize) {
+            let mut phases = self.phases.lock().unwrap();
+            let (current, _, completed) = phases.last_mut().unwrap();
+            assert_eq!(phase, *current);
+            *completed = num_completed.max(*completed);
+        }
+    }
+
+    impl Prover for MonitoredFibProver {
+        type BaseField = BaseElement;
+        type Air = FibAir;
+        type Trace = TraceTable<BaseElement>;
+        type HashFn = Blake3;
+        type RandomCoin = DefaultRandomCoin<Blake3>;
+
+        fn get_pub_inputs(&self, trace: &Self::Trace) -> BaseElement {
+            self.prover.get_pub_inputs(trace)
+        }
+
+        fn options(&self) -> &ProofOptions {
+            self.prover.options()
+        }
+
+        fn progress_handler(&self) -> Option<&dyn ProgressHandler> {
+            Some(self)
+        }
+
+        fn cancellation_token(&self) -> Option<&CancellationToken> {
+            Some(&self.token)
+        }
+    }
+}
@@ -0,0 +1,95 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{Prover, ProverError, StarkDomain, StarkProof};
+
+// PROVING SESSION
+// ================================================================================================
+
+/// A session for generating multiple proofs with the same prover.
+///
+/// Building a STARK domain involves computing FFT twiddles for the trace domain and powers of
+/// the constraint evaluation domain generator. A session retains the domain built for the last
+/// proof, and reuses it for the next proof if the computation instance has the same trace length,
+/// constraint evaluation domain, and LDE domain; otherwise, a new domain is built and retained
+/// instead.
+///
+/// Proofs generated within a session are identical to the proofs generated by
+/// [Prover::prove()] for the same traces.
+///
+/// A session can be created via [Prover::new_session()].
+pub struct ProvingSession<'a, P: Prover> {
+    prover: &'a P,
+    domain: Option<StarkDomain<P::BaseField>>,
+}
+
+impl<'a, P: Prover> ProvingSession<'a, P> {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    /// Returns a new session for the specified `prover`.
+    pub fn new(prover: &'a P) -> Self {
+        ProvingSession {
+            prover,
+            domain: None,
+        }
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the prover used by this session.
+    pub fn prover(&self) -> &P {
+        self.prover
+    }
+
+    /// Returns the STARK domain retained from the last proof generated within this session, or
+    /// None if no proofs have been generated yet.
+    pub fn domain(&self) -> Option<&StarkDomain<P::BaseField>> {
+        self.domain.as_ref()
+    }
+
+    // PROOF GENERATION
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a STARK proof attesting to a correct execution of a computation defined by the
+    /// provided trace.
+    ///
+    /// This is equivalent to [Prover::prove()], but reuses the STARK domain retained from the
+    /// previous proof of this session when possible.
+    ///
+    /// # Errors
+    /// Returns an error in the same cases as [Prover::prove()].
+    pub fn prove(&mut self, trace: P::Trace) -> Result<StarkProof, ProverError> {
+        self.prover
+            .prove_with_cached_domain(trace, &mut self.domain)
+    }
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        tests::{build_fib_trace, FibProver},
+        Prover,
+    };
+    use air::ProofOptions;
+
+    #[test]
+    fn session_proofs_match_prover_proofs() {
+        let prover = FibProver::new(ProofOptions::for_testing());
+        let mut session = prover.new_session();
+        assert!(session.domain().is_none());
+
+        // the domain is retained for traces of the same length and rebuilt otherwise
+        for length in [64, 64, 128] {
+            let proof = session.prove(build_fib_trace(length)).unwrap();
+            let expected = prover.prove(build_fib_trace(length)).unwrap();
+            assert_eq!(expected.to_bytes(), proof.to_bytes());
+            assert_eq!(length / 2, session.domain().unwrap().trace_length());
+        }
+    }
+}
@@ -3,14 +3,17 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use crate::TraceTable;
+use crate::{Prover, ProverError, Trace, TraceTable};
 use air::{
     Air, AirContext, Assertion, EvaluationFrame, FieldExtension, ProofOptions, TraceInfo,
     TransitionConstraintDegree,
 };
+use crypto::{hashers::Blake3_256, DefaultRandomCoin};
 use math::{fields::f128::BaseElement, FieldElement, StarkField};
 use utils::collections::Vec;
 
+pub type Blake3 = Blake3_256<BaseElement>;
+
 // FIBONACCI TRACE BUILDER
 // ================================================================================================
 
@@ -28,6 +31,99 @@ pub fn build_fib_trace(length: usize) -> TraceTable<BaseElement> {
     TraceTable::init(vec![reg1, reg2])
 }
 
+// FIBONACCI AIR
+// ================================================================================================
+
+/// AIR for the execution traces built by [build_fib_trace()]; the public input is the value in
+/// the last row of the second column.
+pub struct FibAir {
+    context: AirContext<BaseElement>,
+    result: BaseElement,
+}
+
+impl Air for FibAir {
+    type BaseField = BaseElement;
+    type PublicInputs = BaseElement;
+
+    fn new(trace_info: TraceInfo, result: BaseElement, options: ProofOptions) -> Self {
+        let degrees = vec![
+            TransitionConstraintDegree::new(1),
+            TransitionConstraintDegree::new(1),
+        ];
+        FibAir {
+            context: AirContext::new(trace_info, degrees, 3, options),
+            result,
+        }
+    }
+
+    fn context(&self) -> &AirContext<Self::BaseField> {
+        &self.context
+    }
+
+    fn evaluate_transition<E: FieldElement + From<Self::BaseField>>(
+        &self,
+        frame: &EvaluationFrame<E>,
+        _periodic_values: &[E],
+        result: &mut [E],
+    ) {
+        let current = frame.current();
+        let next = frame.next();
+        result[0] = next[0] - (current[0] + current[1]);
+        result[1] = next[1] - (current[1] + next[0]);
+    }
+
+    fn get_assertions(&self) -> Vec<Assertion<Self::BaseField>> {
+        let last_step = self.trace_length() - 1;
+        vec![
+            Assertion::single(0, 0, BaseElement::ONE),
+            Assertion::single(1, 0, BaseElement::ONE),
+            Assertion::single(1, last_step, self.result),
+        ]
+    }
+}
+
+// FIBONACCI PROVER
+// ================================================================================================
+
+pub struct FibProver {
+    options: ProofOptions,
+    max_proof_size: Option<usize>,
+}
+
+impl FibProver {
+    pub fn new(options: ProofOptions) -> Self {
+        Self {
+            options,
+            max_proof_size: None,
+        }
+    }
+
+    pub fn with_max_proof_size(mut self, max_proof_size: usize) -> Self {
+        self.max_proof_size = Some(max_proof_size);
+        self
+    }
+}
+
+impl Prover for FibProver {
+    type BaseField = BaseElement;
+    type Air = FibAir;
+    type Trace = TraceTable<BaseElement>;
+    type HashFn = Blake3;
+    type RandomCoin = DefaultRandomCoin<Blake3>;
+
+    fn get_pub_inputs(&self, trace: &Self::Trace) -> BaseElement {
+        trace.get(1, trace.length() - 1)
+    }
+
+    fn options(&self) -> &ProofOptions {
+        &self.options
+    }
+
+    fn max_proof_size_bytes(&self) -> Option<usize> {
+        self.max_proof_size
+    }
+}
+
 // MOCK AIR
 // ================================================================================================
 
@@ -42,7 +138,7 @@ impl MockAir {
         Self::new(
             TraceInfo::new(4, trace_length),
             (),
-            ProofOptions::new(32, 8, 0, FieldExtension::None, 4, 31),
+            ProofOptions::for_testing(),
         )
     }
 
@@ -53,7 +149,7 @@ impl MockAir {
         let mut result = Self::new(
             TraceInfo::new(4, trace_length),
             (),
-            ProofOptions::new(32, 8, 0, FieldExtension::None, 4, 31),
+            ProofOptions::for_testing(),
         );
         result.periodic_columns = column_values;
         result
@@ -63,11 +159,22 @@ impl MockAir {
         let mut result = Self::new(
             TraceInfo::new(4, trace_length),
             (),
-            ProofOptions::new(32, 8, 0, FieldExtension::None, 4, 31),
+            ProofOptions::for_testing(),
         );
         result.assertions = assertions;
         result
     }
+
+    /// Similar to [MockAir::with_assertions()], but also sets the number of assertions in the
+    /// AIR context to the number of provided assertions.
+    pub fn with_declared_assertions(
+        assertions: Vec<Assertion<BaseElement>>,
+        trace_length: usize,
+    ) -> Self {
+        let mut result = Self::with_assertions(assertions, trace_length);
+        result.context = build_context(TraceInfo::new(4, trace_length), 8, result.assertions.len());
+        result
+    }
 }
 
 impl Air for MockAir {
@@ -116,3 +223,145 @@ fn build_context<B: StarkField>(
     let t_degrees = vec![TransitionConstraintDegree::new(2)];
     AirContext::new(trace_info, t_degrees, num_assertions, options)
 }
+
+// PROVER TESTS
+// ================================================================================================
+
+#[test]
+fn prove_with_max_proof_size() {
+    let options = ProofOptions::for_testing();
+    let proof = FibProver::new(options.clone())
+        .prove(build_fib_trace(128))
+        .unwrap();
+    let proof_size = proof.to_bytes().len();
+
+    // a proof which fits into the limit is generated as usual
+    let prover = FibProver::new(options.clone()).with_max_proof_size(proof_size);
+    let limited_proof = prover.prove(build_fib_trace(128)).unwrap();
+    assert_eq!(proof.to_bytes(), limited_proof.to_bytes());
+
+    // a proof which does not fit into the limit is rejected by the prover
+    let prover = FibProver::new(options).with_max_proof_size(proof_size - 1);
+    assert_eq!(
+        Err(ProverError::ProofTooLarge(proof_size, proof_size - 1)),
+        prover.prove(build_fib_trace(128)).map(|_| ())
+    );
+}
+
+// invalid traces are detected only in debug mode
+#[cfg(debug_assertions)]
+#[test]
+fn prove_invalid_trace() {
+    let prover = FibProver::new(ProofOptions::for_testing());
+    let mut trace = build_fib_trace(128);
+    trace.set(0, 5, trace.get(0, 5) + BaseElement::ONE);
+    assert!(matches!(
+        prover.prove(trace),
+        Err(ProverError::InvalidTrace(_))
+    ));
+}
+
+#[cfg(feature = "concurrent")]
+#[test]
+fn prove_in_context() {
+    use crate::ComputeContext;
+
+    let prover = FibProver::new(ProofOptions::for_testing());
+    let context = ComputeContext::with_num_threads(2).unwrap();
+
+    // proofs generated on a dedicated thread pool are the same as proofs generated on the global
+    // thread pool
+    let proof = prover.prove_in(&context, build_fib_trace(128)).unwrap();
+    let expected = prover.prove(build_fib_trace(128)).unwrap();
+    assert_eq!(expected.to_bytes(), proof.to_bytes());
+}
+
+#[test]
+fn proof_commitments() {
+    let prover = FibProver::new(ProofOptions::for_testing());
+    let proof = prover.prove(build_fib_trace(256)).unwrap();
+
+    let trace_commitments = proof.trace_commitments::<Blake3>().unwrap();
+    assert_eq!(1, trace_commitments.len());
+    let fri_roots = proof.fri_roots::<Blake3>().unwrap();
+    assert_eq!(proof.fri_proof.num_layers() + 1, fri_roots.len());
+    let constraint_commitment = proof.constraint_commitment::<Blake3>().unwrap();
+    assert_ne!(trace_commitments[0], constraint_commitment);
+
+    // statement digest depends on the context and the commitments
+    let digest = proof.statement_digest::<Blake3>();
+    assert_eq!(digest, proof.clone().statement_digest::<Blake3>());
+    let other_proof = prover.prove(build_fib_trace(512)).unwrap();
+    assert_ne!(digest, other_proof.statement_digest::<Blake3>());
+}
+
+#[test]
+fn unsupported_proof_version() {
+    use crate::{DeserializationError, StarkProof, PROOF_VERSION};
+
+    let proof = FibProver::new(ProofOptions::for_testing())
+        .prove(build_fib_trace(16))
+        .unwrap();
+    let mut bytes = proof.to_bytes();
+    assert_eq!(PROOF_VERSION, bytes[0]);
+
+    bytes[0] = PROOF_VERSION + 1;
+    assert_eq!(
+        Err(DeserializationError::UnsupportedVersion(PROOF_VERSION + 1)),
+        StarkProof::from_bytes(&bytes)
+    );
+}
+
+#[cfg(feature = "interop")]
+#[test]
+fn upstream_interop() {
+    use crate::interop::{
+        fri_proof_from_upstream_bytes, fri_proof_to_upstream_bytes,
+        stark_proof_from_upstream_bytes, stark_proof_to_upstream_bytes,
+    };
+    use air::{GrindingFunction, QuerySampling};
+
+    let options = ProofOptions::new(28, 8, 0, FieldExtension::None, 4, 7);
+    let prover = FibProver::new(options.clone());
+    let trace = build_fib_trace(256);
+    let result = prover.get_pub_inputs(&trace);
+    let proof = prover.prove(trace).unwrap();
+
+    // upstream proofs omit the format version of STARK proofs, the number of preprocessed trace
+    // columns, original trace length, domain offset, query sampling, FRI layer proof aggregation
+    // flags, base field composition flag, and grinding function; since length prefixes are
+    // encoded as fixed-width integers upstream, the exact size difference depends on the proof
+    let upstream = stark_proof_to_upstream_bytes(&proof).unwrap();
+    assert_ne!(proof.to_bytes(), upstream);
+    let parsed = stark_proof_from_upstream_bytes(&upstream).unwrap();
+    assert_eq!(proof.to_bytes(), parsed.to_bytes());
+    assert!(verifier::verify::<FibAir, Blake3, DefaultRandomCoin<Blake3>>(parsed, result).is_ok());
+
+    let upstream = fri_proof_to_upstream_bytes(&proof.fri_proof).unwrap();
+    assert_eq!(
+        proof.fri_proof,
+        fri_proof_from_upstream_bytes(&upstream).unwrap()
+    );
+
+    // features which upstream does not support cannot be converted
+    let prove =
+        |options: ProofOptions| FibProver::new(options).prove(build_fib_trace(256)).unwrap();
+
+    let proof = prove(
+        options
+            .clone()
+            .set_query_sampling(QuerySampling::Stratified(4)),
+    );
+    assert!(stark_proof_to_upstream_bytes(&proof).is_err());
+
+    let proof = prove(options.clone().set_fri_layer_proof_aggregation(true));
+    assert!(stark_proof_to_upstream_bytes(&proof).is_err());
+    assert!(fri_proof_to_upstream_bytes(&proof.fri_proof).is_err());
+
+    let extension_options = ProofOptions::new(28, 8, 0, FieldExtension::Quadratic, 4, 7);
+    let proof = prove(extension_options.set_base_field_composition(true));
+    assert!(stark_proof_to_upstream_bytes(&proof).is_err());
+
+    let proof = prove(options.set_grinding_function(GrindingFunction::MemoryHard));
+    assert!(stark_proof_to_upstream_bytes(&proof).is_err());
+}
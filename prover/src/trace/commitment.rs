@@ -3,7 +3,7 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use crate::RowMatrix;
+use crate::{matrix::open_rows, RowMatrix};
 use air::proof::Queries;
 use crypto::{ElementHasher, MerkleTree};
 use math::FieldElement;
@@ -50,6 +50,14 @@ impl<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> TraceCommitmen
     // STATE MUTATORS
     // --------------------------------------------------------------------------------------------
 
+    /// Sets the LDE of preprocessed columns which precede the columns of the main trace segment.
+    ///
+    /// The commitment to these columns is built separately, and thus, the columns are not
+    /// included in the queries returned from this trace commitment.
+    pub fn set_preprocessed_segment(&mut self, preprocessed_lde: RowMatrix<E::BaseField>) {
+        self.trace_lde.set_preprocessed_segment(preprocessed_lde);
+    }
+
     /// Adds the provided auxiliary segment trace LDE and Merkle tree to this trace commitment.
     pub fn add_segment(&mut self, aux_segment_lde: RowMatrix<E>, aux_segment_tree: MerkleTree<H>) {
         assert_eq!(
@@ -78,7 +86,7 @@ impl<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> TraceCommitmen
     /// from the commitment root to these rows.
     pub fn query(&self, positions: &[usize]) -> Vec<Queries> {
         // build queries for the main trace segment
-        let mut result = vec![build_segment_queries(
+        let mut result = vec![open_rows(
             self.trace_lde.get_main_segment(),
             &self.main_segment_tree,
             positions,
@@ -87,7 +95,7 @@ impl<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> TraceCommitmen
         // build queries for auxiliary trace segments
         for (i, segment_tree) in self.aux_segment_trees.iter().enumerate() {
             let segment_lde = self.trace_lde.get_aux_segment(i);
-            result.push(build_segment_queries(segment_lde, segment_tree, positions));
+            result.push(open_rows(segment_lde, segment_tree, positions));
         }
 
         result
@@ -111,30 +119,3 @@ impl<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> TraceCommitmen
             .collect()
     }
 }
-
-// HELPER FUNCTIONS
-// ================================================================================================
-
-fn build_segment_queries<E, H>(
-    segment_lde: &RowMatrix<E>,
-    segment_tree: &MerkleTree<H>,
-    positions: &[usize],
-) -> Queries
-where
-    E: FieldElement,
-    H: ElementHasher<BaseField = E::BaseField>,
-{
-    // for each position, get the corresponding row from the trace segment LDE and put all these
-    // rows into a single vector
-    let trace_states = positions
-        .iter()
-        .map(|&pos| segment_lde.row(pos).to_vec())
-        .collect::<Vec<_>>();
-
-    // build Merkle authentication paths to the leaves specified by positions
-    let trace_proof = segment_tree
-        .prove_batch(positions)
-        .expect("failed to generate a Merkle proof for trace queries");
-
-    Queries::new(trace_proof, trace_states)
-}
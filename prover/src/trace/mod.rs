@@ -3,9 +3,13 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use super::{matrix::MultiColumnIter, ColMatrix};
+use super::{matrix::MultiColumnIter, ColMatrix, ProverError};
 use air::{Air, AuxTraceRandElements, EvaluationFrame, TraceInfo, TraceLayout};
 use math::{polynom, FieldElement, StarkField};
+use utils::{
+    collections::Vec,
+    string::{String, ToString},
+};
 
 mod trace_lde;
 pub use trace_lde::TraceLde;
@@ -78,9 +82,17 @@ pub trait Trace: Sized {
     // PROVIDED METHODS
     // --------------------------------------------------------------------------------------------
 
+    /// Returns the number of rows in this trace before it was padded to a power of two.
+    ///
+    /// By default, this is the same as the number of rows in the trace.
+    fn original_length(&self) -> usize {
+        self.length()
+    }
+
     /// Returns trace info for this trace.
     fn get_info(&self) -> TraceInfo {
         TraceInfo::new_multi_segment(self.layout().clone(), self.length(), self.meta().to_vec())
+            .set_original_length(self.original_length())
     }
 
     /// Returns the number of columns in the main segment of this trace.
@@ -93,42 +105,59 @@ pub trait Trace: Sized {
         self.layout().aux_trace_width()
     }
 
+    /// Returns the name of the column at the specified index in the main segment of this trace,
+    /// or None if the column was not named.
+    ///
+    /// Column names are used only to make trace validation errors easier to read.
+    fn main_column_name(&self, _index: usize) -> Option<&str> {
+        None
+    }
+
     // VALIDATION
     // --------------------------------------------------------------------------------------------
-    /// Checks if this trace is valid against the specified AIR, and panics if not.
+    /// Checks if this trace is valid against the specified AIR.
     ///
     /// NOTE: this is a very expensive operation and is intended for use only in debug mode.
+    ///
+    /// # Errors
+    /// Returns [ProverError::InvalidTrace] describing the first violation found if the width of
+    /// this trace does not match the AIR, or if this trace does not satisfy any of the assertions
+    /// or transition constraints defined by the AIR.
     fn validate<A, E>(
         &self,
         air: &A,
         aux_segments: &[ColMatrix<E>],
         aux_rand_elements: &AuxTraceRandElements<E>,
-    ) where
+    ) -> Result<(), ProverError>
+    where
         A: Air<BaseField = Self::BaseField>,
         E: FieldElement<BaseField = Self::BaseField>,
     {
         // make sure the width align; if they don't something went terribly wrong
-        assert_eq!(
-            self.main_trace_width(),
-            air.trace_layout().main_trace_width(),
-            "inconsistent trace width: expected {}, but was {}",
-            self.main_trace_width(),
-            air.trace_layout().main_trace_width(),
-        );
+        if self.main_trace_width() != air.trace_layout().main_trace_width() {
+            return Err(ProverError::InvalidTrace(format!(
+                "inconsistent trace width: expected {}, but was {}",
+                air.trace_layout().main_trace_width(),
+                self.main_trace_width(),
+            )));
+        }
 
         // --- 1. make sure the assertions are valid ----------------------------------------------
 
         // first, check assertions against the main segment of the execution trace
         for assertion in air.get_assertions() {
+            let mut result = Ok(());
             assertion.apply(self.length(), |step, value| {
-                assert!(
-                    value == self.main_segment().get(assertion.column(), step),
-                    "trace does not satisfy assertion main_trace({}, {}) == {}",
-                    assertion.column(),
-                    step,
-                    value
-                );
+                if result.is_ok() && value != self.main_segment().get(assertion.column(), step) {
+                    result = Err(ProverError::InvalidTrace(format!(
+                        "trace does not satisfy assertion main_trace({}, {}) == {}",
+                        main_column_label(self, assertion.column()),
+                        step,
+                        value
+                    )));
+                }
             });
+            result?;
         }
 
         // then, check assertions against auxiliary trace segments
@@ -147,15 +176,18 @@ pub trait Trace: Sized {
             }
 
             // get the matrix and verify the assertion against it
+            let mut result = Ok(());
             assertion.apply(self.length(), |step, value| {
-                assert!(
-                    value == aux_segments[segment_idx].get(column_idx, step),
-                    "trace does not satisfy assertion aux_trace({}, {}) == {}",
-                    assertion.column(),
-                    step,
-                    value
-                );
+                if result.is_ok() && value != aux_segments[segment_idx].get(column_idx, step) {
+                    result = Err(ProverError::InvalidTrace(format!(
+                        "trace does not satisfy assertion aux_trace({}, {}) == {}",
+                        assertion.column(),
+                        step,
+                        value
+                    )));
+                }
             });
+            result?;
         }
 
         // --- 2. make sure this trace satisfies all transition constraints -----------------------
@@ -192,10 +224,14 @@ pub trait Trace: Sized {
             self.read_main_frame(step, &mut main_frame);
             air.evaluate_transition(&main_frame, &periodic_values, &mut main_evaluations);
             for (i, &evaluation) in main_evaluations.iter().enumerate() {
-                assert!(
-                    evaluation == Self::BaseField::ZERO,
-                    "main transition constraint {i} did not evaluate to ZERO at step {step}"
-                );
+                if evaluation != Self::BaseField::ZERO {
+                    return Err(ProverError::InvalidTrace(format!(
+                        "main transition constraint {i} did not evaluate to ZERO at step {step}\n\
+                        current row: {}\nnext row: {}",
+                        describe_main_row(self, main_frame.current()),
+                        describe_main_row(self, main_frame.next())
+                    )));
+                }
             }
 
             // evaluate transition constraints for auxiliary trace segments (if any) and make
@@ -210,22 +246,48 @@ pub trait Trace: Sized {
                     &mut aux_evaluations,
                 );
                 for (i, &evaluation) in aux_evaluations.iter().enumerate() {
-                    assert!(
-                        evaluation == E::ZERO,
-                        "auxiliary transition constraint {i} did not evaluate to ZERO at step {step}"
-                    );
+                    if evaluation != E::ZERO {
+                        return Err(ProverError::InvalidTrace(format!(
+                            "auxiliary transition constraint {i} did not evaluate to ZERO at step {step}"
+                        )));
+                    }
                 }
             }
 
             // update x coordinate of the domain
             x *= g;
         }
+
+        Ok(())
     }
 }
 
 // HELPER FUNCTIONS
 // ================================================================================================
 
+/// Returns a label for the main trace segment column at the specified index; the label contains
+/// the name of the column if the column was named.
+fn main_column_label<T: Trace>(trace: &T, index: usize) -> String {
+    match trace.main_column_name(index) {
+        Some(name) => format!("{index} ({name})"),
+        None => index.to_string(),
+    }
+}
+
+/// Returns a description of the provided row of the main trace segment, listing the value of each
+/// column together with the column name (if the column was named).
+fn describe_main_row<T: Trace>(trace: &T, row: &[T::BaseField]) -> String {
+    let values = row
+        .iter()
+        .enumerate()
+        .map(|(i, value)| match trace.main_column_name(i) {
+            Some(name) => format!("{name}={value}"),
+            None => value.to_string(),
+        })
+        .collect::<Vec<_>>();
+    format!("[{}]", values.join(", "))
+}
+
 /// Reads an evaluation frame from the set of provided auxiliary segments. This expects that
 /// `aux_segments` contains at least one entry.
 ///
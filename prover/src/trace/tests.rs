@@ -6,14 +6,15 @@
 use crate::{
     tests::{build_fib_trace, MockAir},
     trace::TracePolyTable,
-    RowMatrix, StarkDomain, Trace, TraceCommitment,
+    ProverError, RowMatrix, StarkDomain, Trace, TraceCommitment, TraceTable,
 };
+use air::{Assertion, AuxTraceRandElements};
 use crypto::{hashers::Blake3_256, ElementHasher, MerkleTree};
 use math::{
     fields::f128::BaseElement, get_power_series, get_power_series_with_offset, polynom,
     FieldElement, StarkField,
 };
-use utils::collections::Vec;
+use utils::{collections::Vec, string::ToString};
 
 type Blake3 = Blake3_256<BaseElement>;
 
@@ -38,6 +39,203 @@ fn new_trace_table() {
     assert_eq!(expected, trace.get_column(1));
 }
 
+#[test]
+fn trace_table_views() {
+    let trace = build_fib_trace(16);
+
+    let columns = trace.columns().collect::<Vec<_>>();
+    assert_eq!(vec![trace.get_column(0), trace.get_column(1)], columns);
+
+    let row = trace.get_row(3);
+    assert_eq!(3, row.index());
+    assert_eq!(2, row.len());
+    assert_eq!(BaseElement::new(21), row.get(1));
+    assert_eq!(
+        vec![BaseElement::new(13), BaseElement::new(21)],
+        row.to_vec()
+    );
+
+    assert_eq!(trace.length(), trace.rows().len());
+    let mut expected = vec![BaseElement::ZERO; trace.main_trace_width()];
+    for row in trace.rows() {
+        trace.read_row_into(row.index(), &mut expected);
+        assert_eq!(expected, row.iter().collect::<Vec<_>>());
+    }
+}
+
+#[test]
+fn trace_table_column_names() {
+    let mut trace = build_fib_trace(16);
+    assert_eq!(None, trace.main_column_name(0));
+
+    trace.set_column_names(vec!["a", "b"]);
+    assert_eq!(Some("a"), trace.main_column_name(0));
+    assert_eq!(Some("b"), trace.main_column_name(1));
+    assert_eq!(None, trace.main_column_name(2));
+}
+
+#[test]
+#[should_panic(expected = "number of column names must be equal to trace width 2, but was 1")]
+fn trace_table_column_names_wrong_count() {
+    let mut trace = build_fib_trace(16);
+    trace.set_column_names(vec!["a"]);
+}
+
+#[test]
+fn trace_table_from_step_fn() {
+    let trace = TraceTable::from_step_fn(2, 8, |step, prev_row, next_row| {
+        next_row[0] = BaseElement::from(step as u32);
+        next_row[1] = prev_row[1] + next_row[0];
+    });
+    assert_eq!(8, trace.length());
+
+    let expected: Vec<BaseElement> = (0..8u32).map(BaseElement::from).collect();
+    assert_eq!(expected, trace.get_column(0));
+    let expected: Vec<BaseElement> = vec![0u32, 1, 3, 6, 10, 15, 21, 28]
+        .into_iter()
+        .map(BaseElement::from)
+        .collect();
+    assert_eq!(expected, trace.get_column(1));
+}
+
+#[test]
+fn trace_table_from_transition() {
+    // builds the same trace as build_fib_trace()
+    let trace = TraceTable::from_transition(vec![BaseElement::ONE; 2], 8, |row| {
+        let next0 = row[0] + row[1];
+        vec![next0, row[1] + next0]
+    });
+    let expected = build_fib_trace(16);
+    assert_eq!(expected.main_trace_width(), trace.main_trace_width());
+    assert_eq!(expected.get_column(0), trace.get_column(0));
+    assert_eq!(expected.get_column(1), trace.get_column(1));
+}
+
+#[test]
+#[should_panic(expected = "transition must return rows of width 2, but returned a row of width 1")]
+fn trace_table_from_transition_wrong_width() {
+    TraceTable::from_transition(vec![BaseElement::ONE; 2], 8, |row| vec![row[0]]);
+}
+
+#[test]
+fn init_padded_trace_table() {
+    let column: Vec<BaseElement> = (1..=5u32).map(BaseElement::from).collect();
+    let trace = TraceTable::init_padded(vec![column.clone(), column]);
+    assert_eq!(8, trace.length());
+    assert_eq!(5, trace.original_length());
+
+    let expected: Vec<BaseElement> = vec![1u32, 2, 3, 4, 5, 5, 5, 5]
+        .into_iter()
+        .map(BaseElement::from)
+        .collect();
+    assert_eq!(expected, trace.get_column(0));
+    assert_eq!(expected, trace.get_column(1));
+
+    let info = trace.get_info();
+    assert_eq!(8, info.length());
+    assert_eq!(5, info.original_length());
+    assert!(info.is_padded());
+}
+
+#[test]
+fn init_padded_trace_table_with_closure() {
+    // a counter which keeps incrementing through the padding rows
+    let column: Vec<BaseElement> = (0..5u32).map(BaseElement::from).collect();
+    let trace = TraceTable::init_padded_with(vec![column], |_, state| {
+        state[0] += BaseElement::ONE;
+    });
+    assert_eq!(8, trace.length());
+    assert_eq!(5, trace.original_length());
+
+    let expected: Vec<BaseElement> = (0..8u32).map(BaseElement::from).collect();
+    assert_eq!(expected, trace.get_column(0));
+
+    // traces which are already a power of two are not padded
+    let trace = TraceTable::init_padded(vec![expected]);
+    assert_eq!(8, trace.length());
+    assert!(!trace.get_info().is_padded());
+}
+
+#[test]
+fn fill_trace_table_fragments() {
+    let mut trace = TraceTable::<BaseElement>::new(2, 16);
+    trace
+        .fill_fragments(4, |fragment| {
+            let offset = BaseElement::from(fragment.offset() as u32);
+            fragment.fill_with_boundary(
+                |state| {
+                    state[0] = offset;
+                    state[1] = offset.square();
+                },
+                |_, state| {
+                    state[0] += BaseElement::ONE;
+                    state[1] = state[0].square();
+                },
+            )
+        })
+        .unwrap();
+
+    let expected: Vec<BaseElement> = (0..16u32).map(BaseElement::from).collect();
+    assert_eq!(expected, trace.get_column(0));
+    let expected: Vec<BaseElement> = (0..16u32).map(|i| BaseElement::from(i * i)).collect();
+    assert_eq!(expected, trace.get_column(1));
+}
+
+#[test]
+fn fill_trace_table_fragments_with_inconsistent_boundary() {
+    let mut trace = TraceTable::<BaseElement>::new(2, 16);
+    let result = trace.fill_fragments(4, |fragment| {
+        // fragment at index 2 starts with an incorrect value in the second column
+        let offset = BaseElement::from(fragment.offset() as u32);
+        let skew = if fragment.index() == 2 {
+            BaseElement::ONE
+        } else {
+            BaseElement::ZERO
+        };
+        fragment.fill_with_boundary(
+            |state| {
+                state[0] = offset;
+                state[1] = offset.square() + skew;
+            },
+            |_, state| {
+                state[0] += BaseElement::ONE;
+                state[1] = state[0].square();
+            },
+        )
+    });
+    assert_eq!(
+        Err(ProverError::InconsistentTraceFragments(1, vec![1])),
+        result
+    );
+}
+
+#[test]
+fn validate_trace_with_wrong_width() {
+    let air = MockAir::with_trace_length(8);
+    let trace = build_fib_trace(16);
+    let result = trace.validate::<_, BaseElement>(&air, &[], &AuxTraceRandElements::new());
+    assert_eq!(
+        Err(ProverError::InvalidTrace(
+            "inconsistent trace width: expected 4, but was 2".to_string()
+        )),
+        result
+    );
+}
+
+#[test]
+fn validate_trace_with_unsatisfied_assertion() {
+    let assertion = Assertion::single(1, 0, BaseElement::new(5));
+    let air = MockAir::with_assertions(vec![assertion], 8);
+    let trace = TraceTable::init(vec![vec![BaseElement::ZERO; 8]; 4]);
+    let result = trace.validate::<_, BaseElement>(&air, &[], &AuxTraceRandElements::new());
+    assert_eq!(
+        Err(ProverError::InvalidTrace(
+            "trace does not satisfy assertion main_trace(1, 0) == 5".to_string()
+        )),
+        result
+    );
+}
+
 #[test]
 fn extend_trace_table() {
     // build the trace and the domain
@@ -17,7 +17,12 @@ use utils::collections::Vec;
 ///   will always be elements in the base field (even when an extension field is used).
 /// - Auxiliary segments: a list of 0 or more segments for traces generated after the prover
 ///   commits to the first trace segment. Currently, at most 1 auxiliary segment is possible.
+///
+/// When the execution trace contains preprocessed columns, extensions of these columns are stored
+/// separately from the rest of the main segment, but they are read as the first columns of the
+/// main segment.
 pub struct TraceLde<E: FieldElement> {
+    preprocessed_lde: Option<RowMatrix<E::BaseField>>,
     main_segment_lde: RowMatrix<E::BaseField>,
     aux_segment_ldes: Vec<RowMatrix<E>>,
     blowup: usize,
@@ -29,6 +34,7 @@ impl<E: FieldElement> TraceLde<E> {
     /// Creates a new trace low-degree extension table from the provided main trace segment LDE.
     pub fn new(main_trace_lde: RowMatrix<E::BaseField>, blowup: usize) -> Self {
         Self {
+            preprocessed_lde: None,
             main_segment_lde: main_trace_lde,
             aux_segment_ldes: Vec::new(),
             blowup,
@@ -38,6 +44,16 @@ impl<E: FieldElement> TraceLde<E> {
     // STATE MUTATORS
     // --------------------------------------------------------------------------------------------
 
+    /// Sets the LDE of preprocessed columns which precede the columns of the main segment.
+    pub fn set_preprocessed_segment(&mut self, preprocessed_lde: RowMatrix<E::BaseField>) {
+        assert_eq!(
+            self.main_segment_lde.num_rows(),
+            preprocessed_lde.num_rows(),
+            "number of rows in preprocessed columns must be of the same as in the main segment"
+        );
+        self.preprocessed_lde = Some(preprocessed_lde);
+    }
+
     /// Adds the provided auxiliary segment LDE to this trace LDE.
     pub fn add_aux_segment(&mut self, aux_segment_lde: RowMatrix<E>) {
         assert_eq!(
@@ -52,8 +68,10 @@ impl<E: FieldElement> TraceLde<E> {
     // --------------------------------------------------------------------------------------------
 
     /// Returns number of columns in the main segment of the execution trace.
+    ///
+    /// This includes preprocessed columns (if any).
     pub fn main_trace_width(&self) -> usize {
-        self.main_segment_lde.num_cols()
+        self.num_preprocessed_cols() + self.main_segment_lde.num_cols()
     }
 
     /// Returns number of columns in the auxiliary segments of the execution trace.
@@ -82,13 +100,16 @@ impl<E: FieldElement> TraceLde<E> {
         // at the end of the trace, next state wraps around and we read the first step again
         let next_lde_step = (lde_step + self.blowup()) % self.trace_len();
 
+        // copy preprocessed column values (if any) into the frame
+        let offset = self.num_preprocessed_cols();
+        if let Some(preprocessed_lde) = &self.preprocessed_lde {
+            frame.current_mut()[..offset].copy_from_slice(preprocessed_lde.row(lde_step));
+            frame.next_mut()[..offset].copy_from_slice(preprocessed_lde.row(next_lde_step));
+        }
+
         // copy main trace segment values into the frame
-        frame
-            .current_mut()
-            .copy_from_slice(self.main_segment_lde.row(lde_step));
-        frame
-            .next_mut()
-            .copy_from_slice(self.main_segment_lde.row(next_lde_step));
+        frame.current_mut()[offset..].copy_from_slice(self.main_segment_lde.row(lde_step));
+        frame.next_mut()[offset..].copy_from_slice(self.main_segment_lde.row(next_lde_step));
     }
 
     /// Reads current and next rows from the auxiliary trace segment into the specified frame.
@@ -107,6 +128,8 @@ impl<E: FieldElement> TraceLde<E> {
     }
 
     /// Returns a reference to [Matrix] representing the main trace segment.
+    ///
+    /// Preprocessed columns are not included in the returned matrix.
     pub fn get_main_segment(&self) -> &RowMatrix<E::BaseField> {
         &self.main_segment_lde
     }
@@ -116,4 +139,14 @@ impl<E: FieldElement> TraceLde<E> {
     pub fn get_aux_segment(&self, aux_segment_idx: usize) -> &RowMatrix<E> {
         &self.aux_segment_ldes[aux_segment_idx]
     }
+
+    // HELPER METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the number of preprocessed columns in this trace LDE.
+    fn num_preprocessed_cols(&self) -> usize {
+        self.preprocessed_lde
+            .as_ref()
+            .map_or(0, |lde| lde.num_cols())
+    }
 }
@@ -4,9 +4,13 @@
 // LICENSE file in the root directory of this source tree.
 
 use super::{ColMatrix, Trace};
+use crate::{
+    matrix::{ColumnIter, RowView},
+    ProverError,
+};
 use air::{EvaluationFrame, TraceInfo, TraceLayout};
 use math::{FieldElement, StarkField};
-use utils::{collections::Vec, uninit_vector};
+use utils::{collections::Vec, string::String};
 
 #[cfg(not(feature = "concurrent"))]
 use utils::collections::vec;
@@ -34,16 +38,26 @@ const MIN_FRAGMENT_LENGTH: usize = 2;
 /// 1. Lengths of all columns in the execution trace must be the same.
 /// 2. The length of the columns must be some power of two.
 ///
+/// If the length of the columns is not a power of two, [TraceTable::init_padded()] or
+/// [TraceTable::init_padded_with()] functions can be used instead. These functions pad the
+/// trace to the next power of two and record the original length of the trace in the trace info
+/// so that assertions against the last step of the actual computation can still be expressed
+/// via [TraceInfo::original_length()].
+///
 /// The other approach is to use [TraceTable::new()] function, which takes trace width and
 /// length as parameters. This function will allocate memory for the trace, but will not fill it
-/// with data. To fill the execution trace, you can use the [fill()](TraceTable::fill) method,
-/// which takes two closures as parameters:
+/// with data (all values are initially set to zero). To fill the execution trace, you can use the
+/// [fill()](TraceTable::fill) method, which takes two closures as parameters:
 ///
 /// 1. The first closure is responsible for initializing the first state of the computation
 ///    (the first row of the execution trace).
 /// 2. The second closure receives the previous state of the execution trace as input, and must
 ///    update it to the next state of the computation.
 ///
+/// For simple computations, [TraceTable::from_transition()] and [TraceTable::from_step_fn()]
+/// functions combine allocation and filling of the trace into a single step. The former infers
+/// trace width from the initial row and computes each subsequent row from the previous one.
+///
 /// You can also use [TraceTable::with_meta()] function to create a blank execution trace.
 /// This function work just like [TraceTable::new()] function, but also takes a metadata
 /// parameter which can be an arbitrary sequence of bytes up to 64KB in size.
@@ -59,10 +73,17 @@ const MIN_FRAGMENT_LENGTH: usize = 2;
 /// [fill()](TraceTableFragment::fill) method to fill all fragments with data in parallel.
 /// The semantics of the fragment's [TraceTableFragment::fill()] method are identical to the
 /// semantics of the [TraceTable::fill()] method.
+///
+/// When fragments are not independent (i.e., the first row of a fragment is supposed to follow
+/// from the last row of the previous fragment), [fill_fragments()](TraceTable::fill_fragments)
+/// method can be used instead. This method fills fragments in parallel as well, but also makes
+/// sure that the rows at the boundaries between fragments are consistent with each other.
 pub struct TraceTable<B: StarkField> {
     layout: TraceLayout,
     trace: ColMatrix<B>,
+    original_length: usize,
     meta: Vec<u8>,
+    column_names: Vec<String>,
 }
 
 impl<B: StarkField> TraceTable<B> {
@@ -71,7 +92,7 @@ impl<B: StarkField> TraceTable<B> {
 
     /// Creates a new execution trace of the specified width and length.
     ///
-    /// This allocates all the required memory for the trace, but does not initialize it. It is
+    /// This allocates all the required memory for the trace and sets all values to zero. It is
     /// expected that the trace will be filled using one of the data mutator methods.
     ///
     /// # Panics
@@ -86,7 +107,7 @@ impl<B: StarkField> TraceTable<B> {
     /// Creates a new execution trace of the specified width and length, and with the specified
     /// metadata.
     ///
-    /// This allocates all the required memory for the trace, but does not initialize it. It is
+    /// This allocates all the required memory for the trace and sets all values to zero. It is
     /// expected that the trace will be filled using one of the data mutator methods.
     ///
     /// # Panics
@@ -129,11 +150,15 @@ impl<B: StarkField> TraceTable<B> {
             meta.len()
         );
 
-        let columns = unsafe { (0..width).map(|_| uninit_vector(length)).collect() };
+        // the trace is zero-initialized because it is filled by user-provided closures which
+        // are not guaranteed to write every cell before it is read
+        let columns = (0..width).map(|_| B::zeroed_vector(length)).collect();
         Self {
             layout: TraceLayout::new(width, [0], [0]),
             trace: ColMatrix::new(columns),
+            original_length: length,
             meta,
+            column_names: Vec::new(),
         }
     }
 
@@ -141,7 +166,7 @@ impl<B: StarkField> TraceTable<B> {
     ///
     /// # Panics
     /// Panics if:
-    /// * The `columns` vector is empty or has over 255 columns.
+    /// * The `columns` vector is empty or has over 65535 columns.
     /// * Number of elements in any of the columns is smaller than 8, greater than the biggest
     ///   multiplicative subgroup in the field `B`, or is not a power of two.
     /// * Number of elements is not identical for all columns.
@@ -184,8 +209,148 @@ impl<B: StarkField> TraceTable<B> {
         Self {
             layout: TraceLayout::new(columns.len(), [0], [0]),
             trace: ColMatrix::new(columns),
+            original_length: trace_length,
             meta: vec![],
+            column_names: Vec::new(),
+        }
+    }
+
+    /// Creates a new execution trace from a list of provided trace columns of arbitrary length.
+    ///
+    /// The columns are padded to the next power of two (but to at least 4 rows) by repeating the
+    /// last row of the trace. The original length of the columns is recorded in the trace info.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * The `columns` vector is empty or has over 65535 columns.
+    /// * Columns are empty or the padded length of the columns is greater than the biggest
+    ///   multiplicative subgroup in the field `B`.
+    /// * Number of elements is not identical for all columns.
+    pub fn init_padded(columns: Vec<Vec<B>>) -> Self {
+        Self::init_padded_with(columns, |_, _| {})
+    }
+
+    /// Creates a new execution trace from a list of provided trace columns of arbitrary length
+    /// padding the columns with rows computed by the provided closure.
+    ///
+    /// The columns are padded to the next power of two (but to at least 4 rows). The `pad`
+    /// closure is used to compute the padding rows in the same way as the `update` closure is
+    /// used by the [fill()](TraceTable::fill) method: it receives the index of the last updated
+    /// row and a mutable reference to the last updated state; the contents of the state are
+    /// copied into the next row of the trace after the closure returns. The original length of
+    /// the columns is recorded in the trace info.
+    ///
+    /// Padding rows are subject to the same transition constraints as all other rows, and thus,
+    /// the closure must compute rows which are consistent with the constraints of the
+    /// computation.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * The `columns` vector is empty or has over 65535 columns.
+    /// * Columns are empty or the padded length of the columns is greater than the biggest
+    ///   multiplicative subgroup in the field `B`.
+    /// * Number of elements is not identical for all columns.
+    pub fn init_padded_with<F>(mut columns: Vec<Vec<B>>, mut pad: F) -> Self
+    where
+        F: FnMut(usize, &mut [B]),
+    {
+        assert!(
+            !columns.is_empty(),
+            "execution trace must consist of at least one column"
+        );
+        let original_length = columns[0].len();
+        assert!(original_length > 0, "execution trace must not be empty");
+        for column in columns.iter().skip(1) {
+            assert_eq!(
+                column.len(),
+                original_length,
+                "all columns traces must have the same length"
+            );
         }
+
+        let trace_length = original_length
+            .next_power_of_two()
+            .max(TraceInfo::MIN_TRACE_LENGTH);
+        let mut state: Vec<B> = columns
+            .iter()
+            .map(|column| column[original_length - 1])
+            .collect();
+        for i in original_length - 1..trace_length - 1 {
+            pad(i, &mut state);
+            for (column, &value) in columns.iter_mut().zip(state.iter()) {
+                column.push(value);
+            }
+        }
+
+        let mut result = Self::init(columns);
+        result.original_length = original_length;
+        result
+    }
+
+    /// Creates a new execution trace of the specified width and length by computing each row of
+    /// the trace with the provided closure.
+    ///
+    /// The `step` closure is invoked once for every row of the trace and receives three
+    /// parameters:
+    /// - index of the row being computed (starting with 0).
+    /// - a reference to the previous row of the trace; for the first row, this is a row of all
+    ///   zeros.
+    /// - a mutable reference to the row being computed, initialized to all zeros.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * `width` is zero or greater than 65535.
+    /// * `length` is smaller than 4, greater than the biggest multiplicative subgroup in the
+    ///   field `B`, or is not a power of two.
+    pub fn from_step_fn<F>(width: usize, length: usize, mut step: F) -> Self
+    where
+        F: FnMut(usize, &[B], &mut [B]),
+    {
+        let mut result = Self::new(width, length);
+        let mut prev_row = vec![B::ZERO; width];
+        let mut next_row = vec![B::ZERO; width];
+        for i in 0..length {
+            next_row.iter_mut().for_each(|value| *value = B::ZERO);
+            step(i, &prev_row, &mut next_row);
+            result.update_row(i, &next_row);
+            core::mem::swap(&mut prev_row, &mut next_row);
+        }
+        result
+    }
+
+    /// Creates a new execution trace of the specified length starting with the provided
+    /// `init_row` and computing each subsequent row by applying the `transition` closure to the
+    /// previous row.
+    ///
+    /// Width of the trace is inferred from the length of `init_row`.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * `init_row` is empty or contains more than 65535 values.
+    /// * `length` is smaller than 4, greater than the biggest multiplicative subgroup in the
+    ///   field `B`, or is not a power of two.
+    /// * The `transition` closure returns a row with a number of values different from the
+    ///   width of the trace.
+    pub fn from_transition<F>(init_row: Vec<B>, length: usize, mut transition: F) -> Self
+    where
+        F: FnMut(&[B]) -> Vec<B>,
+    {
+        let mut result = Self::new(init_row.len(), length);
+        let mut row = init_row;
+        result.update_row(0, &row);
+        for i in 1..length {
+            row = transition(&row);
+            assert_eq!(
+                row.len(),
+                result.main_trace_width(),
+                "transition must return rows of width {}, but returned a row of width {} for step {}",
+                result.main_trace_width(),
+                row.len(),
+                i
+            );
+            result.update_row(i, &row);
+        }
+        result
     }
 
     // DATA MUTATORS
@@ -216,6 +381,39 @@ impl<B: StarkField> TraceTable<B> {
         self.meta = meta
     }
 
+    /// Marks the first `num_columns` columns of this execution trace as preprocessed columns.
+    ///
+    /// Values in preprocessed columns must not depend on inputs of the computation; a prover for
+    /// such traces must provide a commitment to these columns via
+    /// [Prover::preprocessed_commitment()](crate::Prover::preprocessed_commitment).
+    ///
+    /// # Panics
+    /// Panics if `num_columns` is not smaller than the width of this execution trace.
+    pub fn set_num_preprocessed_columns(&mut self, num_columns: usize) {
+        self.layout = self
+            .layout
+            .clone()
+            .set_num_preprocessed_columns(num_columns);
+    }
+
+    /// Sets names for the columns of this execution trace.
+    ///
+    /// Column names are optional and are used only to make trace validation errors easier to
+    /// read (e.g., to report which named columns were involved in an unsatisfied constraint).
+    ///
+    /// # Panics
+    /// Panics if the number of provided names is not equal to the width of this trace.
+    pub fn set_column_names<S: Into<String>>(&mut self, names: Vec<S>) {
+        assert_eq!(
+            names.len(),
+            self.width(),
+            "number of column names must be equal to trace width {}, but was {}",
+            self.width(),
+            names.len()
+        );
+        self.column_names = names.into_iter().map(Into::into).collect();
+    }
+
     /// Fill all rows in the execution trace.
     ///
     /// The rows are filled by executing the provided closures as follows:
@@ -259,7 +457,10 @@ impl<B: StarkField> TraceTable<B> {
     /// Panics if `fragment_length` is smaller than 2, greater than the length of the trace,
     /// or is not a power of two.
     #[cfg(not(feature = "concurrent"))]
-    pub fn fragments(&mut self, fragment_length: usize) -> vec::IntoIter<TraceTableFragment<B>> {
+    pub fn fragments(
+        &mut self,
+        fragment_length: usize,
+    ) -> vec::IntoIter<TraceTableFragment<'_, B>> {
         self.build_fragments(fragment_length).into_iter()
     }
 
@@ -275,13 +476,77 @@ impl<B: StarkField> TraceTable<B> {
     pub fn fragments(
         &mut self,
         fragment_length: usize,
-    ) -> rayon::vec::IntoIter<TraceTableFragment<B>> {
+    ) -> rayon::vec::IntoIter<TraceTableFragment<'_, B>> {
         self.build_fragments(fragment_length).into_par_iter()
     }
 
+    /// Fills the execution trace by building the specified number of fragments in parallel.
+    ///
+    /// The trace is broken into `num_fragments` equally sized fragments, and the `build` closure
+    /// is executed for each fragment (in multiple threads when `concurrent` feature is enabled).
+    /// The closure must fill all rows of the fragment, and must return the state which would
+    /// immediately follow the last row of the fragment. [TraceTableFragment::fill_with_boundary()]
+    /// method can be used to do both of these things at once.
+    ///
+    /// After all fragments are filled, the boundary state returned for every fragment (except for
+    /// the last one) is compared against the first row of the next fragment.
+    ///
+    /// # Errors
+    /// Returns an error if a boundary state returned for a fragment does not match the first
+    /// row of the next fragment. The error contains the index of the fragment and indexes of all
+    /// columns for which the values did not match.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * `num_fragments` is zero or is not a power of two.
+    /// * The resulting fragment length is smaller than 2.
+    /// * A boundary state returned by the `build` closure does not have the same number of
+    ///   elements as the width of the trace.
+    pub fn fill_fragments<F>(&mut self, num_fragments: usize, build: F) -> Result<(), ProverError>
+    where
+        F: Fn(&mut TraceTableFragment<B>) -> Vec<B> + Send + Sync,
+    {
+        assert!(
+            num_fragments > 0 && num_fragments.is_power_of_two(),
+            "number of fragments must be a power of 2, but was {num_fragments}"
+        );
+        let fragment_length = self.length() / num_fragments;
+
+        // fill all fragments and collect the boundary states; the order of boundaries is the
+        // same as the order of fragments regardless of how the fragments were processed.
+        let boundaries = self
+            .fragments(fragment_length)
+            .map(|mut fragment| build(&mut fragment))
+            .collect::<Vec<_>>();
+
+        // make sure the boundary state of each fragment matches the first row of the next one
+        let width = self.width();
+        for (i, boundary) in boundaries.iter().enumerate().take(num_fragments - 1) {
+            assert_eq!(
+                boundary.len(),
+                width,
+                "boundary state of fragment {i} must have {width} elements, but had {}",
+                boundary.len()
+            );
+
+            let next_row = (i + 1) * fragment_length;
+            let mismatched_columns = (0..width)
+                .filter(|&col_idx| boundary[col_idx] != self.get(col_idx, next_row))
+                .collect::<Vec<_>>();
+            if !mismatched_columns.is_empty() {
+                return Err(ProverError::InconsistentTraceFragments(
+                    i,
+                    mismatched_columns,
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
     /// Returns a vector of trace fragments each covering the number of steps specified by the
     /// `fragment_length` parameter.
-    fn build_fragments(&mut self, fragment_length: usize) -> Vec<TraceTableFragment<B>> {
+    fn build_fragments(&mut self, fragment_length: usize) -> Vec<TraceTableFragment<'_, B>> {
         assert!(
             fragment_length >= MIN_FRAGMENT_LENGTH,
             "fragment length must be at least {MIN_FRAGMENT_LENGTH}, but was {fragment_length}"
@@ -329,6 +594,25 @@ impl<B: StarkField> TraceTable<B> {
         self.trace.get_column(col_idx)
     }
 
+    /// Returns an iterator over all columns of this execution trace.
+    pub fn columns(&self) -> ColumnIter<'_, B> {
+        self.trace.columns()
+    }
+
+    /// Returns a view of the row at the specified step of this execution trace; no data is copied
+    /// when the view is created.
+    ///
+    /// # Panics
+    /// Panics if `step` is out of bounds for this trace.
+    pub fn get_row(&self, step: usize) -> RowView<'_, B> {
+        self.trace.get_row(step)
+    }
+
+    /// Returns an iterator over views of all rows of this execution trace.
+    pub fn rows(&self) -> impl ExactSizeIterator<Item = RowView<'_, B>> {
+        self.trace.rows()
+    }
+
     /// Returns value of the cell in the specified column at the specified row of this trace.
     pub fn get(&self, column: usize, step: usize) -> B {
         self.trace.get(column, step)
@@ -354,6 +638,10 @@ impl<B: StarkField> Trace for TraceTable<B> {
         self.trace.num_rows()
     }
 
+    fn original_length(&self) -> usize {
+        self.original_length
+    }
+
     fn meta(&self) -> &[u8] {
         &self.meta
     }
@@ -368,6 +656,10 @@ impl<B: StarkField> Trace for TraceTable<B> {
         &self.trace
     }
 
+    fn main_column_name(&self, index: usize) -> Option<&str> {
+        self.column_names.get(index).map(String::as_str)
+    }
+
     fn build_aux_segment<E>(
         &mut self,
         _aux_segments: &[ColMatrix<E>],
@@ -452,6 +744,31 @@ impl<'a, B: StarkField> TraceTableFragment<'a, B> {
         }
     }
 
+    /// Fills all rows in the fragment and returns the state which follows the last row.
+    ///
+    /// This method works identically to [fill()](TraceTableFragment::fill), but executes the
+    /// `update` closure one extra time after the last row of the fragment has been filled. The
+    /// state resulting from this extra update is not written into the fragment, but is returned
+    /// to the caller instead. This is useful for checking that adjacent fragments are consistent
+    /// with each other (see [TraceTable::fill_fragments()]).
+    pub fn fill_with_boundary<I, T>(&mut self, init_state: I, mut update_state: T) -> Vec<B>
+    where
+        I: FnOnce(&mut [B]),
+        T: FnMut(usize, &mut [B]),
+    {
+        let mut state = vec![B::ZERO; self.width()];
+        init_state(&mut state);
+        self.update_row(0, &state);
+
+        for i in 0..self.length() - 1 {
+            update_state(i, &mut state);
+            self.update_row(i + 1, &state);
+        }
+
+        update_state(self.length() - 1, &mut state);
+        state
+    }
+
     /// Updates a single row in the fragment with provided data.
     pub fn update_row(&mut self, row_idx: usize, row_data: &[B]) {
         for (column, &value) in self.data.iter_mut().zip(row_data) {
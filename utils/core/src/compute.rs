@@ -0,0 +1,75 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use rayon::{ThreadPool, ThreadPoolBuildError, ThreadPoolBuilder};
+use std::sync::Arc;
+
+// COMPUTE CONTEXT
+// ================================================================================================
+
+/// A thread pool on which concurrent code of Winterfell crates is executed.
+///
+/// By default, concurrent code runs on the global rayon thread pool. When many proofs are
+/// generated concurrently, this may lead to oversubscription of CPU cores, and does not allow
+/// limiting parallelism of individual requests. A compute context executes an operation on a
+/// dedicated thread pool instead; all parallel iterators used (directly or indirectly) by the
+/// operation run on the threads of this pool.
+///
+/// All clones of a context share the same thread pool.
+#[derive(Debug, Clone)]
+pub struct ComputeContext {
+    pool: Arc<ThreadPool>,
+}
+
+impl ComputeContext {
+    // CONSTRUCTORS
+    // --------------------------------------------------------------------------------------------
+    /// Returns a new context which executes operations on the specified thread pool.
+    ///
+    /// The thread pool can be configured via [ThreadPoolBuilder] (e.g., to pin threads to
+    /// specific cores via a start handler).
+    pub fn new(pool: ThreadPool) -> Self {
+        ComputeContext {
+            pool: Arc::new(pool),
+        }
+    }
+
+    /// Returns a new context which executes operations on a new thread pool with the specified
+    /// number of threads.
+    ///
+    /// # Errors
+    /// Returns an error if the thread pool could not be created.
+    pub fn with_num_threads(num_threads: usize) -> Result<Self, ThreadPoolBuildError> {
+        let pool = ThreadPoolBuilder::new().num_threads(num_threads).build()?;
+        Ok(Self::new(pool))
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the number of threads in the thread pool of this context.
+    pub fn num_threads(&self) -> usize {
+        self.pool.current_num_threads()
+    }
+
+    /// Returns the thread pool of this context.
+    pub fn thread_pool(&self) -> &ThreadPool {
+        &self.pool
+    }
+
+    // EXECUTION
+    // --------------------------------------------------------------------------------------------
+
+    /// Executes `op` on the thread pool of this context and returns its result.
+    ///
+    /// The calling thread is blocked until `op` completes.
+    pub fn install<OP, R>(&self, op: OP) -> R
+    where
+        OP: FnOnce() -> R + Send,
+        R: Send,
+    {
+        self.pool.install(op)
+    }
+}
@@ -6,6 +6,79 @@
 use crate::string::String;
 use core::fmt;
 
+// ERROR CODES
+// ================================================================================================
+
+/// Defines categories of errors returned by public APIs of Winterfell crates.
+#[repr(u8)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorCategory {
+    /// Errors which occur during deserialization of values (e.g., proofs).
+    Deserialization = 1,
+    /// Errors returned by cryptographic primitives (e.g., Merkle trees and random coins).
+    Crypto = 2,
+    /// Errors in the definitions of computations (e.g., invalid assertions).
+    Air = 3,
+    /// Errors which occur during proof generation.
+    Prover = 4,
+    /// Errors which occur during proof verification.
+    Verifier = 5,
+    /// Errors which occur during verification of FRI proofs.
+    Fri = 6,
+}
+
+/// A stable numeric code of an error returned by public APIs of Winterfell crates.
+///
+/// Each code consists of an error category and an index of the error within the category. Codes
+/// never change across releases, and thus, can be used by FFI or WASM bindings to map errors to
+/// values which do not depend on the details of Rust error types.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ErrorCode {
+    category: ErrorCategory,
+    index: u8,
+}
+
+impl ErrorCode {
+    /// Returns a new error code for the error with the specified `index` in the `category`.
+    ///
+    /// # Panics
+    /// Panics if `index` is greater than 99.
+    pub const fn new(category: ErrorCategory, index: u8) -> Self {
+        assert!(index < 100, "error index cannot be greater than 99");
+        Self { category, index }
+    }
+
+    /// Returns the category of this error code.
+    pub fn category(&self) -> ErrorCategory {
+        self.category
+    }
+
+    /// Returns the index of this error code within its category.
+    pub fn index(&self) -> u8 {
+        self.index
+    }
+
+    /// Returns this error code encoded as `category * 100 + index`.
+    pub fn as_u16(&self) -> u16 {
+        self.category as u16 * 100 + self.index as u16
+    }
+}
+
+impl fmt::Display for ErrorCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "E{:03}", self.as_u16())
+    }
+}
+
+/// Defines a mapping of errors to stable [ErrorCode]s.
+///
+/// When new error variants are added, they must be assigned new indexes; indexes of existing
+/// variants must never change.
+pub trait ToErrorCode {
+    /// Returns the stable code of this error.
+    fn error_code(&self) -> ErrorCode;
+}
+
 // DESERIALIZATION ERROR
 // ================================================================================================
 
@@ -18,6 +91,8 @@ pub enum DeserializationError {
     UnexpectedEOF,
     /// Deserialization has finished but not all bytes have been consumed.
     UnconsumedBytes,
+    /// The input was serialized using an unsupported version of the serialization format.
+    UnsupportedVersion(u8),
     /// An unknown error has occurred.
     UnknownError(String),
 }
@@ -35,9 +110,25 @@ impl fmt::Display for DeserializationError {
             Self::UnconsumedBytes => {
                 write!(f, "not all bytes were consumed")
             }
+            Self::UnsupportedVersion(version) => {
+                write!(f, "unsupported serialization format version {version}")
+            }
             Self::UnknownError(err_msg) => {
                 write!(f, "unknown error: {err_msg}")
             }
         }
     }
 }
+
+impl ToErrorCode for DeserializationError {
+    fn error_code(&self) -> ErrorCode {
+        let index = match self {
+            Self::InvalidValue(_) => 1,
+            Self::UnexpectedEOF => 2,
+            Self::UnconsumedBytes => 3,
+            Self::UnsupportedVersion(_) => 4,
+            Self::UnknownError(_) => 5,
+        };
+        ErrorCode::new(ErrorCategory::Deserialization, index)
+    }
+}
@@ -17,13 +17,20 @@ pub mod iterators;
 pub mod string;
 
 use collections::Vec;
-use core::{convert::TryInto, mem, slice};
+use core::convert::TryInto;
+#[cfg(not(feature = "forbid-unsafe-bytes"))]
+use core::{mem, slice};
 
 mod serde;
 pub use serde::{ByteReader, ByteWriter, Deserializable, Serializable, SliceReader};
 
 mod errors;
-pub use errors::DeserializationError;
+pub use errors::{DeserializationError, ErrorCategory, ErrorCode, ToErrorCode};
+
+#[cfg(feature = "concurrent")]
+mod compute;
+#[cfg(feature = "concurrent")]
+pub use compute::ComputeContext;
 
 #[cfg(test)]
 mod tests;
@@ -55,6 +62,10 @@ pub trait AsBytes {
     fn as_bytes(&self) -> &[u8];
 }
 
+// Borrowed byte views of byte arrays cannot be constructed without re-interpreting memory, and
+// thus, these implementations are not available when `forbid-unsafe-bytes` feature is enabled.
+
+#[cfg(not(feature = "forbid-unsafe-bytes"))]
 impl<const N: usize, const M: usize> AsBytes for [[u8; N]; M] {
     /// Flattens a two-dimensional array of bytes into a slice of bytes.
     fn as_bytes(&self) -> &[u8] {
@@ -64,6 +75,7 @@ impl<const N: usize, const M: usize> AsBytes for [[u8; N]; M] {
     }
 }
 
+#[cfg(not(feature = "forbid-unsafe-bytes"))]
 impl<const N: usize> AsBytes for [[u8; N]] {
     /// Flattens a slice of byte arrays into a slice of bytes.
     fn as_bytes(&self) -> &[u8] {
@@ -81,11 +93,16 @@ impl<const N: usize> AsBytes for [[u8; N]] {
 /// This is usually faster than requesting a vector with initialized memory and is useful when we
 /// overwrite all contents of the vector immediately after memory allocation.
 ///
+/// In debug builds, the memory of the returned vector is zero-filled so that reading values before
+/// initializing them leads to deterministic (though still incorrect) behavior.
+///
 /// # Safety
 /// Using values from the returned vector before initializing them will lead to undefined behavior.
 #[allow(clippy::uninit_vec)]
 pub unsafe fn uninit_vector<T>(length: usize) -> Vec<T> {
-    let mut vector = Vec::with_capacity(length);
+    let mut vector: Vec<T> = Vec::with_capacity(length);
+    #[cfg(debug_assertions)]
+    vector.as_mut_ptr().write_bytes(0, length);
     vector.set_len(length);
     vector
 }
@@ -97,6 +114,8 @@ pub unsafe fn uninit_vector<T>(length: usize) -> Vec<T> {
 /// an array of `N` elements.
 ///
 /// This function just re-interprets the underlying memory and is thus zero-copy.
+/// When `forbid-unsafe-bytes` feature is enabled, the elements are moved into a newly allocated
+/// vector instead.
 /// # Panics
 /// Panics if `n` is not divisible by `N`.
 ///
@@ -116,17 +135,35 @@ pub fn group_vector_elements<T, const N: usize>(source: Vec<T>) -> Vec<[T; N]> {
         N,
         source.len()
     );
-    let mut v = mem::ManuallyDrop::new(source);
-    let p = v.as_mut_ptr();
-    let len = v.len() / N;
-    let cap = v.capacity() / N;
-    unsafe { Vec::from_raw_parts(p as *mut [T; N], len, cap) }
+    #[cfg(not(feature = "forbid-unsafe-bytes"))]
+    {
+        let mut v = mem::ManuallyDrop::new(source);
+        let p = v.as_mut_ptr();
+        let len = v.len() / N;
+        let cap = v.capacity() / N;
+        unsafe { Vec::from_raw_parts(p as *mut [T; N], len, cap) }
+    }
+    #[cfg(feature = "forbid-unsafe-bytes")]
+    {
+        let mut result = Vec::with_capacity(source.len() / N);
+        let mut elements = source.into_iter();
+        for _ in 0..result.capacity() {
+            let group: Vec<T> = elements.by_ref().take(N).collect();
+            match group.try_into() {
+                Ok(group) => result.push(group),
+                Err(_) => unreachable!("source length is divisible by {N}"),
+            }
+        }
+        result
+    }
 }
 
 /// Transmutes a slice of `n` elements into a slice of `n` / `N` elements, each of which is
 /// an array of `N` elements.
 ///
-/// This function just re-interprets the underlying memory and is thus zero-copy.
+/// This function just re-interprets the underlying memory and is thus zero-copy. It is not
+/// available when `forbid-unsafe-bytes` feature is enabled; [slice::chunks_exact()] can be used
+/// instead.
 /// # Panics
 /// Panics if `n` is not divisible by `N`.
 ///
@@ -138,6 +175,7 @@ pub fn group_vector_elements<T, const N: usize>(source: Vec<T>) -> Vec<[T; N]> {
 ///
 /// assert_eq!(&[[0, 1], [2, 3], [4, 5], [6, 7]], b);
 /// ```
+#[cfg(not(feature = "forbid-unsafe-bytes"))]
 pub fn group_slice_elements<T, const N: usize>(source: &[T]) -> &[[T; N]] {
     assert_eq!(
         source.len() % N,
@@ -151,7 +189,9 @@ pub fn group_slice_elements<T, const N: usize>(source: &[T]) -> &[[T; N]] {
 
 /// Transmutes a slice of `n` arrays each of length `N`, into a slice of `N` * `n` elements.
 ///
-/// This function just re-interprets the underlying memory and is thus zero-copy.
+/// This function just re-interprets the underlying memory and is thus zero-copy. It is not
+/// available when `forbid-unsafe-bytes` feature is enabled; the arrays can be iterated over
+/// instead.
 /// # Example
 /// ```
 /// # use winter_utils::flatten_slice_elements;
@@ -160,6 +200,7 @@ pub fn group_slice_elements<T, const N: usize>(source: &[T]) -> &[[T; N]] {
 /// let b = flatten_slice_elements(&a);
 /// assert_eq!(&[1, 2, 3, 4, 5, 6, 7, 8], b);
 /// ```
+#[cfg(not(feature = "forbid-unsafe-bytes"))]
 pub fn flatten_slice_elements<T, const N: usize>(source: &[[T; N]]) -> &[T] {
     let p = source.as_ptr();
     let len = source.len() * N;
@@ -169,6 +210,8 @@ pub fn flatten_slice_elements<T, const N: usize>(source: &[[T; N]]) -> &[T] {
 /// Transmutes a vector of `n` arrays each of length `N`, into a vector of `N` * `n` elements.
 ///
 /// This function just re-interprets the underlying memory and is thus zero-copy.
+/// When `forbid-unsafe-bytes` feature is enabled, the elements are moved into a newly allocated
+/// vector instead.
 /// # Example
 /// ```
 /// # use winter_utils::flatten_vector_elements;
@@ -178,11 +221,16 @@ pub fn flatten_slice_elements<T, const N: usize>(source: &[[T; N]]) -> &[T] {
 /// assert_eq!(vec![1, 2, 3, 4, 5, 6, 7, 8], b);
 /// ```
 pub fn flatten_vector_elements<T, const N: usize>(source: Vec<[T; N]>) -> Vec<T> {
-    let v = mem::ManuallyDrop::new(source);
-    let p = v.as_ptr();
-    let len = v.len() * N;
-    let cap = v.capacity() * N;
-    unsafe { Vec::from_raw_parts(p as *mut T, len, cap) }
+    #[cfg(not(feature = "forbid-unsafe-bytes"))]
+    {
+        let v = mem::ManuallyDrop::new(source);
+        let p = v.as_ptr();
+        let len = v.len() * N;
+        let cap = v.capacity() * N;
+        unsafe { Vec::from_raw_parts(p as *mut T, len, cap) }
+    }
+    #[cfg(feature = "forbid-unsafe-bytes")]
+    source.into_iter().flatten().collect()
 }
 
 // TRANSPOSING
@@ -4,6 +4,7 @@
 // LICENSE file in the root directory of this source tree.
 
 use super::{Deserializable, DeserializationError, Vec};
+use crate::string::ToString;
 
 // BYTE READER TRAIT
 // ================================================================================================
@@ -103,6 +104,67 @@ pub trait ByteReader {
         Ok(u128::from_le_bytes(bytes))
     }
 
+    /// Returns a usize value read from `self` using variable-length encoding.
+    ///
+    /// The value is expected to be encoded as described in
+    /// [ByteWriter::write_usize()](super::ByteWriter::write_usize).
+    ///
+    /// # Errors
+    /// Returns a [DeserializationError] if:
+    /// * A usize value could not be read from `self`.
+    /// * The encoding is not the shortest possible encoding of the value.
+    /// * The encoded value does not fit into a usize.
+    fn read_usize(&mut self) -> Result<usize, DeserializationError> {
+        let mut result = 0_u64;
+        let mut shift = 0;
+        loop {
+            let byte = self.read_u8()?;
+            let bits = (byte & 0x7f) as u64;
+            if shift == 63 && byte > 1 {
+                return Err(DeserializationError::InvalidValue(
+                    "encoded usize value is too large".to_string(),
+                ));
+            }
+            result |= bits << shift;
+
+            if byte & 0x80 == 0 {
+                // the last byte of a canonical encoding is non-zero unless it is the only byte
+                if byte == 0 && shift > 0 {
+                    return Err(DeserializationError::InvalidValue(
+                        "usize value is not encoded canonically".to_string(),
+                    ));
+                }
+                break;
+            }
+            shift += 7;
+        }
+
+        usize::try_from(result).map_err(|_| {
+            DeserializationError::InvalidValue(format!(
+                "encoded value {result} does not fit into a usize"
+            ))
+        })
+    }
+
+    /// Returns a usize value read from `self` using variable-length encoding, and checks that the
+    /// value does not exceed `max_value`.
+    ///
+    /// This method should be used to read lengths and counts from untrusted sources before
+    /// allocating memory based on them.
+    ///
+    /// # Errors
+    /// Returns a [DeserializationError] if a usize value could not be read from `self`, or if the
+    /// value is greater than `max_value`.
+    fn read_usize_bounded(&mut self, max_value: usize) -> Result<usize, DeserializationError> {
+        let value = self.read_usize()?;
+        if value > max_value {
+            return Err(DeserializationError::InvalidValue(format!(
+                "value {value} exceeds the maximum of {max_value}"
+            )));
+        }
+        Ok(value)
+    }
+
     /// Reads a deserializable value from `self`.
     ///
     /// # Panics
@@ -161,7 +223,7 @@ impl<'a> ByteReader for SliceReader<'a> {
     }
 
     fn check_eor(&self, num_bytes: usize) -> Result<(), DeserializationError> {
-        if self.pos + num_bytes > self.source.len() {
+        if num_bytes > self.source.len() - self.pos {
             return Err(DeserializationError::UnexpectedEOF);
         }
         Ok(())
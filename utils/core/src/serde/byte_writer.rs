@@ -62,6 +62,23 @@ pub trait ByteWriter: Sized {
         self.write_bytes(&value.to_le_bytes());
     }
 
+    /// Writes a usize value into `self` using variable-length encoding.
+    ///
+    /// The value is written in LEB128 format: 7 bits of the value are written per byte, starting
+    /// with the least significant bits, and the most significant bit of every byte except for the
+    /// last one is set. Thus, values smaller than 128 are written as a single byte.
+    ///
+    /// # Panics
+    /// Panics if the value could not be written into `self`.
+    fn write_usize(&mut self, value: usize) {
+        let mut value = value as u64;
+        while value >= 0x80 {
+            self.write_u8((value as u8) | 0x80);
+            value >>= 7;
+        }
+        self.write_u8(value as u8);
+    }
+
     /// Writes a serializable value into `self`.
     ///
     /// # Panics
@@ -3,7 +3,10 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use super::{flatten_slice_elements, DeserializationError, Vec};
+use super::{DeserializationError, Vec};
+
+#[cfg(not(feature = "forbid-unsafe-bytes"))]
+use super::flatten_slice_elements;
 
 mod byte_reader;
 pub use byte_reader::{ByteReader, SliceReader};
@@ -11,6 +14,13 @@ pub use byte_reader::{ByteReader, SliceReader};
 mod byte_writer;
 pub use byte_writer::ByteWriter;
 
+// CONSTANTS
+// ================================================================================================
+
+/// Maximum number of bytes which [Deserializable::read_batch_from()] allocates before reading any
+/// elements from the source.
+const MAX_PREALLOCATED_BYTES: usize = 1 << 20;
+
 // SERIALIZABLE TRAIT
 // ================================================================================================
 
@@ -67,15 +77,21 @@ impl<T: Serializable> Serializable for &Vec<T> {
 
 impl<T: Serializable, const N: usize> Serializable for Vec<[T; N]> {
     fn write_into<W: ByteWriter>(&self, target: &mut W) {
-        let source = flatten_slice_elements(self);
-        T::write_batch_into(source, target);
+        #[cfg(not(feature = "forbid-unsafe-bytes"))]
+        T::write_batch_into(flatten_slice_elements(self), target);
+        #[cfg(feature = "forbid-unsafe-bytes")]
+        self.iter()
+            .for_each(|batch| T::write_batch_into(batch, target));
     }
 }
 
 impl<T: Serializable, const N: usize> Serializable for &Vec<[T; N]> {
     fn write_into<W: ByteWriter>(&self, target: &mut W) {
-        let source = flatten_slice_elements(self);
-        T::write_batch_into(source, target);
+        #[cfg(not(feature = "forbid-unsafe-bytes"))]
+        T::write_batch_into(flatten_slice_elements(self), target);
+        #[cfg(feature = "forbid-unsafe-bytes")]
+        self.iter()
+            .for_each(|batch| T::write_batch_into(batch, target));
     }
 }
 
@@ -87,8 +103,11 @@ impl<T: Serializable> Serializable for &[T] {
 
 impl<T: Serializable, const N: usize> Serializable for &[[T; N]] {
     fn write_into<W: ByteWriter>(&self, target: &mut W) {
-        let source = flatten_slice_elements(self);
-        T::write_batch_into(source, target);
+        #[cfg(not(feature = "forbid-unsafe-bytes"))]
+        T::write_batch_into(flatten_slice_elements(self), target);
+        #[cfg(feature = "forbid-unsafe-bytes")]
+        self.iter()
+            .for_each(|batch| T::write_batch_into(batch, target));
     }
 }
 
@@ -137,11 +156,16 @@ pub trait Deserializable: Sized {
     ///
     /// Note: if the error occurs, the reader is not rolled back to the state prior to calling
     /// this function.
+    ///
+    /// Since `num_elements` is frequently read from an untrusted source, at most
+    /// 1 MB worth of elements is allocated upfront; the vector grows beyond
+    /// that only as elements are successfully read from the `source`.
     fn read_batch_from<R: ByteReader>(
         source: &mut R,
         num_elements: usize,
     ) -> Result<Vec<Self>, DeserializationError> {
-        let mut result = Vec::with_capacity(num_elements);
+        let max_preallocated = MAX_PREALLOCATED_BYTES / core::mem::size_of::<Self>().max(1);
+        let mut result = Vec::with_capacity(num_elements.min(max_preallocated));
         for _ in 0..num_elements {
             let element = Self::read_from(source)?;
             result.push(element)
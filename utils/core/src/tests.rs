@@ -3,7 +3,10 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use super::{collections::Vec, ByteReader, ByteWriter, Serializable, SliceReader};
+use super::{
+    collections::Vec, string::ToString, ByteReader, ByteWriter, Deserializable,
+    DeserializationError, ErrorCategory, ErrorCode, Serializable, SliceReader, ToErrorCode,
+};
 
 // VECTOR UTILS TESTS
 // ================================================================================================
@@ -86,6 +89,62 @@ fn read_u8_vec() {
     assert_eq!(vec![6, 7], a.read_vec(2).unwrap());
     assert_eq!(vec![8], a.read_vec(1).unwrap());
     assert!(a.read_vec(2).is_err());
+
+    // lengths which would overflow the reader position are rejected
+    let mut a = SliceReader::new(&source);
+    a.read_u8().unwrap();
+    assert_eq!(
+        Err(DeserializationError::UnexpectedEOF),
+        a.read_vec(usize::MAX)
+    );
+}
+
+#[test]
+fn read_usize() {
+    let values = [
+        0,
+        1,
+        127,
+        128,
+        300,
+        16383,
+        16384,
+        u32::MAX as usize,
+        usize::MAX,
+    ];
+    let mut source = Vec::new();
+    for &value in values.iter() {
+        source.write_usize(value);
+    }
+    assert_eq!(vec![0, 1, 0x7f, 0x80, 0x01], source[..5].to_vec());
+
+    let mut a = SliceReader::new(&source);
+    for &value in values.iter() {
+        assert_eq!(value, a.read_usize().unwrap());
+    }
+    assert!(!a.has_more_bytes());
+    assert_eq!(Err(DeserializationError::UnexpectedEOF), a.read_usize());
+
+    // encodings which are not the shortest possible are rejected
+    let mut a = SliceReader::new(&[0x81, 0x00]);
+    assert!(a.read_usize().is_err());
+
+    // so are encodings of values which do not fit into 64 bits
+    let mut source = vec![0xff; 9];
+    source.push(0x02);
+    assert!(SliceReader::new(&source).read_usize().is_err());
+    let mut source = vec![0xff; 10];
+    source.push(0x01);
+    assert!(SliceReader::new(&source).read_usize().is_err());
+
+    // bounded reads reject values above the bound
+    let mut source = Vec::new();
+    source.write_usize(1000);
+    assert_eq!(
+        1000,
+        SliceReader::new(&source).read_usize_bounded(1000).unwrap()
+    );
+    assert!(SliceReader::new(&source).read_usize_bounded(999).is_err());
 }
 
 // SERIALIZATION TESTS
@@ -97,6 +156,12 @@ impl Serializable for u128 {
     }
 }
 
+impl Deserializable for u128 {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        source.read_u128()
+    }
+}
+
 #[test]
 fn write_serializable() {
     let mut target: Vec<u8> = Vec::new();
@@ -147,3 +212,72 @@ fn write_serializable_array_batch() {
         assert_eq!(i, reader.read_u128().unwrap());
     }
 }
+
+#[test]
+fn read_deserializable_batch() {
+    let mut source: Vec<u8> = Vec::new();
+    vec![1u128, 2, 3, 4].write_into(&mut source);
+
+    let mut reader = SliceReader::new(&source);
+    assert_eq!(
+        vec![1u128, 2, 3, 4],
+        u128::read_batch_from(&mut reader, 4).unwrap()
+    );
+
+    // an element count which cannot be satisfied by the source results in an error rather than
+    // an attempt to allocate memory for all of the elements
+    let mut reader = SliceReader::new(&source);
+    assert_eq!(
+        Err(DeserializationError::UnexpectedEOF),
+        u128::read_batch_from(&mut reader, usize::MAX)
+    );
+}
+
+// COMPUTE CONTEXT TESTS
+// ================================================================================================
+
+#[cfg(feature = "concurrent")]
+#[test]
+fn compute_context() {
+    use super::{rayon::prelude::*, ComputeContext};
+
+    let context = ComputeContext::with_num_threads(3).unwrap();
+    assert_eq!(3, context.num_threads());
+
+    // parallel iterators executed within the context run on the threads of its pool
+    let num_threads = context.install(|| {
+        (0..64)
+            .into_par_iter()
+            .map(|_| rayon::current_thread_index().unwrap())
+            .max()
+            .unwrap()
+            + 1
+    });
+    assert!(num_threads <= 3);
+    assert_eq!(3, context.clone().install(rayon::current_num_threads));
+}
+
+// ERROR CODE TESTS
+// ================================================================================================
+
+#[test]
+fn error_code_encoding() {
+    let code = ErrorCode::new(ErrorCategory::Verifier, 7);
+    assert_eq!(ErrorCategory::Verifier, code.category());
+    assert_eq!(7, code.index());
+    assert_eq!(507, code.as_u16());
+    assert_eq!("E507", code.to_string());
+
+    let err = DeserializationError::UnexpectedEOF;
+    assert_eq!(
+        ErrorCode::new(ErrorCategory::Deserialization, 2),
+        err.error_code()
+    );
+    assert_eq!("E102", err.error_code().to_string());
+}
+
+#[test]
+#[should_panic]
+fn error_code_index_too_large() {
+    let _ = ErrorCode::new(ErrorCategory::Prover, 100);
+}
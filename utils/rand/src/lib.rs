@@ -7,15 +7,111 @@
 //!
 //! These functions are intended to be used in tests, benchmarks, and examples. When compiled to
 //! WebAssembly target, all of the functions are omitted.
+//!
+//! By default, random values are drawn from the thread-local generator of the `rand` crate. The
+//! source of randomness used by the current thread can be replaced with a deterministic generator
+//! via [set_entropy()] or [with_entropy()]; this makes tests and simulation runs reproducible.
+//! When `WINTER_RAND_SEED` environment variable is set to an integer, every thread starts with a
+//! deterministic generator seeded with this integer.
 
 pub use internal::*;
 
 #[cfg(not(target_family = "wasm"))]
 mod internal {
-    use core::{convert::TryInto, fmt::Debug};
+    use core::{cell::RefCell, convert::TryInto, fmt::Debug};
     use rand::prelude::*;
+    use std::env;
     use utils::Randomizable;
 
+    // CONSTANTS
+    // ============================================================================================
+
+    /// Name of the environment variable which, when set to an integer, seeds the default source
+    /// of entropy of every thread.
+    pub const SEED_ENV_VAR: &str = "WINTER_RAND_SEED";
+
+    // ENTROPY
+    // ============================================================================================
+
+    /// A source of randomness for random value generation functions of this crate.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum Entropy {
+        /// Randomness is drawn from the thread-local generator of the `rand` crate which is
+        /// seeded by the operating system.
+        Os,
+        /// Randomness is drawn from a deterministic pseudo-random generator seeded with the
+        /// specified seed.
+        Seeded([u8; 32]),
+    }
+
+    impl Entropy {
+        /// Returns the default source of entropy for a new thread.
+        ///
+        /// This is [Entropy::Os] unless `WINTER_RAND_SEED` environment variable is set to an
+        /// integer, in which case a source seeded with this integer is returned.
+        ///
+        /// # Panics
+        /// Panics if `WINTER_RAND_SEED` environment variable is set to a value which is not an
+        /// integer.
+        pub fn from_env() -> Self {
+            match env::var(SEED_ENV_VAR) {
+                Ok(seed) => {
+                    let seed = seed.trim().parse::<u64>().unwrap_or_else(|_| {
+                        panic!("{SEED_ENV_VAR} must be an integer, but was '{seed}'")
+                    });
+                    Self::from_u64(seed)
+                }
+                Err(_) => Entropy::Os,
+            }
+        }
+
+        /// Returns a deterministic source of entropy seeded with the specified integer.
+        pub fn from_u64(seed: u64) -> Self {
+            let mut result = [0; 32];
+            result[..8].copy_from_slice(&seed.to_le_bytes());
+            Entropy::Seeded(result)
+        }
+
+        /// Returns a random number generator which draws randomness from this source.
+        fn into_rng(self) -> Option<StdRng> {
+            match self {
+                Entropy::Os => None,
+                Entropy::Seeded(seed) => Some(StdRng::from_seed(seed)),
+            }
+        }
+    }
+
+    thread_local! {
+        /// Deterministic generator used by the current thread; when `None`, the thread-local
+        /// generator of the `rand` crate is used.
+        static RNG: RefCell<Option<StdRng>> = RefCell::new(Entropy::from_env().into_rng());
+    }
+
+    /// Sets the source of entropy for all random value generation functions of this crate
+    /// invoked from the current thread.
+    pub fn set_entropy(entropy: Entropy) {
+        RNG.with(|rng| *rng.borrow_mut() = entropy.into_rng());
+    }
+
+    /// Executes `op` with random value generation functions of this crate invoked from the
+    /// current thread drawing randomness from the specified source, and returns the result.
+    ///
+    /// The previous source of entropy of the current thread is restored after `op` completes.
+    pub fn with_entropy<T>(entropy: Entropy, op: impl FnOnce() -> T) -> T {
+        let previous = RNG.with(|rng| rng.replace(entropy.into_rng()));
+        let result = op();
+        RNG.with(|rng| *rng.borrow_mut() = previous);
+        result
+    }
+
+    /// Executes `f` with the random number generator of the current thread.
+    fn with_rng<T>(f: impl FnOnce(&mut dyn RngCore) -> T) -> T {
+        RNG.with(|rng| match rng.borrow_mut().as_mut() {
+            Some(rng) => f(rng),
+            None => f(&mut thread_rng()),
+        })
+    }
+
     // RANDOM VALUE GENERATION
     // ============================================================================================
 
@@ -27,7 +123,7 @@ mod internal {
     /// * A valid value could not be generated after 1000 tries.
     pub fn rand_value<R: Randomizable>() -> R {
         for _ in 0..1000 {
-            let bytes = rand::thread_rng().gen::<[u8; 32]>();
+            let bytes = with_rng(|rng| rng.gen::<[u8; 32]>());
             if let Some(value) = R::from_random_bytes(&bytes[..R::VALUE_SIZE]) {
                 return value;
             }
@@ -44,7 +140,7 @@ mod internal {
     /// * A valid value could not be generated after 1000 tries.
     pub fn rand_vector<R: Randomizable>(n: usize) -> Vec<R> {
         let mut result = Vec::with_capacity(n);
-        let seed = rand::thread_rng().gen::<[u8; 32]>();
+        let seed = with_rng(|rng| rng.gen::<[u8; 32]>());
         let mut g = StdRng::from_seed(seed);
         for _ in 0..1000 * n {
             let bytes = g.gen::<[u8; 32]>();
@@ -114,7 +210,7 @@ mod internal {
 
     /// Randomly shuffles slice elements.
     pub fn shuffle<T>(values: &mut [T]) {
-        values.shuffle(&mut thread_rng());
+        with_rng(|rng| values.shuffle(rng));
     }
 }
 
@@ -126,7 +222,7 @@ mod internal {}
 
 #[cfg(test)]
 mod tests {
-    use super::{rand_array, rand_value};
+    use super::{rand_array, rand_value, rand_vector, set_entropy, shuffle, with_entropy, Entropy};
 
     #[test]
     fn rand_primitives() {
@@ -161,4 +257,30 @@ mod tests {
         let b = rand_array::<u8, 34>();
         assert_ne!(a, b);
     }
+
+    #[test]
+    fn seeded_entropy() {
+        let run = |seed| {
+            with_entropy(Entropy::from_u64(seed), || {
+                let mut values = rand_vector::<u64>(16);
+                shuffle(&mut values);
+                (rand_value::<u64>(), values)
+            })
+        };
+        assert_eq!(run(1), run(1));
+        assert_ne!(run(1), run(2));
+    }
+
+    #[test]
+    fn nested_entropy() {
+        set_entropy(Entropy::from_u64(3));
+        let expected = [rand_value::<u64>(), rand_value::<u64>()];
+
+        // the previous source of entropy is restored after the closure completes
+        set_entropy(Entropy::from_u64(3));
+        let first = rand_value::<u64>();
+        let _ = with_entropy(Entropy::from_u64(4), rand_value::<u64>);
+        assert_eq!(expected, [first, rand_value::<u64>()]);
+        set_entropy(Entropy::Os);
+    }
 }
@@ -5,12 +5,15 @@
 
 use crate::VerifierError;
 use air::{
-    proof::{Queries, StarkProof, Table},
+    proof::{Queries, RowOpenings, StarkProof, Table},
     Air, EvaluationFrame,
 };
-use crypto::{BatchMerkleProof, ElementHasher, MerkleTree};
-use fri::VerifierChannel as FriVerifierChannel;
-use math::{FieldElement, StarkField};
+use crypto::{BatchMerkleProof, ElementHasher, MerkleTreeError};
+use fri::{
+    folding::fold_positions, utils::map_positions_to_indexes, LayerMultiproof,
+    VerifierChannel as FriVerifierChannel,
+};
+use math::FieldElement;
 use utils::{collections::Vec, string::ToString};
 
 // VERIFIER CHANNEL
@@ -23,14 +26,16 @@ use utils::{collections::Vec, string::ToString};
 /// well-formed in the context of the computation for the specified [Air].
 pub struct VerifierChannel<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> {
     // trace queries
+    preprocessed_root: Option<H::Digest>,
     trace_roots: Vec<H::Digest>,
     trace_queries: Option<TraceQueries<E, H>>,
     // constraint queries
     constraint_root: H::Digest,
-    constraint_queries: Option<ConstraintQueries<E, H>>,
+    constraint_queries: Option<RowOpenings<E, H>>,
     // FRI proof
     fri_roots: Option<Vec<H::Digest>>,
     fri_layer_proofs: Vec<BatchMerkleProof<H>>,
+    fri_layer_multiproof: Option<LayerMultiproof<H>>,
     fri_layer_queries: Vec<Vec<E>>,
     fri_remainder: Option<Vec<E>>,
     fri_num_partitions: usize,
@@ -45,12 +50,16 @@ impl<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> VerifierChanne
     // CONSTRUCTOR
     // --------------------------------------------------------------------------------------------
     /// Creates and returns a new [VerifierChannel] initialized from the specified `proof`.
+    ///
+    /// `preprocessed_root` must be provided if and only if the execution trace of the computation
+    /// contains preprocessed columns.
     pub fn new<A: Air<BaseField = E::BaseField>>(
         air: &A,
         proof: StarkProof,
+        preprocessed_root: Option<H::Digest>,
     ) -> Result<Self, VerifierError> {
         let StarkProof {
-            context,
+            context: _,
             commitments,
             trace_queries,
             constraint_queries,
@@ -59,9 +68,13 @@ impl<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> VerifierChanne
             pow_nonce,
         } = proof;
 
-        // make AIR and proof base fields are the same
-        if E::BaseField::get_modulus_le_bytes() != context.field_modulus_bytes() {
-            return Err(VerifierError::InconsistentBaseField);
+        match (
+            air.trace_layout().has_preprocessed_columns(),
+            preprocessed_root,
+        ) {
+            (true, None) => return Err(VerifierError::PreprocessedCommitmentNotProvided),
+            (false, Some(_)) => return Err(VerifierError::UnexpectedPreprocessedCommitment),
+            _ => (),
         }
 
         let num_trace_segments = air.trace_layout().num_segments();
@@ -80,16 +93,52 @@ impl<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> VerifierChanne
 
         // --- parse trace and constraint queries -------------------------------------------------
         let trace_queries = TraceQueries::new(trace_queries, air)?;
-        let constraint_queries = ConstraintQueries::new(constraint_queries, air)?;
+        let constraint_queries = parse_constraint_queries(constraint_queries, air)?;
 
         // --- parse FRI proofs -------------------------------------------------------------------
         let fri_num_partitions = fri_proof.num_partitions();
         let fri_remainder = fri_proof
             .parse_remainder()
             .map_err(|err| VerifierError::ProofDeserializationError(err.to_string()))?;
-        let (fri_layer_queries, fri_layer_proofs) = fri_proof
-            .parse_layers::<H, E>(lde_domain_size, fri_options.folding_factor())
-            .map_err(|err| VerifierError::ProofDeserializationError(err.to_string()))?;
+        if fri_num_partitions > lde_domain_size {
+            return Err(VerifierError::ProofDeserializationError(format!(
+                "number of FRI partitions cannot exceed {lde_domain_size}, but was {fri_num_partitions}"
+            )));
+        }
+        let num_fri_layers = fri_options.num_fri_layers(lde_domain_size);
+        if fri_proof.num_layers() != num_fri_layers {
+            return Err(VerifierError::ProofDeserializationError(format!(
+                "expected {} FRI layers, but was {}",
+                num_fri_layers,
+                fri_proof.num_layers()
+            )));
+        }
+        let (fri_layer_queries, fri_layer_proofs, fri_layer_multiproof) =
+            if fri_options.aggregates_layer_proofs() {
+                let (layer_queries, multiproof) = fri_proof
+                    .parse_aggregated_layers::<H, E>(lde_domain_size, fri_options.folding_factor())
+                    .map_err(|err| VerifierError::ProofDeserializationError(err.to_string()))?;
+                (layer_queries, Vec::new(), Some(multiproof))
+            } else {
+                let (layer_queries, layer_proofs) = fri_proof
+                    .parse_layers::<H, E>(lde_domain_size, fri_options.folding_factor())
+                    .map_err(|err| VerifierError::ProofDeserializationError(err.to_string()))?;
+                (layer_queries, layer_proofs, None)
+            };
+
+        // make sure the remainder is not too long for its degree bound; this is checked again
+        // during FRI verification, but checking it here rejects such proofs before any Merkle
+        // proofs are verified
+        let max_remainder_size = (0..num_fri_layers)
+            .fold(air.trace_poly_degree() + 1, |size, _| {
+                size / fri_options.folding_factor()
+            })
+            .min(fri_options.max_remainder_size(lde_domain_size));
+        if fri_remainder.len() > max_remainder_size {
+            return Err(VerifierError::FriVerificationFailed(
+                fri::VerifierError::RemainderDegreeMismatch(max_remainder_size.saturating_sub(1)),
+            ));
+        }
 
         // --- parse out-of-domain evaluation frame -----------------------------------------------
         let (ood_main_trace_frame, ood_aux_trace_frame, ood_constraint_evaluations) = ood_frame
@@ -99,6 +148,7 @@ impl<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> VerifierChanne
 
         Ok(VerifierChannel {
             // trace queries
+            preprocessed_root,
             trace_roots,
             trace_queries: Some(trace_queries),
             // constraint queries
@@ -107,6 +157,7 @@ impl<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> VerifierChanne
             // FRI proof
             fri_roots: Some(fri_roots),
             fri_layer_proofs,
+            fri_layer_multiproof,
             fri_layer_queries,
             fri_remainder: Some(fri_remainder),
             fri_num_partitions,
@@ -129,6 +180,11 @@ impl<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> VerifierChanne
         &self.trace_roots
     }
 
+    /// Returns the commitment to preprocessed trace columns provided to the verifier, if any.
+    pub fn read_preprocessed_commitment(&self) -> Option<H::Digest> {
+        self.preprocessed_root
+    }
+
     /// Returns constraint evaluation commitment sent by the prover.
     pub fn read_constraint_commitment(&self) -> H::Digest {
         self.constraint_root
@@ -158,12 +214,54 @@ impl<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> VerifierChanne
         self.pow_nonce
     }
 
+    /// Returns the number of leaves and the number of internal nodes in each of the batch Merkle
+    /// proofs contained in this channel.
+    ///
+    /// The proofs are listed in the following order: proofs for each trace segment, proof for
+    /// preprocessed trace columns (if any), proof for constraint evaluations, and proofs for
+    /// each FRI layer.
+    ///
+    /// If FRI layer proofs are aggregated into a single multiproof, all nodes of the multiproof
+    /// are attributed to the first FRI layer.
+    pub(crate) fn merkle_proof_sizes(&self, fri_folding_factor: usize) -> Vec<(usize, usize)> {
+        let trace_queries = self.trace_queries.as_ref().expect("already read");
+        let constraint_queries = self.constraint_queries.as_ref().expect("already read");
+        let mut result = core::iter::once(trace_queries.main.proof())
+            .chain(trace_queries.aux.iter().map(|openings| openings.proof()))
+            .chain(
+                trace_queries
+                    .preprocessed
+                    .iter()
+                    .map(|openings| openings.proof()),
+            )
+            .chain(core::iter::once(constraint_queries.proof()))
+            .chain(self.fri_layer_proofs.iter())
+            .map(|proof| {
+                let num_nodes = proof.nodes.iter().map(|nodes| nodes.len()).sum();
+                (proof.leaves.len(), num_nodes)
+            })
+            .collect::<Vec<_>>();
+
+        if let Some(multiproof) = &self.fri_layer_multiproof {
+            let num_nodes = multiproof.num_nodes();
+            for (i, layer_queries) in self.fri_layer_queries.iter().enumerate() {
+                let num_leaves = layer_queries.len() / fri_folding_factor;
+                result.push((num_leaves, if i == 0 { num_nodes } else { 0 }));
+            }
+        }
+
+        result
+    }
+
     /// Returns trace states at the specified positions of the LDE domain. This also checks if
     /// the trace states are valid against the trace commitment sent by the prover.
     ///
     /// For computations requiring multiple trace segments, trace states for auxiliary segments
     /// are also included as the second value of the returned tuple (trace states for all auxiliary
     /// segments are merged into a single table). Otherwise, the second value is None.
+    ///
+    /// States of preprocessed columns (if any) are checked against the preprocessed commitment and
+    /// are included as the first columns of the main trace states.
     #[allow(clippy::type_complexity)]
     pub fn read_queried_trace_states(
         &mut self,
@@ -172,12 +270,36 @@ impl<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> VerifierChanne
         let queries = self.trace_queries.take().expect("already read");
 
         // make sure the states included in the proof correspond to the trace commitment
-        for (root, proof) in self.trace_roots.iter().zip(queries.query_proofs.iter()) {
-            MerkleTree::verify_batch(root, positions, proof)
-                .map_err(|_| VerifierError::TraceQueryDoesNotMatchCommitment)?;
+        let mismatch = |_| VerifierError::TraceQueryDoesNotMatchCommitment;
+        queries
+            .main
+            .verify(&self.trace_roots[0], positions)
+            .map_err(mismatch)?;
+        for (root, openings) in self.trace_roots.iter().skip(1).zip(queries.aux.iter()) {
+            openings.verify(root, positions).map_err(mismatch)?;
+        }
+        if let (Some(root), Some(openings)) = (&self.preprocessed_root, &queries.preprocessed) {
+            openings.verify(root, positions).map_err(mismatch)?;
         }
 
-        Ok((queries.main_states, queries.aux_states))
+        // place preprocessed column states in front of the main trace segment states, and merge
+        // states of all auxiliary segments into a single table
+        let main_states = match queries.preprocessed {
+            Some(openings) => Table::merge(vec![openings.into_rows(), queries.main.into_rows()]),
+            None => queries.main.into_rows(),
+        };
+        let aux_states = if queries.aux.is_empty() {
+            None
+        } else {
+            let aux_states = queries
+                .aux
+                .into_iter()
+                .map(RowOpenings::into_rows)
+                .collect();
+            Some(Table::merge(aux_states))
+        };
+
+        Ok((main_states, aux_states))
     }
 
     /// Returns constraint evaluations at the specified positions of the LDE domain. This also
@@ -189,10 +311,139 @@ impl<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> VerifierChanne
     ) -> Result<Table<E>, VerifierError> {
         let queries = self.constraint_queries.take().expect("already read");
 
-        MerkleTree::verify_batch(&self.constraint_root, positions, &queries.query_proofs)
+        queries
+            .verify(&self.constraint_root, positions)
             .map_err(|_| VerifierError::ConstraintQueryDoesNotMatchCommitment)?;
 
-        Ok(queries.evaluations)
+        Ok(queries.into_rows())
+    }
+
+    // QUERY RESTRICTION
+    // --------------------------------------------------------------------------------------------
+
+    /// Restricts all query data in this channel to the queries at the specified indexes, and
+    /// returns the positions of these queries in the LDE domain.
+    ///
+    /// `positions` must be the full list of query positions drawn by the verifier, and `indexes`
+    /// must be sorted, free of duplicates, and within bounds of `positions`. Merkle
+    /// authentication paths for the selected queries are extracted from batch Merkle proofs (or
+    /// from the FRI layer multiproof) in the channel; thus, only these paths (rather than all
+    /// paths) are verified when the data is read from the channel. FRI layer queries are
+    /// restricted to the rows into which the selected queries are folded at every FRI layer.
+    ///
+    /// Every batch Merkle proof is expanded into individual authentication paths only once,
+    /// regardless of the number of selected queries.
+    ///
+    /// # Errors
+    /// Returns an error if authentication paths could not be extracted from any of the batch
+    /// Merkle proofs or from the FRI layer multiproof.
+    ///
+    /// # Panics
+    /// Panics if the queries have already been read from this channel.
+    pub fn restrict_to_queries(
+        &mut self,
+        positions: &[usize],
+        indexes: &[usize],
+        lde_domain_size: usize,
+        folding_factor: usize,
+    ) -> Result<Vec<usize>, VerifierError> {
+        let selected_positions = indexes.iter().map(|&i| positions[i]).collect::<Vec<_>>();
+
+        // --- trace and constraint queries -------------------------------------------------------
+        let trace_queries = self.trace_queries.take().expect("already read");
+        let trace_mismatch = |_| VerifierError::TraceQueryDoesNotMatchCommitment;
+        self.trace_queries = Some(TraceQueries {
+            main: restrict_openings(trace_queries.main, positions, indexes)
+                .map_err(trace_mismatch)?,
+            aux: trace_queries
+                .aux
+                .into_iter()
+                .map(|openings| restrict_openings(openings, positions, indexes))
+                .collect::<Result<_, _>>()
+                .map_err(trace_mismatch)?,
+            preprocessed: trace_queries
+                .preprocessed
+                .map(|openings| restrict_openings(openings, positions, indexes))
+                .transpose()
+                .map_err(trace_mismatch)?,
+        });
+
+        let constraint_queries = self.constraint_queries.take().expect("already read");
+        self.constraint_queries = Some(
+            restrict_openings(constraint_queries, positions, indexes)
+                .map_err(|_| VerifierError::ConstraintQueryDoesNotMatchCommitment)?,
+        );
+
+        // --- FRI layer queries ------------------------------------------------------------------
+        let layer_mismatch =
+            || VerifierError::FriVerificationFailed(fri::VerifierError::LayerCommitmentMismatch);
+        let mut layer_positions = positions.to_vec();
+        let mut selected_layer_positions = selected_positions.clone();
+        let mut domain_size = lde_domain_size;
+        let mut multiproof_layers = (Vec::new(), Vec::new(), Vec::new());
+        let mut layer_proofs = core::mem::take(&mut self.fri_layer_proofs).into_iter();
+        for queries in self.fri_layer_queries.iter_mut() {
+            // determine the rows of the layer into which the selected queries are folded; the
+            // rows must be listed in the order in which the FRI verifier folds the positions
+            let folded_positions = fold_positions(&layer_positions, domain_size, folding_factor);
+            let selected_folded_positions =
+                fold_positions(&selected_layer_positions, domain_size, folding_factor);
+            let row_idxs = selected_folded_positions
+                .iter()
+                .map(|p| folded_positions.iter().position(|q| q == p))
+                .collect::<Option<Vec<_>>>()
+                .expect("folded position not found");
+            if queries.len() != folded_positions.len() * folding_factor {
+                return Err(layer_mismatch());
+            }
+
+            let map_positions = |positions: &[usize]| {
+                map_positions_to_indexes(
+                    positions,
+                    domain_size,
+                    folding_factor,
+                    self.fri_num_partitions,
+                )
+            };
+            let layer_indexes = map_positions(&folded_positions);
+            if self.fri_layer_multiproof.is_some() {
+                let leaves = queries
+                    .chunks(folding_factor)
+                    .map(H::hash_elements)
+                    .collect::<Vec<_>>();
+                multiproof_layers.0.push(layer_indexes);
+                multiproof_layers.1.push(leaves);
+                multiproof_layers
+                    .2
+                    .push(map_positions(&selected_folded_positions));
+            } else {
+                let proof = layer_proofs.next().ok_or_else(layer_mismatch)?;
+                let proof = restrict_proof(proof, &layer_indexes, &row_idxs)
+                    .map_err(|_| layer_mismatch())?;
+                self.fri_layer_proofs.push(proof);
+            }
+            *queries = row_idxs
+                .iter()
+                .flat_map(|&row_idx| {
+                    &queries[row_idx * folding_factor..(row_idx + 1) * folding_factor]
+                })
+                .copied()
+                .collect();
+
+            layer_positions = folded_positions;
+            selected_layer_positions = selected_folded_positions;
+            domain_size /= folding_factor;
+        }
+
+        if let Some(multiproof) = self.fri_layer_multiproof.take() {
+            let (layer_indexes, layer_leaves, selected_indexes) = multiproof_layers;
+            let multiproof = multiproof
+                .restrict(&layer_indexes, &layer_leaves, &selected_indexes)
+                .map_err(|_| layer_mismatch())?;
+            self.fri_layer_multiproof = Some(multiproof);
+        }
+
+        Ok(selected_positions)
     }
 }
 
@@ -225,83 +476,95 @@ where
     fn take_fri_remainder(&mut self) -> Vec<E> {
         self.fri_remainder.take().expect("already read")
     }
+
+    fn take_fri_layer_multiproof(&mut self) -> Option<LayerMultiproof<H>> {
+        self.fri_layer_multiproof.take()
+    }
 }
 
 // TRACE QUERIES
 // ================================================================================================
 
-/// Container of trace query data, including:
-/// * Queried states for all trace segments.
-/// * Merkle authentication paths for all queries.
-///
-/// Trace states for all auxiliary segments are stored in a single table.
+/// Container of trace query data, including openings of the main trace segment, openings of
+/// each auxiliary trace segment, and openings of preprocessed columns (if any).
 struct TraceQueries<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> {
-    query_proofs: Vec<BatchMerkleProof<H>>,
-    main_states: Table<E::BaseField>,
-    aux_states: Option<Table<E>>,
+    main: RowOpenings<E::BaseField, H>,
+    aux: Vec<RowOpenings<E, H>>,
+    preprocessed: Option<RowOpenings<E::BaseField, H>>,
 }
 
 impl<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> TraceQueries<E, H> {
-    /// Parses the provided trace queries into trace states in the specified field and
-    /// corresponding Merkle authentication paths.
+    /// Parses the provided trace queries into openings of trace segments in the specified field.
     pub fn new<A: Air<BaseField = E::BaseField>>(
         mut queries: Vec<Queries>,
         air: &A,
     ) -> Result<Self, VerifierError> {
-        assert_eq!(
-            queries.len(),
-            air.trace_layout().num_segments(),
-            "expected {} trace segment queries, but received {}",
-            air.trace_layout().num_segments(),
-            queries.len()
-        );
+        let layout = air.trace_layout();
+        if queries.len() != layout.num_trace_commitments() {
+            return Err(VerifierError::ProofDeserializationError(format!(
+                "expected {} trace segment queries, but received {}",
+                layout.num_trace_commitments(),
+                queries.len()
+            )));
+        }
 
         let num_queries = air.options().num_queries();
+        let lde_domain_size = air.lde_domain_size();
 
-        // parse main trace segment queries; parsing also validates that hashes of each table row
-        // form the leaves of Merkle authentication paths in the proofs
-        let main_segment_width = air.trace_layout().main_trace_width();
-        let main_segment_queries = queries.remove(0);
-        let (main_segment_query_proofs, main_segment_states) = main_segment_queries
-            .parse::<H, E::BaseField>(air.lde_domain_size(), num_queries, main_segment_width)
+        // parse preprocessed column queries (if any); these are always the last set of queries
+        let num_preprocessed_cols = layout.num_preprocessed_columns();
+        let preprocessed = if layout.has_preprocessed_columns() {
+            let preprocessed_queries = queries.pop().expect("no preprocessed queries");
+            let openings = RowOpenings::parse(
+                preprocessed_queries,
+                lde_domain_size,
+                num_queries,
+                num_preprocessed_cols,
+            )
             .map_err(|err| {
                 VerifierError::ProofDeserializationError(format!(
-                    "main trace segment query deserialization failed: {err}"
+                    "preprocessed trace query deserialization failed: {err}"
                 ))
             })?;
+            Some(openings)
+        } else {
+            None
+        };
 
-        // all query proofs will be aggregated into a single vector
-        let mut query_proofs = vec![main_segment_query_proofs];
-
-        // parse auxiliary trace segment queries (if any), and merge resulting tables into a
-        // single table; parsing also validates that hashes of each table row form the leaves
-        // of Merkle authentication paths in the proofs
-        let aux_trace_states = if air.trace_info().is_multi_segment() {
-            let mut aux_trace_states = Vec::new();
-            for (i, segment_queries) in queries.into_iter().enumerate() {
-                let segment_width = air.trace_layout().get_aux_segment_width(i);
-                let (segment_query_proof, segment_trace_states) = segment_queries
-                    .parse::<H, E>(air.lde_domain_size(), num_queries, segment_width)
+        // parse main trace segment queries; parsing also validates that hashes of each table row
+        // form the leaves of Merkle authentication paths in the proofs
+        let main_segment_width = layout.main_trace_width() - num_preprocessed_cols;
+        let main = RowOpenings::parse(
+            queries.remove(0),
+            lde_domain_size,
+            num_queries,
+            main_segment_width,
+        )
+        .map_err(|err| {
+            VerifierError::ProofDeserializationError(format!(
+                "main trace segment query deserialization failed: {err}"
+            ))
+        })?;
+
+        // parse auxiliary trace segment queries (if any)
+        let aux = queries
+            .into_iter()
+            .enumerate()
+            .map(|(i, segment_queries)| {
+                let segment_width = layout.get_aux_segment_width(i);
+                RowOpenings::parse(segment_queries, lde_domain_size, num_queries, segment_width)
                     .map_err(|err| {
                         VerifierError::ProofDeserializationError(format!(
                             "auxiliary trace segment query deserialization failed: {err}"
                         ))
-                    })?;
-
-                query_proofs.push(segment_query_proof);
-                aux_trace_states.push(segment_trace_states);
-            }
-
-            // merge tables for each auxiliary segment into a single table
-            Some(Table::merge(aux_trace_states))
-        } else {
-            None
-        };
+                    })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
 
         Ok(Self {
-            query_proofs,
-            main_states: main_segment_states,
-            aux_states: aux_trace_states,
+            main,
+            aux,
+            preprocessed,
         })
     }
 }
@@ -309,35 +572,28 @@ impl<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> TraceQueries<E
 // CONSTRAINT QUERIES
 // ================================================================================================
 
-/// Container of constraint evaluation query data, including:
-/// * Queried constraint evaluation values.
-/// * Merkle authentication paths for all queries.
-struct ConstraintQueries<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> {
-    query_proofs: BatchMerkleProof<H>,
-    evaluations: Table<E>,
-}
-
-impl<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> ConstraintQueries<E, H> {
-    /// Parses the provided constraint queries into evaluations in the specified field and
-    /// corresponding Merkle authentication paths.
-    pub fn new<A: Air<BaseField = E::BaseField>>(
-        queries: Queries,
-        air: &A,
-    ) -> Result<Self, VerifierError> {
-        let num_queries = air.options().num_queries();
-        let (query_proofs, evaluations) = queries
-            .parse::<H, E>(air.lde_domain_size(), num_queries, air.ce_blowup_factor())
-            .map_err(|err| {
-                VerifierError::ProofDeserializationError(format!(
-                    "constraint evaluation query deserialization failed: {err}"
-                ))
-            })?;
-
-        Ok(Self {
-            query_proofs,
-            evaluations,
-        })
-    }
+/// Parses the provided constraint queries into openings of constraint evaluations in the
+/// specified field.
+fn parse_constraint_queries<E, H, A>(
+    queries: Queries,
+    air: &A,
+) -> Result<RowOpenings<E, H>, VerifierError>
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+    A: Air<BaseField = E::BaseField>,
+{
+    RowOpenings::parse(
+        queries,
+        air.lde_domain_size(),
+        air.options().num_queries(),
+        air.ce_blowup_factor(),
+    )
+    .map_err(|err| {
+        VerifierError::ProofDeserializationError(format!(
+            "constraint evaluation query deserialization failed: {err}"
+        ))
+    })
 }
 
 // TRACE OUT-OF-DOMAIN FRAME
@@ -356,3 +612,51 @@ impl<E: FieldElement> TraceOodFrame<E> {
         }
     }
 }
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns openings containing only the rows at the specified `selected` indexes in the list of
+/// `positions` at which the `openings` were made.
+fn restrict_openings<E, H>(
+    openings: RowOpenings<E, H>,
+    positions: &[usize],
+    selected: &[usize],
+) -> Result<RowOpenings<E, H>, MerkleTreeError>
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+{
+    let (rows, proof) = openings.into_parts();
+    let proof = restrict_proof(proof, positions, selected)?;
+    Ok(RowOpenings::new(rows.select_rows(selected), proof))
+}
+
+/// Returns a batch Merkle proof containing only the authentication paths for the leaves at the
+/// specified `selected` indexes in the list of `indexes` for which the `proof` was built.
+fn restrict_proof<H: ElementHasher>(
+    proof: BatchMerkleProof<H>,
+    indexes: &[usize],
+    selected: &[usize],
+) -> Result<BatchMerkleProof<H>, MerkleTreeError> {
+    let paths = proof.into_paths(indexes)?;
+    let (paths, leaf_indexes): (Vec<_>, Vec<_>) = selected
+        .iter()
+        .map(|&i| (paths[i].clone(), indexes[i]))
+        .unzip();
+    let mut proof = BatchMerkleProof::from_paths(&paths, &leaf_indexes);
+
+    // from_paths() lists leaves in ascending order of their indexes, but verification expects
+    // leaves in the order in which their indexes are provided
+    let mut sorted_indexes = leaf_indexes.clone();
+    sorted_indexes.sort_unstable();
+    proof.leaves = leaf_indexes
+        .iter()
+        .map(|index| {
+            proof.leaves[sorted_indexes
+                .binary_search(index)
+                .expect("index not found")]
+        })
+        .collect();
+    Ok(proof)
+}
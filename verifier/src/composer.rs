@@ -3,7 +3,8 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use air::{proof::Table, Air, DeepCompositionCoefficients, EvaluationFrame};
+use crate::AirData;
+use air::{proof::Table, DeepCompositionCoefficients, EvaluationFrame};
 use math::{batch_inversion, FieldElement};
 use utils::collections::Vec;
 
@@ -18,15 +19,15 @@ pub struct DeepComposer<E: FieldElement> {
 
 impl<E: FieldElement> DeepComposer<E> {
     /// Creates a new composer for computing DEEP composition polynomial values.
-    pub fn new<A: Air<BaseField = E::BaseField>>(
-        air: &A,
+    pub fn new(
+        air_data: &AirData<E::BaseField>,
         query_positions: &[usize],
         z: E,
         cc: DeepCompositionCoefficients<E>,
     ) -> Self {
         // compute LDE domain coordinates for all query positions
-        let g_lde = air.lde_domain_generator();
-        let domain_offset = air.domain_offset();
+        let g_lde = air_data.lde_domain_generator;
+        let domain_offset = air_data.domain_offset;
         let x_coordinates: Vec<E> = query_positions
             .iter()
             .map(|&p| E::from(g_lde.exp_vartime((p as u64).into()) * domain_offset))
@@ -35,7 +36,7 @@ impl<E: FieldElement> DeepComposer<E> {
         DeepComposer {
             cc,
             x_coordinates,
-            z: [z, z * E::from(air.trace_domain_generator())],
+            z: [z, z * E::from(air_data.trace_domain_generator)],
         }
     }
 
@@ -0,0 +1,272 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+//! Estimation of proof verification costs.
+//!
+//! This module can be used to estimate how expensive it would be to verify a given [StarkProof]
+//! in a constrained environment (e.g., in an EVM smart contract) without actually building a
+//! verifier for such an environment. The cost is expressed in terms of hash function invocations,
+//! field operations, and the size of the proof as calldata. These metrics can be used to tune
+//! [ProofOptions](crate::ProofOptions) for a given verification budget: for example, increasing
+//! the blowup factor reduces the number of queries, and thus, the number of Merkle
+//! authentication paths the verifier needs to check.
+//!
+//! The number of hash invocations needed to verify Merkle authentication paths and the size of
+//! the proof are computed exactly. All other metrics are estimates: in particular, the cost of
+//! evaluating transition constraints of the computation at the out-of-domain point is not
+//! included, as it depends on the specifics of the AIR.
+
+use crate::{channel::VerifierChannel, validate_context, VerifierError};
+use air::{proof::StarkProof, Air, FieldExtension, GrindingFunction};
+use crypto::{ElementHasher, MemoryHardProofOfWork};
+use math::{
+    fields::{CubeExtension, QuadExtension},
+    FieldElement,
+};
+
+// CONSTANTS
+// ================================================================================================
+
+/// Gas charged by the EVM for each zero byte of calldata.
+pub const CALLDATA_ZERO_BYTE_GAS: u64 = 4;
+
+/// Gas charged by the EVM for each non-zero byte of calldata.
+pub const CALLDATA_NON_ZERO_BYTE_GAS: u64 = 16;
+
+// VERIFICATION COST
+// ================================================================================================
+
+/// Estimated cost of verifying a STARK proof.
+///
+/// Field operations are expressed in terms of base field operations; a multiplication in a
+/// degree `d` extension field is counted as `d^2` base field multiplications.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct VerificationCost {
+    /// Number of hash function invocations, including hashing of queried values into Merkle tree
+    /// leaves, merging of Merkle tree nodes, and operations of the public coin.
+    pub hash_invocations: usize,
+    /// Number of base field elements hashed into Merkle tree leaves.
+    pub hashed_elements: usize,
+    /// Number of base field multiplications.
+    pub field_multiplications: usize,
+    /// Number of field inversions.
+    pub field_inversions: usize,
+    /// Size of the serialized proof in bytes.
+    pub calldata_bytes: usize,
+    /// Number of zero bytes in the serialized proof.
+    pub calldata_zero_bytes: usize,
+}
+
+impl VerificationCost {
+    /// Returns the amount of gas an EVM-style environment would charge for passing the proof as
+    /// transaction calldata.
+    pub fn calldata_gas(&self) -> u64 {
+        let zero_bytes = self.calldata_zero_bytes as u64;
+        let non_zero_bytes = (self.calldata_bytes - self.calldata_zero_bytes) as u64;
+        zero_bytes * CALLDATA_ZERO_BYTE_GAS + non_zero_bytes * CALLDATA_NON_ZERO_BYTE_GAS
+    }
+}
+
+// COST ESTIMATOR
+// ================================================================================================
+
+/// Returns an estimate of the cost of verifying the specified `proof` for the computation
+/// specified by `AIR` and `HashFn` type parameters against the specified public inputs.
+///
+/// The proof is parsed in the same way as during verification, but none of its components are
+/// checked; thus, the returned estimate does not imply that the proof is valid.
+///
+/// # Errors
+/// Returns an error if the proof is malformed or is not consistent with the computation (i.e.,
+/// if the proof would be rejected by the verifier before any of its components are checked).
+#[rustfmt::skip]
+pub fn estimate_cost<AIR, HashFn>(
+    proof: &StarkProof,
+    pub_inputs: AIR::PublicInputs,
+) -> Result<VerificationCost, VerifierError>
+where
+    AIR: Air,
+    HashFn: ElementHasher<BaseField = AIR::BaseField>,
+{
+    validate_context::<AIR::BaseField>(&proof.context)?;
+    let air = AIR::new(proof.get_trace_info(), pub_inputs, proof.options().clone());
+    estimate_air_cost::<AIR, HashFn>(&air, proof)
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns an estimate of the cost of verifying the specified `proof` for the computation
+/// described by the specified `air`; the context of the proof is assumed to have been validated.
+#[rustfmt::skip]
+pub(crate) fn estimate_air_cost<AIR, HashFn>(
+    air: &AIR,
+    proof: &StarkProof,
+) -> Result<VerificationCost, VerifierError>
+where
+    AIR: Air,
+    HashFn: ElementHasher<BaseField = AIR::BaseField>,
+{
+    match air.options().field_extension() {
+        FieldExtension::None => {
+            estimate_cost_in::<AIR, AIR::BaseField, HashFn>(air, proof)
+        },
+        FieldExtension::Quadratic => {
+            if !<QuadExtension<AIR::BaseField>>::is_supported() {
+                return Err(VerifierError::UnsupportedFieldExtension(2));
+            }
+            estimate_cost_in::<AIR, QuadExtension<AIR::BaseField>, HashFn>(air, proof)
+        },
+        FieldExtension::Cubic => {
+            if !<CubeExtension<AIR::BaseField>>::is_supported() {
+                return Err(VerifierError::UnsupportedFieldExtension(3));
+            }
+            estimate_cost_in::<AIR, CubeExtension<AIR::BaseField>, HashFn>(air, proof)
+        },
+    }
+}
+
+/// Estimates the cost of verifying the `proof` with the computation performed in field `E`.
+fn estimate_cost_in<A, E, H>(air: &A, proof: &StarkProof) -> Result<VerificationCost, VerifierError>
+where
+    A: Air,
+    E: FieldElement<BaseField = A::BaseField>,
+    H: ElementHasher<BaseField = A::BaseField>,
+{
+    let layout = air.trace_layout();
+    let extension_degree = E::EXTENSION_DEGREE;
+    let ext_mul = extension_degree * extension_degree;
+    let num_queries = air.options().num_queries();
+    let fri_options = air.options().to_fri_options();
+    let folding_factor = fri_options.folding_factor();
+    let num_fri_layers = fri_options.num_fri_layers(air.lde_domain_size());
+    let num_remainder_elements = proof.fri_proof.num_remainder_elements::<E>();
+
+    let mut cost = VerificationCost::default();
+
+    // --- proof size -----------------------------------------------------------------------------
+    let proof_bytes = proof.to_bytes();
+    cost.calldata_bytes = proof_bytes.len();
+    cost.calldata_zero_bytes = proof_bytes.iter().filter(|&&b| b == 0).count();
+
+    // --- Merkle authentication paths ------------------------------------------------------------
+    // the widths of the leaves (in base field elements) are listed in the same order as the
+    // proofs returned by the channel
+    let preprocessed_root = layout.has_preprocessed_columns().then(H::Digest::default);
+    let channel = VerifierChannel::<E, H>::new(air, proof.clone(), preprocessed_root)?;
+    let mut leaf_widths = vec![layout.main_trace_width() - layout.num_preprocessed_columns()];
+    for i in 0..layout.num_aux_segments() {
+        leaf_widths.push(layout.get_aux_segment_width(i) * extension_degree);
+    }
+    if layout.has_preprocessed_columns() {
+        leaf_widths.push(layout.num_preprocessed_columns());
+    }
+    leaf_widths.push(air.ce_blowup_factor() * extension_degree);
+    leaf_widths.resize(
+        leaf_widths.len() + num_fri_layers,
+        folding_factor * extension_degree,
+    );
+
+    for ((num_leaves, num_nodes), leaf_width) in channel
+        .merkle_proof_sizes(folding_factor)
+        .into_iter()
+        .zip(leaf_widths)
+    {
+        // each leaf is a hash of queried values; the root is computed from the leaves and the
+        // provided nodes, which form a binary tree in which every internal node is computed by
+        // merging its two children
+        cost.hash_invocations += num_leaves + (num_leaves + num_nodes - 1);
+        cost.hashed_elements += num_leaves * leaf_width;
+    }
+
+    // --- public coin ----------------------------------------------------------------------------
+    // the coin is seeded once, reseeded with every commitment, and every drawn element or
+    // integer requires at least one hash invocation
+    let num_commitments = layout.num_trace_commitments() + 1 + num_fri_layers + 1;
+    let num_aux_rand_elements = (0..layout.num_aux_segments())
+        .map(|i| layout.get_aux_segment_rand_elements(i))
+        .sum::<usize>();
+    let num_constraint_coefficients =
+        2 * air.context().num_transition_constraints() + 2 * air.context().num_assertions();
+    let num_deep_coefficients =
+        layout.main_trace_width() + layout.aux_trace_width() + air.ce_blowup_factor();
+    let num_draws = num_aux_rand_elements
+        + num_constraint_coefficients
+        + 1 // out-of-domain point
+        + num_deep_coefficients
+        + num_fri_layers
+        + num_queries;
+    cost.hash_invocations += 1 + num_commitments + num_draws;
+    if air.options().grinding_factor() > 0 {
+        // verifying query seed proof-of-work requires an extra hash invocation, and the
+        // memory-hard grinding function additionally fills and mixes a table of digests
+        cost.hash_invocations += match air.options().grinding_function() {
+            GrindingFunction::Hash => 1,
+            GrindingFunction::MemoryHard => 1 + 2 * MemoryHardProofOfWork::NUM_BLOCKS,
+        };
+    }
+
+    // --- out-of-domain constraint evaluation ----------------------------------------------------
+    // merging of constraint evaluations requires about two multiplications per constraint;
+    // additionally, value polynomials of sequence assertions are evaluated using Horner's method
+    let sequence_values = air
+        .get_assertions()
+        .iter()
+        .filter(|assertion| assertion.is_sequence())
+        .map(|assertion| assertion.values().len())
+        .sum::<usize>();
+    cost.field_multiplications += num_constraint_coefficients * ext_mul
+        + sequence_values * extension_degree
+        + air.ce_blowup_factor() * ext_mul;
+    // divisors of transition and boundary constraints are inverted together using batch inversion
+    cost.field_inversions += 1;
+
+    // --- DEEP composition -----------------------------------------------------------------------
+    // for every query, each trace column contributes two quotient terms, and each composition
+    // polynomial column contributes a single quotient term; the denominators are inverted once
+    // per query
+    let trace_width = layout.main_trace_width() + layout.aux_trace_width();
+    cost.field_multiplications +=
+        num_queries * (2 * trace_width + air.ce_blowup_factor() + 2) * ext_mul;
+    cost.field_inversions += num_queries * 3;
+
+    // --- FRI ------------------------------------------------------------------------------------
+    // for every query, each layer is folded by interpolating a polynomial of degree
+    // folding_factor - 1 and evaluating it at the layer's random point; then the remainder
+    // polynomial is evaluated at the query position
+    cost.field_multiplications +=
+        num_queries * num_fri_layers * folding_factor * folding_factor * ext_mul;
+    cost.field_inversions += num_queries * num_fri_layers;
+    cost.field_multiplications += num_queries * num_remainder_elements * ext_mul;
+
+    Ok(cost)
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::estimate_cost;
+    use crate::tests::{build_fib_proof, Blake3, FibAir};
+    use air::{FieldExtension, ProofOptions};
+
+    #[test]
+    fn estimate_fib_cost() {
+        let options = ProofOptions::new(28, 8, 0, FieldExtension::None, 4, 7);
+        let (proof, result) = build_fib_proof(128, options);
+        let cost = estimate_cost::<FibAir, Blake3>(&proof, result).unwrap();
+        assert_eq!(proof.to_bytes().len(), cost.calldata_bytes);
+        assert!(cost.calldata_gas() >= 4 * cost.calldata_bytes as u64);
+        assert!(cost.hash_invocations > 0 && cost.field_inversions > 0);
+
+        // a larger blowup factor requires fewer queries, and thus, fewer hash invocations
+        let options = ProofOptions::new(14, 32, 0, FieldExtension::None, 4, 31);
+        let (proof, result) = build_fib_proof(128, options);
+        let other_cost = estimate_cost::<FibAir, Blake3>(&proof, result).unwrap();
+        assert!(other_cost.hash_invocations < cost.hash_invocations);
+        assert!(other_cost.calldata_bytes < cost.calldata_bytes);
+    }
+}
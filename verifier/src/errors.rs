@@ -5,8 +5,9 @@
 
 //! Contains common error types for prover and verifier.
 
+use crate::PolicyViolation;
 use core::fmt;
-use utils::string::String;
+use utils::{string::String, ErrorCategory, ErrorCode, ToErrorCode};
 
 // VERIFIER ERROR
 // ================================================================================================
@@ -16,6 +17,9 @@ pub enum VerifierError {
     /// This error occurs when base field read by a verifier from a proof does not match the
     /// base field of AIR with which the verifier was instantiated.
     InconsistentBaseField,
+    /// This error occurs when the context of a proof (i.e., trace info and proof options) does
+    /// not match the context for which a [PreparedVerifier](crate::PreparedVerifier) was built.
+    InconsistentProofContext,
     /// This error occurs when the base field in which the proof was generated does not support
     /// field extension of degree specified by the proof.
     UnsupportedFieldExtension(usize),
@@ -41,6 +45,21 @@ pub enum VerifierError {
     /// constraint evaluation queries do not represent a polynomial of the degree expected by the
     /// verifier.
     FriVerificationFailed(fri::VerifierError),
+    /// This error occurs when the execution trace of a computation contains preprocessed columns,
+    /// but a commitment to these columns was not provided to the verifier.
+    PreprocessedCommitmentNotProvided,
+    /// This error occurs when a commitment to preprocessed columns was provided to the verifier,
+    /// but the execution trace of the computation does not contain preprocessed columns.
+    UnexpectedPreprocessedCommitment,
+    /// This error occurs when a single query is to be verified, but the index of the query is
+    /// not smaller than the number of queries in the proof.
+    QueryIndexOutOfRange(usize, usize),
+    /// This error occurs when a subset of queries is to be verified, but no query indexes were
+    /// specified.
+    NoQueryIndexes,
+    /// This error occurs when proof options or the hash function used by the verifier do not
+    /// satisfy the [VerifierPolicy](crate::VerifierPolicy) provided to the verifier.
+    PolicyViolation(PolicyViolation),
 }
 
 impl fmt::Display for VerifierError {
@@ -50,6 +69,9 @@ impl fmt::Display for VerifierError {
             Self::InconsistentBaseField =>  {
                 write!(f, "base field of the proof does not match base field of the specified AIR")
             }
+            Self::InconsistentProofContext => {
+                write!(f, "proof context does not match the context expected by the verifier")
+            }
             Self::UnsupportedFieldExtension(degree) => {
                 write!(f, "field extension of degree {degree} is not supported for the proof base field")
             }
@@ -74,6 +96,44 @@ impl fmt::Display for VerifierError {
             Self::FriVerificationFailed(err) => {
                 write!(f, "verification of low-degree proof failed: {err}")
             }
+            Self::PreprocessedCommitmentNotProvided => {
+                write!(f, "execution trace contains preprocessed columns, but commitment to these columns was not provided")
+            }
+            Self::UnexpectedPreprocessedCommitment => {
+                write!(f, "commitment to preprocessed columns was provided, but execution trace does not contain preprocessed columns")
+            }
+            Self::QueryIndexOutOfRange(index, num_queries) => {
+                write!(f, "query index {index} is out of range for a proof with {num_queries} queries")
+            }
+            Self::NoQueryIndexes => {
+                write!(f, "no query indexes were specified")
+            }
+            Self::PolicyViolation(violation) => {
+                write!(f, "proof does not satisfy verifier policy: {violation}")
+            }
         }
     }
 }
+
+impl ToErrorCode for VerifierError {
+    fn error_code(&self) -> ErrorCode {
+        let index = match self {
+            Self::InconsistentBaseField => 1,
+            Self::InconsistentProofContext => 2,
+            Self::UnsupportedFieldExtension(_) => 3,
+            Self::ProofDeserializationError(_) => 4,
+            Self::RandomCoinError => 5,
+            Self::InconsistentOodConstraintEvaluations => 6,
+            Self::TraceQueryDoesNotMatchCommitment => 7,
+            Self::ConstraintQueryDoesNotMatchCommitment => 8,
+            Self::QuerySeedProofOfWorkVerificationFailed => 9,
+            Self::FriVerificationFailed(_) => 10,
+            Self::PreprocessedCommitmentNotProvided => 11,
+            Self::UnexpectedPreprocessedCommitment => 12,
+            Self::QueryIndexOutOfRange(..) => 13,
+            Self::PolicyViolation(_) => 14,
+            Self::NoQueryIndexes => 15,
+        };
+        ErrorCode::new(ErrorCategory::Verifier, index)
+    }
+}
@@ -3,16 +3,22 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use air::{Air, AuxTraceRandElements, ConstraintCompositionCoefficients, EvaluationFrame};
+use crate::AirData;
+use air::{
+    Air, AuxTraceRandElements, ConstraintCompositionCoefficients, ConstraintDivisor,
+    EvaluationFrame,
+};
 use math::{polynom, FieldElement};
 use utils::collections::Vec;
 
 // CONSTRAINT EVALUATION
 // ================================================================================================
 
-/// Evaluates constraints for the specified evaluation frame.
+/// Evaluates constraints for the specified evaluation frame; `air_data` must have been derived
+/// from the specified `air`.
 pub fn evaluate_constraints<A: Air, E: FieldElement<BaseField = A::BaseField>>(
     air: &A,
+    air_data: &AirData<A::BaseField>,
     composition_coefficients: ConstraintCompositionCoefficients<E>,
     main_trace_frame: &EvaluationFrame<E>,
     aux_trace_frame: &Option<EvaluationFrame<E>>,
@@ -25,8 +31,8 @@ pub fn evaluate_constraints<A: Air, E: FieldElement<BaseField = A::BaseField>>(
     let t_constraints = air.get_transition_constraints(&composition_coefficients.transition);
 
     // compute values of periodic columns at x
-    let periodic_values = air
-        .get_periodic_column_polys()
+    let periodic_values = air_data
+        .periodic_column_polys
         .iter()
         .map(|poly| {
             let num_cycles = air.trace_length() / poly.len();
@@ -52,9 +58,9 @@ pub fn evaluate_constraints<A: Air, E: FieldElement<BaseField = A::BaseField>>(
     }
 
     // merge all constraint evaluations into a single value by computing their random linear
-    // combination using coefficients drawn from the public coin. this also divides the result
-    // by the divisor of transition constraints.
-    let mut result = t_constraints.combine_evaluations::<E>(&t_evaluations1, &t_evaluations2, x);
+    // combination using coefficients drawn from the public coin. the result is divided by the
+    // divisor of transition constraints in step 3 below.
+    let t_result = t_constraints.merge_evaluations::<E>(&t_evaluations1, &t_evaluations2, x);
 
     // 2 ----- evaluate boundary constraints ------------------------------------------------------
 
@@ -66,9 +72,14 @@ pub fn evaluate_constraints<A: Air, E: FieldElement<BaseField = A::BaseField>>(
     let mut degree_adjustment = b_constraints.main_constraints()[0].degree_adjustment();
     let mut xp = x.exp_vartime(degree_adjustment.into());
 
+    // divisors of all constraints are collected here so that they can be inverted together;
+    // the transition constraint divisor always comes first
+    let mut divisors = Vec::with_capacity(b_constraints.main_constraints().len() + 1);
+    divisors.push(&air_data.transition_divisor);
+    let mut b_results = Vec::with_capacity(b_constraints.main_constraints().len());
+
     // iterate over boundary constraint groups for the main trace segment (each group has a
-    // distinct divisor), evaluate constraints in each group and add their combination to the
-    // result
+    // distinct divisor), and merge evaluations of constraints in each group into a single value
     for group in b_constraints.main_constraints().iter() {
         // if adjustment degree hasn't changed, no need to recompute `xp` - so just reuse the
         // previous value; otherwise, compute new `xp`
@@ -76,13 +87,13 @@ pub fn evaluate_constraints<A: Air, E: FieldElement<BaseField = A::BaseField>>(
             degree_adjustment = group.degree_adjustment();
             xp = x.exp_vartime(degree_adjustment.into());
         }
-        // evaluate all constraints in the group, and add the evaluation to the result
-        result += group.evaluate_at(main_trace_frame.current(), x, xp);
+        // merge evaluations of all constraints in the group, and record the group's divisor
+        b_results.push(group.merge_evaluations_at(main_trace_frame.current(), x, xp));
+        divisors.push(group.divisor());
     }
 
     // iterate over boundary constraint groups for auxiliary trace segments (each group has a
-    // distinct divisor), evaluate constraints in each group and add their combination to the
-    // result
+    // distinct divisor), and merge evaluations of constraints in each group into a single value
     if let Some(aux_trace_frame) = aux_trace_frame {
         for group in b_constraints.aux_constraints().iter() {
             // if adjustment degree hasn't changed, no need to recompute `xp` - so just reuse the
@@ -91,10 +102,21 @@ pub fn evaluate_constraints<A: Air, E: FieldElement<BaseField = A::BaseField>>(
                 degree_adjustment = group.degree_adjustment();
                 xp = x.exp_vartime(degree_adjustment.into());
             }
-            // evaluate all constraints in the group, and add the evaluation to the result
-            result += group.evaluate_at(aux_trace_frame.current(), x, xp);
+            // merge evaluations of all constraints in the group, and record the group's divisor
+            b_results.push(group.merge_evaluations_at(aux_trace_frame.current(), x, xp));
+            divisors.push(group.divisor());
         }
     }
 
-    result
+    // 3 ----- divide out constraint divisors ------------------------------------------------------
+
+    // evaluate inverses of all divisors at x using a single field inversion, and combine
+    // transition and boundary constraint evaluations into a single value
+    let inv_divisors = ConstraintDivisor::evaluate_inverses_at(&divisors, x);
+    b_results
+        .into_iter()
+        .zip(&inv_divisors[1..])
+        .fold(t_result * inv_divisors[0], |acc, (value, &inv_divisor)| {
+            acc + value * inv_divisor
+        })
 }
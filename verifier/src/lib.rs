@@ -33,25 +33,33 @@
 extern crate alloc;
 
 pub use air::{
-    proof::StarkProof, Air, AirContext, Assertion, AuxTraceRandElements, BoundaryConstraint,
-    BoundaryConstraintGroup, ConstraintCompositionCoefficients, ConstraintDivisor,
-    DeepCompositionCoefficients, EvaluationFrame, FieldExtension, ProofOptions, TraceInfo,
-    TransitionConstraintDegree, TransitionConstraintGroup,
+    proof::{StarkProof, PROOF_VERSION},
+    Air, AirContext, Assertion, AssertionError, Assertions, AuxTraceRandElements,
+    BoundaryConstraint, BoundaryConstraintGroup, ConstraintCompositionCoefficients,
+    ConstraintDivisor, DeepCompositionCoefficients, EvaluationFrame, Expr, FieldExtension,
+    GrindingFunction, MultiInstanceAir, MultiInstanceInputs, ProofOptions, QuerySampling, Register,
+    TraceInfo, TraceStep, TransitionConstraintDegree, TransitionConstraintGroup,
+    TransitionExpressions,
 };
 
 pub use math;
 use math::{
     fields::{CubeExtension, QuadExtension},
-    FieldElement, ToElements,
+    FieldElement, StarkField, ToElements,
 };
 
 pub use utils::{
-    ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable, SliceReader,
+    ByteReader, ByteWriter, Deserializable, DeserializationError, ErrorCategory, ErrorCode,
+    Serializable, SliceReader, ToErrorCode,
 };
 
 pub use crypto;
 use crypto::{ElementHasher, RandomCoin};
 
+use air::proof::Context;
+
+use utils::collections::Vec;
+
 use fri::FriVerifier;
 
 mod channel;
@@ -65,6 +73,24 @@ use composer::DeepComposer;
 
 mod errors;
 pub use errors::VerifierError;
+pub use fri::VerifierError as FriVerifierError;
+
+mod prepared;
+use prepared::AirData;
+pub use prepared::PreparedVerifier;
+
+mod policy;
+pub use policy::{PolicyViolation, VerifierPolicy};
+
+pub mod cost_model;
+
+mod stats;
+pub use stats::VerificationPhase;
+#[cfg(feature = "std")]
+pub use stats::{verify_with_stats, VerificationStats};
+
+#[cfg(test)]
+mod tests;
 
 // VERIFIER
 // ================================================================================================
@@ -88,39 +114,247 @@ where
     AIR: Air, 
     HashFn: ElementHasher<BaseField = AIR::BaseField>,
     RandCoin: RandomCoin<BaseField = AIR::BaseField, Hasher = HashFn>,
+{
+    // make sure the proof context is consistent with the base field of the computation; this
+    // needs to be done before the AIR is instantiated because AIR construction panics on
+    // parameters which are invalid for the field
+    validate_context::<AIR::BaseField>(&proof.context)?;
+
+    let context_elements = proof.context.to_elements();
+    verify_with_context_elements::<AIR, HashFn, RandCoin>(proof, pub_inputs, context_elements, None)
+}
+
+/// Verifies that the specified computation was executed correctly against the specified inputs,
+/// and that the proof satisfies the specified verifier `policy`.
+///
+/// This is the same as [verify()], but the options of the `proof` and the `HashFn` type parameter
+/// are first checked against the `policy`. This should be used when proof options are not fixed
+/// by the verifier up front, as otherwise, a prover could generate a proof with options which
+/// provide insufficient security (e.g., with very few queries).
+///
+/// # Errors
+/// Returns [VerifierError::PolicyViolation] if the proof does not satisfy the `policy`, or an
+/// error for any of the reasons described in [verify()].
+#[rustfmt::skip]
+pub fn verify_with_policy<AIR, HashFn, RandCoin>(
+    proof: StarkProof,
+    pub_inputs: AIR::PublicInputs,
+    policy: &VerifierPolicy,
+) -> Result<(), VerifierError>
+where
+    AIR: Air,
+    HashFn: ElementHasher<BaseField = AIR::BaseField> + 'static,
+    RandCoin: RandomCoin<BaseField = AIR::BaseField, Hasher = HashFn>,
+{
+    policy
+        .check::<HashFn>(proof.options())
+        .map_err(VerifierError::PolicyViolation)?;
+
+    verify::<AIR, HashFn, RandCoin>(proof, pub_inputs)
+}
+
+/// Verifies that the specified computation was executed correctly against the specified inputs
+/// and the specified commitment to preprocessed trace columns.
+///
+/// This is the same as [verify()], but should be used for computations with execution traces
+/// containing preprocessed columns. `preprocessed_root` must be the root of a commitment to these
+/// columns built by the prover (i.e., `PreprocessedCommitment::root()`); the verifier must obtain
+/// it from a trusted source rather than from the prover at proving time.
+///
+/// # Errors
+/// Returns an error if the execution trace of the computation does not contain preprocessed
+/// columns, or for any of the reasons described in [verify()].
+#[rustfmt::skip]
+pub fn verify_with_preprocessed<AIR, HashFn, RandCoin>(
+    proof: StarkProof,
+    pub_inputs: AIR::PublicInputs,
+    preprocessed_root: HashFn::Digest,
+) -> Result<(), VerifierError>
+where
+    AIR: Air,
+    HashFn: ElementHasher<BaseField = AIR::BaseField>,
+    RandCoin: RandomCoin<BaseField = AIR::BaseField, Hasher = HashFn>,
+{
+    validate_context::<AIR::BaseField>(&proof.context)?;
+
+    let context_elements = proof.context.to_elements();
+    verify_with_context_elements::<AIR, HashFn, RandCoin>(
+        proof,
+        pub_inputs,
+        context_elements,
+        Some(preprocessed_root),
+    )
+}
+
+/// Verifies the `proof` assuming that its context has already been validated and that
+/// `context_elements` is the result of encoding the proof context into field elements.
+fn verify_with_context_elements<AIR, HashFn, RandCoin>(
+    proof: StarkProof,
+    pub_inputs: AIR::PublicInputs,
+    context_elements: Vec<AIR::BaseField>,
+    preprocessed_root: Option<HashFn::Digest>,
+) -> Result<(), VerifierError>
+where
+    AIR: Air,
+    HashFn: ElementHasher<BaseField = AIR::BaseField>,
+    RandCoin: RandomCoin<BaseField = AIR::BaseField, Hasher = HashFn>,
 {
     // build a seed for the public coin; the initial seed is a hash of the proof context and the
     // public inputs, but as the protocol progresses, the coin will be reseeded with the info
     // received from the prover
-    let mut public_coin_seed = proof.context.to_elements();
+    let mut public_coin_seed = context_elements;
     public_coin_seed.append(&mut pub_inputs.to_elements());
-    
+
     // create AIR instance for the computation specified in the proof
     let air = AIR::new(proof.get_trace_info(), pub_inputs, proof.options().clone());
+    let air_data = AirData::new(&air);
+
+    verify_with_air::<AIR, HashFn, RandCoin>(
+        &air,
+        &air_data,
+        proof,
+        public_coin_seed,
+        preprocessed_root,
+        None,
+        &mut |_| {},
+    )
+}
 
+/// Verifies a single query of the specified proof against the specified inputs.
+///
+/// This is equivalent to calling [verify_queries()] with a single query index and no
+/// preprocessed commitment; see [verify_queries()] for details. Proofs of computations with
+/// preprocessed trace columns must be verified via [verify_queries()].
+///
+/// # Errors
+/// Returns an error for the same reasons as [verify_queries()].
+pub fn verify_single_query<AIR, HashFn, RandCoin>(
+    proof: StarkProof,
+    pub_inputs: AIR::PublicInputs,
+    query_index: usize,
+) -> Result<(), VerifierError>
+where
+    AIR: Air,
+    HashFn: ElementHasher<BaseField = AIR::BaseField>,
+    RandCoin: RandomCoin<BaseField = AIR::BaseField, Hasher = HashFn>,
+{
+    verify_queries::<AIR, HashFn, RandCoin>(proof, pub_inputs, &[query_index], None)
+}
+
+/// Verifies a subset of queries of the specified proof against the specified inputs.
+///
+/// This can be used for probabilistic auditing of large proofs: different parties can each
+/// verify a random subset of queries of the same proof, and the proof is accepted only if all
+/// queries are verified by some party. `query_indexes` specify the indexes of the queries in the
+/// list of query positions drawn by the verifier, and must be smaller than the number of queries
+/// specified in the proof options; duplicate indexes are ignored. `preprocessed_root` must be
+/// provided if and only if the execution trace of the computation contains preprocessed columns
+/// (see [verify_with_preprocessed()]).
+///
+/// All values needed to re-derive the public coin (i.e., commitments, the out-of-domain frame,
+/// and the proof-of-work nonce) are processed in the same way as by [verify()], and the
+/// out-of-domain consistency check is also performed. However, only Merkle authentication paths
+/// of the specified queries are verified against trace, constraint, and FRI layer commitments
+/// (including proofs with FRI layer proofs aggregated into a single multiproof), and the DEEP
+/// composition polynomial evaluations and FRI folding are checked only for these queries. Thus,
+/// successful verification of a subset of queries provides much weaker soundness guarantees
+/// than verification of the entire proof.
+///
+/// Batch Merkle proofs are expanded into individual authentication paths once per call; thus,
+/// verifying k queries in a single call is cheaper than k calls with one query each.
+///
+/// # Errors
+/// Returns an error if:
+/// * `query_indexes` is empty.
+/// * Any of the `query_indexes` is greater than or equal to the number of queries in the proof.
+/// * The specified queries do not attest to a correct execution of the computation for any of
+///   the reasons described in [verify()].
+pub fn verify_queries<AIR, HashFn, RandCoin>(
+    proof: StarkProof,
+    pub_inputs: AIR::PublicInputs,
+    query_indexes: &[usize],
+    preprocessed_root: Option<HashFn::Digest>,
+) -> Result<(), VerifierError>
+where
+    AIR: Air,
+    HashFn: ElementHasher<BaseField = AIR::BaseField>,
+    RandCoin: RandomCoin<BaseField = AIR::BaseField, Hasher = HashFn>,
+{
+    if query_indexes.is_empty() {
+        return Err(VerifierError::NoQueryIndexes);
+    }
+    let num_queries = proof.options().num_queries();
+    let mut query_indexes = query_indexes.to_vec();
+    query_indexes.sort_unstable();
+    query_indexes.dedup();
+    if let Some(&index) = query_indexes.last().filter(|&&index| index >= num_queries) {
+        return Err(VerifierError::QueryIndexOutOfRange(index, num_queries));
+    }
+
+    validate_context::<AIR::BaseField>(&proof.context)?;
+    let mut public_coin_seed = proof.context.to_elements();
+    public_coin_seed.append(&mut pub_inputs.to_elements());
+    let air = AIR::new(proof.get_trace_info(), pub_inputs, proof.options().clone());
+    let air_data = AirData::new(&air);
+
+    verify_with_air::<AIR, HashFn, RandCoin>(
+        &air,
+        &air_data,
+        proof,
+        public_coin_seed,
+        preprocessed_root,
+        Some(&query_indexes),
+        &mut |_| {},
+    )
+}
+
+/// Verifies the `proof` against the computation described by the specified `air` using a public
+/// coin instantiated with `public_coin_seed`; `on_phase` is invoked after every phase of the
+/// verification procedure is completed. `air_data` must have been derived from an AIR with the
+/// same context as `air`.
+///
+/// If `query_indexes` are provided, only the queries at these indexes are verified.
+#[rustfmt::skip]
+fn verify_with_air<AIR, HashFn, RandCoin>(
+    air: &AIR,
+    air_data: &AirData<AIR::BaseField>,
+    proof: StarkProof,
+    public_coin_seed: Vec<AIR::BaseField>,
+    preprocessed_root: Option<HashFn::Digest>,
+    query_indexes: Option<&[usize]>,
+    on_phase: &mut dyn FnMut(VerificationPhase),
+) -> Result<(), VerifierError>
+where
+    AIR: Air,
+    HashFn: ElementHasher<BaseField = AIR::BaseField>,
+    RandCoin: RandomCoin<BaseField = AIR::BaseField, Hasher = HashFn>,
+{
     // figure out which version of the generic proof verification procedure to run. this is a sort
     // of static dispatch for selecting two generic parameter: extension field and hash function.
     match air.options().field_extension() {
         FieldExtension::None => {
             let public_coin = RandCoin::new(&public_coin_seed);
-            let channel = VerifierChannel::new(&air, proof)?;
-            perform_verification::<AIR, AIR::BaseField, HashFn, RandCoin>(air, channel, public_coin)
+            let channel = VerifierChannel::new(air, proof, preprocessed_root)?;
+            on_phase(VerificationPhase::ProofParsing);
+            perform_verification::<AIR, AIR::BaseField, HashFn, RandCoin>(air, air_data, channel, public_coin, query_indexes, on_phase)
         },
         FieldExtension::Quadratic => {
             if !<QuadExtension<AIR::BaseField>>::is_supported() {
                 return Err(VerifierError::UnsupportedFieldExtension(2));
             }
             let public_coin = RandCoin::new(&public_coin_seed);
-            let channel = VerifierChannel::new(&air, proof)?;
-            perform_verification::<AIR, QuadExtension<AIR::BaseField>, HashFn, RandCoin>(air, channel, public_coin)
+            let channel = VerifierChannel::new(air, proof, preprocessed_root)?;
+            on_phase(VerificationPhase::ProofParsing);
+            perform_verification::<AIR, QuadExtension<AIR::BaseField>, HashFn, RandCoin>(air, air_data, channel, public_coin, query_indexes, on_phase)
         },
         FieldExtension::Cubic => {
             if !<CubeExtension<AIR::BaseField>>::is_supported() {
                 return Err(VerifierError::UnsupportedFieldExtension(3));
             }
             let public_coin = RandCoin::new(&public_coin_seed);
-            let channel = VerifierChannel::new(&air, proof)?;
-            perform_verification::<AIR, CubeExtension<AIR::BaseField>, HashFn, RandCoin>(air, channel, public_coin)
+            let channel = VerifierChannel::new(air, proof, preprocessed_root)?;
+            on_phase(VerificationPhase::ProofParsing);
+            perform_verification::<AIR, CubeExtension<AIR::BaseField>, HashFn, RandCoin>(air, air_data, channel, public_coin, query_indexes, on_phase)
         },
     }
 }
@@ -129,10 +363,16 @@ where
 // ================================================================================================
 /// Performs the actual verification by reading the data from the `channel` and making sure it
 /// attests to a correct execution of the computation specified by the provided `air`.
+///
+/// If `query_indexes` are provided, only the queries at these indexes are verified; `on_phase` is
+/// invoked after each phase of the procedure is completed.
 fn perform_verification<A, E, H, R>(
-    air: A,
+    air: &A,
+    air_data: &AirData<A::BaseField>,
     mut channel: VerifierChannel<E, H>,
     mut public_coin: R,
+    query_indexes: Option<&[usize]>,
+    on_phase: &mut dyn FnMut(VerificationPhase),
 ) -> Result<(), VerifierError>
 where
     A: Air,
@@ -151,6 +391,12 @@ where
     // constraint composition polynomial.
     let trace_commitments = channel.read_trace_commitments();
 
+    // reseed the coin with the commitment to preprocessed trace columns (if any); this commitment
+    // is not sent by the prover, but it is committed to before the main trace segment
+    if let Some(preprocessed_commitment) = channel.read_preprocessed_commitment() {
+        public_coin.reseed(preprocessed_commitment);
+    }
+
     // reseed the coin with the commitment to the main trace segment
     public_coin.reseed(trace_commitments[0]);
 
@@ -168,6 +414,7 @@ where
     let constraint_coeffs = air
         .get_constraint_composition_coefficients(&mut public_coin)
         .map_err(|_| VerifierError::RandomCoinError)?;
+    on_phase(VerificationPhase::TraceCommitments);
 
     // 2 ----- constraint commitment --------------------------------------------------------------
     // read the commitment to evaluations of the constraint composition polynomial over the LDE
@@ -190,7 +437,8 @@ where
     // coin with the OOD frames received from the prover.
     let (ood_main_trace_frame, ood_aux_trace_frame) = channel.read_ood_trace_frame();
     let ood_constraint_evaluation_1 = evaluate_constraints(
-        &air,
+        air,
+        air_data,
         constraint_coeffs,
         &ood_main_trace_frame,
         &ood_aux_trace_frame,
@@ -232,6 +480,7 @@ where
     if ood_constraint_evaluation_1 != ood_constraint_evaluation_2 {
         return Err(VerifierError::InconsistentOodConstraintEvaluations);
     }
+    on_phase(VerificationPhase::OodConsistencyCheck);
 
     // 4 ----- FRI commitments --------------------------------------------------------------------
     // draw coefficients for computing DEEP composition polynomial from the public coin; in the
@@ -255,34 +504,54 @@ where
     )
     .map_err(VerifierError::FriVerificationFailed)?;
     // TODO: make sure air.lde_domain_size() == fri_verifier.domain_size()
+    on_phase(VerificationPhase::FriCommitments);
 
     // 5 ----- trace and constraint queries -------------------------------------------------------
-    // read proof-of-work nonce sent by the prover and update the public coin with it
+    // read proof-of-work nonce sent by the prover and make sure the proof-of-work specified by
+    // the grinding factor and the grinding function is satisfied
     let pow_nonce = channel.read_pow_nonce();
-    public_coin.reseed_with_int(pow_nonce);
-
-    // make sure the proof-of-work specified by the grinding factor is satisfied
-    if public_coin.leading_zeros() < air.options().grinding_factor() {
+    let grinding_factor = air.options().grinding_factor();
+    if grinding_factor > 0
+        && air
+            .options()
+            .grinding_function()
+            .check_nonce(&public_coin, pow_nonce)
+            .map_or(true, |leading_zeros| leading_zeros < grinding_factor)
+    {
         return Err(VerifierError::QuerySeedProofOfWorkVerificationFailed);
     }
 
+    // update the public coin with the proof-of-work nonce
+    public_coin.reseed_with_int(pow_nonce);
+
     // draw pseudo-random query positions for the LDE domain from the public coin; in the
     // interactive version of the protocol, the verifier sends these query positions to the prover,
     // and the prover responds with decommitments against these positions for trace and constraint
     // composition polynomial evaluations.
-    let query_positions = public_coin
-        .draw_integers(air.options().num_queries(), air.lde_domain_size())
+    let mut query_positions = air
+        .get_query_positions(&mut public_coin)
         .map_err(|_| VerifierError::RandomCoinError)?;
 
+    // when only a subset of queries is verified, discard the data of all other queries
+    if let Some(indexes) = query_indexes {
+        query_positions = channel.restrict_to_queries(
+            &query_positions,
+            indexes,
+            air.lde_domain_size(),
+            air.options().to_fri_options().folding_factor(),
+        )?;
+    }
+
     // read evaluations of trace and constraint composition polynomials at the queried positions;
     // this also checks that the read values are valid against trace and constraint commitments
     let (queried_main_trace_states, queried_aux_trace_states) =
         channel.read_queried_trace_states(&query_positions)?;
     let queried_constraint_evaluations = channel.read_constraint_evaluations(&query_positions)?;
+    on_phase(VerificationPhase::Queries);
 
     // 6 ----- DEEP composition -------------------------------------------------------------------
     // compute evaluations of the DEEP composition polynomial at the queried positions
-    let composer = DeepComposer::new(&air, &query_positions, z, deep_coefficients);
+    let composer = DeepComposer::new(air_data, &query_positions, z, deep_coefficients);
     let t_composition = composer.compose_trace_columns(
         queried_main_trace_states,
         queried_aux_trace_states,
@@ -292,11 +561,72 @@ where
     let c_composition = composer
         .compose_constraint_evaluations(queried_constraint_evaluations, ood_constraint_evaluations);
     let deep_evaluations = composer.combine_compositions(t_composition, c_composition);
+    on_phase(VerificationPhase::DeepComposition);
 
     // 7 ----- Verify low-degree proof -------------------------------------------------------------
     // make sure that evaluations of the DEEP composition polynomial we computed in the previous
     // step are in fact evaluations of a polynomial of degree equal to trace polynomial degree
     fri_verifier
         .verify(&mut channel, &deep_evaluations, &query_positions)
-        .map_err(VerifierError::FriVerificationFailed)
+        .map_err(VerifierError::FriVerificationFailed)?;
+    on_phase(VerificationPhase::FriVerification);
+
+    Ok(())
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Checks that the parameters of the proof `context` can be instantiated over the base field
+/// `B`.
+///
+/// Specifically, this makes sure that the field modulus of the proof matches the modulus of `B`,
+/// that the LDE domain implied by the context fits into the largest multiplicative subgroup of
+/// `B`, that the LDE domain offset is neither zero nor an element of this subgroup, and that the
+/// LDE domain segments used for stratified query sampling are large enough.
+fn validate_context<B: StarkField>(context: &Context) -> Result<(), VerifierError> {
+    // make AIR and proof base fields are the same
+    if B::get_modulus_le_bytes() != context.field_modulus_bytes() {
+        return Err(VerifierError::InconsistentBaseField);
+    }
+
+    let lde_domain_size_log2 =
+        context.trace_length().ilog2() + context.options().blowup_factor().ilog2();
+    if lde_domain_size_log2 > B::TWO_ADICITY {
+        return Err(VerifierError::ProofDeserializationError(format!(
+            "LDE domain size cannot exceed 2^{}, but was 2^{}",
+            B::TWO_ADICITY,
+            lde_domain_size_log2
+        )));
+    }
+
+    // on 32-bit platforms, the LDE domain may fit into the field but not into `usize`
+    if lde_domain_size_log2 >= usize::BITS {
+        return Err(VerifierError::ProofDeserializationError(format!(
+            "LDE domain size cannot be greater than or equal to 2^{}, but was 2^{}",
+            usize::BITS,
+            lde_domain_size_log2
+        )));
+    }
+
+    let domain_offset = context.options().domain_offset::<B>();
+    let lde_domain_size = 1u64 << lde_domain_size_log2;
+    if domain_offset == B::ZERO || domain_offset.exp(lde_domain_size.into()) == B::ONE {
+        return Err(VerifierError::ProofDeserializationError(format!(
+            "domain offset {domain_offset} must not be zero or in the LDE domain subgroup"
+        )));
+    }
+
+    if let QuerySampling::Stratified(num_segments) = context.options().query_sampling() {
+        let segment_size = lde_domain_size as usize / num_segments;
+        let max_segment_queries =
+            (context.options().num_queries() + num_segments - 1) / num_segments;
+        if max_segment_queries >= segment_size {
+            return Err(VerifierError::ProofDeserializationError(format!(
+                "LDE domain segments of size {segment_size} are too small for {max_segment_queries} queries per segment"
+            )));
+        }
+    }
+
+    Ok(())
 }
@@ -0,0 +1,231 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use air::ProofOptions;
+use core::{
+    any::{type_name, TypeId},
+    fmt,
+};
+use crypto::Hasher;
+
+// VERIFIER POLICY
+// ================================================================================================
+
+/// Minimum requirements a proof must satisfy to be accepted by a relying party.
+///
+/// Proof options (e.g., the number of queries or the grinding factor) are chosen by the prover and
+/// included in the proof. A relying party which does not fix the options up front can use a
+/// policy to reject proofs whose options fall below its own thresholds, regardless of what the
+/// proof claims about itself. Policies are enforced by
+/// [verify_with_policy()](crate::verify_with_policy) before any other verification work is done.
+///
+/// A policy created via [VerifierPolicy::new()] accepts proofs with any options; requirements are
+/// added using `set_*` methods.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct VerifierPolicy {
+    min_num_queries: usize,
+    min_blowup_factor: usize,
+    min_grinding_factor: u32,
+    required_hash: Option<(TypeId, &'static str)>,
+}
+
+impl VerifierPolicy {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a new policy which does not impose any requirements on proofs.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the minimum number of queries a proof must use.
+    pub fn set_min_num_queries(mut self, num_queries: usize) -> Self {
+        self.min_num_queries = num_queries;
+        self
+    }
+
+    /// Sets the minimum blowup factor a proof must use.
+    pub fn set_min_blowup_factor(mut self, blowup_factor: usize) -> Self {
+        self.min_blowup_factor = blowup_factor;
+        self
+    }
+
+    /// Sets the minimum number of bits of query seed grinding a proof must use.
+    pub fn set_min_grinding_factor(mut self, grinding_factor: u32) -> Self {
+        self.min_grinding_factor = grinding_factor;
+        self
+    }
+
+    /// Requires proofs to be verified using hash function `H`.
+    ///
+    /// Proofs do not record the hash function they were generated with; instead, the hash
+    /// function is selected by the verifier via a type parameter. Requiring a specific hash
+    /// function guards against verifiers instantiated with a weaker hash function (e.g., when the
+    /// hash function is chosen dynamically based on untrusted input).
+    pub fn set_required_hash<H: Hasher + 'static>(mut self) -> Self {
+        self.required_hash = Some((TypeId::of::<H>(), type_name::<H>()));
+        self
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the minimum number of queries a proof must use.
+    pub fn min_num_queries(&self) -> usize {
+        self.min_num_queries
+    }
+
+    /// Returns the minimum blowup factor a proof must use.
+    pub fn min_blowup_factor(&self) -> usize {
+        self.min_blowup_factor
+    }
+
+    /// Returns the minimum number of bits of query seed grinding a proof must use.
+    pub fn min_grinding_factor(&self) -> u32 {
+        self.min_grinding_factor
+    }
+
+    // POLICY ENFORCEMENT
+    // --------------------------------------------------------------------------------------------
+
+    /// Checks whether a proof generated with the specified `options` and verified using hash
+    /// function `H` satisfies this policy.
+    ///
+    /// # Errors
+    /// Returns the first requirement of this policy which is violated.
+    pub fn check<H: Hasher + 'static>(
+        &self,
+        options: &ProofOptions,
+    ) -> Result<(), PolicyViolation> {
+        if options.num_queries() < self.min_num_queries {
+            return Err(PolicyViolation::NotEnoughQueries {
+                min: self.min_num_queries,
+                actual: options.num_queries(),
+            });
+        }
+        if options.blowup_factor() < self.min_blowup_factor {
+            return Err(PolicyViolation::BlowupFactorTooSmall {
+                min: self.min_blowup_factor,
+                actual: options.blowup_factor(),
+            });
+        }
+        if options.grinding_factor() < self.min_grinding_factor {
+            return Err(PolicyViolation::GrindingFactorTooSmall {
+                min: self.min_grinding_factor,
+                actual: options.grinding_factor(),
+            });
+        }
+        if let Some((hash_id, hash_name)) = self.required_hash {
+            if TypeId::of::<H>() != hash_id {
+                return Err(PolicyViolation::HashFunctionNotAllowed {
+                    required: hash_name,
+                    actual: type_name::<H>(),
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+// POLICY VIOLATION
+// ================================================================================================
+
+/// Describes a requirement of a [VerifierPolicy] which a proof does not satisfy.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PolicyViolation {
+    /// The proof uses fewer queries than required by the policy.
+    NotEnoughQueries { min: usize, actual: usize },
+    /// The proof uses a smaller blowup factor than required by the policy.
+    BlowupFactorTooSmall { min: usize, actual: usize },
+    /// The proof uses a smaller grinding factor than required by the policy.
+    GrindingFactorTooSmall { min: u32, actual: u32 },
+    /// The verifier uses a hash function different from the one required by the policy.
+    HashFunctionNotAllowed {
+        required: &'static str,
+        actual: &'static str,
+    },
+}
+
+impl fmt::Display for PolicyViolation {
+    #[rustfmt::skip]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotEnoughQueries { min, actual } => {
+                write!(f, "proof uses {actual} queries, but at least {min} are required")
+            }
+            Self::BlowupFactorTooSmall { min, actual } => {
+                write!(f, "proof uses blowup factor {actual}, but at least {min} is required")
+            }
+            Self::GrindingFactorTooSmall { min, actual } => {
+                write!(f, "proof uses {actual} bits of grinding, but at least {min} are required")
+            }
+            Self::HashFunctionNotAllowed { required, actual } => {
+                write!(f, "proof is verified using hash function {actual}, but {required} is required")
+            }
+        }
+    }
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::{PolicyViolation, VerifierPolicy};
+    use air::{FieldExtension, ProofOptions};
+    use crypto::hashers::{Blake3_256, Sha3_256};
+    use math::fields::f128::BaseElement;
+
+    type Blake3 = Blake3_256<BaseElement>;
+
+    #[test]
+    fn policy_check() {
+        let options = ProofOptions::new(28, 8, 16, FieldExtension::None, 4, 31);
+
+        // a default policy accepts proofs with any options
+        assert_eq!(Ok(()), VerifierPolicy::new().check::<Blake3>(&options));
+
+        // a policy matching the options is satisfied
+        let policy = VerifierPolicy::new()
+            .set_min_num_queries(28)
+            .set_min_blowup_factor(8)
+            .set_min_grinding_factor(16)
+            .set_required_hash::<Blake3>();
+        assert_eq!(Ok(()), policy.check::<Blake3>(&options));
+
+        // options below any of the thresholds are rejected
+        assert_eq!(
+            Err(PolicyViolation::NotEnoughQueries {
+                min: 29,
+                actual: 28
+            }),
+            policy
+                .clone()
+                .set_min_num_queries(29)
+                .check::<Blake3>(&options)
+        );
+        assert_eq!(
+            Err(PolicyViolation::BlowupFactorTooSmall { min: 16, actual: 8 }),
+            policy
+                .clone()
+                .set_min_blowup_factor(16)
+                .check::<Blake3>(&options)
+        );
+        assert_eq!(
+            Err(PolicyViolation::GrindingFactorTooSmall {
+                min: 20,
+                actual: 16
+            }),
+            policy
+                .clone()
+                .set_min_grinding_factor(20)
+                .check::<Blake3>(&options)
+        );
+        assert!(matches!(
+            policy.check::<Sha3_256<BaseElement>>(&options),
+            Err(PolicyViolation::HashFunctionNotAllowed { .. })
+        ));
+    }
+}
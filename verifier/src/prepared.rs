@@ -0,0 +1,189 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use crate::{validate_context, verify_with_air, VerifierError};
+use air::{
+    proof::{Context, StarkProof},
+    Air, ConstraintDivisor,
+};
+use core::marker::PhantomData;
+use crypto::{ElementHasher, RandomCoin};
+use math::{StarkField, ToElements};
+use utils::collections::Vec;
+
+// PREPARED VERIFIER
+// ================================================================================================
+
+/// A verifier for proofs of a fixed computation executed with a fixed set of proof parameters.
+///
+/// When many proofs of the same computation are verified, some of the work done by
+/// [verify()](crate::verify) does not depend on the proof. A prepared verifier performs this work
+/// once at construction time; specifically, it:
+/// * Validates the proof context (i.e., trace info and proof options) against the base field of
+///   the computation.
+/// * Encodes the proof context into field elements used to seed the public coin.
+/// * Computes trace and LDE domain generators, interpolates periodic columns of the computation
+///   into polynomials, and builds the divisor of transition constraints.
+///
+/// Proofs passed to [PreparedVerifier::verify()] must have been generated for exactly the same
+/// trace info and proof options as the ones used to build the verifier; otherwise, they are
+/// rejected. This also protects against a prover downgrading proof parameters.
+///
+/// Work which depends on the public inputs or on randomness drawn during the protocol (e.g.,
+/// instantiating the AIR, building boundary constraints, or evaluating divisors at the
+/// out-of-domain point) is still done for every proof.
+pub struct PreparedVerifier<AIR, HashFn, RandCoin>
+where
+    AIR: Air,
+    HashFn: ElementHasher<BaseField = AIR::BaseField>,
+    RandCoin: RandomCoin<BaseField = AIR::BaseField, Hasher = HashFn>,
+{
+    context: Context,
+    context_elements: Vec<AIR::BaseField>,
+    air_data: AirData<AIR::BaseField>,
+    preprocessed_root: Option<HashFn::Digest>,
+    _random_coin: PhantomData<RandCoin>,
+}
+
+impl<AIR, HashFn, RandCoin> PreparedVerifier<AIR, HashFn, RandCoin>
+where
+    AIR: Air,
+    HashFn: ElementHasher<BaseField = AIR::BaseField>,
+    RandCoin: RandomCoin<BaseField = AIR::BaseField, Hasher = HashFn>,
+{
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a new verifier for proofs of the computation described by `air`.
+    ///
+    /// The trace info and proof options of the verifier are taken from `air`; public inputs of
+    /// `air` are not used, and thus, periodic columns of the computation must not depend on
+    /// public inputs. `preprocessed_root` must be provided if and only if the execution trace of
+    /// the computation contains preprocessed columns (see
+    /// [verify_with_preprocessed()](crate::verify_with_preprocessed)).
+    ///
+    /// # Errors
+    /// Returns an error if the execution trace of the computation cannot be extended into an LDE
+    /// domain over the base field of the computation using the proof options of `air`.
+    pub fn new(
+        air: &AIR,
+        preprocessed_root: Option<HashFn::Digest>,
+    ) -> Result<Self, VerifierError> {
+        let context = Context::new::<AIR::BaseField>(air.trace_info(), air.options().clone());
+        validate_context::<AIR::BaseField>(&context)?;
+        let context_elements = context.to_elements();
+
+        Ok(Self {
+            context,
+            context_elements,
+            air_data: AirData::new(air),
+            preprocessed_root,
+            _random_coin: PhantomData,
+        })
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the proof context for which this verifier was prepared.
+    pub fn context(&self) -> &Context {
+        &self.context
+    }
+
+    // VERIFICATION
+    // --------------------------------------------------------------------------------------------
+
+    /// Verifies that the specified computation was executed correctly against the specified
+    /// inputs.
+    ///
+    /// # Errors
+    /// Returns an error if the context of the `proof` is different from the context for which
+    /// this verifier was prepared, or for any of the reasons described in
+    /// [verify()](crate::verify).
+    pub fn verify(
+        &self,
+        proof: StarkProof,
+        pub_inputs: AIR::PublicInputs,
+    ) -> Result<(), VerifierError> {
+        if proof.context != self.context {
+            return Err(VerifierError::InconsistentProofContext);
+        }
+
+        let mut public_coin_seed = self.context_elements.clone();
+        public_coin_seed.append(&mut pub_inputs.to_elements());
+        let air = AIR::new(proof.get_trace_info(), pub_inputs, proof.options().clone());
+
+        verify_with_air::<AIR, HashFn, RandCoin>(
+            &air,
+            &self.air_data,
+            proof,
+            public_coin_seed,
+            self.preprocessed_root,
+            None,
+            &mut |_| {},
+        )
+    }
+}
+
+// AIR DATA
+// ================================================================================================
+
+/// Data derived from the AIR of a computation which depends only on the proof context.
+pub(crate) struct AirData<B: StarkField> {
+    pub trace_domain_generator: B,
+    pub lde_domain_generator: B,
+    pub domain_offset: B,
+    pub periodic_column_polys: Vec<Vec<B>>,
+    pub transition_divisor: ConstraintDivisor<B>,
+}
+
+impl<B: StarkField> AirData<B> {
+    /// Returns data derived from the specified `air`.
+    pub fn new<A: Air<BaseField = B>>(air: &A) -> Self {
+        Self {
+            trace_domain_generator: air.trace_domain_generator(),
+            lde_domain_generator: air.lde_domain_generator(),
+            domain_offset: air.domain_offset(),
+            periodic_column_polys: air.get_periodic_column_polys(),
+            transition_divisor: ConstraintDivisor::from_transition(
+                air.trace_length(),
+                air.context().num_transition_exemptions(),
+            ),
+        }
+    }
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::PreparedVerifier;
+    use crate::{
+        tests::{build_fib_proof, Blake3, FibAir, RandomCoin},
+        VerifierError,
+    };
+    use air::{Air, FieldExtension, ProofOptions};
+    use math::{fields::f128::BaseElement, FieldElement};
+
+    #[test]
+    fn verify_with_prepared_verifier() {
+        let options = ProofOptions::new(28, 8, 0, FieldExtension::None, 4, 7);
+        let (proof, result) = build_fib_proof(8, options.clone());
+
+        let air = FibAir::new(proof.get_trace_info(), result, options);
+        let verifier = PreparedVerifier::<FibAir, Blake3, RandomCoin>::new(&air, None).unwrap();
+        assert!(verifier.verify(proof.clone(), result).is_ok());
+        assert!(verifier.verify(proof, result + BaseElement::ONE).is_err());
+
+        // proofs generated with different proof options are rejected
+        let options = ProofOptions::new(28, 8, 0, FieldExtension::Quadratic, 4, 7);
+        let (proof, _) = build_fib_proof(8, options);
+        assert_eq!(
+            Err(VerifierError::InconsistentProofContext),
+            verifier.verify(proof, result)
+        );
+    }
+}
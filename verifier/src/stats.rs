@@ -0,0 +1,177 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+#[cfg(feature = "std")]
+use crate::{
+    cost_model::{estimate_air_cost, VerificationCost},
+    validate_context, verify_with_air, AirData, VerifierError,
+};
+#[cfg(feature = "std")]
+use air::{proof::StarkProof, Air};
+#[cfg(feature = "std")]
+use crypto::{ElementHasher, RandomCoin};
+#[cfg(feature = "std")]
+use math::ToElements;
+#[cfg(feature = "std")]
+use std::time::{Duration, Instant};
+
+// VERIFICATION PHASE
+// ================================================================================================
+
+/// A phase of the proof verification procedure.
+///
+/// Phases are listed in the order in which they are executed by the verifier.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VerificationPhase {
+    /// Validation of the proof context and instantiation of the AIR.
+    Setup,
+    /// Parsing of the proof into the components read by the verifier.
+    ProofParsing,
+    /// Processing of trace commitments and drawing of constraint composition coefficients.
+    TraceCommitments,
+    /// Evaluation of constraints at the out-of-domain point and comparison of the result with
+    /// out-of-domain evaluations of the constraint composition polynomial.
+    OodConsistencyCheck,
+    /// Processing of FRI layer commitments.
+    FriCommitments,
+    /// Verification of the proof-of-work, drawing of query positions, and verification of trace
+    /// and constraint queries against their commitments.
+    Queries,
+    /// Computation of DEEP composition polynomial evaluations at the queried positions.
+    DeepComposition,
+    /// Verification of the FRI proof.
+    FriVerification,
+}
+
+// VERIFICATION STATS
+// ================================================================================================
+
+/// Statistics collected while verifying a STARK proof.
+#[cfg(feature = "std")]
+#[derive(Debug, Clone)]
+pub struct VerificationStats {
+    /// Cost of verifying the proof as estimated by
+    /// [estimate_cost()](crate::cost_model::estimate_cost); this includes the number of performed
+    /// hash function invocations and an estimate of the number of field operations.
+    pub cost: VerificationCost,
+    /// Time spent in each phase of the verification procedure, in the order in which the phases
+    /// were executed.
+    pub phase_times: Vec<(VerificationPhase, Duration)>,
+}
+
+#[cfg(feature = "std")]
+impl VerificationStats {
+    /// Returns the time spent in the specified phase of the verification procedure.
+    pub fn phase_time(&self, phase: VerificationPhase) -> Duration {
+        self.phase_times
+            .iter()
+            .filter(|(p, _)| *p == phase)
+            .map(|(_, time)| *time)
+            .sum()
+    }
+
+    /// Returns the total time spent verifying the proof.
+    pub fn total_time(&self) -> Duration {
+        self.phase_times.iter().map(|(_, time)| *time).sum()
+    }
+}
+
+// VERIFIER
+// ================================================================================================
+
+/// Verifies that the specified computation was executed correctly against the specified inputs,
+/// and returns statistics collected during verification.
+///
+/// This is the same as [verify()](crate::verify), but additionally measures the time spent in
+/// each phase of the verification procedure, and estimates the cost of verification. Time spent
+/// on estimating the cost is not included in the phase times.
+///
+/// # Errors
+/// Returns an error for any of the reasons described in [verify()](crate::verify).
+#[cfg(feature = "std")]
+pub fn verify_with_stats<AIR, HashFn, RandCoin>(
+    proof: StarkProof,
+    pub_inputs: AIR::PublicInputs,
+) -> Result<VerificationStats, VerifierError>
+where
+    AIR: Air,
+    HashFn: ElementHasher<BaseField = AIR::BaseField>,
+    RandCoin: RandomCoin<BaseField = AIR::BaseField, Hasher = HashFn>,
+{
+    let now = Instant::now();
+    validate_context::<AIR::BaseField>(&proof.context)?;
+    let mut public_coin_seed = proof.context.to_elements();
+    public_coin_seed.append(&mut pub_inputs.to_elements());
+    let air = AIR::new(proof.get_trace_info(), pub_inputs, proof.options().clone());
+    let air_data = AirData::new(&air);
+    let mut phase_times = vec![(VerificationPhase::Setup, now.elapsed())];
+
+    // the cost is estimated before the proof is consumed by the verifier
+    let cost = estimate_air_cost::<AIR, HashFn>(&air, &proof)?;
+
+    let mut now = Instant::now();
+    verify_with_air::<AIR, HashFn, RandCoin>(
+        &air,
+        &air_data,
+        proof,
+        public_coin_seed,
+        None,
+        None,
+        &mut |phase| {
+            phase_times.push((phase, now.elapsed()));
+            now = Instant::now();
+        },
+    )?;
+
+    Ok(VerificationStats { cost, phase_times })
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::{verify_with_stats, VerificationPhase};
+    use crate::{
+        cost_model::estimate_cost,
+        tests::{build_fib_proof, Blake3, FibAir, RandomCoin},
+    };
+    use air::ProofOptions;
+    use math::{fields::f128::BaseElement, FieldElement};
+
+    #[test]
+    fn verify_fib_proof_with_stats() {
+        let (proof, result) = build_fib_proof(128, ProofOptions::for_testing());
+        let stats = verify_with_stats::<FibAir, Blake3, RandomCoin>(proof.clone(), result).unwrap();
+        let cost = estimate_cost::<FibAir, Blake3>(&proof, result).unwrap();
+        assert_eq!(cost, stats.cost);
+
+        // every phase of the verification procedure is timed exactly once, in order
+        let phases = stats
+            .phase_times
+            .iter()
+            .map(|(phase, _)| *phase)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vec![
+                VerificationPhase::Setup,
+                VerificationPhase::ProofParsing,
+                VerificationPhase::TraceCommitments,
+                VerificationPhase::OodConsistencyCheck,
+                VerificationPhase::FriCommitments,
+                VerificationPhase::Queries,
+                VerificationPhase::DeepComposition,
+                VerificationPhase::FriVerification,
+            ],
+            phases
+        );
+        assert!(stats.total_time() >= stats.phase_time(VerificationPhase::Queries));
+
+        // invalid proofs are rejected
+        let result =
+            verify_with_stats::<FibAir, Blake3, RandomCoin>(proof, result + BaseElement::ONE);
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,211 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use crate::{
+    verify, verify_queries, verify_single_query, verify_with_policy, PolicyViolation, ProofOptions,
+    StarkProof, VerifierError, VerifierPolicy,
+};
+use air::{Air, AirContext, Assertion, EvaluationFrame, TraceInfo, TransitionConstraintDegree};
+use crypto::{
+    hashers::{Blake3_256, Sha3_256},
+    DefaultRandomCoin,
+};
+use math::{fields::f128::BaseElement, FieldElement};
+use prover::{Prover, Trace, TraceTable};
+use utils::collections::Vec;
+
+pub type Blake3 = Blake3_256<BaseElement>;
+pub type RandomCoin = DefaultRandomCoin<Blake3>;
+
+// VERIFICATION TESTS
+// ================================================================================================
+
+#[test]
+fn verify_fib_proof() {
+    let (proof, result) = build_fib_proof(64, ProofOptions::for_testing());
+    assert!(verify::<FibAir, Blake3, RandomCoin>(proof.clone(), result).is_ok());
+    assert!(verify::<FibAir, Blake3, RandomCoin>(proof, result + BaseElement::ONE).is_err());
+}
+
+#[test]
+fn verify_with_policy_enforcement() {
+    let options = ProofOptions::for_testing();
+    let (proof, result) = build_fib_proof(64, options.clone());
+
+    // a policy matching the proof options is satisfied
+    let policy = VerifierPolicy::new()
+        .set_min_num_queries(options.num_queries())
+        .set_min_blowup_factor(options.blowup_factor())
+        .set_min_grinding_factor(options.grinding_factor())
+        .set_required_hash::<Blake3>();
+    verify_with_policy::<FibAir, Blake3, RandomCoin>(proof.clone(), result, &policy).unwrap();
+
+    // proofs with weaker options are rejected even if they are otherwise valid
+    let policy = VerifierPolicy::new().set_min_num_queries(options.num_queries() + 1);
+    assert_eq!(
+        Err(VerifierError::PolicyViolation(
+            PolicyViolation::NotEnoughQueries {
+                min: options.num_queries() + 1,
+                actual: options.num_queries()
+            }
+        )),
+        verify_with_policy::<FibAir, Blake3, RandomCoin>(proof.clone(), result, &policy)
+    );
+
+    // so are proofs verified with a hash function other than the required one
+    let policy = VerifierPolicy::new().set_required_hash::<Sha3_256<BaseElement>>();
+    assert!(matches!(
+        verify_with_policy::<FibAir, Blake3, RandomCoin>(proof, result, &policy),
+        Err(VerifierError::PolicyViolation(
+            PolicyViolation::HashFunctionNotAllowed { .. }
+        ))
+    ));
+}
+
+#[test]
+fn verify_query_subsets() {
+    let options = ProofOptions::for_testing();
+    let num_queries = options.num_queries();
+    let (proof, result) = build_fib_proof(64, options.clone());
+
+    // every query of a valid proof can be verified independently
+    for index in 0..num_queries {
+        verify_single_query::<FibAir, Blake3, RandomCoin>(proof.clone(), result, index).unwrap();
+    }
+
+    // queries cannot be verified against wrong public inputs
+    assert!(verify_single_query::<FibAir, Blake3, RandomCoin>(
+        proof.clone(),
+        result + BaseElement::ONE,
+        0
+    )
+    .is_err());
+
+    assert_eq!(
+        Err(VerifierError::QueryIndexOutOfRange(
+            num_queries,
+            num_queries
+        )),
+        verify_single_query::<FibAir, Blake3, RandomCoin>(proof, result, num_queries)
+    );
+
+    // subsets of queries are verified together, including for proofs with aggregated FRI layer
+    // proofs
+    let options = options.set_fri_layer_proof_aggregation(true);
+    let (proof, result) = build_fib_proof(64, options);
+    assert!(proof.fri_proof.num_layers() > 0);
+    for indexes in [&[0][..], &[3, 1, 3], &[0, 7, 12]] {
+        verify_queries::<FibAir, Blake3, RandomCoin>(proof.clone(), result, indexes, None).unwrap();
+    }
+    assert_eq!(
+        Err(VerifierError::NoQueryIndexes),
+        verify_queries::<FibAir, Blake3, RandomCoin>(proof.clone(), result, &[], None)
+    );
+    assert!(verify_queries::<FibAir, Blake3, RandomCoin>(
+        proof,
+        result + BaseElement::ONE,
+        &[0, 1],
+        None
+    )
+    .is_err());
+}
+
+// FIBONACCI AIR
+// ================================================================================================
+
+/// AIR for a Fibonacci sequence computed in 2 columns; the result is the last term of the
+/// sequence.
+pub struct FibAir {
+    context: AirContext<BaseElement>,
+    result: BaseElement,
+}
+
+impl Air for FibAir {
+    type BaseField = BaseElement;
+    type PublicInputs = BaseElement;
+
+    fn new(trace_info: TraceInfo, result: BaseElement, options: ProofOptions) -> Self {
+        let degrees = vec![
+            TransitionConstraintDegree::new(1),
+            TransitionConstraintDegree::new(1),
+        ];
+        FibAir {
+            context: AirContext::new(trace_info, degrees, 3, options),
+            result,
+        }
+    }
+
+    fn context(&self) -> &AirContext<Self::BaseField> {
+        &self.context
+    }
+
+    fn evaluate_transition<E: FieldElement + From<Self::BaseField>>(
+        &self,
+        frame: &EvaluationFrame<E>,
+        _periodic_values: &[E],
+        result: &mut [E],
+    ) {
+        let current = frame.current();
+        let next = frame.next();
+        result[0] = next[0] - (current[0] + current[1]);
+        result[1] = next[1] - (current[1] + next[0]);
+    }
+
+    fn get_assertions(&self) -> Vec<Assertion<Self::BaseField>> {
+        let last_step = self.trace_length() - 1;
+        vec![
+            Assertion::single(0, 0, BaseElement::ONE),
+            Assertion::single(1, 0, BaseElement::ONE),
+            Assertion::single(1, last_step, self.result),
+        ]
+    }
+}
+
+// FIBONACCI PROVER
+// ================================================================================================
+
+pub struct FibProver {
+    options: ProofOptions,
+}
+
+impl FibProver {
+    pub fn new(options: ProofOptions) -> Self {
+        Self { options }
+    }
+
+    /// Returns an execution trace with `trace_length` rows, each containing 2 terms of the
+    /// Fibonacci sequence.
+    pub fn build_trace(&self, trace_length: usize) -> TraceTable<BaseElement> {
+        TraceTable::from_transition(vec![BaseElement::ONE; 2], trace_length, |row| {
+            let next0 = row[0] + row[1];
+            vec![next0, row[1] + next0]
+        })
+    }
+}
+
+impl Prover for FibProver {
+    type BaseField = BaseElement;
+    type Air = FibAir;
+    type Trace = TraceTable<BaseElement>;
+    type HashFn = Blake3;
+    type RandomCoin = RandomCoin;
+
+    fn get_pub_inputs(&self, trace: &Self::Trace) -> BaseElement {
+        trace.get(1, trace.length() - 1)
+    }
+
+    fn options(&self) -> &ProofOptions {
+        &self.options
+    }
+}
+
+/// Returns a proof of a Fibonacci computation with an execution trace of `trace_length` rows
+/// together with the result of the computation.
+pub fn build_fib_proof(trace_length: usize, options: ProofOptions) -> (StarkProof, BaseElement) {
+    let prover = FibProver::new(options);
+    let trace = prover.build_trace(trace_length);
+    let result = prover.get_pub_inputs(&trace);
+    (prover.prove(trace).unwrap(), result)
+}
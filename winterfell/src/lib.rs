@@ -529,11 +529,29 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
 pub use prover::{
-    crypto, iterators, math, Air, AirContext, Assertion, AuxTraceRandElements, BoundaryConstraint,
-    BoundaryConstraintGroup, ByteReader, ByteWriter, ColMatrix, ConstraintCompositionCoefficients,
-    ConstraintDivisor, DeepCompositionCoefficients, Deserializable, DeserializationError,
-    EvaluationFrame, FieldExtension, ProofOptions, Prover, ProverError, Serializable, SliceReader,
-    StarkProof, Trace, TraceInfo, TraceLayout, TraceTable, TraceTableFragment,
-    TransitionConstraintDegree, TransitionConstraintGroup,
+    crypto, iterators, math, params, Air, AirContext, Assertion, AssertionError, Assertions,
+    AuxTraceRandElements, BoundaryConstraint, BoundaryConstraintGroup, ByteReader, ByteWriter,
+    CancellationToken, ColMatrix, ColumnIter, ConstraintCompositionCoefficients, ConstraintDivisor,
+    DebugProver, DebugTrace, DebugTraceDiff, DeepCompositionCoefficients, Deserializable,
+    DeserializationError, ErrorCategory, ErrorCode, EvaluationFrame, Expr, FieldExtension,
+    GrindingFunction, MultiInstanceAir, MultiInstanceInputs, PreprocessedCommitment,
+    ProgressHandler, ProofArtifact, ProofOptions, Prover, ProverError, ProvingPhase, QuerySampling,
+    Register, RowView, Serializable, SliceReader, StarkProof, ToErrorCode, Trace, TraceInfo,
+    TraceLayout, TraceStep, TraceTable, TraceTableFragment, TransitionConstraintDegree,
+    TransitionConstraintGroup, TransitionExpressions, PROOF_VERSION,
 };
-pub use verifier::{verify, VerifierError};
+pub use verifier::{
+    cost_model, verify, verify_queries, verify_single_query, verify_with_policy,
+    verify_with_preprocessed, FriVerifierError, PolicyViolation, PreparedVerifier,
+    VerificationPhase, VerifierError, VerifierPolicy,
+};
+
+#[cfg(feature = "concurrent")]
+pub use prover::ComputeContext;
+#[cfg(feature = "std")]
+pub use prover::DomainCache;
+#[cfg(feature = "std")]
+pub use verifier::{verify_with_stats, VerificationStats};
+
+#[cfg(feature = "interop")]
+pub use prover::interop;